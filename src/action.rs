@@ -1,11 +1,40 @@
+use crate::data::flow::FlowKey;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum Action {
     Quit,
     NavigateToHome,
     NavigateToDevice,
     NavigateToSniffer,
+    NavigateToVoip,
+    NavigateToDualStack,
+    NavigateToOtherL2,
+    NavigateToNeighbors,
+    NavigateToDiscovery,
+    NavigateToMulticast,
+    NavigateToConversations,
+    NavigateToEndpoints,
+    NavigateToFlows,
+    NavigateToPerformance,
+    NavigateToTimeline,
+    NavigateToLatency,
+    NavigateToDns,
+    NavigateToHttp,
+    NavigateToTopTalkers,
+    NavigateToObjects,
+    NavigateToAlerts,
+    NavigateToReplay,
+    NavigateBack,
+    NavigateForward,
     DeviceSelected(String),
+    SetMonitorMode(String, bool), // Device and whether to capture on it in monitor mode
     ApplyFilter(String),
+    ApplyDisplayFilter(String),
     Handled,
     PacketSelected(usize),
+    CommentPacket(String), // Attaches a comment to the packet shown in the details
+    FollowStream(FlowKey),
+    ShowLadder(FlowKey),
+    ExportFlow(FlowKey),
+    ReplayListed,
 }
@@ -1,12 +1,74 @@
+use std::{
+    io::{self, Write},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use ratatui::{Frame, layout::Rect};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders},
+};
 use tokio::sync::mpsc;
 
 use crate::{
     action::Action,
     component::{Component, ComponentRender},
-    pages::{detail::PacketDetailsPage, device::DevicePage, home::HomePage, sniffer::SnifferPage},
+    data::{
+        alert::{Alert, Severity},
+        burst::Timeline,
+        capture_file::CaptureFile,
+        conversation::ConversationStats,
+        discovery::DiscoveryStats,
+        dns_stats::DnsStats,
+        dual_stack::DualStackStats,
+        endpoint::EndpointStats,
+        ethertype::OtherL2Stats,
+        http_objects,
+        http_transactions::HttpStats,
+        latency::LatencyStats,
+        multicast::MulticastStats,
+        neighbor::NeighborStats,
+        profile, replay,
+        tcp_ladder::Ladder,
+        top_talkers::{self, TopTalkers},
+        udp_stream::UdpStream,
+        vlan::{self, VlanGroup},
+        voip::VoipStats,
+    },
+    i18n::tr,
+    pages::{
+        alerts::AlertsPage,
+        conversations::ConversationsPage,
+        detail::PacketDetailsPage,
+        device::DevicePage,
+        discovery::DiscoveryPage,
+        dns::DnsPage,
+        dual_stack::DualStackPage,
+        endpoints::EndpointsPage,
+        file_summary::FileSummaryPage,
+        flows::FlowsPage,
+        follow_stream::FollowStreamPage,
+        home::HomePage,
+        http::HttpPage,
+        ladder::LadderPage,
+        latency::LatencyPage,
+        multicast::MulticastPage,
+        neighbors::NeighborsPage,
+        objects::ObjectsPage,
+        other_l2::OtherL2Page,
+        performance::PerformancePage,
+        replay::ReplayPage,
+        sniffer::SnifferPage,
+        timeline::TimelinePage,
+        top_bar::{TopBar, TopBarProps},
+        top_talkers::TopTalkersPage,
+        vlan_bar::VlanBar,
+        voip::VoipPage,
+    },
+    theme::{self, theme},
     tui::Event,
 };
 
@@ -16,16 +78,160 @@ pub enum Page {
     Device,
     Sniffer,
     PacketDetails,
+    Voip,
+    FileSummary,
+    DualStack,
+    OtherL2,
+    Conversations,
+    Endpoints,
+    Flows,
+    Performance,
+    FollowStream,
+    Ladder,
+    Timeline,
+    Neighbors,
+    Discovery,
+    Multicast,
+    Replay,
+    Latency,
+    Dns,
+    Http,
+    TopTalkers,
+    Objects,
+    Alerts,
+}
+
+impl Page {
+    fn title(&self) -> &'static str {
+        match self {
+            Page::Home => tr!("page.home"),
+            Page::Device => tr!("home.device"),
+            Page::Sniffer => tr!("home.sniffer"),
+            Page::PacketDetails => tr!("page.details"),
+            Page::Voip => tr!("home.voip"),
+            Page::FileSummary => tr!("page.file_summary"),
+            Page::DualStack => tr!("home.dual_stack"),
+            Page::OtherL2 => tr!("home.other_l2"),
+            Page::Conversations => tr!("home.conversations"),
+            Page::Endpoints => tr!("home.endpoints"),
+            Page::Flows => tr!("home.flows"),
+            Page::Performance => tr!("home.performance"),
+            Page::FollowStream => tr!("page.follow_stream"),
+            Page::Ladder => tr!("page.ladder"),
+            Page::Timeline => tr!("home.timeline"),
+            Page::Neighbors => tr!("home.neighbors"),
+            Page::Discovery => tr!("home.discovery"),
+            Page::Multicast => tr!("home.multicast"),
+            Page::Replay => tr!("home.replay"),
+            Page::Latency => tr!("home.latency"),
+            Page::Dns => tr!("home.dns"),
+            Page::Http => tr!("home.http"),
+            Page::TopTalkers => tr!("home.top_talkers"),
+            Page::Objects => tr!("home.objects"),
+            Page::Alerts => tr!("home.alerts"),
+        }
+    }
+
+    /// The page this one is reached from; pages opened on packets or flows
+    /// sit under the sniffer, everything else under Home.
+    fn parent(&self) -> Option<Page> {
+        match self {
+            Page::Home => None,
+            Page::PacketDetails | Page::FollowStream | Page::Ladder => Some(Page::Sniffer),
+            _ => Some(Page::Home),
+        }
+    }
+
+    /// Titles from Home down to this page.
+    fn trail(&self) -> Vec<&'static str> {
+        let mut trail = vec![self.title()];
+        let mut page = self.clone();
+        while let Some(parent) = page.parent() {
+            trail.insert(0, parent.title());
+            page = parent;
+        }
+        trail
+    }
+
+    /// Pages summarizing the whole capture, which can be broken down by VLAN.
+    fn is_statistics(&self) -> bool {
+        matches!(
+            self,
+            Page::Voip
+                | Page::DualStack
+                | Page::OtherL2
+                | Page::Conversations
+                | Page::Endpoints
+                | Page::Timeline
+                | Page::Neighbors
+                | Page::Discovery
+                | Page::Multicast
+                | Page::Latency
+                | Page::Dns
+                | Page::Http
+                | Page::TopTalkers
+        )
+    }
+}
+
+/// The sniffer revision, page, VLAN group and top talkers window a
+/// statistics page was computed for.
+type StatsKey = ((usize, usize, usize), Page, VlanGroup, Duration);
+
+/// Maximum number of places remembered for going back.
+const HISTORY_LIMIT: usize = 100;
+/// How long the border stays in each color while flashing for an alert.
+const FLASH_PERIOD_MS: u128 = 500;
+
+/// A place the user has visited: a page plus whatever it was showing.
+/// Page-local state such as list positions lives in the pages themselves,
+/// which persist for the whole session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Location {
+    page: Page,
+    packet: Option<usize>,
 }
 
 pub struct App {
     pub should_quit: bool,
     pub current_page: Page,
+    current_packet: Option<usize>,
+    back_history: Vec<Location>,
+    forward_history: Vec<Location>,
 
     pub home_page: HomePage,
     pub device_page: DevicePage,
     pub sniffer_page: SnifferPage,
     pub packet_details_page: PacketDetailsPage,
+    pub voip_page: VoipPage,
+    pub file_summary_page: FileSummaryPage,
+    pub dual_stack_page: DualStackPage,
+    pub other_l2_page: OtherL2Page,
+    pub conversations_page: ConversationsPage,
+    pub endpoints_page: EndpointsPage,
+    pub flows_page: FlowsPage,
+    pub performance_page: PerformancePage,
+    pub follow_stream_page: FollowStreamPage,
+    pub ladder_page: LadderPage,
+    pub timeline_page: TimelinePage,
+    pub neighbors_page: NeighborsPage,
+    pub discovery_page: DiscoveryPage,
+    pub multicast_page: MulticastPage,
+    pub replay_page: ReplayPage,
+    pub latency_page: LatencyPage,
+    pub dns_page: DnsPage,
+    pub http_page: HttpPage,
+    pub top_talkers_page: TopTalkersPage,
+    pub objects_page: ObjectsPage,
+    pub alerts_page: AlertsPage,
+    top_bar: TopBar,
+    vlan_bar: VlanBar,
+    alerts_seen: usize,   // Sniffer alerts already signaled
+    alert: Option<Alert>, // Latest alert raised while away from the sniffer
+    bell: bool,
+    objects_revision: Option<(usize, usize, usize)>, // Sniffer revision the objects were taken at
+    vlan_revision: Option<(usize, usize, usize)>, // Sniffer revision the VLAN groups were taken at
+    stats_key: Option<StatsKey>, // What the statistics page shown was computed from
 
     action_tx: mpsc::UnboundedSender<Action>,
 }
@@ -35,10 +241,42 @@ impl App {
         Self {
             should_quit: false,
             current_page: Page::Home,
+            current_packet: None,
+            back_history: Vec::new(),
+            forward_history: Vec::new(),
             home_page: HomePage::new(),
             device_page: DevicePage::new(),
             sniffer_page: SnifferPage::new(),
             packet_details_page: PacketDetailsPage::new(),
+            voip_page: VoipPage::new(),
+            file_summary_page: FileSummaryPage::new(),
+            dual_stack_page: DualStackPage::new(),
+            other_l2_page: OtherL2Page::new(),
+            conversations_page: ConversationsPage::new(),
+            endpoints_page: EndpointsPage::new(),
+            flows_page: FlowsPage::new(),
+            performance_page: PerformancePage::new(),
+            follow_stream_page: FollowStreamPage::new(),
+            ladder_page: LadderPage::new(),
+            timeline_page: TimelinePage::new(),
+            neighbors_page: NeighborsPage::new(),
+            discovery_page: DiscoveryPage::new(),
+            multicast_page: MulticastPage::new(),
+            replay_page: ReplayPage::new(),
+            latency_page: LatencyPage::new(),
+            dns_page: DnsPage::new(),
+            http_page: HttpPage::new(),
+            top_talkers_page: TopTalkersPage::new(),
+            objects_page: ObjectsPage::new(),
+            alerts_page: AlertsPage::new(),
+            top_bar: TopBar::new(),
+            vlan_bar: VlanBar::new(),
+            alerts_seen: 0,
+            alert: None,
+            bell: true,
+            objects_revision: None,
+            vlan_revision: None,
+            stats_key: None,
             action_tx,
         }
     }
@@ -54,10 +292,147 @@ impl App {
             .register_action_handler(action_tx.clone())?;
         self.packet_details_page
             .register_action_handler(action_tx.clone())?; // Register packet details page
+        self.voip_page.register_action_handler(action_tx.clone())?;
+        self.file_summary_page
+            .register_action_handler(action_tx.clone())?;
+        self.dual_stack_page
+            .register_action_handler(action_tx.clone())?;
+        self.other_l2_page
+            .register_action_handler(action_tx.clone())?;
+        self.conversations_page
+            .register_action_handler(action_tx.clone())?;
+        self.endpoints_page
+            .register_action_handler(action_tx.clone())?;
+        self.flows_page.register_action_handler(action_tx.clone())?;
+        self.performance_page
+            .register_action_handler(action_tx.clone())?;
+        self.follow_stream_page
+            .register_action_handler(action_tx.clone())?;
+        self.ladder_page
+            .register_action_handler(action_tx.clone())?;
+        self.timeline_page
+            .register_action_handler(action_tx.clone())?;
+        self.neighbors_page
+            .register_action_handler(action_tx.clone())?;
+        self.discovery_page
+            .register_action_handler(action_tx.clone())?;
+        self.multicast_page
+            .register_action_handler(action_tx.clone())?;
+        self.replay_page
+            .register_action_handler(action_tx.clone())?;
+        self.latency_page
+            .register_action_handler(action_tx.clone())?;
+        self.dns_page.register_action_handler(action_tx.clone())?;
+        self.http_page.register_action_handler(action_tx.clone())?;
+        self.top_talkers_page
+            .register_action_handler(action_tx.clone())?;
+        self.objects_page
+            .register_action_handler(action_tx.clone())?;
+        self.alerts_page
+            .register_action_handler(action_tx.clone())?;
+
+        Ok(())
+    }
+
+    /// Whether to ring the terminal bell on alerts.
+    pub fn set_bell(&mut self, bell: bool) {
+        self.bell = bell;
+    }
+
+    /// Starts in analysis-only mode when live capture is not possible, so
+    /// capture files can still be opened and analysed.
+    pub fn set_capture_unavailable(&mut self, reason: String) {
+        self.home_page.set_capture_unavailable(reason.clone());
+        self.sniffer_page.set_capture_unavailable(reason);
+    }
+
+    /// Loads an opened capture file and shows its summary first.
+    pub fn open_capture(&mut self, capture: CaptureFile) {
+        let source = capture.summary.path.display().to_string();
+        self.sniffer_page
+            .load_capture(capture.packets, capture.comments, &source);
+        self.file_summary_page.set_summary(capture.summary);
+        self.navigate(Page::FileSummary);
+    }
+
+    /// Picks the device to capture on as the device page would, for
+    /// `--interface`.
+    pub fn select_device(&mut self, device_name: String) -> Result<()> {
+        self.sniffer_page
+            .update(Action::DeviceSelected(device_name))?;
+        self.navigate(Page::Sniffer);
+        Ok(())
+    }
 
+    /// Starts capturing synthesized traffic on the sniffer page.
+    pub fn start_demo(&mut self) -> Result<()> {
+        self.sniffer_page.start_demo()?;
+        self.navigate(Page::Sniffer);
         Ok(())
     }
 
+    fn location(&self) -> Location {
+        Location {
+            page: self.current_page.clone(),
+            packet: self
+                .current_packet
+                .filter(|_| self.current_page == Page::PacketDetails),
+        }
+    }
+
+    fn navigate(&mut self, page: Page) {
+        self.visit(Location { page, packet: None });
+    }
+
+    /// Goes to `location`, remembering the current place for going back.
+    fn visit(&mut self, location: Location) {
+        let from = self.location();
+        if location == from {
+            return;
+        }
+        self.back_history.push(from);
+        if self.back_history.len() > HISTORY_LIMIT {
+            self.back_history.remove(0);
+        }
+        self.forward_history.clear();
+        self.restore(location);
+    }
+
+    fn restore(&mut self, location: Location) {
+        if let Some(index) = location.packet
+            && let Some(packet) = self.sniffer_page.get_packet(index)
+        {
+            let matches = self.sniffer_page.search_matches(&packet);
+            let offloaded = self.sniffer_page.is_offloaded(&packet);
+            self.packet_details_page.set_packet(packet, offloaded);
+            self.packet_details_page.set_highlights(matches);
+            let (reported_on, errors) = self.sniffer_page.icmp_related(index);
+            self.packet_details_page.set_related(reported_on, errors);
+            self.packet_details_page
+                .set_comment(self.sniffer_page.comment(index));
+            self.current_packet = Some(index);
+        }
+        if location.page == Page::Sniffer {
+            self.alert = None;
+        }
+        self.current_page = location.page;
+        self.refresh_current_page();
+    }
+
+    fn go_back(&mut self) {
+        if let Some(location) = self.back_history.pop() {
+            self.forward_history.push(self.location());
+            self.restore(location);
+        }
+    }
+
+    fn go_forward(&mut self) {
+        if let Some(location) = self.forward_history.pop() {
+            self.back_history.push(self.location());
+            self.restore(location);
+        }
+    }
+
     pub fn quit(&mut self) {
         self.should_quit = true;
     }
@@ -65,7 +440,20 @@ impl App {
     pub fn handle_events(&mut self, event: Event) -> Result<()> {
         let action = match event {
             Event::Key(key_event) => {
-                if let Some(action) = self.handle_global_key_events(key_event)? {
+                // Keys typed into prompts are not shortcuts.
+                let editing = match self.current_page {
+                    Page::Sniffer => self.sniffer_page.is_editing(),
+                    Page::Device => self.device_page.is_editing(),
+                    Page::PacketDetails => self.packet_details_page.is_editing(),
+                    Page::Objects => self.objects_page.is_editing(),
+                    _ => false,
+                };
+                let global = if editing {
+                    None
+                } else {
+                    self.handle_global_key_events(key_event)?
+                };
+                if let Some(action) = global {
                     Some(action)
                 } else {
                     match self.current_page {
@@ -73,14 +461,64 @@ impl App {
                         Page::Device => self.device_page.handle_events(event)?,
                         Page::Sniffer => self.sniffer_page.handle_events(event)?,
                         Page::PacketDetails => self.packet_details_page.handle_events(event)?, // Handle packet details events
+                        Page::Voip => self.voip_page.handle_events(event)?,
+                        Page::FileSummary => self.file_summary_page.handle_events(event)?,
+                        Page::DualStack => self.dual_stack_page.handle_events(event)?,
+                        Page::OtherL2 => self.other_l2_page.handle_events(event)?,
+                        Page::Conversations => self.conversations_page.handle_events(event)?,
+                        Page::Endpoints => self.endpoints_page.handle_events(event)?,
+                        Page::Flows => self.flows_page.handle_events(event)?,
+                        Page::Performance => self.performance_page.handle_events(event)?,
+                        Page::FollowStream => self.follow_stream_page.handle_events(event)?,
+                        Page::Ladder => self.ladder_page.handle_events(event)?,
+                        Page::Timeline => self.timeline_page.handle_events(event)?,
+                        Page::Neighbors => self.neighbors_page.handle_events(event)?,
+                        Page::Discovery => self.discovery_page.handle_events(event)?,
+                        Page::Multicast => self.multicast_page.handle_events(event)?,
+                        Page::Replay => self.replay_page.handle_events(event)?,
+                        Page::Latency => self.latency_page.handle_events(event)?,
+                        Page::Dns => self.dns_page.handle_events(event)?,
+                        Page::Http => self.http_page.handle_events(event)?,
+                        Page::TopTalkers => self.top_talkers_page.handle_events(event)?,
+                        Page::Objects => self.objects_page.handle_events(event)?,
+                        Page::Alerts => self.alerts_page.handle_events(event)?,
                     }
                 }
             }
-            Event::Mouse(_) | Event::Tick => match self.current_page {
+            Event::Tick => {
+                // The sniffer keeps ingesting packets while other pages are
+                // shown, so their statistics stay live.
+                let action = self.sniffer_page.handle_events(event)?;
+                self.check_alerts();
+                self.refresh_current_page();
+                action
+            }
+            Event::Mouse(_) => match self.current_page {
                 Page::Home => self.home_page.handle_events(event)?,
                 Page::Device => self.device_page.handle_events(event)?,
                 Page::Sniffer => self.sniffer_page.handle_events(event)?,
                 Page::PacketDetails => self.packet_details_page.handle_events(event)?, // Handle packet details events
+                Page::Voip => self.voip_page.handle_events(event)?,
+                Page::FileSummary => self.file_summary_page.handle_events(event)?,
+                Page::DualStack => self.dual_stack_page.handle_events(event)?,
+                Page::OtherL2 => self.other_l2_page.handle_events(event)?,
+                Page::Conversations => self.conversations_page.handle_events(event)?,
+                Page::Endpoints => self.endpoints_page.handle_events(event)?,
+                Page::Flows => self.flows_page.handle_events(event)?,
+                Page::Performance => self.performance_page.handle_events(event)?,
+                Page::FollowStream => self.follow_stream_page.handle_events(event)?,
+                Page::Ladder => self.ladder_page.handle_events(event)?,
+                Page::Timeline => self.timeline_page.handle_events(event)?,
+                Page::Neighbors => self.neighbors_page.handle_events(event)?,
+                Page::Discovery => self.discovery_page.handle_events(event)?,
+                Page::Multicast => self.multicast_page.handle_events(event)?,
+                Page::Replay => self.replay_page.handle_events(event)?,
+                Page::Latency => self.latency_page.handle_events(event)?,
+                Page::Dns => self.dns_page.handle_events(event)?,
+                Page::Http => self.http_page.handle_events(event)?,
+                Page::TopTalkers => self.top_talkers_page.handle_events(event)?,
+                Page::Objects => self.objects_page.handle_events(event)?,
+                Page::Alerts => self.alerts_page.handle_events(event)?,
             },
         };
 
@@ -91,6 +529,139 @@ impl App {
         Ok(())
     }
 
+    /// Signals alerts the sniffer raised since the last tick. The sniffer
+    /// shows them in its status line, so other pages flash their border and
+    /// ring the bell for warnings and worse instead.
+    fn check_alerts(&mut self) {
+        let alerts = self.sniffer_page.alerts();
+        // The packet list was cleared or replaced.
+        if alerts.len() < self.alerts_seen {
+            self.alerts_seen = 0;
+        }
+        let new = &alerts[self.alerts_seen..];
+        let Some(latest) = new.last().cloned() else {
+            return;
+        };
+        let ring = new.iter().any(|alert| alert.severity >= Severity::Warning);
+        self.alerts_seen = alerts.len();
+        if self.current_page == Page::Sniffer {
+            return;
+        }
+        if self.bell && ring {
+            let mut stdout = io::stdout();
+            let _ = stdout.write_all(b"\x07").and_then(|()| stdout.flush());
+        }
+        self.alert = Some(latest);
+    }
+
+    fn refresh_current_page(&mut self) {
+        // Analysis needs the application layers lazy parsing leaves out.
+        if self.current_page.is_statistics()
+            || matches!(
+                self.current_page,
+                Page::FollowStream | Page::Ladder | Page::Objects
+            )
+        {
+            self.sniffer_page.decode_resident();
+        }
+        let mut group = VlanGroup::All;
+        if self.current_page.is_statistics() {
+            let revision = Some(self.sniffer_page.revision());
+            if self.vlan_revision != revision {
+                self.vlan_revision = revision;
+                self.vlan_bar
+                    .set_groups(vlan::groups(self.sniffer_page.packets()));
+            }
+            group = self.vlan_bar.selected;
+            // Redone only when what they are computed from changes, not on
+            // every tick.
+            let key = Some((
+                self.sniffer_page.revision(),
+                self.current_page.clone(),
+                group,
+                self.top_talkers_page.window(),
+            ));
+            if self.stats_key == key {
+                return;
+            }
+            self.stats_key = key;
+        }
+        let packets = vlan::select(self.sniffer_page.packets(), group);
+        match self.current_page {
+            Page::Voip => self.voip_page.set_stats(VoipStats::from_packets(&packets)),
+            Page::DualStack => self
+                .dual_stack_page
+                .set_stats(DualStackStats::from_packets(&packets)),
+            Page::OtherL2 => self
+                .other_l2_page
+                .set_stats(OtherL2Stats::from_packets(&packets)),
+            Page::Conversations => self
+                .conversations_page
+                .set_stats(ConversationStats::from_packets(&packets)),
+            Page::Endpoints => {
+                let mut stats = EndpointStats::from_packets(&packets);
+                if let Some(knowledge) = self.sniffer_page.knowledge() {
+                    stats.name_hosts(knowledge);
+                }
+                self.endpoints_page.set_stats(stats);
+            }
+            Page::Flows => self.flows_page.set_flows(self.sniffer_page.flows()),
+            Page::Performance => self.performance_page.set_timings(profile::snapshot()),
+            Page::Device => self.device_page.poll_devices(),
+            Page::FollowStream => {
+                if let Some(key) = self.follow_stream_page.key().cloned() {
+                    self.follow_stream_page
+                        .set_stream(UdpStream::from_packets(key, self.sniffer_page.packets()));
+                }
+            }
+            Page::Ladder => {
+                if let Some(key) = self.ladder_page.key().cloned() {
+                    self.ladder_page
+                        .set_ladder(Ladder::from_packets(key, self.sniffer_page.packets()));
+                }
+            }
+            Page::Timeline => self
+                .timeline_page
+                .set_timeline(Timeline::from_packets(&packets)),
+            Page::Neighbors => self
+                .neighbors_page
+                .set_stats(NeighborStats::from_packets(&packets)),
+            Page::Discovery => self
+                .discovery_page
+                .set_stats(DiscoveryStats::from_packets(&packets)),
+            Page::Multicast => self
+                .multicast_page
+                .set_stats(MulticastStats::from_packets(&packets)),
+            Page::Latency => self
+                .latency_page
+                .set_stats(LatencyStats::from_packets(&packets)),
+            Page::Dns => self.dns_page.set_stats(DnsStats::from_packets(&packets)),
+            Page::Http => self.http_page.set_stats(HttpStats::from_packets(&packets)),
+            Page::TopTalkers => {
+                let window = self.top_talkers_page.window();
+                self.top_talkers_page.set_talkers(TopTalkers::from_packets(
+                    &packets,
+                    window,
+                    top_talkers::LIMIT,
+                ));
+            }
+            // Bodies are reassembled and decompressed, so only redone when
+            // the packets change.
+            Page::Objects => {
+                let revision = Some(self.sniffer_page.revision());
+                if self.objects_revision != revision {
+                    self.objects_revision = revision;
+                    self.objects_page
+                        .set_objects(http_objects::from_packets(self.sniffer_page.packets()));
+                }
+            }
+            Page::Alerts => self
+                .alerts_page
+                .set_alerts(self.sniffer_page.alerts(), self.sniffer_page.dns_findings()),
+            _ => {}
+        }
+    }
+
     fn handle_global_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
         match key.code {
             KeyCode::Esc => {
@@ -101,10 +672,34 @@ impl App {
                     return Ok(None);
                 }
             }
+            KeyCode::Left if key.modifiers.contains(KeyModifiers::ALT) => {
+                return Ok(Some(Action::NavigateBack));
+            }
+            KeyCode::Right if key.modifiers.contains(KeyModifiers::ALT) => {
+                return Ok(Some(Action::NavigateForward));
+            }
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.quit();
                 return Ok(None);
             }
+            KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                theme::next_theme();
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Some(alert) = self.alert.take() {
+                    return Ok(Some(Action::PacketSelected(alert.index)));
+                }
+            }
+            KeyCode::Char('g')
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && self.current_page.is_statistics()
+                    && self.vlan_bar.is_shown() =>
+            {
+                self.vlan_bar.select_next();
+                self.refresh_current_page();
+                return Ok(Some(Action::Handled));
+            }
             _ => {}
         }
         Ok(None)
@@ -112,25 +707,92 @@ impl App {
 
     pub fn handle_action(&mut self, action: Action) -> Result<()> {
         match action {
-            Action::NavigateToHome => {
-                self.current_page = Page::Home;
+            Action::NavigateToHome => self.navigate(Page::Home),
+            Action::NavigateToDevice => self.navigate(Page::Device),
+            Action::NavigateToSniffer => self.navigate(Page::Sniffer),
+            Action::NavigateToVoip => self.navigate(Page::Voip),
+            Action::NavigateToDualStack => self.navigate(Page::DualStack),
+            Action::NavigateToOtherL2 => self.navigate(Page::OtherL2),
+            Action::NavigateToConversations => self.navigate(Page::Conversations),
+            Action::NavigateToEndpoints => self.navigate(Page::Endpoints),
+            Action::NavigateToFlows => self.navigate(Page::Flows),
+            Action::NavigateToPerformance => self.navigate(Page::Performance),
+            Action::NavigateToTimeline => self.navigate(Page::Timeline),
+            Action::NavigateToNeighbors => self.navigate(Page::Neighbors),
+            Action::NavigateToDiscovery => self.navigate(Page::Discovery),
+            Action::NavigateToMulticast => self.navigate(Page::Multicast),
+            Action::NavigateToLatency => self.navigate(Page::Latency),
+            Action::NavigateToDns => self.navigate(Page::Dns),
+            Action::NavigateToHttp => self.navigate(Page::Http),
+            Action::NavigateToTopTalkers => self.navigate(Page::TopTalkers),
+            Action::NavigateToObjects => self.navigate(Page::Objects),
+            Action::NavigateToAlerts => self.navigate(Page::Alerts),
+            Action::NavigateToReplay => {
+                let frames = self
+                    .sniffer_page
+                    .packets()
+                    .iter()
+                    .map(replay::Frame::from)
+                    .collect();
+                self.replay_page
+                    .set_frames(frames, tr!("replay.source.captured").to_string());
+                self.navigate(Page::Replay);
             }
-            Action::NavigateToDevice => {
-                self.current_page = Page::Device;
+            Action::ReplayListed => {
+                let frames = self
+                    .sniffer_page
+                    .listed_packets()
+                    .iter()
+                    .map(replay::Frame::from)
+                    .collect();
+                self.replay_page
+                    .set_frames(frames, tr!("replay.source.listed").to_string());
+                self.navigate(Page::Replay);
             }
-            Action::NavigateToSniffer => {
-                self.current_page = Page::Sniffer;
+            Action::ApplyDisplayFilter(filter) => {
+                self.sniffer_page
+                    .update(Action::ApplyDisplayFilter(filter))?;
+                self.navigate(Page::Sniffer);
             }
+            Action::NavigateBack => self.go_back(),
+            Action::NavigateForward => self.go_forward(),
             Action::DeviceSelected(device_name) => {
                 self.sniffer_page
                     .update(Action::DeviceSelected(device_name))?;
-                self.current_page = Page::Sniffer;
+                self.navigate(Page::Sniffer);
+            }
+            Action::SetMonitorMode(device_name, on) => {
+                self.sniffer_page
+                    .update(Action::SetMonitorMode(device_name, on))?;
+            }
+            Action::ExportFlow(key) => {
+                self.sniffer_page.update(Action::ExportFlow(key))?;
+                self.navigate(Page::Sniffer);
+            }
+            Action::FollowStream(key) => {
+                self.follow_stream_page
+                    .set_stream(UdpStream::from_packets(key, self.sniffer_page.packets()));
+                self.navigate(Page::FollowStream);
+            }
+            Action::ShowLadder(key) => {
+                self.ladder_page
+                    .set_ladder(Ladder::from_packets(key, self.sniffer_page.packets()));
+                self.navigate(Page::Ladder);
             }
             Action::PacketSelected(index) => {
                 self.sniffer_page.update(Action::PacketSelected(index))?;
-                if let Some(packet) = self.sniffer_page.get_packet(index) {
-                    self.packet_details_page.set_packet(packet);
-                    self.current_page = Page::PacketDetails;
+                if self.sniffer_page.get_packet(index).is_some() {
+                    self.visit(Location {
+                        page: Page::PacketDetails,
+                        packet: Some(index),
+                    });
+                }
+            }
+            Action::CommentPacket(comment) => {
+                if let Some(index) = self.current_packet {
+                    self.sniffer_page.set_comment(index, &comment);
+                    self.packet_details_page
+                        .set_comment(self.sniffer_page.comment(index));
                 }
             }
             Action::Quit => {
@@ -150,6 +812,69 @@ impl App {
                     // Handle packet details updates
                     self.packet_details_page.update(action)?;
                 }
+                Page::Voip => {
+                    self.voip_page.update(action)?;
+                }
+                Page::FileSummary => {
+                    self.file_summary_page.update(action)?;
+                }
+                Page::DualStack => {
+                    self.dual_stack_page.update(action)?;
+                }
+                Page::OtherL2 => {
+                    self.other_l2_page.update(action)?;
+                }
+                Page::Conversations => {
+                    self.conversations_page.update(action)?;
+                }
+                Page::Endpoints => {
+                    self.endpoints_page.update(action)?;
+                }
+                Page::Flows => {
+                    self.flows_page.update(action)?;
+                }
+                Page::Performance => {
+                    self.performance_page.update(action)?;
+                }
+                Page::FollowStream => {
+                    self.follow_stream_page.update(action)?;
+                }
+                Page::Ladder => {
+                    self.ladder_page.update(action)?;
+                }
+                Page::Timeline => {
+                    self.timeline_page.update(action)?;
+                }
+                Page::Neighbors => {
+                    self.neighbors_page.update(action)?;
+                }
+                Page::Discovery => {
+                    self.discovery_page.update(action)?;
+                }
+                Page::Multicast => {
+                    self.multicast_page.update(action)?;
+                }
+                Page::Replay => {
+                    self.replay_page.update(action)?;
+                }
+                Page::Latency => {
+                    self.latency_page.update(action)?;
+                }
+                Page::Dns => {
+                    self.dns_page.update(action)?;
+                }
+                Page::Http => {
+                    self.http_page.update(action)?;
+                }
+                Page::TopTalkers => {
+                    self.top_talkers_page.update(action)?;
+                }
+                Page::Objects => {
+                    self.objects_page.update(action)?;
+                }
+                Page::Alerts => {
+                    self.alerts_page.update(action)?;
+                }
             },
         }
         Ok(())
@@ -157,13 +882,75 @@ impl App {
 }
 
 impl ComponentRender<()> for App {
-    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+    fn render(&mut self, f: &mut Frame, mut area: Rect, _props: ()) {
+        if let Some(ref alert) = self.alert {
+            let elapsed = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            let color = if (elapsed.as_millis() / FLASH_PERIOD_MS).is_multiple_of(2) {
+                theme().alert
+            } else {
+                theme().border
+            };
+            let block = Block::default()
+                .title(tr!("app.alert", alert.message))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color).add_modifier(Modifier::BOLD));
+            let inner = block.inner(area);
+            f.render_widget(block, area);
+            area = inner;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(area);
+        let props = TopBarProps {
+            trail: self.current_page.trail(),
+            capturing: self.sniffer_page.is_capturing(),
+            analysis_only: self.sniffer_page.capture_unavailable(),
+            source: self.sniffer_page.source(),
+            capture_filter: self.sniffer_page.capture_filter(),
+            display_filter: self.sniffer_page.display_filter(),
+        };
+        self.top_bar.render(f, chunks[0], props);
+        area = chunks[1];
+        if self.current_page.is_statistics() && self.vlan_bar.is_shown() {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(1), Constraint::Min(0)])
+                .split(area);
+            self.vlan_bar.render(f, chunks[0], ());
+            area = chunks[1];
+        }
+
         // Render current page
         match self.current_page {
             Page::Home => self.home_page.render(f, area, ()),
             Page::Device => self.device_page.render(f, area, ()),
             Page::Sniffer => self.sniffer_page.render(f, area, ()),
             Page::PacketDetails => self.packet_details_page.render(f, area, ()), // Render packet details page
+            Page::Voip => self.voip_page.render(f, area, ()),
+            Page::FileSummary => self.file_summary_page.render(f, area, ()),
+            Page::DualStack => self.dual_stack_page.render(f, area, ()),
+            Page::OtherL2 => self.other_l2_page.render(f, area, ()),
+            Page::Conversations => self.conversations_page.render(f, area, ()),
+            Page::Endpoints => self.endpoints_page.render(f, area, ()),
+            Page::Flows => self.flows_page.render(f, area, ()),
+            Page::Performance => self.performance_page.render(f, area, ()),
+            Page::FollowStream => self.follow_stream_page.render(f, area, ()),
+            Page::Ladder => self.ladder_page.render(f, area, ()),
+            Page::Timeline => self.timeline_page.render(f, area, ()),
+            Page::Neighbors => self.neighbors_page.render(f, area, ()),
+            Page::Discovery => self.discovery_page.render(f, area, ()),
+            Page::Multicast => self.multicast_page.render(f, area, ()),
+            Page::Replay => self.replay_page.render(f, area, ()),
+            Page::Latency => self.latency_page.render(f, area, ()),
+            Page::Dns => self.dns_page.render(f, area, ()),
+            Page::Http => self.http_page.render(f, area, ()),
+            Page::TopTalkers => self.top_talkers_page.render(f, area, ()),
+            Page::Objects => self.objects_page.render(f, area, ()),
+            Page::Alerts => self.alerts_page.render(f, area, ()),
         }
     }
 }
@@ -0,0 +1,300 @@
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+use crate::{
+    commands::headless::HeadlessOutput,
+    data::{
+        capture_file::{OutputCompression, OutputFormat, TimePrecision},
+        capture_options::Backend,
+    },
+    i18n::Language,
+    theme::ThemeName,
+};
+
+#[derive(Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Open a capture file instead of capturing live; gzip, zstd and xz
+    /// compressed files are decompressed as they are read
+    #[arg(short, long, value_name = "FILE")]
+    pub read: Option<PathBuf>,
+
+    /// Capture synthesized traffic (DNS, HTTP, ARP and retransmissions)
+    /// instead of a device, to try the interface without capture permissions
+    #[arg(long, conflicts_with = "read")]
+    pub demo: bool,
+
+    /// A capture taken at the same time at another point on the path, such
+    /// as the server side; its clock offset is estimated from packets both
+    /// saw and the two are shown interleaved with a capture point column
+    #[arg(long, value_name = "FILE", requires = "read")]
+    pub merge: Option<PathBuf>,
+
+    /// Capture without the TUI, writing packets to stdout until Ctrl+C
+    #[arg(long, requires = "interface")]
+    pub no_tui: bool,
+
+    /// Interface to capture on, preselected in the TUI; "all" captures on
+    /// every interface at once and "auto" picks the one with the default
+    /// route
+    #[arg(short, long, value_name = "NAME")]
+    pub interface: Option<String>,
+
+    /// BPF capture filter for --no-tui, e.g. "tcp port 443"
+    #[arg(short, long, value_name = "EXPR")]
+    pub filter: Option<String>,
+
+    /// What --no-tui writes to stdout
+    #[arg(long, value_enum, default_value_t = HeadlessOutput::Json)]
+    pub output: HeadlessOutput,
+
+    /// Stop --no-tui after this many packets
+    #[arg(short, long, value_name = "COUNT")]
+    pub count: Option<usize>,
+
+    /// Seconds added to every timestamp of a capture file, like editcap -t;
+    /// negative to move them back
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = 0.0,
+        allow_hyphen_values = true,
+        value_parser = time_shift
+    )]
+    pub time_shift: f64,
+
+    /// Parts per million the clock that stamped a capture file ran fast,
+    /// negative if slow; the error built up since its first packet is removed
+    #[arg(
+        long,
+        value_name = "PPM",
+        default_value_t = 0.0,
+        allow_hyphen_values = true,
+        value_parser = clock_drift
+    )]
+    pub clock_drift: f64,
+
+    /// Bytes kept of each frame in live captures, by default 5000 or as
+    /// saved in the capture options; deep decoding wants whole frames, so
+    /// starting a capture offers to raise it when the device's MTU is larger
+    #[arg(long, value_name = "BYTES", value_parser = clap::value_parser!(u32).range(1..))]
+    pub snaplen: Option<u32>,
+
+    /// Capture only frames addressed to this host instead of everything the
+    /// interface sees
+    #[arg(long)]
+    pub no_promisc: bool,
+
+    /// Kernel capture buffer in MiB, by default libpcap's
+    #[arg(long, value_name = "MIB", value_parser = clap::value_parser!(u32).range(1..))]
+    pub buffer_size: Option<u32>,
+
+    /// Milliseconds libpcap waits to fill its buffer before handing packets
+    /// over, by default 100
+    #[arg(long, value_name = "MS")]
+    pub read_timeout: Option<u32>,
+
+    /// Hand each packet over as it arrives instead of a buffer at a time,
+    /// for lower latency at a higher CPU cost
+    #[arg(long)]
+    pub immediate: bool,
+
+    /// What reads frames from local devices, by default libpcap or as saved
+    /// in the capture options; af-packet reads a Linux TPACKET_V3 ring
+    /// without libpcap in the packet path and takes filters as expressions,
+    /// compiled by libpcap, or as raw BPF programs printed by `tcpdump -ddd`;
+    /// monitor mode needs libpcap. Builds without the libpcap feature
+    /// capture only with af-packet
+    #[arg(long, value_enum, value_name = "BACKEND")]
+    pub backend: Option<Backend>,
+
+    /// Packets kept in memory during a live capture before the oldest are
+    /// evicted; 0 keeps everything
+    #[arg(long, value_name = "COUNT", default_value_t = 1_000_000)]
+    pub max_packets: usize,
+
+    /// Packets moved from the capture thread to the list per UI tick; the
+    /// rest stay queued so rendering keeps up. 0 takes everything at once
+    #[arg(long, value_name = "COUNT", default_value_t = 5_000)]
+    pub batch_size: usize,
+
+    /// Memory in MiB a live capture may use for packets before older ones are
+    /// moved to a temporary file; 0 keeps everything in memory
+    #[arg(long, value_name = "MIB", default_value_t = 0)]
+    pub memory_limit: usize,
+
+    /// During live captures, decode application protocols only for packets
+    /// that are shown, or all of them once a statistics page is opened
+    #[arg(long)]
+    pub lazy: bool,
+
+    /// Try UDP between any two unprivileged ports as RTP, not only the media
+    /// ports SIP calls set up in SDP; finds calls whose signalling wasn't
+    /// captured, but mislabels other UDP that looks like RTP
+    #[arg(long)]
+    pub rtp_heuristic: bool,
+
+    /// Don't flag bad checksums on packets sent from this host, which NICs
+    /// with checksum offload only fill in after the packets are captured
+    #[arg(long)]
+    pub ignore_outgoing_checksums: bool,
+
+    /// Seconds a TCP connection may stay idle before it counts as ended
+    #[arg(long, value_name = "SECONDS", default_value_t = 300)]
+    pub tcp_timeout: u64,
+
+    /// Seconds any other flow may stay idle before it counts as ended
+    #[arg(long, value_name = "SECONDS", default_value_t = 60)]
+    pub flow_timeout: u64,
+
+    /// Append per-interval statistics of live captures (packet and bit
+    /// rates, protocol counts, TCP retransmission rate) to a CSV file
+    #[arg(long, value_name = "FILE")]
+    pub stats_csv: Option<PathBuf>,
+
+    /// Seconds covered by each row of --stats-csv
+    #[arg(long, value_name = "SECONDS", default_value_t = 10)]
+    pub stats_interval: u64,
+
+    /// Also write live captures to a ring of pcap files named after FILE
+    /// (FILE_00001.pcap, ...), whatever the UI keeps in memory; by default
+    /// as saved in the capture options
+    #[arg(long, value_name = "FILE")]
+    pub ring: Option<PathBuf>,
+
+    /// Megabytes a ring file may reach before the next one is started, by
+    /// default 100 or as saved in the capture options
+    #[arg(long, value_name = "MB", value_parser = clap::value_parser!(u64).range(1..))]
+    pub ring_size: Option<u64>,
+
+    /// Seconds a ring file may span before the next one is started
+    #[arg(long, value_name = "SECONDS")]
+    pub ring_seconds: Option<u64>,
+
+    /// Ring files kept, the oldest deleted first; 0 keeps them all. By
+    /// default 10 or as saved in the capture options
+    #[arg(long, value_name = "COUNT")]
+    pub ring_files: Option<usize>,
+
+    /// Remember LAN hosts, their MAC addresses, names and aliases in FILE
+    /// across sessions
+    #[arg(long, value_name = "FILE")]
+    pub hosts_db: Option<PathBuf>,
+
+    /// A MaxMind GeoLite2 Country, City or ASN database (.mmdb) to locate
+    /// addresses with; repeat to use several
+    #[arg(long, value_name = "PATH")]
+    pub geoip: Vec<PathBuf>,
+
+    /// Seconds of packets, back from the newest, that W saves as a snapshot
+    #[arg(long, value_name = "SECONDS", default_value_t = 30)]
+    pub snapshot_seconds: u64,
+
+    /// Compress flow exports and converted files as they are written; a
+    /// converted file named .gz or .zst is compressed either way
+    #[arg(long, value_enum)]
+    pub compress: Option<OutputCompression>,
+
+    /// Interface language; defaults to the one in LANG
+    #[arg(long, value_enum)]
+    pub lang: Option<Language>,
+
+    /// Raise an alert when COUNT packets matching a display filter are seen
+    /// within SECONDS, written SEVERITY:COUNT/SECONDS:FILTER with severity
+    /// info, warning or critical; repeat to add rules
+    #[arg(long, value_name = "RULE")]
+    pub alert_rule: Vec<String>,
+
+    /// Don't ring the terminal bell when a warning or critical alert is
+    /// raised on another page
+    #[arg(long)]
+    pub no_bell: bool,
+
+    /// Color theme; Ctrl+T switches themes while running
+    #[arg(long, value_enum, default_value_t = ThemeName::Dark)]
+    pub theme: ThemeName,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Print a summary of a capture file
+    Info {
+        /// The pcap or pcapng file to summarize
+        file: PathBuf,
+    },
+    /// Replay a capture file through the parser and report its speed
+    Bench {
+        /// The pcap or pcapng file to replay
+        file: PathBuf,
+
+        /// How many times to parse the whole file
+        #[arg(short = 'n', long, default_value_t = 1)]
+        iterations: usize,
+    },
+    /// Dry-run a capture filter and/or display filter over a capture file
+    /// and report how many packets of each protocol match
+    TestFilter {
+        /// The pcap or pcapng file to test against
+        file: PathBuf,
+
+        /// A BPF capture filter, as given to -f
+        #[arg(short, long, value_name = "FILTER")]
+        bpf: Option<String>,
+
+        /// A display filter, as typed on the sniffer page
+        #[arg(short, long, value_name = "FILTER")]
+        display: Option<String>,
+    },
+    /// Copy a capture file into pcap or pcapng, like editcap; --time-shift
+    /// and --clock-drift apply
+    Convert {
+        /// The pcap or pcapng file to read
+        input: PathBuf,
+
+        /// The file to write
+        output: PathBuf,
+
+        /// Output format; by default pcapng for a .pcapng name, else pcap
+        #[arg(short = 'F', long, value_enum)]
+        format: Option<OutputFormat>,
+
+        /// Keep at most this many bytes of each frame
+        #[arg(short, long, value_name = "BYTES")]
+        snaplen: Option<u32>,
+
+        /// Timestamp precision of the output
+        #[arg(long, value_enum, default_value_t = TimePrecision::Micro)]
+        precision: TimePrecision,
+    },
+}
+
+/// Largest --time-shift accepted, about three centuries.
+const MAX_TIME_SHIFT: f64 = 1e10;
+
+/// Largest --clock-drift accepted; a clock a million ppm out is not keeping
+/// time at all.
+const MAX_CLOCK_DRIFT: f64 = 1e6;
+
+/// Parses a finite number no further from zero than `limit`.
+fn bounded(text: &str, limit: f64) -> Result<f64, String> {
+    let value: f64 = text
+        .parse()
+        .map_err(|_| format!("{text:?} is not a number"))?;
+    if value.is_finite() && value.abs() <= limit {
+        Ok(value)
+    } else {
+        Err(format!("must be between -{limit} and {limit}"))
+    }
+}
+
+fn time_shift(text: &str) -> Result<f64, String> {
+    bounded(text, MAX_TIME_SHIFT)
+}
+
+fn clock_drift(text: &str) -> Result<f64, String> {
+    bounded(text, MAX_CLOCK_DRIFT)
+}
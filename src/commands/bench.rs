@@ -0,0 +1,199 @@
+#[cfg(feature = "bench")]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::{
+    hint::black_box,
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::Result;
+use pcap::Precision;
+
+use crate::data::{
+    capture_file::{header_time, open_offline},
+    conversation::ConversationStats,
+    dual_stack::DualStackStats,
+    endpoint::EndpointStats,
+    ethertype::OtherL2Stats,
+    packet::{PacketInfo, parse_packet},
+    profile,
+    voip::VoipStats,
+};
+
+/// Counts heap allocations so the benchmark can report them. Replacing the
+/// global allocator affects the whole binary, so it is only installed with
+/// the `bench` feature.
+#[cfg(feature = "bench")]
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "bench")]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[cfg(feature = "bench")]
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Builds one statistics page from parsed packets.
+type Analysis = fn(&[&PacketInfo]);
+
+/// Allocations made so far, or None when they aren't counted.
+fn allocations() -> Option<usize> {
+    cfg!(feature = "bench").then(|| ALLOCATIONS.load(Ordering::Relaxed))
+}
+
+/// Runs `f`, returning its result with the elapsed time and allocations.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, Duration, Option<usize>) {
+    let before = allocations();
+    let start = Instant::now();
+    let value = f();
+    let elapsed = start.elapsed();
+    (
+        value,
+        elapsed,
+        allocations()
+            .zip(before)
+            .map(|(after, before)| after - before),
+    )
+}
+
+/// An allocation count for the report.
+fn format_allocations(allocations: Option<usize>) -> String {
+    allocations.map_or_else(|| "-".to_string(), |count| count.to_string())
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3} ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn parse_all(link_type: u32, frames: &[(SystemTime, Arc<[u8]>)]) -> Vec<PacketInfo> {
+    let start = frames.first().map(|(time, _)| *time);
+    frames
+        .iter()
+        .enumerate()
+        .map(|(i, (captured_at, data))| {
+            let timestamp = start
+                .and_then(|start| captured_at.duration_since(start).ok())
+                .unwrap_or_default();
+            parse_packet(i + 1, timestamp, *captured_at, link_type, data.clone())
+        })
+        .collect()
+}
+
+/// Replays a capture file through dissection and the statistics pages as
+/// fast as possible. Frames are read into memory first so file I/O is not
+/// part of any figure.
+pub fn run(path: &Path, iterations: usize) -> Result<()> {
+    let mut cap = open_offline(path, Precision::Micro)?;
+    let link_type = cap.get_datalink().0 as u32;
+    let mut frames = Vec::new();
+    while let Ok(packet) = cap.next_packet() {
+        frames.push((header_time(packet.header), Arc::<[u8]>::from(packet.data)));
+    }
+    let iterations = iterations.max(1);
+    let bytes: usize = frames.iter().map(|(_, data)| data.len()).sum();
+
+    profile::reset();
+    let mut packets = Vec::new();
+    let mut parse_time = Duration::ZERO;
+    let mut parse_allocations = Some(0);
+    for _ in 0..iterations {
+        let (parsed, elapsed, allocations) = measure(|| parse_all(link_type, &frames));
+        packets = parsed;
+        parse_time += elapsed;
+        parse_allocations = parse_allocations
+            .zip(allocations)
+            .map(|(sum, count)| sum + count);
+    }
+    let parsed = frames.len() * iterations;
+    let seconds = parse_time.as_secs_f64();
+    let per_second = |count: f64| {
+        if seconds > 0.0 { count / seconds } else { 0.0 }
+    };
+
+    println!("{:<22}{}", "File:", path.display());
+    println!("{:<22}{} x {iterations}", "Packets:", frames.len());
+    println!("{:<22}{}", "Parse time:", format_duration(parse_time));
+    println!("{:<22}{:.0}", "Packets/sec:", per_second(parsed as f64));
+    println!(
+        "{:<22}{:.1} MB/s",
+        "Throughput:",
+        per_second((bytes * iterations) as f64) / 1e6
+    );
+    match parse_allocations {
+        Some(allocations) => println!(
+            "{:<22}{allocations} ({:.1} per packet)",
+            "Allocations:",
+            allocations as f64 / parsed.max(1) as f64
+        ),
+        None => println!(
+            "{:<22}not counted (build with --features bench)",
+            "Allocations:"
+        ),
+    }
+
+    println!();
+    println!("{:<22}{:>14}{:>14}", "Analysis", "Time", "Allocations");
+    let analyses: [(&str, Analysis); 5] = [
+        ("Conversations", |p| {
+            black_box(ConversationStats::from_packets(p));
+        }),
+        ("Endpoints", |p| {
+            black_box(EndpointStats::from_packets(p));
+        }),
+        ("VoIP", |p| {
+            black_box(VoipStats::from_packets(p));
+        }),
+        ("Dual-Stack", |p| {
+            black_box(DualStackStats::from_packets(p));
+        }),
+        ("Other L2", |p| {
+            black_box(OtherL2Stats::from_packets(p));
+        }),
+    ];
+    // Borrowed the way the statistics pages get them.
+    let packets: Vec<&PacketInfo> = packets.iter().collect();
+    for (name, analysis) in analyses {
+        let ((), elapsed, allocations) = measure(|| analysis(&packets));
+        println!(
+            "{name:<22}{:>14}{:>14}",
+            format_duration(elapsed),
+            format_allocations(allocations)
+        );
+    }
+
+    // Decoder counters cover every iteration of the parse above.
+    println!();
+    println!(
+        "{:<22}{:>14}{:>14}{:>14}",
+        "Decoder", "Calls", "Time", "Avg"
+    );
+    for (name, timing) in profile::snapshot() {
+        println!(
+            "{name:<22}{:>14}{:>14}{:>14}",
+            timing.calls,
+            format_duration(timing.total),
+            format!("{} ns", timing.average().as_nanos())
+        );
+    }
+    Ok(())
+}
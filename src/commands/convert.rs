@@ -0,0 +1,72 @@
+use std::{path::Path, time::Duration};
+
+use anyhow::{Context, Result};
+use pcap::Precision;
+
+use crate::data::{
+    capture_file::{
+        CaptureWriter, OutputCompression, OutputFile, OutputFormat, TimePrecision, open_offline,
+    },
+    time::{TimeShift, unix_time},
+};
+
+/// Largest frame libpcap itself accepts, used when no snap length is given.
+const MAX_SNAPLEN: u32 = 262_144;
+
+pub struct Options {
+    pub format: Option<OutputFormat>,
+    pub snaplen: Option<u32>,
+    pub precision: TimePrecision,
+    pub time_shift: TimeShift,
+    pub compression: Option<OutputCompression>,
+}
+
+/// Copies every frame of `input`, in any format libpcap reads, to `output`.
+pub fn run(input: &Path, output: &Path, options: Options) -> Result<()> {
+    let mut cap = open_offline(input, Precision::Nano)?;
+    let link_type = cap.get_datalink().0 as u32;
+    let format = options
+        .format
+        .unwrap_or_else(|| OutputFormat::for_path(output));
+    let snaplen = options.snaplen.unwrap_or(MAX_SNAPLEN);
+
+    let compression = options
+        .compression
+        .or_else(|| OutputCompression::for_path(output));
+
+    let file = OutputFile::create(output, compression)
+        .with_context(|| format!("Failed to create {}", output.display()))?;
+    let write_error = || format!("Failed to write {}", output.display());
+    let mut writer = CaptureWriter::new(file, format, options.precision, link_type, snaplen)
+        .with_context(write_error)?;
+
+    let mut first = None;
+    let mut count = 0;
+    let mut truncated = 0;
+    while let Ok(packet) = cap.next_packet() {
+        // With nanosecond precision libpcap puts nanoseconds in tv_usec.
+        let recorded = unix_time(
+            packet.header.ts.tv_sec,
+            Duration::from_nanos(packet.header.ts.tv_usec as u64),
+        );
+        let at = options
+            .time_shift
+            .apply(recorded, *first.get_or_insert(recorded))?;
+        if packet.data.len() > snaplen as usize {
+            truncated += 1;
+        }
+        writer
+            .write(at, packet.data, packet.header.len)
+            .with_context(write_error)?;
+        count += 1;
+    }
+    writer.into_inner().finish().with_context(write_error)?;
+
+    let compressed = compression.map_or(String::new(), |c| format!(" {c:?}"));
+    println!(
+        "Wrote {count} packets to {} ({format:?}{compressed}, {:?} timestamps, {truncated} truncated)",
+        output.display(),
+        options.precision
+    );
+    Ok(())
+}
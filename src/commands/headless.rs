@@ -0,0 +1,159 @@
+use std::{
+    io::{self, BufWriter, ErrorKind, Stdout, Write},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+};
+
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+
+use crate::data::{
+    capture_file::{CaptureWriter, OutputFormat, TimePrecision},
+    capture_options::CaptureOptions,
+    live,
+    packet::{PacketInfo, parse_headers, parse_packet},
+    packet_export::json_line,
+    ring::RingWriter,
+    stats_log::StatsLog,
+};
+
+/// What `--no-tui` writes to stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HeadlessOutput {
+    /// One JSON object per packet and line
+    Json,
+    /// A pcap stream, for piping into other tools
+    Pcap,
+}
+
+pub struct Options {
+    pub interface: String,
+    pub filter: Option<String>,
+    pub output: HeadlessOutput,
+    pub count: Option<usize>,
+    pub capture_options: CaptureOptions,
+    pub stats_log: Option<StatsLog>,
+    pub ring: Option<RingWriter>,
+}
+
+enum Sink {
+    Json(BufWriter<Stdout>),
+    Pcap(CaptureWriter<BufWriter<Stdout>>),
+}
+
+impl Sink {
+    fn write(&mut self, packet: &PacketInfo) -> io::Result<()> {
+        // Flushed per packet so readers of a pipe see packets as they come.
+        match self {
+            Sink::Json(out) => {
+                writeln!(out, "{}", json_line(packet, false))?;
+                out.flush()
+            }
+            Sink::Pcap(writer) => {
+                writer.write(packet.captured_at, &packet.data, packet.length as u32)?;
+                writer.flush()
+            }
+        }
+    }
+}
+
+/// Captures on an interface without the TUI until Ctrl+C, the packet
+/// count or a closed stdout, writing each packet to stdout.
+pub async fn run(mut options: Options) -> Result<()> {
+    options.capture_options.filter = options.filter.clone().unwrap_or_default();
+    let sources = live::open(&options.interface, &options.capture_options)?;
+    let stdout = BufWriter::new(io::stdout());
+    let (mut sink, parse): (Sink, live::Parser) = match options.output {
+        HeadlessOutput::Json => (Sink::Json(stdout), parse_packet),
+        // Only the frames are written, so application protocols can wait.
+        HeadlessOutput::Pcap => {
+            let mut link_types = sources.iter().map(|s| s.capture.link_type());
+            let link_type = link_types.next().unwrap_or_default();
+            if link_types.any(|other| other != link_type) {
+                bail!("The interfaces have different link types; use --output json");
+            }
+            let writer = CaptureWriter::new(
+                stdout,
+                OutputFormat::Pcap,
+                TimePrecision::Micro,
+                link_type,
+                options.capture_options.snaplen,
+            )?;
+            (Sink::Pcap(writer), parse_headers)
+        }
+    };
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stats = Arc::new(Mutex::new(None));
+    let queue_dropped = Arc::new(AtomicUsize::new(0));
+    let (handle, mut packets) = live::spawn(
+        sources,
+        parse,
+        Arc::clone(&stop),
+        Arc::clone(&stats),
+        Arc::clone(&queue_dropped),
+    );
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    let mut written = 0;
+    let mut result = Ok(());
+    while options.count.is_none_or(|count| written < count) {
+        let packet = tokio::select! {
+            packet = packets.recv() => packet,
+            _ = &mut ctrl_c => None,
+        };
+        let Some(packet) = packet else {
+            break;
+        };
+        if let Some(ref mut stats_log) = options.stats_log
+            && let Err(e) = stats_log.add(&packet)
+        {
+            result = Err(e);
+            break;
+        }
+        if let Some(ref mut ring) = options.ring
+            && let Err(e) = ring.add(&packet)
+        {
+            result = Err(e);
+            break;
+        }
+        match sink.write(&packet) {
+            Ok(()) => written += 1,
+            // The reader went away, as with `| head`.
+            Err(e) if e.kind() == ErrorKind::BrokenPipe => break,
+            Err(e) => {
+                result = Err(e).context("Failed to write to stdout");
+                break;
+            }
+        }
+    }
+
+    stop.store(true, Ordering::Relaxed);
+    drop(packets);
+    let _ = handle.join();
+    if let Some(ref mut stats_log) = options.stats_log
+        && let Err(e) = stats_log.finish()
+        && result.is_ok()
+    {
+        result = Err(e);
+    }
+    if let Some(ref mut ring) = options.ring
+        && let Err(e) = ring.finish()
+        && result.is_ok()
+    {
+        result = Err(e);
+    }
+    let dropped = stats
+        .lock()
+        .ok()
+        .and_then(|stats| *stats)
+        .map_or(0, |stat| stat.dropped);
+    eprintln!(
+        "{written} packets captured, {dropped} dropped by the kernel, {} while output fell behind",
+        queue_dropped.load(Ordering::Relaxed)
+    );
+    result
+}
@@ -0,0 +1,13 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::data::{capture_file, time::TimeShift};
+
+pub fn run(path: &Path, time_shift: TimeShift) -> Result<()> {
+    let capture = capture_file::open(path, time_shift)?;
+    for (label, value) in capture.summary.rows() {
+        println!("{:<22}{value}", format!("{label}:"));
+    }
+    Ok(())
+}
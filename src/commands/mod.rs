@@ -0,0 +1,55 @@
+use anyhow::Result;
+
+use crate::{
+    cli::Command,
+    data::{capture_file::OutputCompression, time::TimeShift},
+};
+
+#[cfg(feature = "libpcap")]
+mod bench;
+#[cfg(feature = "libpcap")]
+mod convert;
+pub mod headless;
+mod info;
+#[cfg(feature = "libpcap")]
+mod test_filter;
+
+/// Runs a headless subcommand without starting the TUI.
+#[cfg_attr(not(feature = "libpcap"), allow(unused_variables))]
+pub fn run(
+    command: Command,
+    time_shift: TimeShift,
+    compression: Option<OutputCompression>,
+) -> Result<()> {
+    match command {
+        Command::Info { file } => info::run(&file, time_shift),
+        #[cfg(not(feature = "libpcap"))]
+        Command::Bench { .. } | Command::TestFilter { .. } | Command::Convert { .. } => {
+            anyhow::bail!("Built without libpcap, which reads the capture files")
+        }
+        #[cfg(feature = "libpcap")]
+        Command::Bench { file, iterations } => bench::run(&file, iterations),
+        #[cfg(feature = "libpcap")]
+        Command::TestFilter { file, bpf, display } => {
+            test_filter::run(&file, bpf.as_deref(), display.as_deref())
+        }
+        #[cfg(feature = "libpcap")]
+        Command::Convert {
+            input,
+            output,
+            format,
+            snaplen,
+            precision,
+        } => convert::run(
+            &input,
+            &output,
+            convert::Options {
+                format,
+                snaplen,
+                precision,
+                time_shift,
+                compression,
+            },
+        ),
+    }
+}
@@ -0,0 +1,109 @@
+use std::{collections::HashMap, path::Path, sync::Arc, time::Duration};
+
+use anyhow::{Context, Result, bail};
+use pcap::Precision;
+
+use crate::data::{
+    capture_file::{header_time, open_offline},
+    display_filter::DisplayFilter,
+    packet::parse_packet,
+};
+
+#[derive(Default)]
+struct Counts {
+    packets: usize,
+    matched: usize,
+}
+
+fn percent(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 * 100.0 / whole as f64
+    }
+}
+
+/// Runs a BPF capture filter and/or a display filter over a capture file
+/// without keeping any packets, and reports how many each protocol would
+/// keep. A packet matches when it passes both filters.
+pub fn run(path: &Path, bpf: Option<&str>, display: Option<&str>) -> Result<()> {
+    if bpf.is_none() && display.is_none() {
+        bail!("Give a filter to test with --bpf or --display");
+    }
+    let mut cap = open_offline(path, Precision::Micro)?;
+    let link_type = cap.get_datalink().0 as u32;
+    // Compiled rather than set on the capture so non-matching packets are
+    // still read and counted.
+    let program = bpf
+        .map(|bpf| {
+            cap.compile(bpf, true)
+                .with_context(|| format!("Invalid capture filter {bpf}"))
+        })
+        .transpose()?;
+    let display_filter = display
+        .map(|display| {
+            DisplayFilter::parse(display)
+                .with_context(|| format!("Invalid display filter {display}"))
+        })
+        .transpose()?;
+
+    let mut protocols: HashMap<String, Counts> = HashMap::new();
+    let mut total = Counts::default();
+    while let Ok(packet) = cap.next_packet() {
+        let passes_bpf = program.as_ref().is_none_or(|p| p.filter(packet.data));
+        let captured_at = header_time(packet.header);
+        let info = parse_packet(
+            total.packets + 1,
+            Duration::ZERO,
+            captured_at,
+            link_type,
+            Arc::from(packet.data),
+        );
+        let matched = passes_bpf && display_filter.as_ref().is_none_or(|f| f.matches(&info));
+
+        let counts = protocols.entry(info.protocol).or_default();
+        counts.packets += 1;
+        total.packets += 1;
+        if matched {
+            counts.matched += 1;
+            total.matched += 1;
+        }
+    }
+
+    println!("{:<22}{}", "File:", path.display());
+    if let Some(bpf) = bpf {
+        println!("{:<22}{bpf}", "Capture filter:");
+    }
+    if let Some(display) = display {
+        println!("{:<22}{display}", "Display filter:");
+    }
+    println!(
+        "{:<22}{} of {} ({:.1}%)",
+        "Matched:",
+        total.matched,
+        total.packets,
+        percent(total.matched, total.packets)
+    );
+
+    let mut protocols: Vec<(String, Counts)> = protocols.into_iter().collect();
+    protocols.sort_by(|(a_name, a), (b_name, b)| {
+        b.matched
+            .cmp(&a.matched)
+            .then(b.packets.cmp(&a.packets))
+            .then(a_name.cmp(b_name))
+    });
+    println!();
+    println!(
+        "{:<22}{:>12}{:>12}{:>10}",
+        "Protocol", "Packets", "Matched", "Share"
+    );
+    for (name, counts) in protocols {
+        println!(
+            "{name:<22}{:>12}{:>12}{:>10}",
+            counts.packets,
+            counts.matched,
+            format!("{:.1}%", percent(counts.matched, counts.packets))
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use std::{collections::HashMap, net::IpAddr};
+
+use etherparse::{ArpOperation, InternetSlice, SlicedPacket};
+
+use super::{
+    alert_rule::RuleEngine,
+    packet::{PacketInfo, format_mac},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "info" => Some(Severity::Info),
+            "warning" | "warn" => Some(Severity::Warning),
+            "critical" | "crit" => Some(Severity::Critical),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Info => "Info",
+            Severity::Warning => "Warning",
+            Severity::Critical => "Critical",
+        }
+    }
+}
+
+/// Something in the traffic worth interrupting the user for.
+#[derive(Debug, Clone)]
+pub struct Alert {
+    pub index: usize, // Packet that raised it
+    pub severity: Severity,
+    pub kind: &'static str, // What raised it, "ARP" or "Rule"
+    pub message: String,
+}
+
+/// Watches the IP to MAC bindings ARP announces, and the user's alert
+/// rules. An address answered for by a new MAC address, as with ARP
+/// spoofing or a replaced gateway, raises a critical alert.
+#[derive(Debug, Default)]
+pub struct AlertMonitor {
+    bindings: HashMap<IpAddr, String>,
+    rules: RuleEngine,
+    pub alerts: Vec<Alert>,
+}
+
+impl AlertMonitor {
+    pub fn set_rules(&mut self, rules: RuleEngine) {
+        self.rules = rules;
+    }
+
+    /// Checks `packet`, returning the last alert it raised.
+    pub fn add(&mut self, index: usize, packet: &PacketInfo) -> Option<&Alert> {
+        let before = self.alerts.len();
+        if let Some(alert) = self.check_arp(index, packet) {
+            self.alerts.push(alert);
+        }
+        self.rules.add(index, packet, &mut self.alerts);
+        self.alerts[before..].last()
+    }
+
+    fn check_arp(&mut self, index: usize, packet: &PacketInfo) -> Option<Alert> {
+        let sliced = SlicedPacket::from_ethernet(&packet.data).ok()?;
+        let Some(InternetSlice::Arp(arp)) = sliced.net else {
+            return None;
+        };
+        if !matches!(arp.operation(), ArpOperation::REQUEST | ArpOperation::REPLY) {
+            return None;
+        }
+        let ip = match <[u8; 4]>::try_from(arp.sender_protocol_addr()) {
+            Ok(bytes) => IpAddr::from(bytes),
+            Err(_) => return None,
+        };
+        // Probes come from 0.0.0.0 and bind nothing.
+        if ip.is_unspecified() {
+            return None;
+        }
+        let mac = format_mac(arp.sender_hw_addr());
+        let old = self.bindings.insert(ip, mac.clone())?;
+        if old == mac {
+            return None;
+        }
+        Some(Alert {
+            index,
+            severity: Severity::Critical,
+            kind: "ARP",
+            message: format!("ARP: {ip} moved from {old} to {mac}, possible spoofing"),
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+        self.rules.clear();
+        self.alerts.clear();
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result, bail};
+
+use super::{
+    alert::{Alert, Severity},
+    display_filter::DisplayFilter,
+    packet::PacketInfo,
+};
+
+/// Raises an alert once `threshold` packets matching `filter` are seen
+/// within `window`.
+#[derive(Debug, Clone)]
+pub struct AlertRule {
+    pub severity: Severity,
+    pub threshold: usize,
+    pub window: Duration,
+    pub filter: DisplayFilter,
+}
+
+impl AlertRule {
+    /// Parses a rule written `SEVERITY:COUNT/SECONDS:FILTER`, as in
+    /// `warning:50/10:tcp.flags.rst == 1`. The filter may contain colons.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut parts = text.splitn(3, ':');
+        let (Some(severity), Some(rate), Some(filter)) = (parts.next(), parts.next(), parts.next())
+        else {
+            bail!("Alert rule {text:?} is not SEVERITY:COUNT/SECONDS:FILTER");
+        };
+        let severity = Severity::parse(severity).with_context(|| {
+            format!("Unknown severity {severity:?}, expected info, warning or critical")
+        })?;
+        let (count, seconds) = rate
+            .split_once('/')
+            .with_context(|| format!("Rate {rate:?} is not COUNT/SECONDS"))?;
+        let threshold: usize = count
+            .trim()
+            .parse()
+            .with_context(|| format!("Bad packet count {count:?}"))?;
+        let window = seconds
+            .trim()
+            .parse()
+            .ok()
+            .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+            .filter(|window| !window.is_zero())
+            .with_context(|| format!("Bad window {seconds:?}"))?;
+        if threshold == 0 {
+            bail!("Alert rule {text:?} needs a packet count of at least 1");
+        }
+        let filter = DisplayFilter::parse(filter)
+            .with_context(|| format!("Bad filter in alert rule {text:?}"))?;
+        Ok(Self {
+            severity,
+            threshold,
+            window,
+            filter,
+        })
+    }
+}
+
+/// Counts the packets each rule matches. A rule whose count within its
+/// window reaches its threshold raises an alert and starts counting anew,
+/// so a steady flood alerts once per threshold rather than once per packet.
+#[derive(Debug, Default)]
+pub struct RuleEngine {
+    rules: Vec<AlertRule>,
+    matches: Vec<VecDeque<SystemTime>>, // Recent match times per rule
+}
+
+impl RuleEngine {
+    pub fn new(rules: Vec<AlertRule>) -> Self {
+        let matches = vec![VecDeque::new(); rules.len()];
+        Self { rules, matches }
+    }
+
+    /// Checks `packet` against every rule, appending the alerts it raises.
+    pub fn add(&mut self, index: usize, packet: &PacketInfo, alerts: &mut Vec<Alert>) {
+        if self.rules.is_empty() {
+            return;
+        }
+        // Rules can name application protocols, which lazy parsing skips.
+        let packet = packet.full();
+        for (rule, times) in self.rules.iter().zip(&mut self.matches) {
+            if !rule.filter.matches(&packet) {
+                continue;
+            }
+            times.push_back(packet.captured_at);
+            while let Some(&first) = times.front() {
+                match packet.captured_at.duration_since(first) {
+                    Ok(age) if age > rule.window => times.pop_front(),
+                    _ => break,
+                };
+            }
+            if times.len() < rule.threshold {
+                continue;
+            }
+            times.clear();
+            alerts.push(Alert {
+                index,
+                severity: rule.severity,
+                kind: "Rule",
+                message: format!(
+                    "Rule: {} packets matching {} within {}s",
+                    rule.threshold,
+                    rule.filter.text(),
+                    rule.window.as_secs_f64()
+                ),
+            });
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.matches.iter_mut().for_each(VecDeque::clear);
+    }
+}
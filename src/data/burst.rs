@@ -0,0 +1,160 @@
+use std::{
+    ops::Range,
+    time::{Duration, SystemTime},
+};
+
+use super::packet::PacketInfo;
+
+/// Finest resolution of the timeline; microbursts last tens of milliseconds.
+const BIN_WIDTH: Duration = Duration::from_millis(10);
+/// Bins kept at most; long captures get wider bins instead.
+const MAX_BINS: u128 = 1_000_000;
+/// How far a bin's rate must exceed the average of its second to be part of
+/// a burst.
+const BURST_FACTOR: f64 = 4.0;
+/// Packets a burst needs, so a lone large packet on a quiet link is not one.
+const MIN_BURST_PACKETS: usize = 10;
+
+/// Traffic in one slice of the timeline.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Bin {
+    pub bytes: usize,
+    pub packets: usize,
+}
+
+/// A run of bins far busier than the second around them.
+#[derive(Debug, Clone)]
+pub struct Burst {
+    pub start: Duration, // Since the first packet
+    pub duration: Duration,
+    pub bytes: usize,
+    pub packets: usize,
+    pub peak_bps: f64,   // Highest rate of a single bin
+    pub second_bps: f64, // Average rate of the seconds it falls in
+    pub bins: Range<usize>,
+}
+
+impl Burst {
+    /// How many times the peak exceeds what a per-second graph shows.
+    pub fn ratio(&self) -> f64 {
+        if self.second_bps > 0.0 {
+            self.peak_bps / self.second_bps
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Traffic binned finely enough to show microbursts, with the bursts found.
+#[derive(Debug, Clone, Default)]
+pub struct Timeline {
+    pub bin_width: Duration,
+    pub bins: Vec<Bin>,
+    pub bursts: Vec<Burst>,
+    pub average_bps: f64,
+}
+
+impl Timeline {
+    pub fn from_packets(packets: &[&PacketInfo]) -> Self {
+        let (Some(start), Some(end)) = (
+            packets.iter().map(|packet| packet.captured_at).min(),
+            packets.iter().map(|packet| packet.captured_at).max(),
+        ) else {
+            return Self::default();
+        };
+        let span = end.duration_since(start).unwrap_or_default();
+        let bin_width = BIN_WIDTH.max(Duration::from_nanos(
+            (span.as_nanos() / MAX_BINS).min(u64::MAX as u128) as u64,
+        ));
+        let bin_of = |at: SystemTime| {
+            let offset = at.duration_since(start).unwrap_or_default();
+            (offset.as_nanos() / bin_width.as_nanos()) as usize
+        };
+        let mut bins = vec![Bin::default(); bin_of(end) + 1];
+        for packet in packets {
+            let bin = &mut bins[bin_of(packet.captured_at)];
+            bin.bytes += packet.length;
+            bin.packets += 1;
+        }
+
+        let bin_secs = bin_width.as_secs_f64();
+        let total_bytes: usize = bins.iter().map(|bin| bin.bytes).sum();
+        let average_bps = total_bytes as f64 * 8.0 / (bins.len() as f64 * bin_secs);
+        // Average rate of the second each bin falls in, as a per-second
+        // graph would show it.
+        let per_second = ((1.0 / bin_secs).round() as usize).max(1);
+        let second_bps: Vec<f64> = bins
+            .chunks(per_second)
+            .map(|second| {
+                let bytes: usize = second.iter().map(|bin| bin.bytes).sum();
+                bytes as f64 * 8.0 / (second.len() as f64 * bin_secs)
+            })
+            .collect();
+        let rate = |bin: &Bin| bin.bytes as f64 * 8.0 / bin_secs;
+
+        let mut bursts = Vec::new();
+        let mut i = 0;
+        while i < bins.len() {
+            let is_hot = |i: usize| {
+                bins[i].bytes > 0 && rate(&bins[i]) > BURST_FACTOR * second_bps[i / per_second]
+            };
+            if !is_hot(i) {
+                i += 1;
+                continue;
+            }
+            let first = i;
+            while i < bins.len() && is_hot(i) {
+                i += 1;
+            }
+            let run = &bins[first..i];
+            let packets = run.iter().map(|bin| bin.packets).sum();
+            if packets < MIN_BURST_PACKETS {
+                continue;
+            }
+            let seconds = &second_bps[first / per_second..=(i - 1) / per_second];
+            bursts.push(Burst {
+                start: bin_width * first as u32,
+                duration: bin_width * run.len() as u32,
+                bytes: run.iter().map(|bin| bin.bytes).sum(),
+                packets,
+                peak_bps: run.iter().map(rate).fold(0.0, f64::max),
+                second_bps: seconds.iter().sum::<f64>() / seconds.len() as f64,
+                bins: first..i,
+            });
+        }
+
+        Self {
+            bin_width,
+            bins,
+            bursts,
+            average_bps,
+        }
+    }
+
+    /// Peak bin rate in bits per second within each of `columns` equal
+    /// slices of the timeline, and whether a burst touches the slice.
+    pub fn columns(&self, columns: usize) -> Vec<(f64, bool)> {
+        if self.bins.is_empty() || columns == 0 {
+            return Vec::new();
+        }
+        let bin_secs = self.bin_width.as_secs_f64();
+        let per_column = self.bins.len().div_ceil(columns);
+        self.bins
+            .chunks(per_column)
+            .enumerate()
+            .map(|(column, chunk)| {
+                let first = column * per_column;
+                let range = first..first + chunk.len();
+                let peak = chunk
+                    .iter()
+                    .map(|bin| bin.bytes as f64 * 8.0 / bin_secs)
+                    .fold(0.0, f64::max);
+                let burst = self
+                    .bursts
+                    .iter()
+                    .any(|burst| burst.bins.start < range.end && range.start < burst.bins.end);
+                (peak, burst)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,876 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    mem,
+    os::fd::IntoRawFd,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, SyncSender},
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use flate2::{read::MultiGzDecoder, write::GzEncoder};
+#[cfg(feature = "libpcap")]
+use pcap::{Capture, Offline, PacketHeader, Precision};
+
+use super::{
+    link,
+    merge::Merge,
+    packet::{PacketInfo, parse_packet},
+    time::{TimeShift, format_utc, unix_time},
+};
+
+/// Capinfos-style facts about a capture file.
+#[derive(Debug, Clone)]
+pub struct FileSummary {
+    pub path: PathBuf,
+    pub file_size: u64,
+    pub format: String,
+    pub link_type: String,
+    pub encapsulation: String,
+    pub packet_count: usize,
+    pub data_bytes: u64,
+    pub first: Option<SystemTime>,
+    pub last: Option<SystemTime>,
+    pub time_shift: TimeShift, // Already applied to `first`, `last` and every packet
+    pub merged: Option<Merge>, // A second capture interleaved with this one
+}
+
+impl FileSummary {
+    pub fn duration(&self) -> Duration {
+        match (self.first, self.last) {
+            (Some(first), Some(last)) => last.duration_since(first).unwrap_or_default(),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// The summary as label/value rows, shared by the TUI and `sniffer info`.
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        let secs = self.duration().as_secs_f64();
+        let per_sec = |value: f64| {
+            if secs > 0.0 {
+                format!("{:.2}", value / secs)
+            } else {
+                "n/a".to_string()
+            }
+        };
+        let time = |t: Option<SystemTime>| {
+            t.and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| format_utc(d.as_secs_f64()))
+                .unwrap_or_else(|| "n/a".to_string())
+        };
+        let average_size = if self.packet_count > 0 {
+            format!(
+                "{:.2} bytes",
+                self.data_bytes as f64 / self.packet_count as f64
+            )
+        } else {
+            "n/a".to_string()
+        };
+
+        let mut rows = vec![
+            ("File name", self.path.display().to_string()),
+            ("File format", self.format.clone()),
+            ("File size", format!("{} bytes", self.file_size)),
+            ("Link type", self.link_type.clone()),
+            ("Encapsulation", self.encapsulation.clone()),
+            ("Packets", self.packet_count.to_string()),
+            ("Data size", format!("{} bytes", self.data_bytes)),
+            ("First packet", time(self.first)),
+            ("Last packet", time(self.last)),
+            ("Duration", format!("{secs:.6} seconds")),
+            (
+                "Average packet rate",
+                format!("{} packets/s", per_sec(self.packet_count as f64)),
+            ),
+            (
+                "Average data rate",
+                format!("{} bytes/s", per_sec(self.data_bytes as f64)),
+            ),
+            ("Average packet size", average_size),
+        ];
+        if !self.time_shift.is_identity() {
+            rows.insert(9, ("Time shift", self.time_shift.to_string()));
+        }
+        if let Some(ref merged) = self.merged {
+            rows.insert(1, ("Merged with", merged.path.display().to_string()));
+            rows.push(("Clock offset", merged.describe()));
+        }
+        rows
+    }
+}
+
+pub struct CaptureFile {
+    pub packets: Vec<PacketInfo>,
+    pub comments: BTreeMap<usize, String>, // pcapng packet comments, by position in `packets`
+    pub summary: FileSummary,
+}
+
+#[cfg(feature = "libpcap")]
+pub fn header_time(header: &PacketHeader) -> SystemTime {
+    unix_time(
+        header.ts.tv_sec,
+        Duration::from_micros(header.ts.tv_usec as u64),
+    )
+}
+
+/// Compression a capture file can arrive in, recognized by its magic bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Compression {
+    Gzip,
+    Zstd,
+    Xz,
+}
+
+impl Compression {
+    fn detect(path: &Path) -> io::Result<Option<Self>> {
+        let mut magic = [0u8; 6];
+        let read = File::open(path)?.read(&mut magic)?;
+        Ok(match magic[..read] {
+            [0x1f, 0x8b, ..] => Some(Compression::Gzip),
+            [0x28, 0xb5, 0x2f, 0xfd, ..] => Some(Compression::Zstd),
+            [0xfd, b'7', b'z', b'X', b'Z', 0x00] => Some(Compression::Xz),
+            _ => None,
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Compression::Gzip => "gzip",
+            Compression::Zstd => "zstd",
+            Compression::Xz => "xz",
+        }
+    }
+
+    /// Decompresses `file` into `out` until either ends.
+    fn decompress(self, file: File, out: &mut impl Write) -> io::Result<()> {
+        let mut input = BufReader::new(file);
+        match self {
+            Compression::Gzip => io::copy(&mut MultiGzDecoder::new(input), out).map(drop),
+            Compression::Zstd => io::copy(&mut zstd::Decoder::with_buffer(input)?, out).map(drop),
+            Compression::Xz => lzma_rs::xz_decompress(&mut input, out).map_err(|e| match e {
+                lzma_rs::error::Error::IoError(e) => e,
+                e => io::Error::other(e),
+            }),
+        }
+    }
+}
+
+/// Keeps the first bytes written to it, then refuses the rest so the
+/// decompression feeding it stops.
+struct Prefix {
+    bytes: [u8; 4],
+    len: usize,
+}
+
+impl Write for Prefix {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = buf.len().min(self.bytes.len() - self.len);
+        if n == 0 && !buf.is_empty() {
+            return Err(io::ErrorKind::WriteZero.into());
+        }
+        self.bytes[self.len..self.len + n].copy_from_slice(&buf[..n]);
+        self.len += n;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn detect_format(path: &Path) -> String {
+    let compression = Compression::detect(path).ok().flatten();
+    let mut magic = Prefix {
+        bytes: [0; 4],
+        len: 0,
+    };
+    let _ = match compression {
+        Some(compression) => {
+            File::open(path).and_then(|file| compression.decompress(file, &mut magic))
+        }
+        None => File::open(path).and_then(|file| io::copy(&mut file.take(4), &mut magic).map(drop)),
+    };
+    let format = match (magic.len, u32::from_le_bytes(magic.bytes)) {
+        (4, 0x0a0d_0d0a) => "pcapng",
+        (4, 0xa1b2_c3d4 | 0xd4c3_b2a1) => "pcap (microsecond timestamps)",
+        (4, 0xa1b2_3c4d | 0x4d3c_b2a1) => "pcap (nanosecond timestamps)",
+        _ => "unknown",
+    };
+    match compression {
+        Some(compression) => format!("{format}, {} compressed", compression.name()),
+        None => format.to_string(),
+    }
+}
+
+/// Opens a capture file for libpcap. Gzip, zstd and xz files are
+/// decompressed on a thread that streams into libpcap through a pipe, so
+/// they are never unpacked on disk or whole in memory.
+#[cfg(feature = "libpcap")]
+pub fn open_offline(path: &Path, precision: Precision) -> Result<Capture<Offline>> {
+    let context = || format!("Failed to open capture file {}", path.display());
+    let Some(compression) = Compression::detect(path).with_context(context)? else {
+        return Capture::from_file_with_precision(path, precision).with_context(context);
+    };
+    let file = File::open(path).with_context(context)?;
+    let (reader, mut writer) = io::pipe().with_context(context)?;
+    // Ends when the file does, or when libpcap closes its end of the pipe.
+    thread::spawn(move || compression.decompress(file, &mut writer));
+    // SAFETY: the descriptor is open and owned by nothing else; libpcap
+    // closes it with the capture.
+    unsafe { Capture::from_raw_fd_with_precision(reader.into_raw_fd(), precision) }
+        .with_context(context)
+}
+
+pub const LINKTYPE_ETHERNET: u32 = 1;
+const SNAPLEN: u32 = 65535;
+pub const FILE_HEADER_LEN: u64 = 24;
+pub const RECORD_HEADER_LEN: u64 = 16;
+
+const PCAP_MICROS: u32 = 0xa1b2_c3d4;
+const PCAP_NANOS: u32 = 0xa1b2_3c4d;
+
+fn write_pcap_header(
+    writer: &mut impl Write,
+    magic: u32,
+    snaplen: u32,
+    link_type: u32,
+) -> io::Result<()> {
+    writer.write_all(&magic.to_le_bytes())?;
+    writer.write_all(&2u16.to_le_bytes())?;
+    writer.write_all(&4u16.to_le_bytes())?;
+    writer.write_all(&0i32.to_le_bytes())?;
+    writer.write_all(&0u32.to_le_bytes())?;
+    writer.write_all(&snaplen.to_le_bytes())?;
+    writer.write_all(&link::file_link_type(link_type).to_le_bytes())
+}
+
+/// Writes a classic pcap header for `link_type` frames with microsecond
+/// timestamps, the format every tool reads.
+pub fn write_header(writer: &mut impl Write, link_type: u32) -> io::Result<()> {
+    write_pcap_header(writer, PCAP_MICROS, SNAPLEN, link_type)
+}
+
+pub fn write_record(writer: &mut impl Write, packet: &PacketInfo) -> io::Result<()> {
+    let since_epoch = packet
+        .captured_at
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    writer.write_all(&(since_epoch.as_secs() as u32).to_le_bytes())?;
+    writer.write_all(&since_epoch.subsec_micros().to_le_bytes())?;
+    writer.write_all(&(packet.data.len() as u32).to_le_bytes())?;
+    writer.write_all(&(packet.length as u32).to_le_bytes())?;
+    writer.write_all(&packet.data)
+}
+
+/// The error for a frame whose link type differs from the one a pcap file
+/// was started with; only pcapng can hold several.
+pub fn mixed_link_types(link_type: u32, file_link_type: u32) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!(
+            "a frame has link type {link_type} but the file holds {file_link_type}; \
+             pcap holds one link type, so save as pcapng"
+        ),
+    )
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Pcap,
+    Pcapng,
+}
+
+impl OutputFormat {
+    /// The format a file name asks for: pcapng for `.pcapng`, else pcap. A
+    /// compression extension after it, as in `.pcapng.gz`, is looked past.
+    pub fn for_path(path: &Path) -> Self {
+        let path = match OutputCompression::for_path(path) {
+            Some(_) => Path::new(path.file_stem().unwrap_or_default()),
+            None => path,
+        };
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("pcapng") => OutputFormat::Pcapng,
+            _ => OutputFormat::Pcap,
+        }
+    }
+}
+
+/// Compression for capture files being written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputCompression {
+    Gzip,
+    Zstd,
+}
+
+impl OutputCompression {
+    /// The compression a file name asks for, from its last extension.
+    pub fn for_path(path: &Path) -> Option<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("gz") => Some(OutputCompression::Gzip),
+            Some(ext) if ext.eq_ignore_ascii_case("zst") => Some(OutputCompression::Zstd),
+            _ => None,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputCompression::Gzip => "gz",
+            OutputCompression::Zstd => "zst",
+        }
+    }
+
+    /// Compresses every chunk from `chunks` into `file` until the sender
+    /// is dropped.
+    fn compress(self, chunks: mpsc::Receiver<Vec<u8>>, file: BufWriter<File>) -> io::Result<()> {
+        let mut file = match self {
+            OutputCompression::Gzip => {
+                let mut encoder = GzEncoder::new(file, flate2::Compression::default());
+                for chunk in chunks {
+                    encoder.write_all(&chunk)?;
+                }
+                encoder.finish()?
+            }
+            OutputCompression::Zstd => {
+                let mut encoder = zstd::Encoder::new(file, 0)?;
+                for chunk in chunks {
+                    encoder.write_all(&chunk)?;
+                }
+                encoder.finish()?
+            }
+        };
+        file.flush()
+    }
+}
+
+/// Bytes handed to the compression thread at a time.
+const COMPRESSION_CHUNK: usize = 256 * 1024;
+/// Chunks that may wait for the compression thread before writes block.
+const QUEUED_CHUNKS: usize = 8;
+
+enum Sink {
+    Plain(BufWriter<File>),
+    Compressed {
+        chunks: SyncSender<Vec<u8>>,
+        encoder: JoinHandle<io::Result<()>>,
+    },
+}
+
+/// A capture file being written. Compressed files are compressed on a
+/// background thread fed in chunks, so compression runs alongside whatever
+/// produces the frames. `finish` reports errors from either side.
+pub struct OutputFile {
+    sink: Sink,
+    buffer: Vec<u8>,
+}
+
+impl OutputFile {
+    pub fn create(path: &Path, compression: Option<OutputCompression>) -> io::Result<Self> {
+        let file = BufWriter::new(File::create(path)?);
+        let sink = match compression {
+            None => Sink::Plain(file),
+            Some(compression) => {
+                let (chunks, queue) = mpsc::sync_channel(QUEUED_CHUNKS);
+                let encoder = thread::spawn(move || compression.compress(queue, file));
+                Sink::Compressed { chunks, encoder }
+            }
+        };
+        Ok(Self {
+            sink,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Writes out everything, waiting for the compression thread to finish.
+    pub fn finish(mut self) -> io::Result<()> {
+        match self.sink {
+            Sink::Plain(mut file) => file.flush(),
+            Sink::Compressed { chunks, encoder } => {
+                let sent = chunks.send(mem::take(&mut self.buffer));
+                drop(chunks);
+                let result = encoder
+                    .join()
+                    .unwrap_or_else(|_| Err(io::Error::other("compression thread panicked")));
+                result.and(sent.map_err(|_| io::ErrorKind::BrokenPipe.into()))
+            }
+        }
+    }
+}
+
+impl Write for OutputFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self.sink {
+            Sink::Plain(ref mut file) => file.write(buf),
+            Sink::Compressed { ref chunks, .. } => {
+                self.buffer.extend_from_slice(buf);
+                if self.buffer.len() >= COMPRESSION_CHUNK {
+                    // The thread only stops early on an error, which
+                    // `finish` reports.
+                    chunks
+                        .send(mem::take(&mut self.buffer))
+                        .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+                }
+                Ok(buf.len())
+            }
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.sink {
+            Sink::Plain(ref mut file) => file.flush(),
+            // Compressed output is only complete once finished.
+            Sink::Compressed { .. } => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TimePrecision {
+    Micro,
+    Nano,
+}
+
+impl TimePrecision {
+    /// Time since the epoch in units of this precision.
+    fn ticks(self, since_epoch: Duration) -> u64 {
+        match self {
+            TimePrecision::Micro => since_epoch.as_micros() as u64,
+            TimePrecision::Nano => since_epoch.as_nanos() as u64,
+        }
+    }
+}
+
+/// Writes frames as pcap or pcapng with any link type and precision, for
+/// converting between files.
+pub struct CaptureWriter<W: Write> {
+    writer: W,
+    format: OutputFormat,
+    precision: TimePrecision,
+    snaplen: u32,
+    link_types: Vec<u32>, // Of each pcapng interface, or of the pcap file
+}
+
+const PCAPNG_SECTION_HEADER: u32 = 0x0a0d_0d0a;
+const PCAPNG_INTERFACE_DESCRIPTION: u32 = 1;
+const PCAPNG_PACKET: u32 = 2; // Obsolete, but still read
+const PCAPNG_SIMPLE_PACKET: u32 = 3;
+const PCAPNG_ENHANCED_PACKET: u32 = 6;
+const PCAPNG_BYTE_ORDER: u32 = 0x1a2b_3c4d;
+const PCAPNG_OPT_COMMENT: u16 = 1;
+const PCAPNG_IF_TSRESOL: u16 = 9;
+/// Largest packet block read for its comments, the limit libpcap reads
+/// pcapng files with, so a corrupt length can't ask for gigabytes.
+const PCAPNG_MAX_BLOCK: usize = 16 * 1024 * 1024;
+/// Longest comment a pcapng option can hold; longer ones are cut to it.
+pub const MAX_COMMENT_LEN: usize = u16::MAX as usize;
+
+fn padding(len: usize) -> usize {
+    (4 - len % 4) % 4
+}
+
+impl<W: Write> CaptureWriter<W> {
+    /// Starts the file with the headers for `link_type` frames of at most
+    /// `snaplen` bytes; longer frames are truncated.
+    pub fn new(
+        mut writer: W,
+        format: OutputFormat,
+        precision: TimePrecision,
+        link_type: u32,
+        snaplen: u32,
+    ) -> io::Result<Self> {
+        match format {
+            OutputFormat::Pcap => {
+                let magic = match precision {
+                    TimePrecision::Micro => PCAP_MICROS,
+                    TimePrecision::Nano => PCAP_NANOS,
+                };
+                write_pcap_header(&mut writer, magic, snaplen, link_type)?;
+            }
+            OutputFormat::Pcapng => {
+                writer.write_all(&PCAPNG_SECTION_HEADER.to_le_bytes())?;
+                writer.write_all(&28u32.to_le_bytes())?;
+                writer.write_all(&PCAPNG_BYTE_ORDER.to_le_bytes())?;
+                writer.write_all(&1u16.to_le_bytes())?;
+                writer.write_all(&0u16.to_le_bytes())?;
+                writer.write_all(&(-1i64).to_le_bytes())?; // Section length unknown
+                writer.write_all(&28u32.to_le_bytes())?;
+            }
+        }
+        let mut capture_writer = Self {
+            writer,
+            format,
+            precision,
+            snaplen,
+            link_types: vec![link_type],
+        };
+        if format == OutputFormat::Pcapng {
+            capture_writer.write_interface(link_type)?;
+        }
+        Ok(capture_writer)
+    }
+
+    /// Writes a pcapng interface description block for `link_type` frames.
+    fn write_interface(&mut self, link_type: u32) -> io::Result<()> {
+        // Microseconds are the default resolution and need no option.
+        let options_len = match self.precision {
+            TimePrecision::Micro => 0,
+            TimePrecision::Nano => 12,
+        };
+        let block_len: u32 = 20 + options_len;
+        let writer = &mut self.writer;
+        writer.write_all(&PCAPNG_INTERFACE_DESCRIPTION.to_le_bytes())?;
+        writer.write_all(&block_len.to_le_bytes())?;
+        writer.write_all(&(link::file_link_type(link_type) as u16).to_le_bytes())?;
+        writer.write_all(&0u16.to_le_bytes())?;
+        writer.write_all(&self.snaplen.to_le_bytes())?;
+        if self.precision == TimePrecision::Nano {
+            writer.write_all(&PCAPNG_IF_TSRESOL.to_le_bytes())?;
+            writer.write_all(&1u16.to_le_bytes())?;
+            writer.write_all(&[9, 0, 0, 0])?;
+            writer.write_all(&[0; 4])?; // End of options
+        }
+        writer.write_all(&block_len.to_le_bytes())
+    }
+
+    /// Writes a frame that was `original_len` bytes on the wire.
+    pub fn write(&mut self, at: SystemTime, data: &[u8], original_len: u32) -> io::Result<()> {
+        self.write_block(0, at, data, original_len, None)
+    }
+
+    /// Writes a parsed packet with a comment, which only pcapng keeps. A
+    /// pcapng file gets an interface for each link type as it first
+    /// appears; a pcap file refuses frames of any other than its own.
+    pub fn write_packet(&mut self, packet: &PacketInfo, comment: Option<&str>) -> io::Result<()> {
+        let interface = match self
+            .link_types
+            .iter()
+            .position(|&link_type| link_type == packet.link_type)
+        {
+            Some(interface) => interface,
+            None if self.format == OutputFormat::Pcapng => {
+                self.write_interface(packet.link_type)?;
+                self.link_types.push(packet.link_type);
+                self.link_types.len() - 1
+            }
+            None => return Err(mixed_link_types(packet.link_type, self.link_types[0])),
+        };
+        self.write_block(
+            interface as u32,
+            packet.captured_at,
+            &packet.data,
+            packet.length as u32,
+            comment,
+        )
+    }
+
+    fn write_block(
+        &mut self,
+        interface: u32,
+        at: SystemTime,
+        data: &[u8],
+        original_len: u32,
+        comment: Option<&str>,
+    ) -> io::Result<()> {
+        let since_epoch = at.duration_since(UNIX_EPOCH).unwrap_or_default();
+        let data = &data[..data.len().min(self.snaplen as usize)];
+        let captured_len = data.len() as u32;
+        match self.format {
+            OutputFormat::Pcap => {
+                let fraction = match self.precision {
+                    TimePrecision::Micro => since_epoch.subsec_micros(),
+                    TimePrecision::Nano => since_epoch.subsec_nanos(),
+                };
+                self.writer
+                    .write_all(&(since_epoch.as_secs() as u32).to_le_bytes())?;
+                self.writer.write_all(&fraction.to_le_bytes())?;
+                self.writer.write_all(&captured_len.to_le_bytes())?;
+                self.writer.write_all(&original_len.to_le_bytes())?;
+                self.writer.write_all(data)
+            }
+            OutputFormat::Pcapng => {
+                let timestamp = self.precision.ticks(since_epoch);
+                let padding = padding(data.len());
+                let comment = comment.unwrap_or("");
+                let comment = &comment.as_bytes()[..comment.floor_char_boundary(MAX_COMMENT_LEN)];
+                // The comment option and the end of options after it.
+                let options_len = if comment.is_empty() {
+                    0
+                } else {
+                    4 + comment.len() + self::padding(comment.len()) + 4
+                };
+                let block_len = (32 + data.len() + padding + options_len) as u32;
+                self.writer
+                    .write_all(&PCAPNG_ENHANCED_PACKET.to_le_bytes())?;
+                self.writer.write_all(&block_len.to_le_bytes())?;
+                self.writer.write_all(&interface.to_le_bytes())?;
+                self.writer
+                    .write_all(&((timestamp >> 32) as u32).to_le_bytes())?;
+                self.writer.write_all(&(timestamp as u32).to_le_bytes())?;
+                self.writer.write_all(&captured_len.to_le_bytes())?;
+                self.writer.write_all(&original_len.to_le_bytes())?;
+                self.writer.write_all(data)?;
+                self.writer.write_all(&[0; 3][..padding])?;
+                if !comment.is_empty() {
+                    self.writer.write_all(&PCAPNG_OPT_COMMENT.to_le_bytes())?;
+                    self.writer
+                        .write_all(&(comment.len() as u16).to_le_bytes())?;
+                    self.writer.write_all(comment)?;
+                    self.writer
+                        .write_all(&[0; 3][..self::padding(comment.len())])?;
+                    self.writer.write_all(&[0; 4])?; // End of options
+                }
+                self.writer.write_all(&block_len.to_le_bytes())
+            }
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Writes `packets` to a new pcap file, returning how many were written.
+pub fn save<'a>(
+    path: &Path,
+    compression: Option<OutputCompression>,
+    packets: impl IntoIterator<Item = &'a PacketInfo>,
+) -> Result<usize> {
+    let mut writer = OutputFile::create(path, compression)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut count = 0;
+    let mut packets = packets.into_iter().peekable();
+    let write = || -> io::Result<()> {
+        let link_type = packets
+            .peek()
+            .map_or(LINKTYPE_ETHERNET, |packet| packet.link_type);
+        write_header(&mut writer, link_type)?;
+        for packet in packets {
+            if packet.link_type != link_type {
+                return Err(mixed_link_types(packet.link_type, link_type));
+            }
+            write_record(&mut writer, packet)?;
+            count += 1;
+        }
+        writer.finish()
+    };
+    write().with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(count)
+}
+
+/// Writes `packets` to a new pcapng file, each with its comment if it has
+/// one, returning how many were written.
+pub fn save_pcapng<'a>(
+    path: &Path,
+    compression: Option<OutputCompression>,
+    packets: impl IntoIterator<Item = (&'a PacketInfo, Option<&'a str>)>,
+) -> Result<usize> {
+    let file = OutputFile::create(path, compression)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut count = 0;
+    let mut packets = packets.into_iter().peekable();
+    let write = || -> io::Result<()> {
+        let link_type = packets
+            .peek()
+            .map_or(LINKTYPE_ETHERNET, |(packet, _)| packet.link_type);
+        let mut writer = CaptureWriter::new(
+            file,
+            OutputFormat::Pcapng,
+            TimePrecision::Micro,
+            link_type,
+            SNAPLEN,
+        )?;
+        for (packet, comment) in packets {
+            writer.write_packet(packet, comment)?;
+            count += 1;
+        }
+        writer.into_inner().finish()
+    };
+    write().with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(count)
+}
+
+/// Collects the comments of the packets in a pcapng stream into
+/// `comments`, by position. Packets are counted over the block types
+/// libpcap returns as packets, in the same order.
+fn scan_comments(reader: &mut impl Read, comments: &mut BTreeMap<usize, String>) -> io::Result<()> {
+    let mut big_endian = false;
+    let mut position = 0;
+    let mut header = [0u8; 8];
+    loop {
+        match reader.read_exact(&mut header) {
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            result => result?,
+        }
+        // The section header's type reads the same in either byte order,
+        // and the byte order magic after it tells which one follows.
+        if u32::from_le_bytes([header[0], header[1], header[2], header[3]]) == PCAPNG_SECTION_HEADER
+        {
+            let mut magic = [0u8; 4];
+            reader.read_exact(&mut magic)?;
+            big_endian = u32::from_be_bytes(magic) == PCAPNG_BYTE_ORDER;
+        }
+        let word = |bytes: &[u8]| {
+            let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+            if big_endian {
+                u32::from_be_bytes(bytes)
+            } else {
+                u32::from_le_bytes(bytes)
+            }
+        };
+        let block_type = word(&header[..4]);
+        let len = word(&header[4..]) as usize;
+        let read = if block_type == PCAPNG_SECTION_HEADER {
+            12
+        } else {
+            8
+        };
+        if len < read + 4 || !len.is_multiple_of(4) {
+            return Err(io::ErrorKind::InvalidData.into());
+        }
+        if !matches!(
+            block_type,
+            PCAPNG_PACKET | PCAPNG_ENHANCED_PACKET | PCAPNG_SIMPLE_PACKET
+        ) {
+            let rest = (len - read) as u64;
+            if io::copy(&mut reader.by_ref().take(rest), &mut io::sink())? < rest {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            continue;
+        }
+        if len > PCAPNG_MAX_BLOCK {
+            return Err(io::ErrorKind::InvalidData.into());
+        }
+        let mut body = vec![0; len - read];
+        reader.read_exact(&mut body)?;
+        // Packet and enhanced packet blocks share their layout up to the
+        // options: 20 bytes of header fields, then the padded frame.
+        if block_type != PCAPNG_SIMPLE_PACKET && body.len() >= 24 {
+            let captured = word(&body[12..16]) as usize;
+            let start = 20usize
+                .saturating_add(captured)
+                .saturating_add(padding(captured));
+            let mut options = body.get(start..body.len() - 4).unwrap_or(&[]);
+            while options.len() >= 4 {
+                let half = |bytes: [u8; 2]| {
+                    if big_endian {
+                        u16::from_be_bytes(bytes)
+                    } else {
+                        u16::from_le_bytes(bytes)
+                    }
+                };
+                let code = half([options[0], options[1]]);
+                let value_len = half([options[2], options[3]]) as usize;
+                let Some(value) = options.get(4..4 + value_len) else {
+                    break;
+                };
+                if code == 0 {
+                    break;
+                }
+                if code == PCAPNG_OPT_COMMENT {
+                    let text = String::from_utf8_lossy(value);
+                    comments
+                        .entry(position)
+                        .and_modify(|comment| {
+                            comment.push_str("; ");
+                            comment.push_str(&text);
+                        })
+                        .or_insert_with(|| text.into_owned());
+                }
+                let next = 4 + value_len + padding(value_len);
+                options = options.get(next..).unwrap_or(&[]);
+            }
+        }
+        position += 1;
+    }
+}
+
+/// The packet comments of a pcapng file by packet position, none for
+/// other formats. A damaged file yields the comments before the damage.
+fn read_comments(path: &Path) -> BTreeMap<usize, String> {
+    let mut comments = BTreeMap::new();
+    if !detect_format(path).starts_with("pcapng") {
+        return comments;
+    }
+    let Ok(file) = File::open(path) else {
+        return comments;
+    };
+    let _ = match Compression::detect(path).ok().flatten() {
+        Some(compression) => io::pipe().and_then(|(reader, mut writer)| {
+            thread::spawn(move || compression.decompress(file, &mut writer));
+            scan_comments(&mut BufReader::new(reader), &mut comments)
+        }),
+        None => scan_comments(&mut BufReader::new(file), &mut comments),
+    };
+    comments
+}
+
+/// Capture files are read by libpcap, so without it none can be opened.
+#[cfg(not(feature = "libpcap"))]
+pub fn open(path: &Path, _time_shift: TimeShift) -> Result<CaptureFile> {
+    anyhow::bail!("Built without libpcap, which reads {}", path.display())
+}
+
+/// Reads every packet of a pcap or pcapng file, correcting timestamps by
+/// `time_shift`.
+#[cfg(feature = "libpcap")]
+pub fn open(path: &Path, time_shift: TimeShift) -> Result<CaptureFile> {
+    let file_size = std::fs::metadata(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?
+        .len();
+    let mut cap = open_offline(path, Precision::Micro)?;
+
+    let datalink = cap.get_datalink();
+    let link_name = datalink
+        .get_name()
+        .unwrap_or_else(|_| "unknown".to_string());
+    let encapsulation = datalink
+        .get_description()
+        .unwrap_or_else(|_| "unknown".to_string());
+
+    let mut packets = Vec::new();
+    let mut data_bytes = 0;
+    let mut recorded_first = None;
+    let mut first = None;
+    let mut last = None;
+    while let Ok(packet) = cap.next_packet() {
+        let recorded = header_time(packet.header);
+        let captured_at = time_shift.apply(recorded, *recorded_first.get_or_insert(recorded))?;
+        let start = *first.get_or_insert(captured_at);
+        last = Some(captured_at);
+        data_bytes += packet.header.len as u64;
+
+        packets.push(parse_packet(
+            packets.len() + 1,
+            captured_at.duration_since(start).unwrap_or_default(),
+            captured_at,
+            datalink.0 as u32,
+            packet.data.into(),
+        ));
+    }
+
+    let summary = FileSummary {
+        path: path.to_path_buf(),
+        file_size,
+        format: detect_format(path),
+        link_type: format!("{link_name} ({})", datalink.0),
+        encapsulation,
+        packet_count: packets.len(),
+        data_bytes,
+        first,
+        last,
+        time_shift,
+        merged: None,
+    };
+    Ok(CaptureFile {
+        packets,
+        comments: read_comments(path),
+        summary,
+    })
+}
@@ -0,0 +1,186 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+
+use super::{
+    capture_file::OutputCompression, filter_history::config_dir, live::SNAPLEN, ring::RingOptions,
+};
+
+/// File of the config directory the options are kept in.
+const FILE_NAME: &str = "capture.conf";
+
+/// What reads frames from local devices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Backend {
+    #[cfg_attr(feature = "libpcap", default)]
+    Libpcap,
+    /// Linux packet sockets with a TPACKET_V3 ring, read without copying
+    #[cfg_attr(not(feature = "libpcap"), default)]
+    AfPacket,
+}
+
+impl Backend {
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::Libpcap => "libpcap",
+            Backend::AfPacket => "af-packet",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        [Backend::Libpcap, Backend::AfPacket]
+            .into_iter()
+            .find(|backend| backend.name() == value)
+    }
+}
+
+/// How libpcap opens devices for a live capture, and the ring of files it is
+/// also written to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureOptions {
+    /// Also take frames addressed to other hosts
+    pub promisc: bool,
+    /// Bytes kept of each frame
+    pub snaplen: u32,
+    /// Kernel buffer in MiB; None keeps libpcap's default
+    pub buffer_size: Option<u32>,
+    /// Milliseconds libpcap waits to fill its buffer before handing it over
+    pub timeout: u32,
+    /// Hand over each frame as it arrives rather than a buffer at a time
+    pub immediate: bool,
+    /// Put Wi-Fi interfaces in monitor mode, taking raw 802.11 frames from
+    /// every network in range; chosen per device, so not saved
+    pub monitor: bool,
+    /// BPF capture filter attached as each device opens, before any frame
+    /// is read; empty to take every frame. Set per capture, so not saved
+    pub filter: String,
+    /// What opens local devices; rpcap and demo captures ignore it
+    pub backend: Backend,
+    /// Also write live captures to a ring of pcap files named after this
+    pub ring_path: Option<PathBuf>,
+    /// Megabytes a ring file may reach before the next one is started
+    pub ring_size: u64,
+    /// Seconds a ring file may span before the next one is started
+    pub ring_seconds: Option<u64>,
+    /// Ring files kept, the oldest deleted first; 0 keeps them all
+    pub ring_files: usize,
+}
+
+impl Default for CaptureOptions {
+    fn default() -> Self {
+        Self {
+            promisc: true,
+            snaplen: SNAPLEN,
+            buffer_size: None,
+            timeout: 100,
+            immediate: false,
+            monitor: false,
+            filter: String::new(),
+            backend: Backend::default(),
+            ring_path: None,
+            ring_size: 100,
+            ring_seconds: None,
+            ring_files: 10,
+        }
+    }
+}
+
+fn path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(FILE_NAME))
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+impl CaptureOptions {
+    /// Reads the options saved in the config directory, one `key = value`
+    /// per line. Keys missing from the file, unknown or with a bad value
+    /// keep their defaults.
+    pub fn load() -> Self {
+        let mut options = Self::default();
+        let Some(text) = path().and_then(|path| fs::read_to_string(path).ok()) else {
+            return options;
+        };
+        for (key, value) in text.lines().filter_map(|line| line.split_once('=')) {
+            let value = value.trim();
+            match key.trim() {
+                "promisc" => options.promisc = parse_bool(value).unwrap_or(options.promisc),
+                "snaplen" => {
+                    options.snaplen = value
+                        .parse()
+                        .ok()
+                        .filter(|&snaplen| snaplen > 0)
+                        .unwrap_or(options.snaplen);
+                }
+                "buffer_size" => {
+                    options.buffer_size = value.parse().ok().filter(|&size| size > 0);
+                }
+                "timeout" => options.timeout = value.parse().unwrap_or(options.timeout),
+                "immediate" => options.immediate = parse_bool(value).unwrap_or(options.immediate),
+                "backend" => {
+                    options.backend = Backend::parse(value).unwrap_or(options.backend);
+                }
+                "ring" => options.ring_path = (!value.is_empty()).then(|| PathBuf::from(value)),
+                "ring_size" => {
+                    options.ring_size = value
+                        .parse()
+                        .ok()
+                        .filter(|&size| size > 0)
+                        .unwrap_or(options.ring_size);
+                }
+                "ring_seconds" => {
+                    options.ring_seconds = value.parse().ok().filter(|&seconds| seconds > 0);
+                }
+                "ring_files" => options.ring_files = value.parse().unwrap_or(options.ring_files),
+                _ => {}
+            }
+        }
+        options
+    }
+
+    /// The ring of files live captures are also written to, if one is set.
+    pub fn ring_options(&self, compression: Option<OutputCompression>) -> Option<RingOptions> {
+        Some(RingOptions {
+            path: self.ring_path.clone()?,
+            max_bytes: Some(self.ring_size * 1024 * 1024),
+            max_duration: self.ring_seconds.map(Duration::from_secs),
+            files: self.ring_files,
+            compression,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(path) = path() else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let text = format!(
+            "promisc = {}\nsnaplen = {}\nbuffer_size = {}\ntimeout = {}\nimmediate = {}\n\
+             backend = {}\nring = {}\nring_size = {}\nring_seconds = {}\n\
+             ring_files = {}\n",
+            self.promisc,
+            self.snaplen,
+            self.buffer_size.unwrap_or(0),
+            self.timeout,
+            self.immediate,
+            self.backend.name(),
+            self.ring_path
+                .as_deref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+            self.ring_size,
+            self.ring_seconds.unwrap_or(0),
+            self.ring_files
+        );
+        fs::write(&path, text).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
@@ -0,0 +1,83 @@
+use std::net::IpAddr;
+
+/// A header whose checksum doesn't match its contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BadChecksum {
+    pub header: &'static str,
+    pub found: u16,
+    pub expected: u16,
+}
+
+/// Sums `data` as big-endian 16-bit words, a trailing odd byte padded with
+/// zero, onto `sum`.
+fn add(mut sum: u32, data: &[u8]) -> u32 {
+    let mut words = data.chunks_exact(2);
+    for word in &mut words {
+        sum += u32::from(u16::from_be_bytes([word[0], word[1]]));
+    }
+    if let [last] = words.remainder() {
+        sum += u32::from(*last) << 8;
+    }
+    sum
+}
+
+/// The ones' complement of the ones' complement sum.
+fn fold(mut sum: u32) -> u16 {
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+/// Checks the checksum at `offset` in `data`, `sum` holding the
+/// pseudo-header if there is one.
+fn check(header: &'static str, sum: u32, data: &[u8], offset: usize) -> Option<BadChecksum> {
+    let found = u16::from_be_bytes([*data.get(offset)?, *data.get(offset + 1)?]);
+    let sum = add(add(sum, &data[..offset]), &data[offset + 2..]);
+    let expected = match fold(sum) {
+        // UDP sends a computed zero as all ones, zero meaning no checksum.
+        0 if header == "UDP" => 0xffff,
+        expected => expected,
+    };
+    (found != expected).then_some(BadChecksum {
+        header,
+        found,
+        expected,
+    })
+}
+
+/// Verifies the checksum of an IPv4 header.
+pub fn ipv4(header: &[u8]) -> Option<BadChecksum> {
+    check("IPv4", 0, header, 10)
+}
+
+/// Verifies the checksum of a TCP, UDP, ICMP or ICMPv6 `segment` carried
+/// from `src` to `dst` as IP protocol `protocol`. Other protocols, and UDP
+/// over IPv4 sent without a checksum, pass.
+pub fn transport(src: IpAddr, dst: IpAddr, protocol: u8, segment: &[u8]) -> Option<BadChecksum> {
+    let (header, offset) = match protocol {
+        1 => ("ICMPv4", 2),
+        6 => ("TCP", 16),
+        17 => ("UDP", 6),
+        58 => ("ICMPv6", 2),
+        _ => return None,
+    };
+    let pseudo = match (src, dst) {
+        // ICMP for IPv4 covers the message alone.
+        _ if protocol == 1 => 0,
+        (IpAddr::V4(src), IpAddr::V4(dst)) => {
+            if protocol == 17 && segment.get(6..8) == Some(&[0, 0]) {
+                return None;
+            }
+            let sum = add(add(0, &src.octets()), &dst.octets());
+            sum + u32::from(protocol) + segment.len() as u32
+        }
+        (IpAddr::V6(src), IpAddr::V6(dst)) => {
+            let sum = add(add(0, &src.octets()), &dst.octets());
+            let len = segment.len() as u32;
+            sum + (len >> 16) + (len & 0xffff) + u32::from(protocol)
+        }
+        _ => return None,
+    };
+    check(header, pseudo, segment, offset)
+}
@@ -0,0 +1,231 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+use super::{
+    flow::{Endpoint, FlowKey},
+    packet::{PacketInfo, Transport},
+};
+
+/// Traffic sent in one direction of a conversation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DirectionStats {
+    pub packets: usize,
+    pub bytes: usize,
+}
+
+/// All packets exchanged between the two endpoints of a flow.
+#[derive(Debug, Clone)]
+pub struct Conversation {
+    pub key: FlowKey,
+    pub a_to_b: DirectionStats,
+    pub b_to_a: DirectionStats,
+    pub first: SystemTime,
+    pub last: SystemTime,
+}
+
+impl Conversation {
+    pub fn new(key: FlowKey, first: SystemTime) -> Self {
+        Self {
+            key,
+            a_to_b: DirectionStats::default(),
+            b_to_a: DirectionStats::default(),
+            first,
+            last: first,
+        }
+    }
+
+    /// Whether `packet`, which must belong to this conversation, was sent by
+    /// endpoint A.
+    pub fn is_from_a(&self, packet: &PacketInfo) -> bool {
+        packet.src_addr.as_ref() == Some(&self.key.a.addr) && packet.src_port == self.key.a.port
+    }
+
+    /// Counts `packet`, which must belong to this conversation.
+    pub fn add(&mut self, packet: &PacketInfo) {
+        let direction = if self.is_from_a(packet) {
+            &mut self.a_to_b
+        } else {
+            &mut self.b_to_a
+        };
+        direction.packets += 1;
+        direction.bytes += packet.length;
+        self.last = packet.captured_at;
+    }
+
+    pub fn packets(&self) -> usize {
+        self.a_to_b.packets + self.b_to_a.packets
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.a_to_b.bytes + self.b_to_a.bytes
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.last.duration_since(self.first).unwrap_or_default()
+    }
+
+    /// Average rate in bits per second, or `None` for a single instant.
+    pub fn throughput(&self, direction: &DirectionStats) -> Option<f64> {
+        let seconds = self.duration().as_secs_f64();
+        (seconds > 0.0).then(|| direction.bytes as f64 * 8.0 / seconds)
+    }
+
+    /// A display filter matching exactly this conversation in both directions.
+    pub fn display_filter(&self) -> String {
+        let (transport, address) = match self.key.transport {
+            Some(Transport::Tcp) => (Some("tcp"), "ip"),
+            Some(Transport::Udp) => (Some("udp"), "ip"),
+            Some(Transport::Icmpv4) => (Some("icmp"), "ip"),
+            Some(Transport::Icmpv6) => (Some("icmpv6"), "ip"),
+            None if self.key.a.addr.is_ok() => (None, "ip"),
+            None => (None, "eth"),
+        };
+        let side = |src: &Endpoint, dst: &Endpoint| {
+            let addr = |endpoint: &Endpoint| match &endpoint.addr {
+                Ok(ip) => ip.to_string(),
+                Err(mac) => mac.clone(),
+            };
+            let mut terms = vec![
+                format!("{address}.src == {}", addr(src)),
+                format!("{address}.dst == {}", addr(dst)),
+            ];
+            if let (Some(proto), Some(src_port), Some(dst_port)) = (transport, src.port, dst.port) {
+                terms.push(format!("{proto}.srcport == {src_port}"));
+                terms.push(format!("{proto}.dstport == {dst_port}"));
+            }
+            terms.join(" and ")
+        };
+        let filter = format!(
+            "({}) or ({})",
+            side(&self.key.a, &self.key.b),
+            side(&self.key.b, &self.key.a)
+        );
+        match transport {
+            Some(proto) => format!("{proto} and ({filter})"),
+            None => filter,
+        }
+    }
+}
+
+/// Columns the conversations page can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortColumn {
+    Protocol,
+    AddressA,
+    AddressB,
+    PacketsAToB,
+    BytesAToB,
+    PacketsBToA,
+    BytesBToA,
+    Packets,
+    #[default]
+    Bytes,
+    Duration,
+    ThroughputAToB,
+    ThroughputBToA,
+}
+
+impl SortColumn {
+    pub const ALL: [SortColumn; 12] = [
+        SortColumn::Protocol,
+        SortColumn::AddressA,
+        SortColumn::AddressB,
+        SortColumn::PacketsAToB,
+        SortColumn::BytesAToB,
+        SortColumn::PacketsBToA,
+        SortColumn::BytesBToA,
+        SortColumn::Packets,
+        SortColumn::Bytes,
+        SortColumn::Duration,
+        SortColumn::ThroughputAToB,
+        SortColumn::ThroughputBToA,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            SortColumn::Protocol => "Proto",
+            SortColumn::AddressA => "Address A",
+            SortColumn::AddressB => "Address B",
+            SortColumn::PacketsAToB => "Pkts A→B",
+            SortColumn::BytesAToB => "Bytes A→B",
+            SortColumn::PacketsBToA => "Pkts B→A",
+            SortColumn::BytesBToA => "Bytes B→A",
+            SortColumn::Packets => "Packets",
+            SortColumn::Bytes => "Bytes",
+            SortColumn::Duration => "Duration",
+            SortColumn::ThroughputAToB => "bps A→B",
+            SortColumn::ThroughputBToA => "bps B→A",
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|&c| c == self).unwrap_or(0)
+    }
+
+    pub fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConversationStats {
+    pub conversations: Vec<Conversation>,
+    index: HashMap<FlowKey, usize>,
+}
+
+impl ConversationStats {
+    pub fn from_packets(packets: &[&PacketInfo]) -> Self {
+        let mut stats = Self::default();
+        for packet in packets {
+            stats.add(packet);
+        }
+        stats
+    }
+
+    pub fn add(&mut self, packet: &PacketInfo) {
+        let Some(key) = FlowKey::from_packet(packet) else {
+            return;
+        };
+        let index = *self.index.entry(key.clone()).or_insert_with(|| {
+            self.conversations
+                .push(Conversation::new(key, packet.captured_at));
+            self.conversations.len() - 1
+        });
+        self.conversations[index].add(packet);
+    }
+
+    /// Sorts by `column`; numeric columns put the largest first unless
+    /// `reversed`, text columns sort alphabetically.
+    pub fn sort(&mut self, column: SortColumn, reversed: bool) {
+        let rate = |c: &Conversation, d: &DirectionStats| c.throughput(d).unwrap_or(0.0);
+        self.conversations.sort_by(|x, y| {
+            let order = match column {
+                SortColumn::Protocol => x.key.to_string().cmp(&y.key.to_string()),
+                SortColumn::AddressA => x.key.a.cmp(&y.key.a),
+                SortColumn::AddressB => x.key.b.cmp(&y.key.b),
+                SortColumn::PacketsAToB => y.a_to_b.packets.cmp(&x.a_to_b.packets),
+                SortColumn::BytesAToB => y.a_to_b.bytes.cmp(&x.a_to_b.bytes),
+                SortColumn::PacketsBToA => y.b_to_a.packets.cmp(&x.b_to_a.packets),
+                SortColumn::BytesBToA => y.b_to_a.bytes.cmp(&x.b_to_a.bytes),
+                SortColumn::Packets => y.packets().cmp(&x.packets()),
+                SortColumn::Bytes => y.bytes().cmp(&x.bytes()),
+                SortColumn::Duration => y.duration().cmp(&x.duration()),
+                SortColumn::ThroughputAToB => rate(y, &y.a_to_b).total_cmp(&rate(x, &x.a_to_b)),
+                SortColumn::ThroughputBToA => rate(y, &y.b_to_a).total_cmp(&rate(x, &x.b_to_a)),
+            };
+            if reversed { order.reverse() } else { order }
+        });
+        self.index = self
+            .conversations
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.key.clone(), i))
+            .collect();
+    }
+}
@@ -0,0 +1,393 @@
+use std::{
+    collections::VecDeque,
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(feature = "libpcap")]
+use anyhow::Context;
+use anyhow::Result;
+use etherparse::PacketBuilder;
+#[cfg(feature = "libpcap")]
+use pcap::{BpfProgram, Capture, Linktype};
+
+use super::{
+    capture_file::LINKTYPE_ETHERNET,
+    live::{CaptureBackend, Stat},
+};
+
+/// Name of the pseudo-device that synthesizes traffic instead of capturing.
+pub const DEVICE: &str = "demo";
+
+/// Longest wait for the next frame, like the read timeout of a live handle,
+/// so stopping takes effect quickly.
+const POLL_TIMEOUT: Duration = Duration::from_millis(100);
+
+const BROADCAST: [u8; 6] = [0xff; 6];
+const GATEWAY: Host = Host {
+    mac: [0x02, 0x00, 0x5e, 0x00, 0x00, 0x01],
+    ip: [192, 168, 1, 1],
+};
+const CLIENTS: [Host; 3] = [
+    Host {
+        mac: [0x02, 0x00, 0x5e, 0x00, 0x00, 0x10],
+        ip: [192, 168, 1, 10],
+    },
+    Host {
+        mac: [0x02, 0x00, 0x5e, 0x00, 0x00, 0x11],
+        ip: [192, 168, 1, 11],
+    },
+    Host {
+        mac: [0x02, 0x00, 0x5e, 0x00, 0x00, 0x12],
+        ip: [192, 168, 1, 12],
+    },
+];
+/// Web sites the clients visit, at documentation addresses.
+const SITES: [(&str, [u8; 4]); 4] = [
+    ("example.com", [203, 0, 113, 10]),
+    ("www.example.org", [203, 0, 113, 25]),
+    ("api.example.net", [198, 51, 100, 7]),
+    ("cdn.example.com", [198, 51, 100, 80]),
+];
+const PAGES: [&str; 4] = ["/", "/index.html", "/api/v1/status", "/assets/app.js"];
+
+#[derive(Clone, Copy)]
+struct Host {
+    mac: [u8; 6],
+    ip: [u8; 4],
+}
+
+/// A frame and how long after the previous one it goes out.
+type Timed = (Duration, Vec<u8>);
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+/// A small xorshift generator; the demo needs variety, not quality.
+struct Rng(u64);
+
+impl Rng {
+    fn new() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+        Self(seed | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n.max(1)
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
+
+fn arp(operation: u16, sender: Host, target_mac: [u8; 6], target_ip: [u8; 4]) -> Vec<u8> {
+    let destination = if operation == 1 {
+        BROADCAST
+    } else {
+        target_mac
+    };
+    let mut frame = Vec::with_capacity(42);
+    frame.extend_from_slice(&destination);
+    frame.extend_from_slice(&sender.mac);
+    frame.extend_from_slice(&[0x08, 0x06, 0x00, 0x01, 0x08, 0x00, 6, 4]);
+    frame.extend_from_slice(&operation.to_be_bytes());
+    frame.extend_from_slice(&sender.mac);
+    frame.extend_from_slice(&sender.ip);
+    frame.extend_from_slice(&target_mac);
+    frame.extend_from_slice(&target_ip);
+    frame
+}
+
+fn dns_message(id: u16, name: &str, answer: Option<[u8; 4]>) -> Vec<u8> {
+    let flags: u16 = if answer.is_some() { 0x8180 } else { 0x0100 };
+    let mut message = Vec::new();
+    message.extend_from_slice(&id.to_be_bytes());
+    message.extend_from_slice(&flags.to_be_bytes());
+    message.extend_from_slice(&[0, 1, 0, answer.is_some() as u8, 0, 0, 0, 0]);
+    for label in name.split('.') {
+        message.push(label.len() as u8);
+        message.extend_from_slice(label.as_bytes());
+    }
+    message.extend_from_slice(&[0, 0, 1, 0, 1]); // Root, type A, class IN
+    if let Some(addr) = answer {
+        // Name pointer to the question, type A, class IN, TTL 300.
+        message.extend_from_slice(&[0xc0, 0x0c, 0, 1, 0, 1, 0, 0, 0x01, 0x2c, 0, 4]);
+        message.extend_from_slice(&addr);
+    }
+    message
+}
+
+fn udp(from: (Host, [u8; 6]), to: [u8; 4], ports: (u16, u16), payload: &[u8]) -> Vec<u8> {
+    let (host, next_hop) = from;
+    let mut frame = Vec::new();
+    let _ = PacketBuilder::ethernet2(host.mac, next_hop)
+        .ipv4(host.ip, to, 64)
+        .udp(ports.0, ports.1)
+        .write(&mut frame, payload);
+    frame
+}
+
+/// One direction of a TCP connection between a client and a server behind
+/// the gateway.
+struct TcpSide {
+    mac: [u8; 6],
+    next_hop: [u8; 6],
+    ip: [u8; 4],
+    port: u16,
+    seq: u32,
+}
+
+#[derive(Clone, Copy)]
+enum Flags {
+    Syn,
+    SynAck,
+    Ack,
+    Push,
+    Fin,
+}
+
+fn tcp(from: &mut TcpSide, to: &TcpSide, flags: Flags, payload: &[u8]) -> Vec<u8> {
+    let builder = PacketBuilder::ethernet2(from.mac, from.next_hop)
+        .ipv4(from.ip, to.ip, 64)
+        .tcp(from.port, to.port, from.seq, 64_240);
+    let builder = match flags {
+        Flags::Syn => builder.syn(),
+        Flags::SynAck => builder.syn().ack(to.seq),
+        Flags::Ack => builder.ack(to.seq),
+        Flags::Push => builder.psh().ack(to.seq),
+        Flags::Fin => builder.fin().ack(to.seq),
+    };
+    let mut frame = Vec::new();
+    let _ = builder.write(&mut frame, payload);
+    from.seq = from.seq.wrapping_add(payload.len() as u32);
+    if matches!(flags, Flags::Syn | Flags::SynAck | Flags::Fin) {
+        from.seq = from.seq.wrapping_add(1);
+    }
+    frame
+}
+
+/// Synthesized traffic of a small LAN: clients resolving names, fetching
+/// pages over HTTP with the odd retransmission, and ARP, produced at a
+/// realistic pace and filtered like a live capture.
+pub struct DemoCapture {
+    rng: Rng,
+    pending: VecDeque<Timed>,
+    last_sent: Instant,
+    frame: Vec<u8>,
+    #[cfg(feature = "libpcap")]
+    program: Option<BpfProgram>,
+    stats: Stat,
+    next_port: u16,
+    next_id: u16,
+}
+
+impl Default for DemoCapture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DemoCapture {
+    pub fn new() -> Self {
+        let mut rng = Rng::new();
+        let next_port = 49_152 + rng.below(10_000) as u16;
+        let next_id = rng.next() as u16;
+        Self {
+            rng,
+            pending: VecDeque::new(),
+            last_sent: Instant::now(),
+            frame: Vec::new(),
+            #[cfg(feature = "libpcap")]
+            program: None,
+            stats: Stat {
+                received: 0,
+                dropped: 0,
+                if_dropped: 0,
+            },
+            next_port,
+            next_id,
+        }
+    }
+
+    /// Queues the frames of the next exchange, after a quiet spell.
+    fn schedule(&mut self) {
+        let mut frames = match self.rng.below(10) {
+            0 | 1 => self.arp_exchange(),
+            2..=4 => self.dns_lookup().0,
+            _ => self.page_fetch(),
+        };
+        if let Some(first) = frames.first_mut() {
+            first.0 += ms(20 + self.rng.below(400));
+        }
+        self.pending.extend(frames);
+    }
+
+    fn arp_exchange(&mut self) -> Vec<Timed> {
+        let client = *self.rng.pick(&CLIENTS);
+        let (asker, target) = if self.rng.below(2) == 0 {
+            (client, GATEWAY)
+        } else {
+            (GATEWAY, client)
+        };
+        vec![
+            (Duration::ZERO, arp(1, asker, [0; 6], target.ip)),
+            (
+                ms(1 + self.rng.below(5)),
+                arp(2, target, asker.mac, asker.ip),
+            ),
+        ]
+    }
+
+    /// A client asking the gateway for a site's address; returns the
+    /// frames, the client and the site.
+    fn dns_lookup(&mut self) -> (Vec<Timed>, Host, (&'static str, [u8; 4])) {
+        let client = *self.rng.pick(&CLIENTS);
+        let (name, addr) = *self.rng.pick(&SITES);
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        let port = self.ephemeral_port();
+        let query = dns_message(id, name, None);
+        let response = dns_message(id, name, Some(addr));
+        let frames = vec![
+            (
+                Duration::ZERO,
+                udp((client, GATEWAY.mac), GATEWAY.ip, (port, 53), &query),
+            ),
+            (
+                ms(2 + self.rng.below(40)),
+                udp((GATEWAY, client.mac), client.ip, (53, port), &response),
+            ),
+        ];
+        (frames, client, (name, addr))
+    }
+
+    /// A name lookup followed by an HTTP request on a new connection, whose
+    /// response is sometimes retransmitted.
+    fn page_fetch(&mut self) -> Vec<Timed> {
+        let (mut frames, client, (name, addr)) = self.dns_lookup();
+        let rtt = 10 + self.rng.below(60);
+        let mut local = TcpSide {
+            mac: client.mac,
+            next_hop: GATEWAY.mac,
+            ip: client.ip,
+            port: self.ephemeral_port(),
+            seq: self.rng.next() as u32,
+        };
+        let mut remote = TcpSide {
+            mac: GATEWAY.mac,
+            next_hop: client.mac,
+            ip: addr,
+            port: 80,
+            seq: self.rng.next() as u32,
+        };
+        let path = *self.rng.pick(&PAGES);
+        let request = format!(
+            "GET {path} HTTP/1.1\r\nHost: {name}\r\nUser-Agent: sniffer-demo\r\n\
+             Accept: */*\r\n\r\n"
+        );
+        let body = "<html><body>Hello from the demo</body></html>\n"
+            .repeat(1 + self.rng.below(20) as usize);
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        );
+
+        frames.push((ms(1), tcp(&mut local, &remote, Flags::Syn, &[])));
+        frames.push((ms(rtt), tcp(&mut remote, &local, Flags::SynAck, &[])));
+        frames.push((ms(1), tcp(&mut local, &remote, Flags::Ack, &[])));
+        frames.push((
+            Duration::ZERO,
+            tcp(&mut local, &remote, Flags::Push, request.as_bytes()),
+        ));
+        frames.push((ms(1), tcp(&mut remote, &local, Flags::Ack, &[])));
+        let data = tcp(&mut remote, &local, Flags::Push, response.as_bytes());
+        frames.push((ms(rtt / 2 + self.rng.below(50)), data.clone()));
+        if self.rng.below(5) == 0 {
+            // Lost on the way; the server sends it again after its timeout.
+            frames.push((ms(200 + rtt * 2), data));
+        }
+        frames.push((ms(rtt / 2), tcp(&mut local, &remote, Flags::Ack, &[])));
+        frames.push((ms(5), tcp(&mut local, &remote, Flags::Fin, &[])));
+        frames.push((ms(rtt), tcp(&mut remote, &local, Flags::Fin, &[])));
+        frames.push((ms(1), tcp(&mut local, &remote, Flags::Ack, &[])));
+        frames
+    }
+
+    fn ephemeral_port(&mut self) -> u16 {
+        let port = self.next_port;
+        self.next_port = if port == u16::MAX { 49_152 } else { port + 1 };
+        port
+    }
+}
+
+impl CaptureBackend for DemoCapture {
+    /// Compiles `filter` for Ethernet and drops frames it doesn't match.
+    #[cfg(feature = "libpcap")]
+    fn filter(&mut self, filter: &str) -> Result<()> {
+        let program = Capture::dead(Linktype::ETHERNET)?
+            .compile(filter, true)
+            .with_context(|| format!("Invalid capture filter {filter}"))?;
+        self.program = Some(program);
+        Ok(())
+    }
+
+    /// Without libpcap nothing compiles filters, so only the empty one,
+    /// which keeps every frame, is taken.
+    #[cfg(not(feature = "libpcap"))]
+    fn filter(&mut self, filter: &str) -> Result<()> {
+        if !filter.trim().is_empty() {
+            anyhow::bail!("Built without libpcap, so the demo capture can't be filtered");
+        }
+        Ok(())
+    }
+
+    fn stats(&mut self) -> Option<Stat> {
+        Some(self.stats)
+    }
+
+    /// The next frame once it is due, stamped with the time it went out,
+    /// waiting up to `POLL_TIMEOUT` for it.
+    fn next_packet(&mut self) -> Option<(SystemTime, &[u8])> {
+        if self.pending.is_empty() {
+            self.schedule();
+        }
+        let &(gap, _) = self.pending.front()?;
+        let due = self.last_sent + gap;
+        let now = Instant::now();
+        if due > now {
+            thread::sleep((due - now).min(POLL_TIMEOUT));
+            if due > Instant::now() {
+                return None;
+            }
+        }
+        let (_, frame) = self.pending.pop_front()?;
+        // Catching up after a slow consumer would send a burst instead.
+        let now = Instant::now();
+        self.last_sent = due.max(now.checked_sub(POLL_TIMEOUT).unwrap_or(now));
+        self.stats.received = self.stats.received.wrapping_add(1);
+        #[cfg(feature = "libpcap")]
+        if let Some(ref program) = self.program
+            && !program.filter(&frame)
+        {
+            return None;
+        }
+        self.frame = frame;
+        Some((SystemTime::now(), &self.frame))
+    }
+
+    fn link_type(&self) -> u32 {
+        LINKTYPE_ETHERNET
+    }
+}
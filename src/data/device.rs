@@ -0,0 +1,169 @@
+use std::net::IpAddr;
+
+use anyhow::Result;
+
+/// What the system says about an interface's state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Flags {
+    pub up: bool,
+    pub running: bool,
+    pub loopback: bool,
+    pub wireless: bool,
+    pub connected: Option<bool>, // None where it doesn't apply or isn't known
+}
+
+/// An address of an interface, with the netmask and the broadcast or
+/// point-to-point peer address that go with it where known.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Address {
+    pub addr: IpAddr,
+    pub netmask: Option<IpAddr>,
+    pub broadcast_addr: Option<IpAddr>,
+    pub dst_addr: Option<IpAddr>,
+}
+
+/// An interface live captures can be opened on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Device {
+    pub name: String,
+    pub desc: Option<String>,
+    pub addresses: Vec<Address>,
+    pub flags: Flags,
+}
+
+impl From<&str> for Device {
+    fn from(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            desc: None,
+            addresses: Vec::new(),
+            flags: Flags::default(),
+        }
+    }
+}
+
+#[cfg(feature = "libpcap")]
+impl From<pcap::Device> for Device {
+    fn from(device: pcap::Device) -> Self {
+        let flags = &device.flags;
+        let connected = match flags.connection_status {
+            pcap::ConnectionStatus::Connected => Some(true),
+            pcap::ConnectionStatus::Disconnected => Some(false),
+            pcap::ConnectionStatus::Unknown | pcap::ConnectionStatus::NotApplicable => None,
+        };
+        Self {
+            flags: Flags {
+                up: flags.is_up(),
+                running: flags.is_running(),
+                loopback: flags.is_loopback(),
+                wireless: flags.is_wireless(),
+                connected,
+            },
+            addresses: device
+                .addresses
+                .iter()
+                .map(|address| Address {
+                    addr: address.addr,
+                    netmask: address.netmask,
+                    broadcast_addr: address.broadcast_addr,
+                    dst_addr: address.dst_addr,
+                })
+                .collect(),
+            name: device.name,
+            desc: device.desc,
+        }
+    }
+}
+
+impl Device {
+    /// Every device libpcap can capture on.
+    #[cfg(feature = "libpcap")]
+    pub fn list() -> Result<Vec<Self>> {
+        Ok(pcap::Device::list()?.into_iter().map(Self::from).collect())
+    }
+
+    /// The device libpcap captures on when none is named.
+    #[cfg(feature = "libpcap")]
+    pub fn lookup() -> Result<Option<Self>> {
+        Ok(pcap::Device::lookup()?.map(Self::from))
+    }
+
+    /// Every interface getifaddrs(3) reports, in its order. Broadcast and
+    /// peer addresses are left out.
+    #[cfg(not(feature = "libpcap"))]
+    pub fn list() -> Result<Vec<Self>> {
+        let mut first = std::ptr::null_mut();
+        // SAFETY: the list is freed below.
+        if unsafe { libc::getifaddrs(&mut first) } != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        let mut devices: Vec<Self> = Vec::new();
+        let mut next = first;
+        // SAFETY: the list stays allocated until freeifaddrs, and each
+        // entry's name is NUL-terminated and its address null or of the
+        // family it says.
+        while let Some(entry) = unsafe { next.as_ref() } {
+            next = entry.ifa_next;
+            let name = unsafe { std::ffi::CStr::from_ptr(entry.ifa_name) }.to_string_lossy();
+            let index = match devices.iter().position(|device| device.name == name) {
+                Some(index) => index,
+                None => {
+                    let flags = entry.ifa_flags as libc::c_int;
+                    devices.push(Self {
+                        flags: Flags {
+                            up: flags & libc::IFF_UP != 0,
+                            running: flags & libc::IFF_RUNNING != 0,
+                            loopback: flags & libc::IFF_LOOPBACK != 0,
+                            wireless: super::live::is_wireless(&name),
+                            connected: None,
+                        },
+                        ..Self::from(&*name)
+                    });
+                    devices.len() - 1
+                }
+            };
+            if let Some(addr) = unsafe { ip_addr(entry.ifa_addr) } {
+                devices[index].addresses.push(Address {
+                    addr,
+                    netmask: unsafe { ip_addr(entry.ifa_netmask) },
+                    broadcast_addr: None,
+                    dst_addr: None,
+                });
+            }
+        }
+        // SAFETY: `first` came from getifaddrs and is freed once.
+        unsafe { libc::freeifaddrs(first) };
+        Ok(devices)
+    }
+
+    /// The first interface that is up and not loopback.
+    #[cfg(not(feature = "libpcap"))]
+    pub fn lookup() -> Result<Option<Self>> {
+        Ok(Self::list()?
+            .into_iter()
+            .find(|device| device.flags.up && device.flags.running && !device.flags.loopback))
+    }
+}
+
+/// The IP address in `addr`, if it holds one.
+///
+/// # Safety
+///
+/// `addr` must be null or point at a socket address of the family it says.
+#[cfg(not(feature = "libpcap"))]
+unsafe fn ip_addr(addr: *const libc::sockaddr) -> Option<IpAddr> {
+    // SAFETY: guaranteed by the caller.
+    unsafe {
+        match libc::c_int::from(addr.as_ref()?.sa_family) {
+            libc::AF_INET => {
+                let addr = &*addr.cast::<libc::sockaddr_in>();
+                Some(IpAddr::from(addr.sin_addr.s_addr.to_ne_bytes()))
+            }
+            libc::AF_INET6 => {
+                let addr = &*addr.cast::<libc::sockaddr_in6>();
+                Some(IpAddr::from(addr.sin6_addr.s6_addr))
+            }
+            _ => None,
+        }
+    }
+}
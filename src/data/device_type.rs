@@ -0,0 +1,376 @@
+use std::{collections::HashMap, net::IpAddr};
+
+use super::{
+    dns,
+    knowledge::is_local,
+    packet::{PacketInfo, Transport, format_mac},
+};
+
+const DHCP_SERVER_PORT: u16 = 67;
+const DHCP_CLIENT_PORT: u16 = 68;
+const DHCP_MAGIC_COOKIE: [u8; 4] = [99, 130, 83, 99];
+const SSDP_PORT: u16 = 1900;
+
+/// What kind of device a host is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeviceType {
+    Phone,
+    Computer,
+    Printer,
+    Camera,
+    Tv,
+    Speaker,
+    Router,
+}
+
+impl DeviceType {
+    pub fn label(self) -> &'static str {
+        match self {
+            DeviceType::Phone => "Phone",
+            DeviceType::Computer => "Computer",
+            DeviceType::Printer => "Printer",
+            DeviceType::Camera => "Camera",
+            DeviceType::Tv => "TV",
+            DeviceType::Speaker => "Speaker",
+            DeviceType::Router => "Router",
+        }
+    }
+}
+
+/// Vendors by OUI, with the kind of device they mostly make when they make
+/// mostly one kind.
+const OUIS: [([u8; 3], &str, Option<DeviceType>); 32] = [
+    ([0x00, 0x00, 0x0c], "Cisco", Some(DeviceType::Router)),
+    ([0x00, 0x00, 0x48], "Epson", Some(DeviceType::Printer)),
+    ([0x00, 0x00, 0xaa], "Xerox", Some(DeviceType::Printer)),
+    ([0x00, 0x03, 0x93], "Apple", None),
+    ([0x00, 0x05, 0x69], "VMware", Some(DeviceType::Computer)),
+    ([0x00, 0x0c, 0x29], "VMware", Some(DeviceType::Computer)),
+    ([0x00, 0x0e, 0x58], "Sonos", Some(DeviceType::Speaker)),
+    (
+        [0x00, 0x15, 0x5d],
+        "Microsoft Hyper-V",
+        Some(DeviceType::Computer),
+    ),
+    ([0x00, 0x40, 0x8c], "Axis", Some(DeviceType::Camera)),
+    ([0x00, 0x50, 0x56], "VMware", Some(DeviceType::Computer)),
+    ([0x00, 0x80, 0x77], "Brother", Some(DeviceType::Printer)),
+    ([0x04, 0x18, 0xd6], "Ubiquiti", Some(DeviceType::Router)),
+    ([0x24, 0xa4, 0x3c], "Ubiquiti", Some(DeviceType::Router)),
+    ([0x3c, 0x07, 0x54], "Apple", None),
+    ([0x3c, 0xef, 0x8c], "Dahua", Some(DeviceType::Camera)),
+    ([0x44, 0x19, 0xb6], "Hikvision", Some(DeviceType::Camera)),
+    ([0x4c, 0x5e, 0x0c], "MikroTik", Some(DeviceType::Router)),
+    ([0x5c, 0xaa, 0xfd], "Sonos", Some(DeviceType::Speaker)),
+    ([0x64, 0xeb, 0x8c], "Epson", Some(DeviceType::Printer)),
+    ([0x94, 0x9f, 0x3e], "Sonos", Some(DeviceType::Speaker)),
+    ([0xac, 0xcc, 0x8e], "Axis", Some(DeviceType::Camera)),
+    ([0xb0, 0xa7, 0x37], "Roku", Some(DeviceType::Tv)),
+    (
+        [0xb8, 0x27, 0xeb],
+        "Raspberry Pi",
+        Some(DeviceType::Computer),
+    ),
+    ([0xbc, 0xad, 0x28], "Hikvision", Some(DeviceType::Camera)),
+    ([0xc0, 0x56, 0xe3], "Hikvision", Some(DeviceType::Camera)),
+    (
+        [0xd8, 0x3a, 0xdd],
+        "Raspberry Pi",
+        Some(DeviceType::Computer),
+    ),
+    ([0xdc, 0x3a, 0x5e], "Roku", Some(DeviceType::Tv)),
+    (
+        [0xdc, 0xa6, 0x32],
+        "Raspberry Pi",
+        Some(DeviceType::Computer),
+    ),
+    (
+        [0xe4, 0x5f, 0x01],
+        "Raspberry Pi",
+        Some(DeviceType::Computer),
+    ),
+    ([0xe4, 0x8d, 0x8c], "MikroTik", Some(DeviceType::Router)),
+    ([0xf0, 0x18, 0x98], "Apple", None),
+    ([0xf4, 0xf5, 0xd8], "Google", None),
+];
+
+/// Substrings of DHCP vendor classes, host names, mDNS service types and
+/// SSDP device types, matched case-insensitively, with what they point to.
+const DHCP_VENDOR_CLASSES: [(&str, DeviceType); 4] = [
+    ("android-dhcp", DeviceType::Phone),
+    ("msft", DeviceType::Computer),
+    ("jetdirect", DeviceType::Printer),
+    ("ipcam", DeviceType::Camera),
+];
+const HOST_NAMES: [(&str, DeviceType); 16] = [
+    ("iphone", DeviceType::Phone),
+    ("ipad", DeviceType::Phone),
+    ("android", DeviceType::Phone),
+    ("galaxy", DeviceType::Phone),
+    ("pixel", DeviceType::Phone),
+    ("desktop-", DeviceType::Computer),
+    ("laptop-", DeviceType::Computer),
+    ("macbook", DeviceType::Computer),
+    ("imac", DeviceType::Computer),
+    ("printer", DeviceType::Printer),
+    ("brw", DeviceType::Printer), // Brother's default names
+    ("epson", DeviceType::Printer),
+    ("camera", DeviceType::Camera),
+    ("chromecast", DeviceType::Tv),
+    ("roku", DeviceType::Tv),
+    ("sonos", DeviceType::Speaker),
+];
+const MDNS_SERVICES: [(&str, DeviceType); 11] = [
+    ("_ipp._tcp", DeviceType::Printer),
+    ("_ipps._tcp", DeviceType::Printer),
+    ("_printer._tcp", DeviceType::Printer),
+    ("_pdl-datastream._tcp", DeviceType::Printer),
+    ("_airplay._tcp", DeviceType::Tv),
+    ("_googlecast._tcp", DeviceType::Tv),
+    ("_sonos._tcp", DeviceType::Speaker),
+    ("_rtsp._tcp", DeviceType::Camera),
+    ("_apple-mobdev2._tcp", DeviceType::Phone),
+    ("_companion-link._tcp", DeviceType::Phone),
+    ("_smb._tcp", DeviceType::Computer),
+];
+const SSDP_DEVICES: [(&str, DeviceType); 6] = [
+    ("mediarenderer", DeviceType::Tv),
+    ("printer", DeviceType::Printer),
+    ("digitalsecuritycamera", DeviceType::Camera),
+    ("zoneplayer", DeviceType::Speaker),
+    ("internetgatewaydevice", DeviceType::Router),
+    ("wandevice", DeviceType::Router),
+];
+
+/// The first entry of `table` whose pattern occurs in `text`.
+fn find(
+    table: &'static [(&'static str, DeviceType)],
+    text: &str,
+) -> Option<(&'static str, DeviceType)> {
+    let text = text.to_lowercase();
+    table
+        .iter()
+        .find(|(pattern, _)| text.contains(pattern))
+        .copied()
+}
+
+/// A hint at what a device is, and how much it counts for.
+struct Evidence {
+    kind: DeviceType,
+    weight: u32,
+    source: String,
+}
+
+/// What a host was classified as and why.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Classification {
+    pub kind: DeviceType,
+    pub vendor: Option<&'static str>,
+    pub evidence: Vec<String>, // Hints that agree with `kind`
+}
+
+impl Classification {
+    pub fn label(&self) -> String {
+        match self.vendor {
+            Some(vendor) => format!("{} ({vendor})", self.kind.label()),
+            None => self.kind.label().to_string(),
+        }
+    }
+}
+
+/// Classifies hosts on the local network by combining what their traffic
+/// gives away: the vendor in their MAC address, their DHCP requests and the
+/// services they advertise over mDNS and SSDP. Hints are gathered per MAC
+/// address, since a DHCP client has no address yet.
+#[derive(Default)]
+pub struct Classifier {
+    macs: HashMap<IpAddr, String>,
+    evidence: HashMap<String, Vec<Evidence>>,
+}
+
+impl Classifier {
+    pub fn from_packets(packets: &[&PacketInfo]) -> Self {
+        let mut classifier = Self::default();
+        for packet in packets {
+            classifier.add(packet);
+        }
+        classifier
+    }
+
+    pub fn add(&mut self, packet: &PacketInfo) {
+        // Tunnelled packets carry the MAC addresses of the outer frame.
+        if !packet.outer_addrs.is_empty() {
+            return;
+        }
+        let Some(mac) = packet.data.get(6..12).map(format_mac) else {
+            return;
+        };
+        if let Some(Ok(src)) = packet.src_addr
+            && is_local(src)
+        {
+            self.macs.insert(src, mac.clone());
+        }
+        if packet.transport != Some(Transport::Udp) {
+            return;
+        }
+        let payload = packet.payload();
+        match (packet.src_port, packet.dst_port) {
+            (Some(DHCP_CLIENT_PORT), Some(DHCP_SERVER_PORT)) => self.add_dhcp(payload),
+            (Some(dns::MDNS_PORT), _) => self.add_mdns(&mac, payload),
+            (_, Some(SSDP_PORT)) | (Some(SSDP_PORT), _) => self.add_ssdp(&mac, payload),
+            _ => {}
+        }
+    }
+
+    fn hint(&mut self, mac: &str, kind: DeviceType, weight: u32, source: String) {
+        let evidence = self.evidence.entry(mac.to_string()).or_default();
+        if !evidence.iter().any(|e| e.source == source) {
+            evidence.push(Evidence {
+                kind,
+                weight,
+                source,
+            });
+        }
+    }
+
+    /// Reads the vendor class (option 60) and host name (option 12) of a
+    /// DHCP request.
+    fn add_dhcp(&mut self, payload: &[u8]) {
+        if payload.len() < 240 || payload[0] != 1 || payload[2] != 6 {
+            return;
+        }
+        if payload[236..240] != DHCP_MAGIC_COOKIE {
+            return;
+        }
+        let mac = format_mac(&payload[28..34]);
+        let mut offset = 240;
+        while let Some(&code) = payload.get(offset) {
+            match code {
+                0 => {
+                    offset += 1;
+                    continue;
+                }
+                255 => break,
+                _ => {}
+            }
+            let Some(&len) = payload.get(offset + 1) else {
+                break;
+            };
+            let Some(value) = payload.get(offset + 2..offset + 2 + len as usize) else {
+                break;
+            };
+            let value = String::from_utf8_lossy(value);
+            match code {
+                60 => {
+                    if let Some((_, kind)) = find(&DHCP_VENDOR_CLASSES, &value) {
+                        self.hint(&mac, kind, 3, format!("DHCP vendor class {value}"));
+                    }
+                }
+                12 => {
+                    if let Some((_, kind)) = find(&HOST_NAMES, &value) {
+                        self.hint(&mac, kind, 2, format!("DHCP host name {value}"));
+                    }
+                }
+                _ => {}
+            }
+            offset += 2 + len as usize;
+        }
+    }
+
+    /// Reads the service types and host names an mDNS response announces.
+    fn add_mdns(&mut self, mac: &str, payload: &[u8]) {
+        let Some(message) = dns::parse(payload, false) else {
+            return;
+        };
+        if !message.is_response {
+            return;
+        }
+        for record in &message.answers {
+            let name = record.name.trim_end_matches('.');
+            if let Some((service, kind)) = find(&MDNS_SERVICES, name) {
+                self.hint(mac, kind, 3, format!("mDNS {service}"));
+            } else if name.ends_with(".local")
+                && let Some((_, kind)) = find(&HOST_NAMES, name)
+            {
+                self.hint(mac, kind, 2, format!("mDNS name {name}"));
+            }
+        }
+    }
+
+    /// Reads the device type in an SSDP announcement or search response.
+    fn add_ssdp(&mut self, mac: &str, payload: &[u8]) {
+        let text = String::from_utf8_lossy(payload);
+        if text.starts_with("M-SEARCH") {
+            return;
+        }
+        for line in text.lines() {
+            let Some((header, value)) = line.split_once(':') else {
+                continue;
+            };
+            if !["nt", "st", "usn"].contains(&header.trim().to_lowercase().as_str()) {
+                continue;
+            }
+            if let Some((_, kind)) = find(&SSDP_DEVICES, value) {
+                self.hint(mac, kind, 2, format!("SSDP {}", value.trim()));
+            }
+        }
+    }
+
+    /// What the device behind `mac` most likely is, weighing the hints
+    /// about it against each other; the vendor alone decides only when
+    /// nothing else is known.
+    pub fn classify_mac(&self, mac: &str) -> Option<Classification> {
+        let oui = parse_oui(mac).and_then(|oui| OUIS.iter().find(|(prefix, ..)| *prefix == oui));
+        let vendor = oui.map(|&(_, vendor, _)| vendor);
+
+        let mut scores: HashMap<DeviceType, u32> = HashMap::new();
+        let evidence = self
+            .evidence
+            .get(mac)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for e in evidence {
+            *scores.entry(e.kind).or_default() += e.weight;
+        }
+        if let Some(&(_, _, Some(kind))) = oui {
+            *scores.entry(kind).or_default() += 1;
+        }
+        let (&kind, _) = scores
+            .iter()
+            .max_by_key(|&(&kind, &score)| (score, kind.label()))?;
+
+        let mut reasons: Vec<String> = evidence
+            .iter()
+            .filter(|e| e.kind == kind)
+            .map(|e| e.source.clone())
+            .collect();
+        if let Some(&(_, vendor, Some(oui_kind))) = oui
+            && oui_kind == kind
+        {
+            reasons.push(format!("OUI {vendor}"));
+        }
+        Some(Classification {
+            kind,
+            vendor,
+            evidence: reasons,
+        })
+    }
+
+    /// What the device at `addr` most likely is, if its MAC address was
+    /// seen on the local network.
+    pub fn classify(&self, addr: &Result<IpAddr, String>) -> Option<Classification> {
+        match addr {
+            Ok(ip) => self.classify_mac(self.macs.get(ip)?),
+            Err(mac) => self.classify_mac(mac),
+        }
+    }
+}
+
+fn parse_oui(mac: &str) -> Option<[u8; 3]> {
+    let mut oui = [0; 3];
+    let mut octets = mac.split(':');
+    for byte in &mut oui {
+        *byte = u8::from_str_radix(octets.next()?, 16).ok()?;
+    }
+    Some(oui)
+}
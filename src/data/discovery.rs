@@ -0,0 +1,414 @@
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    sync::Arc,
+};
+
+use etherparse::{LinkExtSlice, LinkSlice, SlicedPacket};
+
+use super::{
+    layer::Layer,
+    packet::{PacketInfo, format_mac},
+};
+
+/// EtherType of LLDP announcements.
+pub const LLDP_ETHERTYPE: u16 = 0x88cc;
+/// LLC/SNAP header of CDP, which goes over 802.3 frames with Cisco's OUI.
+const CDP_SNAP_HEADER: [u8; 8] = [0xaa, 0xaa, 0x03, 0x00, 0x00, 0x0c, 0x20, 0x00];
+/// Largest 802.3 length field; values above it are EtherTypes.
+const MAX_FRAME_LENGTH: u16 = 1500;
+
+const LLDP_END: u8 = 0;
+const LLDP_CHASSIS_ID: u8 = 1;
+const LLDP_PORT_ID: u8 = 2;
+const LLDP_TTL: u8 = 3;
+const LLDP_PORT_DESCRIPTION: u8 = 4;
+const LLDP_SYSTEM_NAME: u8 = 5;
+const LLDP_SYSTEM_DESCRIPTION: u8 = 6;
+const LLDP_CAPABILITIES: u8 = 7;
+const LLDP_MANAGEMENT_ADDRESS: u8 = 8;
+const LLDP_ORGANIZATIONAL: u8 = 127;
+/// Organizationally specific TLV of IEEE 802.1 carrying the port VLAN ID.
+const IEEE_802_1_OUI: [u8; 3] = [0x00, 0x80, 0xc2];
+const PORT_VLAN_ID: u8 = 1;
+
+const CDP_DEVICE_ID: u16 = 0x0001;
+const CDP_ADDRESSES: u16 = 0x0002;
+const CDP_PORT_ID: u16 = 0x0003;
+const CDP_CAPABILITIES: u16 = 0x0004;
+const CDP_SOFTWARE_VERSION: u16 = 0x0005;
+const CDP_PLATFORM: u16 = 0x0006;
+const CDP_NATIVE_VLAN: u16 = 0x000a;
+/// NLPID of IPv4 in a CDP address.
+const CDP_NLPID_IPV4: u8 = 0xcc;
+
+const LLDP_CAPABILITY_NAMES: [&str; 8] = [
+    "Other",
+    "Repeater",
+    "Bridge",
+    "WLAN AP",
+    "Router",
+    "Telephone",
+    "DOCSIS",
+    "Station",
+];
+const CDP_CAPABILITY_NAMES: [&str; 8] = [
+    "Router",
+    "TB Bridge",
+    "SR Bridge",
+    "Switch",
+    "Host",
+    "IGMP",
+    "Repeater",
+    "Phone",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Protocol {
+    Lldp,
+    Cdp,
+}
+
+impl Protocol {
+    pub fn label(self) -> &'static str {
+        match self {
+            Protocol::Lldp => "LLDP",
+            Protocol::Cdp => "CDP",
+        }
+    }
+}
+
+/// What a switch or router says about itself in one announcement.
+#[derive(Debug, Clone, Default)]
+pub struct Announcement {
+    pub chassis_id: String,
+    pub port_id: String,
+    pub system_name: Option<String>,
+    pub port_description: Option<String>,
+    pub description: Option<String>, // LLDP system description or CDP platform
+    pub software: Option<String>,
+    pub vlan: Option<u16>,
+    pub capabilities: Vec<&'static str>,
+    pub management: Option<IpAddr>,
+    pub ttl: Option<u16>,
+}
+
+fn text(value: &[u8]) -> String {
+    String::from_utf8_lossy(value)
+        .trim_end_matches('\0')
+        .trim()
+        .to_string()
+}
+
+fn capability_names(names: &[&'static str; 8], bits: u32) -> Vec<&'static str> {
+    names
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| bits & 1 << bit != 0)
+        .map(|(_, &name)| name)
+        .collect()
+}
+
+/// An IANA address family number followed by the address.
+fn family_address(value: &[u8]) -> Option<IpAddr> {
+    match (value.first()?, value.get(1..)?) {
+        (1, addr) => Some(IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(addr).ok()?))),
+        (2, addr) => Some(IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(addr).ok()?))),
+        _ => None,
+    }
+}
+
+/// A chassis or port ID: a subtype, then a MAC address, a network address
+/// or a name depending on it.
+fn lldp_id(value: &[u8], mac_subtype: u8, address_subtype: u8) -> String {
+    let Some((&subtype, id)) = value.split_first() else {
+        return String::new();
+    };
+    if subtype == mac_subtype && id.len() == 6 {
+        format_mac(id)
+    } else if subtype == address_subtype
+        && let Some(addr) = family_address(id)
+    {
+        addr.to_string()
+    } else {
+        text(id)
+    }
+}
+
+/// Reads the TLVs of an LLDPDU: a 7-bit type and 9-bit length each, up to
+/// the End TLV.
+fn parse_lldp(payload: &[u8]) -> Option<Announcement> {
+    let mut announcement = Announcement::default();
+    let mut rest = payload;
+    let mut has_chassis = false;
+    while let Some(header) = rest.get(..2) {
+        let tlv_type = header[0] >> 1;
+        let len = (usize::from(header[0] & 1) << 8) | usize::from(header[1]);
+        let Some(value) = rest.get(2..2 + len) else {
+            break;
+        };
+        rest = &rest[2 + len..];
+        match tlv_type {
+            LLDP_END => break,
+            LLDP_CHASSIS_ID => {
+                announcement.chassis_id = lldp_id(value, 4, 5);
+                has_chassis = true;
+            }
+            LLDP_PORT_ID => announcement.port_id = lldp_id(value, 3, 4),
+            LLDP_TTL => {
+                announcement.ttl = value
+                    .get(..2)
+                    .map(|ttl| u16::from_be_bytes([ttl[0], ttl[1]]));
+            }
+            LLDP_PORT_DESCRIPTION => announcement.port_description = Some(text(value)),
+            LLDP_SYSTEM_NAME => announcement.system_name = Some(text(value)),
+            LLDP_SYSTEM_DESCRIPTION => announcement.description = Some(text(value)),
+            LLDP_CAPABILITIES => {
+                // Enabled capabilities follow the supported ones.
+                if let Some(enabled) = value.get(2..4) {
+                    let bits = u32::from(u16::from_be_bytes([enabled[0], enabled[1]]));
+                    announcement.capabilities = capability_names(&LLDP_CAPABILITY_NAMES, bits);
+                }
+            }
+            LLDP_MANAGEMENT_ADDRESS => {
+                if announcement.management.is_none()
+                    && let Some(&len) = value.first()
+                {
+                    let address = value.get(1..1 + usize::from(len));
+                    announcement.management = address.and_then(family_address);
+                }
+            }
+            LLDP_ORGANIZATIONAL => {
+                if value.starts_with(&IEEE_802_1_OUI)
+                    && value.get(3) == Some(&PORT_VLAN_ID)
+                    && let Some(vlan) = value.get(4..6)
+                {
+                    announcement.vlan = Some(u16::from_be_bytes([vlan[0], vlan[1]]));
+                }
+            }
+            _ => {}
+        }
+    }
+    has_chassis.then_some(announcement)
+}
+
+/// The first IPv4 address of a CDP Addresses TLV.
+fn cdp_address(value: &[u8]) -> Option<IpAddr> {
+    let count = u32::from_be_bytes(value.get(..4)?.try_into().ok()?);
+    let mut rest = value.get(4..)?;
+    for _ in 0..count {
+        let protocol_len = usize::from(*rest.get(1)?);
+        let protocol = rest.get(2..2 + protocol_len)?;
+        let at = 2 + protocol_len;
+        let address_len = usize::from(u16::from_be_bytes(rest.get(at..at + 2)?.try_into().ok()?));
+        let address = rest.get(at + 2..at + 2 + address_len)?;
+        if protocol == [CDP_NLPID_IPV4] && address_len == 4 {
+            return Some(IpAddr::V4(Ipv4Addr::from(
+                <[u8; 4]>::try_from(address).ok()?,
+            )));
+        }
+        rest = &rest[at + 2 + address_len..];
+    }
+    None
+}
+
+/// Reads a CDP message after its LLC/SNAP header: a version, holdtime and
+/// checksum, then TLVs with a 16-bit type and a length counting the header.
+fn parse_cdp(payload: &[u8]) -> Option<Announcement> {
+    let body = payload.strip_prefix(&CDP_SNAP_HEADER)?;
+    let mut announcement = Announcement {
+        ttl: Some(u16::from(*body.get(1)?)),
+        ..Announcement::default()
+    };
+    let mut rest = body.get(4..)?;
+    let mut has_device = false;
+    while let Some(header) = rest.get(..4) {
+        let tlv_type = u16::from_be_bytes([header[0], header[1]]);
+        let len = usize::from(u16::from_be_bytes([header[2], header[3]]));
+        let Some(value) = rest.get(4..len) else {
+            break;
+        };
+        rest = &rest[len..];
+        match tlv_type {
+            CDP_DEVICE_ID => {
+                announcement.chassis_id = text(value);
+                announcement.system_name = Some(text(value));
+                has_device = true;
+            }
+            CDP_ADDRESSES => announcement.management = cdp_address(value),
+            CDP_PORT_ID => announcement.port_id = text(value),
+            CDP_CAPABILITIES => {
+                if let Ok(bits) = value.try_into() {
+                    let bits = u32::from_be_bytes(bits);
+                    announcement.capabilities = capability_names(&CDP_CAPABILITY_NAMES, bits);
+                }
+            }
+            CDP_SOFTWARE_VERSION => {
+                // Only the first line, as the full banner runs long.
+                announcement.software = text(value).lines().next().map(str::to_string);
+            }
+            CDP_PLATFORM => announcement.description = Some(text(value)),
+            CDP_NATIVE_VLAN => {
+                if let Ok(vlan) = value.try_into() {
+                    announcement.vlan = Some(u16::from_be_bytes(vlan));
+                }
+            }
+            _ => {}
+        }
+    }
+    has_device.then_some(announcement)
+}
+
+/// The announcement in the payload of a frame of `ethertype`, which for
+/// CDP is an 802.3 length.
+pub fn parse(ethertype: u16, payload: &[u8]) -> Option<(Protocol, Announcement)> {
+    if ethertype == LLDP_ETHERTYPE {
+        parse_lldp(payload).map(|announcement| (Protocol::Lldp, announcement))
+    } else if ethertype <= MAX_FRAME_LENGTH {
+        parse_cdp(payload).map(|announcement| (Protocol::Cdp, announcement))
+    } else {
+        None
+    }
+}
+
+/// Decodes an LLDP or CDP announcement into a layer.
+pub fn decode(ethertype: u16, payload: &[u8]) -> Option<Layer> {
+    let (protocol, announcement) = parse(ethertype, payload)?;
+    let name = announcement
+        .system_name
+        .as_deref()
+        .unwrap_or(&announcement.chassis_id);
+    let mut layer = Layer::new(
+        protocol.label(),
+        format!("{name}, port {}", announcement.port_id),
+    );
+    layer.field("Chassis ID", &announcement.chassis_id);
+    layer.field("Port ID", &announcement.port_id);
+    if let Some(ref system_name) = announcement.system_name
+        && protocol == Protocol::Lldp
+    {
+        layer.field("System Name", system_name);
+    }
+    if let Some(ref port_description) = announcement.port_description {
+        layer.field("Port Description", port_description);
+    }
+    if let Some(vlan) = announcement.vlan {
+        layer.field("VLAN", vlan.to_string());
+    }
+    if !announcement.capabilities.is_empty() {
+        layer.field("Capabilities", announcement.capabilities.join(", "));
+    }
+    if let Some(management) = announcement.management {
+        layer.field("Management Address", management.to_string());
+    }
+    if let Some(ref description) = announcement.description {
+        let label = match protocol {
+            Protocol::Lldp => "System Description",
+            Protocol::Cdp => "Platform",
+        };
+        layer.detail(label, description);
+    }
+    if let Some(ref software) = announcement.software {
+        layer.detail("Software Version", software);
+    }
+    if let Some(ttl) = announcement.ttl {
+        layer.detail("TTL", format!("{ttl} s"));
+    }
+    Some(layer)
+}
+
+/// The EtherType or 802.3 length of an Ethernet frame and its payload,
+/// past any VLAN tags.
+fn link_payload(packet: &PacketInfo) -> Option<(u16, &[u8])> {
+    let sliced = SlicedPacket::from_ethernet(&packet.data).ok()?;
+    let Some(LinkSlice::Ethernet2(eth)) = sliced.link else {
+        return None;
+    };
+    let mut link = (eth.ether_type().0, eth.payload_slice());
+    for ext in &sliced.link_exts {
+        if let LinkExtSlice::Vlan(vlan) = ext {
+            link = (vlan.ether_type().0, vlan.payload_slice());
+        }
+    }
+    Some(link)
+}
+
+/// A switch or router heard announcing itself on one interface.
+#[derive(Debug, Clone)]
+pub struct Neighbor {
+    pub interface: Option<Arc<str>>,
+    pub protocol: Protocol,
+    pub source: Option<String>, // MAC address the announcements came from
+    pub announcement: Announcement, // The latest one
+    pub announcements: usize,
+}
+
+impl Neighbor {
+    /// The system name, or the chassis ID for those announcing none.
+    pub fn name(&self) -> &str {
+        self.announcement
+            .system_name
+            .as_deref()
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&self.announcement.chassis_id)
+    }
+}
+
+/// The interface a neighbor was heard on, its protocol, chassis and port.
+type NeighborKey = (Option<Arc<str>>, Protocol, String, String);
+
+/// LLDP and CDP neighbors in interface order.
+#[derive(Debug, Clone, Default)]
+pub struct DiscoveryStats {
+    pub neighbors: BTreeMap<NeighborKey, Neighbor>,
+}
+
+impl DiscoveryStats {
+    pub fn from_packets(packets: &[&PacketInfo]) -> Self {
+        let mut stats = Self::default();
+        for packet in packets {
+            stats.add(packet);
+        }
+        stats
+    }
+
+    pub fn add(&mut self, packet: &PacketInfo) {
+        let Some((protocol, announcement)) = packet
+            .ethertype
+            .filter(|&ethertype| ethertype == LLDP_ETHERTYPE || ethertype <= MAX_FRAME_LENGTH)
+            .and_then(|_| link_payload(packet))
+            .and_then(|(ethertype, payload)| parse(ethertype, payload))
+        else {
+            return;
+        };
+        let key = (
+            packet.interface.clone(),
+            protocol,
+            announcement.chassis_id.clone(),
+            announcement.port_id.clone(),
+        );
+        let neighbor = self.neighbors.entry(key).or_insert(Neighbor {
+            interface: packet.interface.clone(),
+            protocol,
+            source: None,
+            announcement: Announcement::default(),
+            announcements: 0,
+        });
+        if let Some(Err(ref mac)) = packet.src_addr {
+            neighbor.source = Some(mac.clone());
+        }
+        neighbor.announcement = announcement;
+        neighbor.announcements += 1;
+    }
+
+    /// How many interfaces neighbors were heard on.
+    pub fn interfaces(&self) -> usize {
+        let mut interfaces: Vec<&Option<Arc<str>>> = self
+            .neighbors
+            .values()
+            .map(|neighbor| &neighbor.interface)
+            .collect();
+        interfaces.dedup();
+        interfaces.len()
+    }
+}
@@ -0,0 +1,420 @@
+use std::net::IpAddr;
+
+use anyhow::{Result, anyhow, bail};
+
+use super::packet::{PacketInfo, Transport};
+
+/// A Wireshark-style display filter such as
+/// `ip.addr == 10.0.0.0/8 and (tcp.port == 443 or vxlan.vni == 42)`.
+///
+/// Fields name either a built-in packet property (`ip.src`, `tcp.port`,
+/// `frame.len`, ...) or a field of a decoded layer, written as the layer
+/// and field names in lower case with spaces replaced by underscores
+/// (`gre.key`, `sip.call-id`). Addresses match every IP header of a
+/// tunnelled packet, outer and inner.
+#[derive(Debug, Clone)]
+pub struct DisplayFilter {
+    text: String,
+    expr: Expr,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Exists(String),
+    Compare(String, Op, Operand),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+#[derive(Debug, Clone)]
+struct Operand {
+    text: String, // Lower case
+    number: Option<f64>,
+    net: Option<(IpAddr, u8)>,
+}
+
+impl Operand {
+    fn new(text: &str) -> Self {
+        let net = match text.split_once('/') {
+            Some((addr, prefix)) => addr.parse().ok().zip(prefix.parse().ok()),
+            None => text.parse().ok().map(|addr: IpAddr| {
+                let prefix = if addr.is_ipv4() { 32 } else { 128 };
+                (addr, prefix)
+            }),
+        };
+        Self {
+            text: text.to_lowercase(),
+            number: parse_number(text),
+            net,
+        }
+    }
+
+    fn equals(&self, value: &str) -> bool {
+        if let Some((net, prefix)) = self.net
+            && let Ok(addr) = value.parse::<IpAddr>()
+        {
+            return in_network(addr, net, prefix);
+        }
+        if let (Some(a), Some(b)) = (parse_number(value), self.number) {
+            return a == b;
+        }
+        value.eq_ignore_ascii_case(&self.text)
+    }
+}
+
+fn parse_number(text: &str) -> Option<f64> {
+    match text.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok().map(|n| n as f64),
+        None => text.parse().ok(),
+    }
+}
+
+fn in_network(addr: IpAddr, net: IpAddr, prefix: u8) -> bool {
+    match (addr, net) {
+        (IpAddr::V4(addr), IpAddr::V4(net)) => {
+            let mask = u32::MAX
+                .checked_shl(32 - prefix.min(32) as u32)
+                .unwrap_or(0);
+            u32::from(addr) & mask == u32::from(net) & mask
+        }
+        (IpAddr::V6(addr), IpAddr::V6(net)) => {
+            let mask = u128::MAX
+                .checked_shl(128 - prefix.min(128) as u32)
+                .unwrap_or(0);
+            u128::from(addr) & mask == u128::from(net) & mask
+        }
+        _ => false,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | ':' | '-' | '/')
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+        let mut two = |second: char, token: Token, single: Option<Token>| {
+            chars.next();
+            if chars.next_if_eq(&second).is_some() {
+                Ok(token)
+            } else {
+                single.ok_or_else(|| anyhow!("Expected '{c}{second}'"))
+            }
+        };
+        let token = match c {
+            '(' | ')' => {
+                chars.next();
+                if c == '(' { Token::Open } else { Token::Close }
+            }
+            '=' => two('=', Token::Op(Op::Eq), None)?,
+            '!' => two('=', Token::Op(Op::Ne), Some(Token::Not))?,
+            '<' => two('=', Token::Op(Op::Le), Some(Token::Op(Op::Lt)))?,
+            '>' => two('=', Token::Op(Op::Ge), Some(Token::Op(Op::Gt)))?,
+            '&' => two('&', Token::And, None)?,
+            '|' => two('|', Token::Or, None)?,
+            '"' => {
+                chars.next();
+                let mut quoted = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => quoted.extend(chars.next()),
+                        Some(c) => quoted.push(c),
+                        None => bail!("Unterminated string"),
+                    }
+                }
+                Token::Quoted(quoted)
+            }
+            c if is_word_char(c) => {
+                let mut word = String::new();
+                while let Some(c) = chars.next_if(|&c| is_word_char(c)) {
+                    word.push(c);
+                }
+                match word.to_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    "eq" => Token::Op(Op::Eq),
+                    "ne" => Token::Op(Op::Ne),
+                    "lt" => Token::Op(Op::Lt),
+                    "le" => Token::Op(Op::Le),
+                    "gt" => Token::Op(Op::Gt),
+                    "ge" => Token::Op(Op::Ge),
+                    "contains" => Token::Op(Op::Contains),
+                    _ => Token::Word(word),
+                }
+            }
+            c => bail!("Unexpected character '{c}'"),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn or(&mut self) -> Result<Expr> {
+        let mut expr = self.and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<Expr> {
+        let mut expr = self.not()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<Expr> {
+        if self.peek() == Some(&Token::Not) {
+            self.next();
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => bail!("Missing ')'"),
+                }
+            }
+            Some(Token::Word(field)) => {
+                let field = field.to_lowercase();
+                let Some(&Token::Op(op)) = self.peek() else {
+                    return Ok(Expr::Exists(field));
+                };
+                self.next();
+                match self.next() {
+                    Some(Token::Word(value) | Token::Quoted(value)) => {
+                        let operand = Operand::new(&value);
+                        if matches!(op, Op::Lt | Op::Le | Op::Gt | Op::Ge)
+                            && operand.number.is_none()
+                        {
+                            bail!("'{value}' is not a number");
+                        }
+                        Ok(Expr::Compare(field, op, operand))
+                    }
+                    _ => bail!("Expected a value after '{field}'"),
+                }
+            }
+            Some(token) => bail!("Unexpected {token:?}"),
+            None => bail!("Unexpected end of filter"),
+        }
+    }
+}
+
+fn transport_name(transport: Transport) -> &'static str {
+    match transport {
+        Transport::Tcp => "tcp",
+        Transport::Udp => "udp",
+        Transport::Icmpv4 => "icmp",
+        Transport::Icmpv6 => "icmpv6",
+    }
+}
+
+/// Normalizes a layer or field name the way filters spell it.
+fn filter_name(name: &str) -> String {
+    name.to_lowercase().replace(' ', "_")
+}
+
+/// All values `field` takes in `packet`; empty when the field is absent.
+fn field_values(packet: &PacketInfo, field: &str) -> Vec<String> {
+    let ip = |addr: &Option<Result<IpAddr, String>>| match addr {
+        Some(Ok(ip)) => Some(*ip),
+        _ => None,
+    };
+    // Hardware addresses are only kept for frames without an IP layer.
+    let mac = |addr: &Option<Result<IpAddr, String>>| match addr {
+        Some(Err(mac)) => Some(mac.clone()),
+        _ => None,
+    };
+    let sources = || {
+        packet
+            .outer_addrs
+            .iter()
+            .map(|(src, _)| *src)
+            .chain(ip(&packet.src_addr))
+    };
+    let destinations = || {
+        packet
+            .outer_addrs
+            .iter()
+            .map(|(_, dst)| *dst)
+            .chain(ip(&packet.dst_addr))
+    };
+    let transport = packet.transport.map(transport_name);
+    let ports = |proto: Option<&str>, src: bool, dst: bool| {
+        if proto.is_some() && proto != transport {
+            return Vec::new();
+        }
+        let src = packet.src_port.filter(|_| src);
+        let dst = packet.dst_port.filter(|_| dst);
+        src.into_iter().chain(dst).map(|p| p.to_string()).collect()
+    };
+
+    let (prefix, name) = field.split_once('.').unwrap_or(("", field));
+    match field {
+        "ip.src" => sources().map(|a| a.to_string()).collect(),
+        "ip.dst" => destinations().map(|a| a.to_string()).collect(),
+        "ip.addr" => sources()
+            .chain(destinations())
+            .map(|a| a.to_string())
+            .collect(),
+        "port" => ports(None, true, true),
+        "eth.src" => mac(&packet.src_addr).into_iter().collect(),
+        "eth.dst" => mac(&packet.dst_addr).into_iter().collect(),
+        "eth.addr" => mac(&packet.src_addr)
+            .into_iter()
+            .chain(mac(&packet.dst_addr))
+            .collect(),
+        "eth.type" => packet
+            .ethertype
+            .map(|ethertype| format!("0x{ethertype:04x}"))
+            .into_iter()
+            .collect(),
+        "frame.len" => vec![packet.length.to_string()],
+        "frame.number" => vec![packet.id.to_string()],
+        "protocol" => vec![packet.protocol.clone()],
+        "info" => vec![packet.info.clone()],
+        _ if matches!(prefix, "tcp" | "udp") => match name {
+            "port" => ports(Some(prefix), true, true),
+            "srcport" => ports(Some(prefix), true, false),
+            "dstport" => ports(Some(prefix), false, true),
+            _ => Vec::new(),
+        },
+        _ => packet
+            .layers
+            .iter()
+            .filter(|layer| filter_name(layer.name) == prefix)
+            .flat_map(|layer| &layer.fields)
+            .filter(|f| filter_name(&f.name) == name)
+            .map(|f| f.value.clone())
+            .collect(),
+    }
+}
+
+/// Whether a bare protocol name such as `tcp` or `vxlan` is present.
+fn has_protocol(packet: &PacketInfo, name: &str) -> bool {
+    let transport = packet.transport.map(transport_name);
+    match name {
+        "ip" => {
+            packet.outer_addrs.iter().any(|(src, _)| src.is_ipv4())
+                || matches!(packet.src_addr, Some(Ok(IpAddr::V4(_))))
+        }
+        "ipv6" => {
+            packet.outer_addrs.iter().any(|(src, _)| src.is_ipv6())
+                || matches!(packet.src_addr, Some(Ok(IpAddr::V6(_))))
+        }
+        "arp" => packet.protocol == "ARP",
+        _ if transport == Some(name) => true,
+        _ => packet
+            .layers
+            .iter()
+            .any(|layer| filter_name(layer.name) == name),
+    }
+}
+
+impl Expr {
+    fn matches(&self, packet: &PacketInfo) -> bool {
+        match self {
+            Expr::Or(a, b) => a.matches(packet) || b.matches(packet),
+            Expr::And(a, b) => a.matches(packet) && b.matches(packet),
+            Expr::Not(expr) => !expr.matches(packet),
+            Expr::Exists(field) if field.contains('.') => !field_values(packet, field).is_empty(),
+            Expr::Exists(name) => has_protocol(packet, name),
+            Expr::Compare(field, op, operand) => {
+                let values = field_values(packet, field);
+                let any = |test: &dyn Fn(&str) -> bool| values.iter().any(|v| test(v));
+                let number = |v: &str| parse_number(v).zip(operand.number);
+                match op {
+                    Op::Eq => any(&|v| operand.equals(v)),
+                    Op::Ne => !any(&|v| operand.equals(v)),
+                    Op::Lt => any(&|v| number(v).is_some_and(|(a, b)| a < b)),
+                    Op::Le => any(&|v| number(v).is_some_and(|(a, b)| a <= b)),
+                    Op::Gt => any(&|v| number(v).is_some_and(|(a, b)| a > b)),
+                    Op::Ge => any(&|v| number(v).is_some_and(|(a, b)| a >= b)),
+                    Op::Contains => any(&|v| v.to_lowercase().contains(&operand.text)),
+                }
+            }
+        }
+    }
+}
+
+impl DisplayFilter {
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            position: 0,
+        };
+        let expr = parser.or()?;
+        if let Some(token) = parser.peek() {
+            bail!("Unexpected {token:?}");
+        }
+        Ok(Self {
+            text: text.trim().to_string(),
+            expr,
+        })
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn matches(&self, packet: &PacketInfo) -> bool {
+        self.expr.matches(packet)
+    }
+}
@@ -0,0 +1,234 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use super::layer::Layer;
+
+pub const PORT: u16 = 53;
+pub const MDNS_PORT: u16 = 5353;
+
+#[derive(Debug, Clone)]
+pub enum RecordData {
+    Address(IpAddr),
+    Name(String), // CNAME, NS and PTR targets
+    Other(usize), // Length of data not decoded
+}
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub name: String,
+    pub rtype: u16,
+    pub ttl: u32,
+    pub data: RecordData,
+}
+
+#[derive(Debug, Clone)]
+pub struct DnsMessage {
+    pub id: u16,
+    pub is_response: bool,
+    pub opcode: u8,
+    pub rcode: u8,
+    pub questions: Vec<(String, u16)>,
+    pub answers: Vec<Record>,
+}
+
+pub fn type_name(rtype: u16) -> String {
+    match rtype {
+        1 => "A".to_string(),
+        2 => "NS".to_string(),
+        5 => "CNAME".to_string(),
+        6 => "SOA".to_string(),
+        12 => "PTR".to_string(),
+        15 => "MX".to_string(),
+        16 => "TXT".to_string(),
+        28 => "AAAA".to_string(),
+        33 => "SRV".to_string(),
+        64 => "SVCB".to_string(),
+        65 => "HTTPS".to_string(),
+        255 => "ANY".to_string(),
+        _ => format!("TYPE{rtype}"),
+    }
+}
+
+pub fn rcode_name(rcode: u8) -> &'static str {
+    match rcode {
+        0 => "No error",
+        1 => "Format error",
+        2 => "Server failure",
+        3 => "No such name",
+        4 => "Not implemented",
+        5 => "Refused",
+        _ => "Unknown",
+    }
+}
+
+fn read_u16(buf: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes([
+        *buf.get(offset)?,
+        *buf.get(offset + 1)?,
+    ]))
+}
+
+/// Reads a possibly compressed domain name at `offset`. Returns the name
+/// and the offset just past it in the original position.
+fn read_name(buf: &[u8], mut offset: usize) -> Option<(String, usize)> {
+    let mut labels: Vec<String> = Vec::new();
+    let mut end = None;
+    // Every pointer must go backwards, which also rules out loops.
+    let mut limit = offset;
+    loop {
+        let len = *buf.get(offset)? as usize;
+        if len & 0xc0 == 0xc0 {
+            let pointer = ((len & 0x3f) << 8) | *buf.get(offset + 1)? as usize;
+            if pointer >= limit {
+                return None;
+            }
+            end.get_or_insert(offset + 2);
+            offset = pointer;
+            limit = pointer;
+            continue;
+        }
+        if len == 0 {
+            let name = if labels.is_empty() {
+                "<Root>".to_string()
+            } else {
+                labels.join(".")
+            };
+            return Some((name, end.unwrap_or(offset + 1)));
+        }
+        let label = buf.get(offset + 1..offset + 1 + len)?;
+        labels.push(String::from_utf8_lossy(label).into_owned());
+        offset += 1 + len;
+    }
+}
+
+fn read_record(buf: &[u8], offset: usize) -> Option<(Record, usize)> {
+    let (name, offset) = read_name(buf, offset)?;
+    let rtype = read_u16(buf, offset)?;
+    let ttl = u32::from_be_bytes(buf.get(offset + 4..offset + 8)?.try_into().ok()?);
+    let len = read_u16(buf, offset + 8)? as usize;
+    let start = offset + 10;
+    let rdata = buf.get(start..start + len)?;
+    let data = match rtype {
+        1 if len == 4 => {
+            RecordData::Address(IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(rdata).ok()?)))
+        }
+        28 if len == 16 => RecordData::Address(IpAddr::V6(Ipv6Addr::from(
+            <[u8; 16]>::try_from(rdata).ok()?,
+        ))),
+        2 | 5 | 12 => RecordData::Name(read_name(buf, start)?.0),
+        _ => RecordData::Other(len),
+    };
+    Some((
+        Record {
+            name,
+            rtype,
+            ttl,
+            data,
+        },
+        start + len,
+    ))
+}
+
+/// Parses the header, questions and answers of a DNS message. Over TCP
+/// the message is preceded by a two-byte length.
+pub fn parse(payload: &[u8], over_tcp: bool) -> Option<DnsMessage> {
+    let buf = if over_tcp { payload.get(2..)? } else { payload };
+    let flags = read_u16(buf, 2)?;
+    let question_count = read_u16(buf, 4)?;
+    let answer_count = read_u16(buf, 6)?;
+    let opcode = ((flags >> 11) & 0x0f) as u8;
+    if opcode > 6 {
+        return None;
+    }
+
+    let mut message = DnsMessage {
+        id: read_u16(buf, 0)?,
+        is_response: flags & 0x8000 != 0,
+        opcode,
+        rcode: (flags & 0x0f) as u8,
+        questions: Vec::new(),
+        answers: Vec::new(),
+    };
+    let mut offset = 12;
+    for _ in 0..question_count {
+        let (name, end) = read_name(buf, offset)?;
+        message.questions.push((name, read_u16(buf, end)?));
+        offset = end + 4;
+    }
+    // A truncated answer section still yields the records before the cut.
+    for _ in 0..answer_count {
+        let Some((record, end)) = read_record(buf, offset) else {
+            break;
+        };
+        message.answers.push(record);
+        offset = end;
+    }
+    Some(message)
+}
+
+impl RecordData {
+    fn describe(&self) -> String {
+        match self {
+            RecordData::Address(addr) => addr.to_string(),
+            RecordData::Name(name) => name.clone(),
+            RecordData::Other(len) => format!("{len} bytes"),
+        }
+    }
+}
+
+pub fn decode(payload: &[u8], over_tcp: bool) -> Option<Layer> {
+    let message = parse(payload, over_tcp)?;
+
+    let kind = match (message.opcode, message.is_response) {
+        (0, false) => "Standard query",
+        (0, true) => "Standard query response",
+        (_, false) => "Query",
+        (_, true) => "Response",
+    };
+    let mut summary = format!("{kind} 0x{:04x}", message.id);
+    for (name, qtype) in &message.questions {
+        summary.push_str(&format!(" {} {name}", type_name(*qtype)));
+    }
+    if message.is_response && message.rcode != 0 {
+        summary.push_str(&format!(" {}", rcode_name(message.rcode)));
+    }
+    for record in &message.answers {
+        summary.push_str(&format!(
+            " {} {}",
+            type_name(record.rtype),
+            record.data.describe()
+        ));
+    }
+
+    let mut layer = Layer::new("DNS", summary);
+    layer.detail("Transaction ID", format!("0x{:04x}", message.id));
+    layer.field(
+        "Type",
+        if message.is_response {
+            "Response"
+        } else {
+            "Query"
+        },
+    );
+    if message.is_response {
+        layer.field(
+            "Reply Code",
+            format!("{} ({})", message.rcode, rcode_name(message.rcode)),
+        );
+    }
+    for (name, qtype) in &message.questions {
+        layer.field("Query", format!("{name} {}", type_name(*qtype)));
+    }
+    for record in &message.answers {
+        layer.field(
+            "Answer",
+            format!(
+                "{} {} {} (TTL {})",
+                record.name,
+                type_name(record.rtype),
+                record.data.describe(),
+                record.ttl
+            ),
+        );
+    }
+    Some(layer)
+}
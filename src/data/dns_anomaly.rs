@@ -0,0 +1,199 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    net::IpAddr,
+    time::{Duration, SystemTime},
+};
+
+use super::{
+    dns,
+    packet::{PacketInfo, Transport},
+};
+
+/// Labels longer than this are rare in names people type but common in
+/// data smuggled through DNS; 63 is the most a label can hold.
+const LONG_LABEL: usize = 52;
+
+/// Subdomains at least this long are checked for randomness.
+const ENTROPY_MIN_LEN: usize = 30;
+
+/// Bits of Shannon entropy per character above which a subdomain looks
+/// like encoded data rather than words.
+const ENTROPY_THRESHOLD: f64 = 3.8;
+
+/// NXDOMAIN responses to one client within `NXDOMAIN_WINDOW` that make a
+/// burst, as when malware walks generated domain names.
+const NXDOMAIN_BURST: usize = 20;
+const NXDOMAIN_WINDOW: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DnsAnomaly {
+    NxdomainBurst,
+    LongLabel,
+    HighEntropy, // Subdomains typical of DNS tunneling
+}
+
+impl DnsAnomaly {
+    /// Short enough for the Analysis column.
+    pub fn label(self) -> &'static str {
+        match self {
+            DnsAnomaly::NxdomainBurst => "NXDOMAIN Rate",
+            DnsAnomaly::LongLabel => "Long Label",
+            DnsAnomaly::HighEntropy => "High Entropy",
+        }
+    }
+}
+
+/// Packets showing one anomaly for one domain, or for one client in the
+/// case of NXDOMAIN bursts.
+#[derive(Debug, Clone)]
+pub struct DnsFinding {
+    pub anomaly: DnsAnomaly,
+    pub subject: String,
+    pub example: String, // Name queried by the latest packet
+    pub packets: usize,
+    pub index: usize, // Latest packet
+    pub id: usize,
+}
+
+/// The domain a name belongs to, taken as its last two labels.
+fn base_domain(name: &str) -> &str {
+    match name.rmatch_indices('.').nth(1) {
+        Some((dot, _)) => &name[dot + 1..],
+        None => name,
+    }
+}
+
+/// Shannon entropy of `text` in bits per character.
+fn entropy(text: &str) -> f64 {
+    let mut counts = [0usize; 256];
+    for byte in text.bytes() {
+        counts[byte.to_ascii_lowercase() as usize] += 1;
+    }
+    let len = text.len() as f64;
+    counts
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// What is suspicious about querying `name`, if anything.
+fn check_name(name: &str) -> Option<DnsAnomaly> {
+    if name.split('.').any(|label| label.len() > LONG_LABEL) {
+        return Some(DnsAnomaly::LongLabel);
+    }
+    let base = base_domain(name);
+    let subdomain: String = name[..name.len() - base.len()]
+        .chars()
+        .filter(|&c| c != '.')
+        .collect();
+    (subdomain.len() >= ENTROPY_MIN_LEN && entropy(&subdomain) >= ENTROPY_THRESHOLD)
+        .then_some(DnsAnomaly::HighEntropy)
+}
+
+/// Watches unicast DNS for signs of tunneling and domain generation:
+/// very long labels, random-looking subdomains and bursts of NXDOMAIN.
+#[derive(Debug, Default)]
+pub struct DnsMonitor {
+    nxdomains: HashMap<IpAddr, VecDeque<SystemTime>>, // Recent NXDOMAIN times per client
+    anomalies: HashMap<usize, DnsAnomaly>,            // By packet index
+    groups: HashMap<(DnsAnomaly, String), usize>,     // Into `findings`
+    pub findings: Vec<DnsFinding>,
+}
+
+impl DnsMonitor {
+    pub fn add(&mut self, index: usize, packet: &PacketInfo) {
+        let over_tcp = match packet.transport {
+            Some(Transport::Tcp) => true,
+            Some(Transport::Udp) => false,
+            _ => return,
+        };
+        if packet.src_port != Some(dns::PORT) && packet.dst_port != Some(dns::PORT) {
+            return;
+        }
+        let Some(message) = dns::parse(packet.payload(), over_tcp) else {
+            return;
+        };
+        let name = message
+            .questions
+            .first()
+            .map(|(name, _)| name.trim_end_matches('.').to_string())
+            .unwrap_or_default();
+
+        if !message.is_response {
+            if let Some(anomaly) = check_name(&name) {
+                let domain = base_domain(&name).to_string();
+                self.record(anomaly, domain, name, index, packet.id);
+            }
+            return;
+        }
+        // NXDOMAIN
+        if message.rcode != 3 {
+            return;
+        }
+        let Some(Ok(client)) = packet.dst_addr else {
+            return;
+        };
+        let times = self.nxdomains.entry(client).or_default();
+        times.push_back(packet.captured_at);
+        while let Some(&first) = times.front() {
+            match packet.captured_at.duration_since(first) {
+                Ok(age) if age > NXDOMAIN_WINDOW => times.pop_front(),
+                _ => break,
+            };
+        }
+        if times.len() >= NXDOMAIN_BURST {
+            let subject = client.to_string();
+            self.record(DnsAnomaly::NxdomainBurst, subject, name, index, packet.id);
+        }
+    }
+
+    fn record(
+        &mut self,
+        anomaly: DnsAnomaly,
+        subject: String,
+        example: String,
+        index: usize,
+        id: usize,
+    ) {
+        self.anomalies.insert(index, anomaly);
+        let group = *self
+            .groups
+            .entry((anomaly, subject.clone()))
+            .or_insert_with(|| {
+                self.findings.push(DnsFinding {
+                    anomaly,
+                    subject,
+                    example: String::new(),
+                    packets: 0,
+                    index,
+                    id,
+                });
+                self.findings.len() - 1
+            });
+        let finding = &mut self.findings[group];
+        finding.example = example;
+        finding.packets += 1;
+        finding.index = index;
+        finding.id = id;
+    }
+
+    pub fn anomaly(&self, index: usize) -> Option<DnsAnomaly> {
+        self.anomalies.get(&index).copied()
+    }
+
+    /// Forgets the tags of packets before `start`; findings stay.
+    pub fn evict(&mut self, start: usize) {
+        self.anomalies.retain(|index, _| *index >= start);
+    }
+
+    pub fn clear(&mut self) {
+        self.nxdomains.clear();
+        self.anomalies.clear();
+        self.groups.clear();
+        self.findings.clear();
+    }
+}
@@ -0,0 +1,182 @@
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    time::Duration,
+};
+
+use super::{
+    dns,
+    latency::{Endpoint, Matched, Matcher, Service},
+    packet::{PacketInfo, Transport},
+};
+
+/// Reply code of a response saying the name does not exist.
+const NXDOMAIN: u8 = 3;
+
+/// Names and resolvers listed on the DNS page.
+pub const TOP: usize = 10;
+
+/// A query and, once matched by transaction ID and addresses, its response.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    pub query: usize,            // Packet ID of the query
+    pub response: Option<usize>, // Packet ID of the response, if seen
+    pub client: Endpoint,
+    pub server: Endpoint,
+    pub name: String,
+    pub qtype: u16,
+    pub rcode: Option<u8>,
+    pub latency: Option<Duration>,
+    pub retransmissions: usize, // Queries repeated before the response
+}
+
+/// Queries and response times of one resolver.
+#[derive(Debug, Clone)]
+pub struct Resolver {
+    pub addr: IpAddr,
+    pub queries: usize,
+    pub nxdomain: usize,
+    samples: Vec<Duration>, // Sorted
+}
+
+impl Resolver {
+    pub fn unanswered(&self) -> usize {
+        self.queries.saturating_sub(self.samples.len())
+    }
+
+    /// The response time `percent` of the answered queries were within, by
+    /// the nearest-rank method.
+    pub fn percentile(&self, percent: u32) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let rank = (self.samples.len() * percent as usize).div_ceil(100);
+        Some(self.samples[rank.clamp(1, self.samples.len()) - 1])
+    }
+}
+
+/// Unicast DNS queries matched to their responses, with the names asked
+/// most and the resolvers answering slowest.
+#[derive(Debug, Clone, Default)]
+pub struct DnsStats {
+    pub transactions: Vec<Transaction>, // In the order the queries were sent
+    pub names: Vec<(String, usize)>,    // Most queried first
+    pub resolvers: Vec<Resolver>,       // Slowest p95 first
+    pub responses: usize,
+    pub nxdomain: usize,
+}
+
+impl DnsStats {
+    pub fn from_packets(packets: &[&PacketInfo]) -> Self {
+        let mut stats = Self::default();
+        // Queries are matched by client, server and ID, the same over TCP
+        // and UDP since a truncated answer is retried over TCP.
+        let mut matcher = Matcher::default();
+        let mut names: HashMap<String, usize> = HashMap::new();
+        let mut resolvers: HashMap<IpAddr, Resolver> = HashMap::new();
+        for packet in packets {
+            let Some(matched) = matcher
+                .add(packet, stats.transactions.len())
+                .filter(|matched| matched.service() == Service::Dns)
+            else {
+                continue;
+            };
+            let over_tcp = packet.transport == Some(Transport::Tcp);
+            let Some(message) = dns::parse(packet.payload(), over_tcp) else {
+                continue;
+            };
+            match matched {
+                Matched::Request {
+                    repeats: Some(index),
+                    ..
+                } => stats.transactions[index].retransmissions += 1,
+                Matched::Request { client, server, .. } => {
+                    let (name, qtype) = message.questions.first().cloned().unwrap_or_default();
+                    *names.entry(name.clone()).or_default() += 1;
+                    resolvers
+                        .entry(server.0)
+                        .or_insert_with(|| Resolver {
+                            addr: server.0,
+                            queries: 0,
+                            nxdomain: 0,
+                            samples: Vec::new(),
+                        })
+                        .queries += 1;
+                    stats.transactions.push(Transaction {
+                        query: packet.id,
+                        response: None,
+                        client,
+                        server,
+                        name,
+                        qtype,
+                        rcode: None,
+                        latency: None,
+                        retransmissions: 0,
+                    });
+                }
+                Matched::Response {
+                    server, answers, ..
+                } => {
+                    stats.responses += 1;
+                    if message.rcode == NXDOMAIN {
+                        stats.nxdomain += 1;
+                    }
+                    let Some((index, latency)) = answers else {
+                        continue;
+                    };
+                    let transaction = &mut stats.transactions[index];
+                    transaction.response = Some(packet.id);
+                    transaction.rcode = Some(message.rcode);
+                    transaction.latency = Some(latency);
+                    if let Some(resolver) = resolvers.get_mut(&server.0) {
+                        resolver.samples.push(latency);
+                        if message.rcode == NXDOMAIN {
+                            resolver.nxdomain += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut names: Vec<(String, usize)> = names
+            .into_iter()
+            .filter(|(name, _)| !name.is_empty())
+            .collect();
+        names.sort_by(|x, y| y.1.cmp(&x.1).then_with(|| x.0.cmp(&y.0)));
+        names.truncate(TOP);
+        stats.names = names;
+
+        let mut resolvers: Vec<Resolver> = resolvers.into_values().collect();
+        for resolver in &mut resolvers {
+            resolver.samples.sort();
+        }
+        resolvers.sort_by(|x, y| {
+            y.percentile(95)
+                .cmp(&x.percentile(95))
+                .then(x.addr.cmp(&y.addr))
+        });
+        stats.resolvers = resolvers;
+        stats
+    }
+
+    pub fn unanswered(&self) -> usize {
+        self.transactions
+            .iter()
+            .filter(|transaction| transaction.response.is_none())
+            .count()
+    }
+
+    /// Share of responses that said the name does not exist.
+    pub fn nxdomain_rate(&self) -> Option<f64> {
+        (self.responses > 0).then(|| self.nxdomain as f64 / self.responses as f64)
+    }
+
+    /// Distinct names queried.
+    pub fn distinct_names(&self) -> usize {
+        self.transactions
+            .iter()
+            .map(|transaction| transaction.name.as_str())
+            .collect::<HashSet<_>>()
+            .len()
+    }
+}
@@ -0,0 +1,203 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    time::{Duration, SystemTime},
+};
+
+use super::{
+    dns::{self, RecordData},
+    flow::Endpoint,
+    packet::{PacketInfo, Transport},
+};
+
+/// Connection attempts to the same host and port starting within this
+/// window of the first one are treated as one Happy Eyeballs race.
+const RACE_WINDOW: Duration = Duration::from_secs(10);
+
+/// One TCP connection attempt, from the first SYN to the SYN-ACK.
+#[derive(Debug, Clone)]
+pub struct Attempt {
+    pub server: IpAddr,
+    pub started: SystemTime,
+    pub connected: Option<SystemTime>,
+}
+
+/// Parallel IPv4/IPv6 connection attempts to one resolved host name.
+#[derive(Debug, Clone)]
+pub struct Race {
+    pub host: String,
+    pub port: u16,
+    pub resolved_v4: bool,
+    pub resolved_v6: bool,
+    pub attempts: Vec<Attempt>,
+}
+
+impl Race {
+    fn started(&self) -> SystemTime {
+        self.attempts[0].started
+    }
+
+    pub fn attempts_by_family(&self) -> (usize, usize) {
+        let v6 = self.attempts.iter().filter(|a| a.server.is_ipv6()).count();
+        (self.attempts.len() - v6, v6)
+    }
+
+    /// The attempt whose handshake completed first.
+    pub fn winner(&self) -> Option<&Attempt> {
+        self.attempts
+            .iter()
+            .filter(|a| a.connected.is_some())
+            .min_by_key(|a| a.connected)
+    }
+
+    /// Time from the first attempt until the other family was first tried.
+    pub fn fallback_delay(&self) -> Option<Duration> {
+        let first = &self.attempts[0];
+        let other = self
+            .attempts
+            .iter()
+            .find(|a| a.server.is_ipv6() != first.server.is_ipv6())?;
+        other.started.duration_since(first.started).ok()
+    }
+
+    /// Time from the first attempt until any handshake completed.
+    pub fn connect_time(&self) -> Option<Duration> {
+        self.winner()?
+            .connected?
+            .duration_since(self.started())
+            .ok()
+    }
+
+    pub fn verdict(&self) -> &'static str {
+        let (v4, v6) = self.attempts_by_family();
+        let winner_v6 = self.winner().map(|a| a.server.is_ipv6());
+        match (v4 > 0, v6 > 0, winner_v6) {
+            (true, true, Some(true)) => "IPv6 won",
+            (true, true, Some(false)) if self.attempts[0].server.is_ipv6() => "Fell back to IPv4",
+            (true, true, Some(false)) => "IPv4 won",
+            (_, _, None) => "No connection",
+            (true, false, _) if self.resolved_v6 => "IPv4 only, AAAA ignored",
+            (true, false, _) => "IPv4 only",
+            (false, true, _) => "IPv6 only",
+            (false, false, _) => "No connection",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DualStackStats {
+    pub races: Vec<Race>,
+    names: HashMap<IpAddr, String>,
+    families: HashMap<String, (bool, bool)>, // Resolved A and AAAA per host
+    open_races: HashMap<(String, u16), usize>,
+    attempts: HashMap<(Endpoint, IpAddr, u16), (usize, usize)>,
+}
+
+impl DualStackStats {
+    pub fn from_packets(packets: &[&PacketInfo]) -> Self {
+        let mut stats = Self::default();
+        for packet in packets {
+            stats.add(packet);
+        }
+        stats
+    }
+
+    pub fn add(&mut self, packet: &PacketInfo) {
+        if packet.has_layer("DNS") {
+            self.add_dns(packet);
+        }
+        let (Some(flags), Some(Ok(src)), Some(Ok(dst)), Some(src_port), Some(dst_port)) = (
+            packet.tcp_flags,
+            packet.src_addr.clone(),
+            packet.dst_addr.clone(),
+            packet.src_port,
+            packet.dst_port,
+        ) else {
+            return;
+        };
+        if !flags.syn {
+            return;
+        }
+
+        if flags.ack {
+            let client = Endpoint {
+                addr: Ok(dst),
+                port: Some(dst_port),
+            };
+            if let Some(&(race, attempt)) = self.attempts.get(&(client, src, src_port)) {
+                let attempt = &mut self.races[race].attempts[attempt];
+                attempt.connected.get_or_insert(packet.captured_at);
+            }
+            return;
+        }
+
+        let client = Endpoint {
+            addr: Ok(src),
+            port: Some(src_port),
+        };
+        let key = (client, dst, dst_port);
+        // Retransmitted SYNs belong to the attempt already recorded.
+        if self.attempts.contains_key(&key) {
+            return;
+        }
+        let Some(host) = self.names.get(&dst).cloned() else {
+            return;
+        };
+
+        let attempt = Attempt {
+            server: dst,
+            started: packet.captured_at,
+            connected: None,
+        };
+        let race_key = (host.clone(), dst_port);
+        let race = match self.open_races.get(&race_key) {
+            Some(&race)
+                if packet
+                    .captured_at
+                    .duration_since(self.races[race].started())
+                    .is_ok_and(|elapsed| elapsed <= RACE_WINDOW) =>
+            {
+                race
+            }
+            _ => {
+                let (resolved_v4, resolved_v6) =
+                    self.families.get(&host).copied().unwrap_or_default();
+                self.races.push(Race {
+                    host,
+                    port: dst_port,
+                    resolved_v4,
+                    resolved_v6,
+                    attempts: Vec::new(),
+                });
+                self.open_races.insert(race_key, self.races.len() - 1);
+                self.races.len() - 1
+            }
+        };
+        self.races[race].attempts.push(attempt);
+        let index = self.races[race].attempts.len() - 1;
+        self.attempts.insert(key, (race, index));
+    }
+
+    /// Remembers which host name each answered address belongs to. Answers
+    /// reached through a CNAME chain are attributed to the queried name.
+    fn add_dns(&mut self, packet: &PacketInfo) {
+        let over_tcp = packet.transport == Some(Transport::Tcp);
+        let Some(message) = dns::parse(packet.payload(), over_tcp) else {
+            return;
+        };
+        let Some((host, _)) = message.questions.first().filter(|_| message.is_response) else {
+            return;
+        };
+        for record in &message.answers {
+            if let RecordData::Address(addr) = record.data {
+                self.names.insert(addr, host.clone());
+                let families = self.families.entry(host.clone()).or_default();
+                if addr.is_ipv4() {
+                    families.0 = true;
+                } else {
+                    families.1 = true;
+                }
+            }
+        }
+    }
+}
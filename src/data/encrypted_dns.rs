@@ -0,0 +1,102 @@
+use std::net::IpAddr;
+
+use super::{layer::Layer, tls};
+
+/// DNS over TLS (RFC 7858).
+pub const DOT_PORT: u16 = 853;
+
+/// Public resolvers that serve DNS over HTTPS on their anycast addresses.
+const RESOLVERS: [(&str, &str); 16] = [
+    ("1.1.1.1", "Cloudflare"),
+    ("1.0.0.1", "Cloudflare"),
+    ("2606:4700:4700::1111", "Cloudflare"),
+    ("2606:4700:4700::1001", "Cloudflare"),
+    ("8.8.8.8", "Google"),
+    ("8.8.4.4", "Google"),
+    ("2001:4860:4860::8888", "Google"),
+    ("2001:4860:4860::8844", "Google"),
+    ("9.9.9.9", "Quad9"),
+    ("149.112.112.112", "Quad9"),
+    ("2620:fe::fe", "Quad9"),
+    ("208.67.222.222", "OpenDNS"),
+    ("208.67.220.220", "OpenDNS"),
+    ("94.140.14.14", "AdGuard"),
+    ("94.140.15.15", "AdGuard"),
+    ("185.228.168.168", "CleanBrowsing"),
+];
+
+/// Server names of well-known DNS over HTTPS endpoints.
+const DOH_HOSTS: [(&str, &str); 9] = [
+    ("cloudflare-dns.com", "Cloudflare"),
+    ("dns.google", "Google"),
+    ("dns.quad9.net", "Quad9"),
+    ("doh.opendns.com", "OpenDNS"),
+    ("dns.adguard.com", "AdGuard"),
+    ("dns.adguard-dns.com", "AdGuard"),
+    ("dns.nextdns.io", "NextDNS"),
+    ("doh.cleanbrowsing.org", "CleanBrowsing"),
+    ("doh.mullvad.net", "Mullvad"),
+];
+
+pub fn resolver_name(addr: IpAddr) -> Option<&'static str> {
+    RESOLVERS
+        .iter()
+        .find(|(ip, _)| ip.parse() == Ok(addr))
+        .map(|(_, name)| *name)
+}
+
+/// Matches the host itself and its subdomains, such as
+/// `mozilla.cloudflare-dns.com`.
+pub fn doh_host_name(server_name: &str) -> Option<&'static str> {
+    let server_name = server_name.to_ascii_lowercase();
+    DOH_HOSTS
+        .iter()
+        .find(|(host, _)| {
+            server_name == *host
+                || server_name
+                    .strip_suffix(host)
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        })
+        .map(|(_, name)| *name)
+}
+
+/// Decodes TLS on the DoT port as DNS over TLS.
+pub fn decode_dot(payload: &[u8], peers: [Option<IpAddr>; 2]) -> Option<Layer> {
+    let records = tls::parse(payload)?;
+    let mut layer = tls::layer("DoT", &records);
+    layer.summary = format!("DNS over TLS: {}", layer.summary);
+    if let Some(provider) = peers.into_iter().flatten().find_map(resolver_name) {
+        layer.field("Resolver", provider);
+    }
+    Some(layer)
+}
+
+/// Decodes TLS on an HTTPS port, tagging it as likely DNS over HTTPS when
+/// the server name or the peer address belongs to a known DoH service.
+/// Only the ClientHello carries a server name, so the rest of a connection
+/// is recognised by address alone.
+pub fn decode_https(payload: &[u8], peers: [Option<IpAddr>; 2]) -> Option<Layer> {
+    let records = tls::parse(payload)?;
+    let by_name = records
+        .client_hello
+        .as_ref()
+        .and_then(|hello| hello.server_name.as_deref())
+        .and_then(doh_host_name);
+    let by_address = peers.into_iter().flatten().find_map(resolver_name);
+    let Some(provider) = by_name.or(by_address) else {
+        return Some(tls::layer("TLS", &records));
+    };
+
+    let mut layer = tls::layer("DoH", &records);
+    layer.summary = format!("Likely DNS over HTTPS ({provider}): {}", layer.summary);
+    layer.field("Resolver", provider);
+    layer.field(
+        "Identified By",
+        if by_name.is_some() {
+            "Server name"
+        } else {
+            "Resolver address"
+        },
+    );
+    Some(layer)
+}
@@ -0,0 +1,264 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    net::IpAddr,
+};
+
+use super::{
+    conversation::DirectionStats,
+    device_type::{Classification, Classifier},
+    flow::Endpoint,
+    knowledge::KnowledgeBase,
+    packet::{PacketInfo, Transport},
+};
+
+/// A port a host was seen serving on, inferred passively from traffic.
+#[derive(Debug, Clone)]
+pub struct Service {
+    pub transport: Transport,
+    pub port: u16,
+    pub handshakes: usize, // Completed TCP handshakes with this port as server
+    pub protocols: Vec<String>, // Application protocols decoded on it
+}
+
+impl Service {
+    /// Port and transport in the usual `80/tcp` form.
+    pub fn port_label(&self) -> String {
+        let transport = match self.transport {
+            Transport::Tcp => "tcp",
+            Transport::Udp => "udp",
+            Transport::Icmpv4 | Transport::Icmpv6 => "icmp",
+        };
+        format!("{}/{transport}", self.port)
+    }
+
+    /// Open when a handshake completed, otherwise only seen in use.
+    pub fn state(&self) -> &'static str {
+        if self.handshakes > 0 {
+            "open"
+        } else {
+            "observed"
+        }
+    }
+
+    pub fn name(&self) -> String {
+        if self.protocols.is_empty() {
+            "unknown".to_string()
+        } else {
+            self.protocols.join(", ")
+        }
+    }
+}
+
+/// Traffic sent and received by one address.
+#[derive(Debug, Clone)]
+pub struct Host {
+    pub addr: Result<IpAddr, String>,
+    pub tx: DirectionStats,
+    pub rx: DirectionStats,
+    pub services: Vec<Service>, // By transport and port
+    pub name: Option<String>,   // Alias or learned name from the knowledge base
+    pub device: Option<Classification>,
+}
+
+impl Host {
+    pub fn packets(&self) -> usize {
+        self.tx.packets + self.rx.packets
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.tx.bytes + self.rx.bytes
+    }
+
+    /// A display filter matching every packet to or from this host.
+    pub fn display_filter(&self) -> String {
+        match &self.addr {
+            Ok(ip) => format!("ip.addr == {ip}"),
+            Err(mac) => format!("eth.addr == {mac}"),
+        }
+    }
+}
+
+/// Per-address totals, busiest host first. Frames without an IP layer are
+/// counted against their MAC addresses.
+#[derive(Debug, Clone, Default)]
+pub struct EndpointStats {
+    pub hosts: Vec<Host>,
+    pub total_bytes: usize,
+}
+
+impl EndpointStats {
+    pub fn from_packets(packets: &[&PacketInfo]) -> Self {
+        let mut hosts: HashMap<Result<IpAddr, String>, Host> = HashMap::new();
+        let mut total_bytes = 0;
+        for packet in packets {
+            total_bytes += packet.length;
+            let sides = [(&packet.src_addr, true), (&packet.dst_addr, false)];
+            for (addr, sent) in sides {
+                let Some(addr) = addr else {
+                    continue;
+                };
+                let host = hosts.entry(addr.clone()).or_insert_with(|| Host {
+                    addr: addr.clone(),
+                    tx: DirectionStats::default(),
+                    rx: DirectionStats::default(),
+                    services: Vec::new(),
+                    name: None,
+                    device: None,
+                });
+                let direction = if sent { &mut host.tx } else { &mut host.rx };
+                direction.packets += 1;
+                direction.bytes += packet.length;
+            }
+        }
+
+        for (addr, service) in infer_services(packets) {
+            if let Some(host) = hosts.get_mut(&addr) {
+                host.services.push(service);
+            }
+        }
+        let classifier = Classifier::from_packets(packets);
+        for host in hosts.values_mut() {
+            host.services
+                .sort_by_key(|service| (service.transport as u8, service.port));
+            host.device = classifier.classify(&host.addr);
+        }
+
+        let mut hosts: Vec<Host> = hosts.into_values().collect();
+        hosts.sort_by(|a, b| b.bytes().cmp(&a.bytes()).then_with(|| a.addr.cmp(&b.addr)));
+        Self { hosts, total_bytes }
+    }
+
+    /// Names hosts the knowledge base recognizes.
+    pub fn name_hosts(&mut self, knowledge: &KnowledgeBase) {
+        for host in &mut self.hosts {
+            if let Ok(ip) = host.addr {
+                host.name = knowledge.name(ip).map(str::to_string);
+            }
+        }
+    }
+
+    /// A plain-text report of the services seen on each host, laid out like
+    /// a port scan but built only from observed traffic.
+    pub fn service_report(&self) -> String {
+        let hosts: Vec<&Host> = self
+            .hosts
+            .iter()
+            .filter(|host| !host.services.is_empty())
+            .collect();
+        let mut report = format!(
+            "# Passive service report: {} hosts with observed services\n\
+             # A port is open when a TCP handshake with it completed; other ports\n\
+             # were only seen carrying a decoded protocol.\n",
+            hosts.len()
+        );
+        for host in hosts {
+            let addr = match &host.addr {
+                Ok(ip) => ip.to_string(),
+                Err(mac) => mac.clone(),
+            };
+            let _ = writeln!(
+                report,
+                "\nHost {addr} ({} packets sent, {} received)",
+                host.tx.packets, host.rx.packets
+            );
+            if let Some(ref device) = host.device {
+                let _ = writeln!(
+                    report,
+                    "Device: {} [{}]",
+                    device.label(),
+                    device.evidence.join("; ")
+                );
+            }
+            let _ = writeln!(
+                report,
+                "{:<11}{:<10}{:<12}SERVICE",
+                "PORT", "STATE", "HANDSHAKES"
+            );
+            for service in &host.services {
+                let _ = writeln!(
+                    report,
+                    "{:<11}{:<10}{:<12}{}",
+                    service.port_label(),
+                    service.state(),
+                    service.handshakes,
+                    service.name()
+                );
+            }
+        }
+        report
+    }
+}
+
+/// Infers which side of the traffic is serving. A TCP port counts as a
+/// service once a handshake with it completes. A packet whose application
+/// protocol was decoded credits the protocol to the server side: the side
+/// already known to serve, or else the one with the lower port.
+fn infer_services(packets: &[&PacketInfo]) -> Vec<(Result<IpAddr, String>, Service)> {
+    let mut services: HashMap<Endpoint, Service> = HashMap::new();
+    let mut accepted: HashSet<(Endpoint, Endpoint)> = HashSet::new(); // Client, server after SYN-ACK
+    for packet in packets {
+        let (
+            Some(transport @ (Transport::Tcp | Transport::Udp)),
+            Some(src_addr),
+            Some(dst_addr),
+            Some(src_port),
+            Some(dst_port),
+        ) = (
+            packet.transport,
+            packet.src_addr.clone(),
+            packet.dst_addr.clone(),
+            packet.src_port,
+            packet.dst_port,
+        )
+        else {
+            continue;
+        };
+        let src = Endpoint {
+            addr: src_addr,
+            port: Some(src_port),
+        };
+        let dst = Endpoint {
+            addr: dst_addr,
+            port: Some(dst_port),
+        };
+        if let Some(flags) = packet.tcp_flags {
+            if flags.syn && flags.ack {
+                accepted.insert((dst.clone(), src.clone()));
+            } else if flags.ack && !flags.syn && accepted.remove(&(src.clone(), dst.clone())) {
+                service_at(&mut services, &dst, transport).handshakes += 1;
+            }
+        }
+        if packet.protocol == "TCP" || packet.protocol == "UDP" {
+            continue;
+        }
+        let server = if services.contains_key(&dst) {
+            &dst
+        } else if services.contains_key(&src) || src_port < dst_port {
+            &src
+        } else {
+            &dst
+        };
+        let protocols = &mut service_at(&mut services, server, transport).protocols;
+        if !protocols.contains(&packet.protocol) {
+            protocols.push(packet.protocol.clone());
+        }
+    }
+    services
+        .into_iter()
+        .map(|(server, service)| (server.addr, service))
+        .collect()
+}
+
+fn service_at<'a>(
+    services: &'a mut HashMap<Endpoint, Service>,
+    server: &Endpoint,
+    transport: Transport,
+) -> &'a mut Service {
+    services.entry(server.clone()).or_insert_with(|| Service {
+        transport,
+        port: server.port.unwrap_or_default(),
+        handshakes: 0,
+        protocols: Vec::new(),
+    })
+}
@@ -0,0 +1,122 @@
+use std::{collections::BTreeMap, time::SystemTime};
+
+use super::packet::PacketInfo;
+
+pub const IPV4: u16 = 0x0800;
+pub const ARP: u16 = 0x0806;
+pub const IPV6: u16 = 0x86dd;
+
+/// Names of EtherTypes seen often enough to be worth recognising; anything
+/// else is listed by its hex value.
+pub fn name(ethertype: u16) -> Option<&'static str> {
+    let name = match ethertype {
+        IPV4 => "IPv4",
+        ARP => "ARP",
+        0x0842 => "WoL",
+        0x22f0 => "AVTP",
+        0x22f3 => "TRILL",
+        0x6002 => "DEC MOP RC",
+        0x6558 => "Trans Ether Bridging",
+        0x8035 => "RARP",
+        0x809b => "AppleTalk",
+        0x80f3 => "AARP",
+        0x8100 => "802.1Q",
+        0x8137 => "IPX",
+        0x8808 => "Ethernet Flow Control",
+        0x8809 => "Slow Protocols",
+        0x8847 => "MPLS",
+        0x8848 => "MPLS Multicast",
+        0x8863 => "PPPoE Discovery",
+        0x8864 => "PPPoE Session",
+        0x886d => "Intel ANS",
+        0x887b => "HomePlug",
+        0x888e => "EAPOL",
+        0x8892 => "PROFINET",
+        0x889a => "HyperSCSI",
+        0x88a2 => "ATA over Ethernet",
+        0x88a4 => "EtherCAT",
+        0x88a8 => "802.1ad",
+        0x88ab => "Ethernet Powerlink",
+        0x88b5 | 0x88b6 => "Local Experimental",
+        0x88b8 => "GOOSE",
+        0x88ba => "Sampled Values",
+        0x88cc => "LLDP",
+        0x88cd => "SERCOS III",
+        0x88e1 => "HomePlug AV",
+        0x88e3 => "MRP",
+        0x88e5 => "MACsec",
+        0x88e7 => "PBB",
+        0x88f7 => "PTP",
+        0x88fb => "PRP",
+        0x8902 => "CFM",
+        0x8906 => "FCoE",
+        0x8914 => "FIP",
+        0x8915 => "RoCE",
+        0x891d => "TTE",
+        0x892f => "HSR",
+        0x9000 => "Loopback",
+        IPV6 => "IPv6",
+        _ => return None,
+    };
+    Some(name)
+}
+
+pub fn describe(ethertype: u16) -> String {
+    match name(ethertype) {
+        Some(name) => format!("0x{ethertype:04x} ({name})"),
+        None => format!("0x{ethertype:04x}"),
+    }
+}
+
+/// Frames of one EtherType other than IPv4, IPv6 and ARP.
+#[derive(Debug, Clone)]
+pub struct OtherL2Entry {
+    pub ethertype: u16,
+    pub packets: usize,
+    pub bytes: usize,
+    pub sources: Vec<String>,
+    pub first: SystemTime,
+    pub last: SystemTime,
+}
+
+/// The "Other L2" summary: everything that is neither IP nor ARP, grouped
+/// by EtherType in ascending order.
+#[derive(Debug, Clone, Default)]
+pub struct OtherL2Stats {
+    pub entries: BTreeMap<u16, OtherL2Entry>,
+}
+
+impl OtherL2Stats {
+    pub fn from_packets(packets: &[&PacketInfo]) -> Self {
+        let mut stats = Self::default();
+        for packet in packets {
+            stats.add(packet);
+        }
+        stats
+    }
+
+    pub fn add(&mut self, packet: &PacketInfo) {
+        let Some(ethertype) = packet.ethertype else {
+            return;
+        };
+        if matches!(ethertype, IPV4 | IPV6 | ARP) {
+            return;
+        }
+        let entry = self.entries.entry(ethertype).or_insert(OtherL2Entry {
+            ethertype,
+            packets: 0,
+            bytes: 0,
+            sources: Vec::new(),
+            first: packet.captured_at,
+            last: packet.captured_at,
+        });
+        entry.packets += 1;
+        entry.bytes += packet.length;
+        entry.last = packet.captured_at;
+        if let Some(Err(ref mac)) = packet.src_addr
+            && !entry.sources.contains(mac)
+        {
+            entry.sources.push(mac.clone());
+        }
+    }
+}
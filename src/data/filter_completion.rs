@@ -0,0 +1,142 @@
+use std::collections::BTreeSet;
+
+use super::packet::PacketInfo;
+
+/// Candidates offered at most for one word.
+pub const COMPLETION_LIMIT: usize = 10;
+
+/// Addresses and protocol names collected at most from a capture.
+const VOCABULARY_LIMIT: usize = 2_000;
+
+/// Words of the BPF syntax libpcap understands.
+const BPF_KEYWORDS: &[&str] = &[
+    "and",
+    "arp",
+    "broadcast",
+    "dst",
+    "ether",
+    "gateway",
+    "greater",
+    "host",
+    "icmp",
+    "icmp6",
+    "ip",
+    "ip6",
+    "less",
+    "multicast",
+    "net",
+    "not",
+    "or",
+    "port",
+    "portrange",
+    "proto",
+    "rarp",
+    "sctp",
+    "src",
+    "tcp",
+    "udp",
+    "vlan",
+];
+
+/// Operators, fields and protocols display filters understand; protocols
+/// decoded in the capture are added to these.
+const DISPLAY_KEYWORDS: &[&str] = &[
+    "and",
+    "arp",
+    "contains",
+    "eth.addr",
+    "eth.dst",
+    "eth.src",
+    "eth.type",
+    "frame.len",
+    "frame.number",
+    "icmp",
+    "icmpv6",
+    "info",
+    "ip",
+    "ip.addr",
+    "ip.dst",
+    "ip.src",
+    "ipv6",
+    "not",
+    "or",
+    "port",
+    "protocol",
+    "tcp",
+    "tcp.dstport",
+    "tcp.port",
+    "tcp.srcport",
+    "udp",
+    "udp.dstport",
+    "udp.port",
+    "udp.srcport",
+];
+
+/// Whether `c` ends a word of a filter.
+fn is_separator(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '(' | ')' | '=' | '!' | '<' | '>')
+}
+
+/// The start of the word that ends at `cursor` in `text`, and the word.
+pub fn word_at(text: &str, cursor: usize) -> (usize, &str) {
+    let before = &text[..cursor];
+    let start = before
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| is_separator(c))
+        .map(|(i, c)| i + c.len_utf8())
+        .unwrap_or(0);
+    (start, &before[start..])
+}
+
+/// Addresses seen in `packets`, and for display filters the names of the
+/// protocols decoded in them, to offer as completions.
+pub fn vocabulary<'a>(packets: impl Iterator<Item = &'a PacketInfo>, display: bool) -> Vec<String> {
+    let mut words = BTreeSet::new();
+    for packet in packets {
+        for addr in [&packet.src_addr, &packet.dst_addr].into_iter().flatten() {
+            words.insert(match addr {
+                Ok(ip) => ip.to_string(),
+                Err(mac) => mac.clone(),
+            });
+        }
+        if display {
+            for layer in &packet.layers {
+                words.insert(layer.name.to_lowercase().replace(' ', "_"));
+            }
+        }
+        if words.len() >= VOCABULARY_LIMIT {
+            break;
+        }
+    }
+    words.into_iter().collect()
+}
+
+/// Completions of `prefix`: keywords of the filter kind first, then words
+/// from the capture, leaving out the prefix itself.
+pub fn complete(prefix: &str, display: bool, vocabulary: &[String]) -> Vec<String> {
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    let keywords = if display {
+        DISPLAY_KEYWORDS
+    } else {
+        BPF_KEYWORDS
+    };
+    let prefix = prefix.to_lowercase();
+    let wanted = |word: &str| word.to_lowercase().starts_with(&prefix) && word != prefix;
+    let mut candidates: Vec<String> = Vec::new();
+    let words = keywords
+        .iter()
+        .copied()
+        .chain(vocabulary.iter().map(String::as_str));
+    for word in words.filter(|word| wanted(word)) {
+        if !candidates.iter().any(|c| c == word) {
+            candidates.push(word.to_string());
+        }
+        if candidates.len() == COMPLETION_LIMIT {
+            break;
+        }
+    }
+    candidates
+}
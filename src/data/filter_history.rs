@@ -0,0 +1,60 @@
+use std::{env, fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Filters remembered of each kind; the oldest are forgotten first.
+const HISTORY_LIMIT: usize = 100;
+
+/// The directory settings are kept in across sessions: `$XDG_CONFIG_HOME`,
+/// `~/.config` or `%APPDATA%`, under `sniffer`.
+pub fn config_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+    Some(base.join("sniffer"))
+}
+
+/// Filters applied in earlier sessions, oldest first, one per line in a
+/// file of the config directory.
+#[derive(Debug, Clone, Default)]
+pub struct FilterHistory {
+    path: Option<PathBuf>, // None without a config directory
+    pub entries: Vec<String>,
+}
+
+impl FilterHistory {
+    /// Reads the history kept in `file_name`, empty if there is none yet
+    /// or it can't be read.
+    pub fn load(file_name: &str) -> Self {
+        let path = config_dir().map(|dir| dir.join(file_name));
+        let entries = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|text| text.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+        Self { path, entries }
+    }
+
+    /// Makes `filter` the newest entry, moving it there if already present.
+    pub fn add(&mut self, filter: &str) {
+        self.entries.retain(|entry| entry != filter);
+        self.entries.push(filter.to_string());
+        let excess = self.entries.len().saturating_sub(HISTORY_LIMIT);
+        self.entries.drain(..excess);
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let mut text = self.entries.join("\n");
+        text.push('\n');
+        fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
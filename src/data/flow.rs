@@ -0,0 +1,83 @@
+use std::{fmt, net::IpAddr};
+
+use super::{
+    icmp::quoted_flow,
+    packet::{PacketInfo, Transport},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Endpoint {
+    pub addr: Result<IpAddr, String>,
+    pub port: Option<u16>,
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.addr, self.port) {
+            (Ok(IpAddr::V6(ip)), Some(port)) => write!(f, "[{ip}]:{port}"),
+            (Ok(ip), Some(port)) => write!(f, "{ip}:{port}"),
+            (Ok(ip), None) => write!(f, "{ip}"),
+            (Err(hw_addr), _) => write!(f, "{hw_addr}"),
+        }
+    }
+}
+
+/// Identifies a conversation regardless of direction: the two endpoints are
+/// stored in sorted order so both directions map to the same key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FlowKey {
+    pub transport: Option<Transport>,
+    pub a: Endpoint,
+    pub b: Endpoint,
+}
+
+impl FlowKey {
+    pub fn from_packet(packet: &PacketInfo) -> Option<Self> {
+        let src = Endpoint {
+            addr: packet.src_addr.clone()?,
+            port: packet.src_port,
+        };
+        let dst = Endpoint {
+            addr: packet.dst_addr.clone()?,
+            port: packet.dst_port,
+        };
+        Some(Self::new(packet.transport, src, dst))
+    }
+
+    fn new(transport: Option<Transport>, src: Endpoint, dst: Endpoint) -> Self {
+        let (a, b) = if src <= dst { (src, dst) } else { (dst, src) };
+        Self { transport, a, b }
+    }
+
+    /// The flow of the packet an ICMP error reports on, read from the
+    /// original headers quoted in the error.
+    pub fn from_icmp_error(packet: &PacketInfo) -> Option<Self> {
+        let quoted = packet.data.get(packet.quoted.clone()?)?;
+        let (transport, src, dst) = quoted_flow(quoted)?;
+        Some(Self::new(transport, src, dst))
+    }
+
+    pub fn contains(&self, packet: &PacketInfo) -> bool {
+        Self::from_packet(packet).as_ref() == Some(self)
+    }
+
+    /// Whether `packet` belongs to this flow or is an ICMP error about one
+    /// of its packets.
+    pub fn includes(&self, packet: &PacketInfo) -> bool {
+        self.contains(packet) || Self::from_icmp_error(packet).as_ref() == Some(self)
+    }
+}
+
+impl fmt::Display for FlowKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let transport = match self.transport {
+            Some(Transport::Tcp) => "TCP",
+            Some(Transport::Udp) => "UDP",
+            Some(Transport::Icmpv4) => "ICMPv4",
+            Some(Transport::Icmpv6) => "ICMPv6",
+            None if self.a.addr.is_ok() => "IP",
+            None => "L2",
+        };
+        write!(f, "{transport} {} ↔ {}", self.a, self.b)
+    }
+}
@@ -0,0 +1,249 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, SystemTime},
+};
+
+use super::{
+    conversation::Conversation,
+    flow::FlowKey,
+    packet::{PacketInfo, Transport},
+};
+
+/// Ended flows kept for review; older ones are dropped first.
+pub const ARCHIVE_LIMIT: usize = 10_000;
+
+/// How long a TCP connection closed from both sides waits for its final ACK
+/// before it is archived.
+const CLOSE_LINGER: Duration = Duration::from_secs(2);
+
+/// Idle flows are looked for at most this often, in capture time.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long a flow may stay idle before it is considered over.
+#[derive(Debug, Clone, Copy)]
+pub struct FlowTimeouts {
+    pub tcp: Duration,
+    pub other: Duration,
+}
+
+impl Default for FlowTimeouts {
+    fn default() -> Self {
+        Self {
+            tcp: Duration::from_secs(300),
+            other: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndReason {
+    Fin,
+    Reset,
+    Timeout,
+}
+
+impl EndReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            EndReason::Fin => "FIN",
+            EndReason::Reset => "RST",
+            EndReason::Timeout => "Timeout",
+        }
+    }
+}
+
+/// Where a flow still receiving packets is in its life.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FlowState {
+    Handshake,   // TCP, no ACK yet without SYN
+    Established, // TCP, past its handshake or picked up midway
+    Closing,     // TCP, FIN sent from both sides
+    Open,        // Anything but TCP
+}
+
+impl FlowState {
+    pub fn label(self) -> &'static str {
+        match self {
+            FlowState::Handshake => "Handshake",
+            FlowState::Established => "Established",
+            FlowState::Closing => "Closing",
+            FlowState::Open => "Open",
+        }
+    }
+}
+
+/// A flow still receiving packets.
+#[derive(Debug, Clone)]
+pub struct ActiveFlow {
+    pub conversation: Conversation,
+    established: bool,
+    fin_from_a: bool,
+    fin_from_b: bool,
+}
+
+impl ActiveFlow {
+    /// Both sides have sent FIN; only the final ACK is still expected.
+    pub fn is_closing(&self) -> bool {
+        self.fin_from_a && self.fin_from_b
+    }
+
+    pub fn state(&self) -> FlowState {
+        if self.conversation.key.transport != Some(Transport::Tcp) {
+            FlowState::Open
+        } else if self.is_closing() {
+            FlowState::Closing
+        } else if self.established {
+            FlowState::Established
+        } else {
+            FlowState::Handshake
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EndedFlow {
+    pub conversation: Conversation,
+    pub reason: EndReason,
+}
+
+impl EndedFlow {
+    pub fn summary(&self) -> String {
+        let conversation = &self.conversation;
+        format!(
+            "{}: {} packets, {} bytes in {:.3}s, ended by {}",
+            conversation.key,
+            conversation.packets(),
+            conversation.bytes(),
+            conversation.duration().as_secs_f64(),
+            self.reason.label()
+        )
+    }
+}
+
+/// Follows flows as packets arrive, moving each one to a bounded archive when
+/// TCP tears it down or it goes idle, so state stays small on long captures.
+/// Time is taken from packet timestamps, which keeps file replays faithful.
+#[derive(Debug, Clone, Default)]
+pub struct FlowTracker {
+    timeouts: FlowTimeouts,
+    active: HashMap<FlowKey, ActiveFlow>,
+    ended: VecDeque<EndedFlow>,
+    dropped: usize, // Ended flows pushed out of the archive
+    last_sweep: Option<SystemTime>,
+}
+
+impl FlowTracker {
+    pub fn set_timeouts(&mut self, timeouts: FlowTimeouts) {
+        self.timeouts = timeouts;
+    }
+
+    pub fn clear(&mut self) {
+        self.active.clear();
+        self.ended.clear();
+        self.dropped = 0;
+        self.last_sweep = None;
+    }
+
+    pub fn add(&mut self, packet: &PacketInfo) {
+        self.expire(packet.captured_at);
+        let Some(key) = FlowKey::from_packet(packet) else {
+            return;
+        };
+        let flags = packet.tcp_flags.unwrap_or_default();
+        // A new SYN on the ports of a closing connection starts a new one.
+        if flags.syn && !flags.ack && self.active.get(&key).is_some_and(ActiveFlow::is_closing) {
+            self.end(&key, EndReason::Fin);
+        }
+        let flow = self
+            .active
+            .entry(key.clone())
+            .or_insert_with(|| ActiveFlow {
+                conversation: Conversation::new(key.clone(), packet.captured_at),
+                established: false,
+                fin_from_a: false,
+                fin_from_b: false,
+            });
+        flow.established |= flags.ack && !flags.syn;
+        if flags.fin {
+            if flow.conversation.is_from_a(packet) {
+                flow.fin_from_a = true;
+            } else {
+                flow.fin_from_b = true;
+            }
+        }
+        flow.conversation.add(packet);
+        if flags.rst {
+            self.end(&key, EndReason::Reset);
+        }
+    }
+
+    /// Archives flows idle since before `now`, oldest first. Live captures
+    /// call this on every tick so quiet links still expire.
+    pub fn expire(&mut self, now: SystemTime) {
+        if self
+            .last_sweep
+            .is_some_and(|at| now.duration_since(at).unwrap_or_default() < SWEEP_INTERVAL)
+        {
+            return;
+        }
+        self.last_sweep = Some(now);
+
+        let mut expired: Vec<(SystemTime, FlowKey, EndReason)> = self
+            .active
+            .iter()
+            .filter_map(|(key, flow)| {
+                let (limit, reason) = if flow.is_closing() {
+                    (CLOSE_LINGER, EndReason::Fin)
+                } else if key.transport == Some(Transport::Tcp) {
+                    (self.timeouts.tcp, EndReason::Timeout)
+                } else {
+                    (self.timeouts.other, EndReason::Timeout)
+                };
+                let idle = now
+                    .duration_since(flow.conversation.last)
+                    .unwrap_or_default();
+                (idle >= limit).then(|| (flow.conversation.last, key.clone(), reason))
+            })
+            .collect();
+        expired.sort_by_key(|(last, _, _)| *last);
+        for (_, key, reason) in expired {
+            self.end(&key, reason);
+        }
+    }
+
+    fn end(&mut self, key: &FlowKey, reason: EndReason) {
+        let Some(flow) = self.active.remove(key) else {
+            return;
+        };
+        self.ended.push_back(EndedFlow {
+            conversation: flow.conversation,
+            reason,
+        });
+        if self.ended.len() > ARCHIVE_LIMIT {
+            self.ended.pop_front();
+            self.dropped += 1;
+        }
+    }
+
+    pub fn active(&self) -> impl Iterator<Item = &ActiveFlow> {
+        self.active.values()
+    }
+
+    /// Ended flows in the order they ended.
+    pub fn ended(&self) -> &VecDeque<EndedFlow> {
+        &self.ended
+    }
+
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Flows ended since the last clear, including those no longer archived.
+    pub fn ended_total(&self) -> usize {
+        self.ended.len() + self.dropped
+    }
+
+    pub fn is_active(&self, key: &FlowKey) -> bool {
+        self.active.contains_key(key)
+    }
+}
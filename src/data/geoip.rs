@@ -0,0 +1,124 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    path::PathBuf,
+    sync::{Mutex, OnceLock, PoisonError},
+};
+
+use anyhow::{Context, Result, bail};
+use maxminddb::{Reader, geoip2};
+
+/// Addresses whose lookups are kept before the cache starts over.
+const CACHE_LIMIT: usize = 65_536;
+
+/// Country and autonomous system of an address.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Location {
+    pub country: Option<String>, // ISO 3166 code
+    pub asn: Option<u32>,
+    pub organization: Option<String>,
+}
+
+impl Location {
+    /// The short form for list columns, such as `US AS15169`.
+    pub fn short(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(ref country) = self.country {
+            parts.push(country.clone());
+        }
+        if let Some(asn) = self.asn {
+            parts.push(format!("AS{asn}"));
+        }
+        parts.join(" ")
+    }
+
+    /// The long form for the detail page, such as `US, AS15169 Google LLC`.
+    pub fn describe(&self) -> String {
+        let mut asn = self.asn.map(|asn| format!("AS{asn}"));
+        if let (Some(asn), Some(organization)) = (&mut asn, &self.organization) {
+            asn.push(' ');
+            asn.push_str(organization);
+        }
+        [self.country.clone(), asn]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// GeoLite2 country (or city) and ASN databases with the lookups made so
+/// far, since the same few addresses come up in packet after packet.
+struct GeoIp {
+    country: Option<Reader<Vec<u8>>>,
+    asn: Option<Reader<Vec<u8>>>,
+    cache: Mutex<HashMap<IpAddr, Option<Location>>>,
+}
+
+static GEOIP: OnceLock<GeoIp> = OnceLock::new();
+
+/// Opens the MaxMind databases at `paths`, telling country, city and ASN
+/// databases apart by their metadata, and enables lookups with them.
+pub fn init(paths: &[PathBuf]) -> Result<()> {
+    let mut geoip = GeoIp {
+        country: None,
+        asn: None,
+        cache: Mutex::new(HashMap::new()),
+    };
+    for path in paths {
+        let reader = Reader::open_readfile(path)
+            .with_context(|| format!("Failed to open GeoIP database {}", path.display()))?;
+        let kind = &reader.metadata.database_type;
+        if kind.contains("ASN") {
+            geoip.asn = Some(reader);
+        } else if kind.contains("Country") || kind.contains("City") {
+            geoip.country = Some(reader);
+        } else {
+            bail!(
+                "{} is a {kind} database, not a country, city or ASN one",
+                path.display()
+            );
+        }
+    }
+    let _ = GEOIP.set(geoip);
+    Ok(())
+}
+
+pub fn is_enabled() -> bool {
+    GEOIP.get().is_some()
+}
+
+/// Where `addr` is, or `None` without databases or for addresses they
+/// don't cover, such as private ones.
+pub fn lookup(addr: IpAddr) -> Option<Location> {
+    let geoip = GEOIP.get()?;
+    let mut cache = geoip.cache.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(location) = cache.get(&addr) {
+        return location.clone();
+    }
+
+    let country = geoip.country.as_ref().and_then(|reader| {
+        let country: geoip2::Country = reader.lookup(addr).ok()?;
+        let country = country.country.or(country.registered_country)?;
+        country.iso_code.map(str::to_string)
+    });
+    let asn = geoip
+        .asn
+        .as_ref()
+        .and_then(|reader| reader.lookup::<geoip2::Asn>(addr).ok());
+    let location = Location {
+        country,
+        asn: asn.as_ref().and_then(|asn| asn.autonomous_system_number),
+        organization: asn
+            .as_ref()
+            .and_then(|asn| asn.autonomous_system_organization)
+            .map(str::to_string),
+    };
+    let location = (location != Location::default()).then_some(location);
+
+    if cache.len() >= CACHE_LIMIT {
+        cache.clear();
+    }
+    cache.insert(addr, location.clone());
+    location
+}
@@ -0,0 +1,85 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
+
+use super::{
+    flow::FlowKey,
+    packet::{PacketInfo, Transport},
+};
+
+/// A connection between its first SYN and the ACK that completes it.
+#[derive(Debug)]
+struct Opening {
+    syn: SystemTime,
+    from_a: bool,  // Whether the SYN was sent from the key's `a`
+    syn_ack: bool, // Whether the server has answered
+}
+
+/// Times TCP handshakes as packets are captured: the SYN-ACK is tagged
+/// with how long the server took to answer the SYN, and the ACK that
+/// completes the handshake with the time since the SYN.
+#[derive(Debug, Default)]
+pub struct HandshakeTimer {
+    openings: HashMap<FlowKey, Opening>,
+    times: HashMap<usize, Duration>, // Since the SYN, by packet index
+}
+
+impl HandshakeTimer {
+    pub fn add(&mut self, index: usize, packet: &PacketInfo) {
+        if packet.transport != Some(Transport::Tcp) {
+            return;
+        }
+        let Some(flags) = packet.tcp_flags else {
+            return;
+        };
+        let Some(key) = FlowKey::from_packet(packet) else {
+            return;
+        };
+        let from_a = packet.src_addr.as_ref() == Some(&key.a.addr) && packet.src_port == key.a.port;
+        if flags.rst {
+            self.openings.remove(&key);
+            return;
+        }
+        if flags.syn && !flags.ack {
+            // A retransmitted SYN keeps the time of the first.
+            self.openings.entry(key).or_insert(Opening {
+                syn: packet.captured_at,
+                from_a,
+                syn_ack: false,
+            });
+            return;
+        }
+        let Some(opening) = self.openings.get_mut(&key) else {
+            return;
+        };
+        let since = packet
+            .captured_at
+            .duration_since(opening.syn)
+            .unwrap_or_default();
+        if flags.syn && flags.ack && from_a != opening.from_a {
+            if !opening.syn_ack {
+                opening.syn_ack = true;
+                self.times.insert(index, since);
+            }
+        } else if flags.ack && opening.syn_ack && from_a == opening.from_a {
+            self.times.insert(index, since);
+            self.openings.remove(&key);
+        }
+    }
+
+    /// Time since the SYN, for a SYN-ACK or the ACK completing a handshake.
+    pub fn time(&self, index: usize) -> Option<Duration> {
+        self.times.get(&index).copied()
+    }
+
+    /// Forgets the times of packets before `start`.
+    pub fn evict(&mut self, start: usize) {
+        self.times.retain(|index, _| *index >= start);
+    }
+
+    pub fn clear(&mut self) {
+        self.openings.clear();
+        self.times.clear();
+    }
+}
@@ -0,0 +1,229 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use etherparse::{EtherType, InternetSlice, LinkExtSlice, LinkSlice, SlicedPacket, TransportSlice};
+
+use super::{
+    ethertype,
+    layer::Layer,
+    link,
+    packet::{PacketInfo, format_mac, range_in},
+    wlan,
+};
+
+fn be16(header: &[u8], at: usize) -> u16 {
+    u16::from_be_bytes([header[at], header[at + 1]])
+}
+
+fn be32(header: &[u8], at: usize) -> u32 {
+    u32::from_be_bytes([header[at], header[at + 1], header[at + 2], header[at + 3]])
+}
+
+fn ethernet(header: &[u8]) -> Layer {
+    let destination = format_mac(&header[0..6]);
+    let source = format_mac(&header[6..12]);
+    let mut layer = Layer::new("Ethernet", format!("{source} → {destination}"));
+    layer.field_at(0..6, "Destination", destination);
+    layer.field_at(6..12, "Source", source);
+    layer.field_at(12..14, "EtherType", ethertype::describe(be16(header, 12)));
+    layer
+}
+
+fn vlan(header: &[u8]) -> Layer {
+    let tci = be16(header, 0);
+    let id = tci & 0x0fff;
+    let mut layer = Layer::new("802.1Q", format!("VLAN {id}"));
+    layer.field_at(0..1, "Priority", (tci >> 13).to_string());
+    layer.field_at(0..2, "VLAN ID", id.to_string());
+    layer.field_at(2..4, "EtherType", ethertype::describe(be16(header, 2)));
+    layer
+}
+
+fn ipv4(header: &[u8]) -> Layer {
+    let source = Ipv4Addr::from(be32(header, 12));
+    let destination = Ipv4Addr::from(be32(header, 16));
+    let header_len = usize::from(header[0] & 0x0f) * 4;
+    let flags = be16(header, 6);
+    let mut layer = Layer::new("IPv4", format!("{source} → {destination}"));
+    layer.field_at(0..1, "Header Length", format!("{header_len} bytes"));
+    layer.field_at(1..2, "DSCP/ECN", format!("0x{:02x}", header[1]));
+    layer.field_at(2..4, "Total Length", be16(header, 2).to_string());
+    layer.field_at(4..6, "Identification", format!("0x{:04x}", be16(header, 4)));
+    layer.field_at(6..8, "Flags", format!("0x{:x}", flags >> 13));
+    layer.field_at(6..8, "Fragment Offset", ((flags & 0x1fff) * 8).to_string());
+    layer.field_at(8..9, "TTL", header[8].to_string());
+    layer.field_at(9..10, "Protocol", header[9].to_string());
+    layer.field_at(10..12, "Checksum", format!("0x{:04x}", be16(header, 10)));
+    layer.field_at(12..16, "Source", source.to_string());
+    layer.field_at(16..20, "Destination", destination.to_string());
+    if header_len > 20 {
+        layer.field_at(
+            20..header_len,
+            "Options",
+            format!("{} bytes", header_len - 20),
+        );
+    }
+    layer
+}
+
+fn ipv6(header: &[u8]) -> Layer {
+    let source = Ipv6Addr::from(<[u8; 16]>::try_from(&header[8..24]).unwrap_or_default());
+    let destination = Ipv6Addr::from(<[u8; 16]>::try_from(&header[24..40]).unwrap_or_default());
+    let first = be32(header, 0);
+    let mut layer = Layer::new("IPv6", format!("{source} → {destination}"));
+    layer.field_at(
+        0..2,
+        "Traffic Class",
+        format!("0x{:02x}", first >> 20 & 0xff),
+    );
+    layer.field_at(1..4, "Flow Label", format!("0x{:05x}", first & 0xfffff));
+    layer.field_at(4..6, "Payload Length", be16(header, 4).to_string());
+    layer.field_at(6..7, "Next Header", header[6].to_string());
+    layer.field_at(7..8, "Hop Limit", header[7].to_string());
+    layer.field_at(8..24, "Source", source.to_string());
+    layer.field_at(24..40, "Destination", destination.to_string());
+    layer
+}
+
+fn arp(packet: &[u8]) -> Layer {
+    let hardware_len = usize::from(packet[4]);
+    let protocol_len = usize::from(packet[5]);
+    let operation = match be16(packet, 6) {
+        1 => "Request".to_string(),
+        2 => "Reply".to_string(),
+        other => other.to_string(),
+    };
+    let mut layer = Layer::new("ARP", operation.clone());
+    layer.field_at(0..2, "Hardware Type", be16(packet, 0).to_string());
+    layer.field_at(2..4, "Protocol Type", ethertype::describe(be16(packet, 2)));
+    layer.field_at(6..8, "Operation", operation);
+    let mut at = 8;
+    for name in ["Sender", "Target"] {
+        let hardware = at..at + hardware_len;
+        let protocol = hardware.end..hardware.end + protocol_len;
+        at = protocol.end;
+        let (Some(mac), Some(addr)) = (packet.get(hardware.clone()), packet.get(protocol.clone()))
+        else {
+            break;
+        };
+        let addr = match <[u8; 4]>::try_from(addr) {
+            Ok(octets) => Ipv4Addr::from(octets).to_string(),
+            Err(_) => format_mac(addr),
+        };
+        layer.field_at(hardware, format!("{name} MAC"), format_mac(mac));
+        layer.field_at(protocol, format!("{name} Address"), addr);
+    }
+    layer
+}
+
+fn tcp(header: &[u8]) -> Layer {
+    let source = be16(header, 0);
+    let destination = be16(header, 2);
+    let header_len = usize::from(header[12] >> 4) * 4;
+    let mut layer = Layer::new("TCP", format!("{source} → {destination}"));
+    layer.field_at(0..2, "Source Port", source.to_string());
+    layer.field_at(2..4, "Destination Port", destination.to_string());
+    layer.field_at(4..8, "Sequence", be32(header, 4).to_string());
+    layer.field_at(8..12, "Acknowledgment", be32(header, 8).to_string());
+    layer.field_at(12..13, "Header Length", format!("{header_len} bytes"));
+    layer.field_at(
+        12..14,
+        "Flags",
+        format!("0x{:03x}", be16(header, 12) & 0x01ff),
+    );
+    layer.field_at(14..16, "Window", be16(header, 14).to_string());
+    layer.field_at(16..18, "Checksum", format!("0x{:04x}", be16(header, 16)));
+    layer.field_at(18..20, "Urgent Pointer", be16(header, 18).to_string());
+    if header_len > 20 {
+        layer.field_at(
+            20..header_len,
+            "Options",
+            format!("{} bytes", header_len - 20),
+        );
+    }
+    layer
+}
+
+fn udp(header: &[u8]) -> Layer {
+    let source = be16(header, 0);
+    let destination = be16(header, 2);
+    let mut layer = Layer::new("UDP", format!("{source} → {destination}"));
+    layer.field_at(0..2, "Source Port", source.to_string());
+    layer.field_at(2..4, "Destination Port", destination.to_string());
+    layer.field_at(4..6, "Length", be16(header, 4).to_string());
+    layer.field_at(6..8, "Checksum", format!("0x{:04x}", be16(header, 6)));
+    layer
+}
+
+fn icmp(name: &'static str, message: &[u8]) -> Layer {
+    let mut layer = Layer::new(name, format!("Type {}, code {}", message[0], message[1]));
+    layer.field_at(0..1, "Type", message[0].to_string());
+    layer.field_at(1..2, "Code", message[1].to_string());
+    layer.field_at(2..4, "Checksum", format!("0x{:04x}", be16(message, 2)));
+    if message.len() >= 8 {
+        layer.field_at(
+            4..8,
+            "Rest of Header",
+            format!("0x{:08x}", be32(message, 4)),
+        );
+    }
+    layer
+}
+
+/// The link, network and transport headers of a packet as layers whose
+/// fields know their bytes, for the protocol tree of the details page.
+/// Only the outermost headers are listed; what a tunnel carries is left to
+/// its layer.
+pub fn layers(packet: &PacketInfo) -> Vec<Layer> {
+    let data = &packet.data[..];
+    let framing = if wlan::is_wireless(packet.link_type) {
+        let Some(framing) = wlan::dissect(packet.link_type, data) else {
+            return Vec::new();
+        };
+        Some(framing)
+    } else {
+        link::decode(packet.link_type, data)
+    };
+    let sliced = match framing {
+        Some(framing) => match framing.payload {
+            Some((ether_type, payload)) => {
+                SlicedPacket::from_ether_type(EtherType(ether_type), payload)
+            }
+            None => return Vec::new(),
+        },
+        None => SlicedPacket::from_ethernet(data),
+    };
+    let Ok(sliced) = sliced else {
+        return Vec::new();
+    };
+
+    let mut layers = Vec::new();
+    let mut add = |layer: fn(&[u8]) -> Layer, header: &[u8]| {
+        layers.push(layer(header).placed(range_in(data, header)));
+    };
+    if let Some(LinkSlice::Ethernet2(ref eth)) = sliced.link {
+        add(ethernet, eth.header_slice());
+    }
+    for ext in &sliced.link_exts {
+        if let LinkExtSlice::Vlan(vlan_slice) = ext {
+            add(vlan, vlan_slice.header_slice());
+        }
+    }
+    match sliced.net {
+        Some(InternetSlice::Ipv4(ref slice)) => add(ipv4, slice.header().slice()),
+        Some(InternetSlice::Ipv6(ref slice)) => add(ipv6, slice.header().slice()),
+        Some(InternetSlice::Arp(ref slice)) => add(arp, slice.slice()),
+        None => {}
+    }
+    match sliced.transport {
+        Some(TransportSlice::Tcp(ref slice)) => add(tcp, slice.header_slice()),
+        Some(TransportSlice::Udp(ref slice)) => add(udp, slice.header_slice()),
+        Some(TransportSlice::Icmpv4(ref slice)) => {
+            add(|message| icmp("ICMPv4", message), slice.slice())
+        }
+        Some(TransportSlice::Icmpv6(ref slice)) => {
+            add(|message| icmp("ICMPv6", message), slice.slice())
+        }
+        None => {}
+    }
+    layers
+}
@@ -0,0 +1,231 @@
+/// Header names and values of HPACK's static table, indexed from 1.
+const STATIC_TABLE: [(&str, &str); 61] = [
+    (":authority", ""),
+    (":method", "GET"),
+    (":method", "POST"),
+    (":path", "/"),
+    (":path", "/index.html"),
+    (":scheme", "http"),
+    (":scheme", "https"),
+    (":status", "200"),
+    (":status", "204"),
+    (":status", "206"),
+    (":status", "304"),
+    (":status", "400"),
+    (":status", "404"),
+    (":status", "500"),
+    ("accept-charset", ""),
+    ("accept-encoding", "gzip, deflate"),
+    ("accept-language", ""),
+    ("accept-ranges", ""),
+    ("accept", ""),
+    ("access-control-allow-origin", ""),
+    ("age", ""),
+    ("allow", ""),
+    ("authorization", ""),
+    ("cache-control", ""),
+    ("content-disposition", ""),
+    ("content-encoding", ""),
+    ("content-language", ""),
+    ("content-length", ""),
+    ("content-location", ""),
+    ("content-range", ""),
+    ("content-type", ""),
+    ("cookie", ""),
+    ("date", ""),
+    ("etag", ""),
+    ("expect", ""),
+    ("expires", ""),
+    ("from", ""),
+    ("host", ""),
+    ("if-match", ""),
+    ("if-modified-since", ""),
+    ("if-none-match", ""),
+    ("if-range", ""),
+    ("if-unmodified-since", ""),
+    ("last-modified", ""),
+    ("link", ""),
+    ("location", ""),
+    ("max-forwards", ""),
+    ("proxy-authenticate", ""),
+    ("proxy-authorization", ""),
+    ("range", ""),
+    ("referer", ""),
+    ("refresh", ""),
+    ("retry-after", ""),
+    ("server", ""),
+    ("set-cookie", ""),
+    ("strict-transport-security", ""),
+    ("transfer-encoding", ""),
+    ("user-agent", ""),
+    ("vary", ""),
+    ("via", ""),
+    ("www-authenticate", ""),
+];
+
+/// Symbols of the HPACK Huffman code by code length, each length's in
+/// ascending order. The code is canonical, so these give every code; only
+/// the codes up to 15 bits are listed, which cover all printable ASCII but
+/// the backslash.
+const HUFFMAN_SYMBOLS: [(u8, &[u8]); 10] = [
+    (5, b"012aceiost"),
+    (6, b" %-./3456789=A_bdfghlmnpru"),
+    (7, b":BCDEFGHIJKLMNOPQRSTUVWYjkqvwxyz"),
+    (8, b"&*,;XZ"),
+    (10, b"!\"()?"),
+    (11, b"'+|"),
+    (12, b"#>"),
+    (13, b"\0$@[]~"),
+    (14, b"^}"),
+    (15, b"<`{"),
+];
+
+/// Decodes a Huffman-coded string. Strings using the longer codes, which
+/// are control and non-ASCII bytes, are not decoded.
+fn huffman(data: &[u8]) -> Option<String> {
+    let mut out = Vec::new();
+    let mut code: u32 = 0;
+    let mut len = 0;
+    for bit in data
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| byte >> i & 1))
+    {
+        code = code << 1 | u32::from(bit);
+        len += 1;
+        // Walk the lengths, keeping the canonical code's first value for
+        // each, until the one the bits read so far have.
+        let mut first: u32 = 0;
+        let mut previous_len = 0;
+        for &(symbol_len, symbols) in &HUFFMAN_SYMBOLS {
+            first <<= symbol_len - previous_len;
+            previous_len = symbol_len;
+            if symbol_len == len {
+                if let Some(offset) = code.checked_sub(first)
+                    && let Some(&symbol) = symbols.get(offset as usize)
+                {
+                    out.push(symbol);
+                    code = 0;
+                    len = 0;
+                }
+                break;
+            }
+            first += symbols.len() as u32;
+        }
+        if len > 15 {
+            return None;
+        }
+    }
+    // What is left is padding, the leading bits of the end-of-string code.
+    if len >= 8 || code != (1 << len) - 1 {
+        return None;
+    }
+    String::from_utf8(out).ok()
+}
+
+/// Reads an integer with an `prefix_bits`-bit prefix, returning it with
+/// the bytes it took.
+fn integer(data: &[u8], prefix_bits: u8) -> Option<(usize, usize)> {
+    let max = (1usize << prefix_bits) - 1;
+    let mut value = usize::from(*data.first()?) & max;
+    if value < max {
+        return Some((value, 1));
+    }
+    let mut shift = 0;
+    for (i, &byte) in data.iter().enumerate().skip(1) {
+        value = value.checked_add(usize::from(byte & 0x7f).checked_shl(shift)?)?;
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+        shift += 7;
+        if shift > 28 {
+            return None;
+        }
+    }
+    None
+}
+
+/// Reads a string literal, Huffman-coded when its first bit is set.
+fn string(data: &[u8]) -> Option<(Option<String>, usize)> {
+    let (len, used) = integer(data, 7)?;
+    let bytes = data.get(used..used + len)?;
+    let text = if data[0] & 0x80 != 0 {
+        huffman(bytes)
+    } else {
+        String::from_utf8(bytes.to_vec()).ok()
+    };
+    Some((text, used + len))
+}
+
+/// A decoded header. Fields referring to the connection's dynamic table,
+/// which earlier header blocks filled, are None.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub name: Option<String>,
+    pub value: Option<String>,
+}
+
+fn indexed(index: usize) -> (Option<&'static str>, Option<&'static str>) {
+    match index.checked_sub(1).and_then(|i| STATIC_TABLE.get(i)) {
+        Some(&(name, value)) => (Some(name), Some(value)),
+        None => (None, None),
+    }
+}
+
+/// Decodes a header block as far as one block allows: the static table and
+/// literals are known, but entries of the dynamic table only when this
+/// block added them.
+pub fn decode(mut block: &[u8]) -> Option<Vec<Header>> {
+    let mut headers = Vec::new();
+    let mut dynamic: Vec<Header> = Vec::new();
+    let lookup = |dynamic: &[Header], index: usize| -> Header {
+        match indexed(index) {
+            (Some(name), Some(value)) => Header {
+                name: Some(name.to_string()),
+                value: Some(value.to_string()),
+            },
+            _ => dynamic
+                .get(index - STATIC_TABLE.len() - 1)
+                .cloned()
+                .unwrap_or(Header {
+                    name: None,
+                    value: None,
+                }),
+        }
+    };
+    while let Some(&first) = block.first() {
+        if first & 0x80 != 0 {
+            let (index, used) = integer(block, 7)?;
+            if index == 0 {
+                return None;
+            }
+            headers.push(lookup(&dynamic, index));
+            block = &block[used..];
+            continue;
+        }
+        if first & 0xe0 == 0x20 {
+            // Dynamic table size update
+            let (_, used) = integer(block, 5)?;
+            block = &block[used..];
+            continue;
+        }
+        let incremental = first & 0xc0 == 0x40;
+        let (index, mut used) = integer(block, if incremental { 6 } else { 4 })?;
+        let name = if index == 0 {
+            let (name, name_len) = string(&block[used..])?;
+            used += name_len;
+            name
+        } else {
+            lookup(&dynamic, index).name
+        };
+        let (value, value_len) = string(&block[used..])?;
+        used += value_len;
+        let header = Header { name, value };
+        if incremental {
+            // Newest entries have the lowest dynamic index.
+            dynamic.insert(0, header.clone());
+        }
+        headers.push(header);
+        block = &block[used..];
+    }
+    Some(headers)
+}
@@ -0,0 +1,71 @@
+/// Request methods that start an HTTP/1 request line.
+const METHODS: [&str; 9] = [
+    "GET", "POST", "PUT", "HEAD", "DELETE", "OPTIONS", "PATCH", "CONNECT", "TRACE",
+];
+
+/// The first line of an HTTP/1 message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartLine<'a> {
+    Request { method: &'a str, target: &'a str },
+    Response { status: u16 },
+}
+
+impl<'a> StartLine<'a> {
+    /// Parses a request line or status line, without its CRLF.
+    pub fn parse(line: &'a str) -> Option<Self> {
+        let mut parts = line.split(' ');
+        let first = parts.next()?;
+        if first.starts_with("HTTP/1.") {
+            let status = parts.next()?;
+            if status.len() != 3 {
+                return None;
+            }
+            return Some(StartLine::Response {
+                status: status.parse().ok()?,
+            });
+        }
+        let target = parts.next()?;
+        if !METHODS.contains(&first) || !parts.next()?.starts_with("HTTP/1.") {
+            return None;
+        }
+        Some(StartLine::Request {
+            method: first,
+            target,
+        })
+    }
+
+    /// The start line at the front of `data`, which need not hold the rest
+    /// of the head.
+    pub fn at(data: &'a [u8]) -> Option<Self> {
+        let end = data.windows(2).position(|w| w == b"\r\n")?;
+        Self::parse(std::str::from_utf8(&data[..end]).ok()?)
+    }
+}
+
+/// The start line and headers of an HTTP/1 message.
+pub struct Head<'a> {
+    pub start: StartLine<'a>,
+    headers: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Head<'a> {
+    pub fn header(&self, name: &str) -> Option<&'a str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|&(_, value)| value)
+    }
+}
+
+/// Parses the head at the start of `data`, returning it and its length.
+pub fn parse_head(data: &[u8]) -> Option<(Head<'_>, usize)> {
+    let len = data.windows(4).position(|w| w == b"\r\n\r\n")? + 4;
+    let text = std::str::from_utf8(&data[..len - 4]).ok()?;
+    let mut lines = text.split("\r\n");
+    let start = StartLine::parse(lines.next()?)?;
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .collect();
+    Some((Head { start, headers }, len))
+}
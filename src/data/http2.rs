@@ -0,0 +1,308 @@
+use super::{
+    hpack::{self, Header},
+    layer::Layer,
+};
+
+/// What a client sends first on a cleartext HTTP/2 connection.
+const PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+const FRAME_HEADER_LEN: usize = 9;
+/// Largest frame payload a peer may allow; anything longer is not HTTP/2.
+const MAX_FRAME_LEN: usize = (1 << 24) - 1;
+
+const DATA: u8 = 0;
+const HEADERS: u8 = 1;
+const PRIORITY: u8 = 2;
+const RST_STREAM: u8 = 3;
+const SETTINGS: u8 = 4;
+const PUSH_PROMISE: u8 = 5;
+const PING: u8 = 6;
+const GOAWAY: u8 = 7;
+const WINDOW_UPDATE: u8 = 8;
+const CONTINUATION: u8 = 9;
+
+const FLAG_END_STREAM: u8 = 0x1;
+const FLAG_PADDED: u8 = 0x8;
+const FLAG_PRIORITY: u8 = 0x20;
+
+/// Length of the prefix of each gRPC message: a compressed flag and a
+/// 32-bit length.
+const GRPC_PREFIX_LEN: usize = 5;
+
+fn frame_name(frame_type: u8) -> &'static str {
+    match frame_type {
+        0 => "DATA",
+        1 => "HEADERS",
+        2 => "PRIORITY",
+        3 => "RST_STREAM",
+        4 => "SETTINGS",
+        5 => "PUSH_PROMISE",
+        6 => "PING",
+        7 => "GOAWAY",
+        8 => "WINDOW_UPDATE",
+        9 => "CONTINUATION",
+        _ => "Unknown",
+    }
+}
+
+fn error_name(code: u32) -> &'static str {
+    match code {
+        0 => "NO_ERROR",
+        1 => "PROTOCOL_ERROR",
+        2 => "INTERNAL_ERROR",
+        3 => "FLOW_CONTROL_ERROR",
+        4 => "SETTINGS_TIMEOUT",
+        5 => "STREAM_CLOSED",
+        6 => "FRAME_SIZE_ERROR",
+        7 => "REFUSED_STREAM",
+        8 => "CANCEL",
+        9 => "COMPRESSION_ERROR",
+        10 => "CONNECT_ERROR",
+        11 => "ENHANCE_YOUR_CALM",
+        12 => "INADEQUATE_SECURITY",
+        13 => "HTTP_1_1_REQUIRED",
+        _ => "Unknown",
+    }
+}
+
+/// Names of gRPC status codes.
+fn grpc_status_name(status: &str) -> &'static str {
+    match status {
+        "0" => "OK",
+        "1" => "CANCELLED",
+        "2" => "UNKNOWN",
+        "3" => "INVALID_ARGUMENT",
+        "4" => "DEADLINE_EXCEEDED",
+        "5" => "NOT_FOUND",
+        "6" => "ALREADY_EXISTS",
+        "7" => "PERMISSION_DENIED",
+        "8" => "RESOURCE_EXHAUSTED",
+        "9" => "FAILED_PRECONDITION",
+        "10" => "ABORTED",
+        "11" => "OUT_OF_RANGE",
+        "12" => "UNIMPLEMENTED",
+        "13" => "INTERNAL",
+        "14" => "UNAVAILABLE",
+        "15" => "DATA_LOSS",
+        "16" => "UNAUTHENTICATED",
+        _ => "Unknown",
+    }
+}
+
+struct Frame<'a> {
+    frame_type: u8,
+    flags: u8,
+    stream: u32,
+    payload: &'a [u8],
+}
+
+/// Whether a frame of this type may have this stream and length: connection
+/// frames are on stream 0 and the others never, and some have fixed sizes.
+fn plausible(frame_type: u8, stream: u32, len: usize) -> bool {
+    match frame_type {
+        SETTINGS => stream == 0 && len.is_multiple_of(6),
+        PING => stream == 0 && len == 8,
+        GOAWAY => stream == 0 && len >= 8,
+        WINDOW_UPDATE => len == 4,
+        PRIORITY => stream != 0 && len == 5,
+        RST_STREAM => stream != 0 && len == 4,
+        DATA | HEADERS | PUSH_PROMISE | CONTINUATION => stream != 0,
+        _ => false,
+    }
+}
+
+/// Splits a segment into frames, None unless they fill it exactly. Frames
+/// continuing from or into other segments are not followed.
+fn frames(mut data: &[u8]) -> Option<Vec<Frame<'_>>> {
+    let mut frames = Vec::new();
+    while !data.is_empty() {
+        let header = data.get(..FRAME_HEADER_LEN)?;
+        let len =
+            usize::from(header[0]) << 16 | usize::from(header[1]) << 8 | usize::from(header[2]);
+        let frame_type = header[3];
+        let stream = u32::from_be_bytes(header[5..9].try_into().ok()?);
+        if stream & 0x8000_0000 != 0 || len > MAX_FRAME_LEN || !plausible(frame_type, stream, len) {
+            return None;
+        }
+        frames.push(Frame {
+            frame_type,
+            flags: header[4],
+            stream,
+            payload: data.get(FRAME_HEADER_LEN..FRAME_HEADER_LEN + len)?,
+        });
+        data = &data[FRAME_HEADER_LEN + len..];
+    }
+    (!frames.is_empty()).then_some(frames)
+}
+
+/// The payload of a DATA or HEADERS frame without its padding and, for
+/// HEADERS, priority.
+fn content<'a>(frame: &Frame<'a>) -> Option<&'a [u8]> {
+    let mut payload = frame.payload;
+    let mut padding = 0;
+    if frame.flags & FLAG_PADDED != 0 {
+        padding = usize::from(*payload.first()?);
+        payload = &payload[1..];
+    }
+    if frame.frame_type == HEADERS && frame.flags & FLAG_PRIORITY != 0 {
+        payload = payload.get(5..)?;
+    }
+    payload.get(..payload.len().checked_sub(padding)?)
+}
+
+fn header<'a>(headers: &'a [Header], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|header| header.name.as_deref() == Some(name))
+        .and_then(|header| header.value.as_deref())
+}
+
+/// The gRPC messages of a DATA frame, as compressed flag and length, when
+/// the length prefixes account for all of it.
+fn grpc_messages(mut data: &[u8]) -> Option<Vec<(bool, usize)>> {
+    let mut messages = Vec::new();
+    while !data.is_empty() {
+        let prefix = data.get(..GRPC_PREFIX_LEN)?;
+        if prefix[0] > 1 {
+            return None;
+        }
+        let len = u32::from_be_bytes(prefix[1..5].try_into().ok()?) as usize;
+        data = data.get(GRPC_PREFIX_LEN + len..)?;
+        messages.push((prefix[0] == 1, len));
+    }
+    (!messages.is_empty()).then_some(messages)
+}
+
+/// Describes a HEADERS frame: a request, response or trailers. Returns the
+/// summary and whether it belongs to a gRPC call.
+fn describe_headers(layer: &mut Layer, stream: u32, headers: &[Header]) -> (String, bool) {
+    let content_type = header(headers, "content-type");
+    let path = header(headers, ":path");
+    let status = header(headers, "grpc-status");
+    let grpc = content_type
+        .is_some_and(|content_type| content_type.starts_with("application/grpc"))
+        || status.is_some();
+    if let Some(content_type) = content_type {
+        layer.field("Content-Type", content_type);
+    }
+    if let Some(authority) = header(headers, ":authority") {
+        layer.field("Authority", authority);
+    }
+    let summary = if let Some(path) = path {
+        let method = header(headers, ":method").unwrap_or("?");
+        layer.field("Method", method);
+        layer.field("Path", path);
+        // gRPC paths are /package.Service/Method.
+        match path.trim_start_matches('/').split_once('/') {
+            Some((service, rpc)) if grpc => {
+                layer.field("Service", service);
+                layer.field("RPC", rpc);
+                format!("Call {service}/{rpc} (stream {stream})")
+            }
+            _ => format!("{method} {path} (stream {stream})"),
+        }
+    } else if let Some(status) = status {
+        let name = grpc_status_name(status);
+        layer.field("grpc-status", format!("{status} ({name})"));
+        if let Some(message) = header(headers, "grpc-message") {
+            layer.field("grpc-message", message);
+        }
+        format!("Status {name} (stream {stream})")
+    } else if let Some(status) = header(headers, ":status") {
+        layer.field("Status", status);
+        format!("Response {status} (stream {stream})")
+    } else {
+        format!("HEADERS (stream {stream})")
+    };
+    if let Some(timeout) = header(headers, "grpc-timeout") {
+        layer.detail("grpc-timeout", timeout);
+    }
+    let unknown = headers
+        .iter()
+        .filter(|header| header.name.is_none() || header.value.is_none())
+        .count();
+    if unknown > 0 {
+        layer.detail("Headers from earlier blocks", unknown.to_string());
+    }
+    (summary, grpc)
+}
+
+/// Decodes the HTTP/2 frames of a cleartext TCP segment, naming the layer
+/// gRPC when a frame belongs to a gRPC call: its headers give the service
+/// and method and its DATA frames hold length-prefixed messages.
+pub fn decode(payload: &[u8]) -> Option<Layer> {
+    let preface = payload.starts_with(PREFACE);
+    let frames = frames(payload.strip_prefix(PREFACE).unwrap_or(payload))?;
+    let mut layer = Layer::new("HTTP2", String::new());
+    let mut summaries = Vec::new();
+    let mut grpc = false;
+    if preface {
+        summaries.push("Connection Preface".to_string());
+    }
+    for frame in &frames {
+        let name = frame_name(frame.frame_type);
+        layer.detail(
+            format!("{name} Frame"),
+            format!("stream {}, {} bytes", frame.stream, frame.payload.len()),
+        );
+        match frame.frame_type {
+            HEADERS => {
+                let Some(headers) = content(frame).and_then(hpack::decode) else {
+                    summaries.push(format!("HEADERS (stream {})", frame.stream));
+                    continue;
+                };
+                let (summary, is_grpc) = describe_headers(&mut layer, frame.stream, &headers);
+                grpc |= is_grpc;
+                summaries.push(summary);
+            }
+            DATA => {
+                let data = content(frame).unwrap_or_default();
+                let end = if frame.flags & FLAG_END_STREAM != 0 {
+                    ", end"
+                } else {
+                    ""
+                };
+                if let Some(messages) = grpc_messages(data) {
+                    grpc = true;
+                    for &(compressed, len) in &messages {
+                        let compressed = if compressed { ", compressed" } else { "" };
+                        layer.field("Message", format!("{len} bytes{compressed}"));
+                    }
+                    let sizes: Vec<String> = messages
+                        .iter()
+                        .map(|(_, len)| format!("{len} bytes"))
+                        .collect();
+                    summaries.push(format!(
+                        "Message {} (stream {}{end})",
+                        sizes.join(", "),
+                        frame.stream
+                    ));
+                } else {
+                    summaries.push(format!(
+                        "DATA {} bytes (stream {}{end})",
+                        data.len(),
+                        frame.stream
+                    ));
+                }
+            }
+            RST_STREAM | GOAWAY => {
+                // GOAWAY carries the last stream ID before its error code.
+                let at = if frame.frame_type == GOAWAY { 4 } else { 0 };
+                let code = frame
+                    .payload
+                    .get(at..at + 4)
+                    .and_then(|code| code.try_into().ok())
+                    .map_or(0, u32::from_be_bytes);
+                let error = error_name(code);
+                layer.field("Error", error);
+                summaries.push(format!("{name} {error} (stream {})", frame.stream));
+            }
+            _ => summaries.push(name.to_string()),
+        }
+    }
+    summaries.dedup();
+    if grpc {
+        layer.name = "gRPC";
+    }
+    layer.summary = summaries.join("; ");
+    Some(layer)
+}
@@ -0,0 +1,301 @@
+use std::{collections::HashMap, io::Read};
+
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+
+use super::{
+    flow::FlowKey,
+    http::{Head, StartLine, parse_head},
+    packet::{PacketInfo, Transport},
+};
+
+/// Most bytes a body is decompressed to, so a small compression bomb in the
+/// capture can't take all memory.
+const MAX_DECODED: usize = 64 * 1024 * 1024;
+
+/// A body transferred in an HTTP/1 response, as listed by Wireshark's
+/// Export HTTP Objects.
+#[derive(Debug, Clone)]
+pub struct HttpObject {
+    pub id: usize, // Packet that completed the response
+    pub host: String,
+    pub target: String, // Request target the response answers
+    pub content_type: String,
+    pub data: Vec<u8>,   // Body with transfer and content codings undone
+    pub truncated: bool, // Still content coded, as it would decode past MAX_DECODED
+}
+
+impl HttpObject {
+    /// A file name for the object, taken from the last segment of its
+    /// target.
+    pub fn file_name(&self) -> String {
+        let path = self.target.split(['?', '#']).next().unwrap_or("");
+        let name: String = path
+            .rsplit('/')
+            .next()
+            .unwrap_or("")
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        if name.trim_matches(['.', '_']).is_empty() {
+            format!("object-{}", self.id)
+        } else {
+            name
+        }
+    }
+}
+
+/// One direction of a TCP connection in sequence order, up to the first
+/// segment missing from the capture.
+struct Stream {
+    data: Vec<u8>,
+    ends: Vec<(usize, usize)>, // Offset after each segment's bytes, and its packet
+}
+
+impl Stream {
+    /// Puts `segments`, in capture order, back in sequence order. Bytes
+    /// sent again are taken from the first copy captured.
+    fn reassemble(segments: &[&PacketInfo]) -> Self {
+        let mut stream = Self {
+            data: Vec::new(),
+            ends: Vec::new(),
+        };
+        // Data starts after the SYN, or else with the first segment seen.
+        let base = segments
+            .iter()
+            .find(|packet| packet.tcp_flags.is_some_and(|flags| flags.syn))
+            .and_then(|packet| packet.tcp_sequence)
+            .map(|sequence| sequence.seq.wrapping_add(1))
+            .or_else(|| {
+                segments
+                    .iter()
+                    .find(|packet| !packet.payload().is_empty())
+                    .and_then(|packet| packet.tcp_sequence)
+                    .map(|sequence| sequence.seq)
+            });
+        let Some(base) = base else {
+            return stream;
+        };
+        let mut pieces: Vec<(usize, &PacketInfo)> = segments
+            .iter()
+            .filter(|packet| !packet.payload().is_empty())
+            .filter_map(|&packet| {
+                let offset = packet.tcp_sequence?.seq.wrapping_sub(base);
+                // Segments from before the base wrap around to huge offsets.
+                (offset < 1 << 31).then_some((offset as usize, packet))
+            })
+            .collect();
+        pieces.sort_by_key(|&(offset, _)| offset);
+        for (offset, packet) in pieces {
+            let payload = packet.payload();
+            let end = offset + payload.len();
+            if offset > stream.data.len() {
+                break;
+            }
+            if end <= stream.data.len() {
+                continue;
+            }
+            stream
+                .data
+                .extend_from_slice(&payload[stream.data.len() - offset..]);
+            stream.ends.push((end, packet.id));
+        }
+        stream
+    }
+
+    /// The packet that carried the byte before `end`.
+    fn packet_at(&self, end: usize) -> usize {
+        self.ends
+            .iter()
+            .find(|&&(segment_end, _)| segment_end >= end)
+            .or(self.ends.last())
+            .map_or(0, |&(_, id)| id)
+    }
+}
+
+/// Undoes chunked transfer coding at the start of `data`, returning the
+/// body and the bytes it took, or None if the last chunk wasn't captured.
+fn dechunk(data: &[u8]) -> Option<(Vec<u8>, usize)> {
+    let line_end = |pos: usize| {
+        data.get(pos..)?
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .map(|i| pos + i)
+    };
+    let mut body = Vec::new();
+    let mut pos = 0;
+    loop {
+        let end = line_end(pos)?;
+        let size = std::str::from_utf8(&data[pos..end]).ok()?;
+        let size = usize::from_str_radix(size.split(';').next()?.trim(), 16).ok()?;
+        pos = end + 2;
+        if size == 0 {
+            // Trailers, up to an empty line.
+            loop {
+                let end = line_end(pos)?;
+                let empty = end == pos;
+                pos = end + 2;
+                if empty {
+                    return Some((body, pos));
+                }
+            }
+        }
+        body.extend_from_slice(data.get(pos..pos.checked_add(size)?)?);
+        pos += size + 2;
+    }
+}
+
+/// Undoes gzip and deflate content coding, keeping the body as it was
+/// sent if it doesn't decompress. A body that would decompress past
+/// [`MAX_DECODED`] is kept as sent too, and reported as truncated.
+fn decode(body: Vec<u8>, encoding: Option<&str>) -> (Vec<u8>, bool) {
+    let mut decoded = Vec::new();
+    let limit = MAX_DECODED as u64 + 1;
+    let result = match encoding.map(str::to_ascii_lowercase).as_deref() {
+        Some("gzip" | "x-gzip") => MultiGzDecoder::new(&body[..])
+            .take(limit)
+            .read_to_end(&mut decoded),
+        Some("deflate") => ZlibDecoder::new(&body[..])
+            .take(limit)
+            .read_to_end(&mut decoded),
+        _ => return (body, false),
+    };
+    match result {
+        Ok(len) if len > MAX_DECODED => (body, true),
+        Ok(_) => (decoded, false),
+        Err(_) => (body, false),
+    }
+}
+
+struct Message<'a> {
+    head: Head<'a>,
+    body: Vec<u8>,
+    len: usize, // Head and body as sent
+}
+
+/// Parses the HTTP/1 message at the start of `data`, None until all of it
+/// was captured. A response without a length runs to the end of the
+/// stream; `bodyless` responses have no body whatever their headers say.
+fn parse_message(data: &[u8], response: bool, bodyless: bool) -> Option<Message<'_>> {
+    let (head, head_len) = parse_head(data)?;
+    let rest = &data[head_len..];
+    let chunked = head
+        .header("Transfer-Encoding")
+        .is_some_and(|coding| coding.to_ascii_lowercase().contains("chunked"));
+    let (body, body_len) = if bodyless {
+        (Vec::new(), 0)
+    } else if chunked {
+        dechunk(rest)?
+    } else if let Some(length) = head.header("Content-Length") {
+        let length: usize = length.parse().ok()?;
+        (rest.get(..length)?.to_vec(), length)
+    } else if response {
+        (rest.to_vec(), rest.len())
+    } else {
+        (Vec::new(), 0)
+    };
+    Some(Message {
+        head,
+        body,
+        len: head_len + body_len,
+    })
+}
+
+/// The objects in the responses of one connection, each paired with the
+/// request it answers.
+fn exchange(requests: &Stream, responses: &Stream) -> Vec<HttpObject> {
+    let mut objects = Vec::new();
+    let mut request_pos = 0;
+    let mut response_pos = 0;
+    while let Some((head, _)) = parse_head(&responses.data[response_pos..]) {
+        let request = parse_message(&requests.data[request_pos..], false, false);
+        let (method, target) = match request.as_ref().map(|request| request.head.start) {
+            Some(StartLine::Request { method, target }) => (method, target),
+            _ => ("", ""),
+        };
+        let StartLine::Response { status } = head.start else {
+            break;
+        };
+        let bodyless = status < 200 || status == 204 || status == 304 || method == "HEAD";
+        let Some(response) = parse_message(&responses.data[response_pos..], true, bodyless) else {
+            break;
+        };
+        response_pos += response.len;
+        // Interim responses come before the one that answers the request.
+        if status < 200 {
+            continue;
+        }
+        if let Some(ref request) = request {
+            request_pos += request.len;
+        }
+        if response.body.is_empty() {
+            continue;
+        }
+        let host = request
+            .as_ref()
+            .and_then(|request| request.head.header("Host"))
+            .unwrap_or("");
+        let content_type = response.head.header("Content-Type").unwrap_or("");
+        let encoding = response.head.header("Content-Encoding");
+        let (data, truncated) = decode(response.body, encoding);
+        objects.push(HttpObject {
+            id: responses.packet_at(response_pos),
+            host: host.to_string(),
+            target: target.to_string(),
+            content_type: content_type.to_string(),
+            data,
+            truncated,
+        });
+    }
+    objects
+}
+
+/// The objects transferred over HTTP/1 in `packets`, by connection in the
+/// order the connections were first seen.
+pub fn from_packets(packets: &[PacketInfo]) -> Vec<HttpObject> {
+    let mut order = Vec::new();
+    let mut connections: HashMap<FlowKey, Vec<&PacketInfo>> = HashMap::new();
+    for packet in packets {
+        if packet.transport != Some(Transport::Tcp) {
+            continue;
+        }
+        let Some(key) = FlowKey::from_packet(packet) else {
+            continue;
+        };
+        connections
+            .entry(key.clone())
+            .or_insert_with(|| {
+                order.push(key);
+                Vec::new()
+            })
+            .push(packet);
+    }
+
+    let mut objects = Vec::new();
+    for key in order {
+        let mut segments = connections.remove(&key).unwrap_or_default();
+        segments.sort_by_key(|packet| packet.captured_at);
+        let (a, b): (Vec<&PacketInfo>, Vec<&PacketInfo>) =
+            segments.into_iter().partition(|packet| {
+                packet.src_addr.as_ref() == Some(&key.a.addr) && packet.src_port == key.a.port
+            });
+        let (a, b) = (Stream::reassemble(&a), Stream::reassemble(&b));
+        let is_response = |stream: &Stream| {
+            matches!(
+                StartLine::at(&stream.data),
+                Some(StartLine::Response { .. })
+            )
+        };
+        if is_response(&b) {
+            objects.extend(exchange(&a, &b));
+        } else if is_response(&a) {
+            objects.extend(exchange(&b, &a));
+        }
+    }
+    objects
+}
@@ -0,0 +1,216 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    net::IpAddr,
+    time::{Duration, SystemTime},
+};
+
+use super::{
+    http::{StartLine, parse_head},
+    packet::{PacketInfo, Transport},
+};
+
+/// URLs listed in the statistics, slowest first.
+pub const SLOWEST: usize = 20;
+
+/// One side of a connection.
+type Endpoint = (IpAddr, u16);
+
+/// An HTTP/1 request and, once answered on its connection, the status and
+/// how long the first byte of the response took.
+#[derive(Debug, Clone)]
+pub struct HttpTransaction {
+    pub response: Option<usize>, // Packet index of the response head
+    pub method: String,
+    pub url: String, // Host and target
+    pub asked: SystemTime,
+    pub status: Option<u16>,
+    pub ttfb: Option<Duration>,
+}
+
+impl HttpTransaction {
+    /// Status and time to first byte, short enough for the packet list.
+    pub fn label(&self) -> Option<String> {
+        let (status, ttfb) = (self.status?, self.ttfb?);
+        Some(format!("{status} {:.1} ms", ttfb.as_secs_f64() * 1000.0))
+    }
+}
+
+/// Requests waiting on one connection, and the sequence numbers of the
+/// last request and response heads seen so retransmissions are skipped.
+#[derive(Debug, Default)]
+struct Connection {
+    pending: VecDeque<usize>, // Request packet indices, oldest first
+    last_request: Option<u32>,
+    last_response: Option<u32>,
+}
+
+/// Pairs HTTP/1 requests with the responses that answer them, in order on
+/// each connection, as packets are captured.
+#[derive(Debug, Default)]
+pub struct HttpTracker {
+    connections: HashMap<(Endpoint, Endpoint), Connection>, // By client and server
+    pub transactions: BTreeMap<usize, HttpTransaction>,     // By request packet index
+}
+
+impl HttpTracker {
+    pub fn add(&mut self, index: usize, packet: &PacketInfo) {
+        if packet.transport != Some(Transport::Tcp) {
+            return;
+        }
+        let (Some(Ok(src)), Some(src_port), Some(Ok(dst)), Some(dst_port)) = (
+            packet.src_addr.clone(),
+            packet.src_port,
+            packet.dst_addr.clone(),
+            packet.dst_port,
+        ) else {
+            return;
+        };
+        let payload = packet.payload();
+        let seq = packet.tcp_sequence.map(|sequence| sequence.seq);
+
+        let start = StartLine::at(payload);
+        if let Some(StartLine::Request { method, target }) = start {
+            // A head split over segments is still counted, without its host.
+            let host = parse_head(payload)
+                .and_then(|(head, _)| head.header("Host"))
+                .unwrap_or_default();
+            let connection = self
+                .connections
+                .entry(((src, src_port), (dst, dst_port)))
+                .or_default();
+            if seq.is_some() && connection.last_request == seq {
+                return;
+            }
+            connection.last_request = seq;
+            connection.pending.push_back(index);
+            self.transactions.insert(
+                index,
+                HttpTransaction {
+                    response: None,
+                    method: method.to_string(),
+                    url: format!("{host}{target}"),
+                    asked: packet.captured_at,
+                    status: None,
+                    ttfb: None,
+                },
+            );
+            return;
+        }
+
+        let Some(StartLine::Response { status }) = start else {
+            return;
+        };
+        let Some(connection) = self
+            .connections
+            .get_mut(&((dst, dst_port), (src, src_port)))
+        else {
+            return;
+        };
+        if seq.is_some() && connection.last_response == seq {
+            return;
+        }
+        connection.last_response = seq;
+        // Interim responses come before the one that answers the request.
+        if status < 200 {
+            return;
+        }
+        let Some(request) = connection.pending.pop_front() else {
+            return;
+        };
+        let Some(transaction) = self.transactions.get_mut(&request) else {
+            return;
+        };
+        transaction.response = Some(index);
+        transaction.status = Some(status);
+        transaction.ttfb = Some(
+            packet
+                .captured_at
+                .duration_since(transaction.asked)
+                .unwrap_or_default(),
+        );
+    }
+
+    /// Forgets the transactions of requests before `start`.
+    pub fn evict(&mut self, start: usize) {
+        self.transactions = self.transactions.split_off(&start);
+        for connection in self.connections.values_mut() {
+            connection.pending.retain(|request| *request >= start);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.connections.clear();
+        self.transactions.clear();
+    }
+}
+
+/// Response times of one URL.
+#[derive(Debug, Clone)]
+pub struct SlowUrl {
+    pub method: String,
+    pub url: String,
+    pub requests: usize,
+    pub slowest: Duration,
+    pub total: Duration, // Over the answered requests, for the mean
+    pub answered: usize,
+}
+
+impl SlowUrl {
+    pub fn mean(&self) -> Option<Duration> {
+        (self.answered > 0).then(|| self.total / self.answered as u32)
+    }
+}
+
+/// How HTTP/1 requests were answered: the status codes returned and the
+/// URLs whose responses were slowest to start.
+#[derive(Debug, Clone, Default)]
+pub struct HttpStats {
+    pub requests: usize,
+    pub unanswered: usize,
+    pub statuses: BTreeMap<u16, usize>,
+    pub slowest: Vec<SlowUrl>, // Slowest first byte first
+}
+
+impl HttpStats {
+    pub fn from_packets(packets: &[&PacketInfo]) -> Self {
+        let mut tracker = HttpTracker::default();
+        for packet in packets {
+            tracker.add(packet.id, packet);
+        }
+
+        let mut stats = Self::default();
+        let mut urls: HashMap<(String, String), SlowUrl> = HashMap::new();
+        for transaction in tracker.transactions.into_values() {
+            stats.requests += 1;
+            let url = urls
+                .entry((transaction.method.clone(), transaction.url.clone()))
+                .or_insert_with(|| SlowUrl {
+                    method: transaction.method,
+                    url: transaction.url,
+                    requests: 0,
+                    slowest: Duration::ZERO,
+                    total: Duration::ZERO,
+                    answered: 0,
+                });
+            url.requests += 1;
+            let (Some(status), Some(ttfb)) = (transaction.status, transaction.ttfb) else {
+                stats.unanswered += 1;
+                continue;
+            };
+            *stats.statuses.entry(status).or_default() += 1;
+            url.slowest = url.slowest.max(ttfb);
+            url.total += ttfb;
+            url.answered += 1;
+        }
+        let mut slowest: Vec<SlowUrl> = urls.into_values().filter(|url| url.answered > 0).collect();
+        slowest.sort_by(|x, y| {
+            y.slowest
+                .cmp(&x.slowest)
+                .then_with(|| x.url.cmp(&y.url))
+                .then_with(|| x.method.cmp(&y.method))
+        });
+        slowest.truncate(SLOWEST);
+        stats.slowest = slowest;
+        stats
+    }
+}
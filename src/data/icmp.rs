@@ -0,0 +1,169 @@
+use std::{collections::HashMap, net::IpAddr};
+
+use etherparse::{IpNumber, LaxNetSlice, LaxSlicedPacket};
+
+use super::{
+    flow::{Endpoint, FlowKey},
+    layer::Layer,
+    packet::{PacketInfo, Transport},
+};
+
+fn describe(v6: bool, icmp_type: u8, code: u8) -> (&'static str, Option<&'static str>) {
+    match (v6, icmp_type) {
+        (false, 3) => (
+            "Destination unreachable",
+            match code {
+                0 => Some("network unreachable"),
+                1 => Some("host unreachable"),
+                2 => Some("protocol unreachable"),
+                3 => Some("port unreachable"),
+                4 => Some("fragmentation needed"),
+                9 | 10 | 13 => Some("administratively prohibited"),
+                _ => None,
+            },
+        ),
+        (false, 11) | (true, 3) => (
+            "Time exceeded",
+            match code {
+                0 if v6 => Some("hop limit exceeded in transit"),
+                0 => Some("TTL exceeded in transit"),
+                1 => Some("fragment reassembly time exceeded"),
+                _ => None,
+            },
+        ),
+        (false, 12) | (true, 4) => ("Parameter problem", None),
+        (true, 1) => (
+            "Destination unreachable",
+            match code {
+                0 => Some("no route to destination"),
+                1 => Some("administratively prohibited"),
+                3 => Some("address unreachable"),
+                4 => Some("port unreachable"),
+                _ => None,
+            },
+        ),
+        (true, 2) => ("Packet too big", None),
+        _ => ("ICMP error", None),
+    }
+}
+
+/// The protocol and endpoints of the datagram quoted by an ICMP error. Only
+/// the first 8 bytes of its transport header are guaranteed, which is enough
+/// for the ports.
+pub fn quoted_flow(quoted: &[u8]) -> Option<(Option<Transport>, Endpoint, Endpoint)> {
+    let (src, dst, payload) = match LaxSlicedPacket::from_ip(quoted).ok()?.net? {
+        LaxNetSlice::Ipv4(ipv4) => (
+            IpAddr::V4(ipv4.header().source_addr()),
+            IpAddr::V4(ipv4.header().destination_addr()),
+            ipv4.payload().clone(),
+        ),
+        LaxNetSlice::Ipv6(ipv6) => (
+            IpAddr::V6(ipv6.header().source_addr()),
+            IpAddr::V6(ipv6.header().destination_addr()),
+            ipv6.payload().clone(),
+        ),
+        LaxNetSlice::Arp(_) => return None,
+    };
+    let transport = match payload.ip_number {
+        IpNumber::TCP => Some(Transport::Tcp),
+        IpNumber::UDP => Some(Transport::Udp),
+        IpNumber::ICMP => Some(Transport::Icmpv4),
+        IpNumber::IPV6_ICMP => Some(Transport::Icmpv6),
+        _ => None,
+    };
+    let ports = match (transport, payload.payload) {
+        (Some(Transport::Tcp | Transport::Udp), [s0, s1, d0, d1, ..]) => Some((
+            u16::from_be_bytes([*s0, *s1]),
+            u16::from_be_bytes([*d0, *d1]),
+        )),
+        _ => None,
+    };
+    let src = Endpoint {
+        addr: Ok(src),
+        port: ports.map(|(port, _)| port),
+    };
+    let dst = Endpoint {
+        addr: Ok(dst),
+        port: ports.map(|(_, port)| port),
+    };
+    Some((transport, src, dst))
+}
+
+/// Describes an ICMP error together with the datagram it reports on.
+pub fn decode_error(v6: bool, icmp_type: u8, code: u8, quoted: &[u8]) -> Layer {
+    let (name, reason) = describe(v6, icmp_type, code);
+    let kind = match reason {
+        Some(reason) => format!("{name} ({reason})"),
+        None => name.to_string(),
+    };
+    let mut layer = Layer::new(if v6 { "ICMPv6" } else { "ICMPv4" }, kind.clone());
+    layer.field("Type", format!("{name} ({icmp_type})"));
+    layer.field("Code", code.to_string());
+    if let Some((transport, src, dst)) = quoted_flow(quoted) {
+        let protocol = match transport {
+            Some(Transport::Tcp) => "TCP",
+            Some(Transport::Udp) => "UDP",
+            Some(Transport::Icmpv4) => "ICMPv4",
+            Some(Transport::Icmpv6) => "ICMPv6",
+            None => "IP",
+        };
+        layer.summary = format!("{kind} for {protocol} {src} → {dst}");
+        layer.field("Original Protocol", protocol);
+        layer.field("Original Source", src.to_string());
+        layer.field("Original Destination", dst.to_string());
+    }
+    layer
+}
+
+/// A packet on the other end of an ICMP error link.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LinkedPacket {
+    pub index: usize, // Absolute index in the capture
+    pub id: usize,    // Number shown in the packet list
+}
+
+/// Links ICMP errors to the packets that caused them, taken to be the latest
+/// packet of the quoted flow seen before the error. Packets are identified by
+/// their absolute index in the capture.
+#[derive(Debug, Default)]
+pub struct IcmpLinks {
+    latest: HashMap<FlowKey, usize>,    // Latest packet of each flow
+    reported_on: HashMap<usize, usize>, // Error to the packet it reports on
+    errors: HashMap<usize, Vec<usize>>, // Packet to the errors about it
+}
+
+impl IcmpLinks {
+    pub fn add(&mut self, index: usize, packet: &PacketInfo) {
+        if let Some(key) = FlowKey::from_icmp_error(packet)
+            && let Some(&cause) = self.latest.get(&key)
+        {
+            self.reported_on.insert(index, cause);
+            self.errors.entry(cause).or_default().push(index);
+        }
+        if let Some(key) = FlowKey::from_packet(packet) {
+            self.latest.insert(key, index);
+        }
+    }
+
+    pub fn reported_on(&self, index: usize) -> Option<usize> {
+        self.reported_on.get(&index).copied()
+    }
+
+    pub fn errors(&self, index: usize) -> &[usize] {
+        self.errors.get(&index).map_or(&[], Vec::as_slice)
+    }
+
+    /// Forgets links to packets before `start`.
+    pub fn evict(&mut self, start: usize) {
+        self.latest.retain(|_, index| *index >= start);
+        self.reported_on
+            .retain(|error, cause| *error >= start && *cause >= start);
+        self.errors.retain(|cause, _| *cause >= start);
+    }
+
+    pub fn clear(&mut self) {
+        self.latest.clear();
+        self.reported_on.clear();
+        self.errors.clear();
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    fs,
+    io::ErrorKind,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use anyhow::{Context, Result, bail};
+
+use super::{
+    dns::{self, RecordData},
+    packet::{PacketInfo, Transport, format_mac},
+};
+
+const HEADER: &str = "\
+# Hosts remembered across capture sessions, one per line, tab separated:
+# address, MAC address, first seen, last seen (Unix seconds), alias, names.
+# Edit the alias column to name a device; '-' marks an empty column.
+";
+
+/// What is known about one LAN address.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnownHost {
+    pub mac: Option<String>,
+    pub first_seen: u64,
+    pub last_seen: u64,
+    pub alias: Option<String>,   // Set by hand in the file
+    pub names: BTreeSet<String>, // Learned from DNS and mDNS answers
+}
+
+/// Hosts on the local network learned from traffic, kept in a file so they
+/// are recognized in later sessions. Only private, link-local and unique
+/// local addresses are remembered, since their MAC addresses are those of
+/// the devices themselves rather than of a router.
+#[derive(Debug)]
+pub struct KnowledgeBase {
+    path: PathBuf,
+    hosts: BTreeMap<IpAddr, KnownHost>,
+}
+
+pub fn is_local(addr: IpAddr) -> bool {
+    match addr {
+        IpAddr::V4(v4) => v4.is_private() || v4.is_link_local(),
+        IpAddr::V6(v6) => {
+            let first = v6.segments()[0];
+            first & 0xffc0 == 0xfe80 || first & 0xfe00 == 0xfc00
+        }
+    }
+}
+
+fn column(value: &str) -> Option<String> {
+    (value != "-" && !value.is_empty()).then(|| value.to_string())
+}
+
+impl KnowledgeBase {
+    /// Reads the knowledge base at `path`, starting empty if it does not
+    /// exist yet.
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut knowledge = Self {
+            path: path.to_path_buf(),
+            hosts: BTreeMap::new(),
+        };
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(knowledge),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        };
+        for (number, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let columns: Vec<&str> = line.split('\t').collect();
+            let [addr, mac, first_seen, last_seen, alias, names] = columns[..] else {
+                bail!("{}:{}: expected 6 columns", path.display(), number + 1);
+            };
+            let parse_time = |value: &str| {
+                value
+                    .parse::<u64>()
+                    .with_context(|| format!("{}:{}: bad time {value}", path.display(), number + 1))
+            };
+            let addr: IpAddr = addr.parse().with_context(|| {
+                format!("{}:{}: bad address {addr}", path.display(), number + 1)
+            })?;
+            knowledge.hosts.insert(
+                addr,
+                KnownHost {
+                    mac: column(mac),
+                    first_seen: parse_time(first_seen)?,
+                    last_seen: parse_time(last_seen)?,
+                    alias: column(alias),
+                    names: names.split(',').filter_map(column).collect(),
+                },
+            );
+        }
+        Ok(knowledge)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut text = HEADER.to_string();
+        for (addr, host) in &self.hosts {
+            let names: Vec<&str> = host.names.iter().map(String::as_str).collect();
+            let _ = writeln!(
+                text,
+                "{addr}\t{}\t{}\t{}\t{}\t{}",
+                host.mac.as_deref().unwrap_or("-"),
+                host.first_seen,
+                host.last_seen,
+                host.alias.as_deref().unwrap_or("-"),
+                if names.is_empty() {
+                    "-".to_string()
+                } else {
+                    names.join(",")
+                }
+            );
+        }
+        fs::write(&self.path, text)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.hosts.len()
+    }
+
+    fn host(&mut self, addr: IpAddr, seen: u64) -> &mut KnownHost {
+        let host = self.hosts.entry(addr).or_insert_with(|| KnownHost {
+            first_seen: seen,
+            ..KnownHost::default()
+        });
+        host.first_seen = host.first_seen.min(seen);
+        host.last_seen = host.last_seen.max(seen);
+        host
+    }
+
+    /// Records the local addresses in `packet`, the sender's MAC address and
+    /// any local addresses named in a DNS or mDNS answer.
+    pub fn learn(&mut self, packet: &PacketInfo) {
+        let seen = packet
+            .captured_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if let Some(Ok(src)) = packet.src_addr
+            && is_local(src)
+        {
+            // Tunnelled packets carry the MAC addresses of the outer frame.
+            let mac = packet
+                .outer_addrs
+                .is_empty()
+                .then(|| packet.data.get(6..12))
+                .flatten();
+            let host = self.host(src, seen);
+            if let Some(mac) = mac {
+                host.mac = Some(format_mac(mac));
+            }
+        }
+        if let Some(Ok(dst)) = packet.dst_addr
+            && is_local(dst)
+        {
+            self.host(dst, seen);
+        }
+
+        let over_tcp = match packet.transport {
+            Some(Transport::Tcp) => true,
+            Some(Transport::Udp) => false,
+            _ => return,
+        };
+        if !matches!(packet.src_port, Some(53 | 5353)) {
+            return;
+        }
+        let Some(message) = dns::parse(packet.payload(), over_tcp) else {
+            return;
+        };
+        for record in &message.answers {
+            if let RecordData::Address(addr) = record.data
+                && is_local(addr)
+            {
+                let name = record.name.trim_end_matches('.').to_string();
+                self.host(addr, seen).names.insert(name);
+            }
+        }
+    }
+
+    /// The alias of `addr` or, failing that, of another address of the
+    /// same device; otherwise a name it answered to.
+    pub fn name(&self, addr: IpAddr) -> Option<&str> {
+        let host = self.hosts.get(&addr)?;
+        let device_alias = || {
+            let mac = host.mac.as_ref()?;
+            self.hosts
+                .values()
+                .find(|other| other.mac.as_ref() == Some(mac) && other.alias.is_some())?
+                .alias
+                .as_deref()
+        };
+        host.alias
+            .as_deref()
+            .or_else(device_alias)
+            .or_else(|| host.names.first().map(String::as_str))
+    }
+}
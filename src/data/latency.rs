@@ -0,0 +1,285 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    net::IpAddr,
+    time::{Duration, SystemTime},
+};
+
+use super::{
+    dns,
+    http::StartLine,
+    packet::{PacketInfo, Transport},
+    tls,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Service {
+    Dns,
+    Http,
+    Tls, // ClientHello to ServerHello
+    Tcp, // SYN to SYN-ACK
+}
+
+impl Service {
+    pub fn label(self) -> &'static str {
+        match self {
+            Service::Dns => "DNS",
+            Service::Http => "HTTP",
+            Service::Tls => "TLS",
+            Service::Tcp => "TCP",
+        }
+    }
+}
+
+/// One side of a conversation.
+pub type Endpoint = (IpAddr, u16);
+
+/// Client, server, service and DNS transaction ID of an exchange.
+type Exchange = (Endpoint, Endpoint, Service, Option<u16>);
+
+/// What a packet contributes to a request/response exchange.
+enum Message {
+    Request,
+    Response,
+}
+
+fn classify(packet: &PacketInfo) -> Option<(Service, Message, Option<u16>)> {
+    if packet.transport == Some(Transport::Tcp)
+        && let Some(flags) = packet.tcp_flags
+        && flags.syn
+    {
+        let kind = if flags.ack {
+            Message::Response
+        } else {
+            Message::Request
+        };
+        return Some((Service::Tcp, kind, None));
+    }
+    let payload = packet.payload();
+    if payload.is_empty() {
+        return None;
+    }
+    let has_port = |port| packet.src_port == Some(port) || packet.dst_port == Some(port);
+    match packet.transport? {
+        Transport::Udp | Transport::Tcp if has_port(dns::PORT) => {
+            let message = dns::parse(payload, packet.transport == Some(Transport::Tcp))?;
+            let kind = if message.is_response {
+                Message::Response
+            } else {
+                Message::Request
+            };
+            Some((Service::Dns, kind, Some(message.id)))
+        }
+        Transport::Tcp => {
+            match StartLine::at(payload) {
+                Some(StartLine::Request { .. }) => {
+                    return Some((Service::Http, Message::Request, None));
+                }
+                Some(StartLine::Response { .. }) => {
+                    return Some((Service::Http, Message::Response, None));
+                }
+                None => {}
+            }
+            let records = tls::parse(payload)?;
+            if records.client_hello.is_some() {
+                Some((Service::Tls, Message::Request, None))
+            } else if records.contents.contains(&"Server Hello") {
+                Some((Service::Tls, Message::Response, None))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// A request or response followed by a [`Matcher`].
+pub enum Matched<T> {
+    /// A request, with what was kept for the same one still waiting if it
+    /// repeats a DNS query or SYN
+    Request {
+        client: Endpoint,
+        server: Endpoint,
+        service: Service,
+        repeats: Option<T>,
+    },
+    /// A response, with what was kept for the request it answers and how
+    /// long that took, if the request was seen
+    Response {
+        server: Endpoint,
+        service: Service,
+        answers: Option<(T, Duration)>,
+    },
+}
+
+impl<T> Matched<T> {
+    pub fn service(&self) -> Service {
+        match *self {
+            Matched::Request { service, .. } | Matched::Response { service, .. } => service,
+        }
+    }
+}
+
+/// Pairs requests with their responses: DNS by transaction ID, HTTP and TLS
+/// in order on their connection, SYNs with their SYN-ACKs.
+#[derive(Debug)]
+pub struct Matcher<T> {
+    pending: HashMap<Exchange, VecDeque<(SystemTime, T)>>,
+}
+
+impl<T> Default for Matcher<T> {
+    fn default() -> Self {
+        Self {
+            pending: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Copy> Matcher<T> {
+    /// Follows `packet`, keeping `value` for it if it is a new request.
+    pub fn add(&mut self, packet: &PacketInfo, value: T) -> Option<Matched<T>> {
+        let (Some(Ok(src)), Some(src_port), Some(Ok(dst)), Some(dst_port)) = (
+            packet.src_addr.clone(),
+            packet.src_port,
+            packet.dst_addr.clone(),
+            packet.dst_port,
+        ) else {
+            return None;
+        };
+        let (service, message, id) = classify(packet)?;
+        match message {
+            Message::Request => {
+                let (client, server) = ((src, src_port), (dst, dst_port));
+                let queue = self
+                    .pending
+                    .entry((client, server, service, id))
+                    .or_default();
+                // A retransmitted DNS query or SYN keeps the time of the
+                // first.
+                let repeats = if (id.is_none() && service != Service::Tcp) || queue.is_empty() {
+                    queue.push_back((packet.captured_at, value));
+                    None
+                } else {
+                    queue.front().map(|&(_, value)| value)
+                };
+                Some(Matched::Request {
+                    client,
+                    server,
+                    service,
+                    repeats,
+                })
+            }
+            Message::Response => {
+                let (client, server) = ((dst, dst_port), (src, src_port));
+                let answers = self
+                    .pending
+                    .get_mut(&(client, server, service, id))
+                    .and_then(VecDeque::pop_front)
+                    .map(|(asked, value)| {
+                        (
+                            value,
+                            packet.captured_at.duration_since(asked).unwrap_or_default(),
+                        )
+                    });
+                Some(Matched::Response {
+                    server,
+                    service,
+                    answers,
+                })
+            }
+        }
+    }
+}
+
+/// Response times of one service on one server.
+#[derive(Debug, Clone)]
+pub struct Server {
+    pub addr: IpAddr,
+    pub port: u16,
+    pub service: Service,
+    pub requests: usize,
+    samples: Vec<Duration>, // Sorted
+}
+
+impl Server {
+    pub fn answered(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn unanswered(&self) -> usize {
+        self.requests.saturating_sub(self.samples.len())
+    }
+
+    /// The response time `percent` of the answered requests were within,
+    /// by the nearest-rank method.
+    pub fn percentile(&self, percent: u32) -> Option<Duration> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let rank = (self.samples.len() * percent as usize).div_ceil(100);
+        Some(self.samples[rank.clamp(1, self.samples.len()) - 1])
+    }
+}
+
+/// Response times of the request/response protocols decoded, by server,
+/// for checking services against latency objectives without instrumenting
+/// them.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyStats {
+    pub servers: BTreeMap<(IpAddr, u16, Service), Server>,
+}
+
+impl LatencyStats {
+    pub fn from_packets(packets: &[&PacketInfo]) -> Self {
+        let mut matcher = Matcher::default();
+        let mut stats = Self::default();
+        for packet in packets {
+            match matcher.add(packet, ()) {
+                Some(Matched::Request {
+                    server: (addr, port),
+                    service,
+                    ..
+                }) => {
+                    stats
+                        .servers
+                        .entry((addr, port, service))
+                        .or_insert(Server {
+                            addr,
+                            port,
+                            service,
+                            requests: 0,
+                            samples: Vec::new(),
+                        })
+                        .requests += 1;
+                }
+                Some(Matched::Response {
+                    server: (addr, port),
+                    service,
+                    answers: Some(((), latency)),
+                    ..
+                }) => {
+                    if let Some(server) = stats.servers.get_mut(&(addr, port, service)) {
+                        server.samples.push(latency);
+                    }
+                }
+                _ => {}
+            }
+        }
+        for server in stats.servers.values_mut() {
+            server.samples.sort();
+        }
+        stats
+    }
+
+    /// Servers with the slowest 95th percentile first, those without an
+    /// answer last.
+    pub fn by_p95(&self) -> Vec<&Server> {
+        let mut servers: Vec<&Server> = self.servers.values().collect();
+        servers.sort_by(|a, b| {
+            b.percentile(95)
+                .cmp(&a.percentile(95))
+                .then(a.addr.cmp(&b.addr))
+                .then(a.port.cmp(&b.port))
+        });
+        servers
+    }
+}
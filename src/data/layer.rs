@@ -0,0 +1,123 @@
+use std::ops::Range;
+
+/// A protocol decoded on top of the transport layer, as shown in the
+/// packet details page.
+#[derive(Debug, Clone)]
+pub struct Layer {
+    pub name: &'static str,
+    pub summary: String,
+    pub fields: Vec<Field>,
+    pub range: Option<Range<usize>>, // Bytes of the frame it was decoded from
+}
+
+#[derive(Debug, Clone)]
+pub struct Field {
+    pub name: String,
+    pub value: String,
+    pub detail: bool,                // Only shown at full verbosity
+    pub range: Option<Range<usize>>, // Bytes it was read from, when known
+}
+
+impl Field {
+    pub fn is_visible(&self, verbosity: Verbosity) -> bool {
+        match verbosity {
+            Verbosity::Summary => false,
+            Verbosity::Normal => !self.detail,
+            Verbosity::Full | Verbosity::Raw => true,
+        }
+    }
+}
+
+/// How much of each layer the packet details page shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    Summary,
+    #[default]
+    Normal,
+    Full,
+    Raw,
+}
+
+impl Verbosity {
+    pub fn next(self) -> Self {
+        match self {
+            Verbosity::Summary => Verbosity::Normal,
+            Verbosity::Normal => Verbosity::Full,
+            Verbosity::Full => Verbosity::Raw,
+            Verbosity::Raw => Verbosity::Summary,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Verbosity::Summary => "Summary",
+            Verbosity::Normal => "Normal",
+            Verbosity::Full => "Full",
+            Verbosity::Raw => "Raw",
+        }
+    }
+}
+
+impl Layer {
+    pub fn new(name: &'static str, summary: String) -> Self {
+        Self {
+            name,
+            summary,
+            fields: Vec::new(),
+            range: None,
+        }
+    }
+
+    /// Places the layer at `range` of the frame, the bytes its decoder was
+    /// given, moving the ranges of its fields from there too.
+    pub fn placed(mut self, range: Range<usize>) -> Self {
+        for field in &mut self.fields {
+            field.range = field
+                .range
+                .take()
+                .map(|bytes| range.start + bytes.start..range.start + bytes.end);
+        }
+        self.range = Some(range);
+        self
+    }
+
+    pub fn field(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.fields.push(Field {
+            name: name.into(),
+            value: value.into(),
+            detail: false,
+            range: None,
+        });
+    }
+
+    /// Adds a field read from `range` of the bytes given to the decoder.
+    pub fn field_at(
+        &mut self,
+        range: Range<usize>,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) {
+        self.fields.push(Field {
+            name: name.into(),
+            value: value.into(),
+            detail: false,
+            range: Some(range),
+        });
+    }
+
+    /// Adds a field that is only shown at full verbosity.
+    pub fn detail(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.fields.push(Field {
+            name: name.into(),
+            value: value.into(),
+            detail: true,
+            range: None,
+        });
+    }
+
+    pub fn visible_fields(&self, verbosity: Verbosity) -> impl Iterator<Item = &Field> {
+        self.fields
+            .iter()
+            .filter(move |field| field.is_visible(verbosity))
+    }
+}
@@ -0,0 +1,139 @@
+use super::{layer::Layer, packet::format_mac};
+
+/// BSD loopback: a 4-byte address family in the capturing host's order.
+pub const LINKTYPE_NULL: u32 = 0;
+/// Bare IPv4 or IPv6 packets.
+pub const LINKTYPE_RAW: u32 = 101;
+/// OpenBSD loopback: like `LINKTYPE_NULL` with the family big-endian.
+pub const LINKTYPE_LOOP: u32 = 108;
+/// Linux "cooked" captures, as on the `any` device.
+pub const LINKTYPE_LINUX_SLL: u32 = 113;
+pub const LINKTYPE_IPV4: u32 = 228;
+pub const LINKTYPE_IPV6: u32 = 229;
+pub const LINKTYPE_LINUX_SLL2: u32 = 276;
+
+/// Values libpcap reports for raw IP where its DLT differs from the
+/// LINKTYPE written to files.
+const DLT_RAW: [u32; 2] = [12, 14];
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+/// Protocol values below this are lengths or Linux pseudo-protocols
+/// rather than EtherTypes.
+const MIN_ETHERTYPE: u16 = 0x0600;
+/// ARPHRD_NETLINK, whose protocol field is a netlink family.
+const ARPHRD_NETLINK: u16 = 824;
+
+/// Layers describing the capture's framing rather than what it carries.
+pub const NAMES: [&str; 1] = ["SLL"];
+
+/// The framing of a frame other than Ethernet, and what it carries.
+pub struct Framing<'a> {
+    pub layers: Vec<Layer>,
+    pub source: Option<&'a [u8]>, // Transmitter of 802.11 control frames
+    pub destination: Option<&'a [u8]>, // Receiver of 802.11 control frames
+    /// EtherType and payload of what the framing carries, if decodable
+    pub payload: Option<(u16, &'a [u8])>,
+}
+
+impl<'a> Framing<'a> {
+    fn carrying(payload: Option<(u16, &'a [u8])>) -> Self {
+        Self {
+            layers: Vec::new(),
+            source: None,
+            destination: None,
+            payload,
+        }
+    }
+}
+
+/// The LINKTYPE to write to files for a link type libpcap reported.
+pub fn file_link_type(link_type: u32) -> u32 {
+    if DLT_RAW.contains(&link_type) {
+        LINKTYPE_RAW
+    } else {
+        link_type
+    }
+}
+
+fn u16_at(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+/// An IP packet with the EtherType of its version.
+fn ip(packet: &[u8]) -> Option<(u16, &[u8])> {
+    match packet.first()? >> 4 {
+        4 => Some((ETHERTYPE_IPV4, packet)),
+        6 => Some((ETHERTYPE_IPV6, packet)),
+        _ => None,
+    }
+}
+
+fn packet_type_name(packet_type: u16) -> &'static str {
+    match packet_type {
+        0 => "Unicast to us",
+        1 => "Broadcast",
+        2 => "Multicast",
+        3 => "Unicast to another host",
+        4 => "Sent by us",
+        _ => "Unknown",
+    }
+}
+
+/// Decodes a Linux cooked header: the first version is 16 bytes with the
+/// protocol last, the second 20 bytes with it first and an interface index.
+fn cooked(data: &[u8], version: u8) -> Option<Framing<'_>> {
+    let (header_len, protocol, hardware, packet_type, address) = if version == 1 {
+        let address_len = u16_at(data, 4)?.min(8) as usize;
+        (
+            16,
+            u16_at(data, 14)?,
+            u16_at(data, 2)?,
+            u16_at(data, 0)?,
+            data.get(6..6 + address_len)?,
+        )
+    } else {
+        let address_len = (*data.get(11)?).min(8) as usize;
+        (
+            20,
+            u16_at(data, 0)?,
+            u16_at(data, 8)?,
+            u16::from(*data.get(10)?),
+            data.get(12..12 + address_len)?,
+        )
+    };
+    let payload = data.get(header_len..)?;
+
+    let direction = packet_type_name(packet_type);
+    let mut layer = Layer::new("SLL", direction.to_string());
+    layer.field("Packet Type", direction);
+    if version == 2 {
+        let index = u32::from_be_bytes(data.get(4..8)?.try_into().ok()?);
+        layer.field("Interface Index", index.to_string());
+    }
+    layer.detail("Hardware Type", hardware.to_string());
+    if !address.is_empty() {
+        layer.field("Source", format_mac(address));
+    }
+    layer.field("Protocol", format!("0x{protocol:04x}"));
+
+    let carries_ethertype = protocol >= MIN_ETHERTYPE && hardware != ARPHRD_NETLINK;
+    let mut framing = Framing::carrying(carries_ethertype.then_some((protocol, payload)));
+    framing.layers.push(layer);
+    // Only a 6-byte address is a MAC worth listing as the source.
+    framing.source = (address.len() == 6).then_some(address);
+    Some(framing)
+}
+
+/// Decodes the framing of a `link_type` frame for the loopback, raw IP and
+/// Linux cooked link types, or None for any other link type.
+pub fn decode(link_type: u32, data: &[u8]) -> Option<Framing<'_>> {
+    match link_type {
+        LINKTYPE_NULL | LINKTYPE_LOOP => Some(Framing::carrying(data.get(4..).and_then(ip))),
+        LINKTYPE_RAW | LINKTYPE_IPV4 | LINKTYPE_IPV6 => Some(Framing::carrying(ip(data))),
+        _ if DLT_RAW.contains(&link_type) => Some(Framing::carrying(ip(data))),
+        LINKTYPE_LINUX_SLL => Some(cooked(data, 1).unwrap_or(Framing::carrying(None))),
+        LINKTYPE_LINUX_SLL2 => Some(cooked(data, 2).unwrap_or(Framing::carrying(None))),
+        _ => None,
+    }
+}
@@ -0,0 +1,422 @@
+use std::{
+    collections::HashSet,
+    fs,
+    net::IpAddr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::{Context, Result, anyhow};
+#[cfg(feature = "libpcap")]
+use pcap::{Active, Capture, Linktype};
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+#[cfg(feature = "libpcap")]
+use super::capture_file::header_time;
+#[cfg(target_os = "linux")]
+use super::packet_socket::{self, PacketSocket};
+#[cfg(feature = "rpcap")]
+use super::rpcap::RemoteCapture;
+use super::{
+    capture_options::{Backend, CaptureOptions},
+    demo::{self, DemoCapture},
+    device::Device,
+    packet::PacketInfo,
+    rpcap,
+};
+
+/// How often the capture thread reads the capture's counters.
+const STATS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Packets parsed but not yet taken from the channel, a dozen ticks' worth
+/// at the default batch size. Past it the capture threads drop packets and
+/// count them rather than queue them without limit.
+pub const QUEUE_CAPACITY: usize = 65_536;
+
+/// Bytes kept of each captured frame unless `--snaplen` says otherwise.
+pub const SNAPLEN: u32 = 5000;
+
+/// Link-layer header and a VLAN tag, on top of the MTU.
+const LINK_OVERHEAD: u32 = 18;
+
+/// Name of the pseudo-device that captures on every interface at once,
+/// like tcpdump's `-i any` but with one libpcap handle per interface.
+pub const ALL_INTERFACES: &str = "all";
+
+/// What `--interface` takes for the interface `default_interface` finds.
+pub const AUTO_INTERFACE: &str = "auto";
+
+/// `parse_packet` or `parse_headers`.
+pub type Parser = fn(usize, Duration, SystemTime, u32, Arc<[u8]>) -> PacketInfo;
+
+/// Frame counters of a capture, as libpcap keeps them: frames received,
+/// dropped for want of buffer space, and dropped by the interface.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stat {
+    pub received: u32,
+    pub dropped: u32,
+    pub if_dropped: u32,
+}
+
+/// Something live frames are read from: a local libpcap handle, a Linux
+/// packet socket, a libpcap handle on an rpcapd host, or synthesized
+/// traffic.
+pub trait CaptureBackend: Send {
+    /// Applies a BPF filter, dropping the frames it doesn't match.
+    fn filter(&mut self, filter: &str) -> Result<()>;
+
+    /// The counters since the capture was opened, if they can be read.
+    fn stats(&mut self) -> Option<Stat>;
+
+    /// The next frame with the time it was captured, taken from the
+    /// packet header rather than when this thread got to it. `None` when
+    /// none came within the read timeout.
+    fn next_packet(&mut self) -> Option<(SystemTime, &[u8])>;
+
+    fn link_type(&self) -> u32;
+}
+
+#[cfg(feature = "libpcap")]
+impl CaptureBackend for Capture<Active> {
+    fn filter(&mut self, filter: &str) -> Result<()> {
+        Ok(Capture::filter(self, filter, true)?)
+    }
+
+    fn stats(&mut self) -> Option<Stat> {
+        let stat = Capture::stats(self).ok()?;
+        Some(Stat {
+            received: stat.received,
+            dropped: stat.dropped,
+            if_dropped: stat.if_dropped,
+        })
+    }
+
+    fn next_packet(&mut self) -> Option<(SystemTime, &[u8])> {
+        Capture::next_packet(self)
+            .ok()
+            .map(|packet| (header_time(packet.header), packet.data))
+    }
+
+    fn link_type(&self) -> u32 {
+        self.get_datalink().0 as u32
+    }
+}
+
+/// A capture handle, named when its packets are merged with others.
+pub struct Source {
+    pub interface: Option<Arc<str>>,
+    pub capture: Box<dyn CaptureBackend>,
+}
+
+/// Attaches `filter` to a capture just opened; the empty filter leaves it
+/// taking every frame.
+fn filtered(mut capture: Box<dyn CaptureBackend>, filter: &str) -> Result<Box<dyn CaptureBackend>> {
+    if !filter.is_empty() {
+        capture
+            .filter(filter)
+            .with_context(|| format!("Invalid capture filter {filter}"))?;
+    }
+    Ok(capture)
+}
+
+fn open_device(device: Device, options: &CaptureOptions) -> Result<Box<dyn CaptureBackend>> {
+    match options.backend {
+        #[cfg(feature = "libpcap")]
+        Backend::Libpcap => filtered(Box::new(open_pcap(device, options)?), &options.filter),
+        #[cfg(not(feature = "libpcap"))]
+        Backend::Libpcap => {
+            anyhow::bail!("Built without libpcap; capture with the af-packet backend")
+        }
+        // Attaches the filter itself, before the socket is bound.
+        #[cfg(target_os = "linux")]
+        Backend::AfPacket => Ok(Box::new(PacketSocket::open(&device.name, options)?)),
+        #[cfg(not(target_os = "linux"))]
+        Backend::AfPacket => anyhow::bail!("The af-packet backend needs Linux"),
+    }
+}
+
+#[cfg(feature = "libpcap")]
+fn open_pcap(device: Device, options: &CaptureOptions) -> Result<Capture<Active>, pcap::Error> {
+    let monitor = options.monitor && is_wireless(&device.name);
+    let mut capture = Capture::from_device(device.name.as_str())?
+        .promisc(options.promisc)
+        .snaplen(options.snaplen as i32)
+        .timeout(options.timeout as i32)
+        .immediate_mode(options.immediate)
+        .rfmon(monitor);
+    if let Some(size) = options.buffer_size {
+        let bytes = size.saturating_mul(1024 * 1024);
+        capture = capture.buffer_size(i32::try_from(bytes).unwrap_or(i32::MAX));
+    }
+    capture.open()
+}
+
+/// Whether `device_name` is a Wi-Fi interface, which can capture in
+/// monitor mode. Only known where Linux's sysfs says so.
+pub fn is_wireless(device_name: &str) -> bool {
+    let dir = format!("/sys/class/net/{device_name}");
+    fs::exists(format!("{dir}/wireless")).unwrap_or(false)
+        || fs::exists(format!("{dir}/phy80211")).unwrap_or(false)
+}
+
+/// The interface carrying the default route, from Linux's routing table,
+/// else the one libpcap would pick.
+pub fn default_interface() -> Option<String> {
+    // Interface, destination, gateway, flags, ... with the destination in hex
+    let routes = fs::read_to_string("/proc/net/route").unwrap_or_default();
+    let default_route = routes.lines().skip(1).find_map(|line| {
+        let mut columns = line.split_whitespace();
+        let interface = columns.next()?;
+        (columns.next()? == "00000000").then(|| interface.to_string())
+    });
+    default_route.or_else(|| Some(Device::lookup().ok()??.name))
+}
+
+/// `name` as given to `--interface`, with `AUTO_INTERFACE` replaced by the
+/// default interface.
+pub fn resolve_interface(name: String) -> Result<String> {
+    if name != AUTO_INTERFACE {
+        return Ok(name);
+    }
+    default_interface().ok_or_else(|| anyhow!("No default interface found; pick one by name"))
+}
+
+/// The longest frame `device_name` can deliver, from the interface MTU,
+/// or the longest of any interface for `ALL_INTERFACES`. `None` where the
+/// MTU can't be read: remote devices and systems without Linux's sysfs.
+pub fn largest_frame(device_name: &str) -> Option<u32> {
+    let mtu = |name: &str| -> Option<u32> {
+        let mtu = fs::read_to_string(format!("/sys/class/net/{name}/mtu")).ok()?;
+        mtu.trim().parse().ok()
+    };
+    let largest = if rpcap::is_remote(device_name) {
+        return None;
+    } else if device_name == ALL_INTERFACES {
+        Device::list()
+            .ok()?
+            .iter()
+            .filter_map(|device| mtu(&device.name))
+            .max()?
+    } else {
+        mtu(device_name)?
+    };
+    Some(largest.saturating_add(LINK_OVERHEAD))
+}
+
+/// The addresses of this host's interfaces, none if they can't be listed.
+pub fn local_addrs() -> HashSet<IpAddr> {
+    Device::list()
+        .unwrap_or_default()
+        .iter()
+        .flat_map(|device| device.addresses.iter().map(|address| address.addr))
+        .collect()
+}
+
+/// Opens the device named `device_name` with `options`, or every device
+/// that can be opened for `ALL_INTERFACES`, with the capture filter in
+/// `options` attached. `rpcap://` URLs are opened on their rpcapd host, and
+/// the `demo` pseudo-device synthesizes traffic instead.
+pub fn open(device_name: &str, options: &CaptureOptions) -> Result<Vec<Source>> {
+    if device_name == demo::DEVICE {
+        return Ok(vec![Source {
+            interface: None,
+            capture: filtered(Box::new(DemoCapture::new()), &options.filter)?,
+        }]);
+    }
+    if rpcap::is_remote(device_name) {
+        #[cfg(not(feature = "rpcap"))]
+        anyhow::bail!("Remote capture unsupported: built without the rpcap feature");
+        #[cfg(feature = "rpcap")]
+        return Ok(vec![Source {
+            interface: None,
+            capture: filtered(
+                Box::new(RemoteCapture::open(device_name, options)?),
+                &options.filter,
+            )?,
+        }]);
+    }
+    let devices = Device::list().context("Failed to list devices")?;
+    if device_name != ALL_INTERFACES {
+        let device = devices
+            .into_iter()
+            .find(|d| d.name == device_name)
+            .context("Device not found")?;
+        return Ok(vec![Source {
+            interface: None,
+            capture: open_device(device, options)?,
+        }]);
+    }
+
+    let mut last_error = None;
+    let mut sources = Vec::new();
+    // libpcap's own "any" device would see every packet a second time.
+    for device in devices.into_iter().filter(|d| d.name != "any") {
+        let interface = Arc::from(device.name.as_str());
+        match open_device(device, options) {
+            Ok(capture) => sources.push(Source {
+                interface: Some(interface),
+                capture,
+            }),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    match last_error {
+        Some(e) if sources.is_empty() => Err(e).context("No device could be opened"),
+        _ => Ok(sources),
+    }
+}
+
+/// Checks that live capture is possible by briefly opening the default
+/// device, failing with libpcap's reason (usually missing privileges) when
+/// it is not.
+#[cfg(feature = "libpcap")]
+pub fn check_access() -> Result<()> {
+    let device = Device::lookup()
+        .context("Failed to list devices")?
+        .context("No capture device found")?;
+    Capture::from_device(device.name.as_str())?
+        .snaplen(SNAPLEN as i32)
+        .open()
+        .context("Cannot open a capture device")?;
+    Ok(())
+}
+
+/// Checks that live capture is possible by opening a packet socket,
+/// which the af-packet backend needs.
+#[cfg(not(feature = "libpcap"))]
+pub fn check_access() -> Result<()> {
+    #[cfg(target_os = "linux")]
+    return packet_socket::check_access().context("Cannot open a capture device");
+    #[cfg(not(target_os = "linux"))]
+    anyhow::bail!("Built without libpcap, and the af-packet backend needs Linux");
+}
+
+/// Compiles a BPF filter for Ethernet without opening a device, to tell
+/// whether it is valid before a capture starts with it. Raw programs, which
+/// only the af-packet backend takes, are parsed instead.
+pub fn check_filter(filter: &str) -> Result<()> {
+    #[cfg(target_os = "linux")]
+    if let Some(program) = packet_socket::raw_program(filter) {
+        return program.map(drop);
+    }
+    #[cfg(feature = "libpcap")]
+    Capture::dead(Linktype::ETHERNET)?.compile(filter, true)?;
+    #[cfg(not(feature = "libpcap"))]
+    if !filter.trim().is_empty() {
+        anyhow::bail!(
+            "Built without libpcap, so filters must be BPF programs as `tcpdump -ddd` prints them"
+        );
+    }
+    Ok(())
+}
+
+fn add_stats(total: &mut Stat, now: Stat, before: Stat) {
+    total.received = total
+        .received
+        .wrapping_add(now.received.wrapping_sub(before.received));
+    total.dropped = total
+        .dropped
+        .wrapping_add(now.dropped.wrapping_sub(before.dropped));
+    total.if_dropped = total
+        .if_dropped
+        .wrapping_add(now.if_dropped.wrapping_sub(before.if_dropped));
+}
+
+/// What the capture threads of one `spawn` share.
+struct Shared {
+    parse: Parser,
+    start: SystemTime,
+    packet_id: Mutex<usize>, // Numbering and sending happen together so ids follow channel order
+    packet_tx: mpsc::Sender<PacketInfo>,
+    stop: Arc<AtomicBool>,
+    stats: Arc<Mutex<Option<Stat>>>,
+    queue_dropped: Arc<AtomicUsize>,
+}
+
+fn read_packets(source: Source, shared: &Shared) {
+    let Source {
+        interface,
+        mut capture,
+    } = source;
+    let link_type = capture.link_type();
+    let mut stats_updated: Option<Instant> = None;
+    let mut counted = Stat {
+        received: 0,
+        dropped: 0,
+        if_dropped: 0,
+    };
+    while !shared.stop.load(Ordering::Relaxed) {
+        if stats_updated.is_none_or(|at| at.elapsed() >= STATS_INTERVAL) {
+            stats_updated = Some(Instant::now());
+            if let (Some(stat), Ok(mut total)) = (capture.stats(), shared.stats.lock()) {
+                add_stats(total.get_or_insert(counted), stat, counted);
+                counted = stat;
+            }
+        }
+        if let Some((captured_at, data)) = capture.next_packet() {
+            let timestamp = captured_at.duration_since(shared.start).unwrap_or_default();
+
+            let mut packet_info = (shared.parse)(0, timestamp, captured_at, link_type, data.into());
+            packet_info.interface = interface.clone();
+
+            let Ok(mut packet_id) = shared.packet_id.lock() else {
+                break;
+            };
+            *packet_id += 1;
+            packet_info.id = *packet_id;
+            match shared.packet_tx.try_send(packet_info) {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) => {
+                    // Numbers stay contiguous over the packets kept.
+                    *packet_id -= 1;
+                    shared.queue_dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(TrySendError::Closed(_)) => break,
+            }
+        }
+    }
+}
+
+/// Reads packets from every source on a thread of its own until `stop` is
+/// set or the receiver is dropped, merging them into one channel numbered
+/// from 1 in arrival order and timed from now. The threads also keep
+/// `stats` at libpcap's counters summed over all sources, and count in
+/// `queue_dropped` the packets dropped with [`QUEUE_CAPACITY`] waiting. The
+/// returned handle finishes once every capture thread has.
+pub fn spawn(
+    sources: Vec<Source>,
+    parse: Parser,
+    stop: Arc<AtomicBool>,
+    stats: Arc<Mutex<Option<Stat>>>,
+    queue_dropped: Arc<AtomicUsize>,
+) -> (JoinHandle<()>, mpsc::Receiver<PacketInfo>) {
+    let (packet_tx, packet_rx) = mpsc::channel(QUEUE_CAPACITY);
+    let shared = Arc::new(Shared {
+        parse,
+        start: SystemTime::now(),
+        packet_id: Mutex::new(0),
+        packet_tx,
+        stop,
+        stats,
+        queue_dropped,
+    });
+    let handles: Vec<JoinHandle<()>> = sources
+        .into_iter()
+        .map(|source| {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || read_packets(source, &shared))
+        })
+        .collect();
+    // The channel closes once the last capture thread drops `shared`.
+    drop(shared);
+    let handle = thread::spawn(move || {
+        for handle in handles {
+            let _ = handle.join();
+        }
+    });
+    (handle, packet_rx)
+}
@@ -0,0 +1,206 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use etherparse::SlicedPacket;
+
+use super::{capture_file::CaptureFile, packet::PacketInfo, time::add_secs};
+
+/// How a second capture was merged into the first.
+#[derive(Debug, Clone)]
+pub struct Merge {
+    pub path: PathBuf,
+    pub offset: f64, // Seconds the second capture's clock was ahead, removed from it
+    pub matched: usize, // Packets seen at both capture points
+    pub both_directions: bool, // Whether matches went both ways, separating offset from delay
+}
+
+impl Merge {
+    pub fn describe(&self) -> String {
+        let basis = if self.both_directions {
+            "both directions"
+        } else {
+            "one direction only, so it includes the one-way delay"
+        };
+        format!(
+            "{:+.6} s from {} matching packets ({basis})",
+            self.offset, self.matched
+        )
+    }
+}
+
+/// Identifies a packet by its addresses and everything above the IP header,
+/// which stay the same along the path while TTL and checksum do not.
+fn fingerprint(packet: &PacketInfo) -> Option<u64> {
+    let sliced = SlicedPacket::from_ethernet(&packet.data)
+        .or_else(|_| SlicedPacket::from_ip(&packet.data))
+        .ok()?;
+    let payload = sliced.net?.ip_payload_ref()?.payload;
+    let mut hasher = DefaultHasher::new();
+    packet.src_addr.hash(&mut hasher);
+    packet.dst_addr.hash(&mut hasher);
+    payload.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Packets that occur exactly once in `packets`, by fingerprint; repeated
+/// ones such as retransmissions can't be told apart.
+fn unique(packets: &[PacketInfo]) -> HashMap<u64, Option<&PacketInfo>> {
+    let mut unique: HashMap<u64, Option<&PacketInfo>> = HashMap::new();
+    for packet in packets {
+        if let Some(fingerprint) = fingerprint(packet) {
+            unique
+                .entry(fingerprint)
+                .and_modify(|seen| *seen = None)
+                .or_insert(Some(packet));
+        }
+    }
+    unique
+}
+
+fn secs(at: SystemTime) -> f64 {
+    match at.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.as_secs_f64(),
+        Err(e) => -e.duration().as_secs_f64(),
+    }
+}
+
+/// Estimates how far the clock of `second` was ahead of that of `first`
+/// from the packets both captured. A packet sent past the first capture
+/// point reaches the second later, so its difference is the offset plus
+/// the delay, while a reply going the other way gives the offset minus the
+/// delay; the offset lies between the smallest of the former and the
+/// largest of the latter. Each pair of hosts seen both ways gives such an
+/// estimate and the median is taken. Returns the offset, the packets it is
+/// based on and whether any pair was seen both ways.
+fn estimate_offset(first: &[PacketInfo], second: &[PacketInfo]) -> Option<(f64, usize, bool)> {
+    let first = unique(first);
+    type Addr = Option<Result<IpAddr, String>>;
+    let mut directions: HashMap<(Addr, Addr), Vec<f64>> = HashMap::new();
+    for (fingerprint, packet) in unique(second) {
+        let (Some(packet), Some(Some(other))) = (packet, first.get(&fingerprint)) else {
+            continue;
+        };
+        let difference = secs(packet.captured_at) - secs(other.captured_at);
+        directions
+            .entry((packet.src_addr.clone(), packet.dst_addr.clone()))
+            .or_default()
+            .push(difference);
+    }
+    let matched = directions.values().map(Vec::len).sum();
+    for differences in directions.values_mut() {
+        differences.sort_by(f64::total_cmp);
+    }
+    let median = |differences: &[f64]| differences[differences.len() / 2];
+
+    let mut estimates: Vec<f64> = Vec::new();
+    for ((src, dst), a) in &directions {
+        let Some(b) = directions.get(&(dst.clone(), src.clone())) else {
+            continue;
+        };
+        // Each pair once.
+        if src > dst {
+            continue;
+        }
+        let (forward, reverse) = if median(a) >= median(b) {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        estimates.push((forward[0] + reverse[reverse.len() - 1]) / 2.0);
+    }
+    if !estimates.is_empty() {
+        estimates.sort_by(f64::total_cmp);
+        return Some((median(&estimates), matched, true));
+    }
+    let mut differences: Vec<f64> = directions.into_values().flatten().collect();
+    if differences.is_empty() {
+        return None;
+    }
+    differences.sort_by(f64::total_cmp);
+    Some((median(&differences), matched, false))
+}
+
+/// Names a capture point after its file.
+fn capture_point(path: &Path) -> String {
+    path.file_stem()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Merges `second`, captured at the same time elsewhere on the path, into
+/// `first`: its clock is brought in line with the first's, the packets of
+/// both are interleaved by time and each is labelled with its capture point,
+/// so loss and delay can be placed between the two. Without packets in
+/// common the clocks are taken to agree.
+pub fn merge(first: &mut CaptureFile, second: CaptureFile) {
+    let (offset, matched, both_directions) =
+        estimate_offset(&first.packets, &second.packets).unwrap_or((0.0, 0, false));
+
+    let first_point: Arc<str> = Arc::from(capture_point(&first.summary.path));
+    let mut second_point = capture_point(&second.summary.path);
+    if *first_point == *second_point {
+        second_point.push_str(" (2)");
+    }
+    let second_point: Arc<str> = Arc::from(second_point);
+    for packet in &mut first.packets {
+        packet.interface = Some(Arc::clone(&first_point));
+    }
+    let mut first_comments = std::mem::take(&mut first.comments);
+    let mut second_comments = second.comments;
+    // Each packet with its comment, so comments follow their packets.
+    let mut commented: Vec<(PacketInfo, Option<String>)> = std::mem::take(&mut first.packets)
+        .into_iter()
+        .enumerate()
+        .map(|(i, packet)| (packet, first_comments.remove(&i)))
+        .collect();
+    commented.extend(
+        second
+            .packets
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut packet)| {
+                packet.captured_at =
+                    add_secs(packet.captured_at, -offset).unwrap_or(packet.captured_at);
+                packet.interface = Some(Arc::clone(&second_point));
+                (packet, second_comments.remove(&i))
+            }),
+    );
+    // Stable, so the first capture's copy of a packet comes first on a tie.
+    commented.sort_by_key(|(packet, _)| packet.captured_at);
+    let mut packets = Vec::with_capacity(commented.len());
+    for (i, (packet, comment)) in commented.into_iter().enumerate() {
+        if let Some(comment) = comment {
+            first.comments.insert(i, comment);
+        }
+        packets.push(packet);
+    }
+
+    let start = packets.first().map(|packet| packet.captured_at);
+    for (i, packet) in packets.iter_mut().enumerate() {
+        packet.id = i + 1;
+        packet.timestamp = start
+            .and_then(|start| packet.captured_at.duration_since(start).ok())
+            .unwrap_or_default();
+    }
+
+    let summary = &mut first.summary;
+    summary.file_size += second.summary.file_size;
+    summary.packet_count = packets.len();
+    summary.data_bytes += second.summary.data_bytes;
+    summary.first = start;
+    summary.last = packets.last().map(|packet| packet.captured_at);
+    summary.merged = Some(Merge {
+        path: second.summary.path,
+        offset,
+        matched,
+        both_directions,
+    });
+    first.packets = packets;
+}
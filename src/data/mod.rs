@@ -1 +1,78 @@
+pub mod alert;
+pub mod alert_rule;
+pub mod burst;
+// Reading files, remote capture and replay are left unreachable without
+// libpcap.
+#[cfg_attr(not(feature = "libpcap"), allow(dead_code, unused_imports))]
+pub mod capture_file;
+pub mod capture_options;
+pub mod checksum;
+pub mod conversation;
+pub mod demo;
+pub mod device;
+pub mod device_type;
+pub mod discovery;
+pub mod display_filter;
+pub mod dns;
+pub mod dns_anomaly;
+pub mod dns_stats;
+pub mod dual_stack;
+pub mod encrypted_dns;
+pub mod endpoint;
+pub mod ethertype;
+pub mod filter_completion;
+pub mod filter_history;
+pub mod flow;
+pub mod flow_tracker;
+pub mod geoip;
+pub mod handshake;
+pub mod headers;
+pub mod hpack;
+pub mod http;
+pub mod http_objects;
+pub mod http_transactions;
+pub mod http2;
+pub mod icmp;
+pub mod knowledge;
+pub mod latency;
+pub mod layer;
+pub mod link;
+pub mod live;
+pub mod merge;
+pub mod multicast;
+pub mod neighbor;
+pub mod netbios;
+pub mod ntp;
 pub mod packet;
+pub mod packet_export;
+#[cfg(target_os = "linux")]
+pub mod packet_socket;
+pub mod plugin;
+pub mod privileges;
+pub mod profile;
+#[cfg_attr(not(feature = "libpcap"), allow(dead_code, unused_imports))]
+pub mod replay;
+pub mod ring;
+pub mod routing;
+#[cfg_attr(not(feature = "rpcap"), allow(dead_code, unused_imports))]
+pub mod rpcap;
+pub mod rtp;
+pub mod saved_filters;
+pub mod search;
+pub mod sip;
+pub mod smb;
+pub mod spool;
+pub mod stats_log;
+pub mod time;
+pub mod tcp_analysis;
+pub mod tcp_graph;
+pub mod tcp_ladder;
+pub mod tls;
+pub mod top_talkers;
+pub mod tunnel;
+pub mod udp_stream;
+pub mod vlan;
+pub mod voip;
+pub mod vpn;
+pub mod wlan;
+pub mod wol;
@@ -0,0 +1,388 @@
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+};
+
+use etherparse::{InternetSlice, SlicedPacket, TransportSlice};
+
+use super::{layer::Layer, packet::PacketInfo};
+
+/// IP protocol number of IGMP.
+pub const IGMP_PROTOCOL: u8 = 2;
+
+const IGMP_QUERY: u8 = 0x11;
+const IGMP_V1_REPORT: u8 = 0x12;
+const IGMP_V2_REPORT: u8 = 0x16;
+const IGMP_LEAVE: u8 = 0x17;
+const IGMP_V3_REPORT: u8 = 0x22;
+
+const MLD_QUERY: u8 = 130;
+const MLD_REPORT: u8 = 131;
+const MLD_DONE: u8 = 132;
+const MLD_V2_REPORT: u8 = 143;
+
+/// ICMPv6 types of MLD messages.
+pub const MLD_TYPES: [u8; 4] = [MLD_QUERY, MLD_REPORT, MLD_DONE, MLD_V2_REPORT];
+
+/// Source filter record types of IGMPv3 and MLDv2 reports.
+const MODE_IS_INCLUDE: u8 = 1;
+const CHANGE_TO_INCLUDE: u8 = 3;
+const BLOCK_OLD_SOURCES: u8 = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Igmp,
+    Mld,
+}
+
+impl Protocol {
+    pub fn label(self) -> &'static str {
+        match self {
+            Protocol::Igmp => "IGMP",
+            Protocol::Mld => "MLD",
+        }
+    }
+}
+
+/// What a report says of its sender's membership of a group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Join,
+    Leave,
+    /// Sources blocked without leaving the group
+    Keep,
+}
+
+/// A membership report or query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// A query for `group`, or for every group when None
+    Query {
+        group: Option<IpAddr>,
+        sources: usize,
+        max_response: u32, // Milliseconds
+    },
+    /// Groups joined or left, each with the sources it names
+    Report {
+        records: Vec<(IpAddr, Change, usize)>,
+    },
+}
+
+/// A membership message with the protocol version it was sent in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Membership {
+    pub protocol: Protocol,
+    pub version: u8,
+    pub message: Message,
+}
+
+fn u16_at(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn address(bytes: &[u8], v6: bool) -> Option<IpAddr> {
+    if v6 {
+        Some(IpAddr::V6(Ipv6Addr::from(
+            <[u8; 16]>::try_from(bytes.get(..16)?).ok()?,
+        )))
+    } else {
+        Some(IpAddr::V4(Ipv4Addr::from(
+            <[u8; 4]>::try_from(bytes.get(..4)?).ok()?,
+        )))
+    }
+}
+
+/// Group records of an IGMPv3 or MLDv2 report: a type, auxiliary data
+/// length in words, source count and group, then the sources and
+/// auxiliary data.
+fn records(mut rest: &[u8], count: u16, v6: bool) -> Vec<(IpAddr, Change, usize)> {
+    let address_len = if v6 { 16 } else { 4 };
+    let mut records = Vec::new();
+    for _ in 0..count {
+        let (Some(&[record_type, aux_len]), Some(sources)) = (rest.get(..2), u16_at(rest, 2))
+        else {
+            break;
+        };
+        let Some(group) = rest.get(4..).and_then(|group| address(group, v6)) else {
+            break;
+        };
+        let sources = usize::from(sources);
+        let change = match record_type {
+            MODE_IS_INCLUDE | CHANGE_TO_INCLUDE if sources == 0 => Change::Leave,
+            BLOCK_OLD_SOURCES => Change::Keep,
+            _ => Change::Join,
+        };
+        records.push((group, change, sources));
+        let len = 4 + address_len * (1 + sources) + 4 * usize::from(aux_len);
+        let Some(next) = rest.get(len..) else {
+            break;
+        };
+        rest = next;
+    }
+    records
+}
+
+/// A general query names the unspecified address as its group.
+fn queried_group(group: IpAddr) -> Option<IpAddr> {
+    (!group.is_unspecified()).then_some(group)
+}
+
+/// Reads an IGMP message, the payload of an IPv4 packet of protocol 2.
+pub fn parse_igmp(payload: &[u8]) -> Option<Membership> {
+    let igmp_type = *payload.first()?;
+    let group = address(payload.get(4..)?, false)?;
+    let (version, message) = match igmp_type {
+        IGMP_QUERY => {
+            // Version 1 leaves the response time zero; version 3 is longer.
+            let version = match (payload.len() >= 12, payload[1]) {
+                (true, _) => 3,
+                (false, 0) => 1,
+                (false, _) => 2,
+            };
+            let max_response = if version == 3 && payload[1] >= 128 {
+                // Floating point: a 4-bit mantissa and 3-bit exponent.
+                let code = payload[1];
+                u32::from(code & 0x0f | 0x10) << ((code >> 4 & 0x07) + 3)
+            } else {
+                u32::from(payload[1])
+            };
+            let sources = if version == 3 {
+                u16_at(payload, 10)?
+            } else {
+                0
+            };
+            let message = Message::Query {
+                group: queried_group(group),
+                sources: usize::from(sources),
+                max_response: max_response * 100,
+            };
+            (version, message)
+        }
+        IGMP_V1_REPORT | IGMP_V2_REPORT | IGMP_LEAVE => {
+            let version = if igmp_type == IGMP_V1_REPORT { 1 } else { 2 };
+            let change = if igmp_type == IGMP_LEAVE {
+                Change::Leave
+            } else {
+                Change::Join
+            };
+            let records = vec![(group, change, 0)];
+            (version, Message::Report { records })
+        }
+        IGMP_V3_REPORT => {
+            let records = records(payload.get(8..)?, u16_at(payload, 6)?, false);
+            (3, Message::Report { records })
+        }
+        _ => return None,
+    };
+    Some(Membership {
+        protocol: Protocol::Igmp,
+        version,
+        message,
+    })
+}
+
+/// Reads an MLD message from a whole ICMPv6 message, header included.
+pub fn parse_mld(icmp: &[u8]) -> Option<Membership> {
+    let mld_type = *icmp.first()?;
+    let (version, message) = match mld_type {
+        MLD_QUERY => {
+            let group = address(icmp.get(8..)?, true)?;
+            let version = if icmp.len() >= 28 { 2 } else { 1 };
+            let sources = if version == 2 { u16_at(icmp, 26)? } else { 0 };
+            let message = Message::Query {
+                group: queried_group(group),
+                sources: usize::from(sources),
+                max_response: u32::from(u16_at(icmp, 4)?),
+            };
+            (version, message)
+        }
+        MLD_REPORT | MLD_DONE => {
+            let group = address(icmp.get(8..)?, true)?;
+            let change = if mld_type == MLD_DONE {
+                Change::Leave
+            } else {
+                Change::Join
+            };
+            let records = vec![(group, change, 0)];
+            (1, Message::Report { records })
+        }
+        MLD_V2_REPORT => {
+            let records = records(icmp.get(8..)?, u16_at(icmp, 6)?, true);
+            (2, Message::Report { records })
+        }
+        _ => return None,
+    };
+    Some(Membership {
+        protocol: Protocol::Mld,
+        version,
+        message,
+    })
+}
+
+fn change_name(change: Change) -> &'static str {
+    match change {
+        Change::Join => "Join",
+        Change::Leave => "Leave",
+        Change::Keep => "Block sources",
+    }
+}
+
+/// A layer describing a membership message.
+pub fn decode(membership: &Membership) -> Layer {
+    let protocol = membership.protocol.label();
+    let version = format!("{protocol}v{}", membership.version);
+    let mut layer = match &membership.message {
+        Message::Query {
+            group,
+            sources,
+            max_response,
+        } => {
+            let summary = match group {
+                Some(group) => format!("Membership Query for {group} ({version})"),
+                None => format!("General Membership Query ({version})"),
+            };
+            let mut layer = Layer::new(protocol, summary);
+            layer.field("Type", "Membership Query");
+            layer.field(
+                "Group",
+                group.map_or("All groups".to_string(), |group| group.to_string()),
+            );
+            if *sources > 0 {
+                layer.field("Sources", sources.to_string());
+            }
+            layer.field("Max Response Time", format!("{max_response} ms"));
+            layer
+        }
+        Message::Report { records } => {
+            let summary = match records.as_slice() {
+                [(group, change, _)] => format!("{} {group} ({version})", change_name(*change)),
+                _ => format!("Membership Report, {} groups ({version})", records.len()),
+            };
+            let mut layer = Layer::new(protocol, summary);
+            let leaving = records
+                .iter()
+                .all(|&(_, change, _)| change == Change::Leave);
+            let kind = match (membership.protocol, leaving) {
+                (Protocol::Igmp, true) => "Leave Group",
+                (Protocol::Mld, true) => "Multicast Listener Done",
+                (_, false) => "Membership Report",
+            };
+            layer.field("Type", kind);
+            for (group, change, sources) in records {
+                let value = if *sources > 0 {
+                    format!("{} ({sources} sources)", change_name(*change))
+                } else {
+                    change_name(*change).to_string()
+                };
+                layer.field(format!("Group {group}"), value);
+            }
+            layer
+        }
+    };
+    layer.field("Version", membership.version.to_string());
+    layer
+}
+
+/// The membership message a packet carries, if any.
+fn membership(data: &[u8]) -> Option<Membership> {
+    let sliced = SlicedPacket::from_ethernet(data).ok()?;
+    if let Some(TransportSlice::Icmpv6(icmp)) = sliced.transport {
+        return parse_mld(icmp.slice());
+    }
+    match sliced.net? {
+        InternetSlice::Ipv4(ipv4) if ipv4.payload().ip_number.0 == IGMP_PROTOCOL => {
+            parse_igmp(ipv4.payload().payload)
+        }
+        _ => None,
+    }
+}
+
+/// A host's membership of a group.
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub joined: bool, // Whether its latest report joined rather than left
+    pub reports: usize,
+}
+
+/// A multicast group and the hosts that reported on it.
+#[derive(Debug, Clone)]
+pub struct Group {
+    pub addr: IpAddr,
+    pub protocol: Protocol,
+    pub members: BTreeMap<IpAddr, Member>,
+    pub queries: usize, // Queries asking about this group alone
+}
+
+impl Group {
+    /// Hosts still joined at the end of the capture.
+    pub fn joined(&self) -> usize {
+        self.members.values().filter(|member| member.joined).count()
+    }
+}
+
+/// Multicast groups by address with the hosts that joined them, from IGMP
+/// and MLD reports.
+#[derive(Debug, Clone, Default)]
+pub struct MulticastStats {
+    pub groups: BTreeMap<IpAddr, Group>,
+    pub general_queries: usize,
+    pub queriers: Vec<IpAddr>,
+}
+
+impl MulticastStats {
+    fn group(&mut self, addr: IpAddr, protocol: Protocol) -> &mut Group {
+        self.groups.entry(addr).or_insert(Group {
+            addr,
+            protocol,
+            members: BTreeMap::new(),
+            queries: 0,
+        })
+    }
+
+    pub fn from_packets(packets: &[&PacketInfo]) -> Self {
+        let mut stats = Self::default();
+        for packet in packets {
+            stats.add(packet);
+        }
+        stats
+    }
+
+    pub fn add(&mut self, packet: &PacketInfo) {
+        let Some(Ok(host)) = packet.src_addr else {
+            return;
+        };
+        let Some(membership) = membership(&packet.data) else {
+            return;
+        };
+        let protocol = membership.protocol;
+        match membership.message {
+            Message::Query { group: None, .. } => {
+                self.general_queries += 1;
+                if !self.queriers.contains(&host) {
+                    self.queriers.push(host);
+                }
+            }
+            Message::Query {
+                group: Some(addr), ..
+            } => self.group(addr, protocol).queries += 1,
+            Message::Report { ref records } => {
+                for &(addr, change, _) in records {
+                    let member = self
+                        .group(addr, protocol)
+                        .members
+                        .entry(host)
+                        .or_insert(Member {
+                            joined: false,
+                            reports: 0,
+                        });
+                    member.reports += 1;
+                    match change {
+                        Change::Join => member.joined = true,
+                        Change::Leave => member.joined = false,
+                        Change::Keep => {}
+                    }
+                }
+            }
+        }
+    }
+}
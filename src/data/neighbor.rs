@@ -0,0 +1,183 @@
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    time::{Duration, SystemTime},
+};
+
+use etherparse::{ArpOperation, InternetSlice, SlicedPacket, TransportSlice};
+
+use super::packet::{PacketInfo, format_mac};
+
+const NEIGHBOR_SOLICITATION: u8 = 135;
+const NEIGHBOR_ADVERTISEMENT: u8 = 136;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Arp,
+    Ndp,
+}
+
+impl Protocol {
+    pub fn label(self) -> &'static str {
+        match self {
+            Protocol::Arp => "ARP",
+            Protocol::Ndp => "NDP",
+        }
+    }
+}
+
+/// An address resolution message about `target`.
+struct Message {
+    protocol: Protocol,
+    target: IpAddr,
+    reply: bool,
+    sender_mac: Option<String>,
+}
+
+fn ip(bytes: &[u8]) -> Option<IpAddr> {
+    match bytes.len() {
+        4 => Some(IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(bytes).ok()?))),
+        16 => Some(IpAddr::V6(Ipv6Addr::from(
+            <[u8; 16]>::try_from(bytes).ok()?,
+        ))),
+        _ => None,
+    }
+}
+
+/// Reads an ARP request or reply, or an NDP solicitation or advertisement.
+/// Gratuitous ARP and duplicate address detection ask nobody, so they are
+/// left out.
+fn message(packet: &PacketInfo) -> Option<Message> {
+    let sliced = SlicedPacket::from_ethernet(&packet.data).ok()?;
+    if let Some(InternetSlice::Arp(arp)) = sliced.net {
+        let sender = ip(arp.sender_protocol_addr())?;
+        let target = ip(arp.target_protocol_addr())?;
+        let operation = arp.operation();
+        if sender == target || !matches!(operation, ArpOperation::REQUEST | ArpOperation::REPLY) {
+            return None;
+        }
+        let reply = operation == ArpOperation::REPLY;
+        return Some(Message {
+            protocol: Protocol::Arp,
+            // A reply is about the address it resolves, its sender's.
+            target: if reply { sender } else { target },
+            reply,
+            sender_mac: Some(format_mac(arp.sender_hw_addr())),
+        });
+    }
+    let Some(TransportSlice::Icmpv6(icmp)) = sliced.transport else {
+        return None;
+    };
+    let reply = match icmp.type_u8() {
+        NEIGHBOR_SOLICITATION => false,
+        NEIGHBOR_ADVERTISEMENT => true,
+        _ => return None,
+    };
+    if !reply && packet.src_addr == Some(Ok(IpAddr::V6(Ipv6Addr::UNSPECIFIED))) {
+        return None;
+    }
+    Some(Message {
+        protocol: Protocol::Ndp,
+        target: ip(icmp.payload().get(..16)?)?,
+        reply,
+        sender_mac: packet.data.get(6..12).map(format_mac),
+    })
+}
+
+/// Resolutions of one address.
+#[derive(Debug, Clone)]
+pub struct Target {
+    pub addr: IpAddr,
+    pub protocol: Protocol,
+    pub requests: usize,
+    pub replies: usize,
+    pub answered: usize, // Requests a reply followed
+    pub min: Option<Duration>,
+    pub max: Option<Duration>,
+    total: Duration,
+    pending: Option<SystemTime>, // First request still waiting for a reply
+    pub answered_by: Vec<String>, // MAC addresses seen in replies
+}
+
+impl Target {
+    /// Requests no reply followed: repeated ones before a reply and those
+    /// still waiting.
+    pub fn unanswered(&self) -> usize {
+        self.requests - self.answered
+    }
+
+    /// Average time from the first request to the reply that ended it.
+    pub fn average(&self) -> Option<Duration> {
+        (self.answered > 0).then(|| self.total / self.answered as u32)
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+}
+
+/// ARP and NDP address resolutions by target address, pairing requests
+/// with the replies that answer them.
+#[derive(Debug, Clone, Default)]
+pub struct NeighborStats {
+    pub targets: BTreeMap<IpAddr, Target>,
+}
+
+impl NeighborStats {
+    pub fn from_packets(packets: &[&PacketInfo]) -> Self {
+        let mut stats = Self::default();
+        for packet in packets {
+            stats.add(packet);
+        }
+        stats
+    }
+
+    pub fn add(&mut self, packet: &PacketInfo) {
+        let Some(message) = message(packet) else {
+            return;
+        };
+        let target = self.targets.entry(message.target).or_insert(Target {
+            addr: message.target,
+            protocol: message.protocol,
+            requests: 0,
+            replies: 0,
+            answered: 0,
+            min: None,
+            max: None,
+            total: Duration::ZERO,
+            pending: None,
+            answered_by: Vec::new(),
+        });
+        if !message.reply {
+            target.requests += 1;
+            target.pending.get_or_insert(packet.captured_at);
+            return;
+        }
+        target.replies += 1;
+        if let Some(mac) = message.sender_mac
+            && !target.answered_by.contains(&mac)
+        {
+            target.answered_by.push(mac);
+        }
+        // Unsolicited replies, such as announcements, measure nothing.
+        let Some(asked) = target.pending.take() else {
+            return;
+        };
+        let latency = packet.captured_at.duration_since(asked).unwrap_or_default();
+        target.answered += 1;
+        target.total += latency;
+        target.min = Some(target.min.map_or(latency, |min| min.min(latency)));
+        target.max = Some(target.max.map_or(latency, |max| max.max(latency)));
+    }
+
+    /// Targets with the most unanswered requests first.
+    pub fn by_unanswered(&self) -> Vec<&Target> {
+        let mut targets: Vec<&Target> = self.targets.values().collect();
+        targets.sort_by(|a, b| {
+            b.unanswered()
+                .cmp(&a.unanswered())
+                .then(a.addr.cmp(&b.addr))
+        });
+        targets
+    }
+}
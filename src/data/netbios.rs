@@ -0,0 +1,171 @@
+use super::{layer::Layer, smb};
+
+pub const NAME_SERVICE_PORT: u16 = 137;
+pub const DATAGRAM_PORT: u16 = 138;
+pub const SESSION_PORT: u16 = 139;
+
+fn suffix_name(suffix: u8) -> &'static str {
+    match suffix {
+        0x00 => "Workstation",
+        0x03 => "Messenger",
+        0x1b => "Domain Master Browser",
+        0x1c => "Domain Controllers",
+        0x1d => "Master Browser",
+        0x1e => "Browser Election",
+        0x20 => "File Server",
+        _ => "Unknown",
+    }
+}
+
+struct NetbiosName {
+    name: String,
+    suffix: u8,
+}
+
+impl std::fmt::Display for NetbiosName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}<{:02x}>", self.name, self.suffix)
+    }
+}
+
+/// Decodes a first-level encoded NetBIOS name (RFC 1001 §14.1) starting at
+/// `offset`, following a compression pointer if present. Returns the name
+/// and the offset just past it.
+fn read_name(buf: &[u8], offset: usize) -> Option<(NetbiosName, usize)> {
+    let len = *buf.get(offset)? as usize;
+    if len & 0xc0 == 0xc0 {
+        let pointer = ((len & 0x3f) << 8) | *buf.get(offset + 1)? as usize;
+        if pointer >= offset {
+            return None;
+        }
+        let (name, _) = read_name(buf, pointer)?;
+        return Some((name, offset + 2));
+    }
+    if len != 32 {
+        return None;
+    }
+    let encoded = buf.get(offset + 1..offset + 33)?;
+    let mut raw = [0u8; 16];
+    for (i, pair) in encoded.chunks(2).enumerate() {
+        let hi = pair[0].checked_sub(b'A')?;
+        let lo = pair[1].checked_sub(b'A')?;
+        if hi > 0x0f || lo > 0x0f {
+            return None;
+        }
+        raw[i] = (hi << 4) | lo;
+    }
+
+    // Skip the scope labels; they are practically never used.
+    let mut end = offset + 33;
+    while let Some(&label) = buf.get(end) {
+        end += 1;
+        if label == 0 {
+            break;
+        }
+        end += label as usize;
+    }
+
+    let name: String = raw[..15]
+        .iter()
+        .map(|&b| if b.is_ascii_graphic() { b as char } else { ' ' })
+        .collect();
+    Some((
+        NetbiosName {
+            name: name.trim_end().to_string(),
+            suffix: raw[15],
+        },
+        end,
+    ))
+}
+
+fn opcode_name(opcode: u8) -> &'static str {
+    match opcode {
+        0 => "Name query",
+        5 => "Registration",
+        6 => "Release",
+        7 => "WACK",
+        8 | 9 => "Refresh",
+        _ => "Unknown",
+    }
+}
+
+/// Decodes a NetBIOS name service (NBNS) message.
+pub fn decode_name_service(payload: &[u8]) -> Option<Layer> {
+    if payload.len() < 12 {
+        return None;
+    }
+    let transaction_id = u16::from_be_bytes([payload[0], payload[1]]);
+    let flags = u16::from_be_bytes([payload[2], payload[3]]);
+    let is_response = flags & 0x8000 != 0;
+    let opcode = ((flags >> 11) & 0x0f) as u8;
+    let rcode = (flags & 0x0f) as u8;
+    let questions = u16::from_be_bytes([payload[4], payload[5]]);
+    let answers = u16::from_be_bytes([payload[6], payload[7]]);
+    let additional = u16::from_be_bytes([payload[10], payload[11]]);
+
+    let (name, _) = read_name(payload, 12)?;
+    let kind = if is_response { " response" } else { "" };
+    let mut summary = format!("{}{kind} {name}", opcode_name(opcode));
+    if is_response && rcode != 0 {
+        summary.push_str(&format!(" (error {rcode})"));
+    }
+
+    let mut layer = Layer::new("NBNS", summary);
+    layer.detail("Transaction ID", format!("0x{transaction_id:04x}"));
+    layer.field(
+        "Type",
+        if is_response { "Response" } else { "Query" }.to_string(),
+    );
+    layer.field("Opcode", format!("{opcode} ({})", opcode_name(opcode)));
+    if is_response {
+        layer.field("Return Code", rcode.to_string());
+    }
+    layer.detail("Questions", questions.to_string());
+    layer.detail("Answers", answers.to_string());
+    layer.detail("Additional Records", additional.to_string());
+    layer.field("Name", name.to_string());
+    layer.field("Name Type", suffix_name(name.suffix).to_string());
+    Some(layer)
+}
+
+/// Decodes a NetBIOS datagram service message.
+pub fn decode_datagram(payload: &[u8]) -> Option<Layer> {
+    let msg_type = *payload.first()?;
+    if !(0x10..=0x12).contains(&msg_type) {
+        return None;
+    }
+    let (source, next) = read_name(payload, 14)?;
+    let (destination, _) = read_name(payload, next)?;
+
+    let mut layer = Layer::new("NBDS", format!("Datagram {source} → {destination}"));
+    let kind = match msg_type {
+        0x10 => "Direct unique",
+        0x11 => "Direct group",
+        _ => "Broadcast",
+    };
+    layer.field("Message Type", format!("0x{msg_type:02x} ({kind})"));
+    layer.field("Source Name", source.to_string());
+    layer.field("Destination Name", destination.to_string());
+    Some(layer)
+}
+
+/// Decodes a NetBIOS session service message, handing session payloads to
+/// the SMB decoder.
+pub fn decode_session(payload: &[u8]) -> Option<Layer> {
+    let msg_type = *payload.first()?;
+    match msg_type {
+        0x00 => smb::decode(payload),
+        0x81 => {
+            let (called, next) = read_name(payload, 4)?;
+            let (calling, _) = read_name(payload, next)?;
+            let mut layer = Layer::new("NBSS", format!("Session request {calling} → {called}"));
+            layer.field("Called Name", called.to_string());
+            layer.field("Calling Name", calling.to_string());
+            Some(layer)
+        }
+        0x82 => Some(Layer::new("NBSS", "Positive session response".to_string())),
+        0x83 => Some(Layer::new("NBSS", "Negative session response".to_string())),
+        0x85 if payload.len() == 4 => Some(Layer::new("NBSS", "Session keep-alive".to_string())),
+        _ => None,
+    }
+}
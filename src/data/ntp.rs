@@ -0,0 +1,130 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{layer::Layer, time::format_utc};
+
+pub const PORT: u16 = 123;
+
+/// Seconds between the NTP era 0 epoch (1900-01-01) and the Unix epoch.
+const NTP_UNIX_OFFSET: f64 = 2_208_988_800.0;
+const HEADER_LEN: usize = 48;
+
+fn mode_name(mode: u8) -> &'static str {
+    match mode {
+        1 => "symmetric active",
+        2 => "symmetric passive",
+        3 => "client",
+        4 => "server",
+        5 => "broadcast",
+        6 => "control",
+        7 => "private",
+        _ => "reserved",
+    }
+}
+
+fn leap_name(leap: u8) -> &'static str {
+    match leap {
+        0 => "no warning",
+        1 => "last minute has 61 seconds",
+        2 => "last minute has 59 seconds",
+        _ => "unsynchronized",
+    }
+}
+
+/// Reads a 64-bit NTP timestamp as seconds since the Unix epoch. Returns
+/// `None` for the all-zero "unset" timestamp.
+fn timestamp(bytes: &[u8]) -> Option<f64> {
+    let secs = u32::from_be_bytes(bytes[0..4].try_into().ok()?);
+    let frac = u32::from_be_bytes(bytes[4..8].try_into().ok()?);
+    if secs == 0 && frac == 0 {
+        return None;
+    }
+    Some(secs as f64 + frac as f64 / 4_294_967_296.0 - NTP_UNIX_OFFSET)
+}
+
+fn short_format(bytes: &[u8]) -> f64 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f64 / 65_536.0
+}
+
+fn reference_id(stratum: u8, bytes: &[u8]) -> String {
+    if stratum <= 1 {
+        bytes
+            .iter()
+            .take_while(|&&b| b != 0)
+            .map(|&b| if b.is_ascii_graphic() { b as char } else { '.' })
+            .collect()
+    } else {
+        format!("{}.{}.{}.{}", bytes[0], bytes[1], bytes[2], bytes[3])
+    }
+}
+
+fn format_timestamp(ts: Option<f64>) -> String {
+    ts.map(format_utc).unwrap_or_else(|| "(unset)".to_string())
+}
+
+/// Decodes an NTP packet carried in a UDP payload. The capture time is
+/// used as the destination timestamp (T4) of server replies to estimate
+/// the local clock offset.
+pub fn decode(payload: &[u8], captured_at: SystemTime) -> Option<Layer> {
+    if payload.len() < HEADER_LEN {
+        return None;
+    }
+
+    let leap = payload[0] >> 6;
+    let version = (payload[0] >> 3) & 0x07;
+    let mode = payload[0] & 0x07;
+    if !(1..=4).contains(&version) || mode == 0 {
+        return None;
+    }
+
+    let stratum = payload[1];
+    let poll = payload[2] as i8;
+    let precision = payload[3] as i8;
+    let reference = timestamp(&payload[16..24]);
+    let origin = timestamp(&payload[24..32]);
+    let receive = timestamp(&payload[32..40]);
+    let transmit = timestamp(&payload[40..48]);
+
+    let mut layer = Layer::new(
+        "NTP",
+        format!("NTPv{version} {}, stratum {stratum}", mode_name(mode)),
+    );
+    layer.detail("Leap Indicator", format!("{leap} ({})", leap_name(leap)));
+    layer.detail("Version", version.to_string());
+    layer.field("Mode", format!("{mode} ({})", mode_name(mode)));
+    layer.field("Stratum", stratum.to_string());
+    layer.detail("Poll Interval", format!("{} s", 2f64.powi(poll as i32)));
+    layer.detail("Precision", format!("{:.9} s", 2f64.powi(precision as i32)));
+    layer.detail(
+        "Root Delay",
+        format!("{:.6} s", short_format(&payload[4..8])),
+    );
+    layer.detail(
+        "Root Dispersion",
+        format!("{:.6} s", short_format(&payload[8..12])),
+    );
+    layer.field("Reference ID", reference_id(stratum, &payload[12..16]));
+    layer.detail("Reference Time", format_timestamp(reference));
+    layer.field("Origin Time (T1)", format_timestamp(origin));
+    layer.field("Receive Time (T2)", format_timestamp(receive));
+    layer.field("Transmit Time (T3)", format_timestamp(transmit));
+
+    if matches!(mode, 4 | 5)
+        && let Some(t3) = transmit
+        && let Ok(arrival) = captured_at.duration_since(UNIX_EPOCH)
+    {
+        let t4 = arrival.as_secs_f64();
+        layer.field("Capture Time (T4)", format_utc(t4));
+        let offset = match (origin, receive) {
+            (Some(t1), Some(t2)) => {
+                let delay = (t4 - t1) - (t3 - t2);
+                layer.field("Round-trip Delay", format!("{:.6} s", delay));
+                ((t2 - t1) + (t3 - t4)) / 2.0
+            }
+            _ => t3 - t4,
+        };
+        layer.field("Estimated Offset", format!("{offset:+.6} s"));
+        layer.summary.push_str(&format!(", offset {offset:+.3}s"));
+    }
+
+    Some(layer)
+}
@@ -1,85 +1,798 @@
-use std::{net::IpAddr, sync::Arc};
+use std::{
+    borrow::Cow,
+    net::IpAddr,
+    ops::Range,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
-use etherparse::{InternetSlice, SlicedPacket, TransportSlice};
+use etherparse::{
+    EtherType, InternetSlice, IpPayloadSlice, Ipv6ExtensionSlice, LenSource, LinkExtSlice,
+    LinkSlice, SlicedPacket, TransportSlice, err::packet::SliceError,
+};
+
+use super::{
+    checksum::{self, BadChecksum},
+    discovery, dns, encrypted_dns, ethertype,
+    flow::Endpoint,
+    http2, icmp,
+    layer::{Layer, Verbosity},
+    link::{self, Framing},
+    multicast, netbios, ntp, plugin, profile, routing, rtp, sip, smb, tls,
+    tunnel::{self, Inner, Tunnel},
+    vpn, wlan, wol,
+};
+
+/// How many tunnels deep `parse_packet` follows nested encapsulation.
+const MAX_TUNNEL_DEPTH: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transport {
+    Tcp,
+    Udp,
+    Icmpv4,
+    Icmpv6,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TcpFlags {
+    pub syn: bool,
+    pub ack: bool,
+    pub fin: bool,
+    pub rst: bool,
+    pub psh: bool,
+}
+
+impl TcpFlags {
+    /// Names of the flags that are set.
+    pub fn names(self) -> Vec<&'static str> {
+        [
+            (self.syn, "SYN"),
+            (self.fin, "FIN"),
+            (self.rst, "RST"),
+            (self.psh, "PSH"),
+            (self.ack, "ACK"),
+        ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+        .collect()
+    }
+}
+
+/// Raw TCP sequence state of a segment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TcpSequence {
+    pub seq: u32,
+    pub ack: u32,
+    pub window: u16,
+}
 
 #[derive(Debug, Clone)]
 pub struct PacketInfo {
     pub id: usize,
-    pub timestamp: String,
+    pub timestamp: Duration, // Since the capture started
+    pub captured_at: SystemTime,
     pub src_addr: Option<Result<IpAddr, String>>,
     pub src_port: Option<u16>,
     pub dst_addr: Option<Result<IpAddr, String>>,
     pub dst_port: Option<u16>,
+    pub outer_addrs: Vec<(IpAddr, IpAddr)>, // Tunnel endpoints, outermost first
     pub protocol: String,
+    pub transport: Option<Transport>,
+    pub tcp_flags: Option<TcpFlags>,
+    pub tcp_sequence: Option<TcpSequence>,
+    pub ethertype: Option<u16>,      // Innermost, after any VLAN tags
+    pub vlan: Option<u16>,           // Outermost VLAN ID on the wire
+    pub interface: Option<Arc<str>>, // Device it came from when capturing on several
+    pub link_type: u32,              // LINKTYPE_ value of the capture it came from
     pub length: usize,
+    pub info: String,
+    pub layers: Vec<Layer>,
     pub data: Arc<[u8]>,
+    pub payload: Range<usize>,           // Transport payload within `data`
+    pub lazy: bool,                      // Parsed only up to the transport headers
+    pub quoted: Option<Range<usize>>,    // Original datagram an ICMP error reports on
+    pub bad_checksums: Vec<BadChecksum>, // Headers whose checksum doesn't match, outermost first
+}
+
+impl PacketInfo {
+    pub fn payload(&self) -> &[u8] {
+        &self.data[self.payload.clone()]
+    }
+
+    /// The packet with application protocols decoded, parsing the frame
+    /// again if only its headers were parsed.
+    pub fn full(&self) -> Cow<'_, PacketInfo> {
+        if self.lazy {
+            let mut packet = parse_packet(
+                self.id,
+                self.timestamp,
+                self.captured_at,
+                self.link_type,
+                self.data.clone(),
+            );
+            packet.interface = self.interface.clone();
+            Cow::Owned(packet)
+        } else {
+            Cow::Borrowed(self)
+        }
+    }
+
+    pub fn has_layer(&self, name: &str) -> bool {
+        self.layers.iter().any(|layer| layer.name == name)
+    }
+
+    /// Ports, flags and sequence numbers of the innermost TCP or UDP header,
+    /// for packets no decoder says more about.
+    fn transport_summary(&self) -> Option<String> {
+        let (Some(src), Some(dst)) = (self.src_port, self.dst_port) else {
+            return None;
+        };
+        let len = self.payload.len();
+        match (self.transport?, self.tcp_flags, self.tcp_sequence) {
+            (Transport::Tcp, Some(flags), Some(sequence)) => {
+                let mut summary = format!(
+                    "{src} → {dst} [{}] Seq={}",
+                    flags.names().join(", "),
+                    sequence.seq
+                );
+                if flags.ack {
+                    summary.push_str(&format!(" Ack={}", sequence.ack));
+                }
+                summary.push_str(&format!(" Win={} Len={len}", sequence.window));
+                Some(summary)
+            }
+            (Transport::Udp, ..) => Some(format!("{src} → {dst} Len={len}")),
+            _ => None,
+        }
+    }
+
+    /// Everything the Info column draws from: the transport header, each
+    /// decoded layer outermost first, then the fields of the innermost one.
+    pub fn info_lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .transport_summary()
+            .into_iter()
+            .map(|summary| format!("{}: {summary}", self.transport_name()))
+            .collect();
+        lines.extend(
+            self.layers
+                .iter()
+                .filter(|layer| !layer.summary.is_empty())
+                .map(|layer| format!("{}: {}", layer.name, layer.summary)),
+        );
+        if let Some(layer) = self.layers.last() {
+            lines.extend(
+                layer
+                    .visible_fields(Verbosity::Normal)
+                    .map(|field| format!("  {}: {}", field.name, field.value)),
+            );
+        }
+        if lines.is_empty() && !self.info.is_empty() {
+            lines.push(self.info.clone());
+        }
+        lines
+    }
+
+    fn transport_name(&self) -> &'static str {
+        match self.transport {
+            Some(Transport::Tcp) => "TCP",
+            Some(Transport::Udp) => "UDP",
+            Some(Transport::Icmpv4) => "ICMPv4",
+            Some(Transport::Icmpv6) => "ICMPv6",
+            None => "",
+        }
+    }
 }
 
-pub fn parse_packet(id: usize, timestamp: String, data: Arc<[u8]>) -> PacketInfo {
-    let mut src_addr: Option<Result<IpAddr, String>> = None;
-    let mut dst_addr: Option<Result<IpAddr, String>> = None;
-    let mut src_port: Option<u16> = None;
-    let mut dst_port: Option<u16> = None;
-    let mut protocol = "Unknown".to_string();
-    match SlicedPacket::from_ethernet(&data) {
-        Ok(packet_info) => {
-            if let Some(ip_slice) = packet_info.net {
-                match ip_slice {
-                    InternetSlice::Ipv4(ipv4) => {
-                        src_addr = Some(Ok(IpAddr::V4(ipv4.header().source().into())));
-                        dst_addr = Some(Ok(IpAddr::V4(ipv4.header().destination().into())));
-                        protocol = format!("IPv4/{:?}", ipv4.header().protocol());
+pub fn format_mac(mac: &[u8]) -> String {
+    let octets: Vec<String> = mac.iter().map(|b| format!("{b:02x}")).collect();
+    octets.join(":")
+}
+
+/// Locates `part`, a subslice of `data`, as a byte range.
+pub fn range_in(data: &[u8], part: &[u8]) -> Range<usize> {
+    let start = part.as_ptr() as usize - data.as_ptr() as usize;
+    start..start + part.len()
+}
+
+fn decode_tcp(
+    peers: [Option<IpAddr>; 2],
+    src_port: u16,
+    dst_port: u16,
+    payload: &[u8],
+) -> Option<Layer> {
+    let has_port = |port| src_port == port || dst_port == port;
+    if payload.is_empty() {
+        return None;
+    }
+    // User dissectors come before the built-in ones for their ports.
+    if let Some(layer) = profile::timed("Plugins", || plugin::decode(src_port, dst_port, payload)) {
+        return Some(layer);
+    }
+    if has_port(dns::PORT) {
+        return profile::timed("DNS", || dns::decode(payload, true));
+    }
+    if has_port(encrypted_dns::DOT_PORT) {
+        return profile::timed("DoT", || encrypted_dns::decode_dot(payload, peers));
+    }
+    if has_port(tls::HTTPS_PORT) || has_port(tls::ALT_HTTPS_PORT) {
+        return profile::timed("TLS", || encrypted_dns::decode_https(payload, peers));
+    }
+    if has_port(smb::PORT) {
+        return profile::timed("SMB", || smb::decode(payload));
+    }
+    if has_port(netbios::SESSION_PORT) {
+        return profile::timed("NetBIOS", || netbios::decode_session(payload));
+    }
+    if has_port(sip::PORT) {
+        return profile::timed("SIP", || sip::decode(payload));
+    }
+    if has_port(routing::BGP_PORT) {
+        return profile::timed("BGP", || routing::decode_bgp(payload));
+    }
+    profile::timed("HTTP/2", || http2::decode(payload))
+}
+
+/// Describes a frame that carries neither IP nor ARP, so it is listed under
+/// its EtherType instead of as Unknown.
+fn decode_ethertype(ethertype: u16, payload: &[u8]) -> Option<Layer> {
+    if let Some(layer) = profile::timed("LLDP/CDP", || discovery::decode(ethertype, payload)) {
+        return Some(layer);
+    }
+    if ethertype == wol::ETHERTYPE
+        && let Some(layer) = profile::timed("WoL", || wol::decode(payload))
+    {
+        return Some(layer);
+    }
+    let mut layer = Layer::new(
+        ethertype::name(ethertype).unwrap_or("Other L2"),
+        format!(
+            "EtherType {}, {} bytes",
+            ethertype::describe(ethertype),
+            payload.len()
+        ),
+    );
+    layer.field("EtherType", ethertype::describe(ethertype));
+    layer.field("Payload Length", format!("{} bytes", payload.len()));
+    Some(layer)
+}
+
+fn decode_udp(
+    src_port: u16,
+    dst_port: u16,
+    payload: &[u8],
+    captured_at: SystemTime,
+) -> Option<Layer> {
+    let has_port = |port| src_port == port || dst_port == port;
+    if !payload.is_empty()
+        && let Some(layer) =
+            profile::timed("Plugins", || plugin::decode(src_port, dst_port, payload))
+    {
+        return Some(layer);
+    }
+    if has_port(dns::PORT) || has_port(dns::MDNS_PORT) {
+        return profile::timed("DNS", || dns::decode(payload, false));
+    }
+    if wol::PORTS.contains(&dst_port)
+        && let Some(layer) = profile::timed("WoL", || wol::decode(payload))
+    {
+        return Some(layer);
+    }
+    if has_port(ntp::PORT) {
+        return profile::timed("NTP", || ntp::decode(payload, captured_at));
+    }
+    if has_port(netbios::NAME_SERVICE_PORT) {
+        return profile::timed("NetBIOS", || netbios::decode_name_service(payload));
+    }
+    if has_port(netbios::DATAGRAM_PORT) {
+        return profile::timed("NetBIOS", || netbios::decode_datagram(payload));
+    }
+    if has_port(sip::PORT) {
+        return profile::timed("SIP", || sip::decode(payload));
+    }
+    if has_port(routing::RIP_PORT) || has_port(routing::RIPNG_PORT) {
+        let ng = has_port(routing::RIPNG_PORT);
+        return profile::timed("RIP", || routing::decode_rip(payload, ng));
+    }
+    if has_port(vpn::NAT_T_PORT) {
+        return profile::timed("ESP", || vpn::decode_nat_t(payload));
+    }
+    if let Some(layer) = profile::timed("WireGuard", || vpn::decode_wireguard(payload)) {
+        return Some(layer);
+    }
+    if rtp::is_candidate(src_port, dst_port) {
+        return profile::timed("RTP", || rtp::decode(payload));
+    }
+    None
+}
+
+/// The headers found so far while walking a frame. Addresses and ports
+/// always describe the innermost packet reached.
+struct Dissection {
+    src_addr: Option<Result<IpAddr, String>>,
+    dst_addr: Option<Result<IpAddr, String>>,
+    src_port: Option<u16>,
+    dst_port: Option<u16>,
+    outer_addrs: Vec<(IpAddr, IpAddr)>,
+    protocol: String,
+    transport: Option<Transport>,
+    tcp_flags: Option<TcpFlags>,
+    tcp_sequence: Option<TcpSequence>,
+    ethertype: Option<u16>,
+    vlan: Option<u16>,
+    layers: Vec<Layer>,
+    payload: Option<Range<usize>>,
+    quoted: Option<Range<usize>>,
+    bad_checksums: Vec<BadChecksum>,
+    applications: bool, // Whether to run the application protocol decoders
+}
+
+impl Dissection {
+    fn new(applications: bool) -> Self {
+        Self {
+            src_addr: None,
+            dst_addr: None,
+            src_port: None,
+            dst_port: None,
+            outer_addrs: Vec::new(),
+            protocol: "Unknown".to_string(),
+            transport: None,
+            tcp_flags: None,
+            tcp_sequence: None,
+            ethertype: None,
+            vlan: None,
+            layers: Vec::new(),
+            payload: None,
+            quoted: None,
+            bad_checksums: Vec::new(),
+            applications,
+        }
+    }
+
+    /// Verifies the checksum of the header an IP packet carries, unless
+    /// it is a fragment or its length doesn't come from the IP header.
+    fn check_transport(&mut self, src: IpAddr, dst: IpAddr, payload: &IpPayloadSlice) {
+        if payload.fragmented || payload.len_source == LenSource::Slice {
+            return;
+        }
+        self.bad_checksums.extend(checksum::transport(
+            src,
+            dst,
+            payload.ip_number.0,
+            payload.payload,
+        ));
+    }
+
+    fn endpoints(&self) -> Option<(Endpoint, Endpoint)> {
+        let src = Endpoint {
+            addr: self.src_addr.clone()?,
+            port: self.src_port,
+        };
+        let dst = Endpoint {
+            addr: self.dst_addr.clone()?,
+            port: self.dst_port,
+        };
+        Some((src, dst))
+    }
+
+    /// Takes the layers and addresses of a frame's framing, then dissects
+    /// the packet it carries as if it had come over Ethernet.
+    fn dissect_framing(&mut self, data: &[u8], framing: Framing, captured_at: SystemTime) {
+        self.src_addr = framing.source.map(|mac| Err(format_mac(mac)));
+        self.dst_addr = framing.destination.map(|mac| Err(format_mac(mac)));
+        self.layers = framing.layers;
+        if let Some((ethertype, payload)) = framing.payload {
+            let sliced = SlicedPacket::from_ether_type(EtherType(ethertype), payload);
+            self.dissect(data, sliced, captured_at, 0);
+        }
+    }
+
+    fn dissect(
+        &mut self,
+        data: &[u8],
+        sliced: Result<SlicedPacket, SliceError>,
+        captured_at: SystemTime,
+        depth: usize,
+    ) {
+        let Ok(packet_info) = sliced else {
+            return;
+        };
+        let mut link_payload = None;
+        match packet_info.link {
+            Some(LinkSlice::Ethernet2(ref eth)) => {
+                self.src_addr = Some(Err(format_mac(&eth.source())));
+                self.dst_addr = Some(Err(format_mac(&eth.destination())));
+                self.ethertype = Some(eth.ether_type().0);
+                link_payload = Some(eth.payload_slice());
+            }
+            // What a framing other than Ethernet carries, by its EtherType.
+            Some(LinkSlice::EtherPayload(ref payload)) => {
+                self.ethertype = Some(payload.ether_type.0);
+                link_payload = Some(payload.payload);
+            }
+            _ => {}
+        }
+        for ext in &packet_info.link_exts {
+            if let LinkExtSlice::Vlan(vlan) = ext {
+                if depth == 0 && self.vlan.is_none() {
+                    self.vlan = Some(vlan.vlan_identifier().value());
+                }
+                self.ethertype = Some(vlan.ether_type().0);
+                link_payload = Some(vlan.payload_slice());
+            }
+        }
+        if packet_info.net.is_none()
+            && let (Some(ethertype), Some(payload)) = (self.ethertype, link_payload)
+        {
+            if self.applications {
+                let range = range_in(data, payload);
+                let layer = decode_ethertype(ethertype, payload);
+                self.layers.extend(layer.map(|layer| layer.placed(range)));
+            }
+            return;
+        }
+        if let Some(ip_slice) = packet_info.net {
+            match ip_slice {
+                InternetSlice::Ipv4(ipv4) => {
+                    let src = IpAddr::V4(ipv4.header().source().into());
+                    let dst = IpAddr::V4(ipv4.header().destination().into());
+                    self.src_addr = Some(Ok(src));
+                    self.dst_addr = Some(Ok(dst));
+                    self.bad_checksums
+                        .extend(checksum::ipv4(ipv4.header().slice()));
+                    self.check_transport(src, dst, ipv4.payload());
+                    self.protocol = format!("IPv4/{:?}", ipv4.header().protocol());
+                    if packet_info.transport.is_none()
+                        && ipv4.payload().ip_number.0 == tunnel::GRE_PROTOCOL
+                    {
+                        let gre =
+                            profile::timed("GRE", || tunnel::decode_gre(ipv4.payload().payload));
+                        return self.enter_tunnel(data, gre, captured_at, depth);
                     }
-                    InternetSlice::Ipv6(ipv6) => {
-                        src_addr = Some(Ok(IpAddr::V6(ipv6.header().source().into())));
-                        dst_addr = Some(Ok(IpAddr::V6(ipv6.header().destination().into())));
-                        protocol = format!("IPv6/{:?}", ipv6.header().next_header());
+                    if self.applications {
+                        if let Some(auth) = ipv4.extensions().auth {
+                            let range = range_in(data, auth.slice());
+                            self.layers.push(vpn::decode_ah(&auth).placed(range));
+                        }
+                        if packet_info.transport.is_none() {
+                            self.decode_ip_protocol(data, ipv4.payload());
+                        }
                     }
-                    InternetSlice::Arp(arp) => {
-                        src_addr = Some(Err(format!("{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}", 
-                            arp.sender_hw_addr()[0], arp.sender_hw_addr()[1], arp.sender_hw_addr()[2],
-                            arp.sender_hw_addr()[3], arp.sender_hw_addr()[4], arp.sender_hw_addr()[5])));
-                        dst_addr = Some(Err(format!("{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}", 
-                            arp.target_hw_addr()[0], arp.target_hw_addr()[1], arp.target_hw_addr()[2],
-                            arp.target_hw_addr()[3], arp.target_hw_addr()[4], arp.target_hw_addr()[5])));
-                        protocol = "ARP".to_string();
+                }
+                InternetSlice::Ipv6(ipv6) => {
+                    let src = IpAddr::V6(ipv6.header().source().into());
+                    let dst = IpAddr::V6(ipv6.header().destination().into());
+                    self.src_addr = Some(Ok(src));
+                    self.dst_addr = Some(Ok(dst));
+                    self.check_transport(src, dst, ipv6.payload());
+                    self.protocol = format!("IPv6/{:?}", ipv6.header().next_header());
+                    if packet_info.transport.is_none()
+                        && ipv6.payload().ip_number.0 == tunnel::GRE_PROTOCOL
+                    {
+                        let gre =
+                            profile::timed("GRE", || tunnel::decode_gre(ipv6.payload().payload));
+                        return self.enter_tunnel(data, gre, captured_at, depth);
+                    }
+                    if self.applications {
+                        for extension in ipv6.extensions().clone() {
+                            if let Ipv6ExtensionSlice::Authentication(auth) = extension {
+                                let range = range_in(data, auth.slice());
+                                self.layers.push(vpn::decode_ah(&auth).placed(range));
+                            }
+                        }
+                        if packet_info.transport.is_none() {
+                            self.decode_ip_protocol(data, ipv6.payload());
+                        }
                     }
                 }
+                InternetSlice::Arp(arp) => {
+                    self.src_addr = Some(Err(format_mac(arp.sender_hw_addr())));
+                    self.dst_addr = Some(Err(format_mac(arp.target_hw_addr())));
+                    self.protocol = "ARP".to_string();
+                }
             }
-            if let Some(transport_slice) = packet_info.transport {
-                match transport_slice {
-                    TransportSlice::Tcp(tcp) => {
-                        src_port = Some(tcp.source_port());
-                        dst_port = Some(tcp.destination_port());
-                        protocol = "TCP".to_string();
+        }
+        if let Some(transport_slice) = packet_info.transport {
+            match transport_slice {
+                TransportSlice::Tcp(tcp) => {
+                    self.src_port = Some(tcp.source_port());
+                    self.dst_port = Some(tcp.destination_port());
+                    self.protocol = "TCP".to_string();
+                    self.transport = Some(Transport::Tcp);
+                    self.tcp_flags = Some(TcpFlags {
+                        syn: tcp.syn(),
+                        ack: tcp.ack(),
+                        fin: tcp.fin(),
+                        rst: tcp.rst(),
+                        psh: tcp.psh(),
+                    });
+                    self.tcp_sequence = Some(TcpSequence {
+                        seq: tcp.sequence_number(),
+                        ack: tcp.acknowledgment_number(),
+                        window: tcp.window_size(),
+                    });
+                    self.payload = Some(range_in(data, tcp.payload()));
+                    if !self.applications {
+                        return;
+                    }
+                    let ip = |addr: &Option<Result<IpAddr, String>>| match addr {
+                        Some(Ok(ip)) => Some(*ip),
+                        _ => None,
+                    };
+                    let layer = decode_tcp(
+                        [ip(&self.src_addr), ip(&self.dst_addr)],
+                        tcp.source_port(),
+                        tcp.destination_port(),
+                        tcp.payload(),
+                    );
+                    let range = range_in(data, tcp.payload());
+                    self.layers.extend(layer.map(|layer| layer.placed(range)));
+                }
+                TransportSlice::Udp(udp) => {
+                    self.src_port = Some(udp.source_port());
+                    self.dst_port = Some(udp.destination_port());
+                    self.protocol = "UDP".to_string();
+                    self.transport = Some(Transport::Udp);
+                    self.payload = Some(range_in(data, udp.payload()));
+                    match udp.destination_port() {
+                        tunnel::VXLAN_PORT => {
+                            let vxlan =
+                                profile::timed("VXLAN", || tunnel::decode_vxlan(udp.payload()));
+                            return self.enter_tunnel(data, vxlan, captured_at, depth);
+                        }
+                        tunnel::GENEVE_PORT => {
+                            let geneve =
+                                profile::timed("GENEVE", || tunnel::decode_geneve(udp.payload()));
+                            return self.enter_tunnel(data, geneve, captured_at, depth);
+                        }
+                        _ => {}
+                    }
+                    if !self.applications {
+                        return;
                     }
-                    TransportSlice::Udp(udp) => {
-                        src_port = Some(udp.source_port());
-                        dst_port = Some(udp.destination_port());
-                        protocol = "UDP".to_string();
+                    let layer = decode_udp(
+                        udp.source_port(),
+                        udp.destination_port(),
+                        udp.payload(),
+                        captured_at,
+                    );
+                    let range = range_in(data, udp.payload());
+                    self.layers.extend(layer.map(|layer| layer.placed(range)));
+                }
+                TransportSlice::Icmpv4(icmp) => {
+                    self.protocol = "ICMPv4".to_string();
+                    self.transport = Some(Transport::Icmpv4);
+                    // Destination unreachable, time exceeded, parameter problem
+                    if matches!(icmp.type_u8(), 3 | 11 | 12) {
+                        self.quoted = Some(range_in(data, icmp.payload()));
+                        if self.applications {
+                            let layer = icmp::decode_error(
+                                false,
+                                icmp.type_u8(),
+                                icmp.code_u8(),
+                                icmp.payload(),
+                            );
+                            self.layers
+                                .push(layer.placed(range_in(data, icmp.payload())));
+                        }
                     }
-                    TransportSlice::Icmpv4(_) => {
-                        protocol = "ICMPv4".to_string();
+                }
+                TransportSlice::Icmpv6(icmp) => {
+                    self.protocol = "ICMPv6".to_string();
+                    self.transport = Some(Transport::Icmpv6);
+                    // Destination unreachable, packet too big, time exceeded,
+                    // parameter problem
+                    if matches!(icmp.type_u8(), 1..=4) {
+                        self.quoted = Some(range_in(data, icmp.payload()));
+                        if self.applications {
+                            let layer = icmp::decode_error(
+                                true,
+                                icmp.type_u8(),
+                                icmp.code_u8(),
+                                icmp.payload(),
+                            );
+                            self.layers
+                                .push(layer.placed(range_in(data, icmp.payload())));
+                        }
                     }
-                    TransportSlice::Icmpv6(_) => {
-                        protocol = "ICMPv6".to_string();
+                    if self.applications && multicast::MLD_TYPES.contains(&icmp.type_u8()) {
+                        let mld = profile::timed("MLD", || multicast::parse_mld(icmp.slice()));
+                        let range = range_in(data, icmp.slice());
+                        let layer = mld.as_ref().map(multicast::decode);
+                        self.layers.extend(layer.map(|layer| layer.placed(range)));
                     }
                 }
             }
         }
-        Err(_) => {
-            protocol = "Unknown".to_string();
+    }
+
+    /// Decodes what an IP packet carries when it is no transport etherparse
+    /// knows.
+    fn decode_ip_protocol(&mut self, data: &[u8], payload: &IpPayloadSlice) {
+        let layer = match payload.ip_number.0 {
+            multicast::IGMP_PROTOCOL => profile::timed("IGMP", || {
+                multicast::parse_igmp(payload.payload)
+                    .as_ref()
+                    .map(multicast::decode)
+            }),
+            routing::OSPF_PROTOCOL => {
+                profile::timed("OSPF", || routing::decode_ospf(payload.payload))
+            }
+            vpn::ESP_PROTOCOL => profile::timed("ESP", || vpn::decode_esp(payload.payload)),
+            _ => None,
+        };
+        let range = range_in(data, payload.payload);
+        self.layers.extend(layer.map(|layer| layer.placed(range)));
+    }
+
+    /// Records the tunnel layer and continues with the encapsulated packet,
+    /// which then provides the addresses shown for the frame.
+    fn enter_tunnel(
+        &mut self,
+        data: &[u8],
+        tunnel: Option<Tunnel>,
+        captured_at: SystemTime,
+        depth: usize,
+    ) {
+        let Some(Tunnel { mut layer, inner }) = tunnel else {
+            return;
+        };
+        if depth >= MAX_TUNNEL_DEPTH {
+            self.layers.push(layer);
+            return;
+        }
+        if let Some((src, dst)) = self.endpoints() {
+            layer.field("Outer Source", src.to_string());
+            layer.field("Outer Destination", dst.to_string());
+        }
+        if let (Some(Ok(src)), Some(Ok(dst))) = (&self.src_addr, &self.dst_addr) {
+            self.outer_addrs.push((*src, *dst));
+        }
+
+        let index = self.layers.len();
+        self.layers.push(layer);
+        let outer_src = self.src_addr.take();
+        let outer_dst = self.dst_addr.take();
+        self.src_port = None;
+        self.dst_port = None;
+        self.transport = None;
+        self.tcp_flags = None;
+        self.tcp_sequence = None;
+        self.payload = None;
+        self.quoted = None;
+        let sliced = match inner {
+            Inner::Ethernet(frame) => SlicedPacket::from_ethernet(frame),
+            Inner::Ip(packet) => SlicedPacket::from_ip(packet),
+        };
+        self.dissect(data, sliced, captured_at, depth + 1);
+
+        if self.src_addr.is_none() {
+            // The inner packet could not be parsed, keep showing the outer one.
+            self.src_addr = outer_src;
+            self.dst_addr = outer_dst;
+        } else if let Some((src, dst)) = self.endpoints() {
+            let layer = &mut self.layers[index];
+            layer.summary = format!("{}, inner {src} → {dst}", layer.summary);
+            layer.field("Inner Source", src.to_string());
+            layer.field("Inner Destination", dst.to_string());
+        }
+    }
+}
+
+pub fn parse_packet(
+    id: usize,
+    timestamp: Duration,
+    captured_at: SystemTime,
+    link_type: u32,
+    data: Arc<[u8]>,
+) -> PacketInfo {
+    parse(id, timestamp, captured_at, link_type, data, true)
+}
+
+/// Parses addresses, ports and tunnels but leaves application protocols for
+/// `PacketInfo::full`, which is much cheaper at high packet rates.
+pub fn parse_headers(
+    id: usize,
+    timestamp: Duration,
+    captured_at: SystemTime,
+    link_type: u32,
+    data: Arc<[u8]>,
+) -> PacketInfo {
+    parse(id, timestamp, captured_at, link_type, data, false)
+}
+
+/// Whether a layer named `name` only carries what was decoded inside it,
+/// so the Protocol and Info columns describe the inside instead.
+fn is_carrier(name: &str, transport: Option<Transport>, ethertype: Option<u16>) -> bool {
+    (tunnel::NAMES.contains(&name) && transport.is_some())
+        || (vpn::NAMES.contains(&name) && transport.is_some())
+        || ((wlan::NAMES.contains(&name) || link::NAMES.contains(&name)) && ethertype.is_some())
+}
+
+fn parse(
+    id: usize,
+    timestamp: Duration,
+    captured_at: SystemTime,
+    link_type: u32,
+    data: Arc<[u8]>,
+    applications: bool,
+) -> PacketInfo {
+    let mut dissection = Dissection::new(applications);
+    profile::timed(profile::DISSECTION, || {
+        if wlan::is_wireless(link_type) {
+            if let Some(frame) = wlan::dissect(link_type, &data) {
+                dissection.protocol = "802.11".to_string();
+                dissection.dissect_framing(&data, frame, captured_at);
+            }
+        } else if let Some(framing) = link::decode(link_type, &data) {
+            dissection.dissect_framing(&data, framing, captured_at);
+        } else {
+            dissection.dissect(&data, SlicedPacket::from_ethernet(&data), captured_at, 0);
         }
+    });
+    let Dissection {
+        src_addr,
+        dst_addr,
+        src_port,
+        dst_port,
+        outer_addrs,
+        mut protocol,
+        transport,
+        tcp_flags,
+        tcp_sequence,
+        ethertype,
+        vlan,
+        layers,
+        payload,
+        quoted,
+        bad_checksums,
+        ..
+    } = dissection;
+
+    // A tunnel or radio link is only named in the Protocol column when
+    // nothing inside it was decoded further.
+    if let Some(layer) = layers.last()
+        && !is_carrier(layer.name, transport, ethertype)
+    {
+        protocol = layer.name.to_string();
     }
-    PacketInfo {
+    let mut packet = PacketInfo {
         id,
         timestamp,
+        captured_at,
         src_addr,
         src_port,
         dst_addr,
         dst_port,
+        outer_addrs,
         protocol,
+        transport,
+        tcp_flags,
+        tcp_sequence,
+        ethertype,
+        vlan,
+        interface: None,
+        link_type,
         length: data.len(),
+        info: String::new(),
+        layers,
+        payload: payload.unwrap_or(data.len()..data.len()),
         data,
-    }
+        lazy: !applications,
+        quoted,
+        bad_checksums,
+    };
+    // The richest result wins: the innermost decoded protocol, else the
+    // transport header, with a tunnel only when nothing inside it parsed.
+    packet.info = match packet.layers.last() {
+        Some(layer) if !is_carrier(layer.name, packet.transport, packet.ethertype) => {
+            layer.summary.clone()
+        }
+        last => packet
+            .transport_summary()
+            .or_else(|| last.map(|layer| layer.summary.clone()))
+            .unwrap_or_default(),
+    };
+    packet
 }
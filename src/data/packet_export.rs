@@ -0,0 +1,237 @@
+use std::{
+    fmt::Write as _,
+    fs::File,
+    io::{BufWriter, Write},
+    net::IpAddr,
+    path::Path,
+    time::UNIX_EPOCH,
+};
+
+use anyhow::{Context, Result};
+
+use super::{
+    capture_file::{self, OutputCompression},
+    packet::PacketInfo,
+};
+
+const CSV_HEADER: &str =
+    "id,time,relative,interface,protocol,length,src,src_port,dst,dst_port,info";
+
+/// What packets are exported as.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[default]
+    Pcap,
+    Pcapng, // Keeps packet comments
+    Json,   // One object per packet and line
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn next(self) -> Self {
+        match self {
+            ExportFormat::Pcap => ExportFormat::Pcapng,
+            ExportFormat::Pcapng => ExportFormat::Json,
+            ExportFormat::Json => ExportFormat::Csv,
+            ExportFormat::Csv => ExportFormat::Pcap,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ExportFormat::Pcap => "pcap",
+            ExportFormat::Pcapng => "pcapng",
+            ExportFormat::Json => "JSON lines",
+            ExportFormat::Csv => "CSV",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Pcap => "pcap",
+            ExportFormat::Pcapng => "pcapng",
+            ExportFormat::Json => "jsonl",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Quotes a CSV field when it holds a separator, quote or line break.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn addr_text(addr: &Option<Result<IpAddr, String>>) -> Option<String> {
+    match addr {
+        Some(Ok(ip)) => Some(ip.to_string()),
+        Some(Err(mac)) => Some(mac.clone()),
+        None => None,
+    }
+}
+
+fn unix_secs(packet: &PacketInfo) -> f64 {
+    packet
+        .captured_at
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64()
+}
+
+/// `bytes` as offset, hex and ASCII columns of 16 bytes, the way
+/// Wireshark's "Export Packet Bytes" writes them as text.
+pub fn hex_dump(bytes: &[u8]) -> String {
+    let mut dump = String::with_capacity(bytes.len() / 16 * 76 + 76);
+    for (line, chunk) in bytes.chunks(16).enumerate() {
+        let (hex, ascii) = hex_row(chunk);
+        let _ = writeln!(dump, "{:04x}  {hex} {ascii}", line * 16);
+    }
+    dump
+}
+
+/// The hex and ASCII columns of one row of [`hex_dump`], for up to 16
+/// bytes; the hex column is padded to full width.
+pub fn hex_row(chunk: &[u8]) -> (String, String) {
+    let mut hex = String::with_capacity(49);
+    for i in 0..16 {
+        match chunk.get(i) {
+            Some(byte) => {
+                let _ = write!(hex, "{byte:02x} ");
+            }
+            None => hex.push_str("   "),
+        }
+        if i == 7 {
+            hex.push(' ');
+        }
+    }
+    let ascii = chunk
+        .iter()
+        .map(|&byte| {
+            if byte.is_ascii_graphic() || byte == b' ' {
+                byte as char
+            } else {
+                '.'
+            }
+        })
+        .collect();
+    (hex, ascii)
+}
+
+/// `packet` as one line of JSON, with its transport payload in hex if
+/// asked for.
+pub fn json_line(packet: &PacketInfo, payload: bool) -> String {
+    let addr = |addr| addr_text(addr).map_or("null".to_string(), |text| json_string(&text));
+    let port = |port: Option<u16>| port.map_or("null".to_string(), |port| port.to_string());
+    let interface = packet
+        .interface
+        .as_deref()
+        .map_or("null".to_string(), json_string);
+    let mut line = format!(
+        "{{\"id\":{},\"time\":{:.6},\"relative\":{:.6},\"interface\":{interface},\
+         \"protocol\":{},\"length\":{},\"src\":{},\"src_port\":{},\"dst\":{},\
+         \"dst_port\":{},\"info\":{}",
+        packet.id,
+        unix_secs(packet),
+        packet.timestamp.as_secs_f64(),
+        json_string(&packet.protocol),
+        packet.length,
+        addr(&packet.src_addr),
+        port(packet.src_port),
+        addr(&packet.dst_addr),
+        port(packet.dst_port),
+        json_string(&packet.info)
+    );
+    if payload {
+        let _ = write!(line, ",\"payload\":\"{}\"", hex::encode(packet.payload()));
+    }
+    line.push('}');
+    line
+}
+
+/// `packet` as one CSV record, with its transport payload in hex if asked
+/// for.
+fn csv_line(packet: &PacketInfo, payload: bool) -> String {
+    let port = |port: Option<u16>| port.map(|port| port.to_string()).unwrap_or_default();
+    let mut line = format!(
+        "{},{:.6},{:.6},{},{},{},{},{},{},{},{}",
+        packet.id,
+        unix_secs(packet),
+        packet.timestamp.as_secs_f64(),
+        csv_field(packet.interface.as_deref().unwrap_or("")),
+        csv_field(&packet.protocol),
+        packet.length,
+        csv_field(&addr_text(&packet.src_addr).unwrap_or_default()),
+        port(packet.src_port),
+        csv_field(&addr_text(&packet.dst_addr).unwrap_or_default()),
+        port(packet.dst_port),
+        csv_field(&packet.info)
+    );
+    if payload {
+        let _ = write!(line, ",{}", hex::encode(packet.payload()));
+    }
+    line
+}
+
+/// Writes `packets` to `path` in `format`, returning how many were
+/// written. Pcap and pcapng files named .gz or .zst are compressed, and
+/// pcapng keeps the comment paired with each packet.
+pub fn save<'a>(
+    path: &Path,
+    format: ExportFormat,
+    payload: bool,
+    packets: impl IntoIterator<Item = (&'a PacketInfo, Option<&'a str>)>,
+) -> Result<usize> {
+    let compression = OutputCompression::for_path(path);
+    match format {
+        ExportFormat::Pcap => {
+            let packets = packets.into_iter().map(|(packet, _)| packet);
+            return capture_file::save(path, compression, packets);
+        }
+        ExportFormat::Pcapng => return capture_file::save_pcapng(path, compression, packets),
+        ExportFormat::Json | ExportFormat::Csv => {}
+    }
+    let file =
+        File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+    let mut count = 0;
+    let write = || -> std::io::Result<()> {
+        if format == ExportFormat::Csv {
+            let payload_column = if payload { ",payload" } else { "" };
+            writeln!(writer, "{CSV_HEADER}{payload_column}")?;
+        }
+        for (packet, _) in packets {
+            if format == ExportFormat::Csv {
+                writeln!(writer, "{}", csv_line(packet, payload))?;
+            } else {
+                writeln!(writer, "{}", json_line(packet, payload))?;
+            }
+            count += 1;
+        }
+        writer.flush()
+    };
+    write().with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(count)
+}
@@ -0,0 +1,472 @@
+use std::{
+    ffi::{CString, c_int, c_void},
+    fs, io, mem, ptr, slice,
+    sync::atomic::{Ordering, fence},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result, bail};
+
+use super::{
+    capture_file::LINKTYPE_ETHERNET,
+    capture_options::CaptureOptions,
+    live::{CaptureBackend, Stat},
+    time::unix_time,
+};
+
+/// Size of each block of the receive ring, which the kernel fills with
+/// frames and hands over as a whole.
+const BLOCK_SIZE: u32 = 1 << 20;
+/// Blocks in the ring unless a buffer size is set, in MiB like libpcap's.
+const DEFAULT_BLOCKS: u32 = 4;
+/// Slot size the kernel checks the ring against; frames in TPACKET_V3
+/// blocks are packed tighter than this.
+const FRAME_SIZE: u32 = 2048;
+
+/// ARPHRD_* interface types and the link types their frames have.
+fn link_type_of(interface: &str) -> Result<u32> {
+    let path = format!("/sys/class/net/{interface}/type");
+    let kind = fs::read_to_string(&path).with_context(|| format!("Failed to read {path}"))?;
+    Ok(match kind.trim() {
+        "1" | "772" => LINKTYPE_ETHERNET, // Ethernet, loopback
+        "65534" => 101,                   // None (tun): LINKTYPE_RAW
+        "801" => 105,                     // 802.11
+        "803" => 127,                     // 802.11 with radiotap
+        other => bail!("{interface} has link type {other}, which AF_PACKET capture can't read"),
+    })
+}
+
+fn jump(code: u32, k: u32, jt: u8, jf: u8) -> libc::sock_filter {
+    libc::sock_filter {
+        code: code as u16,
+        jt,
+        jf,
+        k,
+    }
+}
+
+/// A classic BPF program given as numbers rather than as a filter
+/// expression, in the form `tcpdump -ddd` prints: the instruction count,
+/// then `code jt jf k` for each instruction, on lines of their own or
+/// separated by commas as iptables and tc take them. `None` when `text` is
+/// an expression.
+pub fn raw_program(text: &str) -> Option<Result<Vec<libc::sock_filter>>> {
+    let text = text.trim();
+    let numeric = text
+        .chars()
+        .all(|c| c.is_ascii_digit() || c.is_ascii_whitespace() || c == ',');
+    if text.is_empty() || !numeric {
+        return None;
+    }
+    Some(parse_program(text).context("Invalid BPF program"))
+}
+
+fn parse_program(text: &str) -> Result<Vec<libc::sock_filter>> {
+    let mut groups = text
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|group| !group.is_empty());
+    let count: usize = groups.next().unwrap_or_default().parse()?;
+    let program = groups
+        .map(|group| {
+            let fields: Vec<&str> = group.split_whitespace().collect();
+            let [code, jt, jf, k] = fields[..] else {
+                bail!("Expected `code jt jf k`, got `{group}`");
+            };
+            let code: u16 = code.parse()?;
+            Ok(jump(code.into(), k.parse()?, jt.parse()?, jf.parse()?))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if program.len() != count {
+        bail!(
+            "It says it has {count} instructions but has {}",
+            program.len()
+        );
+    }
+    Ok(program)
+}
+
+/// `filter` compiled by libpcap for `link_type`.
+#[cfg(feature = "libpcap")]
+fn compile(filter: &str, link_type: u32) -> Result<Vec<libc::sock_filter>> {
+    let compiled = pcap::Capture::dead(pcap::Linktype(link_type as i32))?.compile(filter, true)?;
+    let instructions = compiled.get_instructions();
+    // SAFETY: BpfInstruction is a transparent bpf_insn, laid out like the
+    // kernel's sock_filter.
+    let instructions = unsafe {
+        slice::from_raw_parts(
+            instructions.as_ptr().cast::<libc::sock_filter>(),
+            instructions.len(),
+        )
+    };
+    Ok(instructions.to_vec())
+}
+
+/// Without libpcap only the empty filter, which takes every frame, can be
+/// given as an expression.
+#[cfg(not(feature = "libpcap"))]
+fn compile(filter: &str, _link_type: u32) -> Result<Vec<libc::sock_filter>> {
+    if !filter.trim().is_empty() {
+        bail!(
+            "Built without libpcap, so filters must be BPF programs as `tcpdump -ddd` prints them"
+        );
+    }
+    Ok(vec![jump(libc::BPF_RET | libc::BPF_K, u32::MAX, 0, 0)])
+}
+
+/// Checks that packet sockets can be opened, which takes CAP_NET_RAW.
+#[cfg(not(feature = "libpcap"))]
+pub fn check_access() -> Result<()> {
+    // SAFETY: a plain socket call; the socket is closed right away.
+    let fd = unsafe { libc::socket(libc::AF_PACKET, libc::SOCK_RAW | libc::SOCK_CLOEXEC, 0) };
+    if fd < 0 {
+        return Err(last_error("socket(AF_PACKET)"));
+    }
+    // SAFETY: `fd` is open and closed once.
+    unsafe { libc::close(fd) };
+    Ok(())
+}
+
+fn last_error(what: &str) -> anyhow::Error {
+    anyhow::Error::new(io::Error::last_os_error()).context(format!("{what} failed"))
+}
+
+fn set_option<T>(fd: c_int, name: c_int, value: &T) -> io::Result<()> {
+    // SAFETY: `value` is valid for reads of its size, which the kernel
+    // copies before returning.
+    let result = unsafe {
+        libc::setsockopt(
+            fd,
+            libc::SOL_PACKET,
+            name,
+            (value as *const T).cast(),
+            mem::size_of::<T>() as libc::socklen_t,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// A Linux packet socket reading a TPACKET_V3 receive ring shared with the
+/// kernel, in place of a libpcap handle. Frames are read where the kernel
+/// wrote them and each block is given back once all of its frames are.
+pub struct PacketSocket {
+    fd: c_int,
+    ring: *mut u8,
+    blocks: u32,
+    block: u32,     // Block being read, or the next to wait for
+    remaining: u32, // Frames of `block` not yet read; 0 before it is taken
+    frame: usize,   // Offset of the next frame in `block`
+    reading: bool,  // Whether `block` is ours until its frames are read
+    snaplen: usize,
+    timeout: c_int,
+    link_type: u32,
+    counted: Stat,
+    tagged: Vec<u8>, // The last frame with its VLAN tag put back
+}
+
+// SAFETY: the ring is only reached through `&mut self`.
+unsafe impl Send for PacketSocket {}
+
+impl PacketSocket {
+    /// Opens a packet socket on `interface` with a receive ring of
+    /// `options.buffer_size` MiB, in promiscuous mode if asked. The capture
+    /// filter in `options` is attached before the socket is bound, so no
+    /// frame reaches the ring without passing it.
+    pub fn open(interface: &str, options: &CaptureOptions) -> Result<Self> {
+        let link_type = link_type_of(interface)?;
+        let name = CString::new(interface)?;
+        // SAFETY: `name` is a valid C string.
+        let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+        if index == 0 {
+            return Err(last_error("if_nametoindex")).context("Device not found");
+        }
+        let protocol = (libc::ETH_P_ALL as u16).to_be();
+        // Protocol 0 takes no frames until bind names the one to take, so
+        // none from other interfaces arrive while the socket is set up.
+        // SAFETY: a plain socket call.
+        let fd = unsafe { libc::socket(libc::AF_PACKET, libc::SOCK_RAW | libc::SOCK_CLOEXEC, 0) };
+        if fd < 0 {
+            return Err(last_error("socket(AF_PACKET)"));
+        }
+        // Closed with the socket from here on, also when setting it up fails.
+        let mut socket = Self {
+            fd,
+            ring: ptr::null_mut(),
+            blocks: options.buffer_size.unwrap_or(DEFAULT_BLOCKS).max(1),
+            block: 0,
+            remaining: 0,
+            frame: 0,
+            reading: false,
+            snaplen: options.snaplen as usize,
+            timeout: if options.immediate {
+                1
+            } else {
+                c_int::try_from(options.timeout.max(1)).unwrap_or(c_int::MAX)
+            },
+            link_type,
+            counted: Stat {
+                received: 0,
+                dropped: 0,
+                if_dropped: 0,
+            },
+            tagged: Vec::new(),
+        };
+
+        let version = libc::tpacket_versions::TPACKET_V3 as c_int;
+        set_option(fd, libc::PACKET_VERSION, &version).context("Failed to select TPACKET_V3")?;
+        let frames = (BLOCK_SIZE / FRAME_SIZE)
+            .checked_mul(socket.blocks)
+            .context("Buffer size too large for the receive ring")?;
+        let request = libc::tpacket_req3 {
+            tp_block_size: BLOCK_SIZE,
+            tp_block_nr: socket.blocks,
+            tp_frame_size: FRAME_SIZE,
+            tp_frame_nr: frames,
+            tp_retire_blk_tov: socket.timeout as u32,
+            tp_sizeof_priv: 0,
+            tp_feature_req_word: 0,
+        };
+        set_option(fd, libc::PACKET_RX_RING, &request)
+            .context("Failed to set up the receive ring")?;
+        let len = BLOCK_SIZE as usize * socket.blocks as usize;
+        // SAFETY: maps the ring just set up, unmapped on drop.
+        let ring = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                fd,
+                0,
+            )
+        };
+        if ring == libc::MAP_FAILED {
+            return Err(last_error("mmap"));
+        }
+        socket.ring = ring.cast();
+        if !options.filter.is_empty() {
+            socket
+                .filter(&options.filter)
+                .with_context(|| format!("Invalid capture filter {}", options.filter))?;
+        }
+
+        // SAFETY: all zeros is a valid sockaddr_ll.
+        let mut address: libc::sockaddr_ll = unsafe { mem::zeroed() };
+        address.sll_family = libc::AF_PACKET as u16;
+        address.sll_protocol = protocol;
+        address.sll_ifindex = index as c_int;
+        // SAFETY: `address` is valid for reads of its size.
+        let bound = unsafe {
+            libc::bind(
+                fd,
+                (&address as *const libc::sockaddr_ll).cast(),
+                mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+            )
+        };
+        if bound != 0 {
+            return Err(last_error("bind"));
+        }
+        if options.promisc {
+            let membership = libc::packet_mreq {
+                mr_ifindex: index as c_int,
+                mr_type: libc::PACKET_MR_PROMISC as u16,
+                mr_alen: 0,
+                mr_address: [0; 8],
+            };
+            set_option(fd, libc::PACKET_ADD_MEMBERSHIP, &membership)
+                .context("Failed to enter promiscuous mode")?;
+        }
+        Ok(socket)
+    }
+
+    fn block_header(&self, block: u32) -> *mut libc::tpacket_block_desc {
+        // SAFETY: `block` is below `self.blocks`, so inside the ring.
+        unsafe { self.ring.add(block as usize * BLOCK_SIZE as usize).cast() }
+    }
+
+    /// Whether the kernel has handed `self.block` over.
+    fn block_ready(&self) -> bool {
+        let header = self.block_header(self.block);
+        // SAFETY: the kernel writes the status, so it is read volatile and
+        // fenced before the frames it covers.
+        let status = unsafe { ptr::read_volatile(&raw const (*header).hdr.bh1.block_status) };
+        fence(Ordering::Acquire);
+        status & libc::TP_STATUS_USER != 0
+    }
+
+    /// Gives the block just read back to the kernel and moves to the next.
+    fn release_block(&mut self) {
+        let header = self.block_header(self.block);
+        fence(Ordering::Release);
+        // SAFETY: the block is ours until this write.
+        unsafe {
+            ptr::write_volatile(
+                &raw mut (*header).hdr.bh1.block_status,
+                libc::TP_STATUS_KERNEL,
+            );
+        }
+        self.reading = false;
+        self.block = (self.block + 1) % self.blocks;
+    }
+
+    /// Waits up to the read timeout for the next block to fill.
+    fn wait(&self) {
+        let mut poll = libc::pollfd {
+            fd: self.fd,
+            events: libc::POLLIN | libc::POLLERR,
+            revents: 0,
+        };
+        // SAFETY: `poll` is valid for the call.
+        unsafe { libc::poll(&mut poll, 1, self.timeout) };
+    }
+
+    /// `filter` as a raw BPF program or compiled by libpcap for this link
+    /// type.
+    fn program(&self, filter: &str) -> Result<Vec<libc::sock_filter>> {
+        match raw_program(filter) {
+            Some(program) => program,
+            None => compile(filter, self.link_type),
+        }
+    }
+}
+
+impl CaptureBackend for PacketSocket {
+    /// Applies a BPF filter, given as a raw program or compiled by libpcap
+    /// for this link type, and run by the kernel before frames reach the
+    /// ring.
+    fn filter(&mut self, filter: &str) -> Result<()> {
+        let mut instructions = self.program(filter)?;
+        let program = libc::sock_fprog {
+            len: u16::try_from(instructions.len()).context("Filter too long")?,
+            filter: instructions.as_mut_ptr(),
+        };
+        // SAFETY: `program` is valid for reads of its size; the kernel
+        // copies the instructions.
+        let result = unsafe {
+            libc::setsockopt(
+                self.fd,
+                libc::SOL_SOCKET,
+                libc::SO_ATTACH_FILTER,
+                (&program as *const libc::sock_fprog).cast::<c_void>(),
+                mem::size_of::<libc::sock_fprog>() as libc::socklen_t,
+            )
+        };
+        if result != 0 {
+            return Err(last_error("SO_ATTACH_FILTER"));
+        }
+        Ok(())
+    }
+
+    /// The counters since the socket was opened. The kernel resets its own
+    /// on each read, so they are summed here.
+    fn stats(&mut self) -> Option<Stat> {
+        let mut raw = libc::tpacket_stats_v3 {
+            tp_packets: 0,
+            tp_drops: 0,
+            tp_freeze_q_cnt: 0,
+        };
+        let mut len = mem::size_of::<libc::tpacket_stats_v3>() as libc::socklen_t;
+        // SAFETY: `raw` and `len` are valid for the call.
+        let result = unsafe {
+            libc::getsockopt(
+                self.fd,
+                libc::SOL_PACKET,
+                libc::PACKET_STATISTICS,
+                (&mut raw as *mut libc::tpacket_stats_v3).cast(),
+                &mut len,
+            )
+        };
+        if result != 0 {
+            return None;
+        }
+        // Like libpcap, received counts the dropped frames too.
+        self.counted.received = self.counted.received.wrapping_add(raw.tp_packets);
+        self.counted.dropped = self.counted.dropped.wrapping_add(raw.tp_drops);
+        Some(self.counted)
+    }
+
+    /// The next frame with its capture time, None when none came within
+    /// the read timeout.
+    fn next_packet(&mut self) -> Option<(SystemTime, &[u8])> {
+        if self.reading && self.remaining == 0 {
+            self.release_block();
+        }
+        if !self.reading {
+            if !self.block_ready() {
+                self.wait();
+                if !self.block_ready() {
+                    return None;
+                }
+            }
+            let header = self.block_header(self.block);
+            // SAFETY: the kernel handed the block over with these filled in.
+            let (count, first) = unsafe {
+                let header = &(*header).hdr.bh1;
+                (header.num_pkts, header.offset_to_first_pkt as usize)
+            };
+            self.reading = true;
+            self.remaining = count;
+            self.frame = first;
+            if count == 0 {
+                return None;
+            }
+        }
+
+        // SAFETY: the block is ours and its frames are where their headers
+        // say, until it is released on a later call.
+        unsafe {
+            let block = self.block_header(self.block).cast::<u8>();
+            let frame = block.add(self.frame);
+            let header = &*frame.cast::<libc::tpacket3_hdr>();
+            self.remaining -= 1;
+            self.frame += header.tp_next_offset as usize;
+            let captured_at = unix_time(
+                header.tp_sec,
+                Duration::from_nanos(u64::from(header.tp_nsec)),
+            );
+            let len = (header.tp_snaplen as usize).min(self.snaplen);
+            let data = slice::from_raw_parts(frame.add(usize::from(header.tp_mac)), len);
+            // The kernel takes the 802.1Q tag off and reports it beside the
+            // frame, so it is put back between the addresses and EtherType.
+            if header.tp_status & libc::TP_STATUS_VLAN_VALID != 0
+                && self.link_type == LINKTYPE_ETHERNET
+                && data.len() >= 12
+            {
+                let tpid = if header.tp_status & libc::TP_STATUS_VLAN_TPID_VALID != 0 {
+                    header.hv1.tp_vlan_tpid
+                } else {
+                    libc::ETH_P_8021Q as u16
+                };
+                self.tagged.clear();
+                self.tagged.extend_from_slice(&data[..12]);
+                self.tagged.extend_from_slice(&tpid.to_be_bytes());
+                self.tagged
+                    .extend_from_slice(&(header.hv1.tp_vlan_tci as u16).to_be_bytes());
+                self.tagged.extend_from_slice(&data[12..]);
+                self.tagged.truncate(self.snaplen);
+                return Some((captured_at, &self.tagged));
+            }
+            Some((captured_at, data))
+        }
+    }
+
+    fn link_type(&self) -> u32 {
+        self.link_type
+    }
+}
+
+impl Drop for PacketSocket {
+    fn drop(&mut self) {
+        // SAFETY: the ring and socket are released exactly once.
+        unsafe {
+            if !self.ring.is_null() {
+                libc::munmap(self.ring.cast(), BLOCK_SIZE as usize * self.blocks as usize);
+            }
+            libc::close(self.fd);
+        }
+    }
+}
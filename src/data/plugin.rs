@@ -0,0 +1,178 @@
+use std::{fs, path::Path, sync::OnceLock};
+
+use anyhow::{Context, Result, bail};
+use rhai::{AST, Array, Blob, Dynamic, Engine, Map, Scope, module_resolvers::DummyModuleResolver};
+
+use super::{filter_history::config_dir, layer::Layer};
+
+/// Operations a script may run per packet before it is stopped, so a
+/// runaway loop costs one packet's decoding rather than the capture.
+const MAX_OPERATIONS: u64 = 200_000;
+
+/// Fields the Info column shows from a plugin's result.
+const SUMMARY_FIELDS: usize = 3;
+
+/// A dissector written in Rhai. The script defines `ports()`, returning the
+/// TCP and UDP ports it decodes, and `dissect(payload)`, which gets the
+/// payload as a blob and returns its fields as an array of `[name, value]`
+/// pairs or as a map, or `()` to leave the payload to the built-in
+/// decoders. An optional `name()` names the protocol; by default the file
+/// does. Scripts can't `import` modules, and what they `print` or `debug`
+/// is dropped rather than written over the TUI.
+///
+/// ```rhai
+/// fn ports() { [7777] }
+/// fn dissect(payload) {
+///     if payload.len() < 2 { return (); }
+///     [["Version", payload[0]], ["Opcode", payload[1]]]
+/// }
+/// ```
+struct Plugin {
+    name: &'static str,
+    ports: Vec<u16>,
+    ast: AST,
+}
+
+struct Plugins {
+    engine: Engine,
+    plugins: Vec<Plugin>,
+}
+
+static PLUGINS: OnceLock<Plugins> = OnceLock::new();
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(64 * 1024);
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+    // Modules would be read from anywhere on disk.
+    engine.set_module_resolver(DummyModuleResolver::new());
+    // Both write to the terminal the TUI is drawn on by default.
+    engine.on_print(|_| {});
+    engine.on_debug(|_, _, _| {});
+    engine
+}
+
+fn has_function(ast: &AST, name: &str, params: usize) -> bool {
+    ast.iter_functions()
+        .any(|function| function.name == name && function.params.len() == params)
+}
+
+fn load(engine: &Engine, path: &Path) -> Result<Plugin> {
+    let ast = engine
+        .compile_file(path.to_path_buf())
+        .map_err(|e| anyhow::anyhow!("{e}"))?;
+    if !has_function(&ast, "ports", 0) || !has_function(&ast, "dissect", 1) {
+        bail!("A plugin defines ports() and dissect(payload)");
+    }
+    let ports: Array = engine
+        .call_fn(&mut Scope::new(), &ast, "ports", ())
+        .map_err(|e| anyhow::anyhow!("ports(): {e}"))?;
+    let ports = ports
+        .into_iter()
+        .map(|port| {
+            port.as_int()
+                .ok()
+                .and_then(|port| u16::try_from(port).ok())
+                .context("ports() returns an array of port numbers")
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let name = if has_function(&ast, "name", 0) {
+        engine
+            .call_fn::<String>(&mut Scope::new(), &ast, "name", ())
+            .map_err(|e| anyhow::anyhow!("name(): {e}"))?
+    } else {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+    Ok(Plugin {
+        // Layer names are static; plugins load once and live as long.
+        name: Box::leak(name.into_boxed_str()),
+        ports,
+        ast,
+    })
+}
+
+/// Loads the `.rhai` dissectors in the plugins directory of the config
+/// directory, in file name order, returning how many there were. A script
+/// that doesn't compile stops startup with its error.
+pub fn init() -> Result<usize> {
+    let Some(dir) = config_dir().map(|dir| dir.join("plugins")) else {
+        return Ok(0);
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(0);
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+        .collect();
+    paths.sort();
+
+    let engine = engine();
+    let plugins = paths
+        .iter()
+        .map(|path| {
+            load(&engine, path).with_context(|| format!("Failed to load plugin {}", path.display()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let count = plugins.len();
+    if count > 0 {
+        let _ = PLUGINS.set(Plugins { engine, plugins });
+    }
+    Ok(count)
+}
+
+/// The fields of `value` as returned by `dissect`, or None if it holds none.
+fn fields(value: Dynamic) -> Option<Vec<(String, String)>> {
+    if value.is_map() {
+        let map: Map = value.cast();
+        return Some(
+            map.into_iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        );
+    }
+    let pairs: Array = value.try_cast()?;
+    pairs
+        .into_iter()
+        .map(|pair| match pair.try_cast::<Array>()?.as_slice() {
+            [name, value] => Some((name.to_string(), value.to_string())),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Decodes `payload` with the first plugin for either port, if any
+/// plugin takes it.
+pub fn decode(src_port: u16, dst_port: u16, payload: &[u8]) -> Option<Layer> {
+    let plugins = PLUGINS.get()?;
+    let plugin = plugins
+        .plugins
+        .iter()
+        .find(|plugin| plugin.ports.contains(&src_port) || plugin.ports.contains(&dst_port))?;
+    let payload: Blob = payload.to_vec();
+    let result: Dynamic = plugins
+        .engine
+        .call_fn(&mut Scope::new(), &plugin.ast, "dissect", (payload,))
+        .ok()?;
+    if result.is_unit() {
+        return None;
+    }
+    let fields = fields(result)?;
+    let summary = fields
+        .iter()
+        .take(SUMMARY_FIELDS)
+        .map(|(name, value)| format!("{name}: {value}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut layer = Layer::new(plugin.name, summary);
+    for (name, value) in fields {
+        layer.field(name, value);
+    }
+    Some(layer)
+}
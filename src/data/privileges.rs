@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+/// Capability bit of CAP_NET_RAW, which opening a packet socket needs.
+#[cfg(target_os = "linux")]
+const CAP_NET_RAW: u32 = 13;
+
+/// What keeps this process from capturing live, each with its own fix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Missing {
+    NetRaw,     // Linux: neither root nor CAP_NET_RAW
+    BpfDevices, // macOS: /dev/bpf* not readable
+    Npcap,      // Windows: Npcap not installed
+}
+
+/// The executable to grant rights to, as shown in the instructions.
+pub fn executable() -> String {
+    std::env::current_exe()
+        .unwrap_or_else(|_| PathBuf::from("sniffer"))
+        .display()
+        .to_string()
+}
+
+/// Checks for the rights live capture needs before libpcap is asked, so a
+/// failure can say how to fix it. None when they are there or this can't
+/// tell, leaving libpcap to decide.
+#[cfg(target_os = "linux")]
+pub fn check() -> Option<Missing> {
+    // Effective capabilities as hex; root has them all
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let effective = status
+        .lines()
+        .find_map(|line| line.strip_prefix("CapEff:"))?
+        .trim();
+    let effective = u64::from_str_radix(effective, 16).ok()?;
+    (effective & 1 << CAP_NET_RAW == 0).then_some(Missing::NetRaw)
+}
+
+#[cfg(target_os = "macos")]
+pub fn check() -> Option<Missing> {
+    match std::fs::File::open("/dev/bpf0") {
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => Some(Missing::BpfDevices),
+        _ => None,
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn check() -> Option<Missing> {
+    let system =
+        std::env::var_os("SystemRoot").map_or_else(|| PathBuf::from(r"C:\Windows"), PathBuf::from);
+    let installed = system.join(r"System32\Npcap\wpcap.dll").exists();
+    (!installed).then_some(Missing::Npcap)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub fn check() -> Option<Missing> {
+    None
+}
+
+/// Whether a failure to open a device reads like missing rights, for
+/// systems where `check` could not tell beforehand.
+pub fn is_permission_error(error: &anyhow::Error) -> bool {
+    let message = format!("{error:#}").to_lowercase();
+    ["permission", "not permitted", "access is denied"]
+        .iter()
+        .any(|phrase| message.contains(phrase))
+}
+
+/// What the current platform lacks when opening failed for lack of rights
+/// but `check` found nothing.
+pub fn platform_missing() -> Missing {
+    if cfg!(target_os = "macos") {
+        Missing::BpfDevices
+    } else if cfg!(target_os = "windows") {
+        Missing::Npcap
+    } else {
+        Missing::NetRaw
+    }
+}
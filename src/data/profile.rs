@@ -0,0 +1,62 @@
+use std::{
+    collections::BTreeMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Name under which whole-packet dissection time is recorded; every decoder's
+/// time is part of it.
+pub const DISSECTION: &str = "Dissection";
+
+/// CPU time spent in one decoder since the counters were last reset.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timing {
+    pub calls: u64,
+    pub total: Duration,
+}
+
+impl Timing {
+    pub fn average(&self) -> Duration {
+        if self.calls == 0 {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos((self.total.as_nanos() / self.calls as u128) as u64)
+        }
+    }
+}
+
+// Decoders run on the capture thread while the UI reads the totals, so the
+// counters are shared process-wide rather than kept per packet.
+static TIMINGS: Mutex<BTreeMap<&'static str, Timing>> = Mutex::new(BTreeMap::new());
+
+/// Runs a decoder, charging its wall time to `name`.
+pub fn timed<T>(name: &'static str, decode: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let value = decode();
+    let elapsed = start.elapsed();
+    if let Ok(mut timings) = TIMINGS.lock() {
+        let timing = timings.entry(name).or_default();
+        timing.calls += 1;
+        timing.total += elapsed;
+    }
+    value
+}
+
+/// Every decoder invoked so far, in name order.
+pub fn snapshot() -> Vec<(&'static str, Timing)> {
+    TIMINGS
+        .lock()
+        .map(|timings| {
+            timings
+                .iter()
+                .map(|(&name, &timing)| (name, timing))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn reset() {
+    if let Ok(mut timings) = TIMINGS.lock() {
+        timings.clear();
+    }
+}
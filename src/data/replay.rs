@@ -0,0 +1,230 @@
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime},
+};
+
+use anyhow::{Context, Result};
+#[cfg(feature = "libpcap")]
+use pcap::Capture;
+
+#[cfg(feature = "libpcap")]
+use super::capture_file::LINKTYPE_ETHERNET;
+use super::{device::Device, packet::PacketInfo};
+
+/// Longest single sleep, so stopping takes effect quickly during long gaps.
+const SLEEP_SLICE: Duration = Duration::from_millis(50);
+
+/// How fast frames are sent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rate {
+    /// The gaps between frames as captured, divided by the factor.
+    Timed(u32),
+    PacketsPerSecond(u32),
+    Unlimited,
+}
+
+/// Rates offered on the replay page, slowest first.
+pub const RATES: [Rate; 9] = [
+    Rate::Timed(1),
+    Rate::Timed(2),
+    Rate::Timed(10),
+    Rate::Timed(100),
+    Rate::PacketsPerSecond(10),
+    Rate::PacketsPerSecond(100),
+    Rate::PacketsPerSecond(1_000),
+    Rate::PacketsPerSecond(10_000),
+    Rate::Unlimited,
+];
+
+impl Rate {
+    pub fn label(&self) -> String {
+        match self {
+            Rate::Timed(1) => "Original timing".to_string(),
+            Rate::Timed(factor) => format!("Original timing x{factor}"),
+            Rate::PacketsPerSecond(pps) => format!("{pps} packets/s"),
+            Rate::Unlimited => "As fast as possible".to_string(),
+        }
+    }
+}
+
+/// A frame to send and when it was captured, for original timing.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub captured_at: SystemTime,
+    pub link_type: u32, // LINKTYPE_ value of the capture it came from
+    pub data: Arc<[u8]>,
+}
+
+impl From<&PacketInfo> for Frame {
+    fn from(packet: &PacketInfo) -> Self {
+        Self {
+            captured_at: packet.captured_at,
+            link_type: packet.link_type,
+            data: Arc::clone(&packet.data),
+        }
+    }
+}
+
+/// What a replay has done so far, updated by its thread.
+#[derive(Debug, Clone, Default)]
+pub struct Progress {
+    pub sent: usize,
+    pub failed: usize,
+    pub total: usize,
+    pub skipped: usize, // Frames left out for not being Ethernet
+    pub bytes: usize,
+    pub error: Option<String>, // Last send error
+    pub started: Option<Instant>,
+    pub finished: Option<Instant>,
+}
+
+/// Frames being sent out of an interface on a thread of their own.
+pub struct Replay {
+    stop: Arc<AtomicBool>,
+    progress: Arc<Mutex<Progress>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Interfaces frames can be sent on; libpcap's "any" only captures.
+pub fn interfaces() -> Result<Vec<Device>> {
+    let devices = Device::list().context("Failed to list devices")?;
+    Ok(devices.into_iter().filter(|d| d.name != "any").collect())
+}
+
+/// Sleeps until `deadline` unless `stop` is set first.
+fn sleep_until(deadline: Instant, stop: &AtomicBool) {
+    while !stop.load(Ordering::Relaxed) {
+        let Some(left) = deadline.checked_duration_since(Instant::now()) else {
+            return;
+        };
+        thread::sleep(left.min(SLEEP_SLICE));
+    }
+}
+
+impl Replay {
+    /// Sends `count` frames out of `interface` at `rate`, going round
+    /// `frames` again when `count` is larger; 0 sends each frame once.
+    /// Only Ethernet frames are sent; the rest are counted as skipped.
+    #[cfg(not(feature = "libpcap"))]
+    pub fn start(
+        _interface: &str,
+        _frames: Vec<Frame>,
+        _rate: Rate,
+        _count: usize,
+    ) -> Result<Self> {
+        anyhow::bail!("Built without libpcap, which sends the frames")
+    }
+
+    #[cfg(feature = "libpcap")]
+    pub fn start(interface: &str, frames: Vec<Frame>, rate: Rate, count: usize) -> Result<Self> {
+        let mut capture = Capture::from_device(interface)?
+            .open()
+            .with_context(|| format!("Cannot open {interface} for sending"))?;
+        let listed = frames.len();
+        let frames: Vec<Frame> = frames
+            .into_iter()
+            .filter(|frame| frame.link_type == LINKTYPE_ETHERNET)
+            .collect();
+        let total = match count {
+            _ if frames.is_empty() => 0,
+            0 => frames.len(),
+            count => count,
+        };
+        let stop = Arc::new(AtomicBool::new(false));
+        let progress = Arc::new(Mutex::new(Progress {
+            total,
+            skipped: listed - frames.len(),
+            started: Some(Instant::now()),
+            ..Progress::default()
+        }));
+
+        let handle = {
+            let stop = Arc::clone(&stop);
+            let progress = Arc::clone(&progress);
+            thread::spawn(move || {
+                let start = Instant::now();
+                let mut offset = Duration::ZERO; // From `start` to the current frame
+                for i in 0..total {
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let frame = &frames[i % frames.len()];
+                    match rate {
+                        Rate::Timed(factor) => {
+                            // Going round again starts without a gap.
+                            if i % frames.len() > 0 {
+                                let previous = &frames[i % frames.len() - 1];
+                                let gap = frame
+                                    .captured_at
+                                    .duration_since(previous.captured_at)
+                                    .unwrap_or_default();
+                                offset += gap / factor.max(1);
+                            }
+                            sleep_until(start + offset, &stop);
+                        }
+                        Rate::PacketsPerSecond(pps) => {
+                            let offset = Duration::from_secs_f64(i as f64 / pps.max(1) as f64);
+                            sleep_until(start + offset, &stop);
+                        }
+                        Rate::Unlimited => {}
+                    }
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let result = capture.sendpacket(&*frame.data);
+                    let Ok(mut progress) = progress.lock() else {
+                        break;
+                    };
+                    match result {
+                        Ok(()) => {
+                            progress.sent += 1;
+                            progress.bytes += frame.data.len();
+                        }
+                        Err(e) => {
+                            progress.failed += 1;
+                            progress.error = Some(e.to_string());
+                        }
+                    }
+                }
+                if let Ok(mut progress) = progress.lock() {
+                    progress.finished = Some(Instant::now());
+                }
+            })
+        };
+        Ok(Self {
+            stop,
+            progress,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn progress(&self) -> Progress {
+        self.progress
+            .lock()
+            .map(|progress| progress.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for Replay {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,155 @@
+use std::{
+    collections::VecDeque,
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context, Result};
+
+use super::{
+    capture_file::{
+        FILE_HEADER_LEN, OutputCompression, OutputFile, RECORD_HEADER_LEN, mixed_link_types,
+        write_header, write_record,
+    },
+    packet::PacketInfo,
+};
+
+/// When a ring of capture files moves on to the next file and how many
+/// files it keeps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingOptions {
+    pub path: PathBuf,                  // Files are named after it, numbered
+    pub max_bytes: Option<u64>,         // Size a file may reach
+    pub max_duration: Option<Duration>, // Time a file may span
+    pub files: usize,                   // Files kept, the oldest deleted first; 0 keeps all
+    pub compression: Option<OutputCompression>,
+}
+
+/// The file being written.
+struct Current {
+    file: OutputFile,
+    path: PathBuf,
+    bytes: u64,
+    first: SystemTime,
+    link_type: u32, // Of every frame in the file, which pcap holds one of
+}
+
+/// Captured packets written to a ring of pcap files like tcpdump's `-C`,
+/// `-G` and `-W`, so a capture can run for days and still leave its latest
+/// traffic on disk whatever the UI keeps in memory.
+pub struct RingWriter {
+    options: RingOptions,
+    current: Option<Current>,
+    written: VecDeque<PathBuf>, // Finished files still kept, oldest first
+    sequence: u64,
+}
+
+impl RingWriter {
+    pub fn new(options: RingOptions) -> Self {
+        Self {
+            options,
+            current: None,
+            written: VecDeque::new(),
+            sequence: 0,
+        }
+    }
+
+    pub fn options(&self) -> &RingOptions {
+        &self.options
+    }
+
+    /// `capture.pcap` becomes `capture_00001.pcap`, and so on.
+    fn next_path(&mut self) -> PathBuf {
+        self.sequence += 1;
+        let path = &self.options.path;
+        let stem = path
+            .file_stem()
+            .unwrap_or(path.as_os_str())
+            .to_string_lossy();
+        let mut name = format!("{stem}_{:05}.pcap", self.sequence);
+        if let Some(compression) = self.options.compression {
+            name = format!("{name}.{}", compression.extension());
+        }
+        path.with_file_name(name)
+    }
+
+    fn is_full(&self, current: &Current, packet: &PacketInfo) -> bool {
+        let len = RECORD_HEADER_LEN + packet.data.len() as u64;
+        let too_big = self
+            .options
+            .max_bytes
+            .is_some_and(|max| current.bytes > FILE_HEADER_LEN && current.bytes + len > max);
+        let too_long = self.options.max_duration.is_some_and(|max| {
+            packet
+                .captured_at
+                .duration_since(current.first)
+                .is_ok_and(|span| span >= max)
+        });
+        too_big || too_long
+    }
+
+    /// Writes `packet`, first moving on to the next file if it would make
+    /// the current one too big or too long.
+    pub fn add(&mut self, packet: &PacketInfo) -> Result<()> {
+        if self
+            .current
+            .as_ref()
+            .is_some_and(|current| self.is_full(current, packet))
+        {
+            self.finish()?;
+        }
+        let current = match self.current {
+            Some(ref mut current) if packet.link_type != current.link_type => {
+                return Err(mixed_link_types(packet.link_type, current.link_type))
+                    .with_context(|| format!("Failed to write {}", current.path.display()));
+            }
+            Some(ref mut current) => current,
+            None => {
+                self.prune()?;
+                let path = self.next_path();
+                let mut file = OutputFile::create(&path, self.options.compression)
+                    .with_context(|| format!("Failed to create {}", path.display()))?;
+                write_header(&mut file, packet.link_type)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+                self.current.insert(Current {
+                    file,
+                    path,
+                    bytes: FILE_HEADER_LEN,
+                    first: packet.captured_at,
+                    link_type: packet.link_type,
+                })
+            }
+        };
+        write_record(&mut current.file, packet)
+            .with_context(|| format!("Failed to write {}", current.path.display()))?;
+        current.bytes += RECORD_HEADER_LEN + packet.data.len() as u64;
+        Ok(())
+    }
+
+    /// Deletes the oldest files so a new one keeps the ring at its size.
+    fn prune(&mut self) -> Result<()> {
+        while self.options.files > 0 && self.written.len() >= self.options.files {
+            let Some(oldest) = self.written.pop_front() else {
+                break;
+            };
+            fs::remove_file(&oldest)
+                .with_context(|| format!("Failed to delete {}", oldest.display()))?;
+        }
+        Ok(())
+    }
+
+    /// Completes the current file, as when a capture stops; the next packet
+    /// starts a new one.
+    pub fn finish(&mut self) -> Result<()> {
+        let Some(current) = self.current.take() else {
+            return Ok(());
+        };
+        current
+            .file
+            .finish()
+            .with_context(|| format!("Failed to write {}", current.path.display()))?;
+        self.written.push_back(current.path);
+        Ok(())
+    }
+}
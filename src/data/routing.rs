@@ -0,0 +1,477 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use super::layer::Layer;
+
+/// IP protocol number of OSPF.
+pub const OSPF_PROTOCOL: u8 = 89;
+pub const BGP_PORT: u16 = 179;
+pub const RIP_PORT: u16 = 520;
+pub const RIPNG_PORT: u16 = 521;
+
+/// Routes and LSAs listed one per field; the rest are only counted.
+const MAX_LISTED: usize = 16;
+
+const OSPF_HELLO: u8 = 1;
+const OSPF_LS_UPDATE: u8 = 4;
+
+const BGP_MARKER: [u8; 16] = [0xff; 16];
+const BGP_HEADER_LEN: usize = 19;
+const BGP_OPEN: u8 = 1;
+const BGP_UPDATE: u8 = 2;
+const BGP_NOTIFICATION: u8 = 3;
+/// OPEN capability announcing 4-byte AS numbers.
+const CAPABILITY_AS4: u8 = 65;
+/// Path attributes shown by name; others are left out.
+const ATTRIBUTE_ORIGIN: u8 = 1;
+const ATTRIBUTE_AS_PATH: u8 = 2;
+const ATTRIBUTE_NEXT_HOP: u8 = 3;
+const ATTRIBUTE_MED: u8 = 4;
+const ATTRIBUTE_LOCAL_PREF: u8 = 5;
+const ATTRIBUTE_MP_REACH: u8 = 14;
+const ATTRIBUTE_MP_UNREACH: u8 = 15;
+/// Attribute flag for a two-byte length.
+const EXTENDED_LENGTH: u8 = 0x10;
+const AFI_IPV4: u16 = 1;
+const AFI_IPV6: u16 = 2;
+
+const RIP_ENTRY_LEN: usize = 20;
+/// Address family of a RIPv2 entry carrying authentication.
+const RIP_AUTHENTICATION: u16 = 0xffff;
+const RIP_INFINITY: u32 = 16;
+/// Metric marking a RIPng entry as the next hop of those after it.
+const RIPNG_NEXT_HOP: u8 = 0xff;
+
+fn u16_at(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn u32_at(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn ipv4_at(data: &[u8], at: usize) -> Option<Ipv4Addr> {
+    u32_at(data, at).map(Ipv4Addr::from)
+}
+
+/// Adds one field per value, up to `MAX_LISTED`, then a count of the rest.
+fn list(layer: &mut Layer, name: &str, values: &[String]) {
+    for value in values.iter().take(MAX_LISTED) {
+        layer.field(name, value);
+    }
+    if values.len() > MAX_LISTED {
+        layer.field(name, format!("... {} more", values.len() - MAX_LISTED));
+    }
+}
+
+fn ospf_type_name(ospf_type: u8) -> &'static str {
+    match ospf_type {
+        1 => "Hello",
+        2 => "DB Description",
+        3 => "LS Request",
+        4 => "LS Update",
+        5 => "LS Acknowledge",
+        _ => "Unknown",
+    }
+}
+
+fn lsa_type_name(v3: bool, lsa_type: u16) -> &'static str {
+    match (v3, lsa_type) {
+        (false, 1) | (true, 0x2001) => "Router",
+        (false, 2) | (true, 0x2002) => "Network",
+        (false, 3) | (true, 0x2003) => "Summary",
+        (false, 4) | (true, 0x2004) => "ASBR Summary",
+        (false, 5) | (true, 0x4005) => "AS External",
+        (false, 7) | (true, 0x2007) => "NSSA",
+        (true, 0x0008) => "Link",
+        (true, 0x2009) => "Intra-Area Prefix",
+        _ => "Other",
+    }
+}
+
+/// The LSAs of an LS Update, each with the prefix it advertises when it is
+/// an OSPFv2 summary or external LSA.
+fn lsas(body: &[u8], v3: bool) -> Option<(usize, Vec<String>)> {
+    let count = u32_at(body, 0)? as usize;
+    let mut rest = body.get(4..)?;
+    let mut lsas = Vec::new();
+    for _ in 0..count {
+        let (lsa_type, id) = if v3 {
+            (u16_at(rest, 2)?, ipv4_at(rest, 4)?)
+        } else {
+            (u16::from(*rest.get(3)?), ipv4_at(rest, 4)?)
+        };
+        let router = ipv4_at(rest, 8)?;
+        let len = usize::from(u16_at(rest, 18)?).max(20);
+        let name = lsa_type_name(v3, lsa_type);
+        let advertises = match (v3, lsa_type) {
+            (false, 3 | 5 | 7) => ipv4_at(rest, 20)
+                .map(|mask| format!("{id}/{}", u32::from(mask).count_ones()))
+                .unwrap_or_else(|| id.to_string()),
+            _ => id.to_string(),
+        };
+        lsas.push(format!("{name} {advertises} from {router}"));
+        let Some(next) = rest.get(len..) else {
+            break;
+        };
+        rest = next;
+    }
+    Some((count, lsas))
+}
+
+/// Decodes an OSPF packet, the payload of IP protocol 89: a common header
+/// naming the router and area, then Hello or LS Update details.
+pub fn decode_ospf(payload: &[u8]) -> Option<Layer> {
+    let version = *payload.first()?;
+    let ospf_type = *payload.get(1)?;
+    let router = ipv4_at(payload, 4)?;
+    let area = ipv4_at(payload, 8)?;
+    let v3 = version == 3;
+    let body = payload.get(if v3 { 16 } else { 24 }..)?;
+
+    let name = ospf_type_name(ospf_type);
+    let mut summary = format!("{name}, router {router}, area {area}");
+    let mut layer = Layer::new("OSPF", String::new());
+    layer.field("Version", version.to_string());
+    layer.field("Message Type", name);
+    layer.field("Router ID", router.to_string());
+    layer.field("Area ID", area.to_string());
+    match ospf_type {
+        OSPF_HELLO => {
+            // Both versions put the designated routers and neighbors last.
+            let (hello, dead, priority) = if v3 {
+                (
+                    u32::from(u16_at(body, 8)?),
+                    u32::from(u16_at(body, 10)?),
+                    *body.get(4)?,
+                )
+            } else {
+                layer.field("Network Mask", ipv4_at(body, 0)?.to_string());
+                (u32::from(u16_at(body, 4)?), u32_at(body, 8)?, *body.get(7)?)
+            };
+            let dr = ipv4_at(body, 12)?;
+            let bdr = ipv4_at(body, 16)?;
+            layer.field("Hello Interval", format!("{hello} s"));
+            layer.field("Dead Interval", format!("{dead} s"));
+            layer.field("Priority", priority.to_string());
+            layer.field("Designated Router", dr.to_string());
+            layer.field("Backup Designated Router", bdr.to_string());
+            let neighbors: Vec<String> = body
+                .get(20..)
+                .unwrap_or_default()
+                .chunks_exact(4)
+                .filter_map(|neighbor| ipv4_at(neighbor, 0))
+                .map(|neighbor| neighbor.to_string())
+                .collect();
+            summary = format!("{summary}, {} neighbors", neighbors.len());
+            list(&mut layer, "Neighbor", &neighbors);
+        }
+        OSPF_LS_UPDATE => {
+            let (count, lsas) = lsas(body, v3)?;
+            summary = format!("{summary}, {count} LSAs");
+            layer.field("LSAs", count.to_string());
+            list(&mut layer, "LSA", &lsas);
+        }
+        _ => {}
+    }
+    layer.summary = summary;
+    Some(layer)
+}
+
+fn bgp_type_name(bgp_type: u8) -> &'static str {
+    match bgp_type {
+        1 => "OPEN",
+        2 => "UPDATE",
+        3 => "NOTIFICATION",
+        4 => "KEEPALIVE",
+        5 => "ROUTE-REFRESH",
+        _ => "Unknown",
+    }
+}
+
+fn notification_name(code: u8) -> &'static str {
+    match code {
+        1 => "Message Header Error",
+        2 => "OPEN Message Error",
+        3 => "UPDATE Message Error",
+        4 => "Hold Timer Expired",
+        5 => "Finite State Machine Error",
+        6 => "Cease",
+        7 => "ROUTE-REFRESH Message Error",
+        _ => "Unknown",
+    }
+}
+
+/// Prefixes as a bit length followed by just the bytes it needs.
+fn prefixes(mut data: &[u8], v6: bool) -> Vec<String> {
+    let mut prefixes = Vec::new();
+    while let Some((&bits, rest)) = data.split_first() {
+        let len = usize::from(bits).div_ceil(8);
+        let Some(bytes) = rest.get(..len) else {
+            break;
+        };
+        let addr = if v6 {
+            let mut octets = [0; 16];
+            octets[..len.min(16)].copy_from_slice(&bytes[..len.min(16)]);
+            IpAddr::V6(Ipv6Addr::from(octets))
+        } else {
+            let mut octets = [0; 4];
+            octets[..len.min(4)].copy_from_slice(&bytes[..len.min(4)]);
+            IpAddr::V4(Ipv4Addr::from(octets))
+        };
+        prefixes.push(format!("{addr}/{bits}"));
+        data = &rest[len..];
+    }
+    prefixes
+}
+
+/// AS numbers of an AS_PATH, which are four bytes when both speakers
+/// support it and two otherwise; the width whose segments fill the
+/// attribute exactly is taken.
+fn as_path(data: &[u8]) -> Option<String> {
+    let segments = |width: usize| -> Option<Vec<String>> {
+        let mut rest = data;
+        let mut segments = Vec::new();
+        while let Some(&[segment_type, count]) = rest.get(..2) {
+            let len = 2 + usize::from(count) * width;
+            let numbers: Vec<String> = rest
+                .get(2..len)?
+                .chunks_exact(width)
+                .map(|number| {
+                    number
+                        .iter()
+                        .fold(0u32, |asn, &byte| asn << 8 | u32::from(byte))
+                        .to_string()
+                })
+                .collect();
+            // AS_SET is unordered and shown in braces.
+            segments.push(if segment_type == 1 {
+                format!("{{{}}}", numbers.join(","))
+            } else {
+                numbers.join(" ")
+            });
+            rest = &rest[len..];
+        }
+        rest.is_empty().then_some(segments)
+    };
+    segments(4)
+        .or_else(|| segments(2))
+        .map(|segments| segments.join(" "))
+}
+
+/// Announced and withdrawn prefixes of multiprotocol attributes, which
+/// carry IPv6 and other address families.
+fn mp_prefixes(value: &[u8], reach: bool) -> Option<(bool, Vec<String>)> {
+    let v6 = match u16_at(value, 0)? {
+        AFI_IPV4 => false,
+        AFI_IPV6 => true,
+        _ => return None,
+    };
+    let nlri = if reach {
+        let next_hop_len = usize::from(*value.get(3)?);
+        // Next hop, then a reserved byte.
+        value.get(4 + next_hop_len + 1..)?
+    } else {
+        value.get(3..)?
+    };
+    Some((v6, prefixes(nlri, v6)))
+}
+
+/// Describes an UPDATE: routes withdrawn, path attributes and routes
+/// announced. Returns the summary.
+fn bgp_update(layer: &mut Layer, body: &[u8]) -> Option<String> {
+    let withdrawn_len = usize::from(u16_at(body, 0)?);
+    let mut withdrawn = prefixes(body.get(2..2 + withdrawn_len)?, false);
+    let attributes_at = 2 + withdrawn_len;
+    let attributes_len = usize::from(u16_at(body, attributes_at)?);
+    let mut attributes = body.get(attributes_at + 2..attributes_at + 2 + attributes_len)?;
+    let mut announced = prefixes(body.get(attributes_at + 2 + attributes_len..)?, false);
+
+    while let Some(&[flags, attribute_type]) = attributes.get(..2) {
+        let (value_at, len) = if flags & EXTENDED_LENGTH != 0 {
+            (4, usize::from(u16_at(attributes, 2)?))
+        } else {
+            (3, usize::from(*attributes.get(2)?))
+        };
+        let value = attributes.get(value_at..value_at + len)?;
+        match attribute_type {
+            ATTRIBUTE_ORIGIN => {
+                let origin = match value.first() {
+                    Some(0) => "IGP",
+                    Some(1) => "EGP",
+                    _ => "Incomplete",
+                };
+                layer.field("Origin", origin);
+            }
+            ATTRIBUTE_AS_PATH => {
+                layer.field("AS Path", as_path(value).unwrap_or_else(|| "-".to_string()));
+            }
+            ATTRIBUTE_NEXT_HOP => {
+                if let Some(next_hop) = ipv4_at(value, 0) {
+                    layer.field("Next Hop", next_hop.to_string());
+                }
+            }
+            ATTRIBUTE_MED => {
+                if let Some(med) = u32_at(value, 0) {
+                    layer.field("MED", med.to_string());
+                }
+            }
+            ATTRIBUTE_LOCAL_PREF => {
+                if let Some(preference) = u32_at(value, 0) {
+                    layer.field("Local Preference", preference.to_string());
+                }
+            }
+            ATTRIBUTE_MP_REACH | ATTRIBUTE_MP_UNREACH => {
+                let reach = attribute_type == ATTRIBUTE_MP_REACH;
+                if let Some((v6, prefixes)) = mp_prefixes(value, reach) {
+                    if reach && v6 {
+                        let next_hop = value
+                            .get(4..20)
+                            .and_then(|hop| <[u8; 16]>::try_from(hop).ok());
+                        if let Some(next_hop) = next_hop {
+                            layer.field("Next Hop", Ipv6Addr::from(next_hop).to_string());
+                        }
+                    }
+                    if reach {
+                        announced.extend(prefixes);
+                    } else {
+                        withdrawn.extend(prefixes);
+                    }
+                }
+            }
+            _ => {}
+        }
+        attributes = &attributes[value_at + len..];
+    }
+
+    list(layer, "Withdrawn", &withdrawn);
+    list(layer, "Announced", &announced);
+    Some(match (announced.len(), withdrawn.len()) {
+        (0, 0) => "UPDATE, End-of-RIB".to_string(),
+        (announced, 0) => format!("UPDATE, {announced} announced"),
+        (0, withdrawn) => format!("UPDATE, {withdrawn} withdrawn"),
+        (announced, withdrawn) => {
+            format!("UPDATE, {announced} announced, {withdrawn} withdrawn")
+        }
+    })
+}
+
+/// Describes an OPEN: the speaker's AS, hold time and identifier, with the
+/// 4-byte AS from its capabilities when it has one. Returns the summary.
+fn bgp_open(layer: &mut Layer, body: &[u8]) -> Option<String> {
+    let mut asn = u32::from(u16_at(body, 1)?);
+    let hold_time = u16_at(body, 3)?;
+    let identifier = ipv4_at(body, 5)?;
+    let params_len = usize::from(*body.get(9)?);
+    let mut params = body.get(10..10 + params_len).unwrap_or_default();
+    // Capabilities are parameter 2, each a code, length and value.
+    while let Some(&[param_type, len]) = params.get(..2) {
+        let value = params.get(2..2 + usize::from(len)).unwrap_or_default();
+        if param_type == 2 {
+            let mut capabilities = value;
+            while let Some(&[code, len]) = capabilities.get(..2) {
+                if code == CAPABILITY_AS4
+                    && let Some(as4) = u32_at(capabilities, 2)
+                {
+                    asn = as4;
+                }
+                capabilities = capabilities.get(2 + usize::from(len)..).unwrap_or_default();
+            }
+        }
+        params = params.get(2 + usize::from(len)..).unwrap_or_default();
+    }
+    layer.field("Version", body.first()?.to_string());
+    layer.field("AS", asn.to_string());
+    layer.field("Hold Time", format!("{hold_time} s"));
+    layer.field("BGP Identifier", identifier.to_string());
+    Some(format!("OPEN, AS {asn}, identifier {identifier}"))
+}
+
+/// Decodes the BGP messages of a TCP segment, each a marker, length and
+/// type. Messages split across segments are left out.
+pub fn decode_bgp(payload: &[u8]) -> Option<Layer> {
+    let mut rest = payload;
+    let mut summaries = Vec::new();
+    let mut layer = Layer::new("BGP", String::new());
+    while rest.len() >= BGP_HEADER_LEN && rest.starts_with(&BGP_MARKER) {
+        let len = usize::from(u16_at(rest, 16)?);
+        let bgp_type = rest[18];
+        let Some(body) = rest.get(BGP_HEADER_LEN..len) else {
+            break;
+        };
+        let name = bgp_type_name(bgp_type);
+        layer.field("Message Type", name);
+        let summary = match bgp_type {
+            BGP_OPEN => bgp_open(&mut layer, body),
+            BGP_UPDATE => bgp_update(&mut layer, body),
+            BGP_NOTIFICATION => {
+                let code = *body.first()?;
+                let error = notification_name(code);
+                layer.field("Error", error);
+                layer.field("Error Subcode", body.get(1)?.to_string());
+                Some(format!("NOTIFICATION, {error}"))
+            }
+            _ => None,
+        };
+        summaries.push(summary.unwrap_or_else(|| name.to_string()));
+        rest = &rest[len.max(BGP_HEADER_LEN)..];
+    }
+    if summaries.is_empty() {
+        return None;
+    }
+    layer.summary = summaries.join("; ");
+    Some(layer)
+}
+
+/// Decodes a RIP or RIPng packet: a command and version, then 20-byte
+/// route entries.
+pub fn decode_rip(payload: &[u8], ng: bool) -> Option<Layer> {
+    let command = match *payload.first()? {
+        1 => "Request",
+        2 => "Response",
+        _ => return None,
+    };
+    let version = *payload.get(1)?;
+    let entries = payload.get(4..)?.chunks_exact(RIP_ENTRY_LEN);
+    let mut routes = Vec::new();
+    for entry in entries {
+        if ng {
+            let prefix = Ipv6Addr::from(<[u8; 16]>::try_from(&entry[..16]).ok()?);
+            let metric = entry[19];
+            if metric == RIPNG_NEXT_HOP {
+                routes.push(format!("next hop {prefix}"));
+            } else {
+                routes.push(format!("{prefix}/{} metric {metric}", entry[18]));
+            }
+            continue;
+        }
+        if u16_at(entry, 0)? == RIP_AUTHENTICATION {
+            continue;
+        }
+        let addr = ipv4_at(entry, 4)?;
+        let mask = u32_at(entry, 8)?;
+        let next_hop = ipv4_at(entry, 12)?;
+        let metric = u32_at(entry, 16)?;
+        let mut route = if version >= 2 {
+            format!("{addr}/{} metric {metric}", mask.count_ones())
+        } else {
+            format!("{addr} metric {metric}")
+        };
+        if metric >= RIP_INFINITY {
+            route.push_str(" (unreachable)");
+        }
+        if !next_hop.is_unspecified() {
+            route = format!("{route} via {next_hop}");
+        }
+        routes.push(route);
+    }
+
+    let name = if ng { "RIPng" } else { "RIP" };
+    let mut layer = Layer::new(
+        name,
+        format!("{command} v{version}, {} routes", routes.len()),
+    );
+    layer.field("Command", command);
+    layer.field("Version", version.to_string());
+    list(&mut layer, "Route", &routes);
+    Some(layer)
+}
@@ -0,0 +1,366 @@
+use std::{
+    collections::HashMap,
+    ffi::{CStr, CString, c_char, c_int, c_uint, c_void},
+    fmt,
+    ptr::{self, NonNull},
+    slice,
+    sync::{Mutex, PoisonError},
+    time::{Duration, SystemTime},
+};
+
+use super::{
+    capture_options::CaptureOptions,
+    device::Device,
+    live::{CaptureBackend, Stat},
+    time::unix_time,
+};
+use anyhow::{Result, bail};
+
+/// Scheme of libpcap's remote capture URLs, `rpcap://host[:port]/device`,
+/// served by rpcapd.
+pub const SCHEME: &str = "rpcap://";
+
+// The pcap crate doesn't bind libpcap's remote capture API, so the few
+// functions needed are declared here. libpcap itself is linked by the
+// pcap crate.
+const ERRBUF_SIZE: usize = 256;
+const OPENFLAG_PROMISCUOUS: c_int = 1;
+const OPENFLAG_MAX_RESPONSIVENESS: c_int = 16;
+const RMTAUTH_NULL: c_int = 0;
+const RMTAUTH_PWD: c_int = 1;
+const NETMASK_UNKNOWN: c_uint = 0xffff_ffff;
+
+#[repr(C)]
+struct RawAuth {
+    kind: c_int,
+    username: *mut c_char,
+    password: *mut c_char,
+}
+
+#[repr(C)]
+struct RawDevice {
+    next: *mut RawDevice,
+    name: *mut c_char,
+    description: *mut c_char,
+    addresses: *mut c_void,
+    flags: c_uint,
+}
+
+#[repr(C)]
+struct RawHeader {
+    ts: libc::timeval,
+    caplen: c_uint,
+    len: c_uint,
+}
+
+#[repr(C)]
+struct RawStat {
+    received: c_uint,
+    dropped: c_uint,
+    if_dropped: c_uint,
+}
+
+#[repr(C)]
+struct RawProgram {
+    len: c_uint,
+    instructions: *mut c_void,
+}
+
+#[cfg(feature = "rpcap")]
+unsafe extern "C" {
+    fn pcap_open(
+        source: *const c_char,
+        snaplen: c_int,
+        flags: c_int,
+        read_timeout: c_int,
+        auth: *mut RawAuth,
+        errbuf: *mut c_char,
+    ) -> *mut c_void;
+    fn pcap_findalldevs_ex(
+        source: *const c_char,
+        auth: *mut RawAuth,
+        devices: *mut *mut RawDevice,
+        errbuf: *mut c_char,
+    ) -> c_int;
+    fn pcap_freealldevs(devices: *mut RawDevice);
+    fn pcap_next_ex(p: *mut c_void, header: *mut *mut RawHeader, data: *mut *const u8) -> c_int;
+    fn pcap_stats(p: *mut c_void, stat: *mut RawStat) -> c_int;
+    fn pcap_compile(
+        p: *mut c_void,
+        program: *mut RawProgram,
+        text: *const c_char,
+        optimize: c_int,
+        netmask: c_uint,
+    ) -> c_int;
+    fn pcap_setfilter(p: *mut c_void, program: *mut RawProgram) -> c_int;
+    fn pcap_freecode(program: *mut RawProgram);
+    fn pcap_datalink(p: *mut c_void) -> c_int;
+    fn pcap_geterr(p: *mut c_void) -> *const c_char;
+    fn pcap_close(p: *mut c_void);
+}
+
+/// Username and password for an rpcapd started without `-n`.
+#[derive(Clone)]
+pub struct Auth {
+    pub username: String,
+    pub password: String,
+}
+
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Auth")
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Credentials by host, remembered from the device page so captures on
+/// that host's devices can log in again.
+static CREDENTIALS: Mutex<Option<HashMap<String, Auth>>> = Mutex::new(None);
+
+pub fn is_remote(name: &str) -> bool {
+    name.starts_with(SCHEME)
+}
+
+/// The `host[:port]` part of a remote capture URL.
+fn host(url: &str) -> &str {
+    let rest = url.strip_prefix(SCHEME).unwrap_or(url);
+    rest.split('/').next().unwrap_or(rest)
+}
+
+pub fn remember(host_url: &str, auth: Option<Auth>) {
+    let mut credentials = CREDENTIALS.lock().unwrap_or_else(PoisonError::into_inner);
+    let credentials = credentials.get_or_insert_with(HashMap::new);
+    let host = host(host_url).to_string();
+    match auth {
+        Some(auth) => credentials.insert(host, auth),
+        None => credentials.remove(&host),
+    };
+}
+
+#[cfg(feature = "rpcap")]
+fn remembered(url: &str) -> Option<Auth> {
+    let credentials = CREDENTIALS.lock().unwrap_or_else(PoisonError::into_inner);
+    credentials.as_ref()?.get(host(url)).cloned()
+}
+
+/// `RawAuth` pointing into its own strings, which must outlive the call.
+struct AuthStrings {
+    username: CString,
+    password: CString,
+}
+
+impl AuthStrings {
+    fn new(auth: Option<&Auth>) -> Result<Option<Self>> {
+        auth.map(|auth| {
+            Ok(Self {
+                username: CString::new(auth.username.as_str())?,
+                password: CString::new(auth.password.as_str())?,
+            })
+        })
+        .transpose()
+    }
+
+    fn raw(strings: Option<&Self>) -> RawAuth {
+        match strings {
+            Some(strings) => RawAuth {
+                kind: RMTAUTH_PWD,
+                username: strings.username.as_ptr().cast_mut(),
+                password: strings.password.as_ptr().cast_mut(),
+            },
+            None => RawAuth {
+                kind: RMTAUTH_NULL,
+                username: ptr::null_mut(),
+                password: ptr::null_mut(),
+            },
+        }
+    }
+}
+
+fn errbuf_message(errbuf: &[c_char]) -> String {
+    // SAFETY: libpcap leaves a NUL-terminated message in the buffer.
+    unsafe { CStr::from_ptr(errbuf.as_ptr()) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Asks rpcapd on `host_url` (`rpcap://host[:port]/`) for its devices,
+/// named by their full remote capture URLs.
+#[cfg(feature = "rpcap")]
+pub fn list(host_url: &str, auth: Option<&Auth>) -> Result<Vec<Device>> {
+    let source = CString::new(host_url)?;
+    let strings = AuthStrings::new(auth)?;
+    let mut raw_auth = AuthStrings::raw(strings.as_ref());
+    let mut errbuf = [0 as c_char; ERRBUF_SIZE];
+    let mut first = ptr::null_mut();
+    // SAFETY: every pointer is valid for the call; the list is freed below.
+    let result = unsafe {
+        pcap_findalldevs_ex(
+            source.as_ptr(),
+            &mut raw_auth,
+            &mut first,
+            errbuf.as_mut_ptr(),
+        )
+    };
+    if result != 0 {
+        bail!("{}", errbuf_message(&errbuf));
+    }
+
+    let mut devices = Vec::new();
+    let mut next = first;
+    while let Some(raw) = NonNull::new(next) {
+        // SAFETY: the list stays allocated until pcap_freealldevs.
+        let raw = unsafe { raw.as_ref() };
+        let text = |text: *mut c_char| {
+            // SAFETY: names and descriptions are NUL-terminated or null.
+            NonNull::new(text).map(|text| {
+                unsafe { CStr::from_ptr(text.as_ptr()) }
+                    .to_string_lossy()
+                    .into_owned()
+            })
+        };
+        if let Some(name) = text(raw.name) {
+            let mut device = Device::from(name.as_str());
+            device.desc = text(raw.description);
+            devices.push(device);
+        }
+        next = raw.next;
+    }
+    // SAFETY: `first` came from pcap_findalldevs_ex and is freed once.
+    unsafe { pcap_freealldevs(first) };
+    Ok(devices)
+}
+
+/// Remote capture needs a libpcap built with --enable-remote, so without
+/// the rpcap feature no host is asked.
+#[cfg(not(feature = "rpcap"))]
+pub fn list(_host_url: &str, _auth: Option<&Auth>) -> Result<Vec<Device>> {
+    bail!("Remote capture unsupported: built without the rpcap feature")
+}
+
+/// A live capture on an rpcapd device, standing in for `Capture<Active>`.
+#[cfg(feature = "rpcap")]
+pub struct RemoteCapture {
+    handle: NonNull<c_void>,
+}
+
+// SAFETY: the handle is only used by the thread owning the capture.
+#[cfg(feature = "rpcap")]
+unsafe impl Send for RemoteCapture {}
+
+#[cfg(feature = "rpcap")]
+impl RemoteCapture {
+    /// Opens `url` with `options`, logging in with the credentials
+    /// remembered for its host. The buffer size is left to rpcapd.
+    pub fn open(url: &str, options: &CaptureOptions) -> Result<Self> {
+        let source = CString::new(url)?;
+        let auth = remembered(url);
+        let strings = AuthStrings::new(auth.as_ref())?;
+        let mut raw_auth = AuthStrings::raw(strings.as_ref());
+        let mut errbuf = [0 as c_char; ERRBUF_SIZE];
+        let mut flags = 0;
+        if options.promisc {
+            flags |= OPENFLAG_PROMISCUOUS;
+        }
+        if options.immediate {
+            flags |= OPENFLAG_MAX_RESPONSIVENESS;
+        }
+        // SAFETY: every pointer is valid for the call.
+        let handle = unsafe {
+            pcap_open(
+                source.as_ptr(),
+                options.snaplen as c_int,
+                flags,
+                options.timeout as c_int,
+                &mut raw_auth,
+                errbuf.as_mut_ptr(),
+            )
+        };
+        match NonNull::new(handle) {
+            Some(handle) => Ok(Self { handle }),
+            None => bail!("{}", errbuf_message(&errbuf)),
+        }
+    }
+
+    fn error(&self) -> String {
+        // SAFETY: pcap_geterr returns a NUL-terminated string owned by the handle.
+        unsafe { CStr::from_ptr(pcap_geterr(self.handle.as_ptr())) }
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+#[cfg(feature = "rpcap")]
+impl CaptureBackend for RemoteCapture {
+    fn filter(&mut self, text: &str) -> Result<()> {
+        let text = CString::new(text)?;
+        let mut program = RawProgram {
+            len: 0,
+            instructions: ptr::null_mut(),
+        };
+        // SAFETY: the program is freed after being set, which copies it.
+        unsafe {
+            let handle = self.handle.as_ptr();
+            if pcap_compile(handle, &mut program, text.as_ptr(), 1, NETMASK_UNKNOWN) != 0 {
+                bail!("{}", self.error());
+            }
+            let result = pcap_setfilter(handle, &mut program);
+            pcap_freecode(&mut program);
+            if result != 0 {
+                bail!("{}", self.error());
+            }
+        }
+        Ok(())
+    }
+
+    /// The next frame with the time libpcap captured it, or `None` on a
+    /// read timeout or error.
+    fn next_packet(&mut self) -> Option<(SystemTime, &[u8])> {
+        let mut header = ptr::null_mut();
+        let mut data = ptr::null();
+        // SAFETY: on success both point at the frame until the next call,
+        // which the borrow of `self` prevents.
+        unsafe {
+            if pcap_next_ex(self.handle.as_ptr(), &mut header, &mut data) != 1 {
+                return None;
+            }
+            let ts = (*header).ts;
+            let captured_at = unix_time(ts.tv_sec, Duration::from_micros(ts.tv_usec as u64));
+            Some((
+                captured_at,
+                slice::from_raw_parts(data, (*header).caplen as usize),
+            ))
+        }
+    }
+
+    fn stats(&mut self) -> Option<Stat> {
+        let mut raw = RawStat {
+            received: 0,
+            dropped: 0,
+            if_dropped: 0,
+        };
+        // SAFETY: `raw` is valid for the call.
+        if unsafe { pcap_stats(self.handle.as_ptr(), &mut raw) } != 0 {
+            return None;
+        }
+        Some(Stat {
+            received: raw.received,
+            dropped: raw.dropped,
+            if_dropped: raw.if_dropped,
+        })
+    }
+
+    fn link_type(&self) -> u32 {
+        // SAFETY: the handle is open.
+        unsafe { pcap_datalink(self.handle.as_ptr()) as u32 }
+    }
+}
+
+#[cfg(feature = "rpcap")]
+impl Drop for RemoteCapture {
+    fn drop(&mut self) {
+        // SAFETY: the handle is closed exactly once.
+        unsafe { pcap_close(self.handle.as_ptr()) };
+    }
+}
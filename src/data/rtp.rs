@@ -0,0 +1,126 @@
+use std::{
+    collections::BTreeSet,
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use super::layer::Layer;
+
+const HEADER_LEN: usize = 12;
+
+/// Ports SIP calls have offered media on, learned from their SDP bodies.
+static MEDIA_PORTS: Mutex<BTreeSet<u16>> = Mutex::new(BTreeSet::new());
+
+/// Whether UDP between unprivileged ports is tried as RTP on any port.
+static HEURISTIC: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy)]
+pub struct RtpHeader {
+    pub marker: bool,
+    pub payload_type: u8,
+    pub sequence: u16,
+    pub timestamp: u32,
+    pub ssrc: u32,
+}
+
+/// Returns the codec name and RTP clock rate of a static payload type.
+/// Dynamic types are assumed to be 8 kHz audio.
+pub fn payload_type_info(payload_type: u8) -> (&'static str, u32) {
+    match payload_type {
+        0 => ("PCMU", 8000),
+        3 => ("GSM", 8000),
+        4 => ("G723", 8000),
+        8 => ("PCMA", 8000),
+        9 => ("G722", 8000),
+        13 => ("CN", 8000),
+        18 => ("G729", 8000),
+        26 => ("JPEG", 90_000),
+        31 => ("H261", 90_000),
+        34 => ("H263", 90_000),
+        96..=127 => ("dynamic", 8000),
+        _ => ("unknown", 8000),
+    }
+}
+
+/// Heuristically parses an RTP header. There is no port or magic number to
+/// go by, so the version, a plausible payload type (which also rules out
+/// RTCP) and consistent lengths are required.
+pub fn parse(payload: &[u8]) -> Option<RtpHeader> {
+    if payload.len() < HEADER_LEN || payload[0] >> 6 != 2 {
+        return None;
+    }
+    let padding = payload[0] & 0x20 != 0;
+    let csrc_count = (payload[0] & 0x0f) as usize;
+    let marker = payload[1] & 0x80 != 0;
+    let payload_type = payload[1] & 0x7f;
+    if !matches!(payload_type, 0..=34 | 96..=127) {
+        return None;
+    }
+    let header_len = HEADER_LEN + csrc_count * 4;
+    if payload.len() < header_len {
+        return None;
+    }
+    if padding && *payload.last()? as usize > payload.len() - header_len {
+        return None;
+    }
+
+    Some(RtpHeader {
+        marker,
+        payload_type,
+        sequence: u16::from_be_bytes([payload[2], payload[3]]),
+        timestamp: u32::from_be_bytes(payload[4..8].try_into().ok()?),
+        ssrc: u32::from_be_bytes(payload[8..12].try_into().ok()?),
+    })
+}
+
+/// Remembers `port` as carrying RTP, as an SDP offer or answer said.
+pub fn add_media_port(port: u16) {
+    MEDIA_PORTS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(port);
+}
+
+/// Tries UDP between any two unprivileged ports as RTP, not only on the
+/// ports SIP calls negotiated. Other UDP traffic that happens to pass the
+/// checks in [`parse`] is then labelled RTP too.
+pub fn set_heuristic(enabled: bool) {
+    HEURISTIC.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether a UDP datagram between these ports should be tried as RTP.
+pub fn is_candidate(src_port: u16, dst_port: u16) -> bool {
+    if HEURISTIC.load(Ordering::Relaxed) && src_port >= 1024 && dst_port >= 1024 {
+        return true;
+    }
+    let ports = MEDIA_PORTS.lock().unwrap_or_else(PoisonError::into_inner);
+    ports.contains(&src_port) || ports.contains(&dst_port)
+}
+
+pub fn decode(payload: &[u8]) -> Option<Layer> {
+    let header = parse(payload)?;
+    let (codec, clock_rate) = payload_type_info(header.payload_type);
+
+    let mut layer = Layer::new(
+        "RTP",
+        format!(
+            "PT={} ({codec}), SSRC=0x{:08x}, Seq={}, Time={}{}",
+            header.payload_type,
+            header.ssrc,
+            header.sequence,
+            header.timestamp,
+            if header.marker { ", Mark" } else { "" }
+        ),
+    );
+    layer.field(
+        "Payload Type",
+        format!("{} ({codec}, {clock_rate} Hz)", header.payload_type),
+    );
+    layer.field("SSRC", format!("0x{:08x}", header.ssrc));
+    layer.field("Sequence Number", header.sequence.to_string());
+    layer.detail("Timestamp", header.timestamp.to_string());
+    layer.detail("Marker", header.marker.to_string());
+    Some(layer)
+}
@@ -0,0 +1,108 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::filter_history::config_dir;
+
+/// Filters saved under a name, in the order they were saved, one
+/// `name<Tab>filter` per line in a file of the config directory.
+#[derive(Debug, Clone, Default)]
+pub struct SavedFilters {
+    path: Option<PathBuf>, // None without a config directory
+    pub entries: Vec<(String, String)>,
+}
+
+/// Keeps a name on one line of the file, apart from its filter.
+fn clean(name: &str) -> String {
+    name.replace(['\t', '\n', '\r'], " ").trim().to_string()
+}
+
+impl SavedFilters {
+    /// Reads the filters saved in `file_name`, none if there is no such
+    /// file or it can't be read.
+    pub fn load(file_name: &str) -> Self {
+        let path = config_dir().map(|dir| dir.join(file_name));
+        let entries = path
+            .as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|text| {
+                text.lines()
+                    .filter_map(|line| line.split_once('\t'))
+                    .map(|(name, filter)| (name.to_string(), filter.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { path, entries }
+    }
+
+    /// Like `load`, but starts from `defaults` until the file is first
+    /// written, so deleting every entry sticks.
+    pub fn load_or(file_name: &str, defaults: &[(&str, &str)]) -> Self {
+        let mut filters = Self::load(file_name);
+        let written = filters.path.as_ref().is_some_and(|path| path.exists());
+        if !written {
+            filters.entries = defaults
+                .iter()
+                .map(|&(name, filter)| (name.to_string(), filter.to_string()))
+                .collect();
+        }
+        filters
+    }
+
+    /// Saves `filter` as `name`, replacing the filter already saved under
+    /// that name if any. Returns its index.
+    pub fn set(&mut self, name: &str, filter: &str) -> usize {
+        let name = clean(name);
+        let filter = filter.replace(['\n', '\r'], " ");
+        if let Some(index) = self.entries.iter().position(|(n, _)| *n == name) {
+            self.entries[index].1 = filter;
+            return index;
+        }
+        self.entries.push((name, filter));
+        self.entries.len() - 1
+    }
+
+    /// Renames the filter at `index`; another one with the new name is
+    /// replaced by it.
+    pub fn rename(&mut self, index: usize, name: &str) {
+        let name = clean(name);
+        if index >= self.entries.len() {
+            return;
+        }
+        self.entries[index].0 = name.clone();
+        let mut i = 0;
+        self.entries.retain(|(n, _)| {
+            i += 1;
+            i - 1 == index || *n != name
+        });
+    }
+
+    /// Puts `name` and `filter` in place of the filter at `index`. Returns
+    /// the index they ended up at.
+    pub fn replace(&mut self, index: usize, name: &str, filter: &str) -> usize {
+        self.rename(index, name);
+        self.set(name, filter)
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        if index < self.entries.len() {
+            self.entries.remove(index);
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let text: String = self
+            .entries
+            .iter()
+            .map(|(name, filter)| format!("{name}\t{filter}\n"))
+            .collect();
+        fs::write(path, text).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
@@ -0,0 +1,99 @@
+use std::{fmt, ops::Range};
+
+use anyhow::{Result, bail};
+
+use super::packet::PacketInfo;
+
+/// What the sniffer's search looks for. Input starting with `0x` is a hex
+/// byte sequence, anything else is text matched without regard to case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Pattern {
+    Text(String),
+    Bytes(Vec<u8>),
+}
+
+impl Pattern {
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        if input.is_empty() {
+            bail!("Nothing to search for");
+        }
+        let Some(hex) = input.strip_prefix("0x") else {
+            return Ok(Self::Text(input.to_lowercase()));
+        };
+        let digits: String = hex
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != ':')
+            .collect();
+        if !digits.is_ascii() {
+            bail!("Not a hex byte sequence: {input}");
+        }
+        if digits.is_empty() || !digits.len().is_multiple_of(2) {
+            bail!("Hex search needs whole bytes: {input}");
+        }
+        let bytes = (0..digits.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&digits[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>();
+        match bytes {
+            Ok(bytes) => Ok(Self::Bytes(bytes)),
+            Err(_) => bail!("Not a hex byte sequence: {input}"),
+        }
+    }
+
+    fn needle(&self) -> &[u8] {
+        match self {
+            Self::Text(text) => text.as_bytes(),
+            Self::Bytes(bytes) => bytes,
+        }
+    }
+
+    /// Where the pattern occurs in the packet's payload, as ranges of frame
+    /// offsets for the hex viewer.
+    pub fn payload_matches(&self, packet: &PacketInfo) -> Vec<Range<usize>> {
+        let needle = self.needle();
+        let payload = packet.payload();
+        if needle.is_empty() || payload.len() < needle.len() {
+            return Vec::new();
+        }
+        let start = packet.payload.start;
+        let mut matches = Vec::new();
+        let mut i = 0;
+        while i + needle.len() <= payload.len() {
+            let window = &payload[i..i + needle.len()];
+            let hit = match self {
+                Self::Text(_) => window.eq_ignore_ascii_case(needle),
+                Self::Bytes(_) => window == needle,
+            };
+            if hit {
+                matches.push(start + i..start + i + needle.len());
+                i += needle.len();
+            } else {
+                i += 1;
+            }
+        }
+        matches
+    }
+
+    /// Whether the packet's payload or, for text, its summary matches.
+    pub fn matches(&self, packet: &PacketInfo) -> bool {
+        if let Self::Text(text) = self
+            && packet.info.to_lowercase().contains(text.as_str())
+        {
+            return true;
+        }
+        !self.payload_matches(packet).is_empty()
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text(text) => write!(f, "\"{text}\""),
+            Self::Bytes(bytes) => {
+                write!(f, "0x")?;
+                bytes.iter().try_for_each(|b| write!(f, "{b:02x}"))
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use super::{layer::Layer, rtp};
+
+pub const PORT: u16 = 5060;
+
+const METHODS: [&str; 14] = [
+    "INVITE",
+    "ACK",
+    "BYE",
+    "CANCEL",
+    "REGISTER",
+    "OPTIONS",
+    "PRACK",
+    "SUBSCRIBE",
+    "NOTIFY",
+    "PUBLISH",
+    "INFO",
+    "REFER",
+    "MESSAGE",
+    "UPDATE",
+];
+
+/// The headers of a SIP message that matter for following a call.
+#[derive(Debug, Clone, Default)]
+pub struct SipMessage {
+    pub method: Option<String>,
+    pub request_uri: Option<String>,
+    pub status: Option<(u16, String)>,
+    pub call_id: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub cseq: Option<String>,
+    pub content_type: Option<String>,
+}
+
+/// Parses the start line and headers of a SIP request or response.
+pub fn parse(payload: &[u8]) -> Option<SipMessage> {
+    let text = std::str::from_utf8(payload.get(..payload.len().min(4096))?).ok()?;
+    let mut lines = text.split("\r\n");
+    let start = lines.next()?;
+
+    let mut message = SipMessage::default();
+    if let Some(rest) = start.strip_prefix("SIP/2.0 ") {
+        let (code, reason) = rest.split_once(' ').unwrap_or((rest, ""));
+        message.status = Some((code.parse().ok()?, reason.to_string()));
+    } else {
+        let mut parts = start.split(' ');
+        let method = parts.next()?;
+        let uri = parts.next()?;
+        if !METHODS.contains(&method) || parts.next() != Some("SIP/2.0") {
+            return None;
+        }
+        message.method = Some(method.to_string());
+        message.request_uri = Some(uri.to_string());
+    }
+
+    for line in lines.take_while(|line| !line.is_empty()) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = Some(value.trim().to_string());
+        match name.trim().to_ascii_lowercase().as_str() {
+            "call-id" | "i" => message.call_id = value,
+            "from" | "f" => message.from = value,
+            "to" | "t" => message.to = value,
+            "cseq" => message.cseq = value,
+            "content-type" | "c" => message.content_type = value,
+            _ => {}
+        }
+    }
+    Some(message)
+}
+
+/// The ports of the RTP media lines (`m=audio 49170 RTP/AVP 0`) in the SDP
+/// body of a SIP message.
+pub fn media_ports(payload: &[u8]) -> Vec<u16> {
+    let text = String::from_utf8_lossy(payload);
+    let Some((_, body)) = text.split_once("\r\n\r\n") else {
+        return Vec::new();
+    };
+    body.lines()
+        .filter_map(|line| {
+            let mut fields = line.strip_prefix("m=")?.split(' ');
+            let _media = fields.next()?;
+            // A port may be followed by a count, as in 49170/2
+            let port = fields.next()?.split('/').next()?.parse().ok()?;
+            let proto = fields.next()?;
+            (port != 0 && proto.contains("RTP")).then_some(port)
+        })
+        .collect()
+}
+
+pub fn decode(payload: &[u8]) -> Option<Layer> {
+    let message = parse(payload)?;
+    for port in media_ports(payload) {
+        rtp::add_media_port(port);
+    }
+
+    let summary = match (&message.method, &message.status) {
+        (Some(method), _) => format!(
+            "Request: {method} {}",
+            message.request_uri.as_deref().unwrap_or_default()
+        ),
+        (None, Some((code, reason))) => match message.cseq {
+            Some(ref cseq) => format!("Status: {code} {reason} ({cseq})"),
+            None => format!("Status: {code} {reason}"),
+        },
+        (None, None) => return None,
+    };
+
+    let mut layer = Layer::new("SIP", summary);
+    if let Some(method) = message.method {
+        layer.field("Method", method);
+    }
+    if let Some(uri) = message.request_uri {
+        layer.field("Request-URI", uri);
+    }
+    if let Some((code, reason)) = message.status {
+        layer.field("Status", format!("{code} {reason}"));
+    }
+    for (name, value) in [
+        ("Call-ID", message.call_id),
+        ("From", message.from),
+        ("To", message.to),
+        ("CSeq", message.cseq),
+        ("Content-Type", message.content_type),
+    ] {
+        if let Some(value) = value {
+            layer.field(name, value);
+        }
+    }
+    Some(layer)
+}
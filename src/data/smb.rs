@@ -0,0 +1,155 @@
+use super::layer::Layer;
+
+pub const PORT: u16 = 445;
+
+const SMB2_MAGIC: &[u8; 4] = b"\xfeSMB";
+const SMB1_MAGIC: &[u8; 4] = b"\xffSMB";
+const SMB2_HEADER_LEN: usize = 64;
+
+fn command_name(command: u16) -> &'static str {
+    match command {
+        0x00 => "Negotiate",
+        0x01 => "Session Setup",
+        0x02 => "Logoff",
+        0x03 => "Tree Connect",
+        0x04 => "Tree Disconnect",
+        0x05 => "Create",
+        0x06 => "Close",
+        0x07 => "Flush",
+        0x08 => "Read",
+        0x09 => "Write",
+        0x0a => "Lock",
+        0x0b => "Ioctl",
+        0x0c => "Cancel",
+        0x0d => "Echo",
+        0x0e => "Query Directory",
+        0x0f => "Change Notify",
+        0x10 => "Query Info",
+        0x11 => "Set Info",
+        0x12 => "Oplock Break",
+        _ => "Unknown",
+    }
+}
+
+fn status_name(status: u32) -> Option<&'static str> {
+    match status {
+        0x0000_0000 => Some("STATUS_SUCCESS"),
+        0x0000_0103 => Some("STATUS_PENDING"),
+        0xc000_0016 => Some("STATUS_MORE_PROCESSING_REQUIRED"),
+        0xc000_0022 => Some("STATUS_ACCESS_DENIED"),
+        0xc000_0034 => Some("STATUS_OBJECT_NAME_NOT_FOUND"),
+        0xc000_006d => Some("STATUS_LOGON_FAILURE"),
+        0xc000_00cc => Some("STATUS_BAD_NETWORK_NAME"),
+        0x8000_0006 => Some("STATUS_NO_MORE_FILES"),
+        _ => None,
+    }
+}
+
+fn utf16le(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Returns the UNC path of a Tree Connect request, whose offset is
+/// relative to the start of the SMB2 header.
+fn tree_connect_path(smb: &[u8]) -> Option<String> {
+    let body = smb.get(SMB2_HEADER_LEN..)?;
+    let offset = u16::from_le_bytes([*body.get(4)?, *body.get(5)?]) as usize;
+    let len = u16::from_le_bytes([*body.get(6)?, *body.get(7)?]) as usize;
+    smb.get(offset..offset + len).map(utf16le)
+}
+
+/// Decodes an SMB message preceded by its 4-byte NetBIOS session header,
+/// as used on both TCP 139 and direct-hosted TCP 445.
+pub fn decode(payload: &[u8]) -> Option<Layer> {
+    let smb = payload.get(4..)?;
+    let magic = smb.get(..4)?;
+    if magic == SMB1_MAGIC {
+        let command = *smb.get(4)?;
+        let mut layer = Layer::new("SMB", format!("SMB1 command 0x{command:02x}"));
+        layer.field("Dialect", "SMB1 (CIFS)".to_string());
+        layer.field("Command", format!("0x{command:02x}"));
+        return Some(layer);
+    }
+    if magic != SMB2_MAGIC || smb.len() < SMB2_HEADER_LEN {
+        return None;
+    }
+
+    let credit_charge = u16::from_le_bytes([smb[6], smb[7]]);
+    let status = u32::from_le_bytes(smb[8..12].try_into().ok()?);
+    let command = u16::from_le_bytes([smb[12], smb[13]]);
+    let credits = u16::from_le_bytes([smb[14], smb[15]]);
+    let flags = u32::from_le_bytes(smb[16..20].try_into().ok()?);
+    let is_response = flags & 0x1 != 0;
+    let is_async = flags & 0x2 != 0;
+    let next_command = u32::from_le_bytes(smb[20..24].try_into().ok()?);
+    let message_id = u64::from_le_bytes(smb[24..32].try_into().ok()?);
+    let tree_id = u32::from_le_bytes(smb[36..40].try_into().ok()?);
+    let session_id = u64::from_le_bytes(smb[40..48].try_into().ok()?);
+
+    let mut summary = format!(
+        "{} {}",
+        command_name(command),
+        if is_response { "Response" } else { "Request" }
+    );
+    let path = if command == 0x03 && !is_response {
+        tree_connect_path(smb)
+    } else {
+        None
+    };
+    if let Some(ref path) = path {
+        summary.push_str(&format!(" {path}"));
+    }
+    if is_response && status != 0 {
+        match status_name(status) {
+            Some(name) => summary.push_str(&format!(", {name}")),
+            None => summary.push_str(&format!(", status 0x{status:08x}")),
+        }
+    }
+
+    let mut layer = Layer::new("SMB2", summary);
+    layer.field(
+        "Command",
+        format!("{} (0x{command:02x})", command_name(command)),
+    );
+    layer.field(
+        "Direction",
+        if is_response { "Response" } else { "Request" }.to_string(),
+    );
+    layer.field(
+        "Status",
+        match status_name(status) {
+            Some(name) => format!("0x{status:08x} ({name})"),
+            None => format!("0x{status:08x}"),
+        },
+    );
+    if let Some(path) = path {
+        layer.field("Tree", path);
+    }
+    layer.detail("Credit Charge", credit_charge.to_string());
+    layer.detail(
+        if is_response {
+            "Credits Granted"
+        } else {
+            "Credits Requested"
+        },
+        credits.to_string(),
+    );
+    layer.detail("Flags", format!("0x{flags:08x}"));
+    layer.detail("Next Command", next_command.to_string());
+    layer.detail("Message ID", message_id.to_string());
+    if is_async {
+        let async_id = u64::from_le_bytes(smb[32..40].try_into().ok()?);
+        layer.detail("Async ID", format!("0x{async_id:016x}"));
+    } else {
+        let process_id = u32::from_le_bytes(smb[32..36].try_into().ok()?);
+        layer.detail("Process ID", format!("0x{process_id:08x}"));
+        layer.detail("Tree ID", format!("0x{tree_id:08x}"));
+    }
+    layer.detail("Session ID", format!("0x{session_id:016x}"));
+    layer.detail("Signature", hex::encode(&smb[48..64]));
+    Some(layer)
+}
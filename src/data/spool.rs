@@ -0,0 +1,212 @@
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
+    mem,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+
+use super::{
+    capture_file::{
+        FILE_HEADER_LEN, LINKTYPE_ETHERNET, RECORD_HEADER_LEN, write_header, write_record,
+    },
+    layer::{Field, Layer},
+    packet::{PacketInfo, parse_packet},
+};
+
+/// Approximate memory held by a parsed packet, used to enforce the budget.
+pub fn footprint(packet: &PacketInfo) -> usize {
+    let layers: usize = packet
+        .layers
+        .iter()
+        .map(|layer| {
+            mem::size_of::<Layer>()
+                + layer.summary.len()
+                + layer
+                    .fields
+                    .iter()
+                    .map(|field| mem::size_of::<Field>() + field.name.len() + field.value.len())
+                    .sum::<usize>()
+        })
+        .sum();
+    mem::size_of::<PacketInfo>()
+        + packet.data.len()
+        + packet.protocol.len()
+        + packet.info.len()
+        + layers
+}
+
+/// Bytes of dropped packets a spool file may hold before the packets still
+/// spooled are moved to a fresh file, as long as those take less.
+const COMPACT_AFTER: u64 = 64 * 1024 * 1024;
+
+/// Where a spooled packet's frame lives in the file, plus what is needed to
+/// parse it back into the same row.
+struct Entry {
+    offset: u64,
+    len: usize,
+    id: usize,
+    timestamp: Duration,
+    captured_at: SystemTime,
+    link_type: u32,
+    interface: Option<Arc<str>>,
+}
+
+/// Creates a spool file only this user can read, under a name nothing had,
+/// so a link planted at a guessable path can't redirect the writes.
+fn create_private() -> Result<(PathBuf, File)> {
+    let dir = std::env::temp_dir();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    for attempt in 0..16 {
+        let path = dir.join(format!(
+            "sniffer-{}-{nanos:08x}-{attempt}.pcap",
+            std::process::id()
+        ));
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        match options.open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to create spool file {}", path.display()));
+            }
+        }
+    }
+    anyhow::bail!("Failed to create a spool file in {}", dir.display())
+}
+
+/// Older packets moved out of memory into a pcap file in the temporary
+/// directory. Only frame offsets stay resident; packets are parsed again
+/// when read back. On Unix the file is unlinked as soon as it is open;
+/// elsewhere it is removed when the spool is dropped.
+pub struct Spool {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    reader: File,
+    end: u64,
+    entries: VecDeque<Entry>,
+}
+
+impl Spool {
+    pub fn create() -> Result<Self> {
+        let (path, file) = create_private()?;
+        let mut writer = BufWriter::new(file);
+        // Read back only here, where each entry keeps its own link type.
+        write_header(&mut writer, LINKTYPE_ETHERNET)?;
+        writer.flush()?;
+        let reader = File::open(&path)
+            .with_context(|| format!("Failed to open spool file {}", path.display()))?;
+        // Both handles are open, so the name is no longer needed.
+        #[cfg(unix)]
+        let _ = fs::remove_file(&path);
+        Ok(Self {
+            path,
+            writer,
+            reader,
+            end: FILE_HEADER_LEN,
+            entries: VecDeque::new(),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Appends a packet after the ones already spooled. Call `flush` before
+    /// reading it back.
+    pub fn push(&mut self, packet: &PacketInfo) -> Result<()> {
+        write_record(&mut self.writer, packet)?;
+        self.entries.push_back(Entry {
+            offset: self.end + RECORD_HEADER_LEN,
+            len: packet.data.len(),
+            id: packet.id,
+            timestamp: packet.timestamp,
+            captured_at: packet.captured_at,
+            link_type: packet.link_type,
+            interface: packet.interface.clone(),
+        });
+        self.end += RECORD_HEADER_LEN + packet.data.len() as u64;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().context("Failed to write spool file")
+    }
+
+    /// Reads the packet at `position`, counted from the oldest still spooled.
+    pub fn load(&self, position: usize) -> Option<PacketInfo> {
+        let entry = self.entries.get(position)?;
+        let mut data = vec![0; entry.len];
+        let mut reader = &self.reader;
+        reader.seek(SeekFrom::Start(entry.offset)).ok()?;
+        reader.read_exact(&mut data).ok()?;
+        let mut packet = parse_packet(
+            entry.id,
+            entry.timestamp,
+            entry.captured_at,
+            entry.link_type,
+            data.into(),
+        );
+        packet.interface = entry.interface.clone();
+        Some(packet)
+    }
+
+    /// Forgets the oldest `count` packets. Their bytes stay in the file
+    /// until they pass `COMPACT_AFTER` and outweigh the packets left, which
+    /// are then copied to a new file.
+    pub fn drop_front(&mut self, count: usize) -> Result<()> {
+        self.entries.drain(..count.min(self.entries.len()));
+        let start = self
+            .entries
+            .front()
+            .map_or(self.end, |entry| entry.offset - RECORD_HEADER_LEN);
+        let dead = start - FILE_HEADER_LEN;
+        if dead < COMPACT_AFTER || dead < self.end - start {
+            return Ok(());
+        }
+        self.compact(start)
+    }
+
+    /// Moves the records from `start` on to a new spool file, shifting their
+    /// offsets, and lets the old file go.
+    fn compact(&mut self, start: u64) -> Result<()> {
+        self.flush()?;
+        let mut fresh = Self::create()?;
+        let mut reader = &self.reader;
+        reader.seek(SeekFrom::Start(start))?;
+        io::copy(&mut reader.take(self.end - start), &mut fresh.writer)
+            .context("Failed to compact spool file")?;
+        fresh.flush()?;
+        let shift = start - FILE_HEADER_LEN;
+        fresh.entries = mem::take(&mut self.entries);
+        for entry in &mut fresh.entries {
+            entry.offset -= shift;
+        }
+        fresh.end = self.end - shift;
+        *self = fresh;
+        Ok(())
+    }
+}
+
+impl Drop for Spool {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
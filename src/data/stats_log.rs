@@ -0,0 +1,200 @@
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    net::IpAddr,
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+
+use super::packet::{PacketInfo, Transport};
+
+const HEADER: &str = "time,seconds,packets,bytes,pps,bps,tcp,udp,icmp,arp,other,dns,tls,\
+                      tcp_segments,retransmissions,retransmission_rate";
+
+/// TCP directions whose next sequence number is remembered before the
+/// table starts over.
+const FLOW_LIMIT: usize = 65_536;
+
+/// Empty intervals written at most for one gap in the traffic.
+const GAP_LIMIT: u64 = 10_000;
+
+const ETHERTYPE_ARP: u16 = 0x0806;
+
+/// One direction of a TCP connection.
+type Direction = (IpAddr, u16, IpAddr, u16);
+
+/// Counts for one interval.
+#[derive(Debug, Clone, Copy, Default)]
+struct Row {
+    packets: u64,
+    bytes: u64,
+    tcp: u64,
+    udp: u64,
+    icmp: u64,
+    arp: u64,
+    other: u64,
+    dns: u64,
+    tls: u64,
+    tcp_segments: u64, // Segments carrying data, SYN or FIN
+    retransmissions: u64,
+}
+
+/// Statistics for each interval of a capture, appended to a CSV file as
+/// the capture goes on, so long sessions leave a time series to graph even
+/// when the packets themselves are not kept.
+pub struct StatsLog {
+    writer: BufWriter<File>,
+    interval: Duration,
+    current: Option<(u64, Row)>, // Interval number since the epoch, counts
+    next_seq: HashMap<Direction, u32>,
+}
+
+impl StatsLog {
+    /// Opens `path` for appending, writing the header if the file is new,
+    /// with a row per `interval`.
+    pub fn create(path: &Path, interval: Duration) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let is_new = file.metadata().map(|m| m.len() == 0).unwrap_or(true);
+        let mut writer = BufWriter::new(file);
+        if is_new {
+            writeln!(writer, "{HEADER}")
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        Ok(Self {
+            writer,
+            interval: interval.max(Duration::from_millis(1)),
+            current: None,
+            next_seq: HashMap::new(),
+        })
+    }
+
+    fn interval_of(&self, at: SystemTime) -> u64 {
+        let since = at.duration_since(UNIX_EPOCH).unwrap_or_default();
+        (since.as_nanos() / self.interval.as_nanos()) as u64
+    }
+
+    /// Counts `packet`, first writing out the intervals that ended before it.
+    pub fn add(&mut self, packet: &PacketInfo) -> Result<()> {
+        let interval = self.interval_of(packet.captured_at);
+        match self.current {
+            Some((current, _)) if interval > current => {
+                self.write_current()?;
+                // Quiet intervals are written as zeros so graphs show them.
+                let gap = (interval - current - 1).min(GAP_LIMIT);
+                for empty in interval - gap..interval {
+                    self.write_row(empty, &Row::default())?;
+                }
+                self.current = Some((interval, Row::default()));
+            }
+            Some(_) => {}
+            None => self.current = Some((interval, Row::default())),
+        }
+        let retransmission = self.is_retransmission(packet);
+        let Some((_, ref mut row)) = self.current else {
+            return Ok(());
+        };
+        row.packets += 1;
+        row.bytes += packet.length as u64;
+        match packet.transport {
+            Some(Transport::Tcp) => row.tcp += 1,
+            Some(Transport::Udp) => row.udp += 1,
+            Some(Transport::Icmpv4 | Transport::Icmpv6) => row.icmp += 1,
+            None if packet.ethertype == Some(ETHERTYPE_ARP) => row.arp += 1,
+            None => row.other += 1,
+        }
+        match packet.protocol.as_str() {
+            "DNS" => row.dns += 1,
+            "TLS" => row.tls += 1,
+            _ => {}
+        }
+        if let Some(retransmission) = retransmission {
+            row.tcp_segments += 1;
+            row.retransmissions += retransmission as u64;
+        }
+        Ok(())
+    }
+
+    /// Whether a TCP segment that takes up sequence space repeats some of
+    /// what was sent before in its direction; `None` for other packets.
+    fn is_retransmission(&mut self, packet: &PacketInfo) -> Option<bool> {
+        let (Some(flags), Some(sequence)) = (packet.tcp_flags, packet.tcp_sequence) else {
+            return None;
+        };
+        let (Some(Ok(src)), Some(src_port), Some(Ok(dst)), Some(dst_port)) = (
+            packet.src_addr.clone(),
+            packet.src_port,
+            packet.dst_addr.clone(),
+            packet.dst_port,
+        ) else {
+            return None;
+        };
+        let len = packet.payload().len() as u32 + flags.syn as u32 + flags.fin as u32;
+        if len == 0 {
+            return None;
+        }
+        let end = sequence.seq.wrapping_add(len);
+        if self.next_seq.len() >= FLOW_LIMIT {
+            self.next_seq.clear();
+        }
+        let next = self
+            .next_seq
+            .entry((src, src_port, dst, dst_port))
+            .or_insert(sequence.seq);
+        // Sequence numbers wrap, so "before" is within half the space behind.
+        let retransmission = next.wrapping_sub(end) < 1 << 31;
+        if !retransmission {
+            *next = end;
+        }
+        Some(retransmission)
+    }
+
+    fn write_row(&mut self, interval: u64, row: &Row) -> Result<()> {
+        let seconds = self.interval.as_secs_f64();
+        let start = interval as f64 * seconds;
+        let rate = if row.tcp_segments > 0 {
+            row.retransmissions as f64 / row.tcp_segments as f64
+        } else {
+            0.0
+        };
+        writeln!(
+            self.writer,
+            "{start:.3},{seconds},{},{},{:.2},{:.2},{},{},{},{},{},{},{},{},{},{rate:.4}",
+            row.packets,
+            row.bytes,
+            row.packets as f64 / seconds,
+            row.bytes as f64 * 8.0 / seconds,
+            row.tcp,
+            row.udp,
+            row.icmp,
+            row.arp,
+            row.other,
+            row.dns,
+            row.tls,
+            row.tcp_segments,
+            row.retransmissions,
+        )
+        .context("Failed to write statistics")
+    }
+
+    fn write_current(&mut self) -> Result<()> {
+        if let Some((interval, row)) = self.current.take() {
+            self.write_row(interval, &row)?;
+            self.writer.flush().context("Failed to write statistics")?;
+        }
+        Ok(())
+    }
+
+    /// Writes out the interval in progress, as when a capture stops.
+    pub fn finish(&mut self) -> Result<()> {
+        self.write_current()?;
+        self.next_seq.clear();
+        Ok(())
+    }
+}
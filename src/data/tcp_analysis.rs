@@ -0,0 +1,175 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, SystemTime},
+};
+
+use super::{
+    flow::FlowKey,
+    flow_tracker::FlowTracker,
+    packet::{PacketInfo, Transport},
+};
+
+/// A segment sent again this soon after the previous one in its direction
+/// is taken as reordered on the way rather than retransmitted.
+const OUT_OF_ORDER_WINDOW: Duration = Duration::from_millis(3);
+
+/// Duplicate ACKs after which a retransmission counts as a fast one.
+const FAST_RETRANSMIT_ACKS: usize = 2;
+
+/// What TCP analysis notes about a segment, after Wireshark's
+/// `tcp.analysis` flags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TcpIssue {
+    FastRetransmission, // Answering duplicate ACKs
+    Retransmission,
+    OutOfOrder,
+    ZeroWindow,
+    DuplicateAck,
+    KeepAlive, // At most a byte, one before the next sequence number
+}
+
+impl TcpIssue {
+    pub const ALL: [TcpIssue; 6] = [
+        TcpIssue::FastRetransmission,
+        TcpIssue::Retransmission,
+        TcpIssue::OutOfOrder,
+        TcpIssue::ZeroWindow,
+        TcpIssue::DuplicateAck,
+        TcpIssue::KeepAlive,
+    ];
+
+    /// Short enough for the Analysis column.
+    pub fn label(self) -> &'static str {
+        match self {
+            TcpIssue::FastRetransmission => "Fast Retrans",
+            TcpIssue::Retransmission => "Retrans",
+            TcpIssue::OutOfOrder => "Out-Of-Order",
+            TcpIssue::ZeroWindow => "Zero Window",
+            TcpIssue::DuplicateAck => "Dup ACK",
+            TcpIssue::KeepAlive => "Keep-Alive",
+        }
+    }
+}
+
+/// Sequence state of one direction of a connection.
+#[derive(Default)]
+struct Side {
+    next_seq: Option<u32>, // End of the highest segment sent
+    last_ack: Option<(u32, u16)>,
+    duplicate_acks: usize, // Repeats of `last_ack` so far
+    last_sent: Option<SystemTime>,
+}
+
+/// Tags TCP segments with retransmissions, duplicate ACKs and the like as
+/// they are captured, and counts them for the whole session.
+#[derive(Default)]
+pub struct TcpAnalysis {
+    sides: HashMap<(FlowKey, bool), Side>, // Keyed by flow and whether sent from its `a`
+    issues: HashMap<usize, TcpIssue>,      // By packet index
+    pub counts: BTreeMap<TcpIssue, usize>,
+    pruned_at: usize, // Flows the tracker had ended at the last prune
+}
+
+impl TcpAnalysis {
+    pub fn add(&mut self, index: usize, packet: &PacketInfo) {
+        if packet.transport != Some(Transport::Tcp) {
+            return;
+        }
+        let (Some(flags), Some(sequence)) = (packet.tcp_flags, packet.tcp_sequence) else {
+            return;
+        };
+        let Some(key) = FlowKey::from_packet(packet) else {
+            return;
+        };
+        let from_a = packet.src_addr.as_ref() == Some(&key.a.addr) && packet.src_port == key.a.port;
+        let len = packet.payload().len();
+        // SYN and FIN take up one sequence number each.
+        let end = sequence
+            .seq
+            .wrapping_add(len as u32)
+            .wrapping_add(flags.syn as u32 + flags.fin as u32);
+        let acked_twice = self
+            .sides
+            .get(&(key.clone(), !from_a))
+            .is_some_and(|other| {
+                other.duplicate_acks >= FAST_RETRANSMIT_ACKS
+                    && other.last_ack.is_some_and(|(ack, _)| ack == sequence.seq)
+            });
+        let side = self.sides.entry((key, from_a)).or_default();
+
+        let mut issue = None;
+        // Keep-alives resend the byte before the next one to draw an ACK.
+        let resets = flags.syn || flags.fin || flags.rst;
+        if len <= 1 && !resets && side.next_seq == Some(sequence.seq.wrapping_add(1)) {
+            issue = Some(TcpIssue::KeepAlive);
+        } else if end != sequence.seq
+            && let Some(next) = side.next_seq
+            && next.wrapping_sub(sequence.seq).wrapping_sub(1) < 1 << 31
+        {
+            // Starts before the highest byte already sent.
+            let recent = side.last_sent.is_some_and(|last| {
+                packet
+                    .captured_at
+                    .duration_since(last)
+                    .is_ok_and(|gap| gap < OUT_OF_ORDER_WINDOW)
+            });
+            issue = Some(if acked_twice {
+                TcpIssue::FastRetransmission
+            } else if recent {
+                TcpIssue::OutOfOrder
+            } else {
+                TcpIssue::Retransmission
+            });
+        }
+        if end != sequence.seq {
+            side.next_seq = Some(match side.next_seq {
+                Some(next) if end.wrapping_sub(next) >= 1 << 31 => next,
+                _ => end,
+            });
+            side.last_sent = Some(packet.captured_at);
+        }
+
+        if issue.is_none() && sequence.window == 0 && !resets {
+            issue = Some(TcpIssue::ZeroWindow);
+        }
+        let ack = flags.ack.then_some((sequence.ack, sequence.window));
+        if len == 0 && !resets && ack.is_some() && ack == side.last_ack {
+            side.duplicate_acks += 1;
+            issue = issue.or(Some(TcpIssue::DuplicateAck));
+        } else {
+            side.last_ack = ack;
+            side.duplicate_acks = 0;
+        }
+
+        if let Some(issue) = issue {
+            self.issues.insert(index, issue);
+            *self.counts.entry(issue).or_default() += 1;
+        }
+    }
+
+    pub fn issue(&self, index: usize) -> Option<TcpIssue> {
+        self.issues.get(&index).copied()
+    }
+
+    /// Drops the sequence state of connections `flows` has ended, once it
+    /// has ended more since the last call.
+    pub fn prune(&mut self, flows: &FlowTracker) {
+        if flows.ended_total() == self.pruned_at {
+            return;
+        }
+        self.pruned_at = flows.ended_total();
+        self.sides.retain(|(key, _), _| flows.is_active(key));
+    }
+
+    /// Forgets the tags of packets before `start`; counts stay.
+    pub fn evict(&mut self, start: usize) {
+        self.issues.retain(|index, _| *index >= start);
+    }
+
+    pub fn clear(&mut self) {
+        self.sides.clear();
+        self.issues.clear();
+        self.counts.clear();
+        self.pruned_at = 0;
+    }
+}
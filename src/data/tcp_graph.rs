@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use super::tcp_ladder::{Ladder, Note, Rung};
+
+/// A pause in sending this long, with data still unacknowledged, counts as
+/// a stall.
+const STALL: Duration = Duration::from_millis(200);
+
+/// Intervals the throughput graph divides the connection into.
+const THROUGHPUT_BUCKETS: usize = 60;
+
+/// The points of a tcptrace-style time-sequence graph and of a throughput
+/// graph for one direction of a connection. Times are in seconds since
+/// the connection's first segment.
+#[derive(Debug, Clone, Default)]
+pub struct TcpGraph {
+    pub duration: f64,
+    pub segments: Vec<(f64, f64)>, // End of each new segment's data
+    pub retransmissions: Vec<(f64, f64)>, // End of data sent again
+    pub stalls: Vec<(f64, f64)>,   // Segments that ended a stall
+    pub acks: Vec<(f64, f64)>,     // Acknowledged so far by the other side
+    pub throughput: Vec<(f64, f64)>, // Bytes per second over each interval
+    pub bytes: usize,
+}
+
+impl TcpGraph {
+    /// The side that sent more payload, which is the one worth graphing.
+    pub fn busier_side(ladder: &Ladder) -> bool {
+        let sent = |from_client: bool| -> usize {
+            ladder
+                .rungs
+                .iter()
+                .filter(|rung| rung.from_client == from_client)
+                .map(|rung| rung.len)
+                .sum()
+        };
+        sent(true) >= sent(false)
+    }
+
+    /// Graphs what the client sent if `from_client`, else the server.
+    pub fn from_ladder(ladder: &Ladder, from_client: bool) -> Self {
+        let mut graph = Self::default();
+        let Some(start) = ladder.rungs.first().map(|rung| rung.captured_at) else {
+            return graph;
+        };
+        let at = |rung: &Rung| {
+            rung.captured_at
+                .duration_since(start)
+                .unwrap_or_default()
+                .as_secs_f64()
+        };
+        graph.duration = ladder.rungs.last().map_or(0.0, at);
+
+        let mut highest = 0u32; // Relative end of the data sent so far
+        let mut acked = 0u32;
+        let mut last_sent: Option<f64> = None;
+        for rung in &ladder.rungs {
+            let time = at(rung);
+            if rung.from_client != from_client {
+                if let Some(ack) = rung.ack {
+                    acked = acked.max(ack);
+                    graph.acks.push((time, ack as f64));
+                }
+                continue;
+            }
+            if rung.len == 0 {
+                continue;
+            }
+            let end = rung.seq.wrapping_add(rung.len as u32);
+            let point = (time, end as f64);
+            graph.bytes += rung.len;
+            if rung.note == Some(Note::Retransmission) {
+                graph.retransmissions.push(point);
+            } else {
+                graph.segments.push(point);
+            }
+            let stalled = last_sent.is_some_and(|last| time - last >= STALL.as_secs_f64());
+            if stalled && acked < highest {
+                graph.stalls.push(point);
+            }
+            highest = highest.max(end);
+            last_sent = Some(time);
+        }
+
+        let width = graph.duration.max(f64::EPSILON) / THROUGHPUT_BUCKETS as f64;
+        let mut buckets = [0usize; THROUGHPUT_BUCKETS];
+        for rung in &ladder.rungs {
+            if rung.from_client == from_client && rung.len > 0 {
+                let bucket = ((at(rung) / width) as usize).min(THROUGHPUT_BUCKETS - 1);
+                buckets[bucket] += rung.len;
+            }
+        }
+        graph.throughput = buckets
+            .iter()
+            .enumerate()
+            .map(|(i, &bytes)| ((i as f64 + 0.5) * width, bytes as f64 / width))
+            .collect();
+        graph
+    }
+
+    /// The highest sequence number on the graph.
+    pub fn max_seq(&self) -> f64 {
+        self.segments
+            .iter()
+            .chain(&self.retransmissions)
+            .chain(&self.acks)
+            .map(|&(_, seq)| seq)
+            .fold(0.0, f64::max)
+    }
+
+    pub fn max_throughput(&self) -> f64 {
+        self.throughput
+            .iter()
+            .map(|&(_, rate)| rate)
+            .fold(0.0, f64::max)
+    }
+}
@@ -0,0 +1,155 @@
+use std::time::SystemTime;
+
+use super::{
+    flow::{Endpoint, FlowKey},
+    packet::{PacketInfo, TcpFlags, Transport},
+};
+
+/// Why a segment stands out in the ladder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Note {
+    Retransmission,
+    DuplicateAck(usize), // How many times the same ACK was repeated so far
+}
+
+impl Note {
+    pub fn label(self) -> String {
+        match self {
+            Note::Retransmission => "retransmission".to_string(),
+            Note::DuplicateAck(count) => format!("dup ACK #{count}"),
+        }
+    }
+}
+
+/// One segment of the ladder. Sequence and acknowledgment numbers are
+/// relative to the initial sequence number of their direction.
+#[derive(Debug, Clone)]
+pub struct Rung {
+    pub id: usize,
+    pub captured_at: SystemTime,
+    pub from_client: bool,
+    pub flags: TcpFlags,
+    pub seq: u32,
+    pub ack: Option<u32>,
+    pub len: usize,
+    pub note: Option<Note>,
+}
+
+impl Rung {
+    pub fn label(&self) -> String {
+        let mut label = format!("{} seq={}", self.flags.names().join(","), self.seq);
+        if let Some(ack) = self.ack {
+            label.push_str(&format!(" ack={ack}"));
+        }
+        label.push_str(&format!(" len={}", self.len));
+        label
+    }
+}
+
+/// Per-direction sequence state while building the ladder.
+#[derive(Default)]
+struct Side {
+    isn: Option<u32>,
+    next_seq: Option<u32>, // Relative end of the highest segment sent
+    last_ack: Option<(u32, u16)>,
+    repeats: usize,
+}
+
+/// The segments of one TCP connection in time order, as a sequence/ACK
+/// ladder between the client and the server.
+#[derive(Debug, Clone)]
+pub struct Ladder {
+    pub key: FlowKey,
+    pub client: Endpoint,
+    pub server: Endpoint,
+    pub rungs: Vec<Rung>,
+}
+
+impl Ladder {
+    /// Builds the ladder of `key` from `packets`. The client is the side
+    /// that sent the first bare SYN, or else the sender of the first segment.
+    pub fn from_packets(key: FlowKey, packets: &[PacketInfo]) -> Self {
+        let mut segments: Vec<&PacketInfo> = packets
+            .iter()
+            .filter(|packet| packet.transport == Some(Transport::Tcp) && key.contains(packet))
+            .collect();
+        segments.sort_by_key(|packet| packet.captured_at);
+        let is_from_a = |packet: &PacketInfo| {
+            packet.src_addr.as_ref() == Some(&key.a.addr) && packet.src_port == key.a.port
+        };
+        let client_is_a = segments
+            .iter()
+            .find(|packet| {
+                packet
+                    .tcp_flags
+                    .is_some_and(|flags| flags.syn && !flags.ack)
+            })
+            .or(segments.first())
+            .is_none_or(|packet| is_from_a(packet));
+        let (client, server) = if client_is_a {
+            (key.a.clone(), key.b.clone())
+        } else {
+            (key.b.clone(), key.a.clone())
+        };
+
+        let mut sides = [Side::default(), Side::default()]; // Client, server
+        for packet in &segments {
+            if let Some(sequence) = packet.tcp_sequence {
+                let this = usize::from(is_from_a(packet) != client_is_a);
+                sides[this].isn.get_or_insert(sequence.seq);
+            }
+        }
+        let mut rungs = Vec::with_capacity(segments.len());
+        for packet in segments {
+            let (Some(flags), Some(sequence)) = (packet.tcp_flags, packet.tcp_sequence) else {
+                continue;
+            };
+            let from_client = is_from_a(packet) == client_is_a;
+            let (this, other) = if from_client { (0, 1) } else { (1, 0) };
+            let seq = sequence.seq.wrapping_sub(sides[this].isn.unwrap_or(0));
+            let ack = flags
+                .ack
+                .then(|| sequence.ack.wrapping_sub(sides[other].isn.unwrap_or(0)));
+            let len = packet.payload().len();
+            // SYN and FIN take up one sequence number each.
+            let end = seq
+                .wrapping_add(len as u32)
+                .wrapping_add(flags.syn as u32 + flags.fin as u32);
+
+            let side = &mut sides[this];
+            let mut note = None;
+            if end != seq && side.next_seq.is_some_and(|next| end <= next) {
+                note = Some(Note::Retransmission);
+            }
+            side.next_seq = Some(side.next_seq.map_or(end, |next| next.max(end)));
+            let pure_ack = len == 0 && !flags.syn && !flags.fin && !flags.rst;
+            match ack {
+                Some(ack) if pure_ack && side.last_ack == Some((ack, sequence.window)) => {
+                    side.repeats += 1;
+                    note = Some(Note::DuplicateAck(side.repeats));
+                }
+                _ => {
+                    side.last_ack = ack.map(|ack| (ack, sequence.window));
+                    side.repeats = 0;
+                }
+            }
+
+            rungs.push(Rung {
+                id: packet.id,
+                captured_at: packet.captured_at,
+                from_client,
+                flags,
+                seq,
+                ack,
+                len,
+                note,
+            });
+        }
+        Self {
+            key,
+            client,
+            server,
+            rungs,
+        }
+    }
+}
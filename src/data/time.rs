@@ -0,0 +1,143 @@
+use std::{
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+
+use super::packet::PacketInfo;
+
+/// Correction for the clock of the device a capture was taken on, like
+/// editcap's -t: a constant offset plus a drift built up since the first
+/// packet.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TimeShift {
+    pub offset: f64, // Seconds added to every timestamp
+    pub drift: f64,  // Parts per million the clock ran fast, negative if slow
+}
+
+/// The time `secs` seconds and `subsec` after the Unix epoch, as capture
+/// headers stamp it. Stamps before the epoch or past what `SystemTime` can
+/// hold only come from corrupt headers and read as the epoch itself.
+pub fn unix_time(secs: impl TryInto<u64>, subsec: Duration) -> SystemTime {
+    secs.try_into()
+        .ok()
+        .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+        .and_then(|at| at.checked_add(subsec))
+        .unwrap_or(UNIX_EPOCH)
+}
+
+/// `at` moved by `secs`, or `None` if `secs` is not finite or the result is
+/// past what `SystemTime` can hold.
+pub fn add_secs(at: SystemTime, secs: f64) -> Option<SystemTime> {
+    let magnitude = Duration::try_from_secs_f64(secs.abs()).ok()?;
+    if secs < 0.0 {
+        at.checked_sub(magnitude)
+    } else {
+        at.checked_add(magnitude)
+    }
+}
+
+impl TimeShift {
+    pub fn is_identity(&self) -> bool {
+        self.offset == 0.0 && self.drift == 0.0
+    }
+
+    /// Corrects `at`, a timestamp of a capture whose first packet was
+    /// stamped `first`.
+    #[cfg_attr(not(feature = "libpcap"), allow(dead_code))]
+    pub fn apply(&self, at: SystemTime, first: SystemTime) -> Result<SystemTime> {
+        let elapsed = at.duration_since(first).unwrap_or_default().as_secs_f64();
+        add_secs(at, self.offset - elapsed * self.drift / 1_000_000.0)
+            .with_context(|| format!("Time shift {self} moves a timestamp out of range"))
+    }
+}
+
+impl fmt::Display for TimeShift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:+.6} s", self.offset)?;
+        if self.drift != 0.0 {
+            write!(f, ", drift {:+} ppm", self.drift)?;
+        }
+        Ok(())
+    }
+}
+
+/// How the time column shows when packets were captured.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TimeFormat {
+    #[default]
+    Relative, // Seconds since the capture started
+    Delta,    // Seconds since the packet captured before
+    Absolute, // Time of day, UTC
+}
+
+impl TimeFormat {
+    pub fn next(self) -> Self {
+        match self {
+            TimeFormat::Relative => TimeFormat::Delta,
+            TimeFormat::Delta => TimeFormat::Absolute,
+            TimeFormat::Absolute => TimeFormat::Relative,
+        }
+    }
+
+    pub fn heading(self) -> &'static str {
+        match self {
+            TimeFormat::Relative => "Timestamp",
+            TimeFormat::Delta => "Delta",
+            TimeFormat::Absolute => "Time (UTC)",
+        }
+    }
+
+    /// The time of `packet`, `previous` being the packet captured before
+    /// it, if any.
+    pub fn format(self, packet: &PacketInfo, previous: Option<&PacketInfo>) -> String {
+        match self {
+            TimeFormat::Relative => format!("{:.6}", packet.timestamp.as_secs_f64()),
+            TimeFormat::Delta => {
+                let delta = previous
+                    .and_then(|previous| {
+                        packet.captured_at.duration_since(previous.captured_at).ok()
+                    })
+                    .unwrap_or_default();
+                format!("{:.6}", delta.as_secs_f64())
+            }
+            TimeFormat::Absolute => {
+                let since = packet
+                    .captured_at
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default();
+                // "2026-01-31 12:34:56.123456 UTC" keeps "12:34:56.123456".
+                format_utc(since.as_secs_f64())[11..26].to_string()
+            }
+        }
+    }
+}
+
+/// Formats seconds since the Unix epoch as a UTC date and time with
+/// microsecond precision.
+pub fn format_utc(unix_secs: f64) -> String {
+    let total_micros = (unix_secs * 1_000_000.0).round() as i64;
+    let secs = total_micros.div_euclid(1_000_000);
+    let micros = total_micros.rem_euclid(1_000_000);
+    let days = secs.div_euclid(86_400);
+    let secs_of_day = secs.rem_euclid(86_400);
+
+    // Civil-from-days, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}.{micros:06} UTC",
+        secs_of_day / 3600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60
+    )
+}
@@ -0,0 +1,160 @@
+use super::layer::Layer;
+
+pub const HTTPS_PORT: u16 = 443;
+pub const ALT_HTTPS_PORT: u16 = 8443;
+
+/// The parts of a ClientHello that identify where a connection is going.
+#[derive(Debug, Clone, Default)]
+pub struct ClientHello {
+    pub server_name: Option<String>,
+    pub alpn: Vec<String>,
+}
+
+/// What a TCP segment carrying TLS records contains.
+#[derive(Debug, Clone)]
+pub struct TlsRecords {
+    pub version: u16,
+    pub contents: Vec<&'static str>,
+    pub client_hello: Option<ClientHello>,
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes([
+        *data.get(offset)?,
+        *data.get(offset + 1)?,
+    ]))
+}
+
+pub fn version_name(version: u16) -> &'static str {
+    match version {
+        0x0300 => "SSL 3.0",
+        0x0301 => "TLS 1.0",
+        0x0302 => "TLS 1.1",
+        0x0303 => "TLS 1.2",
+        0x0304 => "TLS 1.3",
+        _ => "Unknown",
+    }
+}
+
+fn handshake_name(kind: u8) -> &'static str {
+    match kind {
+        1 => "Client Hello",
+        2 => "Server Hello",
+        4 => "New Session Ticket",
+        8 => "Encrypted Extensions",
+        11 => "Certificate",
+        12 => "Server Key Exchange",
+        13 => "Certificate Request",
+        14 => "Server Hello Done",
+        15 => "Certificate Verify",
+        16 => "Client Key Exchange",
+        20 => "Finished",
+        _ => "Handshake",
+    }
+}
+
+fn parse_client_hello(body: &[u8]) -> Option<ClientHello> {
+    // Version and random, then the variable-length session ID, cipher
+    // suites and compression methods.
+    let mut offset = 2 + 32;
+    offset += 1 + *body.get(offset)? as usize;
+    offset += 2 + read_u16(body, offset)? as usize;
+    offset += 1 + *body.get(offset)? as usize;
+
+    let mut hello = ClientHello::default();
+    let extensions_end = (offset + 2 + read_u16(body, offset)? as usize).min(body.len());
+    offset += 2;
+    while offset + 4 <= extensions_end {
+        let kind = read_u16(body, offset)?;
+        let len = read_u16(body, offset + 2)? as usize;
+        let data = body.get(offset + 4..offset + 4 + len)?;
+        offset += 4 + len;
+        match kind {
+            0 => {
+                // server_name: list length, name type, name length, name
+                let name_len = read_u16(data, 3)? as usize;
+                let name = data.get(5..5 + name_len)?;
+                hello.server_name = Some(String::from_utf8_lossy(name).into_owned());
+            }
+            16 => {
+                let mut rest = data.get(2..)?;
+                while let Some((&len, tail)) = rest.split_first() {
+                    let protocol = tail.get(..len as usize)?;
+                    hello
+                        .alpn
+                        .push(String::from_utf8_lossy(protocol).into_owned());
+                    rest = &tail[len as usize..];
+                }
+            }
+            _ => {}
+        }
+    }
+    Some(hello)
+}
+
+/// Walks the TLS records at the start of a TCP payload. Records that run
+/// past the end of the segment are still named by their header.
+pub fn parse(payload: &[u8]) -> Option<TlsRecords> {
+    let mut records = TlsRecords {
+        version: read_u16(payload, 1)?,
+        contents: Vec::new(),
+        client_hello: None,
+    };
+    if !(0x0300..=0x0304).contains(&records.version) {
+        return None;
+    }
+
+    let mut offset = 0;
+    while offset + 5 <= payload.len() {
+        let content_type = payload[offset];
+        if read_u16(payload, offset + 1)? >> 8 != 0x03 {
+            break;
+        }
+        let len = read_u16(payload, offset + 3)? as usize;
+        let body = &payload[offset + 5..(offset + 5 + len).min(payload.len())];
+        let content = match content_type {
+            20 => "Change Cipher Spec",
+            21 => "Alert",
+            22 => match body.first() {
+                Some(1) => {
+                    records.client_hello = body.get(4..).and_then(parse_client_hello);
+                    handshake_name(1)
+                }
+                Some(&kind) if records.contents.is_empty() => handshake_name(kind),
+                // Later handshake messages of TLS 1.3 are encrypted.
+                _ => "Handshake",
+            },
+            23 => "Application Data",
+            _ if records.contents.is_empty() => return None,
+            _ => break,
+        };
+        if !records.contents.contains(&content) {
+            records.contents.push(content);
+        }
+        offset += 5 + len;
+    }
+    Some(records)
+}
+
+/// Builds a layer describing `records` under the given protocol name.
+pub fn layer(name: &'static str, records: &TlsRecords) -> Layer {
+    let mut summary = records.contents.join(", ");
+    if let Some(ref hello) = records.client_hello
+        && let Some(ref server_name) = hello.server_name
+    {
+        summary.push_str(&format!(" (SNI {server_name})"));
+    }
+
+    let mut layer = Layer::new(name, summary);
+    layer.detail("Record Version", version_name(records.version));
+    layer.field("Content", records.contents.join(", "));
+    if let Some(ref hello) = records.client_hello {
+        if let Some(ref server_name) = hello.server_name {
+            layer.field("Server Name", server_name.clone());
+        }
+        if !hello.alpn.is_empty() {
+            layer.field("ALPN", hello.alpn.join(", "));
+        }
+    }
+    layer
+}
@@ -0,0 +1,111 @@
+use std::{collections::HashMap, net::IpAddr, time::Duration};
+
+use super::packet::{PacketInfo, Transport};
+
+/// Talkers of each kind listed.
+pub const LIMIT: usize = 20;
+
+/// Windows the top talkers can be taken over, shortest first.
+pub const WINDOWS: [Duration; 4] = [
+    Duration::from_secs(10),
+    Duration::from_secs(60),
+    Duration::from_secs(300),
+    Duration::from_secs(900),
+];
+
+/// Bytes and packets one host or port accounted for in the window.
+#[derive(Debug, Clone)]
+pub struct Talker {
+    pub label: String,
+    pub bytes: usize,
+    pub packets: usize,
+}
+
+/// Who moved the most bytes lately: the busiest senders, receivers and
+/// ports over the last `window` of capture time, counted back from the
+/// newest packet so files read like live captures.
+#[derive(Debug, Clone, Default)]
+pub struct TopTalkers {
+    pub bytes: usize, // All bytes in the window, for shares
+    pub sources: Vec<Talker>,
+    pub destinations: Vec<Talker>,
+    pub ports: Vec<Talker>,
+}
+
+/// The service side of a transport port pair, taken to be the lower port
+/// since clients pick theirs from the ephemeral range.
+fn port_label(packet: &PacketInfo) -> Option<String> {
+    let transport = match packet.transport? {
+        Transport::Tcp => "tcp",
+        Transport::Udp => "udp",
+        Transport::Icmpv4 | Transport::Icmpv6 => return None,
+    };
+    let port = packet.src_port?.min(packet.dst_port?);
+    Some(format!("{port}/{transport}"))
+}
+
+fn address_label(addr: &Result<IpAddr, String>) -> String {
+    match addr {
+        Ok(ip) => ip.to_string(),
+        Err(mac) => mac.clone(),
+    }
+}
+
+fn count(talkers: &mut HashMap<String, (usize, usize)>, label: String, bytes: usize) {
+    let (total, packets) = talkers.entry(label).or_default();
+    *total += bytes;
+    *packets += 1;
+}
+
+/// The `limit` largest by bytes, ties broken by label.
+fn top(talkers: HashMap<String, (usize, usize)>, limit: usize) -> Vec<Talker> {
+    let mut talkers: Vec<Talker> = talkers
+        .into_iter()
+        .map(|(label, (bytes, packets))| Talker {
+            label,
+            bytes,
+            packets,
+        })
+        .collect();
+    talkers.sort_by(|x, y| y.bytes.cmp(&x.bytes).then_with(|| x.label.cmp(&y.label)));
+    talkers.truncate(limit);
+    talkers
+}
+
+impl TopTalkers {
+    /// The `limit` busiest of each kind among `packets`, which are in
+    /// capture order.
+    pub fn from_packets(packets: &[&PacketInfo], window: Duration, limit: usize) -> Self {
+        let mut sources = HashMap::new();
+        let mut destinations = HashMap::new();
+        let mut ports = HashMap::new();
+        let mut bytes = 0;
+        let newest = packets.last().map(|packet| packet.captured_at);
+        let recent = packets.iter().rev().take_while(|packet| {
+            newest.is_some_and(|newest| {
+                newest
+                    .duration_since(packet.captured_at)
+                    .unwrap_or_default()
+                    <= window
+            })
+        });
+        for packet in recent {
+            bytes += packet.length;
+            if let Some(ref addr) = packet.src_addr {
+                count(&mut sources, address_label(addr), packet.length);
+            }
+            if let Some(ref addr) = packet.dst_addr {
+                count(&mut destinations, address_label(addr), packet.length);
+            }
+            if let Some(label) = port_label(packet) {
+                count(&mut ports, label, packet.length);
+            }
+        }
+        Self {
+            bytes,
+            sources: top(sources, limit),
+            destinations: top(destinations, limit),
+            ports: top(ports, limit),
+        }
+    }
+}
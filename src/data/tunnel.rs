@@ -0,0 +1,136 @@
+use super::layer::Layer;
+
+pub const VXLAN_PORT: u16 = 4789;
+pub const GENEVE_PORT: u16 = 6081;
+/// IP protocol number of GRE.
+pub const GRE_PROTOCOL: u8 = 47;
+
+/// Names of the layers produced by this module.
+pub const NAMES: [&str; 3] = ["GRE", "VXLAN", "GENEVE"];
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const ETHERTYPE_TEB: u16 = 0x6558; // Transparent Ethernet bridging
+
+/// The encapsulated packet carried by a tunnel.
+pub enum Inner<'a> {
+    Ethernet(&'a [u8]),
+    Ip(&'a [u8]),
+}
+
+pub struct Tunnel<'a> {
+    pub layer: Layer,
+    pub inner: Inner<'a>,
+}
+
+fn inner_for(protocol_type: u16, data: &[u8]) -> Option<Inner<'_>> {
+    match protocol_type {
+        ETHERTYPE_TEB => Some(Inner::Ethernet(data)),
+        ETHERTYPE_IPV4 | ETHERTYPE_IPV6 => Some(Inner::Ip(data)),
+        _ => None,
+    }
+}
+
+fn protocol_type_name(protocol_type: u16) -> String {
+    let name = match protocol_type {
+        ETHERTYPE_TEB => "Transparent Ethernet Bridging",
+        ETHERTYPE_IPV4 => "IPv4",
+        ETHERTYPE_IPV6 => "IPv6",
+        _ => "Unknown",
+    };
+    format!("{name} (0x{protocol_type:04x})")
+}
+
+/// Decodes a GRE (RFC 2784/2890) header carried directly over IP.
+pub fn decode_gre(payload: &[u8]) -> Option<Tunnel<'_>> {
+    let flags = u16::from_be_bytes([*payload.first()?, *payload.get(1)?]);
+    let protocol_type = u16::from_be_bytes([*payload.get(2)?, *payload.get(3)?]);
+    // Version 1 is the PPTP variant, which carries PPP rather than packets.
+    if flags & 0x0007 != 0 {
+        return None;
+    }
+    let has_checksum = flags & 0x8000 != 0 || flags & 0x4000 != 0;
+    let has_key = flags & 0x2000 != 0;
+    let has_sequence = flags & 0x1000 != 0;
+
+    let mut offset = 4;
+    let mut read_word = || {
+        let word = payload.get(offset..offset + 4)?;
+        offset += 4;
+        Some(u32::from_be_bytes([word[0], word[1], word[2], word[3]]))
+    };
+    let checksum = if has_checksum {
+        Some(read_word()? >> 16)
+    } else {
+        None
+    };
+    let key = if has_key { Some(read_word()?) } else { None };
+    let sequence = if has_sequence {
+        Some(read_word()?)
+    } else {
+        None
+    };
+    let inner = inner_for(protocol_type, payload.get(offset..)?)?;
+
+    let summary = match key {
+        Some(key) => format!("Key {key}, {}", protocol_type_name(protocol_type)),
+        None => protocol_type_name(protocol_type),
+    };
+    let mut layer = Layer::new("GRE", summary);
+    layer.field("Protocol Type", protocol_type_name(protocol_type));
+    if let Some(checksum) = checksum {
+        layer.detail("Checksum", format!("0x{checksum:04x}"));
+    }
+    if let Some(key) = key {
+        layer.field("Key", key.to_string());
+    }
+    if let Some(sequence) = sequence {
+        layer.detail("Sequence Number", sequence.to_string());
+    }
+    Some(Tunnel { layer, inner })
+}
+
+/// Decodes a VXLAN (RFC 7348) header; the inner packet is always Ethernet.
+pub fn decode_vxlan(payload: &[u8]) -> Option<Tunnel<'_>> {
+    let header = payload.get(..8)?;
+    // The I flag must be set for the VNI to be valid.
+    if header[0] & 0x08 == 0 {
+        return None;
+    }
+    let vni = u32::from_be_bytes([0, header[4], header[5], header[6]]);
+
+    let mut layer = Layer::new("VXLAN", format!("VNI {vni}"));
+    layer.detail("Flags", format!("0x{:02x}", header[0]));
+    layer.field("VNI", vni.to_string());
+    Some(Tunnel {
+        layer,
+        inner: Inner::Ethernet(&payload[8..]),
+    })
+}
+
+/// Decodes a GENEVE (RFC 8926) header, skipping its variable-length options.
+pub fn decode_geneve(payload: &[u8]) -> Option<Tunnel<'_>> {
+    let header = payload.get(..8)?;
+    let version = header[0] >> 6;
+    if version != 0 {
+        return None;
+    }
+    let options_len = (header[0] & 0x3f) as usize * 4;
+    let oam = header[1] & 0x80 != 0;
+    let critical = header[1] & 0x40 != 0;
+    let protocol_type = u16::from_be_bytes([header[2], header[3]]);
+    let vni = u32::from_be_bytes([0, header[4], header[5], header[6]]);
+    let inner = inner_for(protocol_type, payload.get(8 + options_len..)?)?;
+
+    let mut layer = Layer::new("GENEVE", format!("VNI {vni}"));
+    layer.field("Protocol Type", protocol_type_name(protocol_type));
+    layer.field("VNI", vni.to_string());
+    layer.detail("Options Length", format!("{options_len} bytes"));
+    if oam {
+        layer.field("OAM", "set");
+    }
+    if critical {
+        layer.field("Critical Options", "present");
+    }
+    Some(Tunnel { layer, inner })
+}
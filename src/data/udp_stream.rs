@@ -0,0 +1,58 @@
+use std::{ops::Range, sync::Arc, time::SystemTime};
+
+use super::{
+    flow::FlowKey,
+    packet::{PacketInfo, Transport},
+};
+
+/// One datagram of a UDP pseudo stream. The payload is shared with the
+/// packet it came from.
+#[derive(Debug, Clone)]
+pub struct Datagram {
+    pub id: usize,
+    pub captured_at: SystemTime,
+    pub from_a: bool,
+    data: Arc<[u8]>,
+    payload: Range<usize>,
+}
+
+impl Datagram {
+    pub fn payload(&self) -> &[u8] {
+        &self.data[self.payload.clone()]
+    }
+}
+
+/// The payloads exchanged between two UDP ports in time order, the closest
+/// UDP has to a stream.
+#[derive(Debug, Clone)]
+pub struct UdpStream {
+    pub key: FlowKey,
+    pub datagrams: Vec<Datagram>,
+}
+
+impl UdpStream {
+    pub fn from_packets(key: FlowKey, packets: &[PacketInfo]) -> Self {
+        let mut datagrams: Vec<Datagram> = packets
+            .iter()
+            .filter(|packet| packet.transport == Some(Transport::Udp) && key.contains(packet))
+            .map(|packet| Datagram {
+                id: packet.id,
+                captured_at: packet.captured_at,
+                from_a: packet.src_addr.as_ref() == Some(&key.a.addr)
+                    && packet.src_port == key.a.port,
+                data: packet.data.clone(),
+                payload: packet.payload.clone(),
+            })
+            .collect();
+        datagrams.sort_by_key(|datagram| datagram.captured_at);
+        Self { key, datagrams }
+    }
+
+    pub fn bytes(&self, from_a: bool) -> usize {
+        self.datagrams
+            .iter()
+            .filter(|datagram| datagram.from_a == from_a)
+            .map(|datagram| datagram.payload().len())
+            .sum()
+    }
+}
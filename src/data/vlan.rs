@@ -0,0 +1,55 @@
+use std::collections::BTreeSet;
+
+use super::packet::PacketInfo;
+
+/// The VLAN the statistics pages are broken down to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum VlanGroup {
+    #[default]
+    All,
+    Untagged,
+    Tagged(u16),
+}
+
+impl VlanGroup {
+    pub fn matches(self, packet: &PacketInfo) -> bool {
+        match self {
+            VlanGroup::All => true,
+            VlanGroup::Untagged => packet.vlan.is_none(),
+            VlanGroup::Tagged(id) => packet.vlan == Some(id),
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            VlanGroup::All => "All".to_string(),
+            VlanGroup::Untagged => "Untagged".to_string(),
+            VlanGroup::Tagged(id) => format!("VLAN {id}"),
+        }
+    }
+}
+
+/// The groups a capture can be broken down into, `All` first. A capture
+/// without VLAN tags, or on a single VLAN, only has `All`.
+pub fn groups(packets: &[PacketInfo]) -> Vec<VlanGroup> {
+    let seen: BTreeSet<VlanGroup> = packets
+        .iter()
+        .map(|packet| match packet.vlan {
+            Some(id) => VlanGroup::Tagged(id),
+            None => VlanGroup::Untagged,
+        })
+        .collect();
+    let mut groups = vec![VlanGroup::All];
+    if seen.len() > 1 {
+        groups.extend(seen);
+    }
+    groups
+}
+
+/// The packets in `group`, borrowed from `packets`.
+pub fn select(packets: &[PacketInfo], group: VlanGroup) -> Vec<&PacketInfo> {
+    packets
+        .iter()
+        .filter(|packet| group.matches(packet))
+        .collect()
+}
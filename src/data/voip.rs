@@ -0,0 +1,148 @@
+use std::{collections::HashMap, time::SystemTime};
+
+use super::{
+    flow::Endpoint,
+    packet::PacketInfo,
+    rtp::{self, payload_type_info},
+    sip,
+};
+
+#[derive(Debug, Clone)]
+pub struct RtpStream {
+    pub ssrc: u32,
+    pub src: Endpoint,
+    pub dst: Endpoint,
+    pub payload_type: u8,
+    pub packets: usize,
+    pub lost: i64,
+    pub jitter_ms: f64,
+    first_seq: u32,
+    highest_seq: u32, // Extended with the wrap-around count
+    last_transit: Option<f64>,
+    jitter: f64, // In RTP timestamp units, per RFC 3550 §6.4.1
+}
+
+impl RtpStream {
+    fn update(&mut self, header: &rtp::RtpHeader, arrival: SystemTime) {
+        self.packets += 1;
+
+        let cycles = self.highest_seq & !0xffff;
+        let mut seq = cycles | header.sequence as u32;
+        // A small sequence number right after a large one is a wrap-around.
+        if seq + 0x8000 < self.highest_seq {
+            seq += 0x1_0000;
+        }
+        self.highest_seq = self.highest_seq.max(seq);
+        let expected = (self.highest_seq - self.first_seq + 1) as i64;
+        self.lost = expected - self.packets as i64;
+
+        let (_, clock_rate) = payload_type_info(header.payload_type);
+        let arrival = arrival
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64()
+            * clock_rate as f64;
+        let transit = arrival - header.timestamp as f64;
+        if let Some(last) = self.last_transit {
+            let d = (transit - last).abs();
+            self.jitter += (d - self.jitter) / 16.0;
+            self.jitter_ms = self.jitter / clock_rate as f64 * 1000.0;
+        }
+        self.last_transit = Some(transit);
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SipCall {
+    pub call_id: String,
+    pub from: String,
+    pub to: String,
+    pub last_method: String,
+    pub last_status: Option<u16>,
+    pub messages: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VoipStats {
+    pub streams: Vec<RtpStream>,
+    pub calls: Vec<SipCall>,
+    stream_index: HashMap<u32, usize>,
+    call_index: HashMap<String, usize>,
+}
+
+impl VoipStats {
+    pub fn from_packets(packets: &[&PacketInfo]) -> Self {
+        let mut stats = Self::default();
+        for packet in packets {
+            stats.add(packet);
+        }
+        stats
+    }
+
+    pub fn add(&mut self, packet: &PacketInfo) {
+        if packet.has_layer("RTP") {
+            self.add_rtp(packet);
+        } else if packet.has_layer("SIP") {
+            self.add_sip(packet);
+        }
+    }
+
+    fn add_rtp(&mut self, packet: &PacketInfo) {
+        let (Some(header), Some(src), Some(dst)) = (
+            rtp::parse(packet.payload()),
+            packet.src_addr.clone(),
+            packet.dst_addr.clone(),
+        ) else {
+            return;
+        };
+        let index = *self.stream_index.entry(header.ssrc).or_insert_with(|| {
+            self.streams.push(RtpStream {
+                ssrc: header.ssrc,
+                src: Endpoint {
+                    addr: src,
+                    port: packet.src_port,
+                },
+                dst: Endpoint {
+                    addr: dst,
+                    port: packet.dst_port,
+                },
+                payload_type: header.payload_type,
+                packets: 0,
+                lost: 0,
+                jitter_ms: 0.0,
+                first_seq: header.sequence as u32,
+                highest_seq: header.sequence as u32,
+                last_transit: None,
+                jitter: 0.0,
+            });
+            self.streams.len() - 1
+        });
+        self.streams[index].update(&header, packet.captured_at);
+    }
+
+    fn add_sip(&mut self, packet: &PacketInfo) {
+        let Some(message) = sip::parse(packet.payload()) else {
+            return;
+        };
+        let Some(call_id) = message.call_id else {
+            return;
+        };
+        let index = *self.call_index.entry(call_id.clone()).or_insert_with(|| {
+            self.calls.push(SipCall {
+                call_id,
+                from: message.from.unwrap_or_default(),
+                to: message.to.unwrap_or_default(),
+                ..Default::default()
+            });
+            self.calls.len() - 1
+        });
+        let call = &mut self.calls[index];
+        call.messages += 1;
+        if let Some(method) = message.method {
+            call.last_method = method;
+        }
+        if let Some((code, _)) = message.status {
+            call.last_status = Some(code);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use etherparse::IpAuthHeaderSlice;
+
+use super::layer::Layer;
+
+/// IP protocol number of IPsec ESP.
+pub const ESP_PROTOCOL: u8 = 50;
+/// UDP port of IPsec NAT traversal, carrying ESP and IKE.
+pub const NAT_T_PORT: u16 = 4500;
+/// Layers that only protect what was decoded after them.
+pub const NAMES: [&str; 1] = ["AH"];
+
+const WIREGUARD_INITIATION: u8 = 1;
+const WIREGUARD_RESPONSE: u8 = 2;
+const WIREGUARD_COOKIE_REPLY: u8 = 3;
+const WIREGUARD_TRANSPORT: u8 = 4;
+/// Authentication tag ending every transport data message.
+const WIREGUARD_TAG_LEN: usize = 16;
+
+fn index(bytes: &[u8]) -> Option<String> {
+    let index = u32::from_le_bytes(bytes.get(..4)?.try_into().ok()?);
+    Some(format!("0x{index:08x}"))
+}
+
+/// Decodes an ESP header, the SPI and sequence number before the
+/// encrypted payload.
+pub fn decode_esp(payload: &[u8]) -> Option<Layer> {
+    let spi = u32::from_be_bytes(payload.get(..4)?.try_into().ok()?);
+    let sequence = u32::from_be_bytes(payload.get(4..8)?.try_into().ok()?);
+    let mut layer = Layer::new("ESP", format!("SPI 0x{spi:08x}, seq {sequence}"));
+    layer.field_at(0..4, "SPI", format!("0x{spi:08x}"));
+    layer.field_at(4..8, "Sequence", sequence.to_string());
+    let encrypted = 8..payload.len();
+    layer.field_at(
+        encrypted.clone(),
+        "Encrypted Length",
+        format!("{} bytes", encrypted.len()),
+    );
+    Some(layer)
+}
+
+/// Describes an authentication header protecting the rest of the packet.
+pub fn decode_ah(auth: &IpAuthHeaderSlice) -> Layer {
+    let spi = auth.spi();
+    let sequence = auth.sequence_number();
+    let mut layer = Layer::new("AH", format!("SPI 0x{spi:08x}, seq {sequence}"));
+    layer.field_at(4..8, "SPI", format!("0x{spi:08x}"));
+    layer.field_at(8..12, "Sequence", sequence.to_string());
+    layer.detail("Next Header", auth.next_header().0.to_string());
+    layer
+}
+
+/// Decodes what goes over the NAT traversal port: a single 0xff byte to
+/// keep the mapping open, IKE after a zero marker, or otherwise ESP.
+pub fn decode_nat_t(payload: &[u8]) -> Option<Layer> {
+    match payload {
+        [0xff] => {
+            let mut layer = Layer::new("ESP", "NAT-keepalive".to_string());
+            layer.field("Message", "NAT-keepalive");
+            Some(layer)
+        }
+        [0, 0, 0, 0, ..] => None,
+        _ => decode_esp(payload),
+    }
+}
+
+/// Recognizes a WireGuard message by its type, three reserved zero bytes
+/// and the fixed length of its type, as WireGuard has no usual port.
+pub fn decode_wireguard(payload: &[u8]) -> Option<Layer> {
+    let (&message_type, rest) = payload.split_first()?;
+    if rest.get(..3)? != [0, 0, 0] {
+        return None;
+    }
+    let mut layer = match message_type {
+        WIREGUARD_INITIATION if payload.len() == 148 => {
+            let sender = index(&payload[4..])?;
+            let mut layer = Layer::new(
+                "WireGuard",
+                format!("Handshake Initiation, sender {sender}"),
+            );
+            layer.field_at(0..1, "Message Type", "Handshake Initiation");
+            layer.field_at(4..8, "Sender Index", sender);
+            layer
+        }
+        WIREGUARD_RESPONSE if payload.len() == 92 => {
+            let sender = index(&payload[4..])?;
+            let receiver = index(&payload[8..])?;
+            let mut layer = Layer::new(
+                "WireGuard",
+                format!("Handshake Response, sender {sender}, receiver {receiver}"),
+            );
+            layer.field_at(0..1, "Message Type", "Handshake Response");
+            layer.field_at(4..8, "Sender Index", sender);
+            layer.field_at(8..12, "Receiver Index", receiver);
+            layer
+        }
+        WIREGUARD_COOKIE_REPLY if payload.len() == 64 => {
+            let receiver = index(&payload[4..])?;
+            let mut layer = Layer::new("WireGuard", format!("Cookie Reply, receiver {receiver}"));
+            layer.field_at(0..1, "Message Type", "Cookie Reply");
+            layer.field_at(4..8, "Receiver Index", receiver);
+            layer
+        }
+        // Padded to 16 bytes, so only empty keepalives are just the header
+        // and tag.
+        WIREGUARD_TRANSPORT
+            if payload.len() >= 16 + WIREGUARD_TAG_LEN
+                && (payload.len() - 16 - WIREGUARD_TAG_LEN).is_multiple_of(16) =>
+        {
+            let receiver = index(&payload[4..])?;
+            let counter = u64::from_le_bytes(payload.get(8..16)?.try_into().ok()?);
+            let data_len = payload.len() - 16 - WIREGUARD_TAG_LEN;
+            let kind = if data_len == 0 {
+                "Keepalive"
+            } else {
+                "Transport Data"
+            };
+            let mut layer = Layer::new(
+                "WireGuard",
+                format!("{kind}, receiver {receiver}, counter {counter}"),
+            );
+            layer.field_at(0..1, "Message Type", kind);
+            layer.field_at(4..8, "Receiver Index", receiver);
+            layer.field_at(8..16, "Counter", counter.to_string());
+            layer.field_at(
+                16..payload.len(),
+                "Encrypted Length",
+                format!("{data_len} bytes"),
+            );
+            layer
+        }
+        _ => return None,
+    };
+    layer.detail("Length", format!("{} bytes", payload.len()));
+    Some(layer)
+}
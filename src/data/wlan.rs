@@ -0,0 +1,414 @@
+use super::{layer::Layer, link::Framing, packet::format_mac};
+
+/// Link type of bare 802.11 frames.
+pub const LINKTYPE_IEEE802_11: u32 = 105;
+/// Link type of 802.11 frames behind a radiotap header, which is how
+/// monitor mode usually delivers them.
+pub const LINKTYPE_IEEE802_11_RADIOTAP: u32 = 127;
+
+/// Layers describing the radio link rather than what it carries.
+pub const NAMES: [&str; 2] = ["Radiotap", "802.11"];
+
+const TYPE_MANAGEMENT: u8 = 0;
+const TYPE_CONTROL: u8 = 1;
+const TYPE_DATA: u8 = 2;
+
+const FLAG_TO_DS: u8 = 0x01;
+const FLAG_FROM_DS: u8 = 0x02;
+const FLAG_RETRY: u8 = 0x08;
+const FLAG_PROTECTED: u8 = 0x40;
+const FLAG_ORDER: u8 = 0x80;
+
+/// Radiotap flag saying the frame ends with its 4-byte FCS.
+const RADIOTAP_FLAG_FCS: u8 = 0x10;
+
+/// Alignment and size of the radiotap fields up to the antenna noise, by
+/// their bit in the present word: TSFT, flags, rate, channel, FHSS,
+/// signal, noise.
+const RADIOTAP_FIELDS: [(usize, usize); 7] =
+    [(8, 8), (1, 1), (1, 1), (2, 4), (2, 2), (1, 1), (1, 1)];
+
+/// Element IDs in the body of management frames.
+const ELEMENT_SSID: u8 = 0;
+const ELEMENT_DS_PARAMETER: u8 = 3;
+
+/// LLC/SNAP header before the EtherType of a data frame's payload.
+const SNAP_HEADER: [u8; 6] = [0xaa, 0xaa, 0x03, 0x00, 0x00, 0x00];
+
+pub fn is_wireless(link_type: u32) -> bool {
+    matches!(
+        link_type,
+        LINKTYPE_IEEE802_11 | LINKTYPE_IEEE802_11_RADIOTAP
+    )
+}
+
+/// The channel number of a center frequency in the 2.4, 5 or 6 GHz band.
+fn channel(mhz: u16) -> Option<u16> {
+    match mhz {
+        2484 => Some(14),
+        2412..=2472 => Some((mhz - 2407) / 5),
+        5000..=5895 => Some((mhz - 5000) / 5),
+        5955..=7115 => Some((mhz - 5950) / 5),
+        _ => None,
+    }
+}
+
+fn u16_at(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+/// How a frame was received, as its radiotap header tells.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Radio {
+    pub signal: Option<i8>,     // Antenna signal in dBm, the RSSI
+    pub noise: Option<i8>,      // Antenna noise in dBm
+    pub frequency: Option<u16>, // Channel center frequency in MHz
+    pub rate: Option<u8>,       // Legacy data rate in units of 500 kb/s
+    fcs: bool,                  // Whether the frame ends with its FCS
+}
+
+impl Radio {
+    pub fn channel(&self) -> Option<u16> {
+        channel(self.frequency?)
+    }
+
+    /// The data rate, like "54 Mb/s".
+    pub fn rate_text(&self) -> Option<String> {
+        let rate = self.rate?;
+        Some(if rate % 2 == 0 {
+            format!("{} Mb/s", rate / 2)
+        } else {
+            format!("{:.1} Mb/s", f64::from(rate) / 2.0)
+        })
+    }
+}
+
+/// Reads the radiotap header at the start of `data`, returning it with
+/// its length.
+fn read_radiotap(data: &[u8]) -> Option<(Radio, usize)> {
+    if data.first() != Some(&0) {
+        return None;
+    }
+    let len = u16_at(data, 2)? as usize;
+    let header = data.get(..len)?;
+    let present = u32::from_le_bytes(header.get(4..8)?.try_into().ok()?);
+    // Bit 31 says another present word follows.
+    let mut offset = 8;
+    let mut word = present;
+    while word & 1 << 31 != 0 {
+        word = u32::from_le_bytes(header.get(offset..offset + 4)?.try_into().ok()?);
+        offset += 4;
+    }
+
+    let mut radio = Radio::default();
+    for (bit, &(align, size)) in RADIOTAP_FIELDS.iter().enumerate() {
+        if present & 1 << bit == 0 {
+            continue;
+        }
+        offset = offset.next_multiple_of(align);
+        let Some(value) = header.get(offset..offset + size) else {
+            break;
+        };
+        offset += size;
+        match bit {
+            1 => radio.fcs = value[0] & RADIOTAP_FLAG_FCS != 0,
+            2 => radio.rate = Some(value[0]),
+            3 => radio.frequency = Some(u16::from_le_bytes([value[0], value[1]])),
+            5 => radio.signal = Some(value[0] as i8),
+            6 => radio.noise = Some(value[0] as i8),
+            _ => {}
+        }
+    }
+    Some((radio, len))
+}
+
+/// The radiotap header of a `link_type` frame, if it has one.
+pub fn radio(link_type: u32, data: &[u8]) -> Option<Radio> {
+    if link_type != LINKTYPE_IEEE802_11_RADIOTAP {
+        return None;
+    }
+    read_radiotap(data).map(|(radio, _)| radio)
+}
+
+/// Decodes the radiotap header at the start of `data`, returning it with
+/// the 802.11 frame after it, less any trailing FCS.
+fn radiotap(data: &[u8]) -> Option<(Layer, &[u8])> {
+    let (radio, len) = read_radiotap(data)?;
+    let mut layer = Layer::new("Radiotap", String::new());
+    let mut summary = Vec::new();
+    if let Some(signal) = radio.signal {
+        layer.field("Signal", format!("{signal} dBm"));
+        summary.push(format!("{signal} dBm"));
+    }
+    if let Some(noise) = radio.noise {
+        layer.field("Noise", format!("{noise} dBm"));
+        if let Some(signal) = radio.signal {
+            layer.field(
+                "SNR",
+                format!("{} dB", i16::from(signal) - i16::from(noise)),
+            );
+        }
+    }
+    if let Some(frequency) = radio.frequency {
+        layer.field("Frequency", format!("{frequency} MHz"));
+    }
+    if let Some(channel) = radio.channel() {
+        layer.field("Channel", channel.to_string());
+        summary.push(format!("Ch {channel}"));
+    }
+    if let Some(rate) = radio.rate_text() {
+        layer.field("Data Rate", rate.clone());
+        summary.push(rate);
+    }
+    layer.summary = summary.join(", ");
+    let frame = &data[len..];
+    let frame = if radio.fcs {
+        &frame[..frame.len().saturating_sub(4)]
+    } else {
+        frame
+    };
+    Some((layer, frame))
+}
+
+fn frame_name(kind: u8, subtype: u8) -> &'static str {
+    match (kind, subtype) {
+        (TYPE_MANAGEMENT, 0) => "Association Request",
+        (TYPE_MANAGEMENT, 1) => "Association Response",
+        (TYPE_MANAGEMENT, 2) => "Reassociation Request",
+        (TYPE_MANAGEMENT, 3) => "Reassociation Response",
+        (TYPE_MANAGEMENT, 4) => "Probe Request",
+        (TYPE_MANAGEMENT, 5) => "Probe Response",
+        (TYPE_MANAGEMENT, 8) => "Beacon",
+        (TYPE_MANAGEMENT, 9) => "ATIM",
+        (TYPE_MANAGEMENT, 10) => "Disassociation",
+        (TYPE_MANAGEMENT, 11) => "Authentication",
+        (TYPE_MANAGEMENT, 12) => "Deauthentication",
+        (TYPE_MANAGEMENT, 13) => "Action",
+        (TYPE_MANAGEMENT, 14) => "Action No Ack",
+        (TYPE_CONTROL, 8) => "Block Ack Request",
+        (TYPE_CONTROL, 9) => "Block Ack",
+        (TYPE_CONTROL, 10) => "PS-Poll",
+        (TYPE_CONTROL, 11) => "RTS",
+        (TYPE_CONTROL, 12) => "CTS",
+        (TYPE_CONTROL, 13) => "ACK",
+        (TYPE_CONTROL, 14) => "CF-End",
+        (TYPE_DATA, 0) => "Data",
+        (TYPE_DATA, 4) => "Null",
+        (TYPE_DATA, 8) => "QoS Data",
+        (TYPE_DATA, 12) => "QoS Null",
+        (TYPE_DATA, _) => "Data",
+        (TYPE_MANAGEMENT | TYPE_CONTROL, _) => "Reserved",
+        _ => "Extension",
+    }
+}
+
+/// Why a station was deauthenticated or disassociated, for the common codes.
+fn reason_name(code: u16) -> Option<&'static str> {
+    Some(match code {
+        1 => "Unspecified",
+        2 => "Previous authentication no longer valid",
+        3 => "Station is leaving",
+        4 => "Inactivity",
+        5 => "AP is unable to handle all associated stations",
+        6 => "Class 2 frame from nonauthenticated station",
+        7 => "Class 3 frame from nonassociated station",
+        8 => "Station is leaving the BSS",
+        9 => "Station is not authenticated",
+        15 => "4-way handshake timeout",
+        23 => "IEEE 802.1X authentication failed",
+        _ => return None,
+    })
+}
+
+/// The SSID and channel announced in the elements of a management body.
+fn elements(mut body: &[u8]) -> (Option<String>, Option<u8>) {
+    let (mut ssid, mut channel) = (None, None);
+    while let [id, len, rest @ ..] = body {
+        let Some(value) = rest.get(..*len as usize) else {
+            break;
+        };
+        match *id {
+            ELEMENT_SSID if ssid.is_none() => {
+                ssid = Some(String::from_utf8_lossy(value).into_owned());
+            }
+            ELEMENT_DS_PARAMETER => channel = value.first().copied(),
+            _ => {}
+        }
+        body = &rest[*len as usize..];
+    }
+    (ssid, channel)
+}
+
+/// Describes an SSID element; beacons of hidden networks send it empty
+/// or zeroed, and probe requests send it empty to ask every network.
+fn describe_ssid(ssid: &str) -> Option<String> {
+    (!ssid.is_empty() && ssid.chars().any(|c| c != '\0')).then(|| format!("\"{ssid}\""))
+}
+
+/// Decodes the body of a management frame into `layer`, returning what
+/// the summary adds after the frame name.
+fn management(layer: &mut Layer, subtype: u8, body: &[u8]) -> Option<String> {
+    // Fixed fields come before the elements in these frames.
+    let fixed = match subtype {
+        0 => 4,
+        1 | 3 => 6,
+        2 => 10,
+        4 => 0,
+        5 | 8 => 12,
+        10 | 12 => {
+            let code = u16_at(body, 0)?;
+            let reason = reason_name(code).unwrap_or("Reserved");
+            layer.field("Reason", format!("{code} ({reason})"));
+            return Some(format!("reason {code}"));
+        }
+        11 => {
+            let algorithm = match u16_at(body, 0)? {
+                0 => "Open System".to_string(),
+                1 => "Shared Key".to_string(),
+                3 => "SAE".to_string(),
+                other => other.to_string(),
+            };
+            let sequence = u16_at(body, 2)?;
+            let status = u16_at(body, 4)?;
+            layer.field("Algorithm", algorithm.clone());
+            layer.field("Transaction", sequence.to_string());
+            layer.field("Status", status.to_string());
+            return Some(format!("{algorithm}, seq {sequence}, status {status}"));
+        }
+        _ => return None,
+    };
+    if subtype == 8 || subtype == 5 {
+        layer.field("Beacon Interval", format!("{} TU", u16_at(body, 8)?));
+    }
+    if subtype == 1 || subtype == 3 {
+        layer.field("Status", u16_at(body, 2)?.to_string());
+    }
+    let (ssid, channel) = elements(body.get(fixed..)?);
+    let ssid = ssid.as_deref().map(describe_ssid);
+    match ssid {
+        Some(Some(ref ssid)) => layer.field("SSID", ssid.clone()),
+        Some(None) if subtype == 4 => layer.field("SSID", "Wildcard"),
+        Some(None) => layer.field("SSID", "Hidden"),
+        None => {}
+    }
+    if let Some(channel) = channel {
+        layer.field("Channel", channel.to_string());
+    }
+    let mut summary = match (subtype, ssid) {
+        (_, Some(Some(ssid))) => format!("SSID {ssid}"),
+        (4, _) => "wildcard SSID".to_string(),
+        (_, Some(None)) => "hidden SSID".to_string(),
+        (_, None) => return None,
+    };
+    if let Some(channel) = channel {
+        summary.push_str(&format!(", Ch {channel}"));
+    }
+    Some(summary)
+}
+
+/// Decodes an 802.11 frame, after its radiotap header for
+/// `LINKTYPE_IEEE802_11_RADIOTAP`.
+pub fn dissect(link_type: u32, data: &[u8]) -> Option<Framing<'_>> {
+    let (layers, frame) = if link_type == LINKTYPE_IEEE802_11_RADIOTAP {
+        let (layer, frame) = radiotap(data)?;
+        (vec![layer], frame)
+    } else {
+        (Vec::new(), data)
+    };
+    let mut result = Framing {
+        layers,
+        source: None,
+        destination: None,
+        payload: None,
+    };
+    let Some(&[control, flags]) = frame.get(..2) else {
+        return Some(result);
+    };
+    let kind = control >> 2 & 0x3;
+    let subtype = control >> 4;
+    let name = frame_name(kind, subtype);
+    let address = |at: usize| frame.get(at..at + 6);
+
+    let mut layer = Layer::new("802.11", name.to_string());
+    layer.field("Frame", name);
+    let flag_names: Vec<&str> = [
+        (FLAG_TO_DS, "To DS"),
+        (FLAG_FROM_DS, "From DS"),
+        (FLAG_RETRY, "Retry"),
+        (FLAG_PROTECTED, "Protected"),
+    ]
+    .into_iter()
+    .filter_map(|(flag, name)| (flags & flag != 0).then_some(name))
+    .collect();
+    if !flag_names.is_empty() {
+        layer.field("Flags", flag_names.join(", "));
+    }
+    if let Some(duration) = u16_at(frame, 2) {
+        layer.detail("Duration", format!("{} µs", duration & 0x7fff));
+    }
+
+    let mut extra = None;
+    match kind {
+        TYPE_CONTROL => {
+            result.destination = address(4);
+            // CTS and ACK name only their receiver.
+            if !matches!(subtype, 12 | 13) {
+                result.source = address(10);
+            }
+            if let Some(receiver) = result.destination {
+                layer.field("Receiver", format_mac(receiver));
+            }
+            if let Some(transmitter) = result.source {
+                layer.field("Transmitter", format_mac(transmitter));
+            }
+        }
+        TYPE_MANAGEMENT | TYPE_DATA => {
+            let (to_ds, from_ds) = (flags & FLAG_TO_DS != 0, flags & FLAG_FROM_DS != 0);
+            let four_addresses = kind == TYPE_DATA && to_ds && from_ds;
+            let (source, destination, bssid) = match (kind == TYPE_DATA, to_ds, from_ds) {
+                (true, false, true) => (address(16), address(4), address(10)),
+                (true, true, false) => (address(10), address(16), address(4)),
+                (true, true, true) => (address(24), address(16), None),
+                _ => (address(10), address(4), address(16)),
+            };
+            result.source = source;
+            result.destination = destination;
+            if let Some(destination) = destination {
+                layer.field("Destination", format_mac(destination));
+            }
+            if let Some(source) = source {
+                layer.field("Source", format_mac(source));
+            }
+            if let Some(bssid) = bssid {
+                layer.field("BSSID", format_mac(bssid));
+            }
+            if let Some(sequence) = u16_at(frame, 22) {
+                layer.detail("Sequence", (sequence >> 4).to_string());
+            }
+            let mut header_len = if four_addresses { 30 } else { 24 };
+            let qos = kind == TYPE_DATA && subtype & 0x8 != 0;
+            if qos {
+                header_len += 2;
+                if flags & FLAG_ORDER != 0 {
+                    header_len += 4; // HT Control
+                }
+            }
+            let body = frame.get(header_len..).unwrap_or_default();
+            if kind == TYPE_MANAGEMENT {
+                extra = management(&mut layer, subtype, body);
+            } else if flags & FLAG_PROTECTED != 0 {
+                extra = Some("protected".to_string());
+            } else if let Some(rest) = body.strip_prefix(&SNAP_HEADER)
+                && let Some(&[high, low]) = rest.get(..2)
+            {
+                result.payload = Some((u16::from_be_bytes([high, low]), &rest[2..]));
+            }
+        }
+        _ => {}
+    }
+    if let Some(extra) = extra {
+        layer.summary = format!("{name}, {extra}");
+    }
+    result.layers.push(layer);
+    Some(result)
+}
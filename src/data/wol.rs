@@ -0,0 +1,28 @@
+use super::{layer::Layer, packet::format_mac};
+
+/// EtherType of a Wake-on-LAN frame sent directly over Ethernet.
+pub const ETHERTYPE: u16 = 0x0842;
+/// UDP ports magic packets are conventionally sent to.
+pub const PORTS: [u16; 2] = [7, 9];
+
+/// Decodes a magic packet: six 0xff bytes followed by the target MAC
+/// address repeated sixteen times, optionally with a SecureOn password.
+pub fn decode(payload: &[u8]) -> Option<Layer> {
+    let start = payload.windows(6).position(|w| w == [0xff; 6])?;
+    let body = payload.get(start + 6..)?;
+    let target = body.get(..6)?;
+    if body.len() < 96 || body[..96].chunks(6).any(|mac| mac != target) {
+        return None;
+    }
+    let password = match body.len() - 96 {
+        4 | 6 => Some(&body[96..]),
+        _ => None,
+    };
+
+    let mut layer = Layer::new("WoL", format!("MagicPacket for {}", format_mac(target)));
+    layer.field("Target", format_mac(target));
+    if let Some(password) = password {
+        layer.field("SecureOn Password", format_mac(password));
+    }
+    Some(layer)
+}
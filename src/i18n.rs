@@ -0,0 +1,1246 @@
+//! Translations of user-facing text.
+//!
+//! Strings are looked up by key in the bundle of the active language and
+//! fall back to English, so a missing translation never blanks the UI.
+//! Placeholders are written `{}` and filled in order by [`tr!`].
+
+use std::{env, fmt, sync::OnceLock};
+
+use clap::ValueEnum;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Language {
+    En,
+    Zh,
+}
+
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+impl Language {
+    /// Picks the language from the usual locale variables, defaulting to
+    /// English.
+    pub fn from_env() -> Self {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        if locale.to_lowercase().starts_with("zh") {
+            Language::Zh
+        } else {
+            Language::En
+        }
+    }
+
+    fn bundle(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Language::En => EN,
+            Language::Zh => ZH,
+        }
+    }
+}
+
+/// Sets the language for the rest of the session; only the first call counts.
+pub fn set_language(language: Language) {
+    let _ = LANGUAGE.set(language);
+}
+
+fn language() -> Language {
+    *LANGUAGE.get_or_init(Language::from_env)
+}
+
+fn lookup(bundle: &[(&'static str, &'static str)], key: &str) -> Option<&'static str> {
+    bundle
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, text)| *text)
+}
+
+/// The text for `key` in the active language.
+pub fn text(key: &'static str) -> &'static str {
+    lookup(language().bundle(), key)
+        .or_else(|| lookup(EN, key))
+        .unwrap_or(key)
+}
+
+/// The text for `key` with each `{}` replaced by the next argument.
+pub fn format(key: &'static str, args: &[&dyn fmt::Display]) -> String {
+    let mut args = args.iter();
+    let mut parts = text(key).split("{}");
+    let mut out = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+/// Translates a key, formatting any arguments into its placeholders.
+macro_rules! tr {
+    ($key:literal) => {
+        $crate::i18n::text($key)
+    };
+    ($key:literal, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::format($key, &[$(&$arg),+])
+    };
+}
+pub(crate) use tr;
+
+const EN: &[(&str, &str)] = &[
+    // Shared
+    ("status", "Status"),
+    ("help", "Help"),
+    ("app.alert", " Alert: {}  Ctrl+A: Jump to Packet "),
+    // Top bar
+    ("bar.capturing", "● Capturing on {}"),
+    ("bar.stopped", "Stopped: {}"),
+    ("bar.idle", "No device selected"),
+    (
+        "bar.analysis_only",
+        "Analysis only: live capture unavailable",
+    ),
+    ("bar.capture_filter", "Capture filter: {}"),
+    ("bar.display_filter", "Display filter: {}"),
+    ("page.home", "Home"),
+    ("page.details", "Packet Details"),
+    ("page.file_summary", "File Summary"),
+    ("page.follow_stream", "Follow Stream"),
+    ("page.ladder", "TCP Ladder"),
+    // Home
+    ("home.title", "Network Packet Sniffer"),
+    (
+        "home.analysis_only",
+        "Analysis-only mode: live capture is unavailable ({}). Capture files opened with -r can still be browsed and analysed; run as root or grant CAP_NET_RAW to capture.",
+    ),
+    ("home.unavailable", "Unavailable without capture privileges"),
+    (
+        "home.welcome",
+        "Welcome to Network Packet Sniffer. Select a module to continue.",
+    ),
+    (
+        "home.help",
+        "↑/↓: Navigate  Enter: Select Module  D: Device Selection  S: Packet Sniffer  Alt+←/→: Back/Forward  Ctrl+T: Theme  Q/Esc: Exit",
+    ),
+    ("home.device", "Device Selection"),
+    (
+        "home.device.about",
+        "Select network interface for packet capture",
+    ),
+    ("home.sniffer", "Packet Sniffer"),
+    ("home.sniffer.about", "Capture and analyze network packets"),
+    ("home.voip", "VoIP Statistics"),
+    ("home.voip.about", "SIP calls and RTP stream loss/jitter"),
+    ("home.dual_stack", "Dual-Stack"),
+    (
+        "home.dual_stack.about",
+        "IPv4/IPv6 connection races per resolved host",
+    ),
+    ("home.other_l2", "Other L2"),
+    (
+        "home.other_l2.about",
+        "Non-IP EtherTypes with their hex values",
+    ),
+    ("home.neighbors", "Address Resolution"),
+    (
+        "home.neighbors.about",
+        "ARP and NDP latency and unanswered requests per target",
+    ),
+    ("home.discovery", "Neighbors"),
+    (
+        "home.discovery.about",
+        "Switches and routers announcing themselves over LLDP and CDP",
+    ),
+    ("home.multicast", "Multicast Groups"),
+    (
+        "home.multicast.about",
+        "Hosts joining and leaving multicast groups over IGMP and MLD",
+    ),
+    ("home.latency", "Service Latency"),
+    (
+        "home.latency.about",
+        "DNS, HTTP, TLS and TCP handshake response time percentiles per server",
+    ),
+    ("home.dns", "DNS"),
+    (
+        "home.dns.about",
+        "Queries matched to responses, unanswered queries, top names and slow resolvers",
+    ),
+    ("home.http", "HTTP"),
+    (
+        "home.http.about",
+        "Requests paired with responses: status codes and the slowest URLs to first byte",
+    ),
+    ("home.top_talkers", "Top Talkers"),
+    (
+        "home.top_talkers.about",
+        "Busiest sources, destinations and ports by bytes, over a sliding window",
+    ),
+    ("home.objects", "HTTP Objects"),
+    (
+        "home.objects.about",
+        "Files transferred over HTTP, to save to disk",
+    ),
+    ("home.alerts", "Alerts"),
+    (
+        "home.alerts.about",
+        "ARP spoofing, --alert-rule matches and suspicious DNS traffic",
+    ),
+    ("home.conversations", "Conversations"),
+    (
+        "home.conversations.about",
+        "Per-flow packets, bytes and throughput in each direction",
+    ),
+    ("home.endpoints", "Endpoints"),
+    (
+        "home.endpoints.about",
+        "Per-host transmitted and received totals",
+    ),
+    ("home.flows", "Flows"),
+    (
+        "home.flows.about",
+        "Open connections and an archive of ended ones",
+    ),
+    ("home.performance", "Performance"),
+    (
+        "home.performance.about",
+        "CPU time and calls per protocol decoder",
+    ),
+    ("home.timeline", "Traffic Timeline"),
+    (
+        "home.timeline.about",
+        "Traffic rate at millisecond resolution with microbursts marked",
+    ),
+    ("home.replay", "Packet Replay"),
+    (
+        "home.replay.about",
+        "Re-send captured packets out of an interface",
+    ),
+    // Device selection
+    ("device.title", "Network Devices"),
+    ("device.probing", "Probing network devices..."),
+    ("device.none", "No network devices found."),
+    (
+        "device.none_refresh",
+        "No devices found. Press F5 to refresh.",
+    ),
+    (
+        "device.found_hint",
+        "Found {} device(s). Use ↑/↓ to navigate, Enter to select.",
+    ),
+    ("device.found", "Found {} device(s)"),
+    (
+        "device.changed",
+        "Interfaces changed, added: {}, removed: {}",
+    ),
+    ("device.all", "All interfaces, merged into one capture"),
+    ("device.list_failed", "Failed to list devices: {}"),
+    ("device.selected", "Selected device: {}"),
+    ("device.monitor", "[monitor]"),
+    ("device.details", "Device Details"),
+    ("device.no_addresses", "none"),
+    (
+        "device.monitor_on",
+        "Monitor mode on for {}: captures take raw 802.11 frames.",
+    ),
+    ("device.monitor_off", "Monitor mode off for {}."),
+    ("device.not_wireless", "{} is not a Wi-Fi interface."),
+    ("device.remote_found", "Found {} device(s) on {}"),
+    ("device.remote_failed", "Failed to list devices on {}: {}"),
+    ("device.remote.title", " Remote Capture (rpcapd) "),
+    ("device.remote.host", "Host:"),
+    ("device.remote.username", "Username:"),
+    ("device.remote.password", "Password:"),
+    (
+        "device.remote.help",
+        "Tab: Next Field  Enter: Connect  Esc: Cancel  (leave Username empty for no login)",
+    ),
+    (
+        "device.send_failed",
+        "Failed to send device selection action.",
+    ),
+    (
+        "device.help",
+        "↑/↓: Navigate  Enter: Select Device  R: Remote Host  W: Monitor Mode  Q/Esc: Home  F5: Refresh",
+    ),
+    (
+        "device.help.selected",
+        "↑/↓: Navigate  Enter: Select Device  R: Remote Host  W: Monitor Mode  Q/Esc: Home  F5: Refresh  C: Clear Selection",
+    ),
+    // Capture privileges
+    ("privileges.title", " Capture Permission Needed "),
+    (
+        "privileges.net_raw",
+        "This process has neither root nor the CAP_NET_RAW capability, which capturing packets needs.",
+    ),
+    (
+        "privileges.setcap",
+        "Grant the capabilities once: sudo setcap cap_net_raw,cap_net_admin=eip {}",
+    ),
+    ("privileges.sudo", "Or run it as root: sudo {}"),
+    (
+        "privileges.setcap_rebuild",
+        "Capabilities set with setcap are lost when the binary is rebuilt or replaced.",
+    ),
+    (
+        "privileges.bpf",
+        "The BPF devices (/dev/bpf*) can't be opened by this user, which capturing packets needs.",
+    ),
+    (
+        "privileges.chmod_bpf",
+        "Install ChmodBPF, which comes with Wireshark, then log out and in again.",
+    ),
+    (
+        "privileges.bpf_group",
+        "Or open the devices to your group: sudo chgrp admin /dev/bpf* && sudo chmod g+rw /dev/bpf*",
+    ),
+    (
+        "privileges.npcap",
+        "Npcap is not installed or only lets administrators capture.",
+    ),
+    (
+        "privileges.npcap_install",
+        "Install Npcap from https://npcap.com, with WinPcap API-compatible mode.",
+    ),
+    (
+        "privileges.npcap_admin",
+        "If it was installed restricted to administrators, run the terminal as Administrator.",
+    ),
+    ("privileges.close", "Any key: Close"),
+    // Sniffer
+    (
+        "sniffer.device_set",
+        "Device set to: {}. Press 'S' to start capturing.",
+    ),
+    ("sniffer.loaded", "Loaded {} packets from {}."),
+    (
+        "sniffer.no_device",
+        "No device selected. Press 'D' to select a device.",
+    ),
+    ("sniffer.starting", "Starting packet capture..."),
+    (
+        "sniffer.geoip_disabled",
+        "No GeoIP database loaded. Start with --geoip PATH to a GeoLite2 .mmdb file.",
+    ),
+    (
+        "sniffer.snaplen_advice",
+        "Snaplen {} truncates frames on {}, which can carry up to {} bytes; streams, reassembly and exports would miss payload.",
+    ),
+    (
+        "sniffer.snaplen_prompt",
+        "Y/Enter: Raise Snaplen to {} and Capture  N: Capture Anyway  Esc: Cancel",
+    ),
+    ("sniffer.snaplen_cancelled", "Capture not started."),
+    ("sniffer.stats_log_error", "Statistics log stopped: {}"),
+    ("sniffer.ring_error", "Ring files stopped: {}"),
+    (
+        "sniffer.analysis_only",
+        "Live capture is unavailable ({}). Open a capture file with -r to analyse it.",
+    ),
+    (
+        "sniffer.capturing",
+        "Capturing packets on {}. Press 'S' to stop.",
+    ),
+    (
+        "sniffer.capturing_filtered",
+        "Capturing packets on {} with filter: {}. Press 'S' to stop.",
+    ),
+    (
+        "sniffer.filter_error",
+        "Filter error: {}. Capturing without filter.",
+    ),
+    (
+        "sniffer.stopped",
+        "Stopped capturing on {}. Captured {} packets.",
+    ),
+    ("sniffer.cleared", "Cleared packet list."),
+    (
+        "sniffer.spool_error",
+        "Cannot spill packets to disk, keeping them in memory: {}",
+    ),
+    (
+        "sniffer.capture_stats",
+        " Received: {}  Dropped by kernel: {}  Dropped by interface: {} ",
+    ),
+    ("sniffer.backlog", " Backlog: {} packets "),
+    (
+        "sniffer.backlog_dropped",
+        " Backlog: {} packets, {} dropped while the list caught up ",
+    ),
+    (
+        "sniffer.filter_applied",
+        "Filter applied: {}. Press 'S' to start capturing.",
+    ),
+    (
+        "sniffer.filter_cleared",
+        "Filter cleared. Press 'S' to start capturing.",
+    ),
+    (
+        "sniffer.display_filter_applied",
+        "Display filter applied: {}",
+    ),
+    ("sniffer.display_filter_cleared", "Display filter cleared."),
+    ("sniffer.display_filter_error", "Display filter error: {}"),
+    ("sniffer.opening", "Opening packet details for packet #{}"),
+    ("sniffer.exported", "Exported {} packets to {}"),
+    ("sniffer.export_error", "Export failed: {}"),
+    (
+        "sniffer.snapshot_empty",
+        "No packets to save in a snapshot.",
+    ),
+    ("sniffer.marked", "{} packets marked (Shift+M: Export)"),
+    ("sniffer.export_none", "No packets to export."),
+    (
+        "sniffer.capture_options_saved",
+        "Capture options saved; they apply from the next capture.",
+    ),
+    (
+        "sniffer.capture_options_restart",
+        "Capture options saved; restart the capture to apply them.",
+    ),
+    (
+        "sniffer.capture_options_error",
+        "Failed to save capture options: {}",
+    ),
+    (
+        "sniffer.follow_udp_only",
+        "Only UDP packets can be followed as a stream.",
+    ),
+    (
+        "sniffer.ladder_tcp_only",
+        "Only TCP packets have a sequence ladder.",
+    ),
+    (
+        "sniffer.search_prompt",
+        "Search (text, or 0x for hex bytes; Enter: Find  Esc: Cancel): ",
+    ),
+    (
+        "sniffer.search_found",
+        "Match for {} in packet #{} (n/N: next/previous)",
+    ),
+    ("sniffer.search_none", "No listed packet matches {}"),
+    ("sniffer.search_error", "Search: {}"),
+    (
+        "sniffer.knowledge_loaded",
+        "Recognizing {} hosts from earlier sessions",
+    ),
+    (
+        "sniffer.help.capturing",
+        "S: Stop Capture  C: Clear Packets  ↑/↓: Scroll  F: Follow  PgUp/PgDn: Page  Home/End: Jump  A: Filter  Shift+A: Display Filter  /: Search  N: Next Match  P: Protocols  V: Flows  I: Expand Info  G: GeoIP Columns  X: Radio Columns  H: Handshake Column  U: Follow UDP  L: TCP Ladder  E: Export Flow  M: Mark  Shift+M: Export  W: Snapshot  T: Time Format  R: Replay Listed  O: Capture Options  D: Device Selection  Enter: Open Packet  Q/Esc: Home",
+    ),
+    (
+        "sniffer.help.following",
+        "S: Stop Capture  C: Clear Packets  F: Unfollow  A: Filter  Shift+A: Display Filter  /: Search  N: Next Match  P: Protocols  V: Flows  D: Device Selection  Enter: Open Packet  Q/Esc: Home",
+    ),
+    (
+        "sniffer.help.ready",
+        "S: Start Capture  C: Clear Packets  A: Filter  Shift+A: Display Filter  /: Search  N: Next Match  P: Protocols  V: Flows  I: Expand Info  G: GeoIP Columns  X: Radio Columns  H: Handshake Column  U: Follow UDP  L: TCP Ladder  E: Export Flow  M: Mark  Shift+M: Export  W: Snapshot  T: Time Format  R: Replay Listed  O: Capture Options  D: Device Selection  Enter: Open Packet  Q/Esc: Home",
+    ),
+    (
+        "sniffer.help.idle",
+        "A: Filter  Shift+A: Display Filter  /: Search  N: Next Match  P: Protocols  V: Flows  I: Expand Info  G: GeoIP Columns  X: Radio Columns  H: Handshake Column  U: Follow UDP  L: TCP Ladder  E: Export Flow  M: Mark  Shift+M: Export  W: Snapshot  T: Time Format  R: Replay Listed  O: Capture Options  D: Device Selection  Enter: Open Packet  Q/Esc: Home",
+    ),
+    // Export dialog
+    ("export.title", "Export Packets"),
+    ("export.file", "File"),
+    ("export.format", "Format"),
+    ("export.scope", "Packets"),
+    ("export.scope.marked", "Marked or selected ({})"),
+    ("export.scope.listed", "Listed ({})"),
+    ("export.payload", "Payload"),
+    ("export.payload.about", "hex-encoded, in JSON and CSV"),
+    (
+        "export.help",
+        "Tab/↑/↓: Field  ←/→/Space: Change  Enter: Export  Esc: Cancel",
+    ),
+    // Capture options dialog
+    ("capture_options.title", "Capture Options"),
+    ("capture_options.promisc", "Promiscuous"),
+    (
+        "capture_options.promisc.about",
+        "also frames for other hosts",
+    ),
+    ("capture_options.snaplen", "Snaplen"),
+    ("capture_options.buffer_size", "Buffer MiB"),
+    ("capture_options.default", "libpcap default"),
+    ("capture_options.timeout", "Timeout ms"),
+    ("capture_options.immediate", "Immediate"),
+    (
+        "capture_options.immediate.about",
+        "deliver each packet at once",
+    ),
+    ("capture_options.backend", "Backend"),
+    (
+        "capture_options.backend.about",
+        "af-packet: Linux ring, no copies",
+    ),
+    ("capture_options.ring", "Ring file"),
+    ("capture_options.ring.off", "none"),
+    ("capture_options.ring_size", "Ring MB"),
+    ("capture_options.ring_seconds", "Ring secs"),
+    ("capture_options.ring_seconds.none", "no limit"),
+    ("capture_options.ring_files", "Ring files"),
+    (
+        "capture_options.help",
+        "Tab/↑/↓: Field  Type: Edit  Space: Toggle  Enter: Save  Esc: Cancel",
+    ),
+    (
+        "capture_options.invalid",
+        "Snaplen, timeout and ring files need numbers; the buffer a positive one or none; ring MB a positive one.",
+    ),
+    // Filter dialog
+    ("filter.capture", "Capture Filter"),
+    ("filter.display", "Display Filter"),
+    ("filter.custom", "Enter Custom Filter"),
+    ("filter.presets", "Filter Presets"),
+    ("filter.clear", "Clear Filter"),
+    ("filter.removes", "(removes current filter)"),
+    ("filter.examples", "Examples:"),
+    ("filter.error", "Error: {}"),
+    ("filter.preview", "Matches {} of the last {} packets"),
+    ("filter.preview_invalid", "Not a complete filter yet: {}"),
+    ("filter.syntax_ok", "Syntax OK"),
+    ("filter.syntax_error", "Syntax error: {}"),
+    (
+        "filter.help.custom",
+        "Tab: Complete or switch to presets  ↑/↓: History  Ctrl+S: Save as  Ctrl+P: Save as preset  Enter: Apply  Esc: Cancel",
+    ),
+    (
+        "filter.help.presets",
+        "Tab: Switch to saved filters  Enter: Apply  ↑/↓: Navigate  a: Add  e: Edit  r: Rename  d: Delete  Esc: Cancel",
+    ),
+    (
+        "filter.help.saved",
+        "Tab: Switch to custom input  Enter: Apply  r: Rename  d: Delete  Esc: Cancel",
+    ),
+    ("filter.saved", "Saved Filters"),
+    (
+        "filter.saved.empty",
+        "No saved filters yet; press Ctrl+S while typing one to save it",
+    ),
+    ("filter.save_as", "Save Filter As"),
+    ("filter.rename", "Rename Filter"),
+    ("filter.save_preset", "Save Preset As"),
+    ("filter.placeholder", "Value for {} in {}"),
+    ("filter.example.http", "HTTP traffic"),
+    ("filter.example.dns", "DNS traffic"),
+    ("filter.example.host", "Traffic to/from specific host"),
+    ("filter.example.net", "Traffic from subnet"),
+    ("filter.example.icmp", "ICMP packets"),
+    (
+        "filter.example.subnet",
+        "Any address in subnet, inner or outer",
+    ),
+    ("filter.example.vxlan", "UDP inside one VXLAN segment"),
+    ("filter.example.fields", "Fields of decoded protocols"),
+    ("filter.example.not_arp", "Everything but ARP"),
+    // Packet details
+    ("detail.no_packet", "No packet selected"),
+    ("detail.info_title", " Packet Information"),
+    ("detail.tree_title", " Protocol Details ({})"),
+    ("detail.hex_title", " Hex Viewer"),
+    ("detail.hex_title_bytes", " Hex Viewer ({} bytes)"),
+    (
+        "detail.help",
+        "Tab: Protocol Tree  ↑/↓: Scroll Hex or Move  ←/→: Collapse/Expand  Click Hex: Select Field  V: Verbosity  R: Related Packet  X: Export Bytes  P: Export Payload  C: Comment  Q: Back to Sniffer  Esc: Back to Home",
+    ),
+    (
+        "detail.save_prompt",
+        "Export bytes as text to (Enter: Save  Esc: Cancel): ",
+    ),
+    ("detail.saved", "Wrote the dump of {} bytes to {}"),
+    (
+        "detail.payload_prompt",
+        "Export payload to (Enter: Save  Esc: Cancel): ",
+    ),
+    ("detail.payload_saved", "Wrote {} bytes of payload to {}"),
+    (
+        "detail.comment_prompt",
+        "Packet comment, empty to remove (Enter: Save  Esc: Cancel): ",
+    ),
+    ("detail.save_error", "Failed to write {}: {}"),
+    // Statistics pages
+    ("stats.help.scroll", "↑/↓: Scroll  Q/Esc: Home"),
+    ("stats.help.home", "Q/Esc: Home"),
+    ("file_summary.help", "Enter: View Packets  Q/Esc: Home"),
+    (
+        "conversations.help",
+        "↑/↓: Select  ←/→: Sort Column  R: Reverse  Enter: Filter Conversation  E: Export pcap  Q/Esc: Home",
+    ),
+    (
+        "endpoints.help",
+        "↑/↓: Select  Enter: Filter Host  E: Export Service Report  Q/Esc: Home",
+    ),
+    ("endpoints.exported", "Service report saved to {}"),
+    ("endpoints.export_error", "Export failed: {}"),
+    (
+        "objects.help",
+        "↑/↓: Select  Enter/S: Save Object  A: Save All  Q/Esc: Home",
+    ),
+    (
+        "alerts.help",
+        "↑/↓: Select  Enter: Latest Packet  Q/Esc: Home",
+    ),
+    (
+        "objects.save_prompt",
+        "Save object to (Enter: Save  Esc: Cancel): ",
+    ),
+    (
+        "objects.save_all_prompt",
+        "Save all objects into directory (Enter: Save  Esc: Cancel): ",
+    ),
+    ("objects.saved", "Saved {} bytes to {}"),
+    ("objects.saved_all", "Saved {} objects into {}"),
+    ("objects.save_error", "Save failed: {}"),
+    ("objects.truncated", "[truncated: too large to decompress]"),
+    (
+        "flows.help",
+        "↑/↓: Select  ←/→: Sort Column  R: Reverse  Tab: Active/Ended  Enter: Filter Flow  Q/Esc: Home",
+    ),
+    (
+        "performance.help",
+        "↑/↓: Scroll  R: Reset Counters  Q/Esc: Home",
+    ),
+    ("top_talkers.help", "←/→/W: Window  Q/Esc: Home"),
+    (
+        "dns.help",
+        "↑/↓/PgUp/PgDn: Select  U: Unanswered Only  Enter: Filter Exchange  Q/Esc: Home",
+    ),
+    (
+        "follow_stream.help",
+        "↑/↓/PgUp/PgDn: Scroll  H: Text/Hex  C: Concatenate  D: Direction  Q: Back to Packets  Esc: Home",
+    ),
+    (
+        "ladder.help",
+        "↑/↓/PgUp/PgDn: Scroll  Home/End: Jump  G: Sequence/Throughput Graph  D: Graph Direction  Q: Back to Packets  Esc: Home",
+    ),
+    ("timeline.help", "↑/↓: Select Burst  Q/Esc: Home"),
+    ("file_summary.title", " Capture File Summary"),
+    ("file_summary.no_file", "No capture file opened"),
+    ("conversations.title", "Conversations ({})"),
+    ("endpoints.title", "Endpoints ({})"),
+    ("endpoints.title_status", "Endpoints ({}) - {}"),
+    ("objects.title", "HTTP Objects ({})"),
+    ("objects.title_status", "HTTP Objects ({}) - {}"),
+    ("alerts.title", "Alerts ({})"),
+    ("performance.title", "Decoder Performance"),
+    ("top_talkers.sources", "Top Sources (last {}, {})"),
+    ("top_talkers.destinations", "Top Destinations (last {})"),
+    ("top_talkers.ports", "Top Ports (last {})"),
+    ("dns.names_title", "Top Queried Names ({} distinct)"),
+    ("dns.resolvers_title", "Slowest Resolvers"),
+    (
+        "dns.queries_title",
+        "DNS Queries ({}, {} unanswered, NXDOMAIN {} of responses{})",
+    ),
+    ("dns.unanswered_shown", ", unanswered shown"),
+    (
+        "http.status_title",
+        "Status Codes ({} requests, {} unanswered)",
+    ),
+    ("http.urls_title", "Slowest URLs (time to first byte)"),
+    ("voip.streams_title", "RTP Streams ({})"),
+    ("voip.calls_title", "SIP Calls ({})"),
+    (
+        "follow_stream.title",
+        "Follow UDP Stream: {} ({} datagrams, A→B {} bytes, B→A {} bytes{})",
+    ),
+    ("follow_stream.title_empty", "Follow UDP Stream"),
+    ("follow_stream.from_a", ", showing A→B"),
+    ("follow_stream.from_b", ", showing B→A"),
+    (
+        "ladder.title",
+        "TCP Ladder: {} ({} segments, {} retransmissions, {} duplicate ACKs)",
+    ),
+    ("ladder.title_empty", "TCP Ladder"),
+    (
+        "ladder.sequence_title",
+        "Time/Sequence: {} → {} ({} bytes, {} retransmissions, {} stalls)",
+    ),
+    ("ladder.throughput_title", "Throughput: {} → {} ({} bytes)"),
+    ("ladder.sequence", "Sequence"),
+    ("ladder.seconds", "Seconds"),
+    (
+        "timeline.title",
+        "Traffic Timeline (peak rate per {} ms, average {}, top {})",
+    ),
+    ("timeline.bursts_title", "Microbursts ({})"),
+    ("discovery.title", "Neighbors ({} devices on {} interfaces)"),
+    ("dual_stack.title", "Dual-Stack Connections ({})"),
+    (
+        "latency.title",
+        "Service Latency ({} servers, {} responses timed)",
+    ),
+    (
+        "multicast.title",
+        "Multicast Groups ({} groups, {} general queries{})",
+    ),
+    ("multicast.querier", ", querier {}"),
+    ("multicast.queriers", ", {} queriers"),
+    (
+        "neighbors.title",
+        "Address Resolution ({} targets, {} unanswered requests)",
+    ),
+    ("other_l2.title", "Other L2 ({} EtherTypes)"),
+    // Replay
+    ("replay.title", " Packet Replay: {} frames, {} "),
+    ("replay.source.captured", "all captured packets"),
+    ("replay.source.listed", "packets listed on the sniffer page"),
+    ("replay.interface", "Interface"),
+    ("replay.rate", "Rate"),
+    ("replay.frames", "Frames"),
+    ("replay.none", "None"),
+    ("replay.count_once", "Each frame once ({})"),
+    ("replay.count", "{}, repeating the list"),
+    ("replay.progress", "Progress"),
+    ("replay.sent", "Sent {} frames, {} bytes"),
+    ("replay.elapsed", "Elapsed {} s, {} frames/s"),
+    ("replay.send_failed", "{} frames failed: {}"),
+    ("replay.skipped", "Skipped {} frames that are not Ethernet"),
+    ("replay.started", "Replaying on {}..."),
+    ("replay.stopped", "Replay stopped."),
+    ("replay.failed", "Replay failed: {}"),
+    (
+        "replay.no_interface",
+        "No interface to send on. Press F5 to refresh.",
+    ),
+    (
+        "replay.no_frames",
+        "No packets to replay. Capture or open a file first.",
+    ),
+    ("replay.interfaces_failed", "Failed to list interfaces: {}"),
+    (
+        "replay.help",
+        "↑/↓: Select Setting  ←/→: Change  Enter: Start/Stop  F5: Refresh Interfaces  Q/Esc: Home",
+    ),
+];
+
+const ZH: &[(&str, &str)] = &[
+    // Shared
+    ("status", "状态"),
+    ("help", "帮助"),
+    ("app.alert", " 警报: {}  Ctrl+A: 跳转到数据包 "),
+    // Top bar
+    ("bar.capturing", "● 正在 {} 上抓包"),
+    ("bar.stopped", "已停止: {}"),
+    ("bar.idle", "未选择设备"),
+    ("bar.analysis_only", "仅分析模式: 无法实时抓包"),
+    ("bar.capture_filter", "抓包过滤器: {}"),
+    ("bar.display_filter", "显示过滤器: {}"),
+    ("page.home", "主页"),
+    ("page.details", "数据包详情"),
+    ("page.file_summary", "文件摘要"),
+    ("page.follow_stream", "跟踪流"),
+    ("page.ladder", "TCP 时序图"),
+    // Home
+    ("home.title", "网络抓包工具"),
+    (
+        "home.analysis_only",
+        "仅分析模式: 无法实时抓包 ({})。仍可浏览和分析用 -r 打开的抓包文件; 以 root 运行或授予 CAP_NET_RAW 权限即可抓包。",
+    ),
+    ("home.unavailable", "无抓包权限, 不可用"),
+    ("home.welcome", "欢迎使用网络抓包工具。请选择一个模块。"),
+    (
+        "home.help",
+        "↑/↓: 导航  Enter: 选择模块  D: 选择设备  S: 抓包  Alt+←/→: 后退/前进  Ctrl+T: 主题  Q/Esc: 退出",
+    ),
+    ("home.device", "选择设备"),
+    ("home.device.about", "选择用于抓包的网络接口"),
+    ("home.sniffer", "抓包"),
+    ("home.sniffer.about", "捕获并分析网络数据包"),
+    ("home.voip", "VoIP 统计"),
+    ("home.voip.about", "SIP 通话与 RTP 流丢包/抖动"),
+    ("home.dual_stack", "双栈"),
+    ("home.dual_stack.about", "按解析主机统计 IPv4/IPv6 连接竞速"),
+    ("home.other_l2", "其他二层协议"),
+    ("home.other_l2.about", "非 IP 的 EtherType 及其十六进制值"),
+    ("home.neighbors", "地址解析"),
+    (
+        "home.neighbors.about",
+        "按目标统计 ARP 与 NDP 的解析延迟和未应答请求",
+    ),
+    ("home.discovery", "邻居设备"),
+    (
+        "home.discovery.about",
+        "通过 LLDP 与 CDP 通告自身的交换机和路由器",
+    ),
+    ("home.multicast", "组播组"),
+    (
+        "home.multicast.about",
+        "通过 IGMP 与 MLD 加入和离开组播组的主机",
+    ),
+    ("home.latency", "服务延迟"),
+    (
+        "home.latency.about",
+        "按服务器统计 DNS、HTTP、TLS 和 TCP 握手响应时间百分位",
+    ),
+    ("home.dns", "DNS"),
+    (
+        "home.dns.about",
+        "查询与响应配对、未应答查询、热门域名和慢速解析器",
+    ),
+    ("home.http", "HTTP"),
+    (
+        "home.http.about",
+        "请求与响应配对：状态码分布和首字节最慢的 URL",
+    ),
+    ("home.top_talkers", "流量排行"),
+    (
+        "home.top_talkers.about",
+        "滑动时间窗口内按字节数排名的源、目的主机和端口",
+    ),
+    ("home.objects", "HTTP 对象"),
+    ("home.objects.about", "通过 HTTP 传输的文件，可保存到磁盘"),
+    ("home.alerts", "告警"),
+    (
+        "home.alerts.about",
+        "ARP 欺骗、--alert-rule 规则命中以及可疑 DNS 流量",
+    ),
+    ("home.conversations", "会话"),
+    (
+        "home.conversations.about",
+        "按流统计双向的包数、字节数与吞吐量",
+    ),
+    ("home.endpoints", "端点"),
+    ("home.endpoints.about", "按主机统计发送与接收总量"),
+    ("home.flows", "流"),
+    ("home.flows.about", "活动连接及已结束连接的归档"),
+    ("home.performance", "性能"),
+    (
+        "home.performance.about",
+        "各协议解码器的 CPU 时间与调用次数",
+    ),
+    ("home.timeline", "流量时间线"),
+    ("home.timeline.about", "毫秒级流量速率，并标出微突发"),
+    ("home.replay", "数据包重放"),
+    ("home.replay.about", "从接口重新发送抓到的数据包"),
+    // Device selection
+    ("device.title", "网络设备"),
+    ("device.probing", "正在探测网络设备..."),
+    ("device.none", "未找到网络设备。"),
+    ("device.none_refresh", "未找到设备。按 F5 刷新。"),
+    (
+        "device.found_hint",
+        "找到 {} 个设备。使用 ↑/↓ 导航，Enter 选择。",
+    ),
+    ("device.found", "找到 {} 个设备"),
+    ("device.changed", "接口已变化，新增: {}，移除: {}"),
+    ("device.all", "所有接口，合并为一次抓包"),
+    ("device.list_failed", "列出设备失败: {}"),
+    ("device.selected", "已选择设备: {}"),
+    ("device.monitor", "[监听]"),
+    ("device.details", "设备详情"),
+    ("device.no_addresses", "无"),
+    (
+        "device.monitor_on",
+        "已为 {} 开启监听模式：抓包将获取原始 802.11 帧。",
+    ),
+    ("device.monitor_off", "已为 {} 关闭监听模式。"),
+    ("device.not_wireless", "{} 不是 Wi-Fi 接口。"),
+    ("device.remote_found", "找到 {} 个设备 ({})"),
+    ("device.remote_failed", "列出 {} 上的设备失败: {}"),
+    ("device.remote.title", " 远程抓包 (rpcapd) "),
+    ("device.remote.host", "主机:"),
+    ("device.remote.username", "用户名:"),
+    ("device.remote.password", "密码:"),
+    (
+        "device.remote.help",
+        "Tab: 下一项  Enter: 连接  Esc: 取消  (用户名留空则不登录)",
+    ),
+    ("device.send_failed", "发送设备选择操作失败。"),
+    (
+        "device.help",
+        "↑/↓: 导航  Enter: 选择设备  R: 远程主机  W: 监听模式  Q/Esc: 主页  F5: 刷新",
+    ),
+    (
+        "device.help.selected",
+        "↑/↓: 导航  Enter: 选择设备  R: 远程主机  W: 监听模式  Q/Esc: 主页  F5: 刷新  C: 清除选择",
+    ),
+    // Capture privileges
+    ("privileges.title", " 需要抓包权限 "),
+    (
+        "privileges.net_raw",
+        "此进程既不是 root 也没有抓包所需的 CAP_NET_RAW 能力。",
+    ),
+    (
+        "privileges.setcap",
+        "一次性授予能力: sudo setcap cap_net_raw,cap_net_admin=eip {}",
+    ),
+    ("privileges.sudo", "或以 root 运行: sudo {}"),
+    (
+        "privileges.setcap_rebuild",
+        "用 setcap 设置的能力会在重新编译或替换程序后失效。",
+    ),
+    (
+        "privileges.bpf",
+        "当前用户无法打开抓包所需的 BPF 设备 (/dev/bpf*)。",
+    ),
+    (
+        "privileges.chmod_bpf",
+        "安装 Wireshark 附带的 ChmodBPF，然后注销并重新登录。",
+    ),
+    (
+        "privileges.bpf_group",
+        "或将设备开放给你的组: sudo chgrp admin /dev/bpf* && sudo chmod g+rw /dev/bpf*",
+    ),
+    (
+        "privileges.npcap",
+        "未安装 Npcap，或 Npcap 仅允许管理员抓包。",
+    ),
+    (
+        "privileges.npcap_install",
+        "从 https://npcap.com 安装 Npcap，并启用 WinPcap API 兼容模式。",
+    ),
+    (
+        "privileges.npcap_admin",
+        "如果安装时限制为仅管理员，请以管理员身份运行终端。",
+    ),
+    ("privileges.close", "任意键: 关闭"),
+    // Sniffer
+    ("sniffer.device_set", "设备已设为: {}。按 'S' 开始抓包。"),
+    ("sniffer.loaded", "已载入 {} 个数据包，来源 {}。"),
+    ("sniffer.no_device", "未选择设备。按 'D' 选择设备。"),
+    ("sniffer.starting", "正在开始抓包..."),
+    (
+        "sniffer.geoip_disabled",
+        "未加载 GeoIP 数据库。请用 --geoip 指定 GeoLite2 .mmdb 文件启动。",
+    ),
+    (
+        "sniffer.snaplen_advice",
+        "截断长度 {} 会截断 {} 上的帧 (最长 {} 字节); 流跟踪、重组和导出将缺失载荷。",
+    ),
+    (
+        "sniffer.snaplen_prompt",
+        "Y/Enter: 将截断长度提高到 {} 并抓包  N: 仍然抓包  Esc: 取消",
+    ),
+    ("sniffer.snaplen_cancelled", "未开始抓包。"),
+    ("sniffer.stats_log_error", "统计日志已停止: {}"),
+    ("sniffer.ring_error", "循环文件写入已停止: {}"),
+    (
+        "sniffer.analysis_only",
+        "无法实时抓包 ({})。请用 -r 打开抓包文件进行分析。",
+    ),
+    ("sniffer.capturing", "正在 {} 上抓包。按 'S' 停止。"),
+    (
+        "sniffer.capturing_filtered",
+        "正在 {} 上抓包，过滤器: {}。按 'S' 停止。",
+    ),
+    (
+        "sniffer.filter_error",
+        "过滤器错误: {}。将不使用过滤器抓包。",
+    ),
+    (
+        "sniffer.stopped",
+        "已停止在 {} 上抓包。共捕获 {} 个数据包。",
+    ),
+    ("sniffer.cleared", "已清空数据包列表。"),
+    (
+        "sniffer.spool_error",
+        "无法将数据包写入磁盘，改为保留在内存中：{}",
+    ),
+    (
+        "sniffer.capture_stats",
+        " 已接收: {}  内核丢弃: {}  接口丢弃: {} ",
+    ),
+    ("sniffer.backlog", " 待处理: {} 个数据包 "),
+    (
+        "sniffer.backlog_dropped",
+        " 待处理: {} 个数据包，{} 个因列表处理不及被丢弃 ",
+    ),
+    (
+        "sniffer.filter_applied",
+        "已应用过滤器: {}。按 'S' 开始抓包。",
+    ),
+    ("sniffer.filter_cleared", "已清除过滤器。按 'S' 开始抓包。"),
+    ("sniffer.display_filter_applied", "已应用显示过滤器: {}"),
+    ("sniffer.display_filter_cleared", "已清除显示过滤器。"),
+    ("sniffer.display_filter_error", "显示过滤器错误: {}"),
+    ("sniffer.opening", "正在打开第 {} 个数据包的详情"),
+    ("sniffer.exported", "已导出 {} 个数据包到 {}"),
+    ("sniffer.export_error", "导出失败: {}"),
+    ("sniffer.snapshot_empty", "没有可保存为快照的数据包。"),
+    ("sniffer.marked", "已标记 {} 个数据包 (Shift+M: 导出)"),
+    ("sniffer.export_none", "没有可导出的数据包。"),
+    (
+        "sniffer.capture_options_saved",
+        "抓包选项已保存，下次抓包时生效。",
+    ),
+    (
+        "sniffer.capture_options_restart",
+        "抓包选项已保存，重新开始抓包后生效。",
+    ),
+    ("sniffer.capture_options_error", "保存抓包选项失败：{}"),
+    ("sniffer.follow_udp_only", "只有 UDP 数据包可以作为流跟踪。"),
+    ("sniffer.ladder_tcp_only", "只有 TCP 数据包有序列时序图。"),
+    (
+        "sniffer.search_prompt",
+        "搜索 (文本, 或以 0x 开头的十六进制字节; Enter: 查找  Esc: 取消): ",
+    ),
+    (
+        "sniffer.search_found",
+        "{} 匹配数据包 #{} (n/N: 下一个/上一个)",
+    ),
+    ("sniffer.search_none", "没有列出的数据包匹配 {}"),
+    ("sniffer.search_error", "搜索: {}"),
+    ("sniffer.knowledge_loaded", "已从之前的会话中识别 {} 台主机"),
+    (
+        "sniffer.help.capturing",
+        "S: 停止抓包  C: 清空  ↑/↓: 滚动  F: 跟随  PgUp/PgDn: 翻页  Home/End: 跳转  A: 过滤器  Shift+A: 显示过滤器  /: 搜索  N: 下一个匹配  P: 协议  V: 流  I: 展开信息  G: GeoIP 列  X: 无线列  H: 握手时延列  U: 跟踪 UDP 流  L: TCP 时序图  E: 导出该流  M: 标记  Shift+M: 导出  W: 快照  T: 时间格式  R: 重放列出的包  O: 抓包选项  D: 选择设备  Enter: 打开数据包  Q/Esc: 主页",
+    ),
+    (
+        "sniffer.help.following",
+        "S: 停止抓包  C: 清空  F: 取消跟随  A: 过滤器  Shift+A: 显示过滤器  /: 搜索  N: 下一个匹配  P: 协议  V: 流  D: 选择设备  Enter: 打开数据包  Q/Esc: 主页",
+    ),
+    (
+        "sniffer.help.ready",
+        "S: 开始抓包  C: 清空  A: 过滤器  Shift+A: 显示过滤器  /: 搜索  N: 下一个匹配  P: 协议  V: 流  I: 展开信息  G: GeoIP 列  X: 无线列  H: 握手时延列  U: 跟踪 UDP 流  L: TCP 时序图  E: 导出该流  M: 标记  Shift+M: 导出  W: 快照  T: 时间格式  R: 重放列出的包  O: 抓包选项  D: 选择设备  Enter: 打开数据包  Q/Esc: 主页",
+    ),
+    (
+        "sniffer.help.idle",
+        "A: 过滤器  Shift+A: 显示过滤器  /: 搜索  N: 下一个匹配  P: 协议  V: 流  I: 展开信息  G: GeoIP 列  X: 无线列  H: 握手时延列  U: 跟踪 UDP 流  L: TCP 时序图  E: 导出该流  M: 标记  Shift+M: 导出  W: 快照  T: 时间格式  R: 重放列出的包  O: 抓包选项  D: 选择设备  Enter: 打开数据包  Q/Esc: 主页",
+    ),
+    // Export dialog
+    ("export.title", "导出数据包"),
+    ("export.file", "文件"),
+    ("export.format", "格式"),
+    ("export.scope", "数据包"),
+    ("export.scope.marked", "已标记或选中 ({})"),
+    ("export.scope.listed", "已列出 ({})"),
+    ("export.payload", "载荷"),
+    ("export.payload.about", "十六进制，仅 JSON 和 CSV"),
+    (
+        "export.help",
+        "Tab/↑/↓: 字段  ←/→/空格: 更改  Enter: 导出  Esc: 取消",
+    ),
+    // Capture options dialog
+    ("capture_options.title", "抓包选项"),
+    ("capture_options.promisc", "混杂模式"),
+    ("capture_options.promisc.about", "同时捕获发往其他主机的帧"),
+    ("capture_options.snaplen", "截断长度"),
+    ("capture_options.buffer_size", "缓冲区 MiB"),
+    ("capture_options.default", "libpcap 默认"),
+    ("capture_options.timeout", "超时 ms"),
+    ("capture_options.immediate", "立即模式"),
+    ("capture_options.immediate.about", "每个数据包立即送达"),
+    ("capture_options.backend", "抓包后端"),
+    (
+        "capture_options.backend.about",
+        "af-packet：Linux 环形缓冲区，零拷贝",
+    ),
+    ("capture_options.ring", "循环文件"),
+    ("capture_options.ring.off", "无"),
+    ("capture_options.ring_size", "循环 MB"),
+    ("capture_options.ring_seconds", "循环秒数"),
+    ("capture_options.ring_seconds.none", "不限"),
+    ("capture_options.ring_files", "循环文件数"),
+    (
+        "capture_options.help",
+        "Tab/↑/↓: 字段  输入: 编辑  空格: 切换  Enter: 保存  Esc: 取消",
+    ),
+    (
+        "capture_options.invalid",
+        "截断长度、超时和循环文件数须为数字，缓冲区须为正数或留空，循环 MB 须为正数。",
+    ),
+    // Filter dialog
+    ("filter.capture", "抓包过滤器"),
+    ("filter.display", "显示过滤器"),
+    ("filter.custom", "输入自定义过滤器"),
+    ("filter.presets", "过滤器预设"),
+    ("filter.clear", "清除过滤器"),
+    ("filter.removes", "(移除当前过滤器)"),
+    ("filter.examples", "示例:"),
+    ("filter.error", "错误: {}"),
+    ("filter.preview", "匹配 {} 个(共检查最近 {} 个数据包)"),
+    ("filter.preview_invalid", "过滤器尚不完整: {}"),
+    ("filter.syntax_ok", "语法正确"),
+    ("filter.syntax_error", "语法错误: {}"),
+    (
+        "filter.help.custom",
+        "Tab: 补全或切换到预设  ↑/↓: 历史  Ctrl+S: 另存为  Ctrl+P: 存为预设  Enter: 应用  Esc: 取消",
+    ),
+    (
+        "filter.help.presets",
+        "Tab: 切换到已保存的过滤器  Enter: 应用  ↑/↓: 导航  a: 添加  e: 编辑  r: 重命名  d: 删除  Esc: 取消",
+    ),
+    (
+        "filter.help.saved",
+        "Tab: 切换到自定义输入  Enter: 应用  r: 重命名  d: 删除  Esc: 取消",
+    ),
+    ("filter.saved", "已保存的过滤器"),
+    (
+        "filter.saved.empty",
+        "尚无已保存的过滤器；输入时按 Ctrl+S 即可保存",
+    ),
+    ("filter.save_as", "过滤器另存为"),
+    ("filter.rename", "重命名过滤器"),
+    ("filter.save_preset", "预设另存为"),
+    ("filter.placeholder", "{} 的值(用于 {})"),
+    ("filter.example.http", "HTTP 流量"),
+    ("filter.example.dns", "DNS 流量"),
+    ("filter.example.host", "与指定主机之间的流量"),
+    ("filter.example.net", "来自子网的流量"),
+    ("filter.example.icmp", "ICMP 数据包"),
+    ("filter.example.subnet", "子网内的任意地址，含内层与外层"),
+    ("filter.example.vxlan", "某个 VXLAN 段内的 UDP"),
+    ("filter.example.fields", "已解码协议的字段"),
+    ("filter.example.not_arp", "除 ARP 外的所有流量"),
+    // Packet details
+    ("detail.no_packet", "未选择数据包"),
+    ("detail.info_title", " 数据包信息"),
+    ("detail.tree_title", " 协议详情 ({})"),
+    ("detail.hex_title", " 十六进制查看器"),
+    ("detail.hex_title_bytes", " 十六进制查看器 ({} 字节)"),
+    (
+        "detail.help",
+        "Tab: 协议树  ↑/↓: 滚动十六进制或移动  ←/→: 折叠/展开  点击十六进制: 选择字段  V: 详细程度  R: 关联数据包  X: 导出字节  P: 导出载荷  C: 注释  Q: 返回抓包  Esc: 返回主页",
+    ),
+    (
+        "detail.save_prompt",
+        "将字节以文本导出到 (Enter: 保存  Esc: 取消): ",
+    ),
+    ("detail.saved", "已将 {} 字节的转储写入 {}"),
+    (
+        "detail.payload_prompt",
+        "将载荷导出到 (Enter: 保存  Esc: 取消): ",
+    ),
+    ("detail.payload_saved", "已将 {} 字节的载荷写入 {}"),
+    (
+        "detail.comment_prompt",
+        "数据包注释，留空则删除 (Enter: 保存  Esc: 取消): ",
+    ),
+    ("detail.save_error", "写入 {} 失败: {}"),
+    // Statistics pages
+    ("stats.help.scroll", "↑/↓: 滚动  Q/Esc: 主页"),
+    ("stats.help.home", "Q/Esc: 主页"),
+    ("file_summary.help", "Enter: 查看数据包  Q/Esc: 主页"),
+    (
+        "conversations.help",
+        "↑/↓: 选择  ←/→: 排序列  R: 反转  Enter: 过滤该会话  E: 导出 pcap  Q/Esc: 主页",
+    ),
+    (
+        "endpoints.help",
+        "↑/↓: 选择  Enter: 过滤该主机  E: 导出服务报告  Q/Esc: 主页",
+    ),
+    ("endpoints.exported", "服务报告已保存到 {}"),
+    ("endpoints.export_error", "导出失败: {}"),
+    (
+        "objects.help",
+        "↑/↓: 选择  Enter/S: 保存对象  A: 全部保存  Q/Esc: 主页",
+    ),
+    ("alerts.help", "↑/↓: 选择  Enter: 最新数据包  Q/Esc: 主页"),
+    (
+        "objects.save_prompt",
+        "将对象保存到 (Enter: 保存  Esc: 取消): ",
+    ),
+    (
+        "objects.save_all_prompt",
+        "将全部对象保存到目录 (Enter: 保存  Esc: 取消): ",
+    ),
+    ("objects.saved", "已将 {} 字节保存到 {}"),
+    ("objects.saved_all", "已将 {} 个对象保存到 {}"),
+    ("objects.save_error", "保存失败: {}"),
+    ("objects.truncated", "[已截断：解压后过大]"),
+    (
+        "flows.help",
+        "↑/↓: 选择  ←/→: 排序列  R: 反向  Tab: 活动/已结束  Enter: 过滤该流  Q/Esc: 主页",
+    ),
+    ("performance.help", "↑/↓: 滚动  R: 重置计数  Q/Esc: 主页"),
+    ("top_talkers.help", "←/→/W: 时间窗口  Q/Esc: 主页"),
+    (
+        "dns.help",
+        "↑/↓/PgUp/PgDn: 选择  U: 仅未应答  Enter: 过滤该次交互  Q/Esc: 主页",
+    ),
+    (
+        "follow_stream.help",
+        "↑/↓/PgUp/PgDn: 滚动  H: 文本/十六进制  C: 连续显示  D: 方向  Q: 返回数据包列表  Esc: 主页",
+    ),
+    (
+        "ladder.help",
+        "↑/↓/PgUp/PgDn: 滚动  Home/End: 跳转  G: 序列号/吞吐量图  D: 图表方向  Q: 返回数据包列表  Esc: 主页",
+    ),
+    ("timeline.help", "↑/↓: 选择突发  Q/Esc: 主页"),
+    ("file_summary.title", " 抓包文件摘要"),
+    ("file_summary.no_file", "未打开抓包文件"),
+    ("conversations.title", "会话 ({})"),
+    ("endpoints.title", "端点 ({})"),
+    ("endpoints.title_status", "端点 ({}) - {}"),
+    ("objects.title", "HTTP 对象 ({})"),
+    ("objects.title_status", "HTTP 对象 ({}) - {}"),
+    ("alerts.title", "告警 ({})"),
+    ("performance.title", "解码器性能"),
+    ("top_talkers.sources", "流量最多的源 (最近 {}, {})"),
+    ("top_talkers.destinations", "流量最多的目的地址 (最近 {})"),
+    ("top_talkers.ports", "流量最多的端口 (最近 {})"),
+    ("dns.names_title", "查询最多的域名 ({} 个不同)"),
+    ("dns.resolvers_title", "最慢的解析服务器"),
+    (
+        "dns.queries_title",
+        "DNS 查询 ({}, {} 个未应答, 响应中 NXDOMAIN 占 {}{})",
+    ),
+    ("dns.unanswered_shown", ", 仅显示未应答"),
+    ("http.status_title", "状态码 ({} 个请求, {} 个未应答)"),
+    ("http.urls_title", "最慢的 URL (首字节时间)"),
+    ("voip.streams_title", "RTP 流 ({})"),
+    ("voip.calls_title", "SIP 呼叫 ({})"),
+    (
+        "follow_stream.title",
+        "跟踪 UDP 流: {} ({} 个数据报, A→B {} 字节, B→A {} 字节{})",
+    ),
+    ("follow_stream.title_empty", "跟踪 UDP 流"),
+    ("follow_stream.from_a", ", 显示 A→B"),
+    ("follow_stream.from_b", ", 显示 B→A"),
+    (
+        "ladder.title",
+        "TCP 时序图: {} ({} 个分段, {} 次重传, {} 个重复 ACK)",
+    ),
+    ("ladder.title_empty", "TCP 时序图"),
+    (
+        "ladder.sequence_title",
+        "时间/序列号: {} → {} ({} 字节, {} 次重传, {} 次停滞)",
+    ),
+    ("ladder.throughput_title", "吞吐量: {} → {} ({} 字节)"),
+    ("ladder.sequence", "序列号"),
+    ("ladder.seconds", "秒"),
+    (
+        "timeline.title",
+        "流量时间线 (每 {} 毫秒峰值速率, 平均 {}, 最高 {})",
+    ),
+    ("timeline.bursts_title", "微突发 ({})"),
+    ("discovery.title", "邻居 ({} 台设备, {} 个接口)"),
+    ("dual_stack.title", "双栈连接 ({})"),
+    ("latency.title", "服务延迟 ({} 个服务器, {} 个响应已计时)"),
+    ("multicast.title", "组播组 ({} 个组, {} 次通用查询{})"),
+    ("multicast.querier", ", 查询器 {}"),
+    ("multicast.queriers", ", {} 个查询器"),
+    ("neighbors.title", "地址解析 ({} 个目标, {} 个未应答请求)"),
+    ("other_l2.title", "其他二层协议 ({} 种 EtherType)"),
+    // Replay
+    ("replay.title", " 数据包重放: {} 帧, {} "),
+    ("replay.source.captured", "所有已抓取的数据包"),
+    ("replay.source.listed", "抓包页面列出的数据包"),
+    ("replay.interface", "接口"),
+    ("replay.rate", "速率"),
+    ("replay.frames", "帧数"),
+    ("replay.none", "无"),
+    ("replay.count_once", "每帧一次 ({})"),
+    ("replay.count", "{}, 循环发送列表"),
+    ("replay.progress", "进度"),
+    ("replay.sent", "已发送 {} 帧, {} 字节"),
+    ("replay.elapsed", "用时 {} 秒, {} 帧/秒"),
+    ("replay.send_failed", "{} 帧发送失败: {}"),
+    ("replay.skipped", "已跳过 {} 个非以太网帧"),
+    ("replay.started", "正在 {} 上重放..."),
+    ("replay.stopped", "重放已停止。"),
+    ("replay.failed", "重放失败: {}"),
+    ("replay.no_interface", "没有可发送的接口。按 F5 刷新。"),
+    (
+        "replay.no_frames",
+        "没有可重放的数据包。请先抓包或打开文件。",
+    ),
+    ("replay.interfaces_failed", "列出接口失败: {}"),
+    (
+        "replay.help",
+        "↑/↓: 选择设置  ←/→: 修改  Enter: 开始/停止  F5: 刷新接口  Q/Esc: 主页",
+    ),
+];
@@ -1,22 +1,105 @@
 use anyhow::Result;
+use clap::Parser;
 use component::ComponentRender;
 use ratatui::crossterm::event::{self, Event as CrosstermEvent, KeyEventKind};
 use tokio::time::{self, Duration};
 
 mod action;
 mod app;
+mod cli;
+mod commands;
 mod component;
 mod data;
+mod i18n;
 mod pages;
+mod theme;
 mod tui;
 
 use app::App;
+use cli::Cli;
+use data::{
+    alert_rule::{AlertRule, RuleEngine},
+    capture_file,
+    capture_options::CaptureOptions,
+    flow_tracker::FlowTimeouts,
+    geoip,
+    knowledge::KnowledgeBase,
+    live, merge, plugin,
+    ring::RingWriter,
+    rtp,
+    stats_log::StatsLog,
+    time::TimeShift,
+};
 use tui::{Event, Tui};
 
 #[tokio::main]
 async fn main() -> Result<()> {
     color_eyre::install().map_err(|_| anyhow::anyhow!("Failed to install color_eyre"))?;
 
+    let cli = Cli::parse();
+    i18n::set_language(cli.lang.unwrap_or_else(i18n::Language::from_env));
+    theme::set_theme(cli.theme);
+    plugin::init()?;
+    rtp::set_heuristic(cli.rtp_heuristic);
+    let time_shift = TimeShift {
+        offset: cli.time_shift,
+        drift: cli.clock_drift,
+    };
+    if let Some(command) = cli.command {
+        return commands::run(command, time_shift, cli.compress);
+    }
+    // Flags override the options saved from the capture options dialog.
+    let mut capture_options = CaptureOptions::load();
+    capture_options.promisc &= !cli.no_promisc;
+    capture_options.immediate |= cli.immediate;
+    capture_options.snaplen = cli.snaplen.unwrap_or(capture_options.snaplen);
+    capture_options.buffer_size = cli.buffer_size.or(capture_options.buffer_size);
+    capture_options.timeout = cli.read_timeout.unwrap_or(capture_options.timeout);
+    capture_options.backend = cli.backend.unwrap_or(capture_options.backend);
+    capture_options.ring_path = cli.ring.or(capture_options.ring_path);
+    capture_options.ring_size = cli.ring_size.unwrap_or(capture_options.ring_size);
+    capture_options.ring_seconds = cli.ring_seconds.or(capture_options.ring_seconds);
+    capture_options.ring_files = cli.ring_files.unwrap_or(capture_options.ring_files);
+    let stats_log = cli
+        .stats_csv
+        .map(|path| StatsLog::create(&path, Duration::from_secs(cli.stats_interval)))
+        .transpose()?;
+    let interface = cli.interface.map(live::resolve_interface).transpose()?;
+    if cli.no_tui
+        && let Some(interface) = interface.clone()
+    {
+        return commands::headless::run(commands::headless::Options {
+            interface,
+            filter: cli.filter,
+            output: cli.output,
+            count: cli.count,
+            ring: capture_options
+                .ring_options(cli.compress)
+                .map(RingWriter::new),
+            capture_options,
+            stats_log,
+        })
+        .await;
+    }
+
+    // Open the capture file before entering the TUI so errors stay readable.
+    let mut capture = cli
+        .read
+        .map(|path| capture_file::open(&path, time_shift))
+        .transpose()?;
+    if let (Some(capture), Some(path)) = (capture.as_mut(), cli.merge) {
+        merge::merge(capture, capture_file::open(&path, time_shift)?);
+    }
+    let knowledge = cli.hosts_db.map(|path| KnowledgeBase::load(&path)).transpose()?;
+    let rules = cli
+        .alert_rule
+        .iter()
+        .map(|rule| AlertRule::parse(rule))
+        .collect::<Result<Vec<_>>>()?;
+    if !cli.geoip.is_empty() {
+        geoip::init(&cli.geoip)?;
+    }
+
     let mut tui = Tui::new()?;
     tui.enter()?;
 
@@ -24,6 +107,40 @@ async fn main() -> Result<()> {
 
     let mut app = App::new(action_tx);
     app.run().await?;
+    app.set_bell(!cli.no_bell);
+    app.sniffer_page.set_max_packets(cli.max_packets);
+    app.sniffer_page.set_memory_limit(cli.memory_limit * 1024 * 1024);
+    app.sniffer_page.set_lazy_parsing(cli.lazy);
+    app.sniffer_page.set_capture_options(capture_options);
+    app.sniffer_page.set_export_compression(cli.compress);
+    app.sniffer_page.set_snapshot_window(Duration::from_secs(cli.snapshot_seconds));
+    app.sniffer_page.set_batch_size(cli.batch_size);
+    if cli.ignore_outgoing_checksums {
+        app.sniffer_page.set_offload_addrs(live::local_addrs());
+    }
+    app.sniffer_page.set_alert_rules(RuleEngine::new(rules));
+    app.sniffer_page.set_flow_timeouts(FlowTimeouts {
+        tcp: Duration::from_secs(cli.tcp_timeout),
+        other: Duration::from_secs(cli.flow_timeout),
+    });
+    if let Some(knowledge) = knowledge {
+        app.sniffer_page.set_knowledge(knowledge);
+    }
+    if let Some(stats_log) = stats_log {
+        app.sniffer_page.set_stats_log(stats_log);
+    }
+    if cli.demo {
+        app.start_demo()?;
+    } else if let Err(e) = live::check_access() {
+        app.set_capture_unavailable(format!("{e:#}"));
+    } else if let Some(interface) = interface
+        && capture.is_none()
+    {
+        app.select_device(interface)?;
+    }
+    if let Some(capture) = capture {
+        app.open_capture(capture);
+    }
 
     let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
     let ticker_tx = tx.clone();
@@ -75,5 +192,6 @@ async fn main() -> Result<()> {
     }
 
     tui.exit()?;
+    app.sniffer_page.save_knowledge()?;
     Ok(())
 }
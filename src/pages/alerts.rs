@@ -0,0 +1,211 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::{
+        alert::{Alert, Severity},
+        dns_anomaly::DnsFinding,
+    },
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+/// One line of the page: an ARP or rule alert, or a group of DNS findings.
+#[derive(Debug, Clone)]
+struct Row {
+    index: usize, // Packet Enter shows
+    severity: Severity,
+    kind: &'static str,
+    subject: String,
+    packets: usize,
+    detail: String,
+}
+
+#[derive(Default)]
+pub struct AlertsPage {
+    rows: Vec<Row>,
+    selected: usize,
+    scroll_position: usize,
+    visible_rows: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+impl AlertsPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shows the sniffer's `alerts` followed by its DNS `findings`.
+    pub fn set_alerts(&mut self, alerts: &[Alert], findings: &[DnsFinding]) {
+        self.rows = alerts
+            .iter()
+            .map(|alert| Row {
+                index: alert.index,
+                severity: alert.severity,
+                kind: alert.kind,
+                subject: String::new(),
+                packets: 1,
+                detail: alert.message.clone(),
+            })
+            .chain(findings.iter().map(|finding| Row {
+                index: finding.index,
+                severity: Severity::Warning,
+                kind: finding.anomaly.label(),
+                subject: finding.subject.clone(),
+                packets: finding.packets,
+                detail: format!("#{}: {}", finding.id, finding.example),
+            }))
+            .collect();
+        self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+    }
+
+    fn render_alerts(&mut self, f: &mut Frame, area: Rect) {
+        let header = ListItem::new(Line::from(
+            [
+                ("Severity", 10),
+                ("Kind", 16),
+                ("Subject", 32),
+                ("Packets", 10),
+                ("Latest", 0),
+            ]
+            .iter()
+            .map(|(name, width)| {
+                Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect::<Vec<_>>(),
+        ));
+
+        self.visible_rows = (area.height as usize).saturating_sub(3);
+        if self.selected < self.scroll_position {
+            self.scroll_position = self.selected;
+        } else if self.selected >= self.scroll_position + self.visible_rows {
+            self.scroll_position = self.selected + 1 - self.visible_rows;
+        }
+
+        let mut items = vec![header];
+        items.extend(
+            self.rows
+                .iter()
+                .enumerate()
+                .skip(self.scroll_position)
+                .take(self.visible_rows)
+                .map(|(i, row)| {
+                    let base_style = if i == self.selected {
+                        Style::default()
+                            .bg(theme().selection)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default()
+                    };
+                    let severity_color = match row.severity {
+                        Severity::Critical => theme().alert,
+                        Severity::Warning => theme().warning,
+                        Severity::Info => theme().accent,
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{:<10}", row.severity.label()),
+                            base_style.fg(severity_color),
+                        ),
+                        Span::styled(format!("{:<16}", row.kind), base_style.fg(theme().text)),
+                        Span::styled(
+                            format!("{:<32.31}", row.subject),
+                            base_style.fg(theme().secondary),
+                        ),
+                        Span::styled(format!("{:<10}", row.packets), base_style.fg(theme().value)),
+                        Span::styled(row.detail.clone(), base_style.fg(theme().text)),
+                    ]))
+                }),
+        );
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("alerts.title", self.rows.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("alerts.help"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for AlertsPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down if self.selected + 1 < self.rows.len() => {
+                self.selected += 1;
+            }
+            KeyCode::PageUp => {
+                self.selected = self.selected.saturating_sub(self.visible_rows.max(1));
+            }
+            KeyCode::PageDown => {
+                self.selected = (self.selected + self.visible_rows.max(1))
+                    .min(self.rows.len().saturating_sub(1));
+            }
+            KeyCode::Enter => {
+                if let Some(row) = self.rows.get(self.selected) {
+                    return Ok(Some(Action::PacketSelected(row.index)));
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for AlertsPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_alerts(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
@@ -0,0 +1,414 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::capture_options::{Backend, CaptureOptions},
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Promisc,
+    Snaplen,
+    BufferSize,
+    Timeout,
+    Immediate,
+    Backend,
+    Ring,
+    RingSize,
+    RingSeconds,
+    RingFiles,
+}
+
+const FIELDS: [Field; 10] = [
+    Field::Promisc,
+    Field::Snaplen,
+    Field::BufferSize,
+    Field::Timeout,
+    Field::Immediate,
+    Field::Backend,
+    Field::Ring,
+    Field::RingSize,
+    Field::RingSeconds,
+    Field::RingFiles,
+];
+
+/// Edits how the next live capture opens its devices.
+pub struct CaptureOptionsDialog {
+    pub is_open: bool,
+    promisc: bool,
+    snaplen: String,
+    buffer_size: String, // Empty for libpcap's default
+    timeout: String,
+    immediate: bool,
+    backend: Backend,
+    ring: String, // Empty to write no ring files
+    ring_size: String,
+    ring_seconds: String, // Empty for no time limit
+    ring_files: String,
+    focus: Field,
+    invalid: bool, // Whether the last confirm found a bad number
+    options: Option<CaptureOptions>,
+}
+
+impl Default for CaptureOptionsDialog {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            promisc: true,
+            snaplen: String::new(),
+            buffer_size: String::new(),
+            timeout: String::new(),
+            immediate: false,
+            backend: Backend::default(),
+            ring: String::new(),
+            ring_size: String::new(),
+            ring_seconds: String::new(),
+            ring_files: String::new(),
+            focus: Field::Promisc,
+            invalid: false,
+            options: None,
+        }
+    }
+}
+
+impl CaptureOptionsDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opens the dialog on the options now in use.
+    pub fn open(&mut self, options: &CaptureOptions) {
+        self.is_open = true;
+        self.promisc = options.promisc;
+        self.snaplen = options.snaplen.to_string();
+        self.buffer_size = options
+            .buffer_size
+            .map(|size| size.to_string())
+            .unwrap_or_default();
+        self.timeout = options.timeout.to_string();
+        self.immediate = options.immediate;
+        self.backend = options.backend;
+        self.ring = options
+            .ring_path
+            .as_deref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default();
+        self.ring_size = options.ring_size.to_string();
+        self.ring_seconds = options
+            .ring_seconds
+            .map(|seconds| seconds.to_string())
+            .unwrap_or_default();
+        self.ring_files = options.ring_files.to_string();
+        self.focus = Field::Promisc;
+        self.invalid = false;
+        self.options = None;
+    }
+
+    /// The options confirmed since the last call, if any.
+    pub fn take_options(&mut self) -> Option<CaptureOptions> {
+        self.options.take()
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let position = FIELDS
+            .iter()
+            .position(|&field| field == self.focus)
+            .unwrap_or(0);
+        let len = FIELDS.len();
+        self.focus = FIELDS[if forward {
+            (position + 1) % len
+        } else {
+            (position + len - 1) % len
+        }];
+    }
+
+    /// The text of the focused number field, if a number field has focus.
+    fn text(&mut self) -> Option<&mut String> {
+        match self.focus {
+            Field::Snaplen => Some(&mut self.snaplen),
+            Field::BufferSize => Some(&mut self.buffer_size),
+            Field::Timeout => Some(&mut self.timeout),
+            Field::Ring => Some(&mut self.ring),
+            Field::RingSize => Some(&mut self.ring_size),
+            Field::RingSeconds => Some(&mut self.ring_seconds),
+            Field::RingFiles => Some(&mut self.ring_files),
+            Field::Promisc | Field::Immediate | Field::Backend => None,
+        }
+    }
+
+    /// Toggles the focused switch.
+    fn change(&mut self) {
+        match self.focus {
+            Field::Promisc => self.promisc = !self.promisc,
+            Field::Immediate => self.immediate = !self.immediate,
+            Field::Backend => {
+                self.backend = match self.backend {
+                    Backend::Libpcap => Backend::AfPacket,
+                    Backend::AfPacket => Backend::Libpcap,
+                };
+            }
+            Field::Snaplen
+            | Field::BufferSize
+            | Field::Timeout
+            | Field::Ring
+            | Field::RingSize
+            | Field::RingSeconds
+            | Field::RingFiles => {}
+        }
+    }
+
+    fn confirm(&mut self) {
+        let snaplen = self.snaplen.parse().ok().filter(|&snaplen| snaplen > 0);
+        let buffer_size = match self.buffer_size.as_str() {
+            "" => Some(None),
+            size => size.parse().ok().filter(|&size| size > 0).map(Some),
+        };
+        let timeout = self.timeout.parse().ok();
+        let ring_size = self.ring_size.parse().ok().filter(|&size| size > 0);
+        let ring_seconds = match self.ring_seconds.as_str() {
+            "" | "0" => Some(None),
+            seconds => seconds.parse().ok().map(Some),
+        };
+        let ring_files = self.ring_files.parse().ok();
+        let (
+            Some(snaplen),
+            Some(buffer_size),
+            Some(timeout),
+            Some(ring_size),
+            Some(ring_seconds),
+            Some(ring_files),
+        ) = (
+            snaplen,
+            buffer_size,
+            timeout,
+            ring_size,
+            ring_seconds,
+            ring_files,
+        )
+        else {
+            self.invalid = true;
+            return;
+        };
+        let ring = self.ring.trim();
+        self.options = Some(CaptureOptions {
+            promisc: self.promisc,
+            snaplen,
+            buffer_size,
+            timeout,
+            immediate: self.immediate,
+            backend: self.backend,
+            ring_path: (!ring.is_empty()).then(|| PathBuf::from(ring)),
+            ring_size,
+            ring_seconds,
+            ring_files,
+            ..CaptureOptions::default()
+        });
+        self.is_open = false;
+    }
+}
+
+impl Component for CaptureOptionsDialog {
+    fn register_action_handler(&mut self, _tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        if let Event::Key(key) = event {
+            self.handle_key_events(key)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Esc => self.is_open = false,
+            KeyCode::Enter => self.confirm(),
+            KeyCode::Tab | KeyCode::Down => self.move_focus(true),
+            KeyCode::BackTab | KeyCode::Up => self.move_focus(false),
+            KeyCode::Backspace => {
+                if let Some(text) = self.text() {
+                    text.pop();
+                }
+            }
+            // The ring is named by a path, the other fields by numbers.
+            KeyCode::Char(c) if self.focus == Field::Ring => self.ring.push(c),
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                if let Some(text) = self.text()
+                    && text.len() < 10
+                {
+                    text.push(c);
+                }
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') => self.change(),
+            _ => {}
+        }
+        Ok(Some(Action::Handled))
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for CaptureOptionsDialog {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        if !self.is_open {
+            return;
+        }
+        let width = area.width.min(64);
+        let height = area.height.min(15);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let check = |on: bool| if on { "[x]" } else { "[ ]" };
+        let buffer_size = if self.buffer_size.is_empty() {
+            tr!("capture_options.default").to_string()
+        } else {
+            self.buffer_size.clone()
+        };
+        let ring = if self.ring.is_empty() {
+            tr!("capture_options.ring.off").to_string()
+        } else {
+            self.ring.clone()
+        };
+        let ring_seconds = if self.ring_seconds.is_empty() {
+            tr!("capture_options.ring_seconds.none").to_string()
+        } else {
+            self.ring_seconds.clone()
+        };
+        let rows = [
+            (
+                Field::Promisc,
+                tr!("capture_options.promisc"),
+                format!(
+                    "{} {}",
+                    check(self.promisc),
+                    tr!("capture_options.promisc.about")
+                ),
+            ),
+            (
+                Field::Snaplen,
+                tr!("capture_options.snaplen"),
+                self.snaplen.clone(),
+            ),
+            (
+                Field::BufferSize,
+                tr!("capture_options.buffer_size"),
+                buffer_size,
+            ),
+            (
+                Field::Timeout,
+                tr!("capture_options.timeout"),
+                self.timeout.clone(),
+            ),
+            (
+                Field::Immediate,
+                tr!("capture_options.immediate"),
+                format!(
+                    "{} {}",
+                    check(self.immediate),
+                    tr!("capture_options.immediate.about")
+                ),
+            ),
+            (
+                Field::Backend,
+                tr!("capture_options.backend"),
+                format!(
+                    "< {} > {}",
+                    self.backend.name(),
+                    tr!("capture_options.backend.about")
+                ),
+            ),
+            (Field::Ring, tr!("capture_options.ring"), ring),
+            (
+                Field::RingSize,
+                tr!("capture_options.ring_size"),
+                self.ring_size.clone(),
+            ),
+            (
+                Field::RingSeconds,
+                tr!("capture_options.ring_seconds"),
+                ring_seconds,
+            ),
+            (
+                Field::RingFiles,
+                tr!("capture_options.ring_files"),
+                self.ring_files.clone(),
+            ),
+        ];
+        let mut lines: Vec<Line> = rows
+            .iter()
+            .map(|(field, label, value)| {
+                let style = if *field == self.focus {
+                    Style::default().bg(theme().selection).fg(theme().text)
+                } else {
+                    Style::default().fg(theme().text)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{label:<12}"),
+                        Style::default()
+                            .fg(theme().accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(value.clone(), style),
+                ])
+            })
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(if self.invalid {
+            Line::styled(
+                tr!("capture_options.invalid"),
+                Style::default().fg(theme().alert),
+            )
+        } else {
+            Line::styled(
+                tr!("capture_options.help"),
+                Style::default().fg(theme().muted),
+            )
+        });
+
+        let dialog = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr!("capture_options.title"))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+                .style(Style::default().bg(theme().background)),
+        );
+        f.render_widget(Clear, popup);
+        f.render_widget(dialog, popup);
+        let row = FIELDS
+            .iter()
+            .position(|&field| field == self.focus)
+            .unwrap_or(0) as u16;
+        if let Some(text) = self.text() {
+            let x = popup.x + 13 + text.chars().count() as u16;
+            if x < popup.x + popup.width - 1 {
+                f.set_cursor_position(ratatui::layout::Position {
+                    x,
+                    y: popup.y + 1 + row,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::{
+        conversation::{ConversationStats, SortColumn},
+        packet::Transport,
+    },
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+const WIDTHS: [usize; 12] = [7, 28, 28, 10, 12, 10, 12, 10, 12, 11, 12, 12];
+
+#[derive(Default)]
+pub struct ConversationsPage {
+    stats: ConversationStats,
+    sort_column: SortColumn,
+    reversed: bool,
+    selected: usize,
+    scroll_position: usize,
+    visible_rows: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+fn format_rate(bits_per_second: Option<f64>) -> String {
+    match bits_per_second {
+        Some(bps) if bps >= 1e6 => format!("{:.1} M", bps / 1e6),
+        Some(bps) if bps >= 1e3 => format!("{:.1} k", bps / 1e3),
+        Some(bps) => format!("{bps:.0}"),
+        None => "-".to_string(),
+    }
+}
+
+impl ConversationsPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_stats(&mut self, mut stats: ConversationStats) {
+        stats.sort(self.sort_column, self.reversed);
+        self.stats = stats;
+        self.selected = self
+            .selected
+            .min(self.stats.conversations.len().saturating_sub(1));
+    }
+
+    fn resort(&mut self) {
+        self.stats.sort(self.sort_column, self.reversed);
+        self.selected = 0;
+        self.scroll_position = 0;
+    }
+
+    fn render_conversations(&mut self, f: &mut Frame, area: Rect) {
+        let header = ListItem::new(Line::from(
+            SortColumn::ALL
+                .iter()
+                .zip(WIDTHS)
+                .map(|(&column, width)| {
+                    let mut title = column.title().to_string();
+                    let mut style = Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD);
+                    if column == self.sort_column {
+                        title.push(if self.reversed { '▲' } else { '▼' });
+                        style = style.fg(theme().highlight);
+                    }
+                    Span::styled(format!("{title:<width$}"), style)
+                })
+                .collect::<Vec<_>>(),
+        ));
+
+        self.visible_rows = (area.height as usize).saturating_sub(3);
+        if self.selected < self.scroll_position {
+            self.scroll_position = self.selected;
+        } else if self.selected >= self.scroll_position + self.visible_rows {
+            self.scroll_position = self.selected + 1 - self.visible_rows;
+        }
+
+        let mut items = vec![header];
+        items.extend(
+            self.stats
+                .conversations
+                .iter()
+                .enumerate()
+                .skip(self.scroll_position)
+                .take(self.visible_rows)
+                .map(|(i, conversation)| {
+                    let protocol = match conversation.key.transport {
+                        Some(Transport::Tcp) => "TCP",
+                        Some(Transport::Udp) => "UDP",
+                        Some(Transport::Icmpv4) => "ICMP",
+                        Some(Transport::Icmpv6) => "ICMPv6",
+                        None if conversation.key.a.addr.is_ok() => "IP",
+                        None => "L2",
+                    };
+                    let cells = [
+                        (protocol.to_string(), theme().accent),
+                        (conversation.key.a.to_string(), theme().secondary),
+                        (conversation.key.b.to_string(), theme().secondary),
+                        (conversation.a_to_b.packets.to_string(), theme().value),
+                        (conversation.a_to_b.bytes.to_string(), theme().value),
+                        (conversation.b_to_a.packets.to_string(), theme().value),
+                        (conversation.b_to_a.bytes.to_string(), theme().value),
+                        (conversation.packets().to_string(), theme().value),
+                        (conversation.bytes().to_string(), theme().value),
+                        (
+                            format!("{:.3} s", conversation.duration().as_secs_f64()),
+                            theme().muted,
+                        ),
+                        (
+                            format_rate(conversation.throughput(&conversation.a_to_b)),
+                            theme().highlight,
+                        ),
+                        (
+                            format_rate(conversation.throughput(&conversation.b_to_a)),
+                            theme().highlight,
+                        ),
+                    ];
+                    let base_style = if i == self.selected {
+                        Style::default()
+                            .bg(theme().selection)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default()
+                    };
+                    ListItem::new(Line::from(
+                        cells
+                            .into_iter()
+                            .zip(WIDTHS)
+                            .map(|((text, color), width)| {
+                                let text: String = text.chars().take(width - 1).collect();
+                                Span::styled(format!("{text:<width$}"), base_style.fg(color))
+                            })
+                            .collect::<Vec<_>>(),
+                    ))
+                }),
+        );
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("conversations.title", self.stats.conversations.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("conversations.help"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for ConversationsPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down if self.selected + 1 < self.stats.conversations.len() => {
+                self.selected += 1;
+            }
+            KeyCode::PageUp => {
+                self.selected = self.selected.saturating_sub(self.visible_rows.max(1));
+            }
+            KeyCode::PageDown => {
+                self.selected = (self.selected + self.visible_rows.max(1))
+                    .min(self.stats.conversations.len().saturating_sub(1));
+            }
+            KeyCode::Left => {
+                self.sort_column = self.sort_column.previous();
+                self.resort();
+            }
+            KeyCode::Right => {
+                self.sort_column = self.sort_column.next();
+                self.resort();
+            }
+            KeyCode::Char('r') => {
+                self.reversed = !self.reversed;
+                self.resort();
+            }
+            KeyCode::Enter => {
+                if let Some(conversation) = self.stats.conversations.get(self.selected) {
+                    return Ok(Some(Action::ApplyDisplayFilter(
+                        conversation.display_filter(),
+                    )));
+                }
+            }
+            KeyCode::Char('e') => {
+                if let Some(conversation) = self.stats.conversations.get(self.selected) {
+                    return Ok(Some(Action::ExportFlow(conversation.key.clone())));
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for ConversationsPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_conversations(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
@@ -1,9 +1,11 @@
+use std::{fs, ops::Range};
+
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEventKind};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
 };
@@ -12,14 +14,51 @@ use tokio::sync::mpsc;
 use crate::{
     action::Action,
     component::{Component, ComponentRender},
-    data::packet::PacketInfo,
+    data::{
+        geoip, headers,
+        icmp::LinkedPacket,
+        layer::{Layer, Verbosity},
+        packet::PacketInfo,
+        packet_export::{hex_dump, hex_row},
+    },
+    i18n::tr,
+    theme::theme,
     tui::Event,
 };
 
+/// What the save prompt writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SaveKind {
+    HexDump, // Offset, hex and ASCII of the whole frame, as text
+    Payload, // The transport payload as it is
+    Comment, // The packet's comment, kept by the sniffer rather than a file
+}
+
+/// A line of the protocol tree: a layer, or one of its fields by index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Row {
+    Layer(usize),
+    Field(usize, usize),
+}
+
 #[derive(Default)]
 pub struct PacketDetailsPage {
     packet: Option<PacketInfo>,
     hex_scroll: usize,
+    verbosity: Verbosity,
+    reported_on: Option<LinkedPacket>,
+    icmp_errors: Vec<LinkedPacket>,
+    highlights: Vec<Range<usize>>, // Search matches in the frame
+    tree: Vec<Layer>,              // Header layers, then the decoded protocols
+    collapsed: Vec<bool>,          // Per layer of the tree
+    selected: usize,               // Row of the tree under the cursor
+    tree_focused: bool,            // Whether ↑/↓ move in the tree rather than scroll the hex
+    hex_area: Rect,                // Where the hex viewer was last rendered
+    // What the save prompt writes and the file name typed, while it is open
+    save_input: Option<(SaveKind, String)>,
+    message: Option<String>, // Outcome of the last save, until the next key
+    offloaded: bool,         // Sent from this host, its bad checksums left to the NIC
+    comment: Option<String>,
     action_tx: Option<mpsc::UnboundedSender<Action>>,
 }
 
@@ -28,9 +67,219 @@ impl PacketDetailsPage {
         Self::default()
     }
 
-    pub fn set_packet(&mut self, packet: PacketInfo) {
+    pub fn set_packet(&mut self, packet: PacketInfo, offloaded: bool) {
+        // Headers start collapsed so the decoded protocols stay in view.
+        let headers = headers::layers(&packet);
+        self.collapsed = vec![true; headers.len()];
+        self.collapsed
+            .resize(headers.len() + packet.layers.len(), false);
+        self.tree = headers;
+        self.tree.extend(packet.layers.iter().cloned());
+        self.selected = 0;
         self.packet = Some(packet);
+        self.offloaded = offloaded;
         self.hex_scroll = 0;
+        self.save_input = None;
+        self.message = None;
+    }
+
+    pub fn set_comment(&mut self, comment: Option<&str>) {
+        self.comment = comment.map(str::to_string);
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.save_input.is_some()
+    }
+
+    /// Writes the hex dump or the payload of the packet to `path`.
+    fn save(&mut self, kind: SaveKind, path: &str) {
+        let Some(ref packet) = self.packet else {
+            return;
+        };
+        let written = match kind {
+            SaveKind::HexDump => fs::write(path, hex_dump(&packet.data))
+                .map(|()| tr!("detail.saved", packet.data.len(), path)),
+            SaveKind::Payload => fs::write(path, packet.payload())
+                .map(|()| tr!("detail.payload_saved", packet.payload().len(), path)),
+            SaveKind::Comment => return,
+        };
+        self.message = Some(written.unwrap_or_else(|e| tr!("detail.save_error", path, e)));
+    }
+
+    /// Edits the open prompt, returning the comment to attach once one is
+    /// entered.
+    fn handle_save_input(&mut self, key: KeyEvent) -> Option<Action> {
+        let (kind, ref mut input) = *self.save_input.as_mut()?;
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.save_input = None,
+            KeyCode::Enter => {
+                let input = self.save_input.take().unwrap_or((kind, String::new())).1;
+                // An empty comment removes the one there was.
+                if kind == SaveKind::Comment {
+                    return Some(Action::CommentPacket(input));
+                }
+                if !input.trim().is_empty() {
+                    self.save(kind, input.trim());
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Packets linked to the shown one through ICMP errors: the packet it
+    /// reports on if it is an error, and the errors reporting on it.
+    pub fn set_related(&mut self, reported_on: Option<LinkedPacket>, errors: Vec<LinkedPacket>) {
+        self.reported_on = reported_on;
+        self.icmp_errors = errors;
+    }
+
+    /// Highlights `matches` in the hex viewer, scrolled to the first one.
+    pub fn set_highlights(&mut self, matches: Vec<Range<usize>>) {
+        if let (Some(first), Some(packet)) = (matches.first(), &self.packet) {
+            let max_scroll = (packet.data.len() / 16).saturating_sub(10);
+            self.hex_scroll = (first.start / 16).min(max_scroll);
+        }
+        self.highlights = matches;
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = Vec::new();
+        for (i, layer) in self.tree.iter().enumerate() {
+            rows.push(Row::Layer(i));
+            if !self.collapsed[i] {
+                rows.extend(
+                    layer
+                        .fields
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, field)| field.is_visible(self.verbosity))
+                        .map(|(j, _)| Row::Field(i, j)),
+                );
+            }
+        }
+        rows
+    }
+
+    /// Bytes of the frame the row under the cursor was read from, a field
+    /// without known bytes standing for its whole layer.
+    fn selected_range(&self) -> Option<Range<usize>> {
+        if !self.tree_focused {
+            return None;
+        }
+        match *self.rows().get(self.selected)? {
+            Row::Layer(i) => self.tree[i].range.clone(),
+            Row::Field(i, j) => {
+                let layer = &self.tree[i];
+                layer.fields[j].range.clone().or(layer.range.clone())
+            }
+        }
+    }
+
+    fn max_hex_scroll(&self) -> usize {
+        self.packet
+            .as_ref()
+            .map_or(0, |packet| (packet.data.len() / 16).saturating_sub(10))
+    }
+
+    /// Scrolls the hex viewer to the selected bytes unless they are shown.
+    fn reveal_selection(&mut self) {
+        let Some(range) = self.selected_range() else {
+            return;
+        };
+        let line = range.start / 16;
+        let visible = (self.hex_area.height as usize).saturating_sub(3).max(1);
+        if line < self.hex_scroll || line >= self.hex_scroll + visible {
+            self.hex_scroll = line.min(self.max_hex_scroll());
+        }
+    }
+
+    /// Sets whether the layer of the row under the cursor is collapsed,
+    /// keeping the cursor on it.
+    fn collapse(&mut self, collapsed: bool) {
+        let (Row::Layer(i) | Row::Field(i, _)) = match self.rows().get(self.selected) {
+            Some(&row) => row,
+            None => return,
+        };
+        self.collapsed[i] = collapsed;
+        self.selected = self
+            .rows()
+            .iter()
+            .position(|&row| row == Row::Layer(i))
+            .unwrap_or(0);
+    }
+
+    /// The frame offset of the byte at a screen position of the hex viewer,
+    /// in either its hex or its ASCII column.
+    fn byte_at(&self, column: u16, row: u16) -> Option<usize> {
+        let area = self.hex_area;
+        // Inside the border, below the column titles
+        if column <= area.x || row < area.y + 2 || row + 1 >= area.y + area.height {
+            return None;
+        }
+        let line = self.hex_scroll + usize::from(row - area.y - 2);
+        let x = usize::from(column - area.x - 1);
+        // Offset, then groups of four bytes, then the ASCII column
+        let index = match x {
+            16..51 => {
+                let x = x - 16;
+                let (group, within) = (x / 9, x % 9);
+                (within < 8).then_some(group * 4 + within / 2)?
+            }
+            58..74 => x - 58,
+            _ => return None,
+        };
+        let offset = line * 16 + index;
+        (offset < self.packet.as_ref()?.data.len()).then_some(offset)
+    }
+
+    /// Selects the narrowest field covering the byte at `offset`, else the
+    /// narrowest layer, opening its layer if needed.
+    fn select_byte(&mut self, offset: usize) {
+        let covers = |range: &Option<Range<usize>>| {
+            range
+                .as_ref()
+                .filter(|range| range.contains(&offset))
+                .map(|range| range.len())
+        };
+        let field = self
+            .tree
+            .iter()
+            .enumerate()
+            .flat_map(|(i, layer)| {
+                layer
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, field)| field.is_visible(self.verbosity))
+                    .filter_map(move |(j, field)| Some((covers(&field.range)?, Row::Field(i, j))))
+            })
+            .min_by_key(|&(len, _)| len);
+        let layer = self
+            .tree
+            .iter()
+            .enumerate()
+            .filter_map(|(i, layer)| Some((covers(&layer.range)?, Row::Layer(i))))
+            .min_by_key(|&(len, _)| len);
+        let Some((_, row)) = field.or(layer) else {
+            return;
+        };
+        if let Row::Field(i, _) = row {
+            self.collapsed[i] = false;
+        }
+        if let Some(position) = self.rows().iter().position(|&r| r == row) {
+            self.selected = position;
+            self.tree_focused = true;
+        }
+    }
+
+    /// The packet 'r' jumps to.
+    fn related(&self) -> Option<LinkedPacket> {
+        self.reported_on.or(self.icmp_errors.first().copied())
     }
 
     fn render_packet_info(&self, f: &mut Frame, area: Rect) {
@@ -40,45 +289,63 @@ impl PacketDetailsPage {
                     Span::styled(
                         "Packet ID: ",
                         Style::default()
-                            .fg(Color::Cyan)
+                            .fg(theme().accent)
                             .add_modifier(Modifier::BOLD),
                     ),
-                    Span::styled(packet.id.to_string(), Style::default().fg(Color::White)),
+                    Span::styled(packet.id.to_string(), Style::default().fg(theme().text)),
                 ]),
                 Line::from(vec![
                     Span::styled(
                         "Timestamp: ",
                         Style::default()
-                            .fg(Color::Cyan)
+                            .fg(theme().accent)
                             .add_modifier(Modifier::BOLD),
                     ),
-                    Span::styled(packet.timestamp.clone(), Style::default().fg(Color::White)),
+                    Span::styled(
+                        format!("{:.6}", packet.timestamp.as_secs_f64()),
+                        Style::default().fg(theme().text),
+                    ),
                 ]),
                 Line::from(vec![
                     Span::styled(
                         "Protocol: ",
                         Style::default()
-                            .fg(Color::Cyan)
+                            .fg(theme().accent)
                             .add_modifier(Modifier::BOLD),
                     ),
-                    Span::styled(packet.protocol.clone(), Style::default().fg(Color::Yellow)),
+                    Span::styled(
+                        packet.protocol.clone(),
+                        Style::default().fg(theme().highlight),
+                    ),
                 ]),
                 Line::from(vec![
                     Span::styled(
                         "Length: ",
                         Style::default()
-                            .fg(Color::Cyan)
+                            .fg(theme().accent)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::styled(
                         format!("{} bytes", packet.length),
-                        Style::default().fg(Color::Green),
+                        Style::default().fg(theme().value),
                     ),
                 ]),
             ];
 
             let mut info_text = info_lines;
 
+            if let Some(ref comment) = self.comment {
+                info_text.push(Line::from(vec![
+                    Span::styled(
+                        "Comment: ",
+                        Style::default()
+                            .fg(theme().accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(comment.clone(), Style::default().fg(theme().highlight)),
+                ]));
+            }
+
             if let Some(ref src) = packet.src_addr {
                 match src {
                     Ok(src_ip) => {
@@ -87,12 +354,12 @@ impl PacketDetailsPage {
                                 Span::styled(
                                     "Source: ",
                                     Style::default()
-                                        .fg(Color::Cyan)
+                                        .fg(theme().accent)
                                         .add_modifier(Modifier::BOLD),
                                 ),
                                 Span::styled(
                                     format!("{src_ip}:{src_port}"),
-                                    Style::default().fg(Color::Magenta),
+                                    Style::default().fg(theme().secondary),
                                 ),
                             ])
                         } else {
@@ -100,10 +367,13 @@ impl PacketDetailsPage {
                                 Span::styled(
                                     "Source IP: ",
                                     Style::default()
-                                        .fg(Color::Cyan)
+                                        .fg(theme().accent)
                                         .add_modifier(Modifier::BOLD),
                                 ),
-                                Span::styled(src_ip.to_string(), Style::default().fg(Color::Magenta)),
+                                Span::styled(
+                                    src_ip.to_string(),
+                                    Style::default().fg(theme().secondary),
+                                ),
                             ])
                         };
                         info_text.push(src_line);
@@ -113,10 +383,10 @@ impl PacketDetailsPage {
                             Span::styled(
                                 "Source MAC: ",
                                 Style::default()
-                                    .fg(Color::Cyan)
+                                    .fg(theme().accent)
                                     .add_modifier(Modifier::BOLD),
                             ),
-                            Span::styled(src_mac, Style::default().fg(Color::Magenta)),
+                            Span::styled(src_mac, Style::default().fg(theme().secondary)),
                         ]);
                         info_text.push(src_line);
                     }
@@ -131,12 +401,12 @@ impl PacketDetailsPage {
                                 Span::styled(
                                     "Destination: ",
                                     Style::default()
-                                        .fg(Color::Cyan)
+                                        .fg(theme().accent)
                                         .add_modifier(Modifier::BOLD),
                                 ),
                                 Span::styled(
                                     format!("{dst_ip}:{dst_port}"),
-                                    Style::default().fg(Color::Magenta),
+                                    Style::default().fg(theme().secondary),
                                 ),
                             ])
                         } else {
@@ -144,10 +414,13 @@ impl PacketDetailsPage {
                                 Span::styled(
                                     "Destination IP: ",
                                     Style::default()
-                                        .fg(Color::Cyan)
+                                        .fg(theme().accent)
                                         .add_modifier(Modifier::BOLD),
                                 ),
-                                Span::styled(dst_ip.to_string(), Style::default().fg(Color::Magenta)),
+                                Span::styled(
+                                    dst_ip.to_string(),
+                                    Style::default().fg(theme().secondary),
+                                ),
                             ])
                         };
                         info_text.push(dst_line);
@@ -157,50 +430,200 @@ impl PacketDetailsPage {
                             Span::styled(
                                 "Destination MAC: ",
                                 Style::default()
-                                    .fg(Color::Cyan)
+                                    .fg(theme().accent)
                                     .add_modifier(Modifier::BOLD),
                             ),
-                            Span::styled(dst_mac, Style::default().fg(Color::Magenta)),
+                            Span::styled(dst_mac, Style::default().fg(theme().secondary)),
                         ]);
                         info_text.push(dst_line);
                     }
                 }
             }
 
+            for (label, addr) in [
+                ("Source Location: ", &packet.src_addr),
+                ("Destination Location: ", &packet.dst_addr),
+            ] {
+                if let Some(Ok(ip)) = addr
+                    && let Some(location) = geoip::lookup(*ip)
+                {
+                    info_text.push(Line::from(vec![
+                        Span::styled(
+                            label,
+                            Style::default()
+                                .fg(theme().accent)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(location.describe(), Style::default().fg(theme().value)),
+                    ]));
+                }
+            }
+
             let paragraph = Paragraph::new(info_text)
                 .block(
                     Block::default()
-                        .title(" Packet Information")
+                        .title(tr!("detail.info_title"))
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::Blue)),
+                        .border_style(Style::default().fg(theme().border)),
                 )
                 .wrap(Wrap { trim: true });
 
             f.render_widget(paragraph, area);
         } else {
-            let no_packet = Paragraph::new("No packet selected")
+            let no_packet = Paragraph::new(tr!("detail.no_packet"))
                 .block(
                     Block::default()
-                        .title(" Packet Information")
+                        .title(tr!("detail.info_title"))
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::Red)),
+                        .border_style(Style::default().fg(theme().alert)),
                 )
-                .style(Style::default().fg(Color::Gray));
+                .style(Style::default().fg(theme().muted));
 
             f.render_widget(no_packet, area);
         }
     }
 
+    fn layer_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        if let Some(ref packet) = self.packet {
+            for (n, row) in self.rows().into_iter().enumerate() {
+                let cursor = if self.tree_focused && n == self.selected {
+                    Modifier::REVERSED
+                } else {
+                    Modifier::empty()
+                };
+                let line = match row {
+                    Row::Layer(i) => {
+                        let layer = &self.tree[i];
+                        let marker = if self.collapsed[i] { '▸' } else { '▾' };
+                        Line::from(vec![Span::styled(
+                            format!("{marker} {}: {}", layer.name, layer.summary),
+                            Style::default()
+                                .fg(theme().highlight)
+                                .add_modifier(Modifier::BOLD | cursor),
+                        )])
+                    }
+                    Row::Field(i, j) => {
+                        let field = &self.tree[i].fields[j];
+                        Line::from(vec![
+                            Span::styled(
+                                format!("    {}: ", field.name),
+                                Style::default().fg(theme().accent).add_modifier(cursor),
+                            ),
+                            Span::styled(
+                                field.value.clone(),
+                                Style::default().fg(theme().text).add_modifier(cursor),
+                            ),
+                        ])
+                    }
+                };
+                lines.push(line);
+            }
+            if !packet.bad_checksums.is_empty() {
+                let (heading, color) = if self.offloaded {
+                    (
+                        "Checksums (sent by this host, likely offloaded)",
+                        theme().muted,
+                    )
+                } else {
+                    ("Checksums", theme().warning)
+                };
+                lines.push(Line::from(vec![Span::styled(
+                    heading,
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                )]));
+                for bad in &packet.bad_checksums {
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("  {}: ", bad.header),
+                            Style::default().fg(theme().accent),
+                        ),
+                        Span::styled(
+                            format!("0x{:04x}, should be 0x{:04x}", bad.found, bad.expected),
+                            Style::default().fg(color),
+                        ),
+                    ]));
+                }
+            }
+            if self.verbosity == Verbosity::Raw && !packet.payload().is_empty() {
+                lines.push(Line::from(vec![Span::styled(
+                    format!("Payload: {} bytes", packet.payload().len()),
+                    Style::default()
+                        .fg(theme().highlight)
+                        .add_modifier(Modifier::BOLD),
+                )]));
+                for (i, chunk) in packet.payload().chunks(16).enumerate() {
+                    let (hex, ascii) = hex_row(chunk);
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("  {:04x}  ", i * 16),
+                            Style::default().fg(theme().accent),
+                        ),
+                        Span::styled(hex, Style::default().fg(theme().text)),
+                        Span::styled(ascii, Style::default().fg(theme().muted)),
+                    ]));
+                }
+            }
+            if self.reported_on.is_some() || !self.icmp_errors.is_empty() {
+                lines.push(Line::from(vec![Span::styled(
+                    "Related Packets",
+                    Style::default()
+                        .fg(theme().highlight)
+                        .add_modifier(Modifier::BOLD),
+                )]));
+            }
+            if let Some(linked) = self.reported_on {
+                lines.push(Line::from(vec![
+                    Span::styled("  Reports on: ", Style::default().fg(theme().accent)),
+                    Span::styled(format!("#{}", linked.id), Style::default().fg(theme().text)),
+                ]));
+            }
+            if !self.icmp_errors.is_empty() {
+                let ids: Vec<String> = self
+                    .icmp_errors
+                    .iter()
+                    .map(|linked| format!("#{}", linked.id))
+                    .collect();
+                lines.push(Line::from(vec![
+                    Span::styled("  ICMP errors: ", Style::default().fg(theme().accent)),
+                    Span::styled(ids.join(", "), Style::default().fg(theme().alert)),
+                ]));
+            }
+        }
+        lines
+    }
+
+    fn render_layers(&self, f: &mut Frame, area: Rect) {
+        // Keep the cursor in view when the tree is taller than its area.
+        let scroll = if self.tree_focused {
+            self.selected
+                .saturating_sub((area.height as usize).saturating_sub(3))
+        } else {
+            0
+        };
+        let paragraph = Paragraph::new(self.layer_lines())
+            .scroll((scroll as u16, 0))
+            .block(
+                Block::default()
+                    .title(tr!("detail.tree_title", self.verbosity.label()))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().border)),
+            )
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(paragraph, area);
+    }
+
     fn render_hex_viewer(&self, f: &mut Frame, area: Rect) {
         if self.packet.is_none() {
-            let no_packet = Paragraph::new("No packet selected")
+            let no_packet = Paragraph::new(tr!("detail.no_packet"))
                 .block(
                     Block::default()
-                        .title(" Hex Viewer")
+                        .title(tr!("detail.hex_title"))
                         .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::Red)),
+                        .border_style(Style::default().fg(theme().alert)),
                 )
-                .style(Style::default().fg(Color::Gray));
+                .style(Style::default().fg(theme().muted));
 
             f.render_widget(no_packet, area);
             return;
@@ -213,19 +636,19 @@ impl PacketDetailsPage {
             Span::styled(
                 format!(" {:^9}", "Offset"),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 format!("{:^48}", "Hex"),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 format!("{:^16}", "ASCII"),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
         ])));
@@ -233,6 +656,8 @@ impl PacketDetailsPage {
         let bytes_per_line = 16;
         let visible_lines = (area.height as usize).saturating_sub(3); // Account for borders and header
         let start_offset = self.hex_scroll * bytes_per_line;
+        let selected = self.selected_range().unwrap_or_default();
+        let selected_style = Style::default().fg(theme().background).bg(theme().accent);
         let end_offset = std::cmp::min(
             start_offset + (visible_lines * bytes_per_line),
             packet.data.len(),
@@ -242,55 +667,100 @@ impl PacketDetailsPage {
             let end = std::cmp::min(offset + bytes_per_line, packet.data.len());
             let line_data = &packet.data[offset..end];
 
-            let mut hex_str = String::new();
-            let mut ascii_str = String::new();
-
-            hex_str.push_str("      ");
+            let highlight = Style::default()
+                .fg(theme().background)
+                .bg(theme().highlight);
+            let mut spans = vec![
+                Span::styled(
+                    format!(" {offset:08x}"),
+                    Style::default().fg(theme().highlight),
+                ),
+                Span::raw(" "),
+                Span::raw("      "),
+            ];
+            let mut ascii_spans = Vec::new();
+            let mut hex_width = 6;
             for (i, &byte) in line_data.iter().enumerate() {
                 if i > 0 && i % 4 == 0 {
-                    hex_str.push(' ');
+                    spans.push(Span::raw(" "));
+                    hex_width += 1;
                 }
-                hex_str.push_str(&format!("{byte:02x}"));
+                let matched = self
+                    .highlights
+                    .iter()
+                    .any(|range| range.contains(&(offset + i)));
+                let style = if selected.contains(&(offset + i)) {
+                    Some(selected_style)
+                } else if matched {
+                    Some(highlight)
+                } else {
+                    None
+                };
+                spans.push(Span::styled(
+                    format!("{byte:02x}"),
+                    style.unwrap_or(Style::default().fg(theme().value)),
+                ));
+                hex_width += 2;
 
                 // ASCII representation
-                if byte.is_ascii_graphic() || byte == b' ' {
-                    ascii_str.push(byte as char);
+                let ascii = if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
                 } else {
-                    ascii_str.push('.');
-                }
+                    '.'
+                };
+                ascii_spans.push(Span::styled(
+                    ascii.to_string(),
+                    style.unwrap_or(Style::default().fg(theme().accent)),
+                ));
             }
 
-            // Pad hex string to maintain alignment
-            while hex_str.len() < 47 {
-                hex_str.push(' ');
-            }
-
-            let line = Line::from(vec![
-                Span::styled(format!(" {offset:08x}"), Style::default().fg(Color::Yellow)),
-                Span::raw(" "),
-                Span::styled(hex_str, Style::default().fg(Color::Green)),
-                Span::raw(" "),
-                Span::styled(ascii_str, Style::default().fg(Color::Cyan)),
-            ]);
+            // Pad hex columns to maintain alignment
+            spans.push(Span::raw(" ".repeat(47usize.saturating_sub(hex_width) + 1)));
+            spans.extend(ascii_spans);
+            let line = Line::from(spans);
 
             hex_lines.push(ListItem::new(line));
         }
 
         let hex_list = List::new(hex_lines).block(
             Block::default()
-                .title(format!(" Hex Viewer ({} bytes)", packet.data.len()))
+                .title(tr!("detail.hex_title_bytes", packet.data.len()))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Blue)),
+                .border_style(Style::default().fg(theme().border)),
         );
 
         f.render_widget(hex_list, area);
     }
 
     fn render_help(&self, f: &mut Frame, area: Rect) {
-        let help_text = "↑/↓: Scroll Hex  Q: Back to Sniffer  Esc: Back to Home";
+        if let Some((kind, ref input)) = self.save_input {
+            let prompt = match kind {
+                SaveKind::HexDump => tr!("detail.save_prompt"),
+                SaveKind::Payload => tr!("detail.payload_prompt"),
+                SaveKind::Comment => tr!("detail.comment_prompt"),
+            };
+            let line = Line::from(vec![
+                Span::styled(prompt, Style::default().fg(theme().highlight)),
+                Span::styled(input.clone(), Style::default().fg(theme().text)),
+            ]);
+            let x = area.x + line.width() as u16;
+            f.render_widget(Paragraph::new(line), area);
+            if x < area.x + area.width {
+                f.set_cursor_position(ratatui::layout::Position { x, y: area.y });
+            }
+            return;
+        }
+        if let Some(ref message) = self.message {
+            let message = Paragraph::new(message.as_str())
+                .style(Style::default().fg(theme().highlight))
+                .alignment(ratatui::layout::Alignment::Center);
+            f.render_widget(message, area);
+            return;
+        }
+        let help_text = tr!("detail.help");
 
         let help = Paragraph::new(help_text)
-            .style(Style::default().fg(Color::Cyan))
+            .style(Style::default().fg(theme().accent))
             .wrap(Wrap { trim: true })
             .alignment(ratatui::layout::Alignment::Center)
             .block(Block::default().borders(Borders::NONE));
@@ -308,19 +778,72 @@ impl Component for PacketDetailsPage {
     fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
         match event {
             Event::Key(key_event) => self.handle_key_events(key_event),
+            Event::Mouse(mouse_event) => {
+                if let MouseEventKind::Down(MouseButton::Left) = mouse_event.kind
+                    && let Some(offset) = self.byte_at(mouse_event.column, mouse_event.row)
+                {
+                    self.select_byte(offset);
+                }
+                Ok(None)
+            }
             _ => Ok(None),
         }
     }
 
     fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.save_input.is_some() {
+            return Ok(Some(self.handle_save_input(key).unwrap_or(Action::Handled)));
+        }
+        self.message = None;
         let packet = match self.packet {
             Some(ref p) => p,
             None => return Ok(None),
         };
         match key.code {
+            KeyCode::Char('x') => {
+                self.save_input = Some((SaveKind::HexDump, format!("packet-{}.txt", packet.id)));
+            }
+            KeyCode::Char('p') if !packet.payload().is_empty() => {
+                let name = format!("packet-{}-payload.bin", packet.id);
+                self.save_input = Some((SaveKind::Payload, name));
+            }
+            KeyCode::Char('c') => {
+                let comment = self.comment.clone().unwrap_or_default();
+                self.save_input = Some((SaveKind::Comment, comment));
+            }
             KeyCode::Char('q') => {
                 return Ok(Some(Action::NavigateToSniffer));
             }
+            KeyCode::Char('v') => {
+                self.verbosity = self.verbosity.next();
+                self.selected = self.selected.min(self.rows().len().saturating_sub(1));
+            }
+            KeyCode::Tab => {
+                self.tree_focused = !self.tree_focused;
+                self.reveal_selection();
+            }
+            KeyCode::Up if self.tree_focused => {
+                self.selected = self.selected.saturating_sub(1);
+                self.reveal_selection();
+            }
+            KeyCode::Down if self.tree_focused => {
+                if self.selected + 1 < self.rows().len() {
+                    self.selected += 1;
+                }
+                self.reveal_selection();
+            }
+            KeyCode::Left if self.tree_focused => self.collapse(true),
+            KeyCode::Right if self.tree_focused => self.collapse(false),
+            KeyCode::Enter if self.tree_focused => {
+                if let Some(&Row::Layer(i)) = self.rows().get(self.selected) {
+                    self.collapse(!self.collapsed[i]);
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some(linked) = self.related() {
+                    return Ok(Some(Action::PacketSelected(linked.index)));
+                }
+            }
             KeyCode::Up => {
                 if self.hex_scroll > 0 {
                     self.hex_scroll -= 1;
@@ -358,17 +881,29 @@ impl Component for PacketDetailsPage {
 
 impl ComponentRender<()> for PacketDetailsPage {
     fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let layer_lines = self.layer_lines().len();
+        let layer_height = if layer_lines > 0 {
+            layer_lines as u16 + 2
+        } else {
+            0
+        };
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(8), // Packet info
-                Constraint::Min(10),   // Hex viewer
-                Constraint::Length(1), // Help
+                Constraint::Length(8),            // Packet info
+                Constraint::Length(layer_height), // Decoded protocols
+                Constraint::Min(10),              // Hex viewer
+                Constraint::Length(1),            // Help
             ])
             .split(area);
 
         self.render_packet_info(f, chunks[0]);
-        self.render_hex_viewer(f, chunks[1]);
-        self.render_help(f, chunks[2]);
+        if layer_height > 0 {
+            self.render_layers(f, chunks[1]);
+        }
+        self.hex_area = chunks[2];
+        self.render_hex_viewer(f, chunks[2]);
+        self.render_help(f, chunks[3]);
     }
 }
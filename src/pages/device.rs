@@ -1,20 +1,51 @@
+use std::{
+    collections::HashSet,
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEventKind};
-use pcap::Device;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
 };
 
 use crate::{
     action::Action,
     component::{Component, ComponentRender},
+    data::{
+        device::Device,
+        live,
+        rpcap::{self, Auth},
+    },
+    i18n::tr,
+    theme::theme,
     tui::Event,
 };
 
+/// How often the device list is checked for interfaces coming and going.
+const HOTPLUG_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Most lines of the detail panel, so a device with many addresses leaves
+/// room for the list.
+const MAX_DETAIL_LINES: usize = 8;
+
+/// Prefix length of a netmask, None unless its ones are contiguous.
+fn prefix_len(netmask: IpAddr) -> Option<u32> {
+    let (ones, bits) = match netmask {
+        IpAddr::V4(mask) => (u32::from(mask).leading_ones(), u32::from(mask).count_ones()),
+        IpAddr::V6(mask) => (
+            u128::from(mask).leading_ones(),
+            u128::from(mask).count_ones(),
+        ),
+    };
+    (ones == bits).then_some(ones)
+}
+
 #[derive(Default)]
 pub struct DevicePage {
     devices: Vec<Device>,
@@ -24,6 +55,32 @@ pub struct DevicePage {
     loading: bool,
     action_tx: Option<tokio::sync::mpsc::UnboundedSender<Action>>,
     mouse_event: Option<(u16, u16)>,
+    remote_devices: Vec<Device>, // Listed by rpcapd hosts, kept across refreshes
+    remote_form: Option<RemoteForm>,
+    monitor: HashSet<String>, // Wi-Fi interfaces to capture on in monitor mode
+    last_poll: Option<Instant>, // When the device list was last checked for changes
+}
+
+/// Host and optional login of an rpcapd to list devices from.
+#[derive(Default)]
+struct RemoteForm {
+    fields: [String; 3], // Host, username, password
+    focus: usize,
+}
+
+impl RemoteForm {
+    fn url(&self) -> String {
+        let host = self.fields[0].trim().trim_end_matches('/');
+        let host = host.strip_prefix(rpcap::SCHEME).unwrap_or(host);
+        format!("{}{host}/", rpcap::SCHEME)
+    }
+
+    fn auth(&self) -> Option<Auth> {
+        (!self.fields[1].is_empty()).then(|| Auth {
+            username: self.fields[1].clone(),
+            password: self.fields[2].clone(),
+        })
+    }
 }
 
 impl DevicePage {
@@ -33,25 +90,24 @@ impl DevicePage {
 
     fn load_devices(&mut self) -> Result<()> {
         self.loading = true;
-        self.status_message = "Probing network devices...".to_string();
+        self.status_message = tr!("device.probing").to_string();
 
         match Device::list() {
             Ok(devices) => {
                 if devices.is_empty() {
-                    self.status_message = "No network devices found.".to_string();
+                    self.status_message = tr!("device.none").to_string();
                 } else {
-                    self.status_message = format!(
-                        "Found {} device(s). Use ↑/↓ to navigate, Enter to select.",
-                        devices.len()
-                    );
-                    self.devices = devices;
-                    if !self.devices.is_empty() {
-                        self.list_state.select(Some(1)); // 0 is the header
-                    }
+                    self.status_message = tr!("device.found_hint", devices.len());
+                    self.set_devices(devices);
+                    // The interface with the default route is the likely pick.
+                    let default = live::default_interface().and_then(|name| {
+                        self.devices.iter().position(|device| device.name == name)
+                    });
+                    self.list_state.select(Some(default.unwrap_or(0) + 1)); // 0 is the header
                 }
             }
             Err(e) => {
-                self.status_message = format!("Failed to list devices: {e}");
+                self.status_message = tr!("device.list_failed", e);
             }
         }
 
@@ -59,39 +115,228 @@ impl DevicePage {
         Ok(())
     }
 
+    /// Lists `devices` after the pseudo-device for all of them and before
+    /// the remote ones.
+    fn set_devices(&mut self, devices: Vec<Device>) {
+        let mut all = Device::from(live::ALL_INTERFACES);
+        all.desc = Some(tr!("device.all").to_string());
+        self.devices = devices;
+        self.devices.insert(0, all);
+        self.devices.extend(self.remote_devices.iter().cloned());
+    }
+
+    /// Lists the devices again when interfaces were added or removed, such
+    /// as a USB adapter plugged in or a VPN tunnel created, keeping the
+    /// cursor on the same device. Checks at most every `HOTPLUG_INTERVAL`.
+    pub fn poll_devices(&mut self) {
+        if self
+            .last_poll
+            .is_some_and(|last| last.elapsed() < HOTPLUG_INTERVAL)
+        {
+            return;
+        }
+        self.last_poll = Some(Instant::now());
+        let Ok(devices) = Device::list() else {
+            return;
+        };
+        let local = |devices: &[Device]| -> HashSet<String> {
+            devices
+                .iter()
+                .map(|device| device.name.clone())
+                .filter(|name| name != live::ALL_INTERFACES && !rpcap::is_remote(name))
+                .collect()
+        };
+        let before = local(&self.devices);
+        let after = local(&devices);
+        if before == after {
+            return;
+        }
+        let mut added: Vec<&String> = after.difference(&before).collect();
+        let mut removed: Vec<&String> = before.difference(&after).collect();
+        added.sort();
+        removed.sort();
+        let names = |names: Vec<&String>| {
+            if names.is_empty() {
+                "-".to_string()
+            } else {
+                names
+                    .iter()
+                    .map(|name| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        };
+        self.status_message = tr!("device.changed", names(added), names(removed));
+
+        let highlighted = self.highlighted().map(|device| device.name.clone());
+        self.set_devices(devices);
+        let position =
+            highlighted.and_then(|name| self.devices.iter().position(|device| device.name == name));
+        self.list_state.select(Some(position.unwrap_or(0) + 1)); // 0 is the header
+    }
+
     fn select_current_device(&mut self) {
         if let Some(selected) = self.list_state.selected()
             && selected <= self.devices.len()
         {
             self.selected_device = Some(self.devices[selected - 1].clone());
-            self.status_message = format!("Selected device: {}", self.devices[selected].name);
+            self.status_message = tr!("device.selected", self.devices[selected - 1].name);
             if let Some(tx) = &self.action_tx {
                 let action = Action::DeviceSelected(self.devices[selected - 1].name.clone());
                 if tx.send(action).is_err() {
-                    self.status_message = "Failed to send device selection action.".to_string();
+                    self.status_message = tr!("device.send_failed").to_string();
                 }
             }
         }
     }
 
+    /// Switches monitor mode for the highlighted device, if it is a Wi-Fi
+    /// interface. It takes effect when a capture starts on it.
+    fn toggle_monitor(&mut self) {
+        let Some(device) = self
+            .list_state
+            .selected()
+            .and_then(|selected| self.devices.get(selected.checked_sub(1)?))
+        else {
+            return;
+        };
+        if !live::is_wireless(&device.name) {
+            self.status_message = tr!("device.not_wireless", device.name);
+            return;
+        }
+        let on = !self.monitor.remove(&device.name);
+        if on {
+            self.monitor.insert(device.name.clone());
+            self.status_message = tr!("device.monitor_on", device.name);
+        } else {
+            self.status_message = tr!("device.monitor_off", device.name);
+        }
+        if let Some(tx) = &self.action_tx {
+            let _ = tx.send(Action::SetMonitorMode(device.name.clone(), on));
+        }
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.remote_form.is_some()
+    }
+
+    /// Lists the devices of the rpcapd host in the form, replacing any
+    /// listed from it before.
+    fn connect_remote(&mut self) {
+        let Some(ref form) = self.remote_form else {
+            return;
+        };
+        let url = form.url();
+        let auth = form.auth();
+        match rpcap::list(&url, auth.as_ref()) {
+            Ok(devices) => {
+                rpcap::remember(&url, auth);
+                self.status_message = tr!("device.remote_found", devices.len(), url);
+                self.devices.retain(|d| !d.name.starts_with(&url));
+                self.remote_devices.retain(|d| !d.name.starts_with(&url));
+                self.devices.extend(devices.iter().cloned());
+                self.remote_devices.extend(devices);
+                if self.list_state.selected().is_none() && !self.devices.is_empty() {
+                    self.list_state.select(Some(1));
+                }
+                self.remote_form = None;
+            }
+            Err(e) => self.status_message = tr!("device.remote_failed", url, e),
+        }
+    }
+
+    fn handle_form_key(&mut self, key: KeyEvent) {
+        let Some(ref mut form) = self.remote_form else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => self.remote_form = None,
+            KeyCode::Enter => self.connect_remote(),
+            KeyCode::Tab | KeyCode::Down => form.focus = (form.focus + 1) % form.fields.len(),
+            KeyCode::BackTab | KeyCode::Up => {
+                form.focus = (form.focus + form.fields.len() - 1) % form.fields.len();
+            }
+            KeyCode::Backspace => {
+                form.fields[form.focus].pop();
+            }
+            KeyCode::Char(c) => form.fields[form.focus].push(c),
+            _ => {}
+        }
+    }
+
+    fn render_remote_form(&self, f: &mut Frame, area: Rect) {
+        let Some(ref form) = self.remote_form else {
+            return;
+        };
+        let width = area.width.min(60);
+        let height = 7.min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        let labels = [
+            tr!("device.remote.host"),
+            tr!("device.remote.username"),
+            tr!("device.remote.password"),
+        ];
+        let mut lines: Vec<Line> = labels
+            .iter()
+            .zip(&form.fields)
+            .enumerate()
+            .map(|(i, (label, value))| {
+                let value = if i == 2 {
+                    "*".repeat(value.chars().count())
+                } else {
+                    value.clone()
+                };
+                let style = if i == form.focus {
+                    Style::default().bg(theme().selection).fg(theme().text)
+                } else {
+                    Style::default().fg(theme().text)
+                };
+                Line::from(vec![
+                    Span::styled(format!("{label:<12}"), Style::default().fg(theme().muted)),
+                    Span::styled(value, style),
+                ])
+            })
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            tr!("device.remote.help"),
+            Style::default().fg(theme().accent),
+        ));
+
+        f.render_widget(Clear, popup);
+        let form = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr!("device.remote.title"))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+                .style(Style::default().bg(theme().background)),
+        );
+        f.render_widget(form, popup);
+    }
+
     fn clear_selection(&mut self) {
         self.list_state.select(None);
         self.selected_device = None;
         if !self.devices.is_empty() {
-            self.status_message = format!("Found {} device(s)", self.devices.len());
+            self.status_message = tr!("device.found", self.devices.len());
         }
     }
 
     fn render_device_list(&self, f: &mut Frame, area: Rect) {
         if self.devices.is_empty() {
-            let empty_message = Paragraph::new("No devices found. Press F5 to refresh.")
+            let empty_message = Paragraph::new(tr!("device.none_refresh"))
                 .alignment(ratatui::layout::Alignment::Center)
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .title("Network Devices"),
+                        .title(tr!("device.title")),
                 )
-                .style(Style::default().fg(Color::Red))
+                .style(Style::default().fg(theme().alert))
                 .wrap(Wrap { trim: true });
 
             f.render_widget(empty_message, area);
@@ -102,19 +347,19 @@ impl DevicePage {
             Span::styled(
                 format!("{:<4}", "No."),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 format!("{:<80}", "Description"),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 "Name",
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
         ]));
@@ -130,53 +375,165 @@ impl DevicePage {
             };
 
             let line = Line::from(vec![
-                Span::styled(format!("{:<4}", i + 1), Style::default().fg(Color::Yellow)),
+                Span::styled(
+                    format!("{:<4}", i + 1),
+                    Style::default().fg(theme().highlight),
+                ),
                 Span::styled(
                     format!("{truncated_desc:<80}"),
-                    Style::default().fg(Color::Gray),
+                    Style::default().fg(theme().muted),
                 ),
-                Span::styled(&device.name, Style::default().fg(Color::Cyan)),
+                Span::styled(&device.name, Style::default().fg(theme().accent)),
+                if self.monitor.contains(&device.name) {
+                    Span::styled(
+                        format!(" {}", tr!("device.monitor")),
+                        Style::default().fg(theme().highlight),
+                    )
+                } else {
+                    Span::raw("")
+                },
             ]);
             ListItem::new(line)
         }));
 
         let selected_style = if self.selected_device.is_some() {
             Style::default()
-                .bg(Color::Blue)
+                .bg(theme().selection)
                 .add_modifier(Modifier::BOLD)
         } else {
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(theme().inactive_selection)
                 .add_modifier(Modifier::BOLD)
         };
 
         let list = List::new(items)
             .block(
                 Block::default()
-                    .title("Network Devices")
+                    .title(tr!("device.title"))
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Blue)),
+                    .border_style(Style::default().fg(theme().border)),
             )
             .highlight_style(selected_style);
 
         f.render_stateful_widget(list, area, &mut self.list_state.clone());
     }
 
+    /// The device under the cursor, which may not be the selected one.
+    fn highlighted(&self) -> Option<&Device> {
+        let index = self.list_state.selected()?.checked_sub(1)?;
+        self.devices.get(index)
+    }
+
+    fn detail_lines(&self) -> Vec<Line<'static>> {
+        let Some(device) = self.highlighted() else {
+            return Vec::new();
+        };
+        let label = |text: &'static str| {
+            Span::styled(
+                text,
+                Style::default()
+                    .fg(theme().accent)
+                    .add_modifier(Modifier::BOLD),
+            )
+        };
+        let flags = &device.flags;
+        let mut names: Vec<&str> = [
+            (flags.up, "up"),
+            (flags.running, "running"),
+            (flags.loopback, "loopback"),
+            (flags.wireless, "wireless"),
+        ]
+        .into_iter()
+        .filter_map(|(set, name)| set.then_some(name))
+        .collect();
+        match flags.connected {
+            Some(true) => names.push("connected"),
+            Some(false) => names.push("disconnected"),
+            None => {}
+        }
+        if self.monitor.contains(&device.name) {
+            names.push("monitor mode");
+        }
+        let flags = if names.is_empty() {
+            "-".to_string()
+        } else {
+            names.join(", ")
+        };
+
+        let mut lines = vec![
+            Line::from(vec![
+                label("Name: "),
+                Span::styled(device.name.clone(), Style::default().fg(theme().text)),
+            ]),
+            Line::from(vec![
+                label("Flags: "),
+                Span::styled(flags, Style::default().fg(theme().highlight)),
+            ]),
+        ];
+        if device.addresses.is_empty() {
+            lines.push(Line::from(vec![
+                label("Addresses: "),
+                Span::styled(
+                    tr!("device.no_addresses"),
+                    Style::default().fg(theme().muted),
+                ),
+            ]));
+        }
+        for address in &device.addresses {
+            let mut text = match address.netmask.and_then(prefix_len) {
+                Some(prefix) => format!("{}/{prefix}", address.addr),
+                None => address.addr.to_string(),
+            };
+            if let Some(netmask) = address.netmask {
+                text.push_str(&format!("  netmask {netmask}"));
+            }
+            if let Some(broadcast) = address.broadcast_addr {
+                text.push_str(&format!("  broadcast {broadcast}"));
+            }
+            if let Some(peer) = address.dst_addr {
+                text.push_str(&format!("  peer {peer}"));
+            }
+            let kind = if address.addr.is_ipv4() {
+                "IPv4: "
+            } else {
+                "IPv6: "
+            };
+            lines.push(Line::from(vec![
+                label(kind),
+                Span::styled(text, Style::default().fg(theme().secondary)),
+            ]));
+        }
+        lines
+    }
+
+    fn render_device_detail(&self, f: &mut Frame, area: Rect) {
+        let detail = Paragraph::new(self.detail_lines())
+            .block(
+                Block::default()
+                    .title(tr!("device.details"))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().border)),
+            )
+            .wrap(Wrap { trim: true });
+
+        f.render_widget(detail, area);
+    }
+
     fn render_status(&self, f: &mut Frame, area: Rect) {
         let status_color = if self.loading {
-            Color::Yellow
+            theme().highlight
         } else if self.devices.is_empty() && !self.status_message.contains("Found") {
-            Color::Red
+            theme().alert
         } else {
-            Color::Green
+            theme().value
         };
 
         let status = Paragraph::new(self.status_message.clone())
             .block(
                 Block::default()
-                    .title("Status")
+                    .title(tr!("status"))
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Blue)),
+                    .border_style(Style::default().fg(theme().border)),
             )
             .style(Style::default().fg(status_color))
             .wrap(Wrap { trim: true });
@@ -186,13 +543,13 @@ impl DevicePage {
 
     fn render_help(&self, f: &mut Frame, area: Rect) {
         let help_text = if self.selected_device.is_some() {
-            "↑/↓: Navigate  Enter: Select Device  Q/Esc: Home  F5: Refresh  C: Clear Selection"
+            tr!("device.help.selected")
         } else {
-            "↑/↓: Navigate  Enter: Select Device  Q/Esc: Home  F5: Refresh"
+            tr!("device.help")
         };
 
         let help = Paragraph::new(help_text)
-            .style(Style::default().fg(Color::Cyan))
+            .style(Style::default().fg(theme().accent))
             .wrap(Wrap { trim: true })
             .alignment(ratatui::layout::Alignment::Center)
             .block(Block::default().borders(Borders::NONE));
@@ -240,6 +597,10 @@ impl Component for DevicePage {
     }
 
     fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.remote_form.is_some() {
+            self.handle_form_key(key);
+            return Ok(Some(Action::Handled));
+        }
         match key.code {
             KeyCode::Up => {
                 if !self.devices.is_empty() {
@@ -274,6 +635,12 @@ impl Component for DevicePage {
             KeyCode::Char('c') => {
                 self.clear_selection();
             }
+            KeyCode::Char('r') => {
+                self.remote_form = Some(RemoteForm::default());
+            }
+            KeyCode::Char('w') => {
+                self.toggle_monitor();
+            }
             KeyCode::Char('q') => {
                 return Ok(Some(Action::NavigateToHome));
             }
@@ -292,10 +659,17 @@ impl Component for DevicePage {
 
 impl ComponentRender<()> for DevicePage {
     fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let detail_lines = self.detail_lines().len();
+        let detail_height = if detail_lines > 0 {
+            detail_lines.min(MAX_DETAIL_LINES) as u16 + 2
+        } else {
+            0
+        };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Min(10),
+                Constraint::Length(detail_height),
                 Constraint::Length(3),
                 Constraint::Length(1),
             ])
@@ -306,7 +680,11 @@ impl ComponentRender<()> for DevicePage {
         }
 
         self.render_device_list(f, chunks[0]);
-        self.render_status(f, chunks[1]);
-        self.render_help(f, chunks[2]);
+        if detail_height > 0 {
+            self.render_device_detail(f, chunks[1]);
+        }
+        self.render_status(f, chunks[2]);
+        self.render_help(f, chunks[3]);
+        self.render_remote_form(f, area);
     }
 }
@@ -0,0 +1,188 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::discovery::DiscoveryStats,
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+#[derive(Default)]
+pub struct DiscoveryPage {
+    stats: DiscoveryStats,
+    scroll_position: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+impl DiscoveryPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_stats(&mut self, stats: DiscoveryStats) {
+        self.stats = stats;
+    }
+
+    fn render_neighbors(&self, f: &mut Frame, area: Rect) {
+        let header = ListItem::new(Line::from(
+            [
+                ("Interface", 12),
+                ("Protocol", 10),
+                ("System Name", 24),
+                ("Chassis ID", 20),
+                ("Port ID", 20),
+                ("VLAN", 6),
+                ("Capabilities", 20),
+                ("Management", 18),
+                ("Seen", 0),
+            ]
+            .iter()
+            .map(|(name, width)| {
+                Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect::<Vec<_>>(),
+        ));
+
+        let mut items = vec![header];
+        let visible = (area.height as usize).saturating_sub(3);
+        items.extend(
+            self.stats
+                .neighbors
+                .values()
+                .skip(self.scroll_position)
+                .take(visible)
+                .map(|neighbor| {
+                    let announcement = &neighbor.announcement;
+                    let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+                    let capabilities = if announcement.capabilities.is_empty() {
+                        "-".to_string()
+                    } else {
+                        announcement.capabilities.join(", ")
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{:<12}", neighbor.interface.as_deref().unwrap_or("-")),
+                            Style::default().fg(theme().muted),
+                        ),
+                        Span::styled(
+                            format!("{:<10}", neighbor.protocol.label()),
+                            Style::default().fg(theme().accent),
+                        ),
+                        Span::styled(
+                            format!("{:<24}", neighbor.name()),
+                            Style::default().fg(theme().secondary),
+                        ),
+                        Span::styled(
+                            format!("{:<20}", announcement.chassis_id),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{:<20}", announcement.port_id),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{:<6}", or_dash(announcement.vlan.map(|v| v.to_string()))),
+                            Style::default().fg(theme().highlight),
+                        ),
+                        Span::styled(
+                            format!("{capabilities:<20}"),
+                            Style::default().fg(theme().text),
+                        ),
+                        Span::styled(
+                            format!(
+                                "{:<18}",
+                                or_dash(announcement.management.map(|addr| addr.to_string()))
+                            ),
+                            Style::default().fg(theme().secondary),
+                        ),
+                        Span::styled(
+                            neighbor.announcements.to_string(),
+                            Style::default().fg(theme().muted),
+                        ),
+                    ]))
+                }),
+        );
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!(
+                    "discovery.title",
+                    self.stats.neighbors.len(),
+                    self.stats.interfaces()
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("stats.help.scroll"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for DiscoveryPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Up => {
+                self.scroll_position = self.scroll_position.saturating_sub(1);
+            }
+            KeyCode::Down if self.scroll_position + 1 < self.stats.neighbors.len() => {
+                self.scroll_position += 1;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for DiscoveryPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_neighbors(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
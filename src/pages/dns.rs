@@ -0,0 +1,338 @@
+use std::{net::IpAddr, time::Duration};
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::{
+        dns::{rcode_name, type_name},
+        dns_stats::{DnsStats, TOP, Transaction},
+    },
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+#[derive(Default)]
+pub struct DnsPage {
+    stats: DnsStats,
+    unanswered_only: bool,
+    selected: usize, // Among the listed transactions, newest first
+    scroll_position: usize,
+    visible_rows: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+fn format_latency(latency: Option<Duration>) -> String {
+    match latency {
+        Some(latency) => format!("{:.2} ms", latency.as_secs_f64() * 1000.0),
+        None => "-".to_string(),
+    }
+}
+
+fn format_endpoint((addr, port): (IpAddr, u16)) -> String {
+    match addr {
+        IpAddr::V6(addr) => format!("[{addr}]:{port}"),
+        addr => format!("{addr}:{port}"),
+    }
+}
+
+fn header(columns: &[(&str, usize)]) -> ListItem<'static> {
+    ListItem::new(Line::from(
+        columns
+            .iter()
+            .map(|(name, width)| {
+                Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect::<Vec<_>>(),
+    ))
+}
+
+impl DnsPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_stats(&mut self, stats: DnsStats) {
+        self.stats = stats;
+        self.selected = self.selected.min(self.listed().len().saturating_sub(1));
+    }
+
+    /// The transactions shown, newest first.
+    fn listed(&self) -> Vec<&Transaction> {
+        self.stats
+            .transactions
+            .iter()
+            .rev()
+            .filter(|transaction| !self.unanswered_only || transaction.response.is_none())
+            .collect()
+    }
+
+    fn render_names(&self, f: &mut Frame, area: Rect) {
+        let mut items = vec![header(&[("Name", 48), ("Queries", 0)])];
+        items.extend(self.stats.names.iter().map(|(name, count)| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{name:<48.47}"),
+                    Style::default().fg(theme().secondary),
+                ),
+                Span::styled(count.to_string(), Style::default().fg(theme().value)),
+            ]))
+        }));
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("dns.names_title", self.stats.distinct_names()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_resolvers(&self, f: &mut Frame, area: Rect) {
+        let mut items = vec![header(&[
+            ("Resolver", 40),
+            ("Queries", 9),
+            ("Unanswered", 12),
+            ("NXDOMAIN", 10),
+            ("p50", 11),
+            ("p95", 0),
+        ])];
+        items.extend(self.stats.resolvers.iter().take(TOP).map(|resolver| {
+            let unanswered_color = if resolver.unanswered() > 0 {
+                theme().alert
+            } else {
+                theme().value
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<40.39}", resolver.addr.to_string()),
+                    Style::default().fg(theme().secondary),
+                ),
+                Span::styled(
+                    format!("{:<9}", resolver.queries),
+                    Style::default().fg(theme().value),
+                ),
+                Span::styled(
+                    format!("{:<12}", resolver.unanswered()),
+                    Style::default().fg(unanswered_color),
+                ),
+                Span::styled(
+                    format!("{:<10}", resolver.nxdomain),
+                    Style::default().fg(theme().value),
+                ),
+                Span::styled(
+                    format!("{:<11}", format_latency(resolver.percentile(50))),
+                    Style::default().fg(theme().highlight),
+                ),
+                Span::styled(
+                    format_latency(resolver.percentile(95)),
+                    Style::default().fg(theme().highlight),
+                ),
+            ]))
+        }));
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("dns.resolvers_title"))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_transactions(&mut self, f: &mut Frame, area: Rect) {
+        self.visible_rows = (area.height as usize).saturating_sub(3);
+        if self.selected < self.scroll_position {
+            self.scroll_position = self.selected;
+        } else if self.selected >= self.scroll_position + self.visible_rows {
+            self.scroll_position = self.selected + 1 - self.visible_rows;
+        }
+
+        let mut items = vec![header(&[
+            ("Query", 9),
+            ("Client", 28),
+            ("Resolver", 28),
+            ("Type", 7),
+            ("Name", 40),
+            ("Response", 22),
+            ("Time", 0),
+        ])];
+        let listed = self.listed();
+        items.extend(
+            listed
+                .iter()
+                .enumerate()
+                .skip(self.scroll_position)
+                .take(self.visible_rows)
+                .map(|(i, transaction)| {
+                    let base_style = if i == self.selected {
+                        Style::default()
+                            .bg(theme().selection)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default()
+                    };
+                    let (response, response_color) = match transaction.rcode {
+                        None => ("No response".to_string(), theme().alert),
+                        Some(0) => (rcode_name(0).to_string(), theme().value),
+                        Some(rcode) => (rcode_name(rcode).to_string(), theme().warning),
+                    };
+                    let response = if transaction.retransmissions > 0 {
+                        format!("{response} ({} retries)", transaction.retransmissions)
+                    } else {
+                        response
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{:<9}", format!("#{}", transaction.query)),
+                            base_style.fg(theme().muted),
+                        ),
+                        Span::styled(
+                            format!("{:<28.27}", format_endpoint(transaction.client)),
+                            base_style.fg(theme().secondary),
+                        ),
+                        Span::styled(
+                            format!("{:<28.27}", format_endpoint(transaction.server)),
+                            base_style.fg(theme().secondary),
+                        ),
+                        Span::styled(
+                            format!("{:<7}", type_name(transaction.qtype)),
+                            base_style.fg(theme().accent),
+                        ),
+                        Span::styled(
+                            format!("{:<40.39}", transaction.name),
+                            base_style.fg(theme().text),
+                        ),
+                        Span::styled(format!("{response:<22.21}"), base_style.fg(response_color)),
+                        Span::styled(
+                            format_latency(transaction.latency),
+                            base_style.fg(theme().highlight),
+                        ),
+                    ]))
+                }),
+        );
+
+        let nxdomain = self
+            .stats
+            .nxdomain_rate()
+            .map_or("-".to_string(), |rate| format!("{:.1}%", rate * 100.0));
+        let shown = if self.unanswered_only {
+            tr!("dns.unanswered_shown")
+        } else {
+            ""
+        };
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!(
+                    "dns.queries_title",
+                    self.stats.transactions.len(),
+                    self.stats.unanswered(),
+                    nxdomain,
+                    shown
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("dns.help"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for DnsPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let len = self.listed().len();
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Char('u') => {
+                self.unanswered_only = !self.unanswered_only;
+                self.selected = 0;
+                self.scroll_position = 0;
+            }
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down if self.selected + 1 < len => {
+                self.selected += 1;
+            }
+            KeyCode::PageUp => {
+                self.selected = self.selected.saturating_sub(self.visible_rows.max(1));
+            }
+            KeyCode::PageDown => {
+                self.selected =
+                    (self.selected + self.visible_rows.max(1)).min(len.saturating_sub(1));
+            }
+            KeyCode::Enter => {
+                if let Some(transaction) = self.listed().get(self.selected) {
+                    let mut filter = format!("frame.number == {}", transaction.query);
+                    if let Some(response) = transaction.response {
+                        filter.push_str(&format!(" or frame.number == {response}"));
+                    }
+                    return Ok(Some(Action::ApplyDisplayFilter(filter)));
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for DnsPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(TOP as u16 + 3),
+                Constraint::Min(5),
+                Constraint::Length(1),
+            ])
+            .split(area);
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(chunks[0]);
+
+        self.render_names(f, top[0]);
+        self.render_resolvers(f, top[1]);
+        self.render_transactions(f, chunks[1]);
+        self.render_help(f, chunks[2]);
+    }
+}
@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::dual_stack::DualStackStats,
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+#[derive(Default)]
+pub struct DualStackPage {
+    stats: DualStackStats,
+    scroll_position: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+fn format_ms(duration: Option<Duration>) -> String {
+    match duration {
+        Some(duration) => format!("{:.1} ms", duration.as_secs_f64() * 1000.0),
+        None => "-".to_string(),
+    }
+}
+
+impl DualStackPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_stats(&mut self, stats: DualStackStats) {
+        self.stats = stats;
+    }
+
+    fn render_races(&self, f: &mut Frame, area: Rect) {
+        let header = ListItem::new(Line::from(
+            [
+                ("Host", 32),
+                ("Port", 7),
+                ("Resolved", 10),
+                ("IPv4/IPv6", 11),
+                ("Winner", 42),
+                ("Fallback", 12),
+                ("Connect", 12),
+                ("Verdict", 0),
+            ]
+            .iter()
+            .map(|(name, width)| {
+                Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect::<Vec<_>>(),
+        ));
+
+        let mut items = vec![header];
+        let visible = (area.height as usize).saturating_sub(3);
+        items.extend(
+            self.stats
+                .races
+                .iter()
+                .skip(self.scroll_position)
+                .take(visible)
+                .map(|race| {
+                    let (v4, v6) = race.attempts_by_family();
+                    let resolved = match (race.resolved_v4, race.resolved_v6) {
+                        (true, true) => "A+AAAA",
+                        (true, false) => "A",
+                        (false, true) => "AAAA",
+                        (false, false) => "-",
+                    };
+                    let winner = match race.winner() {
+                        Some(attempt) => attempt.server.to_string(),
+                        None => "-".to_string(),
+                    };
+                    let verdict = race.verdict();
+                    let verdict_color = match verdict {
+                        "IPv6 won" | "IPv4 won" | "IPv6 only" | "IPv4 only" => theme().value,
+                        "No connection" => theme().alert,
+                        _ => theme().highlight,
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{:<32.31}", race.host),
+                            Style::default().fg(theme().highlight),
+                        ),
+                        Span::styled(
+                            format!("{:<7}", race.port),
+                            Style::default().fg(theme().accent),
+                        ),
+                        Span::styled(
+                            format!("{resolved:<10}"),
+                            Style::default().fg(theme().accent),
+                        ),
+                        Span::styled(
+                            format!("{:<11}", format!("{v4}/{v6}")),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{winner:<42.41}"),
+                            Style::default().fg(theme().secondary),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", format_ms(race.fallback_delay())),
+                            Style::default().fg(theme().muted),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", format_ms(race.connect_time())),
+                            Style::default().fg(theme().muted),
+                        ),
+                        Span::styled(verdict, Style::default().fg(verdict_color)),
+                    ]))
+                }),
+        );
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("dual_stack.title", self.stats.races.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("stats.help.scroll"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for DualStackPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Up => {
+                self.scroll_position = self.scroll_position.saturating_sub(1);
+            }
+            KeyCode::Down if self.scroll_position + 1 < self.stats.races.len() => {
+                self.scroll_position += 1;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for DualStackPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_races(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
@@ -0,0 +1,233 @@
+use std::fs;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::endpoint::EndpointStats,
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+/// Where the passive service report is written, in the working directory.
+const REPORT_FILE: &str = "services.txt";
+
+#[derive(Default)]
+pub struct EndpointsPage {
+    stats: EndpointStats,
+    selected: usize,
+    scroll_position: usize,
+    visible_rows: usize,
+    status: Option<String>,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+impl EndpointsPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_stats(&mut self, stats: EndpointStats) {
+        self.stats = stats;
+        self.selected = self.selected.min(self.stats.hosts.len().saturating_sub(1));
+    }
+
+    fn render_hosts(&mut self, f: &mut Frame, area: Rect) {
+        let header = ListItem::new(Line::from(
+            [
+                ("Address", 42),
+                ("Packets", 10),
+                ("Bytes", 12),
+                ("Tx Packets", 12),
+                ("Tx Bytes", 12),
+                ("Rx Packets", 12),
+                ("Rx Bytes", 12),
+                ("Share", 8),
+                ("Device", 22),
+                ("Services", 0),
+            ]
+            .iter()
+            .map(|(name, width)| {
+                Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect::<Vec<_>>(),
+        ));
+
+        self.visible_rows = (area.height as usize).saturating_sub(3);
+        if self.selected < self.scroll_position {
+            self.scroll_position = self.selected;
+        } else if self.selected >= self.scroll_position + self.visible_rows {
+            self.scroll_position = self.selected + 1 - self.visible_rows;
+        }
+
+        let mut items = vec![header];
+        items.extend(
+            self.stats
+                .hosts
+                .iter()
+                .enumerate()
+                .skip(self.scroll_position)
+                .take(self.visible_rows)
+                .map(|(i, host)| {
+                    let addr = match (&host.addr, &host.name) {
+                        (Ok(ip), Some(name)) => format!("{ip} ({name})"),
+                        (Ok(ip), None) => ip.to_string(),
+                        (Err(mac), _) => mac.clone(),
+                    };
+                    // Every packet counts once for the sender and once for
+                    // the receiver, so a host's share is of all bytes seen.
+                    let share = if self.stats.total_bytes > 0 {
+                        host.bytes() as f64 * 100.0 / self.stats.total_bytes as f64
+                    } else {
+                        0.0
+                    };
+                    let base_style = if i == self.selected {
+                        Style::default()
+                            .bg(theme().selection)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default()
+                    };
+                    let device = host
+                        .device
+                        .as_ref()
+                        .map_or_else(|| "-".to_string(), |device| device.label());
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{addr:<42.41}"), base_style.fg(theme().secondary)),
+                        Span::styled(
+                            format!("{:<10}", host.packets()),
+                            base_style.fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", host.bytes()),
+                            base_style.fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", host.tx.packets),
+                            base_style.fg(theme().accent),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", host.tx.bytes),
+                            base_style.fg(theme().accent),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", host.rx.packets),
+                            base_style.fg(theme().highlight),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", host.rx.bytes),
+                            base_style.fg(theme().highlight),
+                        ),
+                        Span::styled(
+                            format!("{:<8}", format!("{share:.1}%")),
+                            base_style.fg(theme().muted),
+                        ),
+                        Span::styled(format!("{device:<22.21}"), base_style.fg(theme().accent)),
+                        Span::styled(
+                            host.services
+                                .iter()
+                                .map(|service| {
+                                    format!("{} {}", service.port_label(), service.name())
+                                })
+                                .collect::<Vec<_>>()
+                                .join(", "),
+                            base_style.fg(theme().text),
+                        ),
+                    ]))
+                }),
+        );
+
+        let title = match self.status {
+            Some(ref status) => tr!("endpoints.title_status", self.stats.hosts.len(), status),
+            None => tr!("endpoints.title", self.stats.hosts.len()),
+        };
+        let list = List::new(items).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("endpoints.help"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for EndpointsPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down if self.selected + 1 < self.stats.hosts.len() => {
+                self.selected += 1;
+            }
+            KeyCode::Enter => {
+                if let Some(host) = self.stats.hosts.get(self.selected) {
+                    return Ok(Some(Action::ApplyDisplayFilter(host.display_filter())));
+                }
+            }
+            KeyCode::Char('e') => {
+                self.status = Some(match fs::write(REPORT_FILE, self.stats.service_report()) {
+                    Ok(()) => tr!("endpoints.exported", REPORT_FILE),
+                    Err(e) => tr!("endpoints.export_error", e),
+                });
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for EndpointsPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_hosts(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
@@ -0,0 +1,261 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::packet_export::ExportFormat,
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+/// Which packets an export takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportScope {
+    Marked, // Or the selected one when none are marked
+    Listed,
+}
+
+/// An export confirmed in the dialog, for the sniffer to carry out.
+#[derive(Debug, Clone)]
+pub struct ExportRequest {
+    pub path: PathBuf,
+    pub format: ExportFormat,
+    pub scope: ExportScope,
+    pub payload: bool, // Hex-encoded transport payload in JSON and CSV
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Name,
+    Format,
+    Scope,
+    Payload,
+}
+
+const FIELDS: [Field; 4] = [Field::Name, Field::Format, Field::Scope, Field::Payload];
+
+pub struct ExportDialog {
+    pub is_open: bool,
+    file_name: String,
+    format: ExportFormat,
+    scope: ExportScope,
+    payload: bool,
+    focus: Field,
+    marked: usize, // Packets each scope takes
+    listed: usize,
+    request: Option<ExportRequest>,
+}
+
+impl Default for ExportDialog {
+    fn default() -> Self {
+        Self {
+            is_open: false,
+            file_name: String::new(),
+            format: ExportFormat::default(),
+            scope: ExportScope::Marked,
+            payload: false,
+            focus: Field::Name,
+            marked: 0,
+            listed: 0,
+            request: None,
+        }
+    }
+}
+
+impl ExportDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opens the dialog for a file named `stem`, `marked` and `listed`
+    /// being the packets each scope would export. The format and payload
+    /// choice are kept from the last export.
+    pub fn open(&mut self, stem: &str, marked: usize, listed: usize) {
+        self.is_open = true;
+        self.file_name = format!("{stem}.{}", self.format.extension());
+        self.scope = if marked > 0 {
+            ExportScope::Marked
+        } else {
+            ExportScope::Listed
+        };
+        self.focus = Field::Name;
+        self.marked = marked;
+        self.listed = listed;
+        self.request = None;
+    }
+
+    /// The export confirmed since the last call, if any.
+    pub fn take_request(&mut self) -> Option<ExportRequest> {
+        self.request.take()
+    }
+
+    fn move_focus(&mut self, forward: bool) {
+        let position = FIELDS
+            .iter()
+            .position(|&field| field == self.focus)
+            .unwrap_or(0);
+        let len = FIELDS.len();
+        self.focus = FIELDS[if forward {
+            (position + 1) % len
+        } else {
+            (position + len - 1) % len
+        }];
+    }
+
+    /// Changes the value of the focused field other than the file name.
+    fn change(&mut self) {
+        match self.focus {
+            Field::Name => {}
+            Field::Format => {
+                let old = format!(".{}", self.format.extension());
+                self.format = self.format.next();
+                if let Some(stem) = self.file_name.strip_suffix(&old) {
+                    self.file_name = format!("{stem}.{}", self.format.extension());
+                }
+            }
+            Field::Scope => {
+                self.scope = match self.scope {
+                    ExportScope::Marked => ExportScope::Listed,
+                    ExportScope::Listed => ExportScope::Marked,
+                };
+            }
+            Field::Payload => self.payload = !self.payload,
+        }
+    }
+
+    fn confirm(&mut self) {
+        let name = self.file_name.trim();
+        if name.is_empty() {
+            return;
+        }
+        self.request = Some(ExportRequest {
+            path: PathBuf::from(name),
+            format: self.format,
+            scope: self.scope,
+            payload: self.payload,
+        });
+        self.is_open = false;
+    }
+}
+
+impl Component for ExportDialog {
+    fn register_action_handler(&mut self, _tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        if let Event::Key(key) = event {
+            self.handle_key_events(key)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Esc => self.is_open = false,
+            KeyCode::Enter => self.confirm(),
+            KeyCode::Tab | KeyCode::Down => self.move_focus(true),
+            KeyCode::BackTab | KeyCode::Up => self.move_focus(false),
+            KeyCode::Backspace if self.focus == Field::Name => {
+                self.file_name.pop();
+            }
+            KeyCode::Char(c) if self.focus == Field::Name => self.file_name.push(c),
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') => self.change(),
+            _ => {}
+        }
+        Ok(Some(Action::Handled))
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for ExportDialog {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        if !self.is_open {
+            return;
+        }
+        let width = area.width.min(64);
+        let height = area.height.min(9);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+
+        let scope = match self.scope {
+            ExportScope::Marked => tr!("export.scope.marked", self.marked),
+            ExportScope::Listed => tr!("export.scope.listed", self.listed),
+        };
+        let payload = if self.payload { "[x]" } else { "[ ]" };
+        let rows = [
+            (Field::Name, tr!("export.file"), self.file_name.clone()),
+            (
+                Field::Format,
+                tr!("export.format"),
+                format!("< {} >", self.format.name()),
+            ),
+            (Field::Scope, tr!("export.scope"), format!("< {scope} >")),
+            (
+                Field::Payload,
+                tr!("export.payload"),
+                format!("{payload} {}", tr!("export.payload.about")),
+            ),
+        ];
+        let mut lines: Vec<Line> = rows
+            .iter()
+            .map(|(field, label, value)| {
+                let style = if *field == self.focus {
+                    Style::default().bg(theme().selection).fg(theme().text)
+                } else {
+                    Style::default().fg(theme().text)
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!("{label:<10}"),
+                        Style::default()
+                            .fg(theme().accent)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(value.clone(), style),
+                ])
+            })
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            tr!("export.help"),
+            Style::default().fg(theme().muted),
+        ));
+
+        let dialog = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr!("export.title"))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+                .style(Style::default().bg(theme().background)),
+        );
+        f.render_widget(Clear, popup);
+        f.render_widget(dialog, popup);
+        if self.focus == Field::Name {
+            let x = popup.x + 11 + self.file_name.chars().count() as u16;
+            if x < popup.x + popup.width - 1 {
+                f.set_cursor_position(ratatui::layout::Position { x, y: popup.y + 1 });
+            }
+        }
+    }
+}
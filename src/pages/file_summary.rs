@@ -0,0 +1,115 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::capture_file::FileSummary,
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+#[derive(Default)]
+pub struct FileSummaryPage {
+    summary: Option<FileSummary>,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+impl FileSummaryPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_summary(&mut self, summary: FileSummary) {
+        self.summary = Some(summary);
+    }
+
+    fn render_summary(&self, f: &mut Frame, area: Rect) {
+        let lines: Vec<Line> = match self.summary {
+            Some(ref summary) => summary
+                .rows()
+                .into_iter()
+                .map(|(label, value)| {
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{:<22}", format!("{label}:")),
+                            Style::default()
+                                .fg(theme().accent)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(value, Style::default().fg(theme().text)),
+                    ])
+                })
+                .collect(),
+            None => vec![Line::from(tr!("file_summary.no_file"))],
+        };
+
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(tr!("file_summary.title"))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().border)),
+            )
+            .wrap(Wrap { trim: false });
+
+        f.render_widget(paragraph, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("file_summary.help"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for FileSummaryPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Enter => Ok(Some(Action::NavigateToSniffer)),
+            KeyCode::Char('q') => Ok(Some(Action::NavigateToHome)),
+            _ => Ok(None),
+        }
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for FileSummaryPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(10), Constraint::Length(1)])
+            .split(area);
+
+        self.render_summary(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
@@ -1,9 +1,11 @@
+use std::time::{Duration, Instant};
+
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::Style,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
 };
@@ -12,22 +14,157 @@ use tokio::sync::mpsc;
 use crate::{
     action::Action,
     component::{Component, ComponentRender},
+    data::{
+        display_filter::DisplayFilter,
+        filter_completion::{self, COMPLETION_LIMIT},
+        filter_history::FilterHistory,
+        live,
+        saved_filters::SavedFilters,
+    },
+    i18n::tr,
+    theme::theme,
     tui::Event,
 };
 
+/// Pause in typing after which the capture filter is compiled.
+const SYNTAX_DELAY: Duration = Duration::from_millis(300);
+
 pub struct FilterDialog {
     pub is_open: bool,
     pub filter_text: String,
     pub cursor_position: usize,
     pub selected_preset: usize,
     pub mode: FilterMode,
+    pub kind: FilterKind,
+    error: Option<String>,
+    preview: Option<FilterPreview>,
+    history: FilterHistory,
+    history_position: Option<usize>, // Entry shown while going through the history
+    draft: String,                   // Text typed before going into the history
+    saved: SavedFilters,
+    selected_saved: usize,
+    preset_filters: SavedFilters, // Presets as edited, the built-in ones until then
+    editing_preset: Option<usize>, // Preset whose filter is in the input
+    naming: Option<Naming>,       // Name being typed for a filter to save or rename
+    filling: Option<Template>,    // Preset whose placeholders are being filled in
+    edited_at: Option<Instant>,   // Last change to a capture filter not yet compiled
+    syntax: Option<Result<(), String>>, // Whether the capture filter typed compiles
+    vocabulary: Vec<String>,      // Words of the capture offered as completions
+    completion: Option<Completion>,
     action_tx: Option<mpsc::UnboundedSender<Action>>,
 }
 
+/// How the display filter being typed fares against packets already
+/// captured, recomputed as the text or the capture changes.
+#[derive(Debug, Clone)]
+pub struct FilterPreview {
+    pub text: String,        // Filter text the preview was made for
+    pub packet_count: usize, // Packets captured when it was made
+    pub result: Result<PreviewMatches, String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PreviewMatches {
+    pub matched: usize,
+    pub scanned: usize,
+    pub recent: Vec<String>, // Newest matches first
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum FilterMode {
     CustomInput,
     PresetSelection,
+    Saved,
+}
+
+/// Completions offered for the word before the cursor, the selected one
+/// standing in for it in the input.
+#[derive(Debug, Clone)]
+struct Completion {
+    start: usize,   // Where the word starts in the input
+    prefix: String, // The word as typed
+    candidates: Vec<String>,
+    selected: usize,
+}
+
+/// Which list a name being typed is for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NameList {
+    Saved,
+    Presets,
+}
+
+/// A name being typed, either for the filter in the input or as the new
+/// name of a saved filter or preset.
+#[derive(Debug, Clone)]
+struct Naming {
+    text: String,
+    renaming: Option<usize>, // Index of the filter renamed in `list`
+    list: NameList,
+}
+
+/// A preset with `{name}` placeholders whose values are being typed, one
+/// placeholder at a time.
+#[derive(Debug, Clone)]
+struct Template {
+    filter: String,            // With the placeholders filled in so far
+    placeholders: Vec<String>, // Still to fill, the one being typed first
+    text: String,
+}
+
+/// The `{name}` placeholders of a preset, each once, in order.
+fn placeholders(filter: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = filter;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let name = &rest[..end];
+        let is_name =
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if is_name && !names.iter().any(|known| known == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Whether the dialog edits the BPF capture filter or the display filter
+/// applied to packets already captured.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterKind {
+    Capture,
+    Display,
+}
+
+impl FilterKind {
+    /// File in the config directory the filters of this kind are kept in.
+    fn history_file(self) -> &'static str {
+        match self {
+            FilterKind::Capture => "capture_filters",
+            FilterKind::Display => "display_filters",
+        }
+    }
+
+    /// File in the config directory the named filters of this kind are
+    /// saved in.
+    fn saved_file(self) -> &'static str {
+        match self {
+            FilterKind::Capture => "saved_capture_filters",
+            FilterKind::Display => "saved_display_filters",
+        }
+    }
+
+    /// File in the config directory the presets of this kind are kept in
+    /// once edited.
+    fn presets_file(self) -> &'static str {
+        match self {
+            FilterKind::Capture => "capture_filter_presets",
+            FilterKind::Display => "display_filter_presets",
+        }
+    }
 }
 
 impl Default for FilterDialog {
@@ -38,6 +175,22 @@ impl Default for FilterDialog {
             cursor_position: 0,
             selected_preset: 0,
             mode: FilterMode::CustomInput,
+            kind: FilterKind::Capture,
+            error: None,
+            preview: None,
+            history: FilterHistory::default(),
+            history_position: None,
+            draft: String::new(),
+            saved: SavedFilters::default(),
+            selected_saved: 0,
+            preset_filters: SavedFilters::default(),
+            editing_preset: None,
+            naming: None,
+            filling: None,
+            edited_at: None,
+            syntax: None,
+            vocabulary: Vec::new(),
+            completion: None,
             action_tx: None,
         }
     }
@@ -48,22 +201,172 @@ impl FilterDialog {
         Self::default()
     }
 
-    pub fn open(&mut self) {
+    pub fn open(&mut self, kind: FilterKind) {
         self.is_open = true;
+        self.kind = kind;
         self.filter_text.clear();
         self.cursor_position = 0;
         self.selected_preset = 0;
         self.mode = FilterMode::CustomInput;
+        self.error = None;
+        self.preview = None;
+        self.history = FilterHistory::load(kind.history_file());
+        self.history_position = None;
+        self.saved = SavedFilters::load(kind.saved_file());
+        self.selected_saved = 0;
+        self.preset_filters =
+            SavedFilters::load_or(kind.presets_file(), &Self::get_filter_presets(kind));
+        self.editing_preset = None;
+        self.naming = None;
+        self.filling = None;
+        self.edited_at = None;
+        self.syntax = None;
+        self.completion = None;
+    }
+
+    /// Sets the addresses and protocols of the capture offered as
+    /// completions.
+    pub fn set_vocabulary(&mut self, vocabulary: Vec<String>) {
+        self.vocabulary = vocabulary;
+    }
+
+    /// Puts the next (or previous) completion of the word before the cursor
+    /// in its place, first working out the completions. Returns whether
+    /// there were any.
+    fn complete(&mut self, forward: bool) -> bool {
+        let completion = match self.completion.take() {
+            Some(mut completion) => {
+                let len = completion.candidates.len();
+                completion.selected = if forward {
+                    (completion.selected + 1) % len
+                } else {
+                    (completion.selected + len - 1) % len
+                };
+                completion
+            }
+            None => {
+                let (start, prefix) =
+                    filter_completion::word_at(&self.filter_text, self.cursor_position);
+                let display = self.kind == FilterKind::Display;
+                let candidates = filter_completion::complete(prefix, display, &self.vocabulary);
+                if candidates.is_empty() {
+                    return false;
+                }
+                Completion {
+                    start,
+                    prefix: prefix.to_string(),
+                    selected: if forward { 0 } else { candidates.len() - 1 },
+                    candidates,
+                }
+            }
+        };
+        self.replace_word(&completion, &completion.candidates[completion.selected]);
+        self.completion = Some(completion);
+        true
+    }
+
+    /// Puts `word` in place of the word being completed.
+    fn replace_word(&mut self, completion: &Completion, word: &str) {
+        self.filter_text
+            .replace_range(completion.start..self.cursor_position, word);
+        self.cursor_position = completion.start + word.len();
+        self.edited();
+    }
+
+    /// Notes a change to the text of a capture filter, which is compiled
+    /// again once typing pauses.
+    fn edited(&mut self) {
+        if self.kind == FilterKind::Capture {
+            self.edited_at = Some(Instant::now());
+            self.syntax = None;
+            self.error = None;
+        }
+    }
+
+    /// Compiles the capture filter being typed once typing pauses.
+    fn check_syntax(&mut self) {
+        let Some(edited_at) = self.edited_at else {
+            return;
+        };
+        if edited_at.elapsed() < SYNTAX_DELAY {
+            return;
+        }
+        self.edited_at = None;
+        self.syntax = (!self.filter_text.trim().is_empty())
+            .then(|| live::check_filter(&self.filter_text).map_err(|e| format!("{e:#}")));
+    }
+
+    /// Shows the previous (older) or next (newer) filter of the history in
+    /// the input, coming back to what was being typed past the newest.
+    fn recall(&mut self, older: bool) {
+        let len = self.history.entries.len();
+        let position = match (self.history_position, older) {
+            (None, true) if len > 0 => {
+                self.draft = self.filter_text.clone();
+                Some(len - 1)
+            }
+            (Some(position), true) => Some(position.saturating_sub(1)),
+            (Some(position), false) if position + 1 < len => Some(position + 1),
+            (Some(_), false) => None,
+            (None, _) => return,
+        };
+        self.history_position = position;
+        self.filter_text = match position {
+            Some(position) => self.history.entries[position].clone(),
+            None => self.draft.clone(),
+        };
+        self.cursor_position = self.filter_text.len();
+    }
+
+    /// The display filter text to preview, if the current preview is out of
+    /// date for it or for `packet_count` captured packets.
+    pub fn preview_wanted(&self, packet_count: usize) -> Option<&str> {
+        if !self.is_open || self.kind != FilterKind::Display {
+            return None;
+        }
+        match self.preview {
+            Some(ref preview)
+                if preview.text == self.filter_text && preview.packet_count == packet_count =>
+            {
+                None
+            }
+            _ => Some(&self.filter_text),
+        }
+    }
+
+    pub fn set_preview(&mut self, preview: FilterPreview) {
+        self.preview = Some(preview);
     }
 
     pub fn close(&mut self) {
         self.is_open = false;
     }
 
-    pub fn get_filter_presets() -> Vec<(&'static str, &'static str)> {
+    /// The presets offered until the user edits them.
+    pub fn get_filter_presets(kind: FilterKind) -> Vec<(&'static str, &'static str)> {
+        if kind == FilterKind::Display {
+            return vec![
+                ("TCP Traffic", "tcp"),
+                ("UDP Traffic", "udp"),
+                ("Host", "ip.addr == {ip}"),
+                ("TCP Port", "tcp.port == {port}"),
+                ("HTTPS Traffic", "tcp.port == 443"),
+                ("DNS Traffic", "port == 53"),
+                ("Encrypted DNS", "dot or doh"),
+                ("Tunnelled Traffic", "gre or vxlan or geneve"),
+                ("VoIP Traffic", "sip or rtp"),
+                (
+                    "Private Networks",
+                    "ip.addr == 10.0.0.0/8 or ip.addr == 192.168.0.0/16",
+                ),
+                ("Large Packets", "frame.len > 1000"),
+            ];
+        }
         vec![
             ("TCP Traffic", "tcp"),
             ("UDP Traffic", "udp"),
+            ("Host", "host {ip}"),
+            ("TCP Port", "tcp port {port}"),
             ("HTTP Traffic", "tcp port 80 or tcp port 8080"),
             ("HTTPS Traffic", "tcp port 443"),
             ("DNS Traffic", "udp port 53 or tcp port 53"),
@@ -76,22 +379,165 @@ impl FilterDialog {
             ("Multicast", "multicast"),
             ("Large Packets", "greater 1000"),
             ("Small Packets", "less 100"),
-            ("Clear Filter", ""),
         ]
     }
 
+    /// The presets followed by the saved filters, with clearing the filter
+    /// kept last.
+    fn presets(&self) -> Vec<(String, String)> {
+        let mut presets = self.preset_filters.entries.clone();
+        presets.extend(self.saved.entries.iter().cloned());
+        presets.push((tr!("filter.clear").to_string(), String::new()));
+        presets
+    }
+
+    fn list_mut(&mut self, list: NameList) -> &mut SavedFilters {
+        match list {
+            NameList::Saved => &mut self.saved,
+            NameList::Presets => &mut self.preset_filters,
+        }
+    }
+
+    /// Whether `filter` can be applied, noting why not when it can't.
+    fn check(&mut self, filter: &str) -> bool {
+        if filter.trim().is_empty() {
+            return true;
+        }
+        let result = match self.kind {
+            FilterKind::Capture => live::check_filter(filter).map_err(|e| format!("{e:#}")),
+            FilterKind::Display => DisplayFilter::parse(filter)
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+        };
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                self.error = Some(e);
+                false
+            }
+        }
+    }
+
+    /// Saves the filter in the input under the name typed, or renames the
+    /// saved filter or preset being renamed. A preset being edited takes
+    /// the name and filter in place.
+    fn finish_naming(&mut self) {
+        let Some(naming) = self.naming.take() else {
+            return;
+        };
+        if naming.text.trim().is_empty() {
+            return;
+        }
+        match naming.renaming {
+            Some(index) => {
+                let list = self.list_mut(naming.list);
+                list.rename(index, &naming.text);
+                let selected = list
+                    .entries
+                    .iter()
+                    .position(|(name, _)| *name == naming.text.trim())
+                    .unwrap_or(0);
+                match naming.list {
+                    NameList::Saved => self.selected_saved = selected,
+                    NameList::Presets => self.selected_preset = selected,
+                }
+            }
+            None => {
+                let filter = self.filter_text.clone();
+                if filter.trim().is_empty() || !self.check(&filter) {
+                    return;
+                }
+                match naming.list {
+                    NameList::Saved => {
+                        self.selected_saved = self.saved.set(&naming.text, &filter);
+                        self.mode = FilterMode::Saved;
+                    }
+                    NameList::Presets => {
+                        self.selected_preset = match self.editing_preset.take() {
+                            Some(index) => {
+                                self.preset_filters.replace(index, &naming.text, &filter)
+                            }
+                            None => self.preset_filters.set(&naming.text, &filter),
+                        };
+                        self.mode = FilterMode::PresetSelection;
+                    }
+                }
+            }
+        }
+        if let Err(e) = self.list_mut(naming.list).save() {
+            self.error = Some(format!("{e:#}"));
+        }
+    }
+
+    fn delete_saved(&mut self) {
+        if self.selected_saved >= self.saved.entries.len() {
+            return;
+        }
+        self.saved.remove(self.selected_saved);
+        self.selected_saved = self
+            .selected_saved
+            .min(self.saved.entries.len().saturating_sub(1));
+        if let Err(e) = self.saved.save() {
+            self.error = Some(format!("{e:#}"));
+        }
+    }
+
+    fn delete_preset(&mut self) {
+        if self.selected_preset >= self.preset_filters.entries.len() {
+            return;
+        }
+        self.preset_filters.remove(self.selected_preset);
+        self.editing_preset = None;
+        self.selected_preset = self
+            .selected_preset
+            .min(self.presets().len().saturating_sub(1));
+        if let Err(e) = self.preset_filters.save() {
+            self.error = Some(format!("{e:#}"));
+        }
+    }
+
+    /// Applies a preset or saved filter, first asking for the values of
+    /// any placeholders in it.
+    fn choose(&mut self, filter: String) {
+        let placeholders = placeholders(&filter);
+        if placeholders.is_empty() {
+            self.apply_filter(filter);
+            return;
+        }
+        self.filling = Some(Template {
+            filter,
+            placeholders,
+            text: String::new(),
+        });
+    }
+
     fn apply_filter(&mut self, filter: String) {
+        let remembered = (!filter.trim().is_empty()).then(|| filter.clone());
+        if !self.check(&filter) {
+            self.mode = FilterMode::CustomInput;
+            return;
+        }
+        let action = match self.kind {
+            FilterKind::Capture => Action::ApplyFilter(filter),
+            FilterKind::Display => Action::ApplyDisplayFilter(filter),
+        };
+        if let Some(filter) = remembered {
+            self.history.add(&filter);
+            // Failing to keep the history shouldn't keep the filter from
+            // being applied.
+            let _ = self.history.save();
+        }
         if let Some(ref tx) = self.action_tx {
-            let _ = tx.send(Action::ApplyFilter(filter));
+            let _ = tx.send(action);
         }
         self.close();
     }
 
     fn render_custom_input(&self, f: &mut Frame, area: Rect) {
         let input_block = Block::default()
-            .title("Enter Custom Filter")
+            .title(tr!("filter.custom"))
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan));
+            .border_style(Style::default().fg(theme().accent));
 
         let input_area = Layout::default()
             .direction(Direction::Vertical)
@@ -101,29 +547,117 @@ impl FilterDialog {
         // Input field
         let input = Paragraph::new(self.filter_text.as_str())
             .block(input_block)
-            .style(Style::default().fg(Color::White))
+            .style(Style::default().fg(theme().text))
             .wrap(Wrap { trim: false });
 
         f.render_widget(input, input_area[0]);
 
         // Help text
-        let help_text = vec![
-            Line::from("Examples:"),
-            Line::from("  tcp port 80        - HTTP traffic"),
-            Line::from("  udp port 53        - DNS traffic"),
-            Line::from("  host 192.168.1.1   - Traffic to/from specific host"),
-            Line::from("  net 192.168.1.0/24 - Traffic from subnet"),
-            Line::from("  icmp               - ICMP packets"),
-            Line::from(""),
-            Line::from("Tab: Switch to presets  Enter: Apply  Esc: Cancel"),
-        ];
+        let mut help_text = match self.kind {
+            FilterKind::Capture => vec![
+                Line::from(tr!("filter.examples")),
+                Line::from(format!(
+                    "  tcp port 80        - {}",
+                    tr!("filter.example.http")
+                )),
+                Line::from(format!(
+                    "  udp port 53        - {}",
+                    tr!("filter.example.dns")
+                )),
+                Line::from(format!(
+                    "  host 192.168.1.1   - {}",
+                    tr!("filter.example.host")
+                )),
+                Line::from(format!(
+                    "  net 192.168.1.0/24 - {}",
+                    tr!("filter.example.net")
+                )),
+                Line::from(format!(
+                    "  icmp               - {}",
+                    tr!("filter.example.icmp")
+                )),
+            ],
+            FilterKind::Display => vec![
+                Line::from(tr!("filter.examples")),
+                Line::from(format!(
+                    "  tcp.port == 80              - {}",
+                    tr!("filter.example.http")
+                )),
+                Line::from(format!(
+                    "  ip.addr == 192.168.1.0/24   - {}",
+                    tr!("filter.example.subnet")
+                )),
+                Line::from(format!(
+                    "  vxlan.vni == 42 and udp     - {}",
+                    tr!("filter.example.vxlan")
+                )),
+                Line::from(format!(
+                    "  sip.call-id contains abc    - {}",
+                    tr!("filter.example.fields")
+                )),
+                Line::from(format!(
+                    "  not arp                     - {}",
+                    tr!("filter.example.not_arp")
+                )),
+            ],
+        };
+        if let Some(ref error) = self.error {
+            help_text.push(Line::from(""));
+            help_text.push(Line::styled(
+                tr!("filter.error", error),
+                Style::default().fg(theme().alert),
+            ));
+        }
+        match self.syntax {
+            Some(Ok(())) => {
+                help_text.push(Line::from(""));
+                help_text.push(Line::styled(
+                    tr!("filter.syntax_ok"),
+                    Style::default().fg(theme().value),
+                ));
+            }
+            Some(Err(ref e)) => {
+                help_text.push(Line::from(""));
+                help_text.push(Line::styled(
+                    tr!("filter.syntax_error", e),
+                    Style::default().fg(theme().highlight),
+                ));
+            }
+            None => {}
+        }
+        if let Some(ref preview) = self.preview
+            && !preview.text.trim().is_empty()
+        {
+            help_text.push(Line::from(""));
+            match preview.result {
+                Ok(ref matches) => {
+                    help_text.push(Line::styled(
+                        tr!("filter.preview", matches.matched, matches.scanned),
+                        Style::default().fg(theme().value),
+                    ));
+                    help_text.extend(matches.recent.iter().map(|line| {
+                        Line::styled(format!("  {line}"), Style::default().fg(theme().text))
+                    }));
+                }
+                Err(ref e) => help_text.push(Line::styled(
+                    tr!("filter.preview_invalid", e),
+                    Style::default().fg(theme().highlight),
+                )),
+            }
+        }
+        help_text.push(Line::from(""));
+        help_text.push(Line::from(tr!("filter.help.custom")));
 
         let help = Paragraph::new(help_text)
-            .block(Block::default().title("Help").borders(Borders::ALL))
-            .style(Style::default().fg(Color::Gray))
+            .block(Block::default().title(tr!("help")).borders(Borders::ALL))
+            .style(Style::default().fg(theme().muted))
             .wrap(Wrap { trim: false });
 
-        f.render_widget(help, input_area[1]); // Show cursor
+        f.render_widget(help, input_area[1]);
+        if let Some(ref completion) = self.completion {
+            self.render_completion(f, area, input_area[0], completion);
+        }
+        // Show cursor
         if self.mode == FilterMode::CustomInput {
             let cursor_x = input_area[0].x + 1 + self.cursor_position as u16;
             let cursor_y = input_area[0].y + 1;
@@ -136,28 +670,69 @@ impl FilterDialog {
         }
     }
 
+    /// The completions as a list under the word they complete in `input`.
+    fn render_completion(&self, f: &mut Frame, area: Rect, input: Rect, completion: &Completion) {
+        let width = completion
+            .candidates
+            .iter()
+            .map(|candidate| candidate.chars().count())
+            .max()
+            .unwrap_or(0) as u16
+            + 2;
+        let x = (input.x + 1 + completion.start as u16)
+            .min((area.x + area.width).saturating_sub(width));
+        let popup = Rect {
+            x,
+            y: input.y + input.height - 1,
+            width: width.min(area.width),
+            height: (completion.candidates.len().min(COMPLETION_LIMIT) as u16 + 2)
+                .min((area.y + area.height).saturating_sub(input.y + input.height - 1)),
+        };
+        let items: Vec<ListItem> = completion
+            .candidates
+            .iter()
+            .enumerate()
+            .map(|(i, candidate)| {
+                let style = if i == completion.selected {
+                    Style::default().bg(theme().selection).fg(theme().text)
+                } else {
+                    Style::default().fg(theme().text)
+                };
+                ListItem::new(candidate.as_str()).style(style)
+            })
+            .collect();
+        let list = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+                .style(Style::default().bg(theme().background)),
+        );
+        f.render_widget(Clear, popup);
+        f.render_widget(list, popup);
+    }
+
     fn render_preset_selection(&self, f: &mut Frame, area: Rect) {
-        let presets = Self::get_filter_presets();
+        let presets = self.presets();
 
         let items: Vec<ListItem> = presets
             .iter()
             .enumerate()
             .map(|(i, (name, filter))| {
                 let style = if i == self.selected_preset {
-                    Style::default().bg(Color::Blue).fg(Color::White)
+                    Style::default().bg(theme().selection).fg(theme().text)
                 } else {
-                    Style::default().fg(Color::White)
+                    Style::default().fg(theme().text)
                 };
 
                 let line = if filter.is_empty() {
                     Line::from(vec![
                         Span::styled(format!("{name:<20}"), style),
-                        Span::styled("(removes current filter)", Style::default().fg(Color::Gray)),
+                        Span::styled(tr!("filter.removes"), Style::default().fg(theme().muted)),
                     ])
                 } else {
                     Line::from(vec![
                         Span::styled(format!("{name:<20}"), style),
-                        Span::styled(format!("- {filter}"), Style::default().fg(Color::Gray)),
+                        Span::styled(format!("- {filter}"), Style::default().fg(theme().muted)),
                     ])
                 };
 
@@ -168,11 +743,11 @@ impl FilterDialog {
         let list = List::new(items)
             .block(
                 Block::default()
-                    .title("Filter Presets")
+                    .title(tr!("filter.presets"))
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Cyan)),
+                    .border_style(Style::default().fg(theme().accent)),
             )
-            .highlight_style(Style::default().bg(Color::Blue));
+            .highlight_style(Style::default().bg(theme().selection));
 
         f.render_widget(list, area);
 
@@ -184,16 +759,162 @@ impl FilterDialog {
             height: 3,
         };
 
-        let help =
-            Paragraph::new("Tab: Switch to custom input  Enter: Apply  ↑/↓: Navigate  Esc: Cancel")
-                .style(Style::default().fg(Color::Yellow))
-                .alignment(Alignment::Center)
-                .wrap(Wrap { trim: true });
+        let mut help = vec![Line::styled(
+            tr!("filter.help.presets"),
+            Style::default().fg(theme().highlight),
+        )];
+        if let Some(ref error) = self.error {
+            help.push(Line::styled(
+                tr!("filter.error", error),
+                Style::default().fg(theme().alert),
+            ));
+        }
+        let help = Paragraph::new(help)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
 
         f.render_widget(help, help_area);
     }
 }
 
+impl FilterDialog {
+    fn render_saved(&self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .split(area);
+
+        let items: Vec<ListItem> = if self.saved.entries.is_empty() {
+            vec![ListItem::new(Line::styled(
+                tr!("filter.saved.empty"),
+                Style::default().fg(theme().muted),
+            ))]
+        } else {
+            self.saved
+                .entries
+                .iter()
+                .enumerate()
+                .map(|(i, (name, filter))| {
+                    let style = if i == self.selected_saved {
+                        Style::default().bg(theme().selection).fg(theme().text)
+                    } else {
+                        Style::default().fg(theme().text)
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{name:<20}"), style),
+                        Span::styled(format!("- {filter}"), Style::default().fg(theme().muted)),
+                    ]))
+                    .style(style)
+                })
+                .collect()
+        };
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("filter.saved"))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent)),
+        );
+        f.render_widget(list, chunks[0]);
+
+        let mut help = vec![Line::styled(
+            tr!("filter.help.saved"),
+            Style::default().fg(theme().highlight),
+        )];
+        if let Some(ref error) = self.error {
+            help.push(Line::styled(
+                tr!("filter.error", error),
+                Style::default().fg(theme().alert),
+            ));
+        }
+        let help = Paragraph::new(help)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        f.render_widget(help, chunks[1]);
+    }
+
+    /// The name being typed, over the bottom of `area`.
+    fn render_naming(&self, f: &mut Frame, area: Rect, naming: &Naming) {
+        let title = match (naming.renaming, naming.list) {
+            (Some(_), _) => tr!("filter.rename").to_string(),
+            (None, NameList::Saved) => tr!("filter.save_as").to_string(),
+            (None, NameList::Presets) => tr!("filter.save_preset").to_string(),
+        };
+        self.render_prompt(f, area, title, &naming.text);
+    }
+
+    /// A line of input titled `title`, over the bottom of `area`.
+    fn render_prompt(&self, f: &mut Frame, area: Rect, title: String, text: &str) {
+        let naming_area = Rect {
+            x: area.x,
+            y: area.y + area.height.saturating_sub(3),
+            width: area.width,
+            height: area.height.min(3),
+        };
+        let input = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().highlight)),
+            )
+            .style(Style::default().fg(theme().text).bg(theme().background));
+        f.render_widget(Clear, naming_area);
+        f.render_widget(input, naming_area);
+        let cursor_x = naming_area.x + 1 + text.chars().count() as u16;
+        if cursor_x < naming_area.x + naming_area.width.saturating_sub(1) {
+            f.set_cursor_position(ratatui::layout::Position {
+                x: cursor_x,
+                y: naming_area.y + 1,
+            });
+        }
+    }
+
+    fn handle_naming(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let Some(ref mut naming) = self.naming else {
+            return Ok(None);
+        };
+        match key.code {
+            KeyCode::Esc => self.naming = None,
+            KeyCode::Enter => self.finish_naming(),
+            KeyCode::Backspace => {
+                naming.text.pop();
+            }
+            KeyCode::Char(c) => naming.text.push(c),
+            _ => {}
+        }
+        Ok(Some(Action::Handled))
+    }
+
+    /// Puts the value typed in place of the placeholder asked for, and
+    /// applies the filter once none are left.
+    fn handle_filling(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let Some(ref mut template) = self.filling else {
+            return Ok(None);
+        };
+        match key.code {
+            KeyCode::Esc => self.filling = None,
+            KeyCode::Enter if !template.text.trim().is_empty() => {
+                let name = template.placeholders.remove(0);
+                template.filter = template
+                    .filter
+                    .replace(&format!("{{{name}}}"), template.text.trim());
+                template.text.clear();
+                if template.placeholders.is_empty() {
+                    let filter = std::mem::take(&mut template.filter);
+                    self.filling = None;
+                    self.apply_filter(filter);
+                }
+            }
+            KeyCode::Backspace => {
+                template.text.pop();
+            }
+            KeyCode::Char(c) => template.text.push(c),
+            _ => {}
+        }
+        Ok(Some(Action::Handled))
+    }
+}
+
 impl Component for FilterDialog {
     fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
         self.action_tx = Some(tx);
@@ -201,14 +922,42 @@ impl Component for FilterDialog {
     }
 
     fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
-        if let Event::Key(key) = event {
-            self.handle_key_events(key)
-        } else {
-            Ok(None)
+        match event {
+            Event::Key(key) => self.handle_key_events(key),
+            Event::Tick => {
+                self.check_syntax();
+                Ok(None)
+            }
+            _ => Ok(None),
         }
     }
 
     fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.naming.is_some() {
+            return self.handle_naming(key);
+        }
+        if self.filling.is_some() {
+            return self.handle_filling(key);
+        }
+        if self.mode == FilterMode::CustomInput {
+            match key.code {
+                KeyCode::Tab | KeyCode::BackTab if self.complete(key.code == KeyCode::Tab) => {
+                    return Ok(Some(Action::Handled));
+                }
+                // Keeps the completion chosen.
+                KeyCode::Enter if self.completion.take().is_some() => {
+                    return Ok(Some(Action::Handled));
+                }
+                // Goes back to the word as typed.
+                KeyCode::Esc if self.completion.is_some() => {
+                    if let Some(completion) = self.completion.take() {
+                        self.replace_word(&completion, &completion.prefix);
+                    }
+                    return Ok(Some(Action::Handled));
+                }
+                _ => self.completion = None,
+            }
+        }
         match key.code {
             KeyCode::Esc => {
                 self.close();
@@ -217,7 +966,8 @@ impl Component for FilterDialog {
             KeyCode::Tab => {
                 self.mode = match self.mode {
                     FilterMode::CustomInput => FilterMode::PresetSelection,
-                    FilterMode::PresetSelection => FilterMode::CustomInput,
+                    FilterMode::PresetSelection => FilterMode::Saved,
+                    FilterMode::Saved => FilterMode::CustomInput,
                 };
                 Ok(Some(Action::Handled))
             }
@@ -228,9 +978,14 @@ impl Component for FilterDialog {
                         self.apply_filter(filter);
                     }
                     FilterMode::PresetSelection => {
-                        let presets = Self::get_filter_presets();
+                        let presets = self.presets();
                         if let Some((_, filter)) = presets.get(self.selected_preset) {
-                            self.apply_filter(filter.to_string());
+                            self.choose(filter.clone());
+                        }
+                    }
+                    FilterMode::Saved => {
+                        if let Some((_, filter)) = self.saved.entries.get(self.selected_saved) {
+                            self.choose(filter.clone());
                         }
                     }
                 }
@@ -239,6 +994,7 @@ impl Component for FilterDialog {
             _ => match self.mode {
                 FilterMode::CustomInput => self.handle_custom_input(key),
                 FilterMode::PresetSelection => self.handle_preset_sel(key),
+                FilterMode::Saved => self.handle_saved(key),
             },
         }
     }
@@ -250,7 +1006,35 @@ impl Component for FilterDialog {
 
 impl FilterDialog {
     fn handle_custom_input(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let before = self.filter_text.clone();
         match key.code {
+            KeyCode::Char('s')
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && !self.filter_text.trim().is_empty() =>
+            {
+                self.naming = Some(Naming {
+                    text: String::new(),
+                    renaming: None,
+                    list: NameList::Saved,
+                });
+            }
+            KeyCode::Char('p')
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && !self.filter_text.trim().is_empty() =>
+            {
+                // Editing a preset keeps its name unless another is typed.
+                let text = self
+                    .editing_preset
+                    .and_then(|index| self.preset_filters.entries.get(index))
+                    .map(|(name, _)| name.clone())
+                    .unwrap_or_default();
+                self.naming = Some(Naming {
+                    text,
+                    renaming: None,
+                    list: NameList::Presets,
+                });
+            }
+            KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => {}
             KeyCode::Char(c) => {
                 self.filter_text.insert(self.cursor_position, c);
                 self.cursor_position += 1;
@@ -282,13 +1066,39 @@ impl FilterDialog {
             KeyCode::End => {
                 self.cursor_position = self.filter_text.len();
             }
+            KeyCode::Up => self.recall(true),
+            KeyCode::Down => self.recall(false),
+            _ => {}
+        }
+        if self.filter_text != before {
+            self.edited();
+        }
+        Ok(Some(Action::Handled))
+    }
+
+    fn handle_saved(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Up => self.selected_saved = self.selected_saved.saturating_sub(1),
+            KeyCode::Down if self.selected_saved + 1 < self.saved.entries.len() => {
+                self.selected_saved += 1;
+            }
+            KeyCode::Char('r') => {
+                if let Some((name, _)) = self.saved.entries.get(self.selected_saved) {
+                    self.naming = Some(Naming {
+                        text: name.clone(),
+                        renaming: Some(self.selected_saved),
+                        list: NameList::Saved,
+                    });
+                }
+            }
+            KeyCode::Char('d') | KeyCode::Delete => self.delete_saved(),
             _ => {}
         }
         Ok(Some(Action::Handled))
     }
 
     fn handle_preset_sel(&mut self, key: KeyEvent) -> Result<Option<Action>> {
-        let presets = Self::get_filter_presets();
+        let presets = self.presets();
         match key.code {
             KeyCode::Up => {
                 if self.selected_preset > 0 {
@@ -306,6 +1116,32 @@ impl FilterDialog {
             KeyCode::End => {
                 self.selected_preset = presets.len().saturating_sub(1);
             }
+            KeyCode::Char('a') => {
+                self.filter_text.clear();
+                self.cursor_position = 0;
+                self.editing_preset = None;
+                self.mode = FilterMode::CustomInput;
+                self.edited();
+            }
+            KeyCode::Char('e') => {
+                if let Some((_, filter)) = self.preset_filters.entries.get(self.selected_preset) {
+                    self.filter_text = filter.clone();
+                    self.cursor_position = self.filter_text.len();
+                    self.editing_preset = Some(self.selected_preset);
+                    self.mode = FilterMode::CustomInput;
+                    self.edited();
+                }
+            }
+            KeyCode::Char('r') => {
+                if let Some((name, _)) = self.preset_filters.entries.get(self.selected_preset) {
+                    self.naming = Some(Naming {
+                        text: name.clone(),
+                        renaming: Some(self.selected_preset),
+                        list: NameList::Presets,
+                    });
+                }
+            }
+            KeyCode::Char('d') | KeyCode::Delete => self.delete_preset(),
             _ => {}
         }
         Ok(Some(Action::Handled))
@@ -340,10 +1176,13 @@ impl ComponentRender<()> for FilterDialog {
 
         // Render background block
         let bg_block = Block::default()
-            .title("Packet Filter")
+            .title(match self.kind {
+                FilterKind::Capture => tr!("filter.capture"),
+                FilterKind::Display => tr!("filter.display"),
+            })
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::White))
-            .style(Style::default().bg(Color::Black));
+            .border_style(Style::default().fg(theme().text))
+            .style(Style::default().bg(theme().background));
 
         f.render_widget(bg_block, popup_area);
 
@@ -358,6 +1197,18 @@ impl ComponentRender<()> for FilterDialog {
         match self.mode {
             FilterMode::CustomInput => self.render_custom_input(f, inner_area),
             FilterMode::PresetSelection => self.render_preset_selection(f, inner_area),
+            FilterMode::Saved => self.render_saved(f, inner_area),
+        }
+        if let Some(ref naming) = self.naming {
+            self.render_naming(f, inner_area, naming);
+        }
+        if let Some(ref template) = self.filling {
+            let title = tr!(
+                "filter.placeholder",
+                template.placeholders[0],
+                template.filter
+            );
+            self.render_prompt(f, inner_area, title, &template.text);
         }
     }
 }
@@ -0,0 +1,371 @@
+use std::{
+    cmp::Ordering,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::{
+        conversation::Conversation,
+        flow_tracker::{EndReason, EndedFlow, FlowState, FlowTracker},
+        time::format_utc,
+    },
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+/// Columns of the active flows, each of which they can be sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum SortColumn {
+    Flow,
+    Packets,
+    Bytes,
+    #[default]
+    FirstSeen,
+    LastSeen,
+    Duration,
+    State,
+}
+
+impl SortColumn {
+    const ALL: [SortColumn; 7] = [
+        SortColumn::Flow,
+        SortColumn::Packets,
+        SortColumn::Bytes,
+        SortColumn::FirstSeen,
+        SortColumn::LastSeen,
+        SortColumn::Duration,
+        SortColumn::State,
+    ];
+
+    fn title(self) -> &'static str {
+        match self {
+            SortColumn::Flow => "Flow",
+            SortColumn::Packets => "Packets",
+            SortColumn::Bytes => "Bytes",
+            SortColumn::FirstSeen => "First Seen",
+            SortColumn::LastSeen => "Last Seen",
+            SortColumn::Duration => "Duration",
+            SortColumn::State => "State",
+        }
+    }
+
+    fn width(self) -> usize {
+        match self {
+            SortColumn::Flow => 60,
+            SortColumn::Packets => 10,
+            SortColumn::Bytes => 12,
+            SortColumn::FirstSeen | SortColumn::LastSeen => 12,
+            SortColumn::Duration => 12,
+            SortColumn::State => 0,
+        }
+    }
+
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|&c| c == self).unwrap_or(0)
+    }
+
+    fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    fn previous(self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Counts put the largest first, first seen the oldest and last seen
+    /// the most recent.
+    fn compare(self, x: &(Conversation, FlowState), y: &(Conversation, FlowState)) -> Ordering {
+        let ((x, x_state), (y, y_state)) = (x, y);
+        match self {
+            SortColumn::Flow => x.key.to_string().cmp(&y.key.to_string()),
+            SortColumn::Packets => y.packets().cmp(&x.packets()),
+            SortColumn::Bytes => y.bytes().cmp(&x.bytes()),
+            SortColumn::FirstSeen => x.first.cmp(&y.first),
+            SortColumn::LastSeen => y.last.cmp(&x.last),
+            SortColumn::Duration => y.duration().cmp(&x.duration()),
+            SortColumn::State => x_state.cmp(y_state),
+        }
+    }
+}
+
+/// Time of day a flow was seen, UTC to the second.
+fn time_of_day(at: SystemTime) -> String {
+    let since = at.duration_since(UNIX_EPOCH).unwrap_or_default();
+    format_utc(since.as_secs_f64())[11..19].to_string()
+}
+
+#[derive(Default)]
+pub struct FlowsPage {
+    active: Vec<(Conversation, FlowState)>,
+    ended: Vec<EndedFlow>, // Most recently ended first
+    dropped: usize,
+    sort_column: SortColumn,
+    reversed: bool,
+    showing_ended: bool,
+    selected: usize,
+    scroll_position: usize,
+    visible_rows: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+impl FlowsPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_flows(&mut self, tracker: &FlowTracker) {
+        self.active = tracker
+            .active()
+            .map(|flow| (flow.conversation.clone(), flow.state()))
+            .collect();
+        self.sort();
+        self.ended = tracker.ended().iter().rev().cloned().collect();
+        self.dropped = tracker.dropped();
+        self.selected = self.selected.min(self.len().saturating_sub(1));
+    }
+
+    fn sort(&mut self) {
+        let (column, reversed) = (self.sort_column, self.reversed);
+        self.active.sort_by(|x, y| {
+            let order = column.compare(x, y);
+            if reversed { order.reverse() } else { order }
+        });
+    }
+
+    fn resort(&mut self) {
+        self.sort();
+        self.selected = 0;
+        self.scroll_position = 0;
+    }
+
+    fn len(&self) -> usize {
+        if self.showing_ended {
+            self.ended.len()
+        } else {
+            self.active.len()
+        }
+    }
+
+    fn selected_conversation(&self) -> Option<&Conversation> {
+        if self.showing_ended {
+            self.ended.get(self.selected).map(|flow| &flow.conversation)
+        } else {
+            self.active
+                .get(self.selected)
+                .map(|(conversation, _)| conversation)
+        }
+    }
+
+    fn render_flows(&mut self, f: &mut Frame, area: Rect) {
+        let bold = Style::default()
+            .fg(theme().text)
+            .add_modifier(Modifier::BOLD);
+        let header = ListItem::new(Line::from(if self.showing_ended {
+            vec![Span::styled("Summary", bold)]
+        } else {
+            SortColumn::ALL
+                .iter()
+                .map(|&column| {
+                    let mut title = column.title().to_string();
+                    let mut style = bold;
+                    if column == self.sort_column {
+                        title.push(if self.reversed { '▲' } else { '▼' });
+                        style = style.fg(theme().highlight);
+                    }
+                    Span::styled(format!("{title:<width$}", width = column.width()), style)
+                })
+                .collect()
+        }));
+
+        self.visible_rows = (area.height as usize).saturating_sub(3);
+        if self.selected < self.scroll_position {
+            self.scroll_position = self.selected;
+        } else if self.selected >= self.scroll_position + self.visible_rows {
+            self.scroll_position = self.selected + 1 - self.visible_rows;
+        }
+
+        let row_style = |i: usize| {
+            if i == self.selected {
+                Style::default()
+                    .bg(theme().selection)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            }
+        };
+        let mut items = vec![header];
+        if self.showing_ended {
+            items.extend(
+                self.ended
+                    .iter()
+                    .enumerate()
+                    .skip(self.scroll_position)
+                    .take(self.visible_rows)
+                    .map(|(i, flow)| {
+                        let reason_color = match flow.reason {
+                            EndReason::Fin => theme().value,
+                            EndReason::Reset => theme().alert,
+                            EndReason::Timeout => theme().highlight,
+                        };
+                        ListItem::new(Line::from(Span::styled(
+                            flow.summary(),
+                            row_style(i).fg(reason_color),
+                        )))
+                    }),
+            );
+        } else {
+            items.extend(
+                self.active
+                    .iter()
+                    .enumerate()
+                    .skip(self.scroll_position)
+                    .take(self.visible_rows)
+                    .map(|(i, (conversation, state))| {
+                        let state_color = match state {
+                            FlowState::Handshake => theme().highlight,
+                            FlowState::Closing => theme().alert,
+                            FlowState::Established | FlowState::Open => theme().value,
+                        };
+                        ListItem::new(Line::from(vec![
+                            Span::styled(
+                                format!("{:<60.59}", conversation.key.to_string()),
+                                row_style(i).fg(theme().secondary),
+                            ),
+                            Span::styled(
+                                format!("{:<10}", conversation.packets()),
+                                row_style(i).fg(theme().value),
+                            ),
+                            Span::styled(
+                                format!("{:<12}", conversation.bytes()),
+                                row_style(i).fg(theme().value),
+                            ),
+                            Span::styled(
+                                format!("{:<12}", time_of_day(conversation.first)),
+                                row_style(i).fg(theme().muted),
+                            ),
+                            Span::styled(
+                                format!("{:<12}", time_of_day(conversation.last)),
+                                row_style(i).fg(theme().muted),
+                            ),
+                            Span::styled(
+                                format!("{:<12.3}", conversation.duration().as_secs_f64()),
+                                row_style(i).fg(theme().accent),
+                            ),
+                            Span::styled(state.label(), row_style(i).fg(state_color)),
+                        ]))
+                    }),
+            );
+        }
+
+        let title = if self.showing_ended && self.dropped > 0 {
+            format!(
+                "Ended Flows ({}, oldest {} dropped)",
+                self.ended.len(),
+                self.dropped
+            )
+        } else if self.showing_ended {
+            format!("Ended Flows ({})", self.ended.len())
+        } else {
+            format!("Active Flows ({})", self.active.len())
+        };
+        let list = List::new(items).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("flows.help"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for FlowsPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Tab => {
+                self.showing_ended = !self.showing_ended;
+                self.selected = 0;
+                self.scroll_position = 0;
+            }
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down if self.selected + 1 < self.len() => {
+                self.selected += 1;
+            }
+            KeyCode::Left if !self.showing_ended => {
+                self.sort_column = self.sort_column.previous();
+                self.resort();
+            }
+            KeyCode::Right if !self.showing_ended => {
+                self.sort_column = self.sort_column.next();
+                self.resort();
+            }
+            KeyCode::Char('r') if !self.showing_ended => {
+                self.reversed = !self.reversed;
+                self.resort();
+            }
+            KeyCode::Enter => {
+                if let Some(conversation) = self.selected_conversation() {
+                    return Ok(Some(Action::ApplyDisplayFilter(
+                        conversation.display_filter(),
+                    )));
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for FlowsPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_flows(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
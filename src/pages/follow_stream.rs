@@ -0,0 +1,248 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::{flow::FlowKey, packet_export::hex_row, udp_stream::UdpStream},
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+/// Which side's datagrams are shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Sides {
+    #[default]
+    Both,
+    FromA,
+    FromB,
+}
+
+impl Sides {
+    fn next(self) -> Self {
+        match self {
+            Sides::Both => Sides::FromA,
+            Sides::FromA => Sides::FromB,
+            Sides::FromB => Sides::Both,
+        }
+    }
+
+    fn shows(self, from_a: bool) -> bool {
+        match self {
+            Sides::Both => true,
+            Sides::FromA => from_a,
+            Sides::FromB => !from_a,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct FollowStreamPage {
+    stream: Option<UdpStream>,
+    hex: bool,
+    concatenated: bool, // Payloads run together without a line per datagram
+    sides: Sides,
+    scroll_position: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+impl FollowStreamPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn key(&self) -> Option<&FlowKey> {
+        self.stream.as_ref().map(|stream| &stream.key)
+    }
+
+    /// Shows `stream`, keeping the scroll position when it is the same
+    /// stream with more datagrams.
+    pub fn set_stream(&mut self, stream: UdpStream) {
+        if self.key() != Some(&stream.key) {
+            self.scroll_position = 0;
+        }
+        self.stream = Some(stream);
+    }
+
+    fn stream_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        let Some(ref stream) = self.stream else {
+            return lines;
+        };
+        let start = stream
+            .datagrams
+            .first()
+            .map(|datagram| datagram.captured_at);
+        for datagram in &stream.datagrams {
+            if !self.sides.shows(datagram.from_a) {
+                continue;
+            }
+            let (src, dst, color) = if datagram.from_a {
+                (&stream.key.a, &stream.key.b, theme().alert)
+            } else {
+                (&stream.key.b, &stream.key.a, theme().accent)
+            };
+            let offset = start
+                .and_then(|start| datagram.captured_at.duration_since(start).ok())
+                .unwrap_or_default();
+            if !self.concatenated {
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "── #{}  {src} → {dst}  {} bytes  +{:.6}s ──",
+                        datagram.id,
+                        datagram.payload().len(),
+                        offset.as_secs_f64()
+                    ),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                )));
+            }
+            if self.hex {
+                for (i, chunk) in datagram.payload().chunks(16).enumerate() {
+                    let (hex, ascii) = hex_row(chunk);
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("{:04x}  ", i * 16),
+                            Style::default().fg(theme().muted),
+                        ),
+                        Span::styled(hex, Style::default().fg(color)),
+                        Span::styled(ascii, Style::default().fg(theme().muted)),
+                    ]));
+                }
+            } else {
+                // Printable text as-is, line breaks kept, anything else as '.'.
+                let text: String = datagram
+                    .payload()
+                    .iter()
+                    .filter(|&&b| b != b'\r')
+                    .map(|&b| match b {
+                        b'\n' => '\n',
+                        b'\t' => ' ',
+                        b if b.is_ascii_graphic() || b == b' ' => b as char,
+                        _ => '.',
+                    })
+                    .collect();
+                lines.extend(text.lines().map(|line| {
+                    Line::from(Span::styled(line.to_string(), Style::default().fg(color)))
+                }));
+            }
+        }
+        lines
+    }
+
+    fn render_stream(&self, f: &mut Frame, area: Rect) {
+        let lines = self.stream_lines();
+        let sides = match self.sides {
+            Sides::Both => "",
+            Sides::FromA => tr!("follow_stream.from_a"),
+            Sides::FromB => tr!("follow_stream.from_b"),
+        };
+        let title = match self.stream {
+            Some(ref stream) => tr!(
+                "follow_stream.title",
+                stream.key,
+                stream.datagrams.len(),
+                stream.bytes(true),
+                stream.bytes(false),
+                sides
+            ),
+            None => tr!("follow_stream.title_empty").to_string(),
+        };
+        let paragraph = Paragraph::new(lines)
+            .scroll((self.scroll_position.min(u16::MAX as usize) as u16, 0))
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().border)),
+            );
+        f.render_widget(paragraph, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("follow_stream.help"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for FollowStreamPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let max_scroll = self.stream_lines().len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToSniffer)),
+            KeyCode::Char('h') => {
+                self.hex = !self.hex;
+                self.scroll_position = 0;
+            }
+            KeyCode::Char('c') => {
+                self.concatenated = !self.concatenated;
+                self.scroll_position = 0;
+            }
+            KeyCode::Char('d') => {
+                self.sides = self.sides.next();
+                self.scroll_position = 0;
+            }
+            KeyCode::Up => {
+                self.scroll_position = self.scroll_position.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                self.scroll_position = (self.scroll_position + 1).min(max_scroll);
+            }
+            KeyCode::PageUp => {
+                self.scroll_position = self.scroll_position.saturating_sub(10);
+            }
+            KeyCode::PageDown => {
+                self.scroll_position = (self.scroll_position + 10).min(max_scroll);
+            }
+            KeyCode::Home => {
+                self.scroll_position = 0;
+            }
+            KeyCode::End => {
+                self.scroll_position = max_scroll;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for FollowStreamPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_stream(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
@@ -3,7 +3,7 @@ use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEventKind};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
 };
@@ -11,6 +11,8 @@ use ratatui::{
 use crate::{
     action::Action,
     component::{Component, ComponentRender},
+    i18n::tr,
+    theme::theme,
     tui::Event,
 };
 
@@ -19,6 +21,7 @@ pub struct HomePage {
     list_state: ListState,
     action_tx: Option<tokio::sync::mpsc::UnboundedSender<Action>>,
     mouse_event: Option<(u16, u16)>,
+    capture_unavailable: Option<String>, // Why live capture can't be used
 }
 
 impl HomePage {
@@ -28,67 +31,188 @@ impl HomePage {
         home
     }
 
+    /// Disables device selection and replay, explaining `reason` in the status box.
+    pub fn set_capture_unavailable(&mut self, reason: String) {
+        self.capture_unavailable = Some(reason);
+        if self.list_state.selected() == Some(1) {
+            self.list_state.select(Some(2));
+        }
+    }
+
+    fn is_enabled(&self, action: &Action) -> bool {
+        self.capture_unavailable.is_none()
+            || !matches!(action, Action::NavigateToDevice | Action::NavigateToReplay)
+    }
+
+    fn menu_items() -> Vec<(&'static str, &'static str, Action)> {
+        vec![
+            (
+                tr!("home.device"),
+                tr!("home.device.about"),
+                Action::NavigateToDevice,
+            ),
+            (
+                tr!("home.sniffer"),
+                tr!("home.sniffer.about"),
+                Action::NavigateToSniffer,
+            ),
+            (
+                tr!("home.voip"),
+                tr!("home.voip.about"),
+                Action::NavigateToVoip,
+            ),
+            (
+                tr!("home.dual_stack"),
+                tr!("home.dual_stack.about"),
+                Action::NavigateToDualStack,
+            ),
+            (
+                tr!("home.other_l2"),
+                tr!("home.other_l2.about"),
+                Action::NavigateToOtherL2,
+            ),
+            (
+                tr!("home.neighbors"),
+                tr!("home.neighbors.about"),
+                Action::NavigateToNeighbors,
+            ),
+            (
+                tr!("home.discovery"),
+                tr!("home.discovery.about"),
+                Action::NavigateToDiscovery,
+            ),
+            (
+                tr!("home.multicast"),
+                tr!("home.multicast.about"),
+                Action::NavigateToMulticast,
+            ),
+            (
+                tr!("home.conversations"),
+                tr!("home.conversations.about"),
+                Action::NavigateToConversations,
+            ),
+            (
+                tr!("home.endpoints"),
+                tr!("home.endpoints.about"),
+                Action::NavigateToEndpoints,
+            ),
+            (
+                tr!("home.flows"),
+                tr!("home.flows.about"),
+                Action::NavigateToFlows,
+            ),
+            (
+                tr!("home.performance"),
+                tr!("home.performance.about"),
+                Action::NavigateToPerformance,
+            ),
+            (
+                tr!("home.timeline"),
+                tr!("home.timeline.about"),
+                Action::NavigateToTimeline,
+            ),
+            (
+                tr!("home.latency"),
+                tr!("home.latency.about"),
+                Action::NavigateToLatency,
+            ),
+            (
+                tr!("home.dns"),
+                tr!("home.dns.about"),
+                Action::NavigateToDns,
+            ),
+            (
+                tr!("home.http"),
+                tr!("home.http.about"),
+                Action::NavigateToHttp,
+            ),
+            (
+                tr!("home.top_talkers"),
+                tr!("home.top_talkers.about"),
+                Action::NavigateToTopTalkers,
+            ),
+            (
+                tr!("home.objects"),
+                tr!("home.objects.about"),
+                Action::NavigateToObjects,
+            ),
+            (
+                tr!("home.alerts"),
+                tr!("home.alerts.about"),
+                Action::NavigateToAlerts,
+            ),
+            (
+                tr!("home.replay"),
+                tr!("home.replay.about"),
+                Action::NavigateToReplay,
+            ),
+        ]
+    }
+
+    fn menu_action(index: usize) -> Option<Action> {
+        Self::menu_items()
+            .into_iter()
+            .nth(index.checked_sub(1)?)
+            .map(|(_, _, action)| action)
+    }
+
     fn render_menu(&self, f: &mut Frame, area: Rect) {
         let header = ListItem::new(Line::from(vec![
             Span::styled(
                 format!("{:<4}", "No."),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 format!("{:<20}", "Module"),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 "Description",
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
         ]));
 
         let mut items = vec![header];
 
-        let menu_items = vec![
-            ListItem::new(Line::from(vec![
-                Span::styled(format!("{:<4}", "1"), Style::default().fg(Color::Yellow)),
-                Span::styled(
-                    format!("{:<20}", "Device Selection"),
-                    Style::default().fg(Color::Cyan),
-                ),
-                Span::styled(
-                    "Select network interface for packet capture",
-                    Style::default().fg(Color::Gray),
-                ),
-            ])),
-            ListItem::new(Line::from(vec![
-                Span::styled(format!("{:<4}", "2"), Style::default().fg(Color::Yellow)),
-                Span::styled(
-                    format!("{:<20}", "Packet Sniffer"),
-                    Style::default().fg(Color::Cyan),
-                ),
-                Span::styled(
-                    "Capture and analyze network packets",
-                    Style::default().fg(Color::Gray),
-                ),
-            ])),
-        ];
-
-        items.extend(menu_items);
+        items.extend(Self::menu_items().into_iter().enumerate().map(
+            |(i, (name, description, action))| {
+                if !self.is_enabled(&action) {
+                    let style = Style::default()
+                        .fg(theme().faint)
+                        .add_modifier(Modifier::CROSSED_OUT);
+                    return ListItem::new(Line::from(vec![
+                        Span::styled(format!("{:<4}", i + 1), style),
+                        Span::styled(format!("{name:<20}"), style),
+                        Span::styled(tr!("home.unavailable"), Style::default().fg(theme().faint)),
+                    ]));
+                }
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<4}", i + 1),
+                        Style::default().fg(theme().highlight),
+                    ),
+                    Span::styled(format!("{name:<20}"), Style::default().fg(theme().accent)),
+                    Span::styled(description, Style::default().fg(theme().muted)),
+                ]))
+            },
+        ));
 
         let list = List::new(items)
             .block(
                 Block::default()
-                    .title("Network Packet Sniffer")
+                    .title(tr!("home.title"))
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Blue)),
+                    .border_style(Style::default().fg(theme().border)),
             )
             .highlight_style(
                 Style::default()
-                    .bg(Color::DarkGray)
+                    .bg(theme().inactive_selection)
                     .add_modifier(Modifier::BOLD),
             );
 
@@ -96,25 +220,28 @@ impl HomePage {
     }
 
     fn render_status(&self, f: &mut Frame, area: Rect) {
-        let status =
-            Paragraph::new("Welcome to Network Packet Sniffer. Select a module to continue.")
-                .block(
-                    Block::default()
-                        .title("Status")
-                        .borders(Borders::ALL)
-                        .border_style(Style::default().fg(Color::Blue)),
-                )
-                .style(Style::default().fg(Color::Green))
-                .wrap(Wrap { trim: true });
+        let (text, color) = match self.capture_unavailable {
+            Some(ref reason) => (tr!("home.analysis_only", reason), theme().highlight),
+            None => (tr!("home.welcome").to_string(), theme().value),
+        };
+        let status = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(tr!("status"))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().border)),
+            )
+            .style(Style::default().fg(color))
+            .wrap(Wrap { trim: true });
 
         f.render_widget(status, area);
     }
 
     fn render_help(&self, f: &mut Frame, area: Rect) {
-        let help_text = "↑/↓: Navigate  Enter: Select Module  D: Device Selection  S: Packet Sniffer  Q/Esc: Exit";
+        let help_text = tr!("home.help");
 
         let help = Paragraph::new(help_text)
-            .style(Style::default().fg(Color::Cyan))
+            .style(Style::default().fg(theme().accent))
             .wrap(Wrap { trim: true })
             .alignment(ratatui::layout::Alignment::Center)
             .block(Block::default().borders(Borders::NONE));
@@ -126,14 +253,10 @@ impl HomePage {
         if x >= area.x && x < area.x + area.width && y > area.y + 1 && y < area.y + area.height - 1
         {
             let clicked_index = (y - area.y - 2) as usize;
-            if clicked_index < 2 {
+            if clicked_index < Self::menu_items().len() {
                 let menu_item = clicked_index + 1;
                 if self.list_state.selected() == Some(menu_item) {
-                    match menu_item {
-                        1 => return Some(Action::NavigateToDevice),
-                        2 => return Some(Action::NavigateToSniffer),
-                        _ => {}
-                    }
+                    return Self::menu_action(menu_item).filter(|a| self.is_enabled(a));
                 } else {
                     self.list_state.select(Some(menu_item));
                 }
@@ -171,7 +294,7 @@ impl Component for HomePage {
                 let i = match self.list_state.selected() {
                     Some(i) => {
                         if i <= 1 {
-                            2
+                            Self::menu_items().len()
                         } else {
                             i - 1
                         }
@@ -183,7 +306,7 @@ impl Component for HomePage {
             KeyCode::Down => {
                 let i = match self.list_state.selected() {
                     Some(i) => {
-                        if i >= 2 {
+                        if i >= Self::menu_items().len() {
                             1
                         } else {
                             i + 1
@@ -193,12 +316,16 @@ impl Component for HomePage {
                 };
                 self.list_state.select(Some(i));
             }
-            KeyCode::Enter => match self.list_state.selected() {
-                Some(1) => return Ok(Some(Action::NavigateToDevice)),
-                Some(2) => return Ok(Some(Action::NavigateToSniffer)),
-                _ => {}
-            },
-            KeyCode::Char('d') => return Ok(Some(Action::NavigateToDevice)),
+            KeyCode::Enter => {
+                if let Some(action) = self.list_state.selected().and_then(Self::menu_action)
+                    && self.is_enabled(&action)
+                {
+                    return Ok(Some(action));
+                }
+            }
+            KeyCode::Char('d') if self.capture_unavailable.is_none() => {
+                return Ok(Some(Action::NavigateToDevice));
+            }
             KeyCode::Char('s') => return Ok(Some(Action::NavigateToSniffer)),
             KeyCode::Char('q') => {
                 return Ok(Some(Action::Quit));
@@ -0,0 +1,251 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::http_transactions::HttpStats,
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+/// Width of the bar showing each status code's share of the responses.
+const BAR_WIDTH: usize = 10;
+
+#[derive(Default)]
+pub struct HttpPage {
+    stats: HttpStats,
+    scroll_position: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+fn format_latency(latency: Option<Duration>) -> String {
+    match latency {
+        Some(latency) => format!("{:.2} ms", latency.as_secs_f64() * 1000.0),
+        None => "-".to_string(),
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        206 => "Partial Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        307 => "Temporary Redirect",
+        308 => "Permanent Redirect",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "",
+    }
+}
+
+fn status_color(status: u16) -> Color {
+    match status {
+        200..=299 => theme().value,
+        300..=399 => theme().secondary,
+        400..=499 => theme().warning,
+        _ => theme().alert,
+    }
+}
+
+fn header(columns: &[(&str, usize)]) -> ListItem<'static> {
+    ListItem::new(Line::from(
+        columns
+            .iter()
+            .map(|(name, width)| {
+                Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect::<Vec<_>>(),
+    ))
+}
+
+impl HttpPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_stats(&mut self, stats: HttpStats) {
+        self.stats = stats;
+        self.scroll_position = self
+            .scroll_position
+            .min(self.stats.slowest.len().saturating_sub(1));
+    }
+
+    fn render_statuses(&self, f: &mut Frame, area: Rect) {
+        let answered: usize = self.stats.statuses.values().sum();
+        let mut items = vec![header(&[("Status", 30), ("Responses", 11), ("Share", 0)])];
+        items.extend(self.stats.statuses.iter().map(|(&status, &count)| {
+            let share = count as f64 / answered.max(1) as f64;
+            let filled = (share * BAR_WIDTH as f64).round() as usize;
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<30.29}", format!("{status} {}", reason(status))),
+                    Style::default().fg(status_color(status)),
+                ),
+                Span::styled(format!("{count:<11}"), Style::default().fg(theme().value)),
+                Span::styled(
+                    format!("{:<BAR_WIDTH$}", "█".repeat(filled.min(BAR_WIDTH))),
+                    Style::default().fg(theme().highlight),
+                ),
+                Span::styled(
+                    format!(" {:>5.1}%", share * 100.0),
+                    Style::default().fg(theme().muted),
+                ),
+            ]))
+        }));
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!(
+                    "http.status_title",
+                    self.stats.requests,
+                    self.stats.unanswered
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_slowest(&self, f: &mut Frame, area: Rect) {
+        // The URL takes what the other columns leave.
+        let url_width = (area.width as usize).saturating_sub(2 + 9 + 10 + 12 + 12);
+        let mut items = vec![header(&[
+            ("Method", 9),
+            ("URL", url_width),
+            ("Requests", 10),
+            ("Slowest", 12),
+            ("Mean", 0),
+        ])];
+        let visible = (area.height as usize).saturating_sub(3);
+        items.extend(
+            self.stats
+                .slowest
+                .iter()
+                .skip(self.scroll_position)
+                .take(visible)
+                .map(|url| {
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{:<9}", url.method),
+                            Style::default().fg(theme().accent),
+                        ),
+                        Span::styled(
+                            format!(
+                                "{:<url_width$.max$}",
+                                url.url,
+                                max = url_width.saturating_sub(1)
+                            ),
+                            Style::default().fg(theme().secondary),
+                        ),
+                        Span::styled(
+                            format!("{:<10}", url.requests),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", format_latency(Some(url.slowest))),
+                            Style::default().fg(theme().highlight),
+                        ),
+                        Span::styled(
+                            format_latency(url.mean()),
+                            Style::default().fg(theme().highlight),
+                        ),
+                    ]))
+                }),
+        );
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("http.urls_title"))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("stats.help.scroll"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for HttpPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Up => {
+                self.scroll_position = self.scroll_position.saturating_sub(1);
+            }
+            KeyCode::Down if self.scroll_position + 1 < self.stats.slowest.len() => {
+                self.scroll_position += 1;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for HttpPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(chunks[0]);
+
+        self.render_statuses(f, columns[0]);
+        self.render_slowest(f, columns[1]);
+        self.render_help(f, chunks[1]);
+    }
+}
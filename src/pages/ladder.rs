@@ -0,0 +1,327 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Chart, Clear, Dataset, GraphType, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::{
+        flow::FlowKey,
+        tcp_graph::TcpGraph,
+        tcp_ladder::{Ladder, Note},
+    },
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+/// Width of the arrow between the client and server columns.
+const ARROW_WIDTH: usize = 60;
+/// Width of the packet number and time columns before the ladder.
+const PREFIX_WIDTH: usize = 20;
+
+/// What the graph popup over the ladder plots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GraphKind {
+    Sequence, // Sequence numbers over time, like tcptrace
+    Throughput,
+}
+
+#[derive(Default)]
+pub struct LadderPage {
+    ladder: Option<Ladder>,
+    scroll_position: usize,
+    graph: Option<GraphKind>, // While the popup is open
+    graph_from_client: bool,  // Direction graphed
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+impl LadderPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn key(&self) -> Option<&FlowKey> {
+        self.ladder.as_ref().map(|ladder| &ladder.key)
+    }
+
+    /// Shows `ladder`, keeping the scroll position when it is the same
+    /// connection with more segments.
+    pub fn set_ladder(&mut self, ladder: Ladder) {
+        if self.key() != Some(&ladder.key) {
+            self.scroll_position = 0;
+        }
+        self.ladder = Some(ladder);
+    }
+
+    fn ladder_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+        let Some(ref ladder) = self.ladder else {
+            return lines;
+        };
+        let client = ladder.client.to_string();
+        let server = ladder.server.to_string();
+        let gap = (ARROW_WIDTH + 2).saturating_sub(client.chars().count());
+        lines.push(Line::from(Span::styled(
+            format!("{:PREFIX_WIDTH$}{client}{server:>gap$}", ""),
+            Style::default()
+                .fg(theme().text)
+                .add_modifier(Modifier::BOLD),
+        )));
+        let start = ladder.rungs.first().map(|rung| rung.captured_at);
+        for rung in &ladder.rungs {
+            let offset = start
+                .and_then(|start| rung.captured_at.duration_since(start).ok())
+                .unwrap_or_default();
+            let label = rung.label();
+            let dashes = "─".repeat(ARROW_WIDTH.saturating_sub(label.chars().count() + 3));
+            let (arrow, color) = if rung.from_client {
+                (format!(" {label} {dashes}▶"), theme().alert)
+            } else {
+                (format!("◀{dashes} {label} "), theme().accent)
+            };
+            let mut spans = vec![
+                Span::styled(
+                    format!(
+                        "{:<8}{:<12}",
+                        format!("#{}", rung.id),
+                        format!("+{:.6}", offset.as_secs_f64())
+                    ),
+                    Style::default().fg(theme().muted),
+                ),
+                Span::styled("│", Style::default().fg(theme().text)),
+                Span::styled(arrow, Style::default().fg(color)),
+                Span::styled("│", Style::default().fg(theme().text)),
+            ];
+            if let Some(note) = rung.note {
+                let note_color = match note {
+                    Note::Retransmission => theme().warning,
+                    Note::DuplicateAck(_) => theme().highlight,
+                };
+                spans.push(Span::styled(
+                    format!(" {}", note.label()),
+                    Style::default().fg(note_color).add_modifier(Modifier::BOLD),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines
+    }
+
+    fn render_ladder(&self, f: &mut Frame, area: Rect) {
+        let lines = self.ladder_lines();
+        let title = match self.ladder {
+            Some(ref ladder) => {
+                let count = |wanted: fn(Note) -> bool| {
+                    ladder
+                        .rungs
+                        .iter()
+                        .filter(|rung| rung.note.is_some_and(wanted))
+                        .count()
+                };
+                tr!(
+                    "ladder.title",
+                    ladder.key,
+                    ladder.rungs.len(),
+                    count(|note| note == Note::Retransmission),
+                    count(|note| matches!(note, Note::DuplicateAck(_)))
+                )
+            }
+            None => tr!("ladder.title_empty").to_string(),
+        };
+        let paragraph = Paragraph::new(lines)
+            .scroll((self.scroll_position.min(u16::MAX as usize) as u16, 0))
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().border)),
+            );
+        f.render_widget(paragraph, area);
+    }
+
+    fn render_graph(&self, f: &mut Frame, area: Rect, kind: GraphKind) {
+        let Some(ref ladder) = self.ladder else {
+            return;
+        };
+        let graph = TcpGraph::from_ladder(ladder, self.graph_from_client);
+        let (from, to) = if self.graph_from_client {
+            (&ladder.client, &ladder.server)
+        } else {
+            (&ladder.server, &ladder.client)
+        };
+        let scatter = |name: &'static str, points, color| {
+            Dataset::default()
+                .name(name)
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Scatter)
+                .style(Style::default().fg(color))
+                .data(points)
+        };
+        let (title, datasets, y_title, y_max) = match kind {
+            GraphKind::Sequence => (
+                tr!(
+                    "ladder.sequence_title",
+                    from,
+                    to,
+                    graph.bytes,
+                    graph.retransmissions.len(),
+                    graph.stalls.len()
+                ),
+                vec![
+                    Dataset::default()
+                        .name("ACK")
+                        .marker(Marker::Braille)
+                        .graph_type(GraphType::Line)
+                        .style(Style::default().fg(theme().muted))
+                        .data(&graph.acks),
+                    scatter("Data", &graph.segments, theme().accent),
+                    scatter("Retransmission", &graph.retransmissions, theme().warning),
+                    scatter("Stall", &graph.stalls, theme().alert),
+                ],
+                tr!("ladder.sequence"),
+                graph.max_seq(),
+            ),
+            GraphKind::Throughput => (
+                tr!("ladder.throughput_title", from, to, graph.bytes),
+                vec![
+                    Dataset::default()
+                        .name("Bytes/s")
+                        .marker(Marker::Braille)
+                        .graph_type(GraphType::Line)
+                        .style(Style::default().fg(theme().highlight))
+                        .data(&graph.throughput),
+                ],
+                "Bytes/s",
+                graph.max_throughput(),
+            ),
+        };
+        let x_max = graph.duration.max(f64::EPSILON);
+        let y_max = y_max.max(1.0);
+        let labels = |max: f64, precision: usize| {
+            [0.0, max / 2.0, max].map(|value| format!("{value:.precision$}"))
+        };
+        let chart = Chart::new(datasets)
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().border)),
+            )
+            .x_axis(
+                Axis::default()
+                    .title(tr!("ladder.seconds"))
+                    .style(Style::default().fg(theme().muted))
+                    .bounds([0.0, x_max])
+                    .labels(labels(x_max, 3)),
+            )
+            .y_axis(
+                Axis::default()
+                    .title(y_title)
+                    .style(Style::default().fg(theme().muted))
+                    .bounds([0.0, y_max])
+                    .labels(labels(y_max, 0)),
+            );
+
+        let popup = Rect {
+            x: area.x + area.width / 20,
+            y: area.y + area.height / 20,
+            width: area.width - area.width / 10,
+            height: area.height - area.height / 10,
+        };
+        f.render_widget(Clear, popup);
+        f.render_widget(chart, popup);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("ladder.help"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for LadderPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        let max_scroll = self.ladder_lines().len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToSniffer)),
+            KeyCode::Char('g') => {
+                self.graph = match self.graph {
+                    None => {
+                        self.graph_from_client =
+                            self.ladder.as_ref().is_none_or(TcpGraph::busier_side);
+                        Some(GraphKind::Sequence)
+                    }
+                    Some(GraphKind::Sequence) => Some(GraphKind::Throughput),
+                    Some(GraphKind::Throughput) => None,
+                };
+            }
+            KeyCode::Char('d') if self.graph.is_some() => {
+                self.graph_from_client = !self.graph_from_client;
+            }
+            KeyCode::Up => {
+                self.scroll_position = self.scroll_position.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                self.scroll_position = (self.scroll_position + 1).min(max_scroll);
+            }
+            KeyCode::PageUp => {
+                self.scroll_position = self.scroll_position.saturating_sub(10);
+            }
+            KeyCode::PageDown => {
+                self.scroll_position = (self.scroll_position + 10).min(max_scroll);
+            }
+            KeyCode::Home => {
+                self.scroll_position = 0;
+            }
+            KeyCode::End => {
+                self.scroll_position = max_scroll;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for LadderPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_ladder(f, chunks[0]);
+        if let Some(kind) = self.graph {
+            self.render_graph(f, chunks[0], kind);
+        }
+        self.render_help(f, chunks[1]);
+    }
+}
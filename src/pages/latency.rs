@@ -0,0 +1,188 @@
+use std::{net::IpAddr, time::Duration};
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::latency::LatencyStats,
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+#[derive(Default)]
+pub struct LatencyPage {
+    stats: LatencyStats,
+    scroll_position: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+fn format_latency(latency: Option<Duration>) -> String {
+    match latency {
+        Some(latency) => format!("{:.2} ms", latency.as_secs_f64() * 1000.0),
+        None => "-".to_string(),
+    }
+}
+
+impl LatencyPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_stats(&mut self, stats: LatencyStats) {
+        self.stats = stats;
+    }
+
+    fn render_servers(&self, f: &mut Frame, area: Rect) {
+        let header = ListItem::new(Line::from(
+            [
+                ("Server", 48),
+                ("Service", 10),
+                ("Requests", 10),
+                ("Answered", 10),
+                ("Unanswered", 12),
+                ("p50", 12),
+                ("p95", 12),
+                ("p99", 12),
+            ]
+            .iter()
+            .map(|(name, width)| {
+                Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect::<Vec<_>>(),
+        ));
+
+        let servers = self.stats.by_p95();
+        let mut items = vec![header];
+        let visible = (area.height as usize).saturating_sub(3);
+        items.extend(
+            servers
+                .iter()
+                .skip(self.scroll_position)
+                .take(visible)
+                .map(|server| {
+                    let endpoint = match server.addr {
+                        IpAddr::V6(addr) => format!("[{addr}]:{}", server.port),
+                        addr => format!("{addr}:{}", server.port),
+                    };
+                    let unanswered_color = if server.unanswered() > 0 {
+                        theme().alert
+                    } else {
+                        theme().value
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{endpoint:<48}"),
+                            Style::default().fg(theme().secondary),
+                        ),
+                        Span::styled(
+                            format!("{:<10}", server.service.label()),
+                            Style::default().fg(theme().accent),
+                        ),
+                        Span::styled(
+                            format!("{:<10}", server.requests),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{:<10}", server.answered()),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", server.unanswered()),
+                            Style::default().fg(unanswered_color),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", format_latency(server.percentile(50))),
+                            Style::default().fg(theme().highlight),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", format_latency(server.percentile(95))),
+                            Style::default().fg(theme().highlight),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", format_latency(server.percentile(99))),
+                            Style::default().fg(theme().highlight),
+                        ),
+                    ]))
+                }),
+        );
+
+        let answered: usize = servers.iter().map(|server| server.answered()).sum();
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("latency.title", servers.len(), answered))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("stats.help.scroll"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for LatencyPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Up => {
+                self.scroll_position = self.scroll_position.saturating_sub(1);
+            }
+            KeyCode::Down if self.scroll_position + 1 < self.stats.servers.len() => {
+                self.scroll_position += 1;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for LatencyPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_servers(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
@@ -1,5 +1,31 @@
+pub mod alerts;
+pub mod capture_options;
+pub mod conversations;
 pub mod detail;
 pub mod device;
+pub mod discovery;
+pub mod dns;
+pub mod dual_stack;
+pub mod endpoints;
+pub mod export;
+pub mod file_summary;
 pub mod filter;
+pub mod flows;
+pub mod follow_stream;
 pub mod home;
+pub mod http;
+pub mod ladder;
+pub mod latency;
+pub mod multicast;
+pub mod neighbors;
+pub mod objects;
+pub mod other_l2;
+pub mod performance;
+pub mod protocol_bar;
+pub mod replay;
 pub mod sniffer;
+pub mod timeline;
+pub mod top_bar;
+pub mod top_talkers;
+pub mod vlan_bar;
+pub mod voip;
@@ -0,0 +1,183 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::multicast::MulticastStats,
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+#[derive(Default)]
+pub struct MulticastPage {
+    stats: MulticastStats,
+    scroll_position: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+impl MulticastPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_stats(&mut self, stats: MulticastStats) {
+        self.stats = stats;
+    }
+
+    fn render_groups(&self, f: &mut Frame, area: Rect) {
+        let header = ListItem::new(Line::from(
+            [
+                ("Group", 40),
+                ("Protocol", 10),
+                ("Joined", 8),
+                ("Reported", 10),
+                ("Queries", 9),
+                ("Members", 0),
+            ]
+            .iter()
+            .map(|(name, width)| {
+                Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect::<Vec<_>>(),
+        ));
+
+        let mut items = vec![header];
+        let visible = (area.height as usize).saturating_sub(3);
+        items.extend(
+            self.stats
+                .groups
+                .values()
+                .skip(self.scroll_position)
+                .take(visible)
+                .map(|group| {
+                    // Hosts that left are listed in parentheses.
+                    let members = group
+                        .members
+                        .iter()
+                        .map(|(host, member)| {
+                            if member.joined {
+                                host.to_string()
+                            } else {
+                                format!("({host})")
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    let members = if members.is_empty() {
+                        "-".to_string()
+                    } else {
+                        members.join(", ")
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{:<40}", group.addr),
+                            Style::default().fg(theme().secondary),
+                        ),
+                        Span::styled(
+                            format!("{:<10}", group.protocol.label()),
+                            Style::default().fg(theme().accent),
+                        ),
+                        Span::styled(
+                            format!("{:<8}", group.joined()),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{:<10}", group.members.len()),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{:<9}", group.queries),
+                            Style::default().fg(theme().highlight),
+                        ),
+                        Span::styled(members, Style::default().fg(theme().muted)),
+                    ]))
+                }),
+        );
+
+        let queriers = match self.stats.queriers.as_slice() {
+            [] => String::new(),
+            [querier] => tr!("multicast.querier", querier),
+            queriers => tr!("multicast.queriers", queriers.len()),
+        };
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!(
+                    "multicast.title",
+                    self.stats.groups.len(),
+                    self.stats.general_queries,
+                    queriers
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("stats.help.scroll"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for MulticastPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Up => {
+                self.scroll_position = self.scroll_position.saturating_sub(1);
+            }
+            KeyCode::Down if self.scroll_position + 1 < self.stats.groups.len() => {
+                self.scroll_position += 1;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for MulticastPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_groups(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
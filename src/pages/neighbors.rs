@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::neighbor::NeighborStats,
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+#[derive(Default)]
+pub struct NeighborsPage {
+    stats: NeighborStats,
+    scroll_position: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+fn format_latency(latency: Option<Duration>) -> String {
+    match latency {
+        Some(latency) => format!("{:.2} ms", latency.as_secs_f64() * 1000.0),
+        None => "-".to_string(),
+    }
+}
+
+impl NeighborsPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_stats(&mut self, stats: NeighborStats) {
+        self.stats = stats;
+    }
+
+    fn render_targets(&self, f: &mut Frame, area: Rect) {
+        let header = ListItem::new(Line::from(
+            [
+                ("Target", 40),
+                ("Protocol", 10),
+                ("Requests", 10),
+                ("Replies", 10),
+                ("Unanswered", 16),
+                ("Min", 12),
+                ("Avg", 12),
+                ("Max", 12),
+                ("Answered By", 0),
+            ]
+            .iter()
+            .map(|(name, width)| {
+                Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect::<Vec<_>>(),
+        ));
+
+        let targets = self.stats.by_unanswered();
+        let mut items = vec![header];
+        let visible = (area.height as usize).saturating_sub(3);
+        items.extend(
+            targets
+                .iter()
+                .skip(self.scroll_position)
+                .take(visible)
+                .map(|target| {
+                    let unanswered = if target.is_pending() {
+                        format!("{} (waiting)", target.unanswered())
+                    } else {
+                        target.unanswered().to_string()
+                    };
+                    let unanswered_color = if target.unanswered() > 0 {
+                        theme().alert
+                    } else {
+                        theme().value
+                    };
+                    let answered_by = match target.answered_by.len() {
+                        0 => "-".to_string(),
+                        1 => target.answered_by[0].clone(),
+                        n => format!("{} (+{} more)", target.answered_by[0], n - 1),
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{:<40}", target.addr),
+                            Style::default().fg(theme().secondary),
+                        ),
+                        Span::styled(
+                            format!("{:<10}", target.protocol.label()),
+                            Style::default().fg(theme().accent),
+                        ),
+                        Span::styled(
+                            format!("{:<10}", target.requests),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{:<10}", target.replies),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{unanswered:<16}"),
+                            Style::default().fg(unanswered_color),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", format_latency(target.min)),
+                            Style::default().fg(theme().highlight),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", format_latency(target.average())),
+                            Style::default().fg(theme().highlight),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", format_latency(target.max)),
+                            Style::default().fg(theme().highlight),
+                        ),
+                        Span::styled(answered_by, Style::default().fg(theme().muted)),
+                    ]))
+                }),
+        );
+
+        let unanswered: usize = targets.iter().map(|target| target.unanswered()).sum();
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("neighbors.title", targets.len(), unanswered))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("stats.help.scroll"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for NeighborsPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Up => {
+                self.scroll_position = self.scroll_position.saturating_sub(1);
+            }
+            KeyCode::Down if self.scroll_position + 1 < self.stats.targets.len() => {
+                self.scroll_position += 1;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for NeighborsPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_targets(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
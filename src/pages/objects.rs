@@ -0,0 +1,283 @@
+use std::{collections::HashSet, fs, path::Path};
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::http_objects::HttpObject,
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+/// Directory all objects are saved to unless another is typed.
+const OBJECTS_DIR: &str = "http-objects";
+
+/// What the save prompt writes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SaveKind {
+    Selected, // To the file typed
+    All,      // Into the directory typed
+}
+
+#[derive(Default)]
+pub struct ObjectsPage {
+    objects: Vec<HttpObject>,
+    selected: usize,
+    scroll_position: usize,
+    visible_rows: usize,
+    // What the save prompt writes and the name typed, while it is open
+    save_input: Option<(SaveKind, String)>,
+    status: Option<String>,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+impl ObjectsPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_objects(&mut self, objects: Vec<HttpObject>) {
+        self.objects = objects;
+        self.selected = self.selected.min(self.objects.len().saturating_sub(1));
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.save_input.is_some()
+    }
+
+    fn save(&mut self, kind: SaveKind, name: &str) {
+        self.status = Some(match kind {
+            SaveKind::Selected => {
+                let Some(object) = self.objects.get(self.selected) else {
+                    return;
+                };
+                match fs::write(name, &object.data) {
+                    Ok(()) => tr!("objects.saved", object.data.len(), name),
+                    Err(e) => tr!("objects.save_error", e),
+                }
+            }
+            SaveKind::All => match self.save_all(Path::new(name)) {
+                Ok(count) => tr!("objects.saved_all", count, name),
+                Err(e) => tr!("objects.save_error", e),
+            },
+        });
+    }
+
+    /// Writes every object into `dir`, telling apart objects of the same
+    /// name by their packet number.
+    fn save_all(&self, dir: &Path) -> std::io::Result<usize> {
+        fs::create_dir_all(dir)?;
+        let mut names = HashSet::new();
+        for object in &self.objects {
+            let mut name = object.file_name();
+            if !names.insert(name.clone()) {
+                name = format!("{}-{name}", object.id);
+                names.insert(name.clone());
+            }
+            fs::write(dir.join(name), &object.data)?;
+        }
+        Ok(self.objects.len())
+    }
+
+    fn handle_save_input(&mut self, key: KeyEvent) {
+        let Some((kind, ref mut input)) = self.save_input else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.save_input = None,
+            KeyCode::Enter => {
+                let input = self.save_input.take().unwrap_or((kind, String::new())).1;
+                if !input.trim().is_empty() {
+                    self.save(kind, input.trim());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn render_objects(&mut self, f: &mut Frame, area: Rect) {
+        let header = ListItem::new(Line::from(
+            [
+                ("Packet", 9),
+                ("Host", 28),
+                ("Content Type", 30),
+                ("Size", 12),
+                ("Filename", 0),
+            ]
+            .iter()
+            .map(|(name, width)| {
+                Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect::<Vec<_>>(),
+        ));
+
+        self.visible_rows = (area.height as usize).saturating_sub(3);
+        if self.selected < self.scroll_position {
+            self.scroll_position = self.selected;
+        } else if self.selected >= self.scroll_position + self.visible_rows {
+            self.scroll_position = self.selected + 1 - self.visible_rows;
+        }
+
+        let mut items = vec![header];
+        items.extend(
+            self.objects
+                .iter()
+                .enumerate()
+                .skip(self.scroll_position)
+                .take(self.visible_rows)
+                .map(|(i, object)| {
+                    let base_style = if i == self.selected {
+                        Style::default()
+                            .bg(theme().selection)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default()
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{:<9}", object.id), base_style.fg(theme().muted)),
+                        Span::styled(
+                            format!("{:<28.27}", object.host),
+                            base_style.fg(theme().secondary),
+                        ),
+                        Span::styled(
+                            format!("{:<30.29}", object.content_type),
+                            base_style.fg(theme().accent),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", object.data.len()),
+                            base_style.fg(theme().value),
+                        ),
+                        Span::styled(
+                            if object.truncated {
+                                format!("{} {}", object.file_name(), tr!("objects.truncated"))
+                            } else {
+                                object.file_name()
+                            },
+                            base_style.fg(theme().text),
+                        ),
+                    ]))
+                }),
+        );
+
+        let title = match self.status {
+            Some(ref status) => tr!("objects.title_status", self.objects.len(), status),
+            None => tr!("objects.title", self.objects.len()),
+        };
+        let list = List::new(items).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        if let Some((kind, ref input)) = self.save_input {
+            let prompt = match kind {
+                SaveKind::Selected => tr!("objects.save_prompt"),
+                SaveKind::All => tr!("objects.save_all_prompt"),
+            };
+            let line = Line::from(vec![
+                Span::styled(prompt, Style::default().fg(theme().highlight)),
+                Span::styled(input.clone(), Style::default().fg(theme().text)),
+            ]);
+            let x = area.x + line.width() as u16;
+            f.render_widget(Paragraph::new(line), area);
+            if x < area.x + area.width {
+                f.set_cursor_position(ratatui::layout::Position { x, y: area.y });
+            }
+            return;
+        }
+        let help = Paragraph::new(tr!("objects.help"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for ObjectsPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.save_input.is_some() {
+            self.handle_save_input(key);
+            return Ok(Some(Action::Handled));
+        }
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down if self.selected + 1 < self.objects.len() => {
+                self.selected += 1;
+            }
+            KeyCode::PageUp => {
+                self.selected = self.selected.saturating_sub(self.visible_rows.max(1));
+            }
+            KeyCode::PageDown => {
+                self.selected = (self.selected + self.visible_rows.max(1))
+                    .min(self.objects.len().saturating_sub(1));
+            }
+            KeyCode::Enter | KeyCode::Char('s') => {
+                if let Some(object) = self.objects.get(self.selected) {
+                    self.save_input = Some((SaveKind::Selected, object.file_name()));
+                }
+            }
+            KeyCode::Char('a') if !self.objects.is_empty() => {
+                self.save_input = Some((SaveKind::All, OBJECTS_DIR.to_string()));
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for ObjectsPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_objects(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
@@ -0,0 +1,163 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::ethertype::{self, OtherL2Stats},
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+#[derive(Default)]
+pub struct OtherL2Page {
+    stats: OtherL2Stats,
+    scroll_position: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+impl OtherL2Page {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_stats(&mut self, stats: OtherL2Stats) {
+        self.stats = stats;
+    }
+
+    fn render_entries(&self, f: &mut Frame, area: Rect) {
+        let header = ListItem::new(Line::from(
+            [
+                ("EtherType", 11),
+                ("Name", 24),
+                ("Packets", 10),
+                ("Bytes", 12),
+                ("Duration", 12),
+                ("Sources", 0),
+            ]
+            .iter()
+            .map(|(name, width)| {
+                Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect::<Vec<_>>(),
+        ));
+
+        let mut items = vec![header];
+        let visible = (area.height as usize).saturating_sub(3);
+        items.extend(
+            self.stats
+                .entries
+                .values()
+                .skip(self.scroll_position)
+                .take(visible)
+                .map(|entry| {
+                    let (name, name_color) = match ethertype::name(entry.ethertype) {
+                        Some(name) => (name, theme().accent),
+                        None => ("Unknown", theme().alert),
+                    };
+                    let duration = entry.last.duration_since(entry.first).unwrap_or_default();
+                    let sources = match entry.sources.len() {
+                        0 => "-".to_string(),
+                        1 => entry.sources[0].clone(),
+                        n => format!("{} (+{} more)", entry.sources[0], n - 1),
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{:<11}", format!("0x{:04x}", entry.ethertype)),
+                            Style::default().fg(theme().highlight),
+                        ),
+                        Span::styled(format!("{name:<24}"), Style::default().fg(name_color)),
+                        Span::styled(
+                            format!("{:<10}", entry.packets),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", entry.bytes),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", format!("{:.3} s", duration.as_secs_f64())),
+                            Style::default().fg(theme().muted),
+                        ),
+                        Span::styled(sources, Style::default().fg(theme().secondary)),
+                    ]))
+                }),
+        );
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("other_l2.title", self.stats.entries.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("stats.help.scroll"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for OtherL2Page {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Up => {
+                self.scroll_position = self.scroll_position.saturating_sub(1);
+            }
+            KeyCode::Down if self.scroll_position + 1 < self.stats.entries.len() => {
+                self.scroll_position += 1;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for OtherL2Page {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_entries(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
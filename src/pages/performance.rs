@@ -0,0 +1,178 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::profile::{self, Timing},
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+#[derive(Default)]
+pub struct PerformancePage {
+    timings: Vec<(&'static str, Timing)>,
+    scroll_position: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+impl PerformancePage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shows whole-packet dissection first, then decoders by total time.
+    pub fn set_timings(&mut self, mut timings: Vec<(&'static str, Timing)>) {
+        timings.sort_by_key(|&(name, timing)| {
+            (name != profile::DISSECTION, std::cmp::Reverse(timing.total))
+        });
+        self.timings = timings;
+    }
+
+    fn render_timings(&self, f: &mut Frame, area: Rect) {
+        let header = ListItem::new(Line::from(
+            [
+                ("Decoder", 16),
+                ("Calls", 12),
+                ("Total", 14),
+                ("Average", 12),
+                ("Share", 0),
+            ]
+            .iter()
+            .map(|(name, width)| {
+                Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect::<Vec<_>>(),
+        ));
+
+        let dissection = self
+            .timings
+            .iter()
+            .find(|(name, _)| *name == profile::DISSECTION)
+            .map(|(_, timing)| timing.total.as_secs_f64())
+            .unwrap_or_default();
+
+        let mut items = vec![header];
+        let visible = (area.height as usize).saturating_sub(3);
+        items.extend(
+            self.timings
+                .iter()
+                .skip(self.scroll_position)
+                .take(visible)
+                .map(|(name, timing)| {
+                    // Decoders run inside dissection, so their share is of
+                    // the time spent dissecting.
+                    let share = if dissection > 0.0 {
+                        format!("{:.1}%", timing.total.as_secs_f64() * 100.0 / dissection)
+                    } else {
+                        "-".to_string()
+                    };
+                    let name_color = if *name == profile::DISSECTION {
+                        theme().highlight
+                    } else {
+                        theme().accent
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!("{name:<16}"), Style::default().fg(name_color)),
+                        Span::styled(
+                            format!("{:<12}", timing.calls),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!(
+                                "{:<14}",
+                                format!("{:.3} ms", timing.total.as_secs_f64() * 1000.0)
+                            ),
+                            Style::default().fg(theme().value),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", format!("{} ns", timing.average().as_nanos())),
+                            Style::default().fg(theme().secondary),
+                        ),
+                        Span::styled(share, Style::default().fg(theme().muted)),
+                    ]))
+                }),
+        );
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("performance.title"))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("performance.help"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for PerformancePage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Char('r') => {
+                profile::reset();
+                self.timings.clear();
+                self.scroll_position = 0;
+            }
+            KeyCode::Up => {
+                self.scroll_position = self.scroll_position.saturating_sub(1);
+            }
+            KeyCode::Down if self.scroll_position + 1 < self.timings.len() => {
+                self.scroll_position += 1;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for PerformancePage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+
+        self.render_timings(f, chunks[0]);
+        self.render_help(f, chunks[1]);
+    }
+}
@@ -0,0 +1,152 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    component::ComponentRender,
+    data::packet::{PacketInfo, Transport},
+    theme::theme,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolChip {
+    Tcp,
+    Udp,
+    Icmp,
+    Arp,
+    Dns,
+    Tls,
+    Http,
+    Other,
+}
+
+impl ProtocolChip {
+    pub const ALL: [ProtocolChip; 8] = [
+        ProtocolChip::Tcp,
+        ProtocolChip::Udp,
+        ProtocolChip::Icmp,
+        ProtocolChip::Arp,
+        ProtocolChip::Dns,
+        ProtocolChip::Tls,
+        ProtocolChip::Http,
+        ProtocolChip::Other,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProtocolChip::Tcp => "TCP",
+            ProtocolChip::Udp => "UDP",
+            ProtocolChip::Icmp => "ICMP",
+            ProtocolChip::Arp => "ARP",
+            ProtocolChip::Dns => "DNS",
+            ProtocolChip::Tls => "TLS",
+            ProtocolChip::Http => "HTTP",
+            ProtocolChip::Other => "Other",
+        }
+    }
+
+    fn has_port(packet: &PacketInfo, ports: &[u16]) -> bool {
+        packet.src_port.is_some_and(|p| ports.contains(&p))
+            || packet.dst_port.is_some_and(|p| ports.contains(&p))
+    }
+
+    pub fn matches(self, packet: &PacketInfo) -> bool {
+        let is_tcp = packet.transport == Some(Transport::Tcp);
+        match self {
+            ProtocolChip::Tcp => is_tcp,
+            ProtocolChip::Udp => packet.transport == Some(Transport::Udp),
+            ProtocolChip::Icmp => matches!(
+                packet.transport,
+                Some(Transport::Icmpv4 | Transport::Icmpv6)
+            ),
+            ProtocolChip::Arp => packet.protocol == "ARP",
+            // Encrypted DNS counts as DNS too, as well as TLS.
+            ProtocolChip::Dns => {
+                Self::has_port(packet, &[53, 853, 5353]) || packet.has_layer("DoH")
+            }
+            ProtocolChip::Tls => is_tcp && Self::has_port(packet, &[443, 853, 8443]),
+            ProtocolChip::Http => is_tcp && Self::has_port(packet, &[80, 8000, 8080]),
+            ProtocolChip::Other => packet.transport.is_none() && packet.protocol != "ARP",
+        }
+    }
+}
+
+/// A row of protocol chips that narrows the packet list without typing a
+/// filter. No enabled chips means everything is shown.
+#[derive(Default)]
+pub struct ProtocolBar {
+    pub is_open: bool,
+    enabled: [bool; ProtocolChip::ALL.len()],
+}
+
+impl ProtocolBar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn toggle_open(&mut self) {
+        self.is_open = !self.is_open;
+    }
+
+    pub fn is_narrowing(&self) -> bool {
+        self.enabled.iter().any(|&e| e)
+    }
+
+    pub fn matches(&self, packet: &PacketInfo) -> bool {
+        !self.is_narrowing()
+            || ProtocolChip::ALL
+                .iter()
+                .zip(self.enabled)
+                .any(|(chip, enabled)| enabled && chip.matches(packet))
+    }
+
+    /// Handles the chip hotkeys. Returns true if the enabled set changed.
+    pub fn handle_key_events(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('0') => {
+                self.enabled = Default::default();
+                true
+            }
+            KeyCode::Char(c @ '1'..='8') => {
+                let i = c as usize - '1' as usize;
+                self.enabled[i] = !self.enabled[i];
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl ComponentRender<()> for ProtocolBar {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let mut spans = vec![Span::styled(
+            " Protocols: ",
+            Style::default()
+                .fg(theme().text)
+                .add_modifier(Modifier::BOLD),
+        )];
+        for (i, (chip, enabled)) in ProtocolChip::ALL.iter().zip(self.enabled).enumerate() {
+            let style = if enabled {
+                Style::default()
+                    .bg(theme().accent)
+                    .fg(theme().background)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme().muted)
+            };
+            spans.push(Span::styled(format!(" {} {} ", i + 1, chip.label()), style));
+            spans.push(Span::raw(" "));
+        }
+        spans.push(Span::styled(
+            " 0: Show All",
+            Style::default().fg(theme().faint),
+        ));
+
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+}
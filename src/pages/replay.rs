@@ -0,0 +1,314 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Gauge, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::replay::{self, RATES, Replay},
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+/// Frame counts offered; 0 sends each frame once.
+const COUNTS: [usize; 7] = [0, 1, 10, 100, 1_000, 10_000, 100_000];
+
+const SETTINGS: usize = 3; // Interface, rate, count
+
+#[derive(Default)]
+pub struct ReplayPage {
+    frames: Vec<replay::Frame>,
+    source: String, // Where the frames came from
+    interfaces: Vec<String>,
+    interface: usize,
+    rate: usize,  // Index into RATES
+    count: usize, // Index into COUNTS
+    selected: usize,
+    replay: Option<Replay>,
+    status_message: String,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+impl ReplayPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.replay.as_ref().is_some_and(Replay::is_running)
+    }
+
+    /// Sets the frames to send, described by `source`. Ignored while a
+    /// replay is running.
+    pub fn set_frames(&mut self, frames: Vec<replay::Frame>, source: String) {
+        if self.is_running() {
+            return;
+        }
+        self.frames = frames;
+        self.source = source;
+        self.replay = None;
+        if self.interfaces.is_empty() {
+            self.load_interfaces();
+        }
+    }
+
+    fn load_interfaces(&mut self) {
+        match replay::interfaces() {
+            Ok(devices) => {
+                self.interfaces = devices.into_iter().map(|d| d.name).collect();
+                self.interface = self.interface.min(self.interfaces.len().saturating_sub(1));
+            }
+            Err(e) => self.status_message = tr!("replay.interfaces_failed", e),
+        }
+    }
+
+    fn count_label(&self, count: usize) -> String {
+        match count {
+            0 => tr!("replay.count_once", self.frames.len()),
+            count => tr!("replay.count", count),
+        }
+    }
+
+    /// Moves the selected setting one step back or forward.
+    fn change(&mut self, forward: bool) {
+        let step = |value: usize, len: usize| {
+            let len = len.max(1);
+            if forward {
+                (value + 1) % len
+            } else {
+                (value + len - 1) % len
+            }
+        };
+        match self.selected {
+            0 => self.interface = step(self.interface, self.interfaces.len()),
+            1 => self.rate = step(self.rate, RATES.len()),
+            _ => self.count = step(self.count, COUNTS.len()),
+        }
+    }
+
+    fn toggle(&mut self) {
+        if let Some(ref mut replay) = self.replay
+            && replay.is_running()
+        {
+            replay.stop();
+            self.status_message = tr!("replay.stopped").to_string();
+            return;
+        }
+        let Some(interface) = self.interfaces.get(self.interface) else {
+            self.status_message = tr!("replay.no_interface").to_string();
+            return;
+        };
+        if self.frames.is_empty() {
+            self.status_message = tr!("replay.no_frames").to_string();
+            return;
+        }
+        match Replay::start(
+            interface,
+            self.frames.clone(),
+            RATES[self.rate],
+            COUNTS[self.count],
+        ) {
+            Ok(replay) => {
+                self.status_message = tr!("replay.started", interface);
+                self.replay = Some(replay);
+            }
+            Err(e) => self.status_message = tr!("replay.failed", format!("{e:#}")),
+        }
+    }
+
+    fn render_settings(&self, f: &mut Frame, area: Rect) {
+        let interface = self
+            .interfaces
+            .get(self.interface)
+            .cloned()
+            .unwrap_or_else(|| tr!("replay.none").to_string());
+        let rows = [
+            (tr!("replay.interface"), interface),
+            (tr!("replay.rate"), RATES[self.rate].label()),
+            (tr!("replay.frames"), self.count_label(COUNTS[self.count])),
+        ];
+        let lines: Vec<Line> = rows
+            .into_iter()
+            .enumerate()
+            .map(|(i, (label, value))| {
+                let style = if i == self.selected {
+                    Style::default()
+                        .bg(theme().selection)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                Line::from(vec![
+                    Span::styled(format!("{label:<14}"), style.fg(theme().muted)),
+                    Span::styled(format!("◀ {value} ▶"), style.fg(theme().value)),
+                ])
+            })
+            .collect();
+
+        let settings = Paragraph::new(lines).block(
+            Block::default()
+                .title(tr!("replay.title", self.frames.len(), self.source))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(settings, area);
+    }
+
+    fn render_progress(&self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(area);
+        let progress = self
+            .replay
+            .as_ref()
+            .map(Replay::progress)
+            .unwrap_or_default();
+        let done = progress.sent + progress.failed;
+        let ratio = if progress.total > 0 {
+            done as f64 / progress.total as f64
+        } else {
+            0.0
+        };
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .title(tr!("replay.progress"))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().border)),
+            )
+            .gauge_style(Style::default().fg(theme().accent))
+            .ratio(ratio.min(1.0))
+            .label(format!("{done} / {}", progress.total));
+        f.render_widget(gauge, chunks[0]);
+
+        let elapsed = progress
+            .started
+            .map(|started| {
+                progress
+                    .finished
+                    .unwrap_or_else(std::time::Instant::now)
+                    .duration_since(started)
+            })
+            .unwrap_or_default();
+        let seconds = elapsed.as_secs_f64();
+        let rate = if seconds > 0.0 {
+            progress.sent as f64 / seconds
+        } else {
+            0.0
+        };
+        let mut lines = vec![
+            Line::styled(
+                tr!("replay.sent", progress.sent, progress.bytes),
+                Style::default().fg(theme().value),
+            ),
+            Line::styled(
+                tr!(
+                    "replay.elapsed",
+                    format!("{seconds:.1}"),
+                    format!("{rate:.0}")
+                ),
+                Style::default().fg(theme().text),
+            ),
+        ];
+        if progress.skipped > 0 {
+            lines.push(Line::styled(
+                tr!("replay.skipped", progress.skipped),
+                Style::default().fg(theme().warning),
+            ));
+        }
+        if progress.failed > 0 {
+            lines.push(Line::styled(
+                tr!(
+                    "replay.send_failed",
+                    progress.failed,
+                    progress.error.unwrap_or_default()
+                ),
+                Style::default().fg(theme().alert),
+            ));
+        }
+        if !self.status_message.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::styled(
+                self.status_message.clone(),
+                Style::default().fg(theme().highlight),
+            ));
+        }
+        let status = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(tr!("status"))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().border)),
+            )
+            .wrap(Wrap { trim: true });
+        f.render_widget(status, chunks[1]);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("replay.help"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for ReplayPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Up => self.selected = (self.selected + SETTINGS - 1) % SETTINGS,
+            KeyCode::Down => self.selected = (self.selected + 1) % SETTINGS,
+            KeyCode::Left if !self.is_running() => self.change(false),
+            KeyCode::Right if !self.is_running() => self.change(true),
+            KeyCode::Enter => self.toggle(),
+            KeyCode::F(5) if !self.is_running() => self.load_interfaces(),
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for ReplayPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(SETTINGS as u16 + 2),
+                Constraint::Min(6),
+                Constraint::Length(1),
+            ])
+            .split(area);
+
+        self.render_settings(f, chunks[0]);
+        self.render_progress(f, chunks[1]);
+        self.render_help(f, chunks[2]);
+    }
+}
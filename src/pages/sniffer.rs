@@ -1,64 +1,232 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEventKind};
-use pcap::{Capture, Device};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
 };
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::net::IpAddr;
+use std::ops::Range;
+use std::path::PathBuf;
 use std::sync::{
-    Arc,
-    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+    atomic::{AtomicBool, AtomicUsize, Ordering},
 };
 use std::thread;
+use std::time::{Duration, UNIX_EPOCH};
 use tokio::sync::mpsc;
 
 use crate::{
     action::Action,
     component::{Component, ComponentRender},
-    data::packet::{PacketInfo, parse_packet},
-    pages::filter::FilterDialog,
+    data::{
+        alert::{Alert, AlertMonitor},
+        alert_rule::RuleEngine,
+        capture_file::{self, MAX_COMMENT_LEN, OutputCompression},
+        capture_options::CaptureOptions,
+        checksum::BadChecksum,
+        demo,
+        display_filter::DisplayFilter,
+        dns_anomaly::{DnsAnomaly, DnsFinding, DnsMonitor},
+        filter_completion,
+        flow::FlowKey,
+        flow_tracker::{FlowTimeouts, FlowTracker},
+        geoip,
+        handshake::HandshakeTimer,
+        http_transactions::{HttpTracker, HttpTransaction},
+        icmp::{IcmpLinks, LinkedPacket},
+        knowledge::KnowledgeBase,
+        live::{self, Stat},
+        packet::{PacketInfo, Transport, parse_headers, parse_packet},
+        packet_export,
+        privileges::{self, Missing},
+        search::Pattern,
+        spool::{Spool, footprint},
+        ring::RingWriter,
+        rpcap,
+        stats_log::StatsLog,
+        tcp_analysis::{TcpAnalysis, TcpIssue},
+        time::{TimeFormat, format_utc},
+        wlan,
+    },
+    i18n::tr,
+    pages::{
+        capture_options::CaptureOptionsDialog,
+        export::{ExportDialog, ExportRequest, ExportScope},
+        filter::{FilterDialog, FilterKind, FilterPreview, PreviewMatches},
+        protocol_bar::ProtocolBar,
+    },
+    theme::theme,
     tui::Event,
 };
 
+/// Newest packets the display filter preview checks.
+const PREVIEW_SCAN_LIMIT: usize = 10_000;
+/// Matching packets listed in the display filter preview.
+const PREVIEW_LINES: usize = 5;
+/// Info lines shown at most under an expanded row.
+const EXPANDED_LINES: usize = 12;
+/// Width of the Interface column shown when capturing on all interfaces,
+/// which holds the capture point in merged files.
+const INTERFACE_WIDTH: usize = 14;
+/// Width of the TCP analysis column.
+const ANALYSIS_WIDTH: usize = 14;
+const LOCATION_WIDTH: usize = 12;
+/// Widths of the radiotap columns shown for monitor mode captures.
+const SIGNAL_WIDTH: usize = 9;
+const CHANNEL_WIDTH: usize = 5;
+const RATE_WIDTH: usize = 11;
+/// Width of the TCP handshake time column.
+const HANDSHAKE_WIDTH: usize = 11;
+/// Signal below which a frame's RSSI is shown as weak.
+const WEAK_SIGNAL_DBM: i8 = -80;
+
+#[derive(Debug, Clone, PartialEq)]
+enum ListMode {
+    All,
+    FirstPerFlow,
+    Flow(FlowKey),
+}
+
 pub struct SnifferPage {
     device_name: Option<String>,
+    loaded_file: Option<String>, // Capture file the packets came from, if any
     packets: Vec<PacketInfo>,
+    visible: Vec<usize>,   // Packet indices of the rows currently listed
+    max_packets: usize,    // Live captures keep at most this many; 0 for no limit
+    evicted: usize,        // Packets dropped from the front; indices stay stable
+    memory_limit: usize,   // Live captures spill to disk past this many bytes; 0 for no limit
+    resident_bytes: usize, // Approximate memory held by `packets`
+    lazy: bool,            // Live captures leave application protocols until shown
+    spool: Option<Spool>,  // Packets after the evicted ones and before those in memory
+    list_mode: ListMode,
+    flow_starts: HashMap<FlowKey, usize>, // Index of the first packet of each flow
+    flows: FlowTracker,
+    icmp_links: IcmpLinks,
+    tcp_analysis: TcpAnalysis,
+    dns_monitor: DnsMonitor,
+    http: HttpTracker,
+    handshakes: HandshakeTimer,
+    alerts: AlertMonitor,
+    knowledge: Option<KnowledgeBase>, // Hosts remembered across sessions, when enabled
+    stats_log: Option<StatsLog>,      // Where per-interval statistics of live captures go
+    ring: Option<RingWriter>,         // Rotating files live captures are also written to
+    export_compression: Option<OutputCompression>,
+    snapshot_window: Duration, // How far back a snapshot reaches from the newest packet
+    time_format: TimeFormat,
     is_capturing: bool,
     capture_start_time: std::time::SystemTime,
     status_message: String,
     action_tx: Option<mpsc::UnboundedSender<Action>>,
     packet_count: usize,
+    clears: usize, // Times the packets were cleared or replaced
     scroll_position: usize,
+    list_area: Rect, // Where the packet list was last rendered
     following: bool,
     filter_dialog: FilterDialog,
+    protocol_bar: ProtocolBar,
     current_filter: Option<String>,
-    packet_rx: Option<mpsc::UnboundedReceiver<PacketInfo>>,
+    display_filter: Option<DisplayFilter>,
+    search_input: Option<String>, // Text typed at the search prompt while it is open
+    export_dialog: ExportDialog,
+    marked: BTreeSet<usize>,      // Packets marked for export, by index
+    // Comments attached to packets, by index
+    comments: BTreeMap<usize, String>,
+    capture_options: CaptureOptions,
+    capture_options_dialog: CaptureOptionsDialog,
+    monitor_devices: HashSet<String>, // Wi-Fi interfaces to capture on in monitor mode
+    snaplen_advice: Option<u32>, // Frame length to raise the snaplen to, while asking
+    missing_privileges: Option<Missing>, // Shown in a dialog until a key is pressed
+    search: Option<Pattern>,
+    packet_rx: Option<mpsc::Receiver<PacketInfo>>,
+    batch_size: usize, // Packets taken from the channel per tick; 0 for no limit
+    backlog: usize,    // Packets left in the channel after the last tick
+    queue_dropped: Arc<AtomicUsize>, // Packets dropped with the channel full
     capture_thread_handle: Option<thread::JoinHandle<()>>,
     stop_capture_flag: Arc<AtomicBool>,
+    shared_stats: Arc<Mutex<Option<Stat>>>, // Written by the capture thread
+    capture_stats: Option<Stat>,
     selected_packet: Option<usize>, // New field for selected packet index
+    expanded: Option<usize>,        // Packet whose full Info shows under its row
+    show_interface: bool,           // Whether the capture merges several interfaces
+    show_locations: bool,           // GeoIP columns for both addresses
+    show_radio: bool,               // Radiotap signal, channel and rate columns
+    show_handshake: bool,           // Time since the SYN on SYN-ACKs and completing ACKs
+    offload_addrs: HashSet<IpAddr>, // Senders whose checksums aren't flagged
+    capture_unavailable: Option<String>, // Why live capture can't be used
 }
 
 impl Default for SnifferPage {
     fn default() -> Self {
         Self {
             device_name: None,
+            loaded_file: None,
             packets: Vec::new(),
+            visible: Vec::new(),
+            max_packets: 0,
+            evicted: 0,
+            memory_limit: 0,
+            resident_bytes: 0,
+            spool: None,
+            lazy: false,
+            list_mode: ListMode::All,
+            flow_starts: HashMap::new(),
+            flows: FlowTracker::default(),
+            icmp_links: IcmpLinks::default(),
+            tcp_analysis: TcpAnalysis::default(),
+            dns_monitor: DnsMonitor::default(),
+            http: HttpTracker::default(),
+            handshakes: HandshakeTimer::default(),
+            alerts: AlertMonitor::default(),
+            knowledge: None,
+            stats_log: None,
+            ring: None,
+            export_compression: None,
+            snapshot_window: Duration::from_secs(30),
+            time_format: TimeFormat::default(),
             is_capturing: false,
             capture_start_time: std::time::SystemTime::now(),
-            status_message: "No device selected. Press 'D' to select a device.".to_string(),
+            status_message: tr!("sniffer.no_device").to_string(),
             action_tx: None,
             packet_count: 0,
+            clears: 0,
             scroll_position: 0,
+            list_area: Rect::default(),
             following: false,
             filter_dialog: FilterDialog::new(),
+            protocol_bar: ProtocolBar::new(),
             current_filter: None,
+            display_filter: None,
+            search_input: None,
+            export_dialog: ExportDialog::new(),
+            marked: BTreeSet::new(),
+            comments: BTreeMap::new(),
+            capture_options: CaptureOptions::default(),
+            capture_options_dialog: CaptureOptionsDialog::new(),
+            monitor_devices: HashSet::new(),
+            snaplen_advice: None,
+            missing_privileges: None,
+            search: None,
             packet_rx: None,
+            batch_size: 0,
+            backlog: 0,
+            queue_dropped: Arc::new(AtomicUsize::new(0)),
             capture_thread_handle: None,
             stop_capture_flag: Arc::new(AtomicBool::new(false)),
+            shared_stats: Arc::new(Mutex::new(None)),
+            capture_stats: None,
             selected_packet: None, // Initialize as None
+            expanded: None,
+            show_interface: false,
+            show_locations: false,
+            show_radio: false,
+            show_handshake: false,
+            offload_addrs: HashSet::new(),
+            capture_unavailable: None,
         }
     }
 }
@@ -70,84 +238,268 @@ impl SnifferPage {
         }
     }
 
+    pub fn set_max_packets(&mut self, max_packets: usize) {
+        self.max_packets = max_packets;
+    }
+
+    pub fn set_memory_limit(&mut self, bytes: usize) {
+        self.memory_limit = bytes;
+    }
+
+    pub fn set_batch_size(&mut self, batch_size: usize) {
+        self.batch_size = batch_size;
+    }
+
+    pub fn set_export_compression(&mut self, compression: Option<OutputCompression>) {
+        self.export_compression = compression;
+    }
+
+    pub fn set_snapshot_window(&mut self, window: Duration) {
+        self.snapshot_window = window;
+    }
+
+    pub fn set_lazy_parsing(&mut self, lazy: bool) {
+        self.lazy = lazy;
+    }
+
+    pub fn set_capture_options(&mut self, options: CaptureOptions) {
+        self.capture_options = options;
+    }
+
+    /// Stops flagging bad checksums on packets sent from `addrs`, this
+    /// host's own, whose checksums the NIC fills in after capture.
+    pub fn set_offload_addrs(&mut self, addrs: HashSet<IpAddr>) {
+        self.offload_addrs = addrs;
+    }
+
+    /// Whether `packet` was sent from an address whose checksums are left
+    /// to the NIC.
+    pub fn is_offloaded(&self, packet: &PacketInfo) -> bool {
+        matches!(packet.src_addr, Some(Ok(ref ip)) if self.offload_addrs.contains(ip))
+    }
+
+    /// The bad checksums of `packet` worth flagging.
+    fn checksum_errors<'a>(&self, packet: &'a PacketInfo) -> &'a [BadChecksum] {
+        if self.is_offloaded(packet) {
+            &[]
+        } else {
+            &packet.bad_checksums
+        }
+    }
+
+    pub fn set_flow_timeouts(&mut self, timeouts: FlowTimeouts) {
+        self.flows.set_timeouts(timeouts);
+    }
+
     pub fn set_device(&mut self, device_name: String) {
         self.device_name = Some(device_name.clone());
-        self.status_message =
-            format!("Device set to: {device_name}. Press 'S' to start capturing.");
+        self.status_message = tr!("sniffer.device_set", device_name);
+    }
+
+    /// Turns the capture keys off for analysis-only mode, explaining
+    /// `reason` when they are pressed.
+    pub fn set_capture_unavailable(&mut self, reason: String) {
+        if self.loaded_file.is_none() {
+            self.status_message = tr!("sniffer.analysis_only", reason);
+        }
+        self.capture_unavailable = Some(reason);
+    }
+
+    /// Starts capturing on the demo pseudo-device.
+    pub fn start_demo(&mut self) -> Result<()> {
+        self.set_device(demo::DEVICE.to_string());
+        self.start_capture()
+    }
+
+    /// Replaces the packet list with packets read from a capture file.
+    /// Shows `packets` read from `source`, with the `comments` the file
+    /// keeps for them by position.
+    pub fn load_capture(
+        &mut self,
+        packets: Vec<PacketInfo>,
+        comments: BTreeMap<usize, String>,
+        source: &str,
+    ) {
+        if self.is_capturing {
+            self.stop_capture();
+        }
+        self.show_interface = packets.iter().any(|packet| packet.interface.is_some());
+        self.show_radio = packets
+            .iter()
+            .any(|packet| packet.link_type == wlan::LINKTYPE_IEEE802_11_RADIOTAP);
+        self.loaded_file = Some(source.to_string());
+        self.packets.clear();
+        self.clears += 1;
+        self.visible.clear();
+        self.flow_starts.clear();
+        self.flows.clear();
+        self.icmp_links.clear();
+        self.tcp_analysis.clear();
+        self.dns_monitor.clear();
+        self.http.clear();
+        self.handshakes.clear();
+        self.alerts.clear();
+        self.marked.clear();
+        self.comments.clear();
+        self.evicted = 0;
+        self.spool = None;
+        self.resident_bytes = 0;
+        self.packet_count = 0;
+        self.scroll_position = 0;
+        self.following = false;
+        for packet in packets {
+            self.add_packet(packet);
+        }
+        self.comments = comments;
+        self.selected_packet = if self.visible.is_empty() { None } else { Some(0) };
+        self.status_message = tr!("sniffer.loaded", self.packet_count, source);
+    }
+
+    /// Starts capturing, unless decoding needs whole frames and the device
+    /// can deliver frames longer than the snaplen; then asks first whether
+    /// to raise it, so stream and export views don't silently miss data.
+    fn request_capture(&mut self) -> Result<()> {
+        if !self.lazy
+            && let Some(ref device_name) = self.device_name
+            && let Some(frame) = live::largest_frame(device_name)
+            && frame > self.capture_options.snaplen
+        {
+            self.snaplen_advice = Some(frame);
+            self.status_message = tr!(
+                "sniffer.snaplen_advice",
+                self.capture_options.snaplen,
+                device_name,
+                frame
+            );
+            return Ok(());
+        }
+        self.start_capture()
+    }
+
+    /// Keeps the options confirmed in the dialog for the next capture and
+    /// saves them for later sessions.
+    fn set_capture_options_from_dialog(&mut self, options: CaptureOptions) {
+        self.status_message = match options.save() {
+            Ok(()) if self.is_capturing => tr!("sniffer.capture_options_restart").to_string(),
+            Ok(()) => tr!("sniffer.capture_options_saved").to_string(),
+            Err(e) => tr!("sniffer.capture_options_error", e),
+        };
+        self.capture_options = options;
+    }
+
+    /// Starts a new ring of files when the capture options name a different
+    /// one, so a ring carries on numbering across captures otherwise.
+    fn update_ring(&mut self) {
+        let options = self.capture_options.ring_options(self.export_compression);
+        if self.ring.as_ref().map(RingWriter::options) != options.as_ref() {
+            self.ring = options.map(RingWriter::new);
+        }
+    }
+
+    fn handle_snaplen_advice(&mut self, key: KeyEvent) -> Result<()> {
+        let Some(frame) = self.snaplen_advice else {
+            return Ok(());
+        };
+        match key.code {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter => {
+                self.snaplen_advice = None;
+                self.capture_options.snaplen = frame;
+                self.start_capture()?;
+            }
+            KeyCode::Char('n' | 'N') => {
+                self.snaplen_advice = None;
+                self.start_capture()?;
+            }
+            KeyCode::Esc => {
+                self.snaplen_advice = None;
+                self.status_message = tr!("sniffer.snaplen_cancelled").to_string();
+            }
+            _ => {}
+        }
+        Ok(())
     }
 
     fn start_capture(&mut self) -> Result<()> {
+        self.update_ring();
         if let Some(ref device_name) = self.device_name {
-            self.status_message = "Starting packet capture...".to_string();
+            // Remote devices need rights on their host, not here.
+            let local = !rpcap::is_remote(device_name) && device_name != demo::DEVICE;
+            if local && let Some(missing) = privileges::check() {
+                self.missing_privileges = Some(missing);
+                return Ok(());
+            }
+            self.status_message = tr!("sniffer.starting").to_string();
 
-            let devices = Device::list().context("Failed to list devices")?;
-            let device = devices
-                .iter()
-                .find(|d| d.name == *device_name)
-                .context("Device not found")?;
-
-            let mut cap = Capture::from_device(device.clone())?
-                .promisc(true)
-                .snaplen(5000)
-                .timeout(100)
-                .open()?;
-
-            if let Some(ref filter) = self.current_filter {
-                if !filter.is_empty() {
-                    match cap.filter(filter, true) {
-                        Ok(_) => {
-                            self.status_message = format!(
-                                "Capturing packets on {device_name} with filter: {filter}. Press 'S' to stop."
-                            );
-                        }
-                        Err(e) => {
-                            self.status_message =
-                                format!("Filter error: {e}. Capturing without filter.");
-                        }
-                    }
+            // A filter that doesn't compile is reported and left out rather
+            // than failing the capture.
+            let filter = self.current_filter.clone().unwrap_or_default();
+            let filter_error = live::check_filter(&filter).err();
+            let options = CaptureOptions {
+                monitor: self.monitor_devices.contains(device_name),
+                filter: if filter_error.is_none() {
+                    filter.clone()
                 } else {
-                    self.status_message =
-                        format!("Capturing packets on {device_name}. Press 'S' to stop.");
+                    String::new()
+                },
+                ..self.capture_options.clone()
+            };
+            let sources = match live::open(device_name, &options) {
+                Err(e) if local && privileges::is_permission_error(&e) => {
+                    self.missing_privileges = Some(privileges::platform_missing());
+                    self.status_message = format!("{e:#}");
+                    return Ok(());
                 }
-            } else {
-                self.status_message =
-                    format!("Capturing packets on {device_name}. Press 'S' to stop.");
-            }
+                sources => sources?,
+            };
 
-            let (packet_tx, packet_rx) = mpsc::unbounded_channel();
-            self.packet_rx = Some(packet_rx);
+            self.status_message = match filter_error {
+                Some(e) => tr!("sniffer.filter_error", e),
+                None if filter.is_empty() => tr!("sniffer.capturing", device_name),
+                None => tr!("sniffer.capturing_filtered", device_name, filter),
+            };
 
             self.stop_capture_flag.store(false, Ordering::Relaxed);
-            let stop_flag = Arc::clone(&self.stop_capture_flag);
-            let capture_start_time = std::time::SystemTime::now();
-
-            let handle = thread::spawn(move || {
-                let mut packet_id = 0;
-                while !stop_flag.load(Ordering::Relaxed) {
-                    if let Ok(packet) = cap.next_packet() {
-                        packet_id += 1;
-
-                        let timestamp = format!(
-                            "{:.6}",
-                            std::time::SystemTime::now()
-                                .duration_since(capture_start_time)
-                                .unwrap_or_default()
-                                .as_secs_f64()
-                        );
-
-                        let packet_info = parse_packet(packet_id, timestamp, packet.data.into());
-
-                        if packet_tx.send(packet_info).is_err() {
-                            break;
-                        }
-                    }
-                }
-            });
+            self.shared_stats = Arc::new(Mutex::new(None));
+            self.queue_dropped = Arc::new(AtomicUsize::new(0));
+            self.capture_stats = None;
+            let parse = if self.lazy {
+                parse_headers
+            } else {
+                parse_packet
+            };
+            self.show_interface = sources.len() > 1;
+            self.show_radio = sources
+                .iter()
+                .any(|source| source.capture.link_type() == wlan::LINKTYPE_IEEE802_11_RADIOTAP);
+            self.loaded_file = None;
+            let (handle, packet_rx) = live::spawn(
+                sources,
+                parse,
+                Arc::clone(&self.stop_capture_flag),
+                Arc::clone(&self.shared_stats),
+                Arc::clone(&self.queue_dropped),
+            );
+            self.packet_rx = Some(packet_rx);
 
             self.capture_thread_handle = Some(handle);
             self.is_capturing = true;
             self.capture_start_time = std::time::SystemTime::now();
             self.packets.clear();
+            self.clears += 1;
+            self.visible.clear();
+            self.flow_starts.clear();
+            self.flows.clear();
+            self.icmp_links.clear();
+            self.tcp_analysis.clear();
+            self.dns_monitor.clear();
+            self.http.clear();
+            self.handshakes.clear();
+            self.alerts.clear();
+            self.marked.clear();
+            self.comments.clear();
+            self.evicted = 0;
+            self.spool = None;
+            self.resident_bytes = 0;
             self.packet_count = 0;
             self.scroll_position = 0;
         }
@@ -157,7 +509,7 @@ impl SnifferPage {
     fn stop_capture(&mut self) {
         self.stop_capture_flag.store(true, Ordering::Relaxed);
         if self.selected_packet.is_none() {
-            self.selected_packet = Some(self.packets.len().saturating_sub(1));
+            self.selected_packet = self.visible.len().checked_sub(1);
         }
         self.is_capturing = false;
 
@@ -166,84 +518,508 @@ impl SnifferPage {
             let _ = handle.join();
         }
 
+        // Everything captured before stopping is still listed; the channel
+        // holds at most QUEUE_CAPACITY of it.
+        while let Some(packet) = self.packet_rx.as_mut().and_then(|rx| rx.try_recv().ok()) {
+            self.add_packet(packet);
+        }
         self.packet_rx = None;
+        self.backlog = 0;
+        if let Some(ref mut stats_log) = self.stats_log
+            && let Err(e) = stats_log.finish()
+        {
+            self.status_message = tr!("sniffer.stats_log_error", format!("{e:#}"));
+            self.stats_log = None;
+            return;
+        }
+        if let Some(ref mut ring) = self.ring
+            && let Err(e) = ring.finish()
+        {
+            self.status_message = tr!("sniffer.ring_error", format!("{e:#}"));
+            self.ring = None;
+            return;
+        }
 
         if let Some(ref device_name) = self.device_name {
-            self.status_message = format!(
-                "Stopped capturing on {}. Captured {} packets.",
-                device_name, self.packet_count
-            );
+            self.status_message = tr!("sniffer.stopped", device_name, self.packet_count);
         }
     }
 
     fn receive_packets(&mut self) {
-        if let Some(ref mut packet_rx) = self.packet_rx {
-            while let Ok(packet) = packet_rx.try_recv() {
-                self.packet_count += 1;
-                self.packets.push(packet);
+        if let Ok(shared) = self.shared_stats.lock()
+            && shared.is_some()
+        {
+            self.capture_stats = *shared;
+        }
+        // Taking at most one batch per tick keeps the UI responsive at high
+        // rates; the rest waits in the channel while the capture thread
+        // keeps parsing.
+        let batch_size = if self.batch_size > 0 {
+            self.batch_size
+        } else {
+            usize::MAX
+        };
+        for _ in 0..batch_size {
+            let Some(packet) = self.packet_rx.as_mut().and_then(|rx| rx.try_recv().ok()) else {
+                break;
+            };
+            self.add_packet(packet);
+        }
+        self.backlog = self.packet_rx.as_ref().map_or(0, |rx| rx.len());
+    }
+
+    fn add_packet(&mut self, packet: PacketInfo) {
+        self.packet_count += 1;
+        let index = self.resident_start() + self.packets.len();
+        // These read headers and payloads, which lazy parsing keeps too.
+        self.flows.add(&packet);
+        self.icmp_links.add(index, &packet);
+        self.tcp_analysis.add(index, &packet);
+        self.tcp_analysis.prune(&self.flows);
+        self.dns_monitor.add(index, &packet);
+        self.http.add(index, &packet);
+        self.handshakes.add(index, &packet);
+        if let Some(alert) = self.alerts.add(index, &packet) {
+            self.status_message = alert.message.clone();
+        }
+        if let Some(ref mut knowledge) = self.knowledge {
+            knowledge.learn(&packet);
+        }
+        if self.is_capturing
+            && let Some(ref mut stats_log) = self.stats_log
+            && let Err(e) = stats_log.add(&packet)
+        {
+            self.status_message = tr!("sniffer.stats_log_error", format!("{e:#}"));
+            self.stats_log = None;
+        }
+        if self.is_capturing
+            && let Some(ref mut ring) = self.ring
+            && let Err(e) = ring.add(&packet)
+        {
+            self.status_message = tr!("sniffer.ring_error", format!("{e:#}"));
+            self.ring = None;
+        }
+        if let Some(key) = FlowKey::from_packet(&packet) {
+            self.flow_starts.entry(key).or_insert(index);
+        }
+        self.resident_bytes += footprint(&packet);
+        self.packets.push(packet);
+        if self.is_listed(index) {
+            self.visible.push(index);
+        }
+        if self.is_capturing && self.memory_limit > 0 && self.resident_bytes > self.memory_limit {
+            self.spill();
+        }
+        if self.is_capturing
+            && self.max_packets > 0
+            && self.spooled() + self.packets.len() > self.max_packets
+        {
+            self.evict();
+        }
+    }
+
+    fn spooled(&self) -> usize {
+        self.spool.as_ref().map_or(0, Spool::len)
+    }
+
+    /// Changes whenever the packets held in memory do, for callers that
+    /// keep what they derive from them.
+    pub fn revision(&self) -> (usize, usize, usize) {
+        (self.clears, self.resident_start(), self.packet_count)
+    }
+
+    /// Index of the oldest packet held in memory.
+    fn resident_start(&self) -> usize {
+        self.evicted + self.spooled()
+    }
+
+    /// Moves the oldest packets in memory to the spool file until usage is a
+    /// tenth under the limit, so the file is written in batches. Rows keep
+    /// their indices and are read back from disk when shown. If the file
+    /// cannot be written, the limit is lifted and packets stay in memory.
+    fn spill(&mut self) {
+        if self.spool.is_none() {
+            match Spool::create() {
+                Ok(spool) => self.spool = Some(spool),
+                Err(e) => {
+                    self.status_message = tr!("sniffer.spool_error", e);
+                    self.memory_limit = 0;
+                    return;
+                }
+            }
+        }
+        let Some(spool) = self.spool.as_mut() else {
+            return;
+        };
+        let target = self.memory_limit - self.memory_limit / 10;
+        let mut count = 0;
+        let mut result = Ok(());
+        // The newest packet always stays in memory.
+        while self.resident_bytes > target && count + 1 < self.packets.len() {
+            let packet = &self.packets[count];
+            result = spool.push(packet);
+            if result.is_err() {
+                break;
+            }
+            self.resident_bytes -= footprint(packet);
+            count += 1;
+        }
+        self.packets.drain(..count);
+        if let Err(e) = result.and_then(|()| spool.flush()) {
+            self.status_message = tr!("sniffer.spool_error", e);
+            self.memory_limit = 0;
+        }
+    }
+
+    /// Drops the oldest tenth of the buffer at once, so eviction costs one
+    /// shift per batch rather than per packet. Spooled packets go first. Rows
+    /// and the selection move up with the packets that remain.
+    fn evict(&mut self) {
+        let count = self.spooled() + self.packets.len() - self.max_packets + self.max_packets / 10;
+        let from_spool = count.min(self.spooled());
+        if let Some(spool) = self.spool.as_mut() {
+            if let Err(e) = spool.drop_front(from_spool) {
+                self.status_message = tr!("sniffer.spool_error", e);
             }
+            if spool.is_empty() {
+                self.spool = None;
+            }
+        }
+        for packet in self.packets.drain(..count - from_spool) {
+            self.resident_bytes -= footprint(&packet);
+        }
+        self.evicted += count;
+        self.marked = self.marked.split_off(&self.evicted);
+        self.comments = self.comments.split_off(&self.evicted);
+        self.flow_starts.retain(|_, index| *index >= self.evicted);
+        self.icmp_links.evict(self.evicted);
+        self.tcp_analysis.evict(self.evicted);
+        self.dns_monitor.evict(self.evicted);
+        self.http.evict(self.evicted);
+        self.handshakes.evict(self.evicted);
+
+        let gone = self.visible.partition_point(|&index| index < self.evicted);
+        self.visible.drain(..gone);
+        self.scroll_position = self.scroll_position.saturating_sub(gone);
+        // An evicted selection is dropped rather than moved to another packet.
+        self.selected_packet = self.selected_packet.and_then(|row| row.checked_sub(gone));
+    }
+
+    /// Looks a packet up by absolute index, reading it back from the spool
+    /// file if it was moved to disk. Lazily parsed packets come back with
+    /// headers only; see `PacketInfo::full`.
+    fn packet(&self, index: usize) -> Option<Cow<'_, PacketInfo>> {
+        let position = index.checked_sub(self.evicted)?;
+        match position.checked_sub(self.spooled()) {
+            Some(resident) => self.packets.get(resident).map(Cow::Borrowed),
+            None => self.spool.as_ref()?.load(position).map(Cow::Owned),
         }
     }
 
-    fn render_packet_list(&self, f: &mut Frame, area: Rect) {
-        let header = ListItem::new(Line::from(vec![
+    /// Opens the filter dialog, offering the addresses (and for display
+    /// filters the protocols) of the newest packets as completions.
+    fn open_filter_dialog(&mut self, kind: FilterKind) {
+        let packets = self.packets.iter().rev().take(PREVIEW_SCAN_LIMIT);
+        let vocabulary = filter_completion::vocabulary(packets, kind == FilterKind::Display);
+        self.filter_dialog.set_vocabulary(vocabulary);
+        self.filter_dialog.open(kind);
+    }
+
+    /// Recounts the matches of the display filter being typed when the
+    /// text or the capture changed. Only the newest resident packets are
+    /// scanned so typing stays responsive in large captures.
+    fn refresh_filter_preview(&mut self) {
+        let Some(text) = self.filter_dialog.preview_wanted(self.packet_count) else {
+            return;
+        };
+        let text = text.to_string();
+        let result = DisplayFilter::parse(&text)
+            .map(|filter| {
+                let mut matches = PreviewMatches::default();
+                for packet in self.packets.iter().rev().take(PREVIEW_SCAN_LIMIT) {
+                    matches.scanned += 1;
+                    let packet = packet.full();
+                    if !filter.matches(&packet) {
+                        continue;
+                    }
+                    matches.matched += 1;
+                    if matches.recent.len() < PREVIEW_LINES {
+                        matches.recent.push(format!(
+                            "#{:<6} {:<10} {}",
+                            packet.id, packet.protocol, packet.info
+                        ));
+                    }
+                }
+                matches
+            })
+            .map_err(|e| e.to_string());
+        self.filter_dialog.set_preview(FilterPreview {
+            text,
+            packet_count: self.packet_count,
+            result,
+        });
+    }
+
+    fn is_listed(&self, index: usize) -> bool {
+        let Some(packet) = self.packet(index) else {
+            return false;
+        };
+        if self.protocol_bar.is_narrowing() || self.display_filter.is_some() {
+            let packet = packet.full();
+            if !self.protocol_bar.matches(&packet)
+                || self.display_filter.as_ref().is_some_and(|f| !f.matches(&packet))
+            {
+                return false;
+            }
+        }
+        match &self.list_mode {
+            ListMode::All => true,
+            ListMode::FirstPerFlow => FlowKey::from_packet(&packet)
+                .is_none_or(|key| self.flow_starts.get(&key) == Some(&index)),
+            ListMode::Flow(key) => key.contains(&packet),
+        }
+    }
+
+    /// Saves every packet of the flow `key`, with ICMP errors about it, to a
+    /// pcap file in the working directory, compressed if asked to. Spooled
+    /// packets are included.
+    fn export_flow(&mut self, key: &FlowKey) {
+        let end = self.resident_start() + self.packets.len();
+        let packets: Vec<Cow<'_, PacketInfo>> = (self.evicted..end)
+            .filter_map(|index| self.packet(index))
+            .filter(|packet| key.includes(packet))
+            .collect();
+        let name: String = format!("flow-{}-{}", key.a, key.b)
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+            .collect();
+        self.status_message = self.export(&name, &packets);
+    }
+
+    /// Saves the packets of the last `snapshot_window` before the newest
+    /// one, spooled ones included, to a pcap file named after the time
+    /// without stopping the capture.
+    fn export_snapshot(&mut self) {
+        let end = self.resident_start() + self.packets.len();
+        let Some(newest) = end.checked_sub(1).and_then(|index| self.packet(index)) else {
+            self.status_message = tr!("sniffer.snapshot_empty").to_string();
+            return;
+        };
+        let cutoff = newest.captured_at.checked_sub(self.snapshot_window);
+        let mut packets: Vec<Cow<'_, PacketInfo>> = (self.evicted..end)
+            .rev()
+            .map_while(|index| self.packet(index))
+            .take_while(|packet| cutoff.is_none_or(|cutoff| packet.captured_at >= cutoff))
+            .collect();
+        packets.reverse();
+        self.status_message = self.export(&format!("snapshot-{}", Self::stamp()), &packets);
+    }
+
+    /// The current time for naming files, such as "20260131-123456".
+    fn stamp() -> String {
+        let now = std::time::SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        format_utc(now)[..19]
+            .chars()
+            .filter_map(|c| match c {
+                ' ' => Some('-'),
+                '-' | ':' => None,
+                c => Some(c),
+            })
+            .collect()
+    }
+
+    /// Saves `packets` to `name`.pcap in the working directory, compressed
+    /// if asked to, returning the status to show.
+    fn export(&self, name: &str, packets: &[Cow<'_, PacketInfo>]) -> String {
+        let path = match self.export_compression {
+            Some(compression) => PathBuf::from(format!("{name}.pcap.{}", compression.extension())),
+            None => PathBuf::from(format!("{name}.pcap")),
+        };
+        let packets = packets.iter().map(AsRef::as_ref);
+        match capture_file::save(&path, self.export_compression, packets) {
+            Ok(count) => tr!("sniffer.exported", count, path.display()),
+            Err(e) => tr!("sniffer.export_error", e),
+        }
+    }
+
+    /// Rebuilds the listed rows after the display narrowing changed, keeping
+    /// the selected packet selected if it is still listed.
+    fn refresh_visible(&mut self) {
+        let selected = self.selected_packet.and_then(|row| self.visible.get(row).copied());
+        self.visible = (self.evicted..self.resident_start() + self.packets.len())
+            .filter(|&i| self.is_listed(i))
+            .collect();
+        self.scroll_position = 0;
+        self.selected_packet = None;
+        if let Some(row) = selected.and_then(|index| self.visible.binary_search(&index).ok()) {
+            self.select_packet(row);
+        }
+    }
+
+    /// Decodes the application protocols of lazily parsed rows in `rows`,
+    /// keeping the result so each packet is decoded at most once.
+    fn decode_rows(&mut self, rows: Range<usize>) {
+        let resident_start = self.resident_start();
+        for row in rows {
+            if let Some(resident) = self.visible[row].checked_sub(resident_start) {
+                self.decode(resident);
+            }
+        }
+    }
+
+    /// Decodes every packet held in memory, for the pages that analyze the
+    /// whole capture. Each packet is parsed again at most once.
+    pub fn decode_resident(&mut self) {
+        for resident in 0..self.packets.len() {
+            self.decode(resident);
+        }
+    }
+
+    /// Replaces the packet at `resident` in memory with its full parse if
+    /// only its headers were parsed.
+    fn decode(&mut self, resident: usize) {
+        let Some(packet) = self.packets.get_mut(resident) else {
+            return;
+        };
+        if packet.lazy {
+            let full = packet.full().into_owned();
+            self.resident_bytes = self.resident_bytes - footprint(packet) + footprint(&full);
+            *packet = full;
+        }
+    }
+
+    fn render_packet_list(&mut self, f: &mut Frame, area: Rect) {
+        let mut header = vec![
             Span::styled(
                 format!("{:<6}", "No."),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
-                format!("{:<15}", "Timestamp"),
+                format!("{:<15}", self.time_format.heading()),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
+        ];
+        if self.show_interface {
+            header.push(Span::styled(
+                format!(
+                    "{:<INTERFACE_WIDTH$}",
+                    if self.loaded_file.is_some() { "Capture Point" } else { "Interface" }
+                ),
+                Style::default()
+                    .fg(theme().text)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        header.extend([
             Span::styled(
                 format!("{:10}", "Protocol"),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 format!("{:<10}", "Length"),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 format!("{:<47}", "Source"),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(
                 format!("{:<47}", "Destination"),
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme().text)
                     .add_modifier(Modifier::BOLD),
             ),
-        ]));
+        ]);
+        if self.show_locations {
+            for name in ["Src Geo", "Dst Geo"] {
+                header.push(Span::styled(
+                    format!("{name:<LOCATION_WIDTH$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+        }
+        if self.show_radio {
+            for (name, width) in [
+                ("Signal", SIGNAL_WIDTH),
+                ("Ch", CHANNEL_WIDTH),
+                ("Rate", RATE_WIDTH),
+            ] {
+                header.push(Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+        }
+        if self.show_handshake {
+            header.push(Span::styled(
+                format!("{:<HANDSHAKE_WIDTH$}", "Handshake"),
+                Style::default()
+                    .fg(theme().text)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        header.push(Span::styled(
+            format!("{:<ANALYSIS_WIDTH$}", "Analysis"),
+            Style::default()
+                .fg(theme().text)
+                .add_modifier(Modifier::BOLD),
+        ));
+        header.push(Span::styled(
+            "Info",
+            Style::default()
+                .fg(theme().text)
+                .add_modifier(Modifier::BOLD),
+        ));
 
-        let mut items = vec![header];
+        let mut items = vec![ListItem::new(Line::from(header))];
 
-        let visible_start = self.scroll_position;
+        let expansion = self.expansion();
+        let rows = (area.height as usize).saturating_sub(3);
+        // Keep the expanded row and the lines under it in view
+        if let Some(row) = self.selected_packet
+            && !expansion.is_empty()
+            && row + expansion.len() >= self.scroll_position + rows
+        {
+            self.scroll_position = (row + expansion.len() + 1).saturating_sub(rows).min(row);
+        }
+        let visible_start = self.scroll_position.min(self.visible.len());
         let visible_end = std::cmp::min(
-            visible_start + (area.height as usize).saturating_sub(3),
-            self.packets.len(),
+            visible_start + rows.saturating_sub(expansion.len()),
+            self.visible.len(),
         );
+        self.decode_rows(visible_start..visible_end);
 
-        let packet_items: Vec<ListItem> = self
-            .packets
+        let packet_items: Vec<ListItem> = self.visible[visible_start..visible_end]
             .iter()
             .enumerate()
-            .skip(visible_start)
-            .take(visible_end - visible_start)
-            .map(|(i, packet)| {
+            .filter_map(|(row, &index)| {
+                let i = visible_start + row;
+                let packet = self.packet(index)?;
+                let previous = match self.time_format {
+                    TimeFormat::Delta => index.checked_sub(1).and_then(|index| self.packet(index)),
+                    _ => None,
+                };
                 let is_selected = !self.following && self.selected_packet == Some(i);
                 let base_style = if is_selected {
                     Style::default()
-                        .bg(Color::Blue)
+                        .bg(theme().selection)
                         .add_modifier(Modifier::BOLD)
                 } else {
                     Style::default()
@@ -287,67 +1063,246 @@ impl SnifferPage {
                     "N/A".to_string()
                 };                
 
-                let line = Line::from(vec![
+                // Marked packets are flagged with a star, commented ones
+                // with a pencil.
+                let flags = format!(
+                    "{}{}",
+                    if self.marked.contains(&index) { "*" } else { "" },
+                    if self.comments.contains_key(&index) { "✎" } else { "" }
+                );
+                let mut spans = vec![
                     Span::styled(
-                        format!("{:<6}", packet.id),
+                        format!("{:<6}", format!("{}{flags}", packet.id)),
                         base_style.fg(if is_selected {
-                            Color::White
+                            theme().text
+                        } else if self.marked.contains(&index) {
+                            theme().alert
                         } else {
-                            Color::Yellow
+                            theme().highlight
                         }),
                     ),
                     Span::styled(
-                        format!("{:<15}", packet.timestamp),
+                        format!("{:<15}", self.time_format.format(&packet, previous.as_deref())),
                         base_style.fg(if is_selected {
-                            Color::White
+                            theme().text
                         } else {
-                            Color::Gray
+                            theme().muted
                         }),
                     ),
+                ];
+                if self.show_interface {
+                    let interface = packet.interface.as_deref().unwrap_or("-");
+                    spans.push(Span::styled(
+                        format!("{interface:<INTERFACE_WIDTH$.width$}", width = INTERFACE_WIDTH - 1),
+                        base_style.fg(if is_selected {
+                            theme().text
+                        } else {
+                            theme().accent
+                        }),
+                    ));
+                }
+                let bad_checksums = self.checksum_errors(&packet);
+                spans.extend([
                     Span::styled(
                         format!("{:<10}", &packet.protocol[..7.min(packet.protocol.len())]),
                         base_style.fg(if is_selected {
-                            Color::White
+                            theme().text
+                        } else if !bad_checksums.is_empty() {
+                            theme().warning
                         } else {
-                            Color::Cyan
+                            theme().accent
                         }),
                     ),
                     Span::styled(
                         format!("{:<10}", packet.length),
                         base_style.fg(if is_selected {
-                            Color::White
+                            theme().text
                         } else {
-                            Color::Green
+                            theme().value
                         }),
                     ),
                     Span::styled(
                         format!("{source_str:<47}"),
                         base_style.fg(if is_selected {
-                            Color::White
+                            theme().text
                         } else {
-                            Color::Magenta
+                            theme().secondary
                         }),
                     ),
                     Span::styled(
                         format!("{destination_str:<47}"),
                         base_style.fg(if is_selected {
-                            Color::White
+                            theme().text
                         } else {
-                            Color::Magenta
+                            theme().secondary
                         }),
                     ),
                 ]);
-                ListItem::new(line).style(base_style)
+                if self.show_locations {
+                    for addr in [&packet.src_addr, &packet.dst_addr] {
+                        let location = match addr {
+                            Some(Ok(ip)) => geoip::lookup(*ip).map(|l| l.short()),
+                            _ => None,
+                        };
+                        spans.push(Span::styled(
+                            format!(
+                                "{:<LOCATION_WIDTH$.width$}",
+                                location.as_deref().unwrap_or("-"),
+                                width = LOCATION_WIDTH - 1
+                            ),
+                            base_style.fg(if is_selected {
+                                theme().text
+                            } else {
+                                theme().value
+                            }),
+                        ));
+                    }
+                }
+                if self.show_radio {
+                    let radio = wlan::radio(packet.link_type, &packet.data).unwrap_or_default();
+                    let signal = radio.signal.map(|dbm| format!("{dbm} dBm"));
+                    let channel = radio.channel().map(|channel| channel.to_string());
+                    let weak = radio.signal.is_some_and(|dbm| dbm < WEAK_SIGNAL_DBM);
+                    for (text, width, weak) in [
+                        (signal, SIGNAL_WIDTH, weak),
+                        (channel, CHANNEL_WIDTH, false),
+                        (radio.rate_text(), RATE_WIDTH, false),
+                    ] {
+                        spans.push(Span::styled(
+                            format!("{:<width$}", text.as_deref().unwrap_or("-")),
+                            base_style.fg(if is_selected {
+                                theme().text
+                            } else if weak {
+                                theme().warning
+                            } else {
+                                theme().value
+                            }),
+                        ));
+                    }
+                }
+                if self.show_handshake {
+                    let time = self
+                        .handshakes
+                        .time(index)
+                        .map(|time| format!("{:.2} ms", time.as_secs_f64() * 1000.0));
+                    spans.push(Span::styled(
+                        format!("{:<HANDSHAKE_WIDTH$}", time.as_deref().unwrap_or("")),
+                        base_style.fg(if is_selected {
+                            theme().text
+                        } else {
+                            theme().highlight
+                        }),
+                    ));
+                }
+                let analysis = self
+                    .tcp_analysis
+                    .issue(index)
+                    .map(TcpIssue::label)
+                    .or_else(|| self.dns_monitor.anomaly(index).map(DnsAnomaly::label))
+                    .unwrap_or("");
+                spans.push(Span::styled(
+                    format!("{analysis:<ANALYSIS_WIDTH$}"),
+                    base_style.fg(if is_selected {
+                        theme().text
+                    } else {
+                        theme().warning
+                    }),
+                ));
+                if let Some(label) = self
+                    .http
+                    .transactions
+                    .get(&index)
+                    .and_then(HttpTransaction::label)
+                {
+                    spans.push(Span::styled(
+                        format!("[{label}] "),
+                        base_style.fg(if is_selected {
+                            theme().text
+                        } else {
+                            theme().highlight
+                        }),
+                    ));
+                }
+                if let Some(bad) = bad_checksums.first() {
+                    spans.push(Span::styled(
+                        format!("[Bad {} checksum] ", bad.header),
+                        base_style.fg(if is_selected {
+                            theme().text
+                        } else {
+                            theme().warning
+                        }),
+                    ));
+                }
+                spans.push(Span::styled(
+                    packet.info.clone(),
+                    base_style.fg(if is_selected {
+                        theme().text
+                    } else {
+                        theme().muted
+                    }),
+                ));
+                let line = Line::from(spans);
+                if is_selected && !expansion.is_empty() {
+                    let mut lines = vec![line];
+                    lines.extend(expansion.iter().map(|info| {
+                        Line::styled(format!("{:6}{info}", ""), base_style.fg(theme().text))
+                    }));
+                    return Some(ListItem::new(lines).style(base_style));
+                }
+                Some(ListItem::new(line).style(base_style))
             })
             .collect();
 
         items.extend(packet_items);
 
+        let title = match &self.list_mode {
+            ListMode::FirstPerFlow => {
+                format!("First Packet of Each Flow ({} rows)", self.visible.len())
+            }
+            ListMode::Flow(key) => format!("Flow {key} ({} packets)", self.visible.len()),
+            ListMode::All
+                if self.protocol_bar.is_narrowing() || self.display_filter.is_some() =>
+            {
+                format!(
+                    "Captured Packets ({} of {})",
+                    self.visible.len(),
+                    self.packet_count
+                )
+            }
+            ListMode::All => format!("Captured Packets ({})", self.packet_count),
+        };
+        let title = if self.evicted > 0 {
+            format!(
+                "{title} [oldest {} evicted, keeping {}]",
+                self.evicted,
+                self.spooled() + self.packets.len()
+            )
+        } else {
+            title
+        };
+        let title = if self.spooled() > 0 {
+            format!("{title} [{} on disk]", self.spooled())
+        } else {
+            title
+        };
+        let issues: Vec<String> = TcpIssue::ALL
+            .iter()
+            .filter_map(|issue| {
+                let count = self.tcp_analysis.counts.get(issue)?;
+                Some(format!("{count} {}", issue.label()))
+            })
+            .collect();
+        let title = if issues.is_empty() {
+            title
+        } else {
+            format!("{title} [TCP: {}]", issues.join(", "))
+        };
+
         let list = List::new(items).block(
             Block::default()
-                .title(format!("Captured Packets ({})", self.packet_count))
+                .title(title)
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Blue)),
+                .border_style(Style::default().fg(theme().border)),
         );
 
         f.render_widget(list, area);
@@ -355,45 +1310,321 @@ impl SnifferPage {
 
     fn render_status(&self, f: &mut Frame, area: Rect) {
         let status_color = if self.is_capturing {
-            Color::Green
-        } else if self.device_name.is_some() {
-            Color::Yellow
+            theme().value
+        } else if self.device_name.is_some() || !self.packets.is_empty() {
+            theme().highlight
         } else {
-            Color::Red
+            theme().alert
         };
 
+        let mut block = Block::default()
+            .title(tr!("status"))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().border));
+        if let Some(stat) = self.capture_stats {
+            let color = if stat.dropped > 0 || stat.if_dropped > 0 {
+                theme().alert
+            } else {
+                theme().value
+            };
+            block = block.title(
+                Line::styled(
+                    tr!(
+                        "sniffer.capture_stats",
+                        stat.received,
+                        stat.dropped,
+                        stat.if_dropped
+                    ),
+                    Style::default().fg(color),
+                )
+                .right_aligned(),
+            );
+        }
+        let queue_dropped = self.queue_dropped.load(Ordering::Relaxed);
+        if self.backlog > 0 || queue_dropped > 0 {
+            let text = if queue_dropped > 0 {
+                tr!("sniffer.backlog_dropped", self.backlog, queue_dropped)
+            } else {
+                tr!("sniffer.backlog", self.backlog)
+            };
+            block =
+                block.title(Line::styled(text, Style::default().fg(theme().highlight)).centered());
+        }
+
         let status = Paragraph::new(self.status_message.clone())
-            .block(
-                Block::default()
-                    .title("Status")
-                    .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Blue)),
-            )
+            .block(block)
             .style(Style::default().fg(status_color))
             .wrap(Wrap { trim: true });
 
         f.render_widget(status, area);
     }
+    /// Explains what rights live capture lacks and how to grant them on
+    /// this platform.
+    fn render_privileges_dialog(&self, f: &mut Frame, area: Rect) {
+        let Some(missing) = self.missing_privileges else {
+            return;
+        };
+        let executable = privileges::executable();
+        let (reason, steps) = match missing {
+            Missing::NetRaw => (
+                tr!("privileges.net_raw").to_string(),
+                vec![
+                    tr!("privileges.setcap", executable),
+                    tr!("privileges.sudo", executable),
+                    tr!("privileges.setcap_rebuild").to_string(),
+                ],
+            ),
+            Missing::BpfDevices => (
+                tr!("privileges.bpf").to_string(),
+                vec![
+                    tr!("privileges.chmod_bpf").to_string(),
+                    tr!("privileges.bpf_group").to_string(),
+                    tr!("privileges.sudo", executable),
+                ],
+            ),
+            Missing::Npcap => (
+                tr!("privileges.npcap").to_string(),
+                vec![
+                    tr!("privileges.npcap_install").to_string(),
+                    tr!("privileges.npcap_admin").to_string(),
+                ],
+            ),
+        };
+        let mut lines = vec![
+            Line::styled(reason, Style::default().fg(theme().alert)),
+            Line::from(""),
+        ];
+        lines.extend(steps.into_iter().map(|step| {
+            Line::styled(format!("• {step}"), Style::default().fg(theme().text))
+        }));
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            tr!("privileges.close"),
+            Style::default().fg(theme().accent),
+        ));
+
+        let width = area.width.min(90);
+        let height = (lines.len() as u16 + 4).min(area.height);
+        let popup = Rect {
+            x: area.x + (area.width - width) / 2,
+            y: area.y + (area.height - height) / 2,
+            width,
+            height,
+        };
+        f.render_widget(Clear, popup);
+        let dialog = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(tr!("privileges.title"))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme().alert))
+                    .style(Style::default().bg(theme().background)),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(dialog, popup);
+    }
+
     fn render_help(&self, f: &mut Frame, area: Rect) {
+        if let Some(frame) = self.snaplen_advice {
+            let prompt = Paragraph::new(tr!("sniffer.snaplen_prompt", frame))
+                .style(Style::default().fg(theme().highlight))
+                .alignment(ratatui::layout::Alignment::Center);
+            f.render_widget(prompt, area);
+            return;
+        }
+        if let Some(ref input) = self.search_input {
+            let prompt = tr!("sniffer.search_prompt");
+            let line = Line::from(vec![
+                Span::styled(prompt, Style::default().fg(theme().highlight)),
+                Span::styled(input.clone(), Style::default().fg(theme().text)),
+            ]);
+            let x = area.x + line.width() as u16;
+            f.render_widget(Paragraph::new(line), area);
+            if x < area.x + area.width {
+                f.set_cursor_position(ratatui::layout::Position { x, y: area.y });
+            }
+            return;
+        }
         let help_text = if self.is_capturing && !self.following {
-            "S: Stop Capture  C: Clear Packets  ↑/↓: Scroll  F: Follow  Home/End: Jump  A: Filter  D: Device Selection  Enter: Open Packet  Q/Esc: Home"
+            tr!("sniffer.help.capturing")
         } else if self.is_capturing && self.following {
-            "S: Stop Capture  C: Clear Packets  F: Unfollow  A: Filter  D: Device Selection  Enter: Open Packet  Q/Esc: Home"
+            tr!("sniffer.help.following")
         } else if self.device_name.is_some() {
-            "S: Start Capture  C: Clear Packets  A: Filter  D: Device Selection  Enter: Open Packet  Q/Esc: Home"
+            tr!("sniffer.help.ready")
         } else {
-            "A: Filter  D: Device Selection  Enter: Open Packet  Q/Esc: Home"
+            tr!("sniffer.help.idle")
         };
 
         let help = Paragraph::new(help_text)
-            .style(Style::default().fg(Color::Cyan))
+            .style(Style::default().fg(theme().accent))
             .wrap(Wrap { trim: true })
             .alignment(ratatui::layout::Alignment::Center)
             .block(Block::default().borders(Borders::NONE));
 
         f.render_widget(help, area);
     }
-    fn handle_mouse_click(&mut self, x: u16, y: u16, area: Rect) {
+    fn handle_search_input(&mut self, key: KeyEvent) {
+        let Some(ref mut input) = self.search_input else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.search_input = None,
+            KeyCode::Enter => {
+                let input = self.search_input.take().unwrap_or_default();
+                match Pattern::parse(&input) {
+                    Ok(pattern) => {
+                        self.search = Some(pattern);
+                        self.find_match(true);
+                    }
+                    Err(e) => self.status_message = tr!("sniffer.search_error", e),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The packets an export saves: the marked ones, or the selected one
+    /// when none are marked.
+    fn export_selection(&self) -> Vec<usize> {
+        if !self.marked.is_empty() {
+            return self.marked.iter().copied().collect();
+        }
+        self.selected_packet
+            .and_then(|row| self.visible.get(row).copied())
+            .into_iter()
+            .collect()
+    }
+
+    /// Carries out an export confirmed in the export dialog. Packets are
+    /// decoded in full so JSON and CSV carry their application details.
+    fn export_packets(&mut self, request: &ExportRequest) {
+        let indices = match request.scope {
+            ExportScope::Marked => self.export_selection(),
+            ExportScope::Listed => self.visible.clone(),
+        };
+        let packets: Vec<(PacketInfo, Option<&str>)> = indices
+            .into_iter()
+            .filter_map(|index| Some((self.packet(index)?, self.comment(index))))
+            .map(|(packet, comment)| (packet.full().into_owned(), comment))
+            .collect();
+        let packets = packets.iter().map(|(packet, comment)| (packet, *comment));
+        let path = &request.path;
+        self.status_message =
+            match packet_export::save(path, request.format, request.payload, packets) {
+                Ok(count) => tr!("sniffer.exported", count, path.display()),
+                Err(e) => tr!("sniffer.export_error", format!("{e:#}")),
+            };
+    }
+
+    /// Selects the next listed packet matching the search after the selected
+    /// one, or the previous one before it, wrapping around the list.
+    fn find_match(&mut self, forward: bool) {
+        let Some(ref pattern) = self.search else {
+            return;
+        };
+        let len = self.visible.len();
+        let start = match self.selected_packet {
+            Some(row) => row,
+            None if forward => len.saturating_sub(1),
+            None => 0,
+        };
+        let found = (1..=len)
+            .map(|step| {
+                if forward {
+                    (start + step) % len
+                } else {
+                    (start + len - step) % len
+                }
+            })
+            .find(|&row| {
+                self.packet(self.visible[row])
+                    .is_some_and(|packet| pattern.matches(&packet.full()))
+            });
+        match found {
+            Some(row) => {
+                let id = self.packet(self.visible[row]).map_or(0, |packet| packet.id);
+                self.status_message = tr!("sniffer.search_found", pattern, id);
+                self.select_packet(row);
+            }
+            None => self.status_message = tr!("sniffer.search_none", pattern),
+        }
+    }
+
+    /// The full Info of the selected row while it is expanded.
+    fn expansion(&self) -> Vec<String> {
+        let Some(&index) = self.selected_packet.and_then(|row| self.visible.get(row)) else {
+            return Vec::new();
+        };
+        if self.following || self.expanded != Some(index) {
+            return Vec::new();
+        }
+        let Some(packet) = self.packet(index) else {
+            return Vec::new();
+        };
+        let mut lines = packet.full().info_lines();
+        lines.truncate(EXPANDED_LINES);
+        lines
+    }
+
+    /// Whether keys go to the filter dialog or the search prompt.
+    pub fn is_capturing(&self) -> bool {
+        self.is_capturing
+    }
+
+    /// The capture file being shown, or else the selected device.
+    pub fn source(&self) -> Option<&str> {
+        self.loaded_file.as_deref().or(self.device_name.as_deref())
+    }
+
+    pub fn capture_filter(&self) -> Option<&str> {
+        self.current_filter.as_deref().filter(|filter| !filter.is_empty())
+    }
+
+    pub fn display_filter(&self) -> Option<&str> {
+        self.display_filter.as_ref().map(DisplayFilter::text)
+    }
+
+    pub fn capture_unavailable(&self) -> bool {
+        self.capture_unavailable.is_some()
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.filter_dialog.is_open
+            || self.search_input.is_some()
+            || self.export_dialog.is_open
+            || self.capture_options_dialog.is_open
+            || self.snaplen_advice.is_some()
+            || self.missing_privileges.is_some()
+    }
+
+    /// Where the current search matches in `packet`, for highlighting.
+    pub fn search_matches(&self, packet: &PacketInfo) -> Vec<Range<usize>> {
+        self.search
+            .as_ref()
+            .map(|pattern| pattern.payload_matches(packet))
+            .unwrap_or_default()
+    }
+
+    /// Packet rows that fit in the list as last rendered.
+    fn page_rows(&self) -> usize {
+        // Borders and the header row
+        (self.list_area.height as usize).saturating_sub(3).max(1)
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.visible.len().saturating_sub(self.page_rows())
+    }
+
+    fn scroll_to(&mut self, position: usize) {
+        self.scroll_position = position.min(self.max_scroll());
+    }
+
+    fn handle_mouse_click(&mut self, x: u16, y: u16) {
+        let area = self.list_area;
         // Check if click is within the packet list area
         if x > area.x
             && x < area.x + area.width - 1
@@ -401,13 +1632,20 @@ impl SnifferPage {
             && y < area.y + area.height - 1
         {
             let clicked_row = (y - area.y - 2) as usize; // -2 for border and header
-            let packet_index = self.scroll_position + clicked_row;
+            let mut packet_index = self.scroll_position + clicked_row;
+            // Lines of an expanded row belong to it and push later rows down
+            let expanded_lines = self.expansion().len();
+            if let Some(selected) = self.selected_packet
+                && packet_index > selected
+            {
+                packet_index = packet_index.saturating_sub(expanded_lines).max(selected);
+            }
 
-            if packet_index < self.packets.len() {
+            if packet_index < self.visible.len() {
                 if self.selected_packet == Some(packet_index) {
                     // Double-click behavior: open packet details
                     if let Some(tx) = &self.action_tx {
-                        let _ = tx.send(Action::PacketSelected(packet_index));
+                        let _ = tx.send(Action::PacketSelected(self.visible[packet_index]));
                     }
                 } else {
                     // Single-click behavior: select packet
@@ -418,27 +1656,102 @@ impl SnifferPage {
     }
 
     fn select_packet(&mut self, index: usize) {
-        if index < self.packets.len() {
+        if index < self.visible.len() {
             self.selected_packet = Some(index);
 
-            let visible_start = self.scroll_position;
-            let visible_end = visible_start + 20; // Approximate visible area
-
-            if index < visible_start {
+            let rows = self.page_rows();
+            if index < self.scroll_position {
                 self.scroll_position = index;
-            } else if index >= visible_end {
-                self.scroll_position = index.saturating_sub(19);
+            } else if index >= self.scroll_position + rows {
+                self.scroll_position = index + 1 - rows;
             }
         }
     }
 
-    pub fn get_packet(&self, index: usize) -> Option<PacketInfo> {
-        if index < self.packets.len() {
-            Some(self.packets[index].clone())
+    /// Packets held in memory; those spooled to disk are left out, and with
+    /// lazy parsing only rows already shown or decoded by
+    /// [`Self::decode_resident`] have application layers.
+    pub fn packets(&self) -> &[PacketInfo] {
+        &self.packets
+    }
+
+    /// The packets currently listed, after filters and the flow view.
+    pub fn listed_packets(&self) -> Vec<PacketInfo> {
+        self.visible
+            .iter()
+            .filter_map(|&index| self.packet(index).map(|packet| packet.full().into_owned()))
+            .collect()
+    }
+
+    pub fn set_stats_log(&mut self, stats_log: StatsLog) {
+        self.stats_log = Some(stats_log);
+    }
+
+    pub fn set_knowledge(&mut self, knowledge: KnowledgeBase) {
+        self.status_message = tr!("sniffer.knowledge_loaded", knowledge.len());
+        self.knowledge = Some(knowledge);
+    }
+
+    pub fn comment(&self, index: usize) -> Option<&str> {
+        self.comments.get(&index).map(String::as_str)
+    }
+
+    /// Attaches `comment` to the packet at `index`, or removes its comment
+    /// if `comment` is blank.
+    pub fn set_comment(&mut self, index: usize, comment: &str) {
+        let comment = comment.trim();
+        let comment = &comment[..comment.floor_char_boundary(MAX_COMMENT_LEN)];
+        if comment.is_empty() {
+            self.comments.remove(&index);
         } else {
-            None
+            self.comments.insert(index, comment.to_string());
+        }
+    }
+
+    pub fn set_alert_rules(&mut self, rules: RuleEngine) {
+        self.alerts.set_rules(rules);
+    }
+
+    pub fn alerts(&self) -> &[Alert] {
+        &self.alerts.alerts
+    }
+
+    pub fn dns_findings(&self) -> &[DnsFinding] {
+        &self.dns_monitor.findings
+    }
+
+    pub fn knowledge(&self) -> Option<&KnowledgeBase> {
+        self.knowledge.as_ref()
+    }
+
+    pub fn save_knowledge(&self) -> Result<()> {
+        match self.knowledge {
+            Some(ref knowledge) => knowledge.save(),
+            None => Ok(()),
         }
     }
+
+    /// Flow state for the whole session, including evicted packets.
+    pub fn flows(&self) -> &FlowTracker {
+        &self.flows
+    }
+
+    /// The packet an ICMP error at `index` reports on, and the ICMP errors
+    /// reporting on the packet at `index`.
+    pub fn icmp_related(&self, index: usize) -> (Option<LinkedPacket>, Vec<LinkedPacket>) {
+        let linked = |index: usize| {
+            self.packet(index).map(|packet| LinkedPacket { index, id: packet.id })
+        };
+        let errors = self.icmp_links.errors(index);
+        (
+            self.icmp_links.reported_on(index).and_then(linked),
+            errors.iter().filter_map(|&index| linked(index)).collect(),
+        )
+    }
+
+    pub fn get_packet(&self, index: usize) -> Option<PacketInfo> {
+        self.packet(index).map(|packet| packet.full().into_owned())
+    }
 }
 
 impl Component for SnifferPage {
@@ -452,14 +1765,52 @@ impl Component for SnifferPage {
         if self.filter_dialog.is_open
             && let Some(action) = self.filter_dialog.handle_events(event.clone())?
         {
+            self.refresh_filter_preview();
             return Ok(Some(action));
         }
+        if self.search_input.is_some()
+            && let Event::Key(key) = event
+        {
+            self.handle_search_input(key);
+            return Ok(Some(Action::Handled));
+        }
+        if self.export_dialog.is_open
+            && let Some(action) = self.export_dialog.handle_events(event.clone())?
+        {
+            if let Some(request) = self.export_dialog.take_request() {
+                self.export_packets(&request);
+            }
+            return Ok(Some(action));
+        }
+        if self.capture_options_dialog.is_open
+            && let Some(action) = self.capture_options_dialog.handle_events(event.clone())?
+        {
+            if let Some(options) = self.capture_options_dialog.take_options() {
+                self.set_capture_options_from_dialog(options);
+            }
+            return Ok(Some(action));
+        }
+        if self.snaplen_advice.is_some()
+            && let Event::Key(key) = event
+        {
+            self.handle_snaplen_advice(key)?;
+            return Ok(Some(Action::Handled));
+        }
+        if self.missing_privileges.is_some()
+            && let Event::Key(_) = event
+        {
+            self.missing_privileges = None;
+            return Ok(Some(Action::Handled));
+        }
 
         let r = match event {
             Event::Tick => {
                 if self.is_capturing {
                     self.receive_packets();
+                    self.flows.expire(std::time::SystemTime::now());
+                    self.tcp_analysis.prune(&self.flows);
                 }
+                self.refresh_filter_preview();
                 None
             }
             Event::Key(key_event) => self.handle_key_events(key_event)?,
@@ -469,29 +1820,19 @@ impl Component for SnifferPage {
                         if self.following {
                             return Ok(Some(Action::Handled));
                         }
-                        let area = Rect {
-                            x: 0,
-                            y: 0,
-                            width: 100, // Will be updated during render
-                            height: 100,
-                        };
-                        self.handle_mouse_click(mouse_event.column, mouse_event.row, area);
+                        self.handle_mouse_click(mouse_event.column, mouse_event.row);
                     }
                     MouseEventKind::ScrollUp => {
                         if self.following {
                             return Ok(Some(Action::Handled));
                         }
-                        if self.scroll_position > 0 {
-                            self.scroll_position = self.scroll_position.saturating_sub(3);
-                        }
+                        self.scroll_to(self.scroll_position.saturating_sub(3));
                     }
                     MouseEventKind::ScrollDown => {
                         if self.following {
                             return Ok(Some(Action::Handled));
                         }
-                        if self.scroll_position + 20 < self.packets.len() {
-                            self.scroll_position += 3;
-                        }
+                        self.scroll_to(self.scroll_position + 3);
                     }
                     _ => {}
                 }
@@ -502,17 +1843,27 @@ impl Component for SnifferPage {
     }
 
     fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.protocol_bar.is_open && self.protocol_bar.handle_key_events(key) {
+            self.refresh_visible();
+            return Ok(Some(Action::Handled));
+        }
+        if let Some(ref reason) = self.capture_unavailable
+            && matches!(key.code, KeyCode::Char('s' | 'd' | 'a' | 'r'))
+        {
+            self.status_message = tr!("sniffer.analysis_only", reason);
+            self.missing_privileges = privileges::check();
+            return Ok(Some(Action::Handled));
+        }
         match key.code {
             KeyCode::Char('s') => {
                 if self.device_name.is_some() {
                     if self.is_capturing {
                         self.stop_capture();
                     } else {
-                        self.start_capture()?;
+                        self.request_capture()?;
                     }
                 } else {
-                    self.status_message =
-                        "No device selected. Press 'D' to select a device.".to_string();
+                    self.status_message = tr!("sniffer.no_device").to_string();
                 }
                 return Ok(Some(Action::Handled));
             }
@@ -532,15 +1883,55 @@ impl Component for SnifferPage {
                 if self.is_capturing {
                     self.stop_capture();
                 }
-                self.filter_dialog.open();
+                self.open_filter_dialog(FilterKind::Capture);
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('A') => {
+                self.open_filter_dialog(FilterKind::Display);
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('i') => {
+                if !self.following {
+                    let index = self.selected_packet.and_then(|row| self.visible.get(row).copied());
+                    self.expanded = if self.expanded == index { None } else { index };
+                }
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('/') => {
+                self.search_input = Some(String::new());
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                if self.following {
+                    return Ok(Some(Action::Handled));
+                }
+                if self.search.is_some() {
+                    self.find_match(key.code == KeyCode::Char('n'));
+                }
                 return Ok(Some(Action::Handled));
             }
             KeyCode::Char('c') => {
                 self.packets.clear();
+                self.clears += 1;
+                self.visible.clear();
+                self.flow_starts.clear();
+                self.flows.clear();
+                self.icmp_links.clear();
+                self.tcp_analysis.clear();
+                self.dns_monitor.clear();
+                self.http.clear();
+                self.handshakes.clear();
+                self.alerts.clear();
+                self.marked.clear();
+                self.comments.clear();
+                self.evicted = 0;
+                self.spool = None;
+                self.resident_bytes = 0;
                 self.packet_count = 0;
                 self.scroll_position = 0;
                 self.selected_packet = None;
-                self.status_message = "Cleared packet list.".to_string();
+                self.expanded = None;
+                self.status_message = tr!("sniffer.cleared").to_string();
             }
             KeyCode::Char('f') => {
                 if !self.following {
@@ -548,70 +1939,184 @@ impl Component for SnifferPage {
                     self.selected_packet = None;
                 } else {
                     self.following = false;
-                    self.selected_packet = Some(self.packets.len().saturating_sub(1));
-
+                    self.selected_packet = self.visible.len().checked_sub(1);
+                }
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('u') => {
+                if let Some(packet) = self
+                    .selected_packet
+                    .and_then(|row| self.visible.get(row))
+                    .and_then(|&index| self.packet(index))
+                {
+                    if packet.transport == Some(Transport::Udp)
+                        && let Some(key) = FlowKey::from_packet(&packet)
+                    {
+                        return Ok(Some(Action::FollowStream(key)));
+                    }
+                    self.status_message = tr!("sniffer.follow_udp_only").to_string();
+                }
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('l') => {
+                if let Some(packet) = self
+                    .selected_packet
+                    .and_then(|row| self.visible.get(row))
+                    .and_then(|&index| self.packet(index))
+                {
+                    if packet.transport == Some(Transport::Tcp)
+                        && let Some(key) = FlowKey::from_packet(&packet)
+                    {
+                        return Ok(Some(Action::ShowLadder(key)));
+                    }
+                    self.status_message = tr!("sniffer.ladder_tcp_only").to_string();
+                }
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('e') => {
+                if let Some(key) = self
+                    .selected_packet
+                    .and_then(|row| self.visible.get(row))
+                    .and_then(|&index| self.packet(index))
+                    .and_then(|packet| FlowKey::from_packet(&packet))
+                {
+                    self.export_flow(&key);
+                }
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('m') => {
+                if let Some(&index) = self.selected_packet.and_then(|row| self.visible.get(row))
+                    && !self.marked.remove(&index)
+                {
+                    self.marked.insert(index);
                 }
+                self.status_message = tr!("sniffer.marked", self.marked.len());
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('M') => {
+                let marked = self.export_selection().len();
+                if marked == 0 && self.visible.is_empty() {
+                    self.status_message = tr!("sniffer.export_none").to_string();
+                } else {
+                    let stem = format!("packets-{}", Self::stamp());
+                    self.export_dialog.open(&stem, marked, self.visible.len());
+                }
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('o') => {
+                self.capture_options_dialog.open(&self.capture_options);
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('t') => {
+                self.time_format = self.time_format.next();
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('w') => {
+                self.export_snapshot();
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('r') => return Ok(Some(Action::ReplayListed)),
+            KeyCode::Char('x') => {
+                self.show_radio = !self.show_radio;
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('h') => {
+                self.show_handshake = !self.show_handshake;
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('g') => {
+                if geoip::is_enabled() {
+                    self.show_locations = !self.show_locations;
+                } else {
+                    self.status_message = tr!("sniffer.geoip_disabled").to_string();
+                }
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('p') => {
+                self.protocol_bar.toggle_open();
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Char('v') => {
+                self.list_mode = match self.list_mode {
+                    ListMode::All => ListMode::FirstPerFlow,
+                    ListMode::FirstPerFlow => ListMode::All,
+                    ListMode::Flow(_) => ListMode::FirstPerFlow,
+                };
+                self.refresh_visible();
                 return Ok(Some(Action::Handled));
             }
             KeyCode::Enter => {
-                if let Some(selected_index) = self.selected_packet {
-                    return Ok(Some(Action::PacketSelected(selected_index)));
+                if let Some(&index) = self.selected_packet.and_then(|row| self.visible.get(row)) {
+                    if self.list_mode == ListMode::FirstPerFlow
+                        && let Some(key) = self
+                            .packet(index)
+                            .and_then(|packet| FlowKey::from_packet(&packet))
+                    {
+                        self.list_mode = ListMode::Flow(key);
+                        self.refresh_visible();
+                        return Ok(Some(Action::Handled));
+                    }
+                    return Ok(Some(Action::PacketSelected(index)));
                 }
             }
             KeyCode::Up => {
                 if self.following {
                     return Ok(Some(Action::Handled));
                 }
-                if !self.packets.is_empty() {
-                    if let Some(current) = self.selected_packet {
-                        if current > 0 {
-                            self.select_packet(current - 1);
-                        }
-                    } else {
-                        self.select_packet(0);
-                    }
-                } else if self.scroll_position > 0 {
-                    self.scroll_position -= 1;
+                if let Some(current) = self.selected_packet {
+                    self.select_packet(current.saturating_sub(1));
+                } else {
+                    self.select_packet(0);
                 }
             }
             KeyCode::Down => {
                 if self.following {
                     return Ok(Some(Action::Handled));
                 }
-                if !self.packets.is_empty() {
-                    if let Some(current) = self.selected_packet {
-                        if current < self.packets.len() - 1 {
-                            self.select_packet(current + 1);
-                        }
-                    } else {
-                        self.select_packet(0);
-                    }
-                } else if self.scroll_position + 20 < self.packets.len() {
-                    self.scroll_position += 1;
+                if let Some(current) = self.selected_packet {
+                    self.select_packet(current + 1);
+                } else {
+                    self.select_packet(0);
                 }
                 return Ok(Some(Action::Handled));
             }
-            KeyCode::Home => {
+            KeyCode::PageUp => {
                 if self.following {
                     return Ok(Some(Action::Handled));
                 }
-                if !self.packets.is_empty() {
-                    self.select_packet(0);
-                } else {
-                    self.scroll_position = 0;
+                let rows = self.page_rows();
+                self.scroll_to(self.scroll_position.saturating_sub(rows));
+                if let Some(current) = self.selected_packet {
+                    self.select_packet(current.saturating_sub(rows));
+                }
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::PageDown => {
+                if self.following {
+                    return Ok(Some(Action::Handled));
+                }
+                let rows = self.page_rows();
+                self.scroll_to(self.scroll_position + rows);
+                if let Some(current) = self.selected_packet {
+                    self.select_packet((current + rows).min(self.visible.len().saturating_sub(1)));
+                }
+                return Ok(Some(Action::Handled));
+            }
+            KeyCode::Home => {
+                if self.following {
+                    return Ok(Some(Action::Handled));
                 }
+                self.scroll_position = 0;
+                self.select_packet(0);
                 return Ok(Some(Action::Handled));
             }
             KeyCode::End => {
                 if self.following {
                     return Ok(Some(Action::Handled));
                 }
-                if !self.packets.is_empty() {
-                    self.select_packet(self.packets.len() - 1);
-                } else if self.packets.len() > 20 {
-                    self.scroll_position = self.packets.len() - 20;
-                } else {
-                    self.scroll_position = 0;
+                self.scroll_position = self.max_scroll();
+                if let Some(last) = self.visible.len().checked_sub(1) {
+                    self.select_packet(last);
                 }
                 return Ok(Some(Action::Handled));
             }
@@ -625,6 +2130,13 @@ impl Component for SnifferPage {
             Action::DeviceSelected(device_name) => {
                 self.set_device(device_name);
             }
+            Action::SetMonitorMode(device_name, on) => {
+                if on {
+                    self.monitor_devices.insert(device_name);
+                } else {
+                    self.monitor_devices.remove(&device_name);
+                }
+            }
             Action::ApplyFilter(filter) => {
                 self.current_filter = if filter.is_empty() {
                     None
@@ -632,21 +2144,34 @@ impl Component for SnifferPage {
                     Some(filter.clone())
                 };
 
-                if let Some(ref filter_text) = self.current_filter {
-                    self.status_message = format!("Filter applied: {filter_text}");
-                } else {
-                    self.status_message = "Filter cleared".to_string();
+                self.status_message = match self.current_filter {
+                    Some(ref filter_text) => tr!("sniffer.filter_applied", filter_text),
+                    None => tr!("sniffer.filter_cleared").to_string(),
+                };
+            }
+            Action::ApplyDisplayFilter(filter) => {
+                match DisplayFilter::parse(&filter) {
+                    _ if filter.trim().is_empty() => {
+                        self.display_filter = None;
+                        self.status_message = tr!("sniffer.display_filter_cleared").to_string();
+                    }
+                    Ok(display_filter) => {
+                        self.status_message =
+                            tr!("sniffer.display_filter_applied", display_filter.text());
+                        self.display_filter = Some(display_filter);
+                    }
+                    Err(e) => {
+                        self.status_message = tr!("sniffer.display_filter_error", e);
+                    }
                 }
-
-                self.status_message
-                    .push_str(". Press 'S' to start capturing.");
+                self.refresh_visible();
+            }
+            Action::ExportFlow(key) => {
+                self.export_flow(&key);
             }
             Action::PacketSelected(index) => {
-                if index < self.packets.len() {
-                    self.status_message = format!(
-                        "Opening packet details for packet #{}",
-                        self.packets[index].id
-                    );
+                if let Some(packet) = self.packet(index) {
+                    self.status_message = tr!("sniffer.opening", packet.id);
                 }
             }
             _ => {
@@ -659,6 +2184,11 @@ impl Component for SnifferPage {
 
 impl ComponentRender<()> for SnifferPage {
     fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let bar_height = if self.protocol_bar.is_open { 1 } else { 0 };
+        let outer = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(bar_height), Constraint::Min(0)])
+            .split(area);
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -666,26 +2196,31 @@ impl ComponentRender<()> for SnifferPage {
                 Constraint::Length(3),
                 Constraint::Length(1),
             ])
-            .split(area);
+            .split(outer[1]);
 
+        // Key and mouse handling between frames works against this area.
+        self.list_area = chunks[0];
         if self.following && self.is_capturing {
-            self.scroll_position = self
-                .packets
-                .len()
-                .saturating_sub(chunks[0].height as usize - 3);
+            self.scroll_position = self.max_scroll();
+        } else {
+            self.scroll_to(self.scroll_position);
         }
 
-        // Update the mouse click area with actual render area
-        if let Some((x, y)) = std::mem::take(&mut None) {
-            // This would be set by mouse events
-            self.handle_mouse_click(x, y, chunks[0]);
+        if self.protocol_bar.is_open {
+            self.protocol_bar.render(f, outer[0], ());
         }
-
         self.render_packet_list(f, chunks[0]);
         self.render_status(f, chunks[1]);
         self.render_help(f, chunks[2]);
+        if self.export_dialog.is_open {
+            self.export_dialog.render(f, area, ());
+        }
+        if self.capture_options_dialog.is_open {
+            self.capture_options_dialog.render(f, area, ());
+        }
         if self.filter_dialog.is_open {
             self.filter_dialog.render(f, area, ());
         }
+        self.render_privileges_dialog(f, area);
     }
 }
@@ -0,0 +1,263 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::burst::Timeline,
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+/// Rows of the rate chart, not counting its borders and marker row.
+const CHART_ROWS: usize = 8;
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Default)]
+pub struct TimelinePage {
+    timeline: Timeline,
+    selected: usize,
+    scroll_position: usize,
+    visible_rows: usize,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+fn format_rate(bits_per_second: f64) -> String {
+    match bits_per_second {
+        bps if bps >= 1e9 => format!("{:.2} Gbit/s", bps / 1e9),
+        bps if bps >= 1e6 => format!("{:.2} Mbit/s", bps / 1e6),
+        bps if bps >= 1e3 => format!("{:.1} kbit/s", bps / 1e3),
+        bps => format!("{bps:.0} bit/s"),
+    }
+}
+
+impl TimelinePage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_timeline(&mut self, timeline: Timeline) {
+        self.timeline = timeline;
+        self.selected = self
+            .selected
+            .min(self.timeline.bursts.len().saturating_sub(1));
+    }
+
+    /// Peak rate per column as bars, bursts in red, with the selected burst
+    /// pointed out on the marker row below.
+    fn render_chart(&self, f: &mut Frame, area: Rect) {
+        let width = (area.width as usize).saturating_sub(2);
+        let columns = self.timeline.columns(width);
+        let max = columns.iter().map(|&(peak, _)| peak).fold(0.0, f64::max);
+        let per_column = self.timeline.bins.len().div_ceil(width.max(1));
+        let selected = self
+            .timeline
+            .bursts
+            .get(self.selected)
+            .map(|burst| burst.bins.start / per_column.max(1));
+
+        let mut lines = Vec::with_capacity(CHART_ROWS + 1);
+        for row in (0..CHART_ROWS).rev() {
+            let spans: Vec<Span> = columns
+                .iter()
+                .map(|&(peak, burst)| {
+                    // Height in eighths of a row
+                    let height = if max > 0.0 {
+                        (peak / max * (CHART_ROWS * 8) as f64).round() as usize
+                    } else {
+                        0
+                    };
+                    let bar = match height.saturating_sub(row * 8) {
+                        0 => ' ',
+                        fill => BARS[fill.min(8) - 1],
+                    };
+                    let color = if burst { theme().alert } else { theme().value };
+                    Span::styled(bar.to_string(), Style::default().fg(color))
+                })
+                .collect();
+            lines.push(Line::from(spans));
+        }
+        let markers: String = (0..columns.len())
+            .map(|column| {
+                if Some(column) == selected {
+                    '▲'
+                } else if columns[column].1 {
+                    '^'
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+        lines.push(Line::styled(
+            markers,
+            Style::default().fg(theme().highlight),
+        ));
+
+        let title = tr!(
+            "timeline.title",
+            self.timeline.bin_width.as_millis(),
+            format_rate(self.timeline.average_bps),
+            format_rate(max)
+        );
+        let chart = Paragraph::new(lines).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(chart, area);
+    }
+
+    fn render_bursts(&mut self, f: &mut Frame, area: Rect) {
+        let header = ListItem::new(Line::from(
+            [
+                ("Start", 14),
+                ("Duration", 12),
+                ("Packets", 10),
+                ("Bytes", 12),
+                ("Peak Rate", 16),
+                ("Per-Second Rate", 18),
+                ("Peak / Second", 0),
+            ]
+            .iter()
+            .map(|(name, width)| {
+                Span::styled(
+                    format!("{name:<width$}"),
+                    Style::default()
+                        .fg(theme().text)
+                        .add_modifier(Modifier::BOLD),
+                )
+            })
+            .collect::<Vec<_>>(),
+        ));
+
+        self.visible_rows = (area.height as usize).saturating_sub(3);
+        if self.selected < self.scroll_position {
+            self.scroll_position = self.selected;
+        } else if self.selected >= self.scroll_position + self.visible_rows {
+            self.scroll_position = self.selected + 1 - self.visible_rows;
+        }
+
+        let mut items = vec![header];
+        items.extend(
+            self.timeline
+                .bursts
+                .iter()
+                .enumerate()
+                .skip(self.scroll_position)
+                .take(self.visible_rows)
+                .map(|(i, burst)| {
+                    let base_style = if i == self.selected {
+                        Style::default()
+                            .bg(theme().selection)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default()
+                    };
+                    ListItem::new(Line::from(vec![
+                        Span::styled(
+                            format!("{:<14}", format!("+{:.3}s", burst.start.as_secs_f64())),
+                            base_style.fg(theme().muted),
+                        ),
+                        Span::styled(
+                            format!("{:<12}", format!("{} ms", burst.duration.as_millis())),
+                            base_style.fg(theme().highlight),
+                        ),
+                        Span::styled(
+                            format!("{:<10}", burst.packets),
+                            base_style.fg(theme().value),
+                        ),
+                        Span::styled(format!("{:<12}", burst.bytes), base_style.fg(theme().value)),
+                        Span::styled(
+                            format!("{:<16}", format_rate(burst.peak_bps)),
+                            base_style.fg(theme().alert),
+                        ),
+                        Span::styled(
+                            format!("{:<18}", format_rate(burst.second_bps)),
+                            base_style.fg(theme().accent),
+                        ),
+                        Span::styled(
+                            format!("{:.1}x", burst.ratio()),
+                            base_style.fg(theme().secondary),
+                        ),
+                    ]))
+                }),
+        );
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("timeline.bursts_title", self.timeline.bursts.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("timeline.help"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for TimelinePage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down if self.selected + 1 < self.timeline.bursts.len() => {
+                self.selected += 1;
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for TimelinePage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(CHART_ROWS as u16 + 3),
+                Constraint::Min(5),
+                Constraint::Length(1),
+            ])
+            .split(area);
+
+        self.render_chart(f, chunks[0]);
+        self.render_bursts(f, chunks[1]);
+        self.render_help(f, chunks[2]);
+    }
+}
@@ -0,0 +1,86 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{component::ComponentRender, i18n::tr, theme::theme};
+
+/// Where the user is and what is being captured, gathered by the app for
+/// every frame.
+pub struct TopBarProps<'a> {
+    pub trail: Vec<&'static str>, // Page titles from Home down to the current page
+    pub capturing: bool,
+    pub analysis_only: bool,     // Live capture is unavailable
+    pub source: Option<&'a str>, // Device or capture file
+    pub capture_filter: Option<&'a str>,
+    pub display_filter: Option<&'a str>,
+}
+
+/// A line above every page with the breadcrumb trail, the capture state and
+/// the filters in effect.
+#[derive(Default)]
+pub struct TopBar;
+
+impl TopBar {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ComponentRender<TopBarProps<'_>> for TopBar {
+    fn render(&mut self, f: &mut Frame, area: Rect, props: TopBarProps<'_>) {
+        let separator = || Span::styled(" │ ", Style::default().fg(theme().faint));
+        let mut spans = vec![Span::raw(" ")];
+        for (i, title) in props.trail.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" › ", Style::default().fg(theme().faint)));
+            }
+            let style = if i + 1 == props.trail.len() {
+                Style::default()
+                    .fg(theme().text)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme().muted)
+            };
+            spans.push(Span::styled(*title, style));
+        }
+
+        spans.push(separator());
+        spans.push(match (props.capturing, props.source) {
+            (true, Some(source)) => Span::styled(
+                tr!("bar.capturing", source),
+                Style::default()
+                    .fg(theme().alert)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            (false, Some(source)) => Span::styled(
+                tr!("bar.stopped", source),
+                Style::default().fg(theme().value),
+            ),
+            (_, None) if props.analysis_only => Span::styled(
+                tr!("bar.analysis_only"),
+                Style::default().fg(theme().highlight),
+            ),
+            (_, None) => Span::styled(tr!("bar.idle"), Style::default().fg(theme().muted)),
+        });
+        if let Some(filter) = props.capture_filter {
+            spans.push(separator());
+            spans.push(Span::styled(
+                tr!("bar.capture_filter", filter),
+                Style::default().fg(theme().highlight),
+            ));
+        }
+        if let Some(filter) = props.display_filter {
+            spans.push(separator());
+            spans.push(Span::styled(
+                tr!("bar.display_filter", filter),
+                Style::default().fg(theme().accent),
+            ));
+        }
+
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+}
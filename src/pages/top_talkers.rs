@@ -0,0 +1,207 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::top_talkers::{Talker, TopTalkers, WINDOWS},
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+/// Width of the bar showing each talker's share of the window's bytes.
+const BAR_WIDTH: usize = 10;
+
+#[derive(Default)]
+pub struct TopTalkersPage {
+    talkers: TopTalkers,
+    window: usize, // Index into WINDOWS
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+fn format_bytes(bytes: usize) -> String {
+    match bytes as f64 {
+        b if b >= 1e9 => format!("{:.1} GB", b / 1e9),
+        b if b >= 1e6 => format!("{:.1} MB", b / 1e6),
+        b if b >= 1e3 => format!("{:.1} kB", b / 1e3),
+        _ => format!("{bytes} B"),
+    }
+}
+
+fn format_window(window: Duration) -> String {
+    match window.as_secs() {
+        secs if secs >= 60 => format!("{} min", secs / 60),
+        secs => format!("{secs} s"),
+    }
+}
+
+impl TopTalkersPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How far back from the newest packet talkers are counted.
+    pub fn window(&self) -> Duration {
+        WINDOWS[self.window]
+    }
+
+    pub fn set_talkers(&mut self, talkers: TopTalkers) {
+        self.talkers = talkers;
+    }
+
+    fn render_talkers(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        title: String,
+        heading: &str,
+        talkers: &[Talker],
+    ) {
+        // The label takes what the counts, bar and share leave.
+        let label_width = (area.width as usize).saturating_sub(2 + 10 + 8 + BAR_WIDTH + 7);
+        let header = ListItem::new(Line::from(Span::styled(
+            format!(
+                "{heading:<label_width$}{:<10}{:<8}Share",
+                "Bytes", "Packets"
+            ),
+            Style::default()
+                .fg(theme().text)
+                .add_modifier(Modifier::BOLD),
+        )));
+        let mut items = vec![header];
+        let visible = (area.height as usize).saturating_sub(3);
+        items.extend(talkers.iter().take(visible).map(|talker| {
+            let share = if self.talkers.bytes > 0 {
+                talker.bytes as f64 / self.talkers.bytes as f64
+            } else {
+                0.0
+            };
+            let filled = (share * BAR_WIDTH as f64).round() as usize;
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(
+                        "{:<label_width$.max$}",
+                        talker.label,
+                        max = label_width.saturating_sub(1)
+                    ),
+                    Style::default().fg(theme().secondary),
+                ),
+                Span::styled(
+                    format!("{:<10}", format_bytes(talker.bytes)),
+                    Style::default().fg(theme().value),
+                ),
+                Span::styled(
+                    format!("{:<8}", talker.packets),
+                    Style::default().fg(theme().value),
+                ),
+                Span::styled(
+                    format!("{:<BAR_WIDTH$}", "█".repeat(filled.min(BAR_WIDTH))),
+                    Style::default().fg(theme().highlight),
+                ),
+                Span::styled(
+                    format!(" {:>5.1}%", share * 100.0),
+                    Style::default().fg(theme().muted),
+                ),
+            ]))
+        }));
+        let list = List::new(items).block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("top_talkers.help"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for TopTalkersPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        match key.code {
+            KeyCode::Char('q') => return Ok(Some(Action::NavigateToHome)),
+            KeyCode::Char('w') | KeyCode::Right => {
+                self.window = (self.window + 1) % WINDOWS.len();
+            }
+            KeyCode::Left => {
+                self.window = (self.window + WINDOWS.len() - 1) % WINDOWS.len();
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for TopTalkersPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(1)])
+            .split(area);
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 3); 3])
+            .split(chunks[0]);
+
+        let window = format_window(self.window());
+        let total = format_bytes(self.talkers.bytes);
+        let talkers = &self.talkers;
+        self.render_talkers(
+            f,
+            columns[0],
+            tr!("top_talkers.sources", window, total),
+            "Address",
+            &talkers.sources,
+        );
+        self.render_talkers(
+            f,
+            columns[1],
+            tr!("top_talkers.destinations", window),
+            "Address",
+            &talkers.destinations,
+        );
+        self.render_talkers(
+            f,
+            columns[2],
+            tr!("top_talkers.ports", window),
+            "Port",
+            &talkers.ports,
+        );
+        self.render_help(f, chunks[1]);
+    }
+}
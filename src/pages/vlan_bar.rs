@@ -0,0 +1,72 @@
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{component::ComponentRender, data::vlan::VlanGroup, theme::theme};
+
+/// The VLANs of a trunk capture, with the one the statistics pages cover
+/// highlighted. Only shown when there is more than one to choose from.
+#[derive(Default)]
+pub struct VlanBar {
+    groups: Vec<VlanGroup>,
+    pub selected: VlanGroup,
+}
+
+impl VlanBar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_groups(&mut self, groups: Vec<VlanGroup>) {
+        if !groups.contains(&self.selected) {
+            self.selected = VlanGroup::All;
+        }
+        self.groups = groups;
+    }
+
+    pub fn is_shown(&self) -> bool {
+        self.groups.len() > 1
+    }
+
+    /// Moves to the next group, wrapping around to `All`.
+    pub fn select_next(&mut self) {
+        let position = self.groups.iter().position(|&g| g == self.selected);
+        self.selected = position
+            .and_then(|i| self.groups.get(i + 1))
+            .copied()
+            .unwrap_or_default();
+    }
+}
+
+impl ComponentRender<()> for VlanBar {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let mut spans = vec![Span::styled(
+            " Group by VLAN: ",
+            Style::default()
+                .fg(theme().text)
+                .add_modifier(Modifier::BOLD),
+        )];
+        for &group in &self.groups {
+            let style = if group == self.selected {
+                Style::default()
+                    .bg(theme().accent)
+                    .fg(theme().background)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme().muted)
+            };
+            spans.push(Span::styled(format!(" {} ", group.label()), style));
+            spans.push(Span::raw(" "));
+        }
+        spans.push(Span::styled(
+            " Ctrl+G: Next",
+            Style::default().fg(theme().faint),
+        ));
+
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+}
@@ -0,0 +1,212 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+};
+use tokio::sync::mpsc;
+
+use crate::{
+    action::Action,
+    component::{Component, ComponentRender},
+    data::{rtp::payload_type_info, voip::VoipStats},
+    i18n::tr,
+    theme::theme,
+    tui::Event,
+};
+
+#[derive(Default)]
+pub struct VoipPage {
+    stats: VoipStats,
+    action_tx: Option<mpsc::UnboundedSender<Action>>,
+}
+
+impl VoipPage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_stats(&mut self, stats: VoipStats) {
+        self.stats = stats;
+    }
+
+    fn header(columns: &[(&str, usize)]) -> ListItem<'static> {
+        ListItem::new(Line::from(
+            columns
+                .iter()
+                .map(|(name, width)| {
+                    Span::styled(
+                        format!("{name:<width$}"),
+                        Style::default()
+                            .fg(theme().text)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        ))
+    }
+
+    fn render_streams(&self, f: &mut Frame, area: Rect) {
+        let mut items = vec![Self::header(&[
+            ("SSRC", 12),
+            ("Source", 24),
+            ("Destination", 24),
+            ("Codec", 14),
+            ("Packets", 10),
+            ("Lost", 12),
+            ("Jitter", 10),
+        ])];
+
+        items.extend(self.stats.streams.iter().map(|stream| {
+            let (codec, _) = payload_type_info(stream.payload_type);
+            let expected = stream.packets as i64 + stream.lost;
+            let loss_pct = if expected > 0 {
+                stream.lost as f64 * 100.0 / expected as f64
+            } else {
+                0.0
+            };
+            let loss_color = if stream.lost > 0 {
+                theme().alert
+            } else {
+                theme().value
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<12}", format!("0x{:08x}", stream.ssrc)),
+                    Style::default().fg(theme().highlight),
+                ),
+                Span::styled(
+                    format!("{:<24}", stream.src.to_string()),
+                    Style::default().fg(theme().secondary),
+                ),
+                Span::styled(
+                    format!("{:<24}", stream.dst.to_string()),
+                    Style::default().fg(theme().secondary),
+                ),
+                Span::styled(
+                    format!("{:<14}", format!("{} ({codec})", stream.payload_type)),
+                    Style::default().fg(theme().accent),
+                ),
+                Span::styled(
+                    format!("{:<10}", stream.packets),
+                    Style::default().fg(theme().value),
+                ),
+                Span::styled(
+                    format!("{:<12}", format!("{} ({loss_pct:.1}%)", stream.lost)),
+                    Style::default().fg(loss_color),
+                ),
+                Span::styled(
+                    format!("{:.2} ms", stream.jitter_ms),
+                    Style::default().fg(theme().muted),
+                ),
+            ]))
+        }));
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("voip.streams_title", self.stats.streams.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_calls(&self, f: &mut Frame, area: Rect) {
+        let mut items = vec![Self::header(&[
+            ("Call-ID", 36),
+            ("From", 30),
+            ("To", 30),
+            ("Last", 12),
+            ("Messages", 10),
+        ])];
+
+        items.extend(self.stats.calls.iter().map(|call| {
+            let last = match call.last_status {
+                Some(code) => format!("{} {code}", call.last_method),
+                None => call.last_method.clone(),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<36.35}", call.call_id),
+                    Style::default().fg(theme().highlight),
+                ),
+                Span::styled(
+                    format!("{:<30.29}", call.from),
+                    Style::default().fg(theme().secondary),
+                ),
+                Span::styled(
+                    format!("{:<30.29}", call.to),
+                    Style::default().fg(theme().secondary),
+                ),
+                Span::styled(format!("{last:<12}"), Style::default().fg(theme().accent)),
+                Span::styled(
+                    call.messages.to_string(),
+                    Style::default().fg(theme().value),
+                ),
+            ]))
+        }));
+
+        let list = List::new(items).block(
+            Block::default()
+                .title(tr!("voip.calls_title", self.stats.calls.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().border)),
+        );
+        f.render_widget(list, area);
+    }
+
+    fn render_help(&self, f: &mut Frame, area: Rect) {
+        let help = Paragraph::new(tr!("stats.help.home"))
+            .style(Style::default().fg(theme().accent))
+            .wrap(Wrap { trim: true })
+            .alignment(ratatui::layout::Alignment::Center)
+            .block(Block::default().borders(Borders::NONE));
+
+        f.render_widget(help, area);
+    }
+}
+
+impl Component for VoipPage {
+    fn register_action_handler(&mut self, tx: mpsc::UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn handle_events(&mut self, event: Event) -> Result<Option<Action>> {
+        match event {
+            Event::Key(key_event) => self.handle_key_events(key_event),
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if let KeyCode::Char('q') = key.code {
+            return Ok(Some(Action::NavigateToHome));
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, _action: Action) -> Result<Option<Action>> {
+        Ok(None)
+    }
+}
+
+impl ComponentRender<()> for VoipPage {
+    fn render(&mut self, f: &mut Frame, area: Rect, _props: ()) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(60),
+                Constraint::Min(5),
+                Constraint::Length(1),
+            ])
+            .split(area);
+
+        self.render_streams(f, chunks[0]);
+        self.render_calls(f, chunks[1]);
+        self.render_help(f, chunks[2]);
+    }
+}
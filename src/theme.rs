@@ -0,0 +1,119 @@
+//! Colors used across the pages.
+//!
+//! Pages ask [`theme`] for colors by role instead of naming them, so the
+//! whole interface follows the theme picked with `--theme` or cycled at
+//! runtime with Ctrl+T.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use clap::ValueEnum;
+use ratatui::style::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    Mono,
+}
+
+/// Colors by role; each page maps its elements onto these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    pub border: Color,
+    pub selection: Color,          // Background of the selected row
+    pub inactive_selection: Color, // Background of a selection without focus
+    pub background: Color,         // Popups, and text on accent backgrounds
+    pub text: Color,               // Headers and selected rows
+    pub muted: Color,
+    pub faint: Color,
+    pub accent: Color, // Help lines and protocol names
+    pub highlight: Color,
+    pub value: Color, // Counts and sizes
+    pub secondary: Color,
+    pub alert: Color,
+    pub warning: Color,
+}
+
+const DARK: Theme = Theme {
+    border: Color::Blue,
+    selection: Color::Blue,
+    inactive_selection: Color::DarkGray,
+    background: Color::Black,
+    text: Color::White,
+    muted: Color::Gray,
+    faint: Color::DarkGray,
+    accent: Color::Cyan,
+    highlight: Color::Yellow,
+    value: Color::Green,
+    secondary: Color::Magenta,
+    alert: Color::Red,
+    warning: Color::LightRed,
+};
+
+const LIGHT: Theme = Theme {
+    border: Color::Blue,
+    selection: Color::LightBlue,
+    inactive_selection: Color::Gray,
+    background: Color::White,
+    text: Color::Black,
+    muted: Color::DarkGray,
+    faint: Color::Gray,
+    accent: Color::Blue,
+    highlight: Color::Rgb(0xa0, 0x6a, 0x00),
+    value: Color::Rgb(0x00, 0x80, 0x00),
+    secondary: Color::Magenta,
+    alert: Color::Red,
+    warning: Color::Rgb(0xc0, 0x40, 0x00),
+};
+
+/// Shades of gray only, for terminals or eyes that do poorly with color.
+const MONO: Theme = Theme {
+    border: Color::Gray,
+    selection: Color::DarkGray,
+    inactive_selection: Color::DarkGray,
+    background: Color::Black,
+    text: Color::White,
+    muted: Color::Gray,
+    faint: Color::DarkGray,
+    accent: Color::White,
+    highlight: Color::White,
+    value: Color::Gray,
+    secondary: Color::Gray,
+    alert: Color::White,
+    warning: Color::White,
+};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+
+impl ThemeName {
+    const ALL: [ThemeName; 3] = [ThemeName::Dark, ThemeName::Light, ThemeName::Mono];
+
+    fn colors(self) -> &'static Theme {
+        match self {
+            ThemeName::Dark => &DARK,
+            ThemeName::Light => &LIGHT,
+            ThemeName::Mono => &MONO,
+        }
+    }
+}
+
+pub fn set_theme(name: ThemeName) {
+    let index = ThemeName::ALL.iter().position(|&n| n == name).unwrap_or(0);
+    CURRENT.store(index, Ordering::Relaxed);
+}
+
+fn current() -> ThemeName {
+    ThemeName::ALL[CURRENT.load(Ordering::Relaxed) % ThemeName::ALL.len()]
+}
+
+/// Switches to the theme after the active one.
+pub fn next_theme() {
+    let next = (CURRENT.load(Ordering::Relaxed) + 1) % ThemeName::ALL.len();
+    CURRENT.store(next, Ordering::Relaxed);
+}
+
+/// The colors of the active theme.
+pub fn theme() -> &'static Theme {
+    current().colors()
+}